@@ -114,6 +114,7 @@ fn parse_glsl(stage: naga::ShaderStage, inputs: &Inputs) {
     let options = naga::front::glsl::Options {
         stage,
         defines: Default::default(),
+        include_resolver: None,
     };
     for input in &inputs.inner {
         parser
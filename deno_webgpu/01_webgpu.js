@@ -5068,6 +5068,7 @@ webidl.converters["GPUFeatureName"] = webidl.createEnumConverter(
     "indirect-first-instance",
     // shader
     "shader-f16",
+    "clip-distances",
     // texture formats
     "depth32float-stencil8",
     "texture-compression-bc",
@@ -2,7 +2,14 @@
 use anyhow::{anyhow, Context as _};
 #[allow(unused_imports)]
 use std::fs;
-use std::{error::Error, fmt, io::Read, path::Path, str::FromStr};
+use std::{
+    error::Error,
+    fmt,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
 
 /// Translate shaders to different formats.
 #[derive(argh::FromArgs, Debug, Clone)]
@@ -18,7 +25,8 @@ struct Args {
     /// (out-of-bounds indices read zeros, and don't write at all), or
     /// `Unchecked` (generate the simplest code, and whatever happens, happens)
     ///
-    /// `Unchecked` is the default.
+    /// `Unchecked` is the default, except for HLSL, which restricts indices
+    /// and buffers unless a policy is given.
     #[argh(option)]
     index_bounds_check_policy: Option<BoundsCheckPolicyArg>,
 
@@ -101,6 +109,14 @@ struct Args {
     #[argh(option)]
     before_compaction: Option<String>,
 
+    /// run optimization passes on the module's IR and revalidate.
+    ///
+    /// Takes a comma-separated list of the passes to run: `inline`,
+    /// `constant-folding` and `dead-code`, or `all` for all of them. Output
+    /// files will reflect the optimized IR.
+    #[argh(option)]
+    optimize: Option<PassesArg>,
+
     /// bulk validation mode: all filenames are inputs to read and validate.
     #[argh(switch)]
     bulk_validate: bool,
@@ -110,6 +126,8 @@ struct Args {
     version: bool,
 
     /// override value, of the form "foo=N,bar=M", repeatable
+    ///
+    /// WGSL output keeps `override` declarations unless this is given.
     #[argh(option, long = "override")]
     overrides: Vec<Overrides>,
 
@@ -129,6 +147,14 @@ struct Args {
     /// defines to be passed to the parser (only glsl is supported)
     #[argh(option, short = 'D')]
     defines: Vec<Defines>,
+
+    /// directories to search for files named by `#include` directives (only
+    /// glsl is supported)
+    ///
+    /// Files included with quotes are searched for in the directory of the
+    /// including file first.
+    #[argh(option, long = "include-dir")]
+    include_dirs: Vec<String>,
 }
 
 /// Newtype so we can implement [`FromStr`] for `BoundsCheckPolicy`.
@@ -153,6 +179,29 @@ impl FromStr for BoundsCheckPolicyArg {
     }
 }
 
+/// Newtype so we can implement [`FromStr`] for `Passes`.
+#[derive(Debug, Clone, Copy)]
+struct PassesArg(naga::opt::Passes);
+
+impl FromStr for PassesArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use naga::opt::Passes;
+        let mut passes = Passes::empty();
+        for pass in s.split(',') {
+            passes |= match pass.trim().to_lowercase().as_str() {
+                "all" => Passes::all(),
+                "inline" => Passes::INLINE,
+                "constant-folding" => Passes::CONSTANT_FOLDING,
+                "dead-code" => Passes::DEAD_CODE,
+                _ => return Err(format!("Invalid value for --optimize: {pass}")),
+            };
+        }
+        Ok(Self(passes))
+    }
+}
+
 /// Newtype so we can implement [`FromStr`] for `ShaderModel`.
 #[derive(Debug, Clone)]
 struct ShaderModelArg(naga::back::hlsl::ShaderModel);
@@ -303,6 +352,44 @@ impl FromStr for Defines {
     }
 }
 
+/// Resolves GLSL `#include` directives from the file system.
+#[derive(Debug)]
+struct IncludeDirs {
+    /// The directory of the input file.
+    input_dir: PathBuf,
+    /// The directories given with `--include-dir`.
+    include_dirs: Vec<PathBuf>,
+}
+
+impl naga::front::glsl::IncludeResolver for IncludeDirs {
+    fn resolve(
+        &self,
+        name: &str,
+        kind: naga::front::glsl::IncludeKind,
+        includer: Option<&str>,
+    ) -> Result<naga::front::glsl::IncludedFile, String> {
+        let relative_dir = match kind {
+            naga::front::glsl::IncludeKind::Relative => Some(
+                includer
+                    .and_then(|includer| Path::new(includer).parent())
+                    .unwrap_or(&self.input_dir),
+            ),
+            naga::front::glsl::IncludeKind::Standard => None,
+        };
+        let path = relative_dir
+            .into_iter()
+            .chain(self.include_dirs.iter().map(PathBuf::as_path))
+            .map(|dir| dir.join(name))
+            .find(|path| path.is_file())
+            .ok_or_else(|| "file not found in the include directories".to_string())?;
+        let source = fs::read_to_string(&path).map_err(|error| error.to_string())?;
+        Ok(naga::front::glsl::IncludedFile {
+            name: path.display().to_string(),
+            source,
+        })
+    }
+}
+
 #[derive(Default)]
 struct Parameters<'a> {
     validation_flags: naga::valid::ValidationFlags,
@@ -319,6 +406,7 @@ struct Parameters<'a> {
     input_kind: Option<InputKind>,
     shader_stage: Option<ShaderStage>,
     defines: FastHashMap<String, String>,
+    include_dirs: Vec<PathBuf>,
 }
 
 trait PrettyResult {
@@ -421,6 +509,8 @@ fn run() -> anyhow::Result<()> {
         .cloned()
         .collect();
 
+    params.include_dirs = args.include_dirs.iter().map(PathBuf::from).collect();
+
     params.spv_in = naga::front::spv::Options {
         adjust_coordinate_space: !args.keep_coordinate_space,
         strict_capabilities: false,
@@ -442,6 +532,12 @@ fn run() -> anyhow::Result<()> {
     params.dot.cfg_only = args.dot_cfg_only;
 
     params.spv_out.bounds_check_policies = params.bounds_check_policies;
+    if args.index_bounds_check_policy.is_some()
+        || args.buffer_bounds_check_policy.is_some()
+        || args.image_load_bounds_check_policy.is_some()
+    {
+        params.hlsl.bounds_check_policies = params.bounds_check_policies;
+    }
     params.spv_out.flags.set(
         naga::back::spv::WriterFlags::ADJUST_COORDINATE_SPACE,
         !params.keep_coordinate_space,
@@ -503,7 +599,7 @@ fn run() -> anyhow::Result<()> {
             .fold(naga::valid::Capabilities::all(), |caps, path| {
                 use naga::valid::Capabilities as C;
                 let missing = match Path::new(path).extension().and_then(|ex| ex.to_str()) {
-                    Some("wgsl") => C::CLIP_DISTANCE | C::CULL_DISTANCE,
+                    Some("wgsl") => C::CULL_DISTANCE,
                     Some("metal") => C::CULL_DISTANCE,
                     _ => C::empty(),
                 };
@@ -516,7 +612,10 @@ fn run() -> anyhow::Result<()> {
         .subgroup_operations(naga::valid::SubgroupOperationSet::all())
         .validate(&module)
     {
-        Ok(info) => Some(info),
+        Ok(info) => {
+            emit_warnings(&info, input_text.as_deref(), input_path);
+            Some(info)
+        }
         Err(error) => {
             // Validation failure is not fatal. Just report the error.
             if let Some(input) = &input_text {
@@ -565,6 +664,37 @@ fn run() -> anyhow::Result<()> {
         info
     };
 
+    // Optimize the module, if requested.
+    let info = if let Some(PassesArg(passes)) = args.optimize {
+        // Optimize only if validation succeeded. Otherwise, the passes may panic.
+        if info.is_some() {
+            naga::opt::optimize(&mut module, passes);
+
+            // Re-validate the IR after optimization.
+            match naga::valid::Validator::new(params.validation_flags, validation_caps)
+                .validate(&module)
+            {
+                Ok(info) => Some(info),
+                Err(error) => {
+                    // Validation failure is not fatal. Just report the error.
+                    eprintln!("Error validating optimized module:");
+                    if let Some(input) = &input_text {
+                        let filename = input_path.file_name().and_then(std::ffi::OsStr::to_str);
+                        error.emit_to_stderr_with_path(input, filename.unwrap_or("input"));
+                    } else {
+                        print_err(&error);
+                    }
+                    None
+                }
+            }
+        } else {
+            eprintln!("Skipping optimization due to validation failure.");
+            None
+        }
+    } else {
+        info
+    };
+
     // If no output was requested, then report validation results and stop here.
     //
     // If the user asked for output, don't stop: some output formats (".txt",
@@ -609,14 +739,28 @@ fn parse_input(input_path: &Path, input: Vec<u8>, params: &Parameters) -> anyhow
             input_text: None,
             language: naga::back::spv::SourceLanguage::Unknown,
         },
-        InputKind::SpirV => Parsed {
-            module: naga::front::spv::parse_u8_slice(&input, &params.spv_in)?,
-            input_text: None,
-            language: naga::back::spv::SourceLanguage::Unknown,
-        },
+        InputKind::SpirV => {
+            let (module, debug_source) =
+                naga::front::spv::parse_u8_slice_with_debug_source(&input, &params.spv_in)?;
+            // Spans refer to the embedded source code, if there is any.
+            let (input_text, language) = match debug_source {
+                Some(debug) => (Some(debug.source_code), debug.language),
+                None => (None, naga::back::spv::SourceLanguage::Unknown),
+            };
+            Parsed {
+                module,
+                input_text,
+                language,
+            }
+        }
         InputKind::Wgsl => {
             let input = String::from_utf8(input)?;
-            let result = naga::front::wgsl::parse_str(&input);
+            let mut frontend = naga::front::wgsl::Frontend::new();
+            let result = frontend.parse(&input);
+            let filename = input_path.file_name().and_then(std::ffi::OsStr::to_str);
+            for warning in frontend.warnings() {
+                warning.emit_to_stderr_with_path(&input, filename.unwrap_or("input"));
+            }
             match result {
                 Ok(v) => Parsed {
                     module: v,
@@ -650,26 +794,39 @@ fn parse_input(input_path: &Path, input: Vec<u8>, params: &Parameters) -> anyhow
                 }
             };
             let input = String::from_utf8(input)?;
+            let include_resolver = IncludeDirs {
+                input_dir: input_path
+                    .parent()
+                    .map_or_else(PathBuf::new, Path::to_path_buf),
+                include_dirs: params.include_dirs.clone(),
+            };
             let mut parser = naga::front::glsl::Frontend::default();
+            let module = parser
+                .parse(
+                    &naga::front::glsl::Options {
+                        stage: shader_stage.0,
+                        defines: params.defines.clone(),
+                        include_resolver: Some(Arc::new(include_resolver)),
+                    },
+                    &input,
+                )
+                .unwrap_or_else(|error| {
+                    let filename = input_path
+                        .file_name()
+                        .and_then(std::ffi::OsStr::to_str)
+                        .unwrap_or("glsl");
+                    let mut writer = StandardStream::stderr(ColorChoice::Auto);
+                    error.emit_to_writer_with_path(&mut writer, &input, filename);
+                    std::process::exit(1);
+                });
+            // Spans refer to the source with its `#include`s expanded.
+            let input_text = match parser.source_map() {
+                Some(source_map) => source_map.expanded_source().to_string(),
+                None => input,
+            };
             Parsed {
-                module: parser
-                    .parse(
-                        &naga::front::glsl::Options {
-                            stage: shader_stage.0,
-                            defines: params.defines.clone(),
-                        },
-                        &input,
-                    )
-                    .unwrap_or_else(|error| {
-                        let filename = input_path
-                            .file_name()
-                            .and_then(std::ffi::OsStr::to_str)
-                            .unwrap_or("glsl");
-                        let mut writer = StandardStream::stderr(ColorChoice::Auto);
-                        error.emit_to_writer_with_path(&mut writer, &input, filename);
-                        std::process::exit(1);
-                    }),
-                input_text: Some(input),
+                module,
+                input_text: Some(input_text),
                 language: naga::back::spv::SourceLanguage::GLSL,
             }
         }
@@ -716,6 +873,9 @@ fn write_output(
             let (module, info) =
                 naga::back::pipeline_constants::process_overrides(module, info, &params.overrides)
                     .unwrap_pretty();
+            let (module, info) =
+                naga::back::pointer_arguments::specialize_pointer_arguments(&module, &info)
+                    .unwrap_pretty();
 
             let pipeline_options = msl::PipelineOptions::default();
             let (msl, _) =
@@ -750,6 +910,9 @@ fn write_output(
             let (module, info) =
                 naga::back::pipeline_constants::process_overrides(module, info, &params.overrides)
                     .unwrap_pretty();
+            let (module, info) =
+                naga::back::pointer_arguments::specialize_pointer_arguments(&module, &info)
+                    .unwrap_pretty();
 
             let spv =
                 spv::write_vec(&module, &info, &params.spv_out, pipeline_options).unwrap_pretty();
@@ -787,6 +950,9 @@ fn write_output(
             let (module, info) =
                 naga::back::pipeline_constants::process_overrides(module, info, &params.overrides)
                     .unwrap_pretty();
+            let (module, info) =
+                naga::back::pointer_arguments::specialize_pointer_arguments(&module, &info)
+                    .unwrap_pretty();
 
             let mut buffer = String::new();
             let mut writer = glsl::Writer::new(
@@ -818,6 +984,9 @@ fn write_output(
             let (module, info) =
                 naga::back::pipeline_constants::process_overrides(module, info, &params.overrides)
                     .unwrap_pretty();
+            let (module, info) =
+                naga::back::pointer_arguments::specialize_pointer_arguments(&module, &info)
+                    .unwrap_pretty();
 
             let mut buffer = String::new();
             let mut writer = hlsl::Writer::new(&mut buffer, &params.hlsl);
@@ -827,14 +996,22 @@ fn write_output(
         "wgsl" => {
             use naga::back::wgsl;
 
-            let wgsl = wgsl::write_string(
-                module,
-                info.as_ref().ok_or(CliError(
-                    "Generating wgsl output requires validation to \
-                     succeed, and it failed in a previous step",
-                ))?,
-                wgsl::WriterFlags::empty(),
-            )
+            let info = info.as_ref().ok_or(CliError(
+                "Generating wgsl output requires validation to \
+                 succeed, and it failed in a previous step",
+            ))?;
+
+            // Keep `override` declarations unless values were given for them.
+            let wgsl = if params.overrides.is_empty() {
+                wgsl::write_string(module, info, wgsl::WriterFlags::empty())
+            } else {
+                wgsl::write_string_with_pipeline_constants(
+                    module,
+                    info,
+                    wgsl::WriterFlags::empty(),
+                    &params.overrides,
+                )
+            }
             .unwrap_pretty();
             fs::write(output_path, wgsl)?;
         }
@@ -846,6 +1023,19 @@ fn write_output(
     Ok(())
 }
 
+/// Report the warnings from validating `input_path`.
+fn emit_warnings(info: &naga::valid::ModuleInfo, input_text: Option<&str>, input_path: &Path) {
+    for warning in info.warnings() {
+        if let Some(input) = input_text {
+            let filename = input_path.file_name().and_then(std::ffi::OsStr::to_str);
+            warning.emit_to_stderr_with_path(input, filename.unwrap_or("input"));
+        } else {
+            eprint!("warning: ");
+            print_err(&warning.error);
+        }
+    }
+}
+
 fn bulk_validate(args: Args, params: &Parameters) -> anyhow::Result<()> {
     let mut invalid = vec![];
     for input_path in args.files {
@@ -871,14 +1061,17 @@ fn bulk_validate(args: Args, params: &Parameters) -> anyhow::Result<()> {
         validator.subgroup_stages(naga::valid::ShaderStages::all());
         validator.subgroup_operations(naga::valid::SubgroupOperationSet::all());
 
-        if let Err(error) = validator.validate(&module) {
-            invalid.push(input_path.clone());
-            eprintln!("Error validating {input_path}:");
-            if let Some(input) = &input_text {
-                let filename = path.file_name().and_then(std::ffi::OsStr::to_str);
-                error.emit_to_stderr_with_path(input, filename.unwrap_or("input"));
-            } else {
-                print_err(&error);
+        match validator.validate(&module) {
+            Ok(info) => emit_warnings(&info, input_text.as_deref(), path),
+            Err(error) => {
+                invalid.push(input_path.clone());
+                eprintln!("Error validating {input_path}:");
+                if let Some(input) = &input_text {
+                    let filename = path.file_name().and_then(std::ffi::OsStr::to_str);
+                    error.emit_to_stderr_with_path(input, filename.unwrap_or("input"));
+                } else {
+                    print_err(&error);
+                }
             }
         }
     }
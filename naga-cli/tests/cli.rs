@@ -0,0 +1,69 @@
+//! Tests that run the `naga` binary.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Run `naga` on `source`, read from standard input as `input.wgsl`, and
+/// return what it wrote to standard error.
+fn run_wgsl(source: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_naga"))
+        .args(["--stdin-file-path", "input.wgsl"])
+        // Keep colors and log messages out of the output.
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "off")
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(source.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stderr).unwrap()
+}
+
+#[test]
+fn warnings() {
+    let stderr = run_wgsl(
+        "
+diagnostic(warning, precision_loss);
+diagnostic(warning, unused_variable);
+const b: f32 = 16777217;
+@diagnostic(info, unreachable_code)
+fn f() -> i32 {
+    var x = 1;
+    return 2;
+    let y = 3;
+}
+",
+    );
+
+    // From the frontend.
+    assert!(
+        stderr.contains(concat!(
+            "warning: `16777217` cannot be represented exactly as `f32`\n",
+            "  ┌─ input.wgsl:4:16\n",
+        )),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains(concat!(
+            "note: code is unreachable\n",
+            "  ┌─ input.wgsl:9:5\n",
+        )),
+        "{stderr}"
+    );
+    // From the validator.
+    assert!(
+        stderr.contains(concat!(
+            "warning: Local variable [0] 'x' is never read\n",
+            "  ┌─ input.wgsl:7:5\n",
+        )),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,916 @@
+//! Answering questions about a single WGSL document.
+//!
+//! A [`Document`] is parsed and validated once, when it is opened or
+//! changed, and the results are kept around to answer requests.
+//!
+//! Naga's IR records a [`Span`] for each declaration and expression, but not
+//! for the uses of names, which the frontend resolves away. So to find the
+//! declaration a name refers to, we look at the identifier under the cursor
+//! and search the declarations that could be in scope there.
+
+use crate::line_index::{LineIndex, Position, Range};
+use naga::{
+    diagnostic_filter::Severity,
+    front::Typifier,
+    proc::{GlobalCtx, ResolveContext},
+    valid::{Capabilities, ValidationFlags, Validator},
+    Handle, Module, Span,
+};
+use std::{cmp::Reverse, error::Error, ops};
+
+type ByteRange = ops::Range<usize>;
+
+/// A problem with a document.
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub range: Range,
+    pub severity: Severity,
+    pub message: String,
+    /// Other places the problem involves, with an explanation of each.
+    pub related: Vec<(Range, String)>,
+}
+
+/// A declaration in a document.
+#[derive(Debug)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// The declaration, in WGSL, for showing on hover.
+    pub detail: String,
+    /// The whole declaration.
+    pub range: Range,
+    /// Just the declared name.
+    pub selection_range: Range,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SymbolKind {
+    Struct,
+    Constant,
+    Override,
+    GlobalVariable,
+    Function,
+    EntryPoint,
+    Argument,
+    LocalVariable,
+    Let,
+}
+
+/// Hover information for a position in a document.
+#[derive(Debug)]
+pub struct Hover {
+    /// WGSL to show: a declaration, or the type of an expression.
+    pub contents: String,
+    pub range: Range,
+}
+
+/// A function or entry point in a [`Document`]'s module.
+#[derive(Clone, Copy, Debug)]
+enum FunctionRef {
+    Function(Handle<naga::Function>),
+    EntryPoint(usize),
+}
+
+/// What we know about a function's body.
+struct FunctionInfo {
+    function: FunctionRef,
+    /// The text of the whole function.
+    range: ByteRange,
+    /// The types of the function's expressions.
+    typifier: Typifier,
+    /// The number of expressions `typifier` could resolve types for. If the
+    /// module is invalid, this may not be all of them.
+    resolved: usize,
+}
+
+/// A [`Symbol`] together with the byte ranges we need to find it.
+struct Declaration {
+    symbol: Symbol,
+    /// The byte range of the declared name.
+    name: ByteRange,
+    /// The index in [`Document::functions`] of the function declaring this,
+    /// or `None` for module-scope declarations.
+    function: Option<usize>,
+}
+
+pub struct Document {
+    text: String,
+    lines: LineIndex,
+    /// The module, if the document could be parsed.
+    module: Option<Module>,
+    diagnostics: Vec<Diagnostic>,
+    functions: Vec<FunctionInfo>,
+    declarations: Vec<Declaration>,
+}
+
+impl Document {
+    pub fn new(text: String) -> Self {
+        let mut document = Self {
+            lines: LineIndex::new(&text),
+            text,
+            module: None,
+            diagnostics: Vec::new(),
+            functions: Vec::new(),
+            declarations: Vec::new(),
+        };
+
+        let mut frontend = naga::front::wgsl::Frontend::new();
+        let result = frontend.parse_all_errors(&document.text);
+        for warning in frontend.warnings() {
+            let diagnostic = document.parse_diagnostic(warning.severity, &warning.error);
+            document.diagnostics.push(diagnostic);
+        }
+        match result {
+            Ok(module) => {
+                document.validate(&module);
+                document.index(&module);
+                document.module = Some(module);
+            }
+            Err(errors) => {
+                for error in errors.errors {
+                    let diagnostic = document.parse_diagnostic(Severity::Error, &error);
+                    document.diagnostics.push(diagnostic);
+                }
+            }
+        }
+
+        document
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Return the module-scope declarations, in the order they appear.
+    pub fn symbols(&self) -> impl Iterator<Item = &Symbol> {
+        self.declarations
+            .iter()
+            .filter(|declaration| declaration.function.is_none())
+            .map(|declaration| &declaration.symbol)
+    }
+
+    /// Return the declaration of the name at `position`.
+    pub fn definition(&self, position: Position) -> Option<&Symbol> {
+        let offset = self.lines.offset(&self.text, position)?;
+        let (word, _) = self.name_at(offset)?;
+        self.resolve(word, offset)
+            .map(|declaration| &declaration.symbol)
+    }
+
+    /// Describe the name or expression at `position`.
+    ///
+    /// For a name, this is its declaration; for anything else, it is the
+    /// type of the smallest expression containing `position`.
+    pub fn hover(&self, position: Position) -> Option<Hover> {
+        let offset = self.lines.offset(&self.text, position)?;
+        if let Some((word, range)) = self.name_at(offset) {
+            if let Some(declaration) = self.resolve(word, offset) {
+                return Some(Hover {
+                    contents: declaration.symbol.detail.clone(),
+                    range: self.range(range),
+                });
+            }
+        }
+
+        let module = self.module.as_ref()?;
+        let info = self.function_at(offset)?;
+        let function = self.function(info.function);
+        // A `Load` has the same span as its pointer, so prefer later
+        // expressions when spans are the same length.
+        let (handle, range) = function
+            .expressions
+            .iter()
+            .filter_map(|(handle, _)| {
+                let range = function.expressions.get_span(handle).to_range()?;
+                (range.contains(&offset) && handle.index() < info.resolved)
+                    .then_some((handle, range))
+            })
+            .max_by_key(|&(handle, ref range)| (Reverse(range.len()), handle))?;
+        Some(Hover {
+            contents: info.typifier[handle].to_wgsl(&module.to_ctx()),
+            range: self.range(range),
+        })
+    }
+
+    fn range(&self, range: ByteRange) -> Range {
+        self.lines.range(&self.text, range)
+    }
+
+    fn function(&self, function: FunctionRef) -> &naga::Function {
+        let module = self.module.as_ref().unwrap();
+        match function {
+            FunctionRef::Function(handle) => &module.functions[handle],
+            FunctionRef::EntryPoint(index) => &module.entry_points[index].function,
+        }
+    }
+
+    fn function_at(&self, offset: usize) -> Option<&FunctionInfo> {
+        self.functions
+            .iter()
+            .find(|info| info.range.contains(&offset))
+    }
+
+    /// Return the identifier at `offset`, unless it names a struct member.
+    fn name_at(&self, offset: usize) -> Option<(&str, ByteRange)> {
+        let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+        let start = self.text[..offset]
+            .char_indices()
+            .rev()
+            .find(|&(_, c)| !is_ident(c))
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        let end = self.text[offset..]
+            .find(|c| !is_ident(c))
+            .map_or(self.text.len(), |i| offset + i);
+        let word = &self.text[start..end];
+        if word.is_empty() || word.starts_with(|c: char| c.is_ascii_digit()) {
+            return None;
+        }
+        if self.text[..start].trim_end().ends_with('.') {
+            return None;
+        }
+        Some((word, start..end))
+    }
+
+    /// Find the declaration that `name`, used at `offset`, refers to.
+    fn resolve(&self, name: &str, offset: usize) -> Option<&Declaration> {
+        let function = self
+            .functions
+            .iter()
+            .position(|info| info.range.contains(&offset));
+
+        // The innermost local declaration before `offset` whose block has
+        // not ended yet, or failing that, a module-scope declaration.
+        let local = self
+            .declarations
+            .iter()
+            .filter(|declaration| {
+                declaration.function.is_some()
+                    && declaration.function == function
+                    && declaration.symbol.name == name
+                    && declaration.name.start <= offset
+                    && in_scope(&self.text, declaration.name.start..offset)
+            })
+            .max_by_key(|declaration| declaration.name.start);
+        local.or_else(|| {
+            self.declarations.iter().find(|declaration| {
+                declaration.function.is_none() && declaration.symbol.name == name
+            })
+        })
+    }
+
+    /// Validate `module`, and record the errors and warnings found.
+    fn validate(&mut self, module: &Module) {
+        let mut validator = Validator::new(ValidationFlags::all(), Capabilities::all());
+        match validator.validate(module) {
+            Ok(info) => {
+                for warning in info.warnings() {
+                    let diagnostic = self.span_diagnostic(warning.severity, &warning.error);
+                    self.diagnostics.push(diagnostic);
+                }
+            }
+            Err(error) => {
+                let diagnostic = self.span_diagnostic(Severity::Error, &error);
+                self.diagnostics.push(diagnostic);
+            }
+        }
+    }
+
+    fn parse_diagnostic(
+        &self,
+        severity: Severity,
+        error: &naga::front::wgsl::ParseError,
+    ) -> Diagnostic {
+        let mut labels = error.labels().filter_map(|(span, label)| {
+            let range = span.to_range()?;
+            Some((self.range(range), label.to_string()))
+        });
+        let range = labels.next().map(|(range, _)| range).unwrap_or_default();
+        let related = labels.filter(|(_, label)| !label.is_empty()).collect();
+        Diagnostic {
+            range,
+            severity,
+            message: error.message().to_string(),
+            related,
+        }
+    }
+
+    fn span_diagnostic(
+        &self,
+        severity: Severity,
+        error: &naga::WithSpan<naga::valid::ValidationError>,
+    ) -> Diagnostic {
+        let mut message = error.to_string();
+        let mut source = error.source();
+        while let Some(error) = source {
+            message += &format!("\n{error}");
+            source = error.source();
+        }
+
+        let mut spans = error.spans().filter_map(|&(span, ref label)| {
+            let range = span.to_range()?;
+            Some((self.range(range), label.clone()))
+        });
+        let range = spans.next().map(|(range, _)| range).unwrap_or_default();
+        Diagnostic {
+            range,
+            severity,
+            message,
+            related: spans.collect(),
+        }
+    }
+
+    /// Record the declarations in `module`, and the types of its
+    /// functions' expressions.
+    fn index(&mut self, module: &Module) {
+        let gctx = module.to_ctx();
+
+        for (handle, ty) in module.types.iter() {
+            let (Some(name), naga::TypeInner::Struct { members, .. }) =
+                (ty.name.as_ref(), &ty.inner)
+            else {
+                continue;
+            };
+            let mut detail = format!("struct {name} {{\n");
+            for member in members {
+                let member_name = member.name.as_deref().unwrap_or("_");
+                let ty = member.ty.to_wgsl(&gctx);
+                detail += &format!("    {member_name}: {ty},\n");
+            }
+            detail += "}";
+            self.declare(
+                module.types.get_span(handle),
+                name,
+                SymbolKind::Struct,
+                detail,
+                None,
+            );
+        }
+
+        for (handle, constant) in module.constants.iter() {
+            let Some(ref name) = constant.name else {
+                continue;
+            };
+            let detail = format!("const {name}: {}", constant.ty.to_wgsl(&gctx));
+            let span = module.constants.get_span(handle);
+            self.declare(span, name, SymbolKind::Constant, detail, None);
+        }
+
+        for (handle, r#override) in module.overrides.iter() {
+            let Some(ref name) = r#override.name else {
+                continue;
+            };
+            let detail = format!("override {name}: {}", r#override.ty.to_wgsl(&gctx));
+            let span = module.overrides.get_span(handle);
+            self.declare(span, name, SymbolKind::Override, detail, None);
+        }
+
+        for (handle, global) in module.global_variables.iter() {
+            let Some(ref name) = global.name else {
+                continue;
+            };
+            let detail = format!(
+                "var{} {name}: {}",
+                address_space(global.space),
+                global.ty.to_wgsl(&gctx)
+            );
+            let span = module.global_variables.get_span(handle);
+            self.declare(span, name, SymbolKind::GlobalVariable, detail, None);
+        }
+
+        for (handle, function) in module.functions.iter() {
+            let Some(ref name) = function.name else {
+                continue;
+            };
+            // A function's span stops short of its closing brace, so just
+            // use it to find where to start looking.
+            let Some(span) = module.functions.get_span(handle).to_range() else {
+                continue;
+            };
+            let Some(range) = find_function(&self.text, span.start, name) else {
+                continue;
+            };
+            let detail = signature("fn", name, function, &gctx);
+            self.declare_function(
+                module,
+                FunctionRef::Function(handle),
+                range,
+                name,
+                SymbolKind::Function,
+                detail,
+            );
+        }
+
+        for (index, entry_point) in module.entry_points.iter().enumerate() {
+            // The IR doesn't record spans for entry points, so find the
+            // declaration ourselves.
+            let Some(range) = find_function(&self.text, 0, &entry_point.name) else {
+                continue;
+            };
+            let stage = match entry_point.stage {
+                naga::ShaderStage::Vertex => "@vertex",
+                naga::ShaderStage::Fragment => "@fragment",
+                naga::ShaderStage::Compute => "@compute",
+                naga::ShaderStage::Task => "@task",
+                naga::ShaderStage::Mesh => "@mesh",
+            };
+            let detail = signature(
+                &format!("{stage} fn"),
+                &entry_point.name,
+                &entry_point.function,
+                &gctx,
+            );
+            self.declare_function(
+                module,
+                FunctionRef::EntryPoint(index),
+                range,
+                &entry_point.name,
+                SymbolKind::EntryPoint,
+                detail,
+            );
+        }
+
+        self.declarations
+            .sort_by_key(|declaration| declaration.name.start);
+    }
+
+    /// Record the declaration of a function, its arguments and its local
+    /// variables, and the types of its expressions.
+    fn declare_function(
+        &mut self,
+        module: &Module,
+        function_ref: FunctionRef,
+        range: ByteRange,
+        name: &str,
+        kind: SymbolKind,
+        detail: String,
+    ) {
+        let function = match function_ref {
+            FunctionRef::Function(handle) => &module.functions[handle],
+            FunctionRef::EntryPoint(index) => &module.entry_points[index].function,
+        };
+        let gctx = module.to_ctx();
+        let span = Span::new(range.start as u32, range.end as u32);
+        self.declare(span, name, kind, detail, None);
+
+        let mut typifier = Typifier::new();
+        let mut resolved = 0;
+        let resolve_ctx =
+            ResolveContext::with_locals(module, &function.local_variables, &function.arguments);
+        for (handle, _) in function.expressions.iter() {
+            if let Err(error) = typifier.grow(handle, &function.expressions, &resolve_ctx) {
+                log::debug!("can't resolve types in {name:?} past {handle:?}: {error}");
+                break;
+            }
+            resolved += 1;
+        }
+
+        let index = Some(self.functions.len());
+        for (handle, local) in function.local_variables.iter() {
+            let Some(ref local_name) = local.name else {
+                continue;
+            };
+            let detail = format!("var {local_name}: {}", local.ty.to_wgsl(&gctx));
+            let span = function.local_variables.get_span(handle);
+            self.declare(span, local_name, SymbolKind::LocalVariable, detail, index);
+        }
+
+        for (&handle, expression_name) in function.named_expressions.iter() {
+            let span = function.expressions.get_span(handle);
+            let Some(expression_range) = span.to_range() else {
+                continue;
+            };
+            if let naga::Expression::FunctionArgument(argument) = function.expressions[handle] {
+                let argument = &function.arguments[argument as usize];
+                let detail = format!("{expression_name}: {}", argument.ty.to_wgsl(&gctx));
+                self.declare(span, expression_name, SymbolKind::Argument, detail, index);
+                continue;
+            }
+
+            // A `let` declaration's span is that of its initializer, so
+            // look for the name before that.
+            let Some(name_start) = find_name(
+                &self.text,
+                range.start..expression_range.start,
+                expression_name,
+            )
+            .last() else {
+                continue;
+            };
+            let ty = if handle.index() < resolved {
+                format!(": {}", typifier[handle].to_wgsl(&gctx))
+            } else {
+                String::new()
+            };
+            self.declarations.push(Declaration {
+                symbol: Symbol {
+                    name: expression_name.clone(),
+                    kind: SymbolKind::Let,
+                    detail: format!("let {expression_name}{ty}"),
+                    range: self.range(name_start..expression_range.end),
+                    selection_range: self.range(name_start..name_start + expression_name.len()),
+                },
+                name: name_start..name_start + expression_name.len(),
+                function: index,
+            });
+        }
+
+        self.functions.push(FunctionInfo {
+            function: function_ref,
+            range,
+            typifier,
+            resolved,
+        });
+    }
+
+    /// Record the declaration of `name` in `span`.
+    ///
+    /// If `span` is undefined or `name` can't be found in it, the
+    /// declaration is generated rather than written in the document, and is
+    /// ignored.
+    fn declare(
+        &mut self,
+        span: Span,
+        name: &str,
+        kind: SymbolKind,
+        detail: String,
+        function: Option<usize>,
+    ) {
+        let Some(range) = span.to_range() else {
+            return;
+        };
+        let Some(name_start) = find_name(&self.text, range.clone(), name).next() else {
+            return;
+        };
+        let name_range = name_start..name_start + name.len();
+        self.declarations.push(Declaration {
+            symbol: Symbol {
+                name: name.to_string(),
+                kind,
+                detail,
+                range: self.range(range),
+                selection_range: self.range(name_range.clone()),
+            },
+            name: name_range,
+            function,
+        });
+    }
+}
+
+/// Format the address space of a global variable as WGSL, including the
+/// angle brackets, if it has one.
+fn address_space(space: naga::AddressSpace) -> String {
+    use naga::AddressSpace as As;
+
+    match space {
+        As::Function => "<function>".to_string(),
+        As::Private => "<private>".to_string(),
+        As::WorkGroup => "<workgroup>".to_string(),
+        As::Uniform => "<uniform>".to_string(),
+        As::Storage { access } => {
+            if access.contains(naga::StorageAccess::STORE) {
+                "<storage, read_write>".to_string()
+            } else {
+                "<storage>".to_string()
+            }
+        }
+        As::PushConstant => "<push_constant>".to_string(),
+        As::TaskPayload => "<task_payload>".to_string(),
+        As::Handle => String::new(),
+    }
+}
+
+/// Format the signature of `function` as WGSL.
+fn signature(keyword: &str, name: &str, function: &naga::Function, gctx: &GlobalCtx) -> String {
+    let arguments = function
+        .arguments
+        .iter()
+        .map(|argument| {
+            let name = argument.name.as_deref().unwrap_or("_");
+            format!("{name}: {}", argument.ty.to_wgsl(gctx))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    match function.result {
+        Some(ref result) => format!(
+            "{keyword} {name}({arguments}) -> {}",
+            result.ty.to_wgsl(gctx)
+        ),
+        None => format!("{keyword} {name}({arguments})"),
+    }
+}
+
+/// Return the tokens of `text[range]` with their offsets, skipping
+/// whitespace and comments.
+///
+/// Each token is either a run of identifier characters, which might also
+/// be a number, or a single other character. This is just enough to find
+/// names and braces.
+fn tokens(text: &str, range: ByteRange) -> impl Iterator<Item = (usize, &str)> {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let end = range.end;
+    let mut offset = range.start;
+    std::iter::from_fn(move || loop {
+        let rest = &text[offset..end];
+        let c = rest.chars().next()?;
+        if c.is_whitespace() {
+            offset += c.len_utf8();
+        } else if rest.starts_with("//") {
+            offset += rest.find('\n').unwrap_or(rest.len());
+        } else if rest.starts_with("/*") {
+            // Block comments nest in WGSL.
+            let mut depth = 0;
+            let mut length = rest.len();
+            let mut i = 0;
+            while i + 1 < rest.len() {
+                match &rest.as_bytes()[i..i + 2] {
+                    b"/*" => {
+                        depth += 1;
+                        i += 2;
+                    }
+                    b"*/" => {
+                        depth -= 1;
+                        i += 2;
+                        if depth == 0 {
+                            length = i;
+                            break;
+                        }
+                    }
+                    _ => i += 1,
+                }
+            }
+            offset += length;
+        } else {
+            let length = match rest.find(|c| !is_ident(c)) {
+                _ if !is_ident(c) => c.len_utf8(),
+                Some(length) => length,
+                None => rest.len(),
+            };
+            let start = offset;
+            offset += length;
+            return Some((start, &text[start..offset]));
+        }
+    })
+}
+
+/// Return the offsets of the occurrences of `name` in `text[range]`.
+fn find_name<'a>(
+    text: &'a str,
+    range: ByteRange,
+    name: &'a str,
+) -> impl DoubleEndedIterator<Item = usize> + 'a {
+    tokens(text, range)
+        .filter(move |&(_, token)| token == name)
+        .map(|(offset, _)| offset)
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+/// Return true if the block containing `range.start` is still open at
+/// `range.end`.
+fn in_scope(text: &str, range: ByteRange) -> bool {
+    let mut depth = 0;
+    for (_, token) in tokens(text, range) {
+        match token {
+            "{" => depth += 1,
+            "}" if depth == 0 => return false,
+            "}" => depth -= 1,
+            _ => {}
+        }
+    }
+    true
+}
+
+/// Find the declaration of the function `name` in `text`, starting at
+/// `start`.
+///
+/// Return the range from the `fn` keyword to the function's closing brace.
+fn find_function(text: &str, start: usize, name: &str) -> Option<ByteRange> {
+    let mut tokens = tokens(text, start..text.len()).peekable();
+    while let Some((start, token)) = tokens.next() {
+        if token != "fn" || tokens.peek().map(|&(_, next)| next) != Some(name) {
+            continue;
+        }
+        let mut depth = 0;
+        for (offset, token) in tokens.by_ref() {
+            match token {
+                "{" => depth += 1,
+                "}" if depth == 1 => return Some(start..offset + 1),
+                "}" => depth -= 1,
+                _ => {}
+            }
+        }
+        return None;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, SymbolKind};
+    use crate::line_index::{Position, Range};
+    use naga::diagnostic_filter::Severity;
+
+    const SOURCE: &str = "\
+struct Light {
+    color: vec3<f32>,
+    intensity: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> light: Light;
+
+const scale = 2.0; // not `scale` in a comment
+
+fn brightness(l: Light) -> f32 {
+    let scaled = l.intensity * scale;
+    var total = 0.0;
+    {
+        var total = 1.0;
+        total += scaled;
+    }
+    return total;
+}
+
+@fragment
+fn main() -> @location(0) vec4<f32> {
+    return vec4(light.color * brightness(light), 1.0);
+}
+";
+
+    /// Return the position of the `nth` occurrence of `needle` in `SOURCE`,
+    /// plus `delta` characters.
+    fn position(needle: &str, nth: usize, delta: u32) -> Position {
+        let offset = SOURCE.match_indices(needle).nth(nth).unwrap().0;
+        let line = SOURCE[..offset].matches('\n').count() as u32;
+        let line_start = SOURCE[..offset].rfind('\n').map_or(0, |i| i + 1);
+        Position {
+            line,
+            character: (offset - line_start) as u32 + delta,
+        }
+    }
+
+    fn name_range(needle: &str, nth: usize) -> Range {
+        Range {
+            start: position(needle, nth, 0),
+            end: position(needle, nth, needle.len() as u32),
+        }
+    }
+
+    #[test]
+    fn symbols() {
+        let document = Document::new(SOURCE.to_string());
+        assert!(document.diagnostics().is_empty());
+
+        let symbols = document
+            .symbols()
+            .map(|symbol| (symbol.kind, symbol.name.as_str(), symbol.selection_range))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            symbols,
+            [
+                (SymbolKind::Struct, "Light", name_range("Light", 0)),
+                (SymbolKind::GlobalVariable, "light", name_range("light", 0)),
+                (SymbolKind::Constant, "scale", name_range("scale", 0)),
+                (
+                    SymbolKind::Function,
+                    "brightness",
+                    name_range("brightness", 0)
+                ),
+                (SymbolKind::EntryPoint, "main", name_range("main", 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn definition() {
+        let document = Document::new(SOURCE.to_string());
+        let definition = |needle, nth| {
+            let symbol = document.definition(position(needle, nth, 1)).unwrap();
+            (symbol.kind, symbol.selection_range)
+        };
+
+        assert_eq!(
+            definition("Light", 1),
+            (SymbolKind::Struct, name_range("Light", 0))
+        );
+        assert_eq!(
+            definition("scale", 3),
+            (SymbolKind::Constant, name_range("scale", 0))
+        );
+        assert_eq!(
+            definition("l.", 0),
+            (
+                SymbolKind::Argument,
+                Range {
+                    start: position("l:", 0, 0),
+                    end: position("l:", 0, 1),
+                }
+            )
+        );
+        assert_eq!(
+            definition("scaled", 1),
+            (SymbolKind::Let, name_range("scaled", 0))
+        );
+        // The inner `total` shadows the outer one until its block ends.
+        assert_eq!(
+            definition("total", 2),
+            (SymbolKind::LocalVariable, name_range("total", 1))
+        );
+        assert_eq!(
+            definition("total", 3),
+            (SymbolKind::LocalVariable, name_range("total", 0))
+        );
+        assert_eq!(
+            definition("brightness", 1),
+            (SymbolKind::Function, name_range("brightness", 0))
+        );
+
+        // Member names aren't resolved as variables.
+        assert!(document.definition(position(".color", 0, 2)).is_none());
+    }
+
+    #[test]
+    fn hover() {
+        let document = Document::new(SOURCE.to_string());
+        let hover = |needle, nth, delta| document.hover(position(needle, nth, delta)).unwrap();
+
+        assert_eq!(hover("light", 1, 0).contents, "var<uniform> light: Light");
+        assert_eq!(
+            hover("brightness", 1, 0).contents,
+            "fn brightness(l: Light) -> f32"
+        );
+        assert_eq!(hover("scaled", 0, 0).contents, "let scaled: f32");
+        assert_eq!(hover("color *", 0, 0).contents, "vec3<f32>");
+        assert_eq!(hover("1.0)", 0, 0).contents, "f32");
+    }
+
+    #[test]
+    fn diagnostics() {
+        let document = Document::new("fn f() -> f32 { return 1.0 }".to_string());
+        let [ref diagnostic] = *document.diagnostics() else {
+            panic!("expected one diagnostic");
+        };
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert_eq!(diagnostic.range.start.character, 27);
+
+        let document = Document::new("fn f() -> f32 { return 1u; }".to_string());
+        let [ref diagnostic] = *document.diagnostics() else {
+            panic!("expected one diagnostic");
+        };
+        assert_eq!(diagnostic.severity, Severity::Error);
+        assert!(
+            diagnostic.message.contains("The `return` value"),
+            "{}",
+            diagnostic.message
+        );
+
+        let document = Document::new("fn f() {\n    let a = ;\n    let b = 1 +;\n}".to_string());
+        let lines = document
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| diagnostic.range.start.line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [1, 2]);
+    }
+
+    #[test]
+    fn warnings() {
+        let document = Document::new(
+            "\
+diagnostic(warning, precision_loss);
+diagnostic(warning, unused_variable);
+const b: f32 = 16777217;
+@diagnostic(info, unreachable_code)
+fn f() -> i32 {
+    var x = 1;
+    return 2;
+    let y = 3;
+}
+"
+            .to_string(),
+        );
+        let diagnostics = document
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.severity,
+                    diagnostic.range.start.line,
+                    diagnostic.message.lines().last().unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            [
+                (
+                    Severity::Warning,
+                    2,
+                    "`16777217` cannot be represented exactly as `f32`"
+                ),
+                (Severity::Info, 7, "code is unreachable"),
+                (Severity::Warning, 5, "Local variable [0] 'x' is never read"),
+            ]
+        );
+    }
+}
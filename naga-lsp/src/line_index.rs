@@ -0,0 +1,120 @@
+//! Converting between byte offsets and LSP positions.
+
+use serde::{Deserialize, Serialize};
+
+/// A position in a document, as LSP expects it.
+///
+/// `character` counts UTF-16 code units from the start of the line, which is
+/// the only position encoding every client supports.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+/// A range of a document, as LSP expects it.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// The offsets at which each line of a document starts.
+#[derive(Debug)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+        Self { line_starts }
+    }
+
+    /// Return the position of the byte at `offset` in `text`.
+    ///
+    /// Offsets past the end of `text`, or inside a character, are clamped.
+    pub fn position(&self, text: &str, offset: usize) -> Position {
+        let mut offset = offset.min(text.len());
+        while !text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let character = text[self.line_starts[line]..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum::<usize>();
+        Position {
+            line: line as u32,
+            character: character as u32,
+        }
+    }
+
+    /// Return the range of `text` covered by the bytes in `range`.
+    pub fn range(&self, text: &str, range: std::ops::Range<usize>) -> Range {
+        Range {
+            start: self.position(text, range.start),
+            end: self.position(text, range.end),
+        }
+    }
+
+    /// Return the byte offset of `position` in `text`.
+    ///
+    /// Positions past the end of their line are clamped to the end of the
+    /// line, as the LSP specification requires. Return `None` if there is no
+    /// such line.
+    pub fn offset(&self, text: &str, position: Position) -> Option<usize> {
+        let start = *self.line_starts.get(position.line as usize)?;
+        let end = self
+            .line_starts
+            .get(position.line as usize + 1)
+            .map_or(text.len(), |&next| next - 1);
+
+        let mut remaining = position.character as usize;
+        for (offset, ch) in text[start..end].char_indices() {
+            if remaining == 0 || ch == '\r' {
+                return Some(start + offset);
+            }
+            remaining = remaining.saturating_sub(ch.len_utf16());
+        }
+        Some(end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineIndex, Position};
+
+    #[test]
+    fn round_trip() {
+        let text = "fn a() {}\r\n// π ≈ 3.14, 𝜏 ≈ 6.28\nfn b() {}";
+        let index = LineIndex::new(text);
+
+        let check = |offset: usize, line, character| {
+            let position = Position { line, character };
+            assert_eq!(index.position(text, offset), position);
+            assert_eq!(index.offset(text, position), Some(offset));
+        };
+        check(0, 0, 0);
+        check(3, 0, 3);
+        check(11, 1, 0);
+        check(text.find('≈').unwrap(), 1, 5);
+        check(text.find('𝜏').unwrap(), 1, 13);
+        check(text.rfind('≈').unwrap(), 1, 16);
+        check(text.find("fn b").unwrap(), 2, 0);
+        check(text.len(), 2, 9);
+    }
+
+    #[test]
+    fn clamping() {
+        let text = "let x = 1;\r\nlet y = 2;";
+        let index = LineIndex::new(text);
+        let position = |line, character| Position { line, character };
+
+        assert_eq!(index.offset(text, position(0, 100)), Some(10));
+        assert_eq!(index.offset(text, position(2, 0)), None);
+        assert_eq!(index.position(text, 1000), position(1, 10));
+    }
+}
@@ -0,0 +1,43 @@
+/*!
+A language server for WGSL, built on Naga.
+
+The server speaks the [Language Server Protocol] over standard input and
+output. It reports parse and validation errors as diagnostics, shows the
+types of expressions and declarations on hover, and supports go to
+definition and document symbols.
+
+Logging goes to standard error, and is configured with `RUST_LOG`.
+
+[Language Server Protocol]: https://microsoft.github.io/language-server-protocol/
+*/
+
+mod document;
+mod line_index;
+mod server;
+mod transport;
+
+use std::io;
+
+fn main() {
+    env_logger::init();
+
+    // Editors often pass `--stdio`; that's the only transport we support, so
+    // there's nothing to configure.
+    if let Err(error) = run() {
+        log::error!("{error}");
+        std::process::exit(1);
+    }
+}
+
+fn run() -> io::Result<()> {
+    let mut input = io::stdin().lock();
+    let mut server = server::Server::new(io::stdout().lock());
+    while let Some(message) = transport::read_message(&mut input)? {
+        if let Some(exit_code) = server.handle(message)? {
+            std::process::exit(exit_code);
+        }
+    }
+
+    // The client went away without telling us to exit.
+    std::process::exit(1);
+}
@@ -0,0 +1,390 @@
+//! Dispatching LSP requests and notifications.
+
+use crate::{
+    document::{Document, SymbolKind},
+    line_index::Position,
+    transport::write_message,
+};
+use naga::diagnostic_filter::Severity;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{collections::HashMap, io};
+
+/// JSON-RPC error codes.
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TextDocumentIdentifier {
+    uri: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TextDocumentItem {
+    uri: String,
+    text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DidOpenParams {
+    text_document: TextDocumentItem,
+}
+
+/// A change to a document. We only ask for full updates, so this is always
+/// the document's new text.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ContentChange {
+    text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DidChangeParams {
+    text_document: TextDocumentIdentifier,
+    content_changes: Vec<ContentChange>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TextDocumentParams {
+    text_document: TextDocumentIdentifier,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TextDocumentPositionParams {
+    text_document: TextDocumentIdentifier,
+    position: Position,
+}
+
+/// An error to send back in response to a request.
+struct ResponseError {
+    code: i32,
+    message: String,
+}
+
+impl From<serde_json::Error> for ResponseError {
+    fn from(error: serde_json::Error) -> Self {
+        Self {
+            code: INVALID_PARAMS,
+            message: error.to_string(),
+        }
+    }
+}
+
+/// A language server, writing its messages to `W`.
+pub struct Server<W> {
+    output: W,
+    /// The open documents, by URI.
+    documents: HashMap<String, Document>,
+    shutdown_requested: bool,
+}
+
+impl<W: io::Write> Server<W> {
+    pub fn new(output: W) -> Self {
+        Self {
+            output,
+            documents: HashMap::new(),
+            shutdown_requested: false,
+        }
+    }
+
+    /// Handle a message from the client.
+    ///
+    /// Return the code the server should exit with, if the client has told
+    /// it to exit.
+    pub fn handle(&mut self, message: Value) -> io::Result<Option<i32>> {
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // We don't send any requests, so this is a stray response.
+            log::warn!("ignoring unexpected message {message}");
+            return Ok(None);
+        };
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let Some(id) = message.get("id") else {
+            return self.notification(method, params);
+        };
+        let response = match self.request(method, params) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => {
+                log::debug!("{method} failed: {}", error.message);
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": error.code, "message": error.message },
+                })
+            }
+        };
+        write_message(&mut self.output, &response)?;
+        Ok(None)
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Result<Value, ResponseError> {
+        if self.shutdown_requested {
+            return Err(ResponseError {
+                code: INVALID_REQUEST,
+                message: "the server is shutting down".to_string(),
+            });
+        }
+
+        match method {
+            "initialize" => Ok(json!({
+                "capabilities": {
+                    // Full document sync.
+                    "textDocumentSync": 1,
+                    "hoverProvider": true,
+                    "definitionProvider": true,
+                    "documentSymbolProvider": true,
+                },
+                "serverInfo": {
+                    "name": "naga-lsp",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            })),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Ok(Value::Null)
+            }
+            "textDocument/hover" => {
+                let params: TextDocumentPositionParams = serde_json::from_value(params)?;
+                let hover = self
+                    .document(&params.text_document.uri)?
+                    .hover(params.position);
+                Ok(match hover {
+                    Some(hover) => json!({
+                        "contents": {
+                            "kind": "markdown",
+                            "value": format!("```wgsl\n{}\n```", hover.contents),
+                        },
+                        "range": hover.range,
+                    }),
+                    None => Value::Null,
+                })
+            }
+            "textDocument/definition" => {
+                let params: TextDocumentPositionParams = serde_json::from_value(params)?;
+                let uri = params.text_document.uri;
+                let definition = self.document(&uri)?.definition(params.position);
+                Ok(match definition {
+                    Some(symbol) => json!({ "uri": uri, "range": symbol.selection_range }),
+                    None => Value::Null,
+                })
+            }
+            "textDocument/documentSymbol" => {
+                let params: TextDocumentParams = serde_json::from_value(params)?;
+                let symbols = self
+                    .document(&params.text_document.uri)?
+                    .symbols()
+                    .map(|symbol| {
+                        json!({
+                            "name": symbol.name,
+                            "detail": symbol.detail,
+                            "kind": symbol_kind(symbol.kind),
+                            "range": symbol.range,
+                            "selectionRange": symbol.selection_range,
+                        })
+                    })
+                    .collect();
+                Ok(Value::Array(symbols))
+            }
+            _ => Err(ResponseError {
+                code: METHOD_NOT_FOUND,
+                message: format!("unsupported request {method:?}"),
+            }),
+        }
+    }
+
+    fn notification(&mut self, method: &str, params: Value) -> io::Result<Option<i32>> {
+        let result = match method {
+            "exit" => return Ok(Some(if self.shutdown_requested { 0 } else { 1 })),
+            "textDocument/didOpen" => {
+                serde_json::from_value(params).map(|params: DidOpenParams| {
+                    let document = params.text_document;
+                    self.update(document.uri, document.text)
+                })
+            }
+            "textDocument/didChange" => {
+                serde_json::from_value(params).map(|params: DidChangeParams| {
+                    match params.content_changes.into_iter().last() {
+                        Some(change) => self.update(params.text_document.uri, change.text),
+                        None => Ok(()),
+                    }
+                })
+            }
+            "textDocument/didClose" => {
+                serde_json::from_value(params).map(|params: TextDocumentParams| {
+                    let uri = params.text_document.uri;
+                    self.documents.remove(&uri);
+                    self.publish_diagnostics(&uri, Vec::new())
+                })
+            }
+            _ => {
+                log::debug!("ignoring notification {method:?}");
+                Ok(Ok(()))
+            }
+        };
+
+        match result {
+            Ok(result) => result.map(|()| None),
+            Err(error) => {
+                log::warn!("invalid {method} notification: {error}");
+                Ok(None)
+            }
+        }
+    }
+
+    fn document(&self, uri: &str) -> Result<&Document, ResponseError> {
+        self.documents.get(uri).ok_or_else(|| ResponseError {
+            code: INVALID_PARAMS,
+            message: format!("{uri} is not open"),
+        })
+    }
+
+    /// Set the text of the document at `uri`, and report its problems.
+    fn update(&mut self, uri: String, text: String) -> io::Result<()> {
+        let document = Document::new(text);
+        let diagnostics = document
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| {
+                let related = diagnostic
+                    .related
+                    .iter()
+                    .map(|&(range, ref message)| {
+                        json!({
+                            "location": { "uri": uri, "range": range },
+                            "message": message,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                json!({
+                    "range": diagnostic.range,
+                    "severity": match diagnostic.severity {
+                        Severity::Error => 1,
+                        Severity::Warning => 2,
+                        Severity::Info | Severity::Off => 3,
+                    },
+                    "source": "naga",
+                    "message": diagnostic.message,
+                    "relatedInformation": related,
+                })
+            })
+            .collect();
+        self.publish_diagnostics(&uri, diagnostics)?;
+        self.documents.insert(uri, document);
+        Ok(())
+    }
+
+    fn publish_diagnostics(&mut self, uri: &str, diagnostics: Vec<Value>) -> io::Result<()> {
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/publishDiagnostics",
+            "params": { "uri": uri, "diagnostics": diagnostics },
+        });
+        write_message(&mut self.output, &notification)
+    }
+}
+
+/// The LSP `SymbolKind` for `kind`.
+const fn symbol_kind(kind: SymbolKind) -> u32 {
+    match kind {
+        SymbolKind::Struct => 23,
+        SymbolKind::Constant | SymbolKind::Override => 14,
+        SymbolKind::Function | SymbolKind::EntryPoint => 12,
+        SymbolKind::GlobalVariable
+        | SymbolKind::Argument
+        | SymbolKind::LocalVariable
+        | SymbolKind::Let => 13,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Server;
+    use crate::transport::read_message;
+    use serde_json::{json, Value};
+
+    /// Send `messages` to a new server, and return what it sends back.
+    fn run(messages: &[Value]) -> (Vec<Value>, Option<i32>) {
+        let mut output = Vec::new();
+        let mut server = Server::new(&mut output);
+        let mut exit_code = None;
+        for message in messages {
+            exit_code = server.handle(message.clone()).unwrap();
+        }
+
+        let mut reader = &output[..];
+        let mut responses = Vec::new();
+        while let Some(message) = read_message(&mut reader).unwrap() {
+            responses.push(message);
+        }
+        (responses, exit_code)
+    }
+
+    #[test]
+    fn session() {
+        let uri = "file:///shader.wgsl";
+        let (responses, exit_code) = run(&[
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+            json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": {
+                    "textDocument": {
+                        "uri": uri,
+                        "languageId": "wgsl",
+                        "version": 1,
+                        "text": "fn f() -> f32 { return 1u; }",
+                    },
+                },
+            }),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didChange",
+                "params": {
+                    "textDocument": { "uri": uri, "version": 2 },
+                    "contentChanges": [{ "text": "fn f() -> f32 { return 1.0; }" }],
+                },
+            }),
+            json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "textDocument/hover",
+                "params": {
+                    "textDocument": { "uri": uri },
+                    "position": { "line": 0, "character": 3 },
+                },
+            }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "textDocument/rename", "params": {} }),
+            json!({ "jsonrpc": "2.0", "id": 4, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ]);
+        assert_eq!(exit_code, Some(0));
+
+        let [initialize, invalid, valid, hover, rename, shutdown] = &responses[..] else {
+            panic!("unexpected responses {responses:#?}");
+        };
+        assert_eq!(initialize["id"], 1);
+        assert_eq!(initialize["result"]["capabilities"]["hoverProvider"], true);
+        assert_eq!(invalid["params"]["diagnostics"][0]["severity"], 1);
+        assert_eq!(valid["params"]["diagnostics"], json!([]));
+        assert_eq!(
+            hover["result"]["contents"]["value"],
+            "```wgsl\nfn f() -> f32\n```"
+        );
+        assert_eq!(rename["error"]["code"], -32601);
+        assert_eq!(shutdown["result"], Value::Null);
+    }
+
+    #[test]
+    fn exit_without_shutdown() {
+        let (_, exit_code) = run(&[json!({ "jsonrpc": "2.0", "method": "exit" })]);
+        assert_eq!(exit_code, Some(1));
+    }
+}
@@ -0,0 +1,105 @@
+//! Reading and writing LSP messages.
+//!
+//! Each message is a JSON-RPC object preceded by a `Content-Length` header
+//! and a blank line, as described in the [base protocol].
+//!
+//! [base protocol]: https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#baseProtocol
+
+use std::io::{self, BufRead, Write};
+
+/// The largest message we accept, so that a bogus `Content-Length` can't make
+/// us allocate an arbitrary amount of memory.
+const MAX_CONTENT_LENGTH: usize = 64 << 20;
+
+/// Read the next message from `reader`.
+///
+/// Return `Ok(None)` if the input ends before a message starts.
+pub fn read_message(reader: &mut impl BufRead) -> io::Result<Option<serde_json::Value>> {
+    let mut content_length = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return if content_length.is_none() {
+                Ok(None)
+            } else {
+                Err(io::ErrorKind::UnexpectedEof.into())
+            };
+        }
+
+        let header = line.trim_end_matches(['\r', '\n']);
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(invalid_data(format!("malformed header {header:?}")));
+        };
+        if name.eq_ignore_ascii_case("Content-Length") {
+            let length = value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| invalid_data(format!("invalid Content-Length {value:?}")))?;
+            content_length = Some(length);
+        }
+    }
+
+    let Some(content_length) = content_length else {
+        return Err(invalid_data("missing Content-Length header".to_string()));
+    };
+    if content_length > MAX_CONTENT_LENGTH {
+        return Err(invalid_data(format!(
+            "Content-Length {content_length} exceeds the limit of {MAX_CONTENT_LENGTH} bytes"
+        )));
+    }
+    let mut content = vec![0; content_length];
+    reader.read_exact(&mut content)?;
+    serde_json::from_slice(&content)
+        .map(Some)
+        .map_err(Into::into)
+}
+
+/// Write `message` to `writer`, and flush it.
+pub fn write_message(writer: &mut impl Write, message: &serde_json::Value) -> io::Result<()> {
+    let content = serde_json::to_string(message)?;
+    write!(
+        writer,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    writer.flush()
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_message, write_message};
+
+    #[test]
+    fn round_trip() {
+        let message = serde_json::json!({ "jsonrpc": "2.0", "method": "exit" });
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &message).unwrap();
+        write_message(&mut buffer, &message).unwrap();
+
+        let mut reader = &buffer[..];
+        assert_eq!(read_message(&mut reader).unwrap(), Some(message.clone()));
+        assert_eq!(read_message(&mut reader).unwrap(), Some(message));
+        assert_eq!(read_message(&mut reader).unwrap(), None);
+    }
+
+    #[test]
+    fn oversized_message() {
+        let mut reader = &b"Content-Length: 18446744073709551615\r\n\r\n{}"[..];
+        assert!(read_message(&mut reader).is_err());
+    }
+
+    #[test]
+    fn missing_length() {
+        let mut reader = &b"Content-Type: foo\r\n\r\n{}"[..];
+        assert!(read_message(&mut reader).is_err());
+    }
+}
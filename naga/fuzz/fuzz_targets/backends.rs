@@ -0,0 +1,38 @@
+#![no_main]
+#[cfg(not(any(target_arch = "wasm32", target_os = "ios")))]
+mod fuzz {
+    use libfuzzer_sys::fuzz_target;
+
+    fuzz_target!(|module: naga::Module| {
+        use naga::{back, valid as v};
+
+        // Only modules that pass validation are expected to be accepted by the
+        // backends, but every backend must report an error instead of panicking.
+        let mut validator = v::Validator::new(v::ValidationFlags::all(), v::Capabilities::all());
+        let Ok(info) = validator.validate(&module) else {
+            return;
+        };
+        let Ok((module, info)) =
+            back::pipeline_constants::process_overrides(&module, &info, &Default::default())
+        else {
+            return;
+        };
+        // `Capabilities::all()` lets pointers to any address space be passed
+        // to functions, which the backends expect to have been specialized
+        // away, as wgpu-hal does.
+        let Ok((module, info)) =
+            back::pointer_arguments::specialize_pointer_arguments(&module, &info)
+        else {
+            return;
+        };
+
+        let _result = back::spv::write_vec(&module, &info, &Default::default(), None);
+
+        let _result =
+            back::msl::write_string(&module, &info, &Default::default(), &Default::default());
+
+        let options = back::hlsl::Options::default();
+        let mut output = String::new();
+        let _result = back::hlsl::Writer::new(&mut output, &options).write(&module, &info, None);
+    });
+}
@@ -36,6 +36,7 @@ mod fuzz {
             Options {
                 stage: proxy.stage.into(),
                 defines: proxy.defines,
+                include_resolver: None,
             }
         }
     }
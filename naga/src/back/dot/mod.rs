@@ -278,6 +278,13 @@ impl StatementGraph {
                             self.emits.push((id, result));
                             "RayQueryProceed"
                         }
+                        crate::RayQueryFunction::GenerateIntersection { hit_t } => {
+                            self.dependencies.push((id, hit_t, "hit_t"));
+                            "RayQueryGenerateIntersection"
+                        }
+                        crate::RayQueryFunction::ConfirmIntersection => {
+                            "RayQueryConfirmIntersection"
+                        }
                         crate::RayQueryFunction::Terminate => "RayQueryTerminate",
                     }
                 }
@@ -340,6 +347,9 @@ impl StatementGraph {
                             crate::CollectiveOperation::InclusiveScan,
                             crate::SubgroupOperation::Mul,
                         ) => "SubgroupInclusiveMul",
+                        (crate::CollectiveOperation::ClusteredReduce(_), _) => {
+                            "SubgroupClusteredReduce"
+                        }
                         _ => unimplemented!(),
                     }
                 }
@@ -349,12 +359,13 @@ impl StatementGraph {
                     result,
                 } => {
                     match mode {
-                        crate::GatherMode::BroadcastFirst => {}
+                        crate::GatherMode::BroadcastFirst | crate::GatherMode::QuadSwap(_) => {}
                         crate::GatherMode::Broadcast(index)
                         | crate::GatherMode::Shuffle(index)
                         | crate::GatherMode::ShuffleDown(index)
                         | crate::GatherMode::ShuffleUp(index)
-                        | crate::GatherMode::ShuffleXor(index) => {
+                        | crate::GatherMode::ShuffleXor(index)
+                        | crate::GatherMode::QuadBroadcast(index) => {
                             self.dependencies.push((id, index, "index"))
                         }
                     }
@@ -367,8 +378,31 @@ impl StatementGraph {
                         crate::GatherMode::ShuffleDown(_) => "SubgroupShuffleDown",
                         crate::GatherMode::ShuffleUp(_) => "SubgroupShuffleUp",
                         crate::GatherMode::ShuffleXor(_) => "SubgroupShuffleXor",
+                        crate::GatherMode::QuadBroadcast(_) => "SubgroupQuadBroadcast",
+                        crate::GatherMode::QuadSwap(_) => "SubgroupQuadSwap",
                     }
                 }
+                S::MeshFunction(ref fun) => match *fun {
+                    crate::MeshFunction::SetMeshOutputs {
+                        vertex_count,
+                        primitive_count,
+                    } => {
+                        self.dependencies.push((id, vertex_count, "vertex_count"));
+                        self.dependencies
+                            .push((id, primitive_count, "primitive_count"));
+                        "SetMeshOutputs"
+                    }
+                    crate::MeshFunction::SetVertex { index, value } => {
+                        self.dependencies.push((id, index, "index"));
+                        self.dependencies.push((id, value, "value"));
+                        "SetVertex"
+                    }
+                    crate::MeshFunction::SetPrimitive { index, value } => {
+                        self.dependencies.push((id, index, "index"));
+                        self.dependencies.push((id, value, "value"));
+                        "SetPrimitive"
+                    }
+                },
             };
             // Set the last node to the merge node
             last_node = merge_id;
@@ -52,6 +52,12 @@ bitflags::bitflags! {
         const TEXTURE_SHADOW_LOD = 1 << 23;
         /// Subgroup operations
         const SUBGROUP_OPERATIONS = 1 << 24;
+        /// 2 byte floats.
+        const FLOAT16_TYPE = 1 << 25;
+        /// Quad subgroup operations
+        const SUBGROUP_QUAD = 1 << 26;
+        /// Clustered subgroup operations
+        const SUBGROUP_CLUSTERED = 1 << 27;
     }
 }
 
@@ -120,6 +126,9 @@ impl FeaturesManager {
         check_feature!(DYNAMIC_ARRAY_SIZE, 430, 310);
         check_feature!(DUAL_SOURCE_BLENDING, 330, 300 /* with extension */);
         check_feature!(SUBGROUP_OPERATIONS, 430, 310);
+        check_feature!(SUBGROUP_QUAD, 430, 310);
+        check_feature!(SUBGROUP_CLUSTERED, 430, 310);
+        check_feature!(FLOAT16_TYPE, 450, 310);
         match version {
             Version::Embedded { is_webgl: true, .. } => check_feature!(MULTI_VIEW, 140, 300),
             _ => check_feature!(MULTI_VIEW, 140, 310),
@@ -164,6 +173,16 @@ impl FeaturesManager {
             writeln!(out, "#extension GL_ARB_gpu_shader_fp64 : require")?;
         }
 
+        if self.0.contains(Features::FLOAT16_TYPE) {
+            // https://github.com/KhronosGroup/GLSL/blob/main/extensions/ext/GL_EXT_shader_explicit_arithmetic_types.txt
+            writeln!(
+                out,
+                "#extension GL_EXT_shader_explicit_arithmetic_types_float16 : require"
+            )?;
+            // https://github.com/KhronosGroup/GLSL/blob/main/extensions/ext/GL_EXT_shader_16bit_storage.txt
+            writeln!(out, "#extension GL_EXT_shader_16bit_storage : require")?;
+        }
+
         if self.0.contains(Features::CUBE_TEXTURES_ARRAY) {
             if options.version.is_es() {
                 // https://www.khronos.org/registry/OpenGL/extensions/EXT/EXT_texture_cube_map_array.txt
@@ -278,6 +297,14 @@ impl FeaturesManager {
             )?;
         }
 
+        if self.0.contains(Features::SUBGROUP_QUAD) {
+            writeln!(out, "#extension GL_KHR_shader_subgroup_quad : require")?;
+        }
+
+        if self.0.contains(Features::SUBGROUP_CLUSTERED) {
+            writeln!(out, "#extension GL_KHR_shader_subgroup_clustered : require")?;
+        }
+
         Ok(())
     }
 }
@@ -546,13 +573,26 @@ impl<W> Writer<'_, W> {
             }
         }
 
+        for block in module
+            .functions
+            .iter()
+            .map(|(_, f)| &f.body)
+            .chain(std::iter::once(&entry_point.function.body))
+        {
+            subgroup_required_features(block, features);
+        }
+
         self.features.check_availability(self.options.version)
     }
 
     /// Helper method that checks the [`Features`] needed by a scalar
     fn scalar_required_features(&mut self, scalar: Scalar) {
-        if scalar.kind == ScalarKind::Float && scalar.width == 8 {
-            self.features.request(Features::DOUBLE_TYPE);
+        if scalar.kind == ScalarKind::Float {
+            match scalar.width {
+                2 => self.features.request(Features::FLOAT16_TYPE),
+                8 => self.features.request(Features::DOUBLE_TYPE),
+                _ => {}
+            }
         }
     }
 
@@ -580,6 +620,7 @@ impl<W> Writer<'_, W> {
                     interpolation,
                     sampling,
                     second_blend_source,
+                    per_primitive: _,
                 } => {
                     if interpolation == Some(Interpolation::Linear) {
                         self.features.request(Features::NOPERSPECTIVE_QUALIFIER);
@@ -595,3 +636,45 @@ impl<W> Writer<'_, W> {
         }
     }
 }
+
+/// Request the [`Features`] needed by the quad and clustered subgroup
+/// operations in `block`.
+fn subgroup_required_features(block: &crate::Block, features: &mut FeaturesManager) {
+    use crate::Statement as S;
+
+    for statement in block {
+        match *statement {
+            S::SubgroupCollectiveOperation {
+                collective_op: crate::CollectiveOperation::ClusteredReduce(_),
+                ..
+            } => features.request(Features::SUBGROUP_CLUSTERED),
+            S::SubgroupGather {
+                mode: crate::GatherMode::QuadBroadcast(_) | crate::GatherMode::QuadSwap(_),
+                ..
+            } => features.request(Features::SUBGROUP_QUAD),
+            S::Block(ref block) => subgroup_required_features(block, features),
+            S::If {
+                ref accept,
+                ref reject,
+                ..
+            } => {
+                subgroup_required_features(accept, features);
+                subgroup_required_features(reject, features);
+            }
+            S::Switch { ref cases, .. } => {
+                for case in cases {
+                    subgroup_required_features(&case.body, features);
+                }
+            }
+            S::Loop {
+                ref body,
+                ref continuing,
+                ..
+            } => {
+                subgroup_required_features(body, features);
+                subgroup_required_features(continuing, features);
+            }
+            _ => {}
+        }
+    }
+}
@@ -112,7 +112,8 @@ impl crate::AddressSpace {
             | crate::AddressSpace::Uniform
             | crate::AddressSpace::Storage { .. }
             | crate::AddressSpace::Handle
-            | crate::AddressSpace::PushConstant => false,
+            | crate::AddressSpace::PushConstant
+            | crate::AddressSpace::TaskPayload => false,
         }
     }
 }
@@ -316,6 +317,11 @@ pub struct ReflectionInfo {
     pub varying: crate::FastHashMap<String, VaryingLocation>,
     /// List of push constant items in the shader.
     pub push_constant_items: Vec<PushConstantItem>,
+    /// Number of user-defined clip planes written by the entry point.
+    ///
+    /// OpenGL requires each `GL_CLIP_DISTANCEi` in this range to be enabled
+    /// for the written distances to take effect.
+    pub clip_distance_count: u32,
 }
 
 /// Mapping between a texture and its sampler, if it exists.
@@ -434,7 +440,9 @@ impl fmt::Display for VaryingName<'_> {
             }
             crate::Binding::Location { location, .. } => {
                 let prefix = match (self.stage, self.options.output) {
-                    (ShaderStage::Compute, _) => unreachable!(),
+                    (ShaderStage::Compute | ShaderStage::Task | ShaderStage::Mesh, _) => {
+                        unreachable!()
+                    }
                     // pipeline to vertex
                     (ShaderStage::Vertex, false) => "p2vs",
                     // vertex to fragment
@@ -457,6 +465,8 @@ impl ShaderStage {
             ShaderStage::Compute => "cs",
             ShaderStage::Fragment => "fs",
             ShaderStage::Vertex => "vs",
+            ShaderStage::Task => "ts",
+            ShaderStage::Mesh => "ms",
         }
     }
 }
@@ -501,6 +511,9 @@ pub enum Error {
     /// [`crate::Sampling::First`] is unsupported.
     #[error("`{:?}` sampling is unsupported", crate::Sampling::First)]
     FirstSamplingNotSupported,
+    /// Task and mesh shaders are not supported.
+    #[error("{0:?} shaders are not supported")]
+    UnsupportedShaderStage(ShaderStage),
 }
 
 /// Binary operation with a different logic on the GLSL side.
@@ -557,6 +570,8 @@ pub struct Writer<'a, W> {
     multiview: Option<std::num::NonZeroU32>,
     /// Mapping of varying variables to their location. Needed for reflections.
     varying: crate::FastHashMap<String, VaryingLocation>,
+    /// Number of user-defined clip planes. Needed for reflections.
+    clip_distance_count: u32,
 }
 
 impl<'a, W: Write> Writer<'a, W> {
@@ -593,6 +608,10 @@ impl<'a, W: Write> Writer<'a, W> {
             })
             .ok_or(Error::EntryPointNotFound)?;
 
+        if let ShaderStage::Task | ShaderStage::Mesh = pipeline_options.shader_stage {
+            return Err(Error::UnsupportedShaderStage(pipeline_options.shader_stage));
+        }
+
         // Generate a map with names required to write the module
         let mut names = crate::FastHashMap::default();
         let mut namer = proc::Namer::default();
@@ -629,6 +648,7 @@ impl<'a, W: Write> Writer<'a, W> {
             need_bake_expressions: Default::default(),
             continue_ctx: back::continue_forward::ContinueCtx::default(),
             varying: Default::default(),
+            clip_distance_count: 0,
         };
 
         // Find all features required to print this module
@@ -748,15 +768,13 @@ impl<'a, W: Write> Writer<'a, W> {
             match type_key {
                 &crate::PredeclaredType::ModfResult { size, width }
                 | &crate::PredeclaredType::FrexpResult { size, width } => {
+                    let scalar = glsl_scalar(crate::Scalar::float(width))?;
                     let arg_type_name_owner;
                     let arg_type_name = if let Some(size) = size {
-                        arg_type_name_owner =
-                            format!("{}vec{}", if width == 8 { "d" } else { "" }, size as u8);
+                        arg_type_name_owner = format!("{}vec{}", scalar.prefix, size as u8);
                         &arg_type_name_owner
-                    } else if width == 8 {
-                        "double"
                     } else {
-                        "float"
+                        scalar.full
                     };
 
                     let other_type_name_owner;
@@ -1182,7 +1200,7 @@ impl<'a, W: Write> Writer<'a, W> {
             crate::AddressSpace::Private => {
                 self.write_simple_global(handle, global)?;
             }
-            crate::AddressSpace::WorkGroup => {
+            crate::AddressSpace::WorkGroup | crate::AddressSpace::TaskPayload => {
                 self.write_simple_global(handle, global)?;
             }
             crate::AddressSpace::PushConstant => {
@@ -1329,6 +1347,10 @@ impl<'a, W: Write> Writer<'a, W> {
                             self.need_bake_expressions.insert(arg1.unwrap());
                         }
                     }
+                    crate::MathFunction::Dot4I8Packed | crate::MathFunction::Dot4U8Packed => {
+                        self.need_bake_expressions.insert(arg);
+                        self.need_bake_expressions.insert(arg1.unwrap());
+                    }
                     crate::MathFunction::Pack4xI8
                     | crate::MathFunction::Pack4xU8
                     | crate::MathFunction::Unpack4xI8
@@ -1465,6 +1487,7 @@ impl<'a, W: Write> Writer<'a, W> {
                 interpolation,
                 sampling,
                 second_blend_source,
+                per_primitive: _,
             } => (location, interpolation, sampling, second_blend_source),
             crate::Binding::BuiltIn(built_in) => {
                 if let crate::BuiltIn::Position { invariant: true } = built_in {
@@ -1493,6 +1516,18 @@ impl<'a, W: Write> Writer<'a, W> {
                         }
                     }
                 }
+                if let crate::BuiltIn::ClipDistance = built_in {
+                    // Redeclare the built-in with an explicit size, so that the
+                    // whole array can be assigned at once.
+                    if let TypeInner::Array {
+                        size: crate::ArraySize::Constant(size),
+                        ..
+                    } = self.module.types[ty].inner
+                    {
+                        writeln!(self.out, "out float gl_ClipDistance[{size}];")?;
+                        self.clip_distance_count = size.get();
+                    }
+                }
                 return Ok(());
             }
         };
@@ -1504,7 +1539,7 @@ impl<'a, W: Write> Writer<'a, W> {
         let emit_interpolation_and_auxiliary = match self.entry_point.stage {
             ShaderStage::Vertex => output,
             ShaderStage::Fragment => !output,
-            ShaderStage::Compute => false,
+            ShaderStage::Compute | ShaderStage::Task | ShaderStage::Mesh => false,
         };
 
         // Write the I/O locations, if allowed
@@ -1563,6 +1598,7 @@ impl<'a, W: Write> Writer<'a, W> {
                 interpolation: None,
                 sampling: None,
                 second_blend_source,
+                per_primitive: false,
             },
             stage: self.entry_point.stage,
             options: VaryingOptions::from_writer_options(self.options, output),
@@ -2539,9 +2575,27 @@ impl<'a, W: Write> Writer<'a, W> {
                     (crate::CollectiveOperation::InclusiveScan, crate::SubgroupOperation::Mul) => {
                         write!(self.out, "subgroupInclusiveMul(")?
                     }
+                    (crate::CollectiveOperation::ClusteredReduce(_), op) => {
+                        let fun = match op {
+                            crate::SubgroupOperation::Add => "subgroupClusteredAdd",
+                            crate::SubgroupOperation::Mul => "subgroupClusteredMul",
+                            crate::SubgroupOperation::Max => "subgroupClusteredMax",
+                            crate::SubgroupOperation::Min => "subgroupClusteredMin",
+                            crate::SubgroupOperation::And => "subgroupClusteredAnd",
+                            crate::SubgroupOperation::Or => "subgroupClusteredOr",
+                            crate::SubgroupOperation::Xor => "subgroupClusteredXor",
+                            crate::SubgroupOperation::All | crate::SubgroupOperation::Any => {
+                                unreachable!()
+                            }
+                        };
+                        write!(self.out, "{fun}(")?
+                    }
                     _ => unimplemented!(),
                 }
                 self.write_expr(argument, ctx)?;
+                if let crate::CollectiveOperation::ClusteredReduce(size) = collective_op {
+                    write!(self.out, ", {size}u")?;
+                }
                 writeln!(self.out, ");")?;
             }
             Statement::SubgroupGather {
@@ -2575,21 +2629,38 @@ impl<'a, W: Write> Writer<'a, W> {
                     crate::GatherMode::ShuffleXor(_) => {
                         write!(self.out, "subgroupShuffleXor(")?;
                     }
+                    crate::GatherMode::QuadBroadcast(_) => {
+                        write!(self.out, "subgroupQuadBroadcast(")?;
+                    }
+                    crate::GatherMode::QuadSwap(direction) => match direction {
+                        crate::Direction::X => {
+                            write!(self.out, "subgroupQuadSwapHorizontal(")?;
+                        }
+                        crate::Direction::Y => {
+                            write!(self.out, "subgroupQuadSwapVertical(")?;
+                        }
+                        crate::Direction::Diagonal => {
+                            write!(self.out, "subgroupQuadSwapDiagonal(")?;
+                        }
+                    },
                 }
                 self.write_expr(argument, ctx)?;
                 match mode {
-                    crate::GatherMode::BroadcastFirst => {}
+                    crate::GatherMode::BroadcastFirst | crate::GatherMode::QuadSwap(_) => {}
                     crate::GatherMode::Broadcast(index)
                     | crate::GatherMode::Shuffle(index)
                     | crate::GatherMode::ShuffleDown(index)
                     | crate::GatherMode::ShuffleUp(index)
-                    | crate::GatherMode::ShuffleXor(index) => {
+                    | crate::GatherMode::ShuffleXor(index)
+                    | crate::GatherMode::QuadBroadcast(index) => {
                         write!(self.out, ", ")?;
                         self.write_expr(index, ctx)?;
                     }
                 }
                 writeln!(self.out, ");")?;
             }
+            // Mesh shaders are rejected by `Writer::new`
+            Statement::MeshFunction(_) => unreachable!(),
         }
 
         Ok(())
@@ -2652,6 +2723,7 @@ impl<'a, W: Write> Writer<'a, W> {
                     // decimal part even it's zero which is needed for a valid glsl float constant
                     crate::Literal::F64(value) => write!(self.out, "{value:?}LF")?,
                     crate::Literal::F32(value) => write!(self.out, "{value:?}")?,
+                    crate::Literal::F16(value) => write!(self.out, "{value:?}hf")?,
                     // Unsigned integers need a `u` at the end
                     //
                     // While `core` doesn't necessarily need it, it's allowed and since `es` needs it we
@@ -3454,6 +3526,35 @@ impl<'a, W: Write> Writer<'a, W> {
                             "Correct TypeInner for dot product should be already validated"
                         ),
                     },
+                    fun @ (Mf::Dot4I8Packed | Mf::Dot4U8Packed) => {
+                        // There is no packed dot product in GLSL, so extract the
+                        // components as in `Unpack4xI8`/`Unpack4xU8` and sum their products.
+                        write!(self.out, "(")?;
+                        for i in 0..4 {
+                            write!(self.out, " + ")?;
+                            for (j, packed) in [arg, arg1.unwrap()].into_iter().enumerate() {
+                                if j != 0 {
+                                    write!(self.out, " * ")?;
+                                }
+                                write!(self.out, "bitfieldExtract(")?;
+                                // Since bitfieldExtract only sign extends if the value is
+                                // signed, this cast is needed
+                                match fun {
+                                    Mf::Dot4I8Packed => {
+                                        write!(self.out, "int(")?;
+                                        self.write_expr(packed, ctx)?;
+                                        write!(self.out, ")")?;
+                                    }
+                                    Mf::Dot4U8Packed => self.write_expr(packed, ctx)?,
+                                    _ => unreachable!(),
+                                };
+                                write!(self.out, ", {}, 8)", i * 8)?;
+                            }
+                        }
+                        write!(self.out, ")")?;
+
+                        return Ok(());
+                    }
                     Mf::Outer => "outerProduct",
                     Mf::Cross => "cross",
                     Mf::Distance => "distance",
@@ -4625,6 +4726,7 @@ impl<'a, W: Write> Writer<'a, W> {
             uniforms,
             varying: mem::take(&mut self.varying),
             push_constant_items,
+            clip_distance_count: self.clip_distance_count,
         })
     }
 
@@ -4703,7 +4805,7 @@ struct ScalarString<'a> {
 /// Check [`ScalarString`] for the information provided
 ///
 /// # Errors
-/// If a [`Float`](crate::ScalarKind::Float) with an width that isn't 4 or 8
+/// If a [`Float`](crate::ScalarKind::Float) with an width that isn't 2, 4 or 8
 const fn glsl_scalar(scalar: crate::Scalar) -> Result<ScalarString<'static>, Error> {
     use crate::ScalarKind as Sk;
 
@@ -4717,6 +4819,10 @@ const fn glsl_scalar(scalar: crate::Scalar) -> Result<ScalarString<'static>, Err
             full: "uint",
         },
         Sk::Float => match scalar.width {
+            2 => ScalarString {
+                prefix: "f16",
+                full: "float16_t",
+            },
             4 => ScalarString {
                 prefix: "",
                 full: "float",
@@ -4792,6 +4898,12 @@ const fn glsl_built_in(built_in: crate::BuiltIn, options: VaryingOptions) -> &'s
         Bi::SubgroupId => "gl_SubgroupID",
         Bi::SubgroupSize => "gl_SubgroupSize",
         Bi::SubgroupInvocationId => "gl_SubgroupInvocationID",
+        // task and mesh, rejected by `Writer::new`
+        Bi::MeshTaskSize
+        | Bi::PointIndex
+        | Bi::LineIndices
+        | Bi::TriangleIndices
+        | Bi::CullPrimitive => unreachable!(),
     }
 }
 
@@ -4807,6 +4919,7 @@ const fn glsl_storage_qualifier(space: crate::AddressSpace) -> Option<&'static s
         As::Handle => Some("uniform"),
         As::WorkGroup => Some("shared"),
         As::PushConstant => Some("uniform"),
+        As::TaskPayload => Some("taskPayloadSharedEXT"),
     }
 }
 
@@ -175,6 +175,13 @@ impl crate::BuiltIn {
             | Self::SubgroupInvocationId
             | Self::NumSubgroups
             | Self::SubgroupId => unreachable!(),
+            // mesh
+            Self::CullPrimitive => "SV_CullPrimitive",
+            // These builtins are passed to `DispatchMesh` or written to the
+            // `indices` array of a mesh shader
+            Self::MeshTaskSize | Self::PointIndex | Self::LineIndices | Self::TriangleIndices => {
+                unreachable!()
+            }
             Self::BaseInstance | Self::BaseVertex | Self::WorkGroupSize => {
                 return Err(Error::Unimplemented(format!("builtin {self:?}")))
             }
@@ -220,7 +227,7 @@ impl crate::AtomicFunction {
             Self::Min => "Min",
             Self::Max => "Max",
             Self::Exchange { compare: None } => "Exchange",
-            Self::Exchange { .. } => "", //TODO
+            Self::Exchange { compare: Some(_) } => "CompareExchange",
         }
     }
 }
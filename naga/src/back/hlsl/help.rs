@@ -39,6 +39,19 @@ pub(super) struct WrappedArrayLength {
     pub(super) writable: bool,
 }
 
+impl WrappedArrayLength {
+    /// The helper that queries the length of the storage global `var`.
+    pub(super) fn for_global(var: &crate::GlobalVariable) -> Self {
+        let storage_access = match var.space {
+            crate::AddressSpace::Storage { access } => access,
+            _ => crate::StorageAccess::default(),
+        };
+        WrappedArrayLength {
+            writable: storage_access.contains(crate::StorageAccess::STORE),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
 pub(super) struct WrappedImageQuery {
     pub(super) dim: crate::ImageDimension,
@@ -245,10 +258,7 @@ impl<W: Write> super::Writer<'_, W> {
     /// <https://docs.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-to-getdimensions>
     pub(super) fn write_wrapped_image_query_function(
         &mut self,
-        module: &crate::Module,
         wiq: WrappedImageQuery,
-        expr_handle: Handle<crate::Expression>,
-        func_ctx: &FunctionCtx,
     ) -> BackendResult {
         use crate::{
             back::{COMPONENTS, INDENT},
@@ -259,26 +269,6 @@ impl<W: Write> super::Writer<'_, W> {
         const RETURN_VARIABLE_NAME: &str = "ret";
         const MIP_LEVEL_PARAM: &str = "mip_level";
 
-        // Write function return type and name
-        let ret_ty = func_ctx.resolve_type(expr_handle, &module.types);
-        self.write_value_type(module, ret_ty)?;
-        write!(self.out, " ")?;
-        self.write_wrapped_image_query_function_name(wiq)?;
-
-        // Write function parameters
-        write!(self.out, "(")?;
-        // Texture always first parameter
-        self.write_image_type(wiq.dim, wiq.arrayed, wiq.class)?;
-        write!(self.out, " {ARGUMENT_VARIABLE_NAME}")?;
-        // Mipmap is a second parameter if exists
-        if let ImageQuery::SizeLevel = wiq.query {
-            write!(self.out, ", uint {MIP_LEVEL_PARAM}")?;
-        }
-        writeln!(self.out, ")")?;
-
-        // Write function body
-        writeln!(self.out, "{{")?;
-
         let array_coords = usize::from(wiq.arrayed);
         // extra parameter is the mip level count or the sample count
         let extra_coords = match wiq.class {
@@ -298,7 +288,12 @@ impl<W: Write> super::Writer<'_, W> {
                 };
                 (ret, ret.len() + array_coords + extra_coords)
             }
-            ImageQuery::NumLevels | ImageQuery::NumSamples | ImageQuery::NumLayers => {
+            // The layer count directly follows the size.
+            ImageQuery::NumLayers => match wiq.dim {
+                IDim::D1 => ("y", 2 + extra_coords),
+                IDim::D2 | IDim::D3 | IDim::Cube => ("z", 3 + extra_coords),
+            },
+            ImageQuery::NumLevels | ImageQuery::NumSamples => {
                 if wiq.arrayed || wiq.dim == IDim::D3 {
                     ("w", 4)
                 } else {
@@ -307,6 +302,28 @@ impl<W: Write> super::Writer<'_, W> {
             }
         };
 
+        // Write function return type and name
+        match ret_swizzle.len() {
+            1 => write!(self.out, "uint")?,
+            size => write!(self.out, "uint{size}")?,
+        }
+        write!(self.out, " ")?;
+        self.write_wrapped_image_query_function_name(wiq)?;
+
+        // Write function parameters
+        write!(self.out, "(")?;
+        // Texture always first parameter
+        self.write_image_type(wiq.dim, wiq.arrayed, wiq.class)?;
+        write!(self.out, " {ARGUMENT_VARIABLE_NAME}")?;
+        // Mipmap is a second parameter if exists
+        if let ImageQuery::SizeLevel = wiq.query {
+            write!(self.out, ", uint {MIP_LEVEL_PARAM}")?;
+        }
+        writeln!(self.out, ")")?;
+
+        // Write function body
+        writeln!(self.out, "{{")?;
+
         // Write `GetDimensions` function.
         writeln!(self.out, "{INDENT}uint4 {RETURN_VARIABLE_NAME};")?;
         write!(self.out, "{INDENT}{ARGUMENT_VARIABLE_NAME}.GetDimensions(")?;
@@ -798,18 +815,13 @@ impl<W: Write> super::Writer<'_, W> {
             match type_key {
                 &crate::PredeclaredType::ModfResult { size, width }
                 | &crate::PredeclaredType::FrexpResult { size, width } => {
+                    let scalar_name = crate::Scalar::float(width).to_hlsl_str()?;
                     let arg_type_name_owner;
                     let arg_type_name = if let Some(size) = size {
-                        arg_type_name_owner = format!(
-                            "{}{}",
-                            if width == 8 { "double" } else { "float" },
-                            size as u8
-                        );
+                        arg_type_name_owner = format!("{scalar_name}{}", size as u8);
                         &arg_type_name_owner
-                    } else if width == 8 {
-                        "double"
                     } else {
-                        "float"
+                        scalar_name
                     };
 
                     let (defined_func_name, called_func_name, second_field_name, sign_multiplier) =
@@ -842,6 +854,107 @@ impl<W: Write> super::Writer<'_, W> {
             }
         }
 
+        if let Some(ray_desc) = module.special_types.ray_desc {
+            let struct_name = &self.names[&NameKey::Type(ray_desc)];
+            let defined_func_name = super::writer::RAY_DESC_FUNCTION;
+            writeln!(
+                self.out,
+                "RayDesc {defined_func_name}({struct_name} arg) {{
+    RayDesc ret = (RayDesc)0;
+    ret.Origin = arg.origin;
+    ret.TMin = arg.tmin;
+    ret.Direction = arg.dir;
+    ret.TMax = arg.tmax;
+    return ret;
+}}"
+            )?;
+            writeln!(self.out)?;
+        }
+
+        if let Some(ray_intersection) = module.special_types.ray_intersection {
+            self.write_ray_intersection_function(ray_intersection, true)?;
+            self.write_ray_intersection_function(ray_intersection, false)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a function filling a `RayIntersection` struct from the committed
+    /// or candidate intersection of a ray query.
+    ///
+    /// The `kind` member follows [`RayIntersectionType`] for candidates and
+    /// the committed intersection encoding otherwise, which matches
+    /// `COMMITTED_STATUS`.
+    ///
+    /// [`RayIntersectionType`]: crate::back::RayIntersectionType
+    fn write_ray_intersection_function(
+        &mut self,
+        ray_intersection: Handle<crate::Type>,
+        committed: bool,
+    ) -> BackendResult {
+        let struct_name = &self.names[&NameKey::Type(ray_intersection)];
+        let (defined_func_name, which) = if committed {
+            (super::writer::COMMITTED_INTERSECTION_FUNCTION, "Committed")
+        } else {
+            (super::writer::CANDIDATE_INTERSECTION_FUNCTION, "Candidate")
+        };
+        let ray_query = super::writer::RAY_QUERY_TYPE;
+
+        writeln!(
+            self.out,
+            "{struct_name} {defined_func_name}({ray_query} rq) {{"
+        )?;
+        writeln!(self.out, "    {struct_name} ret = ({struct_name})0;")?;
+        // Candidates always describe a hit, only committed intersections can be empty.
+        let (is_triangle, level) = if committed {
+            writeln!(self.out, "    ret.kind = (uint)rq.CommittedStatus();")?;
+            writeln!(
+                self.out,
+                "    if (rq.CommittedStatus() != COMMITTED_NOTHING) {{"
+            )?;
+            writeln!(self.out, "        ret.t = rq.CommittedRayT();")?;
+            ("rq.CommittedStatus() == COMMITTED_TRIANGLE_HIT", "        ")
+        } else {
+            let is_triangle = "rq.CandidateType() == CANDIDATE_NON_OPAQUE_TRIANGLE";
+            writeln!(
+                self.out,
+                "    ret.kind = {is_triangle} ? {}u : {}u;",
+                crate::back::RayIntersectionType::Triangle as u32,
+                crate::back::RayIntersectionType::BoundingBox as u32,
+            )?;
+            (is_triangle, "    ")
+        };
+        for (field, getter) in [
+            ("instance_custom_index", "InstanceID"),
+            ("instance_id", "InstanceIndex"),
+            ("sbt_record_offset", "InstanceContributionToHitGroupIndex"),
+            ("geometry_index", "GeometryIndex"),
+            ("primitive_index", "PrimitiveIndex"),
+            ("object_to_world", "ObjectToWorld4x3"),
+            ("world_to_object", "WorldToObject4x3"),
+        ] {
+            writeln!(self.out, "{level}ret.{field} = rq.{which}{getter}();")?;
+        }
+        writeln!(self.out, "{level}if ({is_triangle}) {{")?;
+        if !committed {
+            writeln!(self.out, "{level}    ret.t = rq.CandidateTriangleRayT();")?;
+        }
+        writeln!(
+            self.out,
+            "{level}    ret.barycentrics = rq.{which}TriangleBarycentrics();"
+        )?;
+        writeln!(
+            self.out,
+            "{level}    ret.front_face = rq.{which}TriangleFrontFace();"
+        )?;
+        writeln!(self.out, "{level}}}")?;
+        if committed {
+            writeln!(self.out, "    }}")?;
+        }
+        writeln!(self.out, "    return ret;")?;
+        writeln!(self.out, "}}")?;
+        writeln!(self.out)?;
+
         Ok(())
     }
 
@@ -1033,6 +1146,34 @@ impl<W: Write> super::Writer<'_, W> {
         self.write_wrapped_zero_value_functions(module, func_ctx.expressions)?;
 
         for (handle, _) in func_ctx.expressions.iter() {
+            // Bounds checks on runtime-sized arrays need the buffer's length.
+            if let crate::Expression::Access { base, .. }
+            | crate::Expression::AccessIndex { base, .. } = func_ctx.expressions[handle]
+            {
+                if self.options.bounds_check_policies.buffer
+                    != crate::proc::BoundsCheckPolicy::Unchecked
+                {
+                    if let crate::TypeInner::Pointer { base: ty, .. } =
+                        *func_ctx.resolve_type(base, &module.types)
+                    {
+                        if let crate::TypeInner::Array {
+                            size: crate::ArraySize::Dynamic,
+                            ..
+                        } = module.types[ty].inner
+                        {
+                            let var_handle =
+                                super::writer::dynamic_array_global(func_ctx.expressions, base);
+                            let wal = WrappedArrayLength::for_global(
+                                &module.global_variables[var_handle],
+                            );
+                            if self.wrapped.array_lengths.insert(wal) {
+                                self.write_wrapped_array_length_function(wal)?;
+                            }
+                        }
+                    }
+                }
+            }
+
             match func_ctx.expressions[handle] {
                 crate::Expression::ArrayLength(expr) => {
                     let global_expr = match func_ctx.expressions[expr] {
@@ -1050,13 +1191,7 @@ impl<W: Write> super::Writer<'_, W> {
                             )))
                         }
                     };
-                    let storage_access = match global_var.space {
-                        crate::AddressSpace::Storage { access } => access,
-                        _ => crate::StorageAccess::default(),
-                    };
-                    let wal = WrappedArrayLength {
-                        writable: storage_access.contains(crate::StorageAccess::STORE),
-                    };
+                    let wal = WrappedArrayLength::for_global(global_var);
 
                     if self.wrapped.array_lengths.insert(wal) {
                         self.write_wrapped_array_length_function(wal)?;
@@ -1078,7 +1213,49 @@ impl<W: Write> super::Writer<'_, W> {
                     };
 
                     if self.wrapped.image_queries.insert(wiq) {
-                        self.write_wrapped_image_query_function(module, wiq, handle, func_ctx)?;
+                        self.write_wrapped_image_query_function(wiq)?;
+                    }
+                }
+                // Bounds-checked image loads query the image's size.
+                crate::Expression::ImageLoad {
+                    image,
+                    array_index,
+                    sample,
+                    level,
+                    ..
+                } if self.options.bounds_check_policies.image_load
+                    != crate::proc::BoundsCheckPolicy::Unchecked =>
+                {
+                    let crate::TypeInner::Image {
+                        dim,
+                        arrayed,
+                        class,
+                    } = *func_ctx.resolve_type(image, &module.types)
+                    else {
+                        unreachable!("we only load from images");
+                    };
+
+                    let size_query = if level.is_some() {
+                        ImageQuery::SizeLevel
+                    } else {
+                        ImageQuery::Size
+                    };
+                    let queries = [
+                        Some(size_query),
+                        level.map(|_| ImageQuery::NumLevels),
+                        array_index.map(|_| ImageQuery::NumLayers),
+                        sample.map(|_| ImageQuery::NumSamples),
+                    ];
+                    for query in queries.into_iter().flatten() {
+                        let wiq = WrappedImageQuery {
+                            dim,
+                            arrayed,
+                            class,
+                            query,
+                        };
+                        if self.wrapped.image_queries.insert(wiq) {
+                            self.write_wrapped_image_query_function(wiq)?;
+                        }
                     }
                 }
                 // Write `WrappedConstructor` for structs that are loaded from `AddressSpace::Storage`
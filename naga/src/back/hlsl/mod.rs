@@ -163,6 +163,8 @@ impl crate::ShaderStage {
             Self::Vertex => "vs",
             Self::Fragment => "ps",
             Self::Compute => "cs",
+            Self::Task => "as",
+            Self::Mesh => "ms",
         }
     }
 }
@@ -207,8 +209,8 @@ pub struct Options {
     pub push_constants_target: Option<BindTarget>,
     /// Should workgroup variables be zero initialized (by polyfilling)?
     pub zero_initialize_workgroup_memory: bool,
-    /// Should we restrict indexing of vectors, matrices and arrays?
-    pub restrict_indexing: bool,
+    /// How should the generated code handle out-of-bounds accesses?
+    pub bounds_check_policies: crate::proc::BoundsCheckPolicies,
 }
 
 impl Default for Options {
@@ -220,7 +222,12 @@ impl Default for Options {
             special_constants_binding: None,
             push_constants_target: None,
             zero_initialize_workgroup_memory: true,
-            restrict_indexing: true,
+            bounds_check_policies: crate::proc::BoundsCheckPolicies {
+                index: crate::proc::BoundsCheckPolicy::Restrict,
+                buffer: crate::proc::BoundsCheckPolicy::Restrict,
+                image_load: crate::proc::BoundsCheckPolicy::Unchecked,
+                binding_array: crate::proc::BoundsCheckPolicy::Unchecked,
+            },
         }
     }
 }
@@ -266,6 +273,8 @@ pub enum Error {
     Custom(String),
     #[error("overrides should not be present at this stage")]
     Override,
+    #[error("task and mesh shaders are not supported prior to shader model 6.5")]
+    UnsupportedMeshShader,
 }
 
 #[derive(Default)]
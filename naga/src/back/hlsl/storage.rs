@@ -57,7 +57,7 @@ correct type if necessary.
 
 use super::{super::FunctionCtx, BackendResult, Error};
 use crate::{
-    proc::{Alignment, NameKey, TypeResolution},
+    proc::{index, Alignment, NameKey, TypeResolution},
     Handle,
 };
 
@@ -88,6 +88,19 @@ pub(super) enum SubAccess {
         value: Handle<crate::Expression>,
         stride: u32,
     },
+
+    /// Like [`Index`], but first clamp `index` to the bounds of the
+    /// array, vector, or matrix `base`, whose length is `length`. This
+    /// is used for the [`Restrict`] bounds check policy.
+    ///
+    /// [`Index`]: SubAccess::Index
+    /// [`Restrict`]: crate::proc::BoundsCheckPolicy::Restrict
+    RestrictedIndex {
+        base: Handle<crate::Expression>,
+        index: index::GuardedIndex,
+        length: index::IndexableLength,
+        stride: u32,
+    },
 }
 
 pub(super) enum StoreValue {
@@ -126,6 +139,35 @@ impl<W: fmt::Write> super::Writer<'_, W> {
                     self.write_expr(module, value, func_ctx)?;
                     write!(self.out, "*{stride}")?;
                 }
+                SubAccess::RestrictedIndex {
+                    base,
+                    index,
+                    ref length,
+                    stride,
+                } => {
+                    write!(self.out, "min(")?;
+                    match index {
+                        index::GuardedIndex::Known(value) => write!(self.out, "{value}u")?,
+                        index::GuardedIndex::Expression(value) => {
+                            write!(self.out, "uint(")?;
+                            self.write_expr(module, value, func_ctx)?;
+                            write!(self.out, ")")?;
+                        }
+                    }
+                    write!(self.out, ", ")?;
+                    match *length {
+                        index::IndexableLength::Known(length) => {
+                            write!(self.out, "{}u", length - 1)?
+                        }
+                        index::IndexableLength::Dynamic => {
+                            let var_handle =
+                                super::writer::dynamic_array_global(func_ctx.expressions, base);
+                            self.write_dynamic_array_length(module, var_handle)?;
+                            write!(self.out, " - 1u")?;
+                        }
+                    }
+                    write!(self.out, ")*{stride}")?;
+                }
             }
         }
         Ok(())
@@ -490,6 +532,10 @@ impl<W: fmt::Write> super::Writer<'_, W> {
         }
         self.temp_access_chain.clear();
 
+        // Storage accesses only ever use the `buffer` policy.
+        let restrict =
+            self.options.bounds_check_policies.buffer == index::BoundsCheckPolicy::Restrict;
+
         loop {
             let (next_expr, access_index) = match func_ctx.expressions[cur_expr] {
                 crate::Expression::GlobalVariable(handle) => return Ok(handle),
@@ -522,12 +568,45 @@ impl<W: fmt::Write> super::Writer<'_, W> {
                 _ => unreachable!(),
             };
 
+            // Decide whether this index needs to be clamped to fall within range.
+            let restriction_needed = |index| {
+                if restrict {
+                    index::access_needs_check(
+                        next_expr,
+                        index,
+                        module,
+                        func_ctx.expressions,
+                        func_ctx.info,
+                    )
+                } else {
+                    None
+                }
+            };
+
             let sub = match (parent, access_index) {
                 (Parent::Array { stride }, AccessIndex::Expression(value)) => {
-                    SubAccess::Index { value, stride }
+                    let index = index::GuardedIndex::Expression(value);
+                    match restriction_needed(index) {
+                        Some(length) => SubAccess::RestrictedIndex {
+                            base: next_expr,
+                            index,
+                            length,
+                            stride,
+                        },
+                        None => SubAccess::Index { value, stride },
+                    }
                 }
-                (Parent::Array { stride }, AccessIndex::Constant(index)) => {
-                    SubAccess::Offset(stride * index)
+                (Parent::Array { stride }, AccessIndex::Constant(value)) => {
+                    let index = index::GuardedIndex::Known(value);
+                    match restriction_needed(index) {
+                        Some(length) => SubAccess::RestrictedIndex {
+                            base: next_expr,
+                            index,
+                            length,
+                            stride,
+                        },
+                        None => SubAccess::Offset(stride * value),
+                    }
                 }
                 (Parent::Struct(members), AccessIndex::Constant(index)) => {
                     SubAccess::Offset(members[index as usize].offset)
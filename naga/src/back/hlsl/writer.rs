@@ -1,10 +1,10 @@
 use super::{
     help::{
-        WrappedArrayLength, WrappedConstructor, WrappedImageQuery, WrappedStructMatrixAccess,
-        WrappedZeroValue,
+        ImageQuery, WrappedArrayLength, WrappedConstructor, WrappedImageQuery,
+        WrappedStructMatrixAccess, WrappedZeroValue,
     },
     storage::StoreValue,
-    BackendResult, Error, FragmentEntryPoint, Options,
+    BackendResult, Error, FragmentEntryPoint, Options, ShaderModel,
 };
 use crate::{
     back::{self, Baked},
@@ -24,6 +24,10 @@ pub(crate) const MODF_FUNCTION: &str = "naga_modf";
 pub(crate) const FREXP_FUNCTION: &str = "naga_frexp";
 pub(crate) const EXTRACT_BITS_FUNCTION: &str = "naga_extractBits";
 pub(crate) const INSERT_BITS_FUNCTION: &str = "naga_insertBits";
+pub(crate) const RAY_DESC_FUNCTION: &str = "naga_ray_desc_from";
+pub(crate) const COMMITTED_INTERSECTION_FUNCTION: &str = "naga_committed_intersection";
+pub(crate) const CANDIDATE_INTERSECTION_FUNCTION: &str = "naga_candidate_intersection";
+pub(crate) const RAY_QUERY_TYPE: &str = "RayQuery<RAY_FLAG_NONE>";
 
 struct EpStructMember {
     name: String,
@@ -56,6 +60,23 @@ pub(super) struct EntryPointInterface {
     /// The `EntryPointBinding::members` array is sorted by binding,
     /// So that we can walk it in `Statement::Return` handler.
     output: Option<EntryPointBinding>,
+    /// The output arrays of a mesh shader.
+    mesh_output: Option<MeshOutputInterface>,
+    /// The name of the `groupshared` payload a task shader passes to
+    /// `DispatchMesh`.
+    task_payload: Option<String>,
+}
+
+/// The `out vertices`, `out indices` and `out primitives` arrays of a mesh
+/// shader entry point.
+///
+/// The vertex and primitive structs are flattened like
+/// [`EntryPointInterface::output`], leaving out the primitive indices.
+struct MeshOutputInterface {
+    vertices: EntryPointBinding,
+    indices_name: String,
+    /// `None` if the primitives carry no data besides their indices.
+    primitives: Option<EntryPointBinding>,
 }
 
 #[derive(Clone, Eq, PartialEq, PartialOrd, Ord)]
@@ -81,6 +102,28 @@ enum Io {
     Output,
 }
 
+/// A check that the [`ReadZeroSkipWrite`] bounds check policy requires
+/// before `base` may be indexed by `index`: `index` must be less than
+/// `length`.
+///
+/// [`ReadZeroSkipWrite`]: index::BoundsCheckPolicy::ReadZeroSkipWrite
+struct BoundsCheck {
+    base: Handle<crate::Expression>,
+    index: index::GuardedIndex,
+    length: index::IndexableLength,
+}
+
+/// The operands of an [`ImageLoad`] expression that select a texel.
+///
+/// [`ImageLoad`]: crate::Expression::ImageLoad
+#[derive(Clone, Copy)]
+struct TexelAddress {
+    coordinate: Handle<crate::Expression>,
+    array_index: Option<Handle<crate::Expression>>,
+    sample: Option<Handle<crate::Expression>>,
+    level: Option<Handle<crate::Expression>>,
+}
+
 const fn is_subgroup_builtin_binding(binding: &Option<crate::Binding>) -> bool {
     let &Some(crate::Binding::BuiltIn(builtin)) = binding else {
         return false;
@@ -94,6 +137,21 @@ const fn is_subgroup_builtin_binding(binding: &Option<crate::Binding>) -> bool {
     )
 }
 
+/// Returns true if `binding` is a mesh shader built-in that is not written
+/// through a semantic, but passed to `DispatchMesh` or written to the
+/// `indices` array.
+const fn is_mesh_intrinsic_builtin_binding(binding: &Option<crate::Binding>) -> bool {
+    matches!(
+        *binding,
+        Some(crate::Binding::BuiltIn(
+            crate::BuiltIn::MeshTaskSize
+                | crate::BuiltIn::PointIndex
+                | crate::BuiltIn::LineIndices
+                | crate::BuiltIn::TriangleIndices
+        ))
+    )
+}
+
 impl<'a, W: fmt::Write> super::Writer<'a, W> {
     pub fn new(out: W, options: &'a Options) -> Self {
         Self {
@@ -139,6 +197,12 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
     ) {
         use crate::Expression;
         self.need_bake_expressions.clear();
+
+        // Indices guarded by `ReadZeroSkipWrite` checks are used twice.
+        let policies = self.options.bounds_check_policies;
+        self.need_bake_expressions
+            .extend(index::find_checked_indexes(module, func, info, policies).iter());
+
         for (fun_handle, expr) in func.expressions.iter() {
             let expr_info = &info[fun_handle];
             let min_ref_count = func.expressions[fun_handle].bake_ref_count();
@@ -146,7 +210,42 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 self.need_bake_expressions.insert(fun_handle);
             }
 
-            if let Expression::Math { fun, arg, .. } = *expr {
+            match *expr {
+                // Accesses to values that need `ReadZeroSkipWrite` checks are
+                // written as statements by `write_named_expr`.
+                Expression::Access { base, index } => {
+                    let base_inner = info[base].ty.inner_with(&module.types);
+                    if expr_info
+                        .ty
+                        .inner_with(&module.types)
+                        .pointer_space()
+                        .is_none()
+                        && !matches!(*base_inner, TypeInner::BindingArray { .. })
+                        && policies.choose_policy(base, &module.types, info)
+                            == index::BoundsCheckPolicy::ReadZeroSkipWrite
+                        && index::access_needs_check(
+                            base,
+                            index::GuardedIndex::Expression(index),
+                            module,
+                            &func.expressions,
+                            info,
+                        )
+                        .is_some()
+                    {
+                        self.need_bake_expressions.insert(fun_handle);
+                    }
+                }
+                // The `Restrict` policy clamps the level of detail once for
+                // the coordinates' bounds, and once for the level itself.
+                Expression::ImageLoad {
+                    level: Some(level), ..
+                } if policies.image_load == index::BoundsCheckPolicy::Restrict => {
+                    self.need_bake_expressions.insert(level);
+                }
+                _ => {}
+            }
+
+            if let Expression::Math { fun, arg, arg1, .. } = *expr {
                 match fun {
                     crate::MathFunction::Asinh
                     | crate::MathFunction::Acosh
@@ -167,6 +266,12 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     | crate::MathFunction::Pack4xU8 => {
                         self.need_bake_expressions.insert(arg);
                     }
+                    crate::MathFunction::Dot4I8Packed | crate::MathFunction::Dot4U8Packed
+                        if self.options.shader_model < ShaderModel::V6_4 =>
+                    {
+                        self.need_bake_expressions.insert(arg);
+                        self.need_bake_expressions.insert(arg1.unwrap());
+                    }
                     crate::MathFunction::CountLeadingZeros => {
                         let inner = info[fun_handle].ty.inner_with(&module.types);
                         if let Some(ScalarKind::Sint) = inner.scalar_kind() {
@@ -209,6 +314,32 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             return Err(Error::Override);
         }
 
+        if self.options.shader_model < ShaderModel::V6_5
+            && module
+                .entry_points
+                .iter()
+                .any(|ep| matches!(ep.stage, ShaderStage::Task | ShaderStage::Mesh))
+        {
+            return Err(Error::UnsupportedMeshShader);
+        }
+
+        // A mesh shader's payload argument shadows the `groupshared` global
+        // written for task shaders, which only works for accesses in the
+        // entry point itself.
+        for ep in module.entry_points.iter() {
+            if let (ShaderStage::Mesh, Some(payload)) = (ep.stage, ep.task_payload) {
+                if module
+                    .functions
+                    .iter()
+                    .any(|(handle, _)| !module_info[handle][payload].is_empty())
+                {
+                    return Err(Error::Unimplemented(
+                        "task payload access outside of the mesh shader entry point".into(),
+                    ));
+                }
+            }
+        }
+
         self.reset(module);
 
         // Write special constants, if needed
@@ -304,13 +435,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         // Write all entry points wrapped structs
         for (index, ep) in module.entry_points.iter().enumerate() {
             let ep_name = self.names[&NameKey::EntryPoint(index as u16)].clone();
-            let ep_io = self.write_ep_interface(
-                module,
-                &ep.function,
-                ep.stage,
-                &ep_name,
-                fragment_entry_point,
-            )?;
+            let ep_io = self.write_ep_interface(module, ep, &ep_name, fragment_entry_point)?;
             self.entry_point_io.push(ep_io);
         }
 
@@ -392,7 +517,19 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
 
             self.write_wrapped_functions(module, &ctx)?;
 
-            if ep.stage == ShaderStage::Compute {
+            if let Some(ref mesh_info) = ep.mesh_info {
+                let topology = match mesh_info.topology {
+                    crate::MeshOutputTopology::Points => "point",
+                    crate::MeshOutputTopology::Lines => "line",
+                    crate::MeshOutputTopology::Triangles => "triangle",
+                };
+                writeln!(self.out, "[outputtopology(\"{topology}\")]")?;
+            }
+
+            if matches!(
+                ep.stage,
+                ShaderStage::Compute | ShaderStage::Task | ShaderStage::Mesh
+            ) {
                 // HLSL is calling workgroup size "num threads"
                 let num_threads = ep.workgroup_size;
                 writeln!(
@@ -420,6 +557,12 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             crate::Binding::BuiltIn(crate::BuiltIn::Position { invariant: true }) => {
                 write!(self.out, "precise ")?;
             }
+            crate::Binding::Location {
+                per_primitive: true,
+                ..
+            } => {
+                write!(self.out, "nointerpolation ")?;
+            }
             crate::Binding::Location {
                 interpolation,
                 sampling,
@@ -451,7 +594,10 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         stage: Option<(ShaderStage, Io)>,
     ) -> BackendResult {
         match *binding {
-            Some(crate::Binding::BuiltIn(builtin)) if !is_subgroup_builtin_binding(binding) => {
+            Some(crate::Binding::BuiltIn(builtin))
+                if !is_subgroup_builtin_binding(binding)
+                    && !is_mesh_intrinsic_builtin_binding(binding) =>
+            {
                 let builtin_str = builtin.to_hlsl_str()?;
                 write!(self.out, " : {builtin_str}")?;
             }
@@ -506,6 +652,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             }
             self.write_type(module, m.ty)?;
             write!(self.out, " {}", &m.name)?;
+            if let TypeInner::Array { base, size, .. } = module.types[m.ty].inner {
+                self.write_array_size(module, base, size)?;
+            }
             self.write_semantic(&m.binding, Some(shader_stage))?;
             writeln!(self.out, ";")?;
         }
@@ -671,11 +820,12 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
     fn write_ep_interface(
         &mut self,
         module: &Module,
-        func: &crate::Function,
-        stage: ShaderStage,
+        ep: &crate::EntryPoint,
         ep_name: &str,
         frag_ep: Option<&FragmentEntryPoint<'_>>,
     ) -> Result<EntryPointInterface, Error> {
+        let func = &ep.function;
+        let stage = ep.stage;
         Ok(EntryPointInterface {
             input: if !func.arguments.is_empty()
                 && (stage == ShaderStage::Fragment
@@ -694,9 +844,92 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 }
                 _ => None,
             },
+            mesh_output: match ep.mesh_info {
+                Some(ref mesh_info) => {
+                    Some(self.write_mesh_output_interface(module, mesh_info, ep_name)?)
+                }
+                None => None,
+            },
+            task_payload: match stage {
+                ShaderStage::Task => Some(self.write_task_payload(ep, ep_name)?),
+                _ => None,
+            },
+        })
+    }
+
+    /// Flatten the vertex and primitive types of a mesh shader into structs
+    /// for its `out vertices` and `out primitives` arrays, leaving out the
+    /// primitive indices.
+    fn write_mesh_output_interface(
+        &mut self,
+        module: &Module,
+        mesh_info: &crate::MeshStageInfo,
+        ep_name: &str,
+    ) -> Result<MeshOutputInterface, Error> {
+        let mut flatten = |ty: Handle<crate::Type>| -> Vec<EpStructMember> {
+            let TypeInner::Struct { ref members, .. } = module.types[ty].inner else {
+                return Vec::new();
+            };
+            members
+                .iter()
+                .enumerate()
+                .filter(|&(_, member)| !is_mesh_intrinsic_builtin_binding(&member.binding))
+                .map(|(index, member)| EpStructMember {
+                    name: self.namer.call_or(&member.name, "member"),
+                    ty: member.ty,
+                    binding: member.binding.clone(),
+                    index: index as u32,
+                })
+                .collect()
+        };
+        let vertex_members = flatten(mesh_info.vertex_output_type);
+        let primitive_members = flatten(mesh_info.primitive_output_type);
+
+        let vertices = self.write_interface_struct(
+            module,
+            (ShaderStage::Mesh, Io::Output),
+            format!("MeshVertexOutput_{ep_name}"),
+            vertex_members,
+        )?;
+        let primitives = if primitive_members.is_empty() {
+            None
+        } else {
+            Some(self.write_interface_struct(
+                module,
+                (ShaderStage::Mesh, Io::Output),
+                format!("MeshPrimitiveOutput_{ep_name}"),
+                primitive_members,
+            )?)
+        };
+        Ok(MeshOutputInterface {
+            vertices,
+            indices_name: self.namer.call("indices"),
+            primitives,
         })
     }
 
+    /// Return the name of the payload a task shader passes to `DispatchMesh`.
+    ///
+    /// `DispatchMesh` always takes a payload, so if the entry point doesn't
+    /// declare one, write a placeholder.
+    fn write_task_payload(
+        &mut self,
+        ep: &crate::EntryPoint,
+        ep_name: &str,
+    ) -> Result<String, Error> {
+        if let Some(payload) = ep.task_payload {
+            return Ok(self.names[&NameKey::GlobalVariable(payload)].clone());
+        }
+        let struct_name = self.namer.call(&format!("TaskPayload_{ep_name}"));
+        let name = self.namer.call(&struct_name.to_lowercase());
+        writeln!(self.out, "struct {struct_name} {{")?;
+        writeln!(self.out, "{}uint _unused;", back::INDENT)?;
+        writeln!(self.out, "}};")?;
+        writeln!(self.out, "groupshared {struct_name} {name};")?;
+        writeln!(self.out)?;
+        Ok(name)
+    }
+
     fn write_ep_argument_initialization(
         &mut self,
         ep: &crate::EntryPoint,
@@ -818,7 +1051,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 self.write_type(module, global.ty)?;
                 ""
             }
-            crate::AddressSpace::WorkGroup => {
+            crate::AddressSpace::WorkGroup | crate::AddressSpace::TaskPayload => {
                 write!(self.out, "groupshared ")?;
                 self.write_type(module, global.ty)?;
                 ""
@@ -1025,12 +1258,16 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         for (index, member) in members.iter().enumerate() {
             if member.binding.is_none() && member.offset > last_offset {
                 // using int as padding should work as long as the backend
-                // doesn't support a type that's less than 4 bytes in size
-                // (Error::UnsupportedScalar catches this)
+                // doesn't support a type that's less than 2 bytes in size
+                // (Error::UnsupportedScalar catches this); any leftover
+                // 2 bytes come from `half` members and are padded with a `half`
                 let padding = (member.offset - last_offset) / 4;
                 for i in 0..padding {
                     writeln!(self.out, "{}int _pad{}_{};", back::INDENT, index, i)?;
                 }
+                if (member.offset - last_offset) % 4 == 2 {
+                    writeln!(self.out, "{}half _pad{}_{};", back::INDENT, index, padding)?;
+                }
             }
             let ty_inner = &module.types[member.ty].inner;
             last_offset = member.offset + ty_inner.size_hlsl(module.to_ctx());
@@ -1104,6 +1341,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             for i in 0..padding {
                 writeln!(self.out, "{}int _end_pad_{};", back::INDENT, i)?;
             }
+            if (span - last_offset) % 4 == 2 {
+                writeln!(self.out, "{}half _end_pad_{};", back::INDENT, padding)?;
+            }
         }
 
         writeln!(self.out, "}};")?;
@@ -1217,6 +1457,12 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             TypeInner::Array { base, size, .. } | TypeInner::BindingArray { base, size } => {
                 self.write_array_size(module, base, size)?;
             }
+            TypeInner::AccelerationStructure => {
+                write!(self.out, "RaytracingAccelerationStructure")?;
+            }
+            TypeInner::RayQuery => {
+                write!(self.out, "{RAY_QUERY_TYPE}")?;
+            }
             _ => return Err(Error::Unimplemented(format!("write_value_type {inner:?}"))),
         }
 
@@ -1253,7 +1499,13 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         }
 
         // Write return type
-        if let Some(ref result) = func.result {
+        let is_task_entry_point = match func_ctx.ty {
+            back::FunctionType::EntryPoint(index) => {
+                module.entry_points[index as usize].stage == ShaderStage::Task
+            }
+            back::FunctionType::Function(_) => false,
+        };
+        if let Some(result) = func.result.as_ref().filter(|_| !is_task_entry_point) {
             match func_ctx.ty {
                 back::FunctionType::Function(_) => {
                     self.write_type(module, result.ty)?;
@@ -1327,13 +1579,18 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                         self.write_semantic(&arg.binding, Some((stage, Io::Input)))?;
                     }
                 }
+                let mut has_arguments = self.entry_point_io[ep_index as usize].input.is_some()
+                    || !func.arguments.is_empty();
                 if need_workgroup_variables_initialization {
-                    if self.entry_point_io[ep_index as usize].input.is_some()
-                        || !func.arguments.is_empty()
-                    {
+                    if has_arguments {
                         write!(self.out, ", ")?;
                     }
                     write!(self.out, "uint3 __local_invocation_id : SV_GroupThreadID")?;
+                    has_arguments = true;
+                }
+                let ep = &module.entry_points[ep_index as usize];
+                if let Some(ref mesh_info) = ep.mesh_info {
+                    self.write_mesh_arguments(module, ep_index, ep, mesh_info, has_arguments)?;
                 }
             }
         }
@@ -1344,7 +1601,9 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         if let back::FunctionType::EntryPoint(index) = func_ctx.ty {
             let stage = module.entry_points[index as usize].stage;
             if let Some(crate::FunctionResult { ref binding, .. }) = func.result {
-                self.write_semantic(binding, Some((stage, Io::Output)))?;
+                if stage != ShaderStage::Task {
+                    self.write_semantic(binding, Some((stage, Io::Output)))?;
+                }
             }
         }
 
@@ -1374,6 +1633,12 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 self.write_array_size(module, base, size)?;
             }
 
+            // Ray queries are opaque objects that can't be initialized
+            if let TypeInner::RayQuery = module.types[local.ty].inner {
+                writeln!(self.out, ";")?;
+                continue;
+            }
+
             write!(self.out, " = ")?;
             // Write the local initializer if needed
             if let Some(init) = local.init {
@@ -1404,6 +1669,58 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         Ok(())
     }
 
+    /// Write the payload and output array arguments of a mesh shader entry
+    /// point.
+    fn write_mesh_arguments(
+        &mut self,
+        module: &Module,
+        ep_index: u16,
+        ep: &crate::EntryPoint,
+        mesh_info: &crate::MeshStageInfo,
+        has_arguments: bool,
+    ) -> BackendResult {
+        let separator = if has_arguments { ", " } else { "" };
+        if let Some(payload) = ep.task_payload {
+            let var = &module.global_variables[payload];
+            write!(self.out, "{separator}in payload ")?;
+            self.write_type(module, var.ty)?;
+            write!(
+                self.out,
+                " {}, ",
+                self.names[&NameKey::GlobalVariable(payload)]
+            )?;
+        } else {
+            write!(self.out, "{separator}")?;
+        }
+
+        let mesh_output = self.entry_point_io[ep_index as usize]
+            .mesh_output
+            .as_ref()
+            .unwrap();
+        let indices_ty = match mesh_info.topology {
+            crate::MeshOutputTopology::Points => "uint",
+            crate::MeshOutputTopology::Lines => "uint2",
+            crate::MeshOutputTopology::Triangles => "uint3",
+        };
+        write!(
+            self.out,
+            "out vertices {} {}[{}], out indices {indices_ty} {}[{}]",
+            mesh_output.vertices.ty_name,
+            mesh_output.vertices.arg_name,
+            mesh_info.max_vertices,
+            mesh_output.indices_name,
+            mesh_info.max_primitives,
+        )?;
+        if let Some(ref primitives) = mesh_output.primitives {
+            write!(
+                self.out,
+                ", out primitives {} {}[{}]",
+                primitives.ty_name, primitives.arg_name, mesh_info.max_primitives,
+            )?;
+        }
+        Ok(())
+    }
+
     fn need_workgroup_variables_initialization(
         &mut self,
         func_ctx: &back::FunctionCtx,
@@ -1623,8 +1940,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     };
 
                     if let Some(name) = expr_name {
-                        write!(self.out, "{level}")?;
-                        self.write_named_expr(module, handle, name, handle, func_ctx)?;
+                        self.write_named_expr(module, handle, name, handle, func_ctx, level)?;
                     }
                 }
             }
@@ -1674,6 +1990,14 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 writeln!(self.out, "{level}return;")?;
             }
             Statement::Return { value: Some(expr) } => {
+                if let back::FunctionType::EntryPoint(index) = func_ctx.ty {
+                    if let Some(ref payload) = self.entry_point_io[index as usize].task_payload {
+                        let payload = payload.clone();
+                        let ep = &module.entry_points[index as usize];
+                        return self.write_task_return(module, ep, expr, &payload, func_ctx, level);
+                    }
+                }
+
                 let base_ty_res = &func_ctx.info[expr].ty;
                 let mut resolved = base_ty_res.inner_with(&module.types);
                 if let TypeInner::Pointer { base, space: _ } = *resolved {
@@ -1724,306 +2048,15 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 }
             }
             Statement::Store { pointer, value } => {
-                let ty_inner = func_ctx.resolve_type(pointer, &module.types);
-                if let Some(crate::AddressSpace::Storage { .. }) = ty_inner.pointer_space() {
-                    let var_handle = self.fill_access_chain(module, pointer, func_ctx)?;
-                    self.write_storage_store(
-                        module,
-                        var_handle,
-                        StoreValue::Expression(value),
-                        func_ctx,
-                        level,
-                    )?;
+                let checks = self.bounds_checks(module, pointer, func_ctx);
+                if checks.is_empty() {
+                    self.write_unchecked_store(module, pointer, value, func_ctx, level)?;
                 } else {
-                    // We treat matrices of the form `matCx2` as a sequence of C `vec2`s.
-                    // See the module-level block comment in mod.rs for details.
-                    //
-                    // We handle matrix Stores here directly (including sub accesses for Vectors and Scalars).
-                    // Loads are handled by `Expression::AccessIndex` (since sub accesses work fine for Loads).
-                    struct MatrixAccess {
-                        base: Handle<crate::Expression>,
-                        index: u32,
-                    }
-                    enum Index {
-                        Expression(Handle<crate::Expression>),
-                        Static(u32),
-                    }
-
-                    let get_members = |expr: Handle<crate::Expression>| {
-                        let resolved = func_ctx.resolve_type(expr, &module.types);
-                        match *resolved {
-                            TypeInner::Pointer { base, .. } => match module.types[base].inner {
-                                TypeInner::Struct { ref members, .. } => Some(members),
-                                _ => None,
-                            },
-                            _ => None,
-                        }
-                    };
-
-                    let mut matrix = None;
-                    let mut vector = None;
-                    let mut scalar = None;
-
-                    let mut current_expr = pointer;
-                    for _ in 0..3 {
-                        let resolved = func_ctx.resolve_type(current_expr, &module.types);
-
-                        match (resolved, &func_ctx.expressions[current_expr]) {
-                            (
-                                &TypeInner::Pointer { base: ty, .. },
-                                &crate::Expression::AccessIndex { base, index },
-                            ) if matches!(
-                                module.types[ty].inner,
-                                TypeInner::Matrix {
-                                    rows: crate::VectorSize::Bi,
-                                    ..
-                                }
-                            ) && get_members(base)
-                                .map(|members| members[index as usize].binding.is_none())
-                                == Some(true) =>
-                            {
-                                matrix = Some(MatrixAccess { base, index });
-                                break;
-                            }
-                            (
-                                &TypeInner::ValuePointer {
-                                    size: Some(crate::VectorSize::Bi),
-                                    ..
-                                },
-                                &crate::Expression::Access { base, index },
-                            ) => {
-                                vector = Some(Index::Expression(index));
-                                current_expr = base;
-                            }
-                            (
-                                &TypeInner::ValuePointer {
-                                    size: Some(crate::VectorSize::Bi),
-                                    ..
-                                },
-                                &crate::Expression::AccessIndex { base, index },
-                            ) => {
-                                vector = Some(Index::Static(index));
-                                current_expr = base;
-                            }
-                            (
-                                &TypeInner::ValuePointer { size: None, .. },
-                                &crate::Expression::Access { base, index },
-                            ) => {
-                                scalar = Some(Index::Expression(index));
-                                current_expr = base;
-                            }
-                            (
-                                &TypeInner::ValuePointer { size: None, .. },
-                                &crate::Expression::AccessIndex { base, index },
-                            ) => {
-                                scalar = Some(Index::Static(index));
-                                current_expr = base;
-                            }
-                            _ => break,
-                        }
-                    }
-
-                    write!(self.out, "{level}")?;
-
-                    if let Some(MatrixAccess { index, base }) = matrix {
-                        let base_ty_res = &func_ctx.info[base].ty;
-                        let resolved = base_ty_res.inner_with(&module.types);
-                        let ty = match *resolved {
-                            TypeInner::Pointer { base, .. } => base,
-                            _ => base_ty_res.handle().unwrap(),
-                        };
-
-                        if let Some(Index::Static(vec_index)) = vector {
-                            self.write_expr(module, base, func_ctx)?;
-                            write!(
-                                self.out,
-                                ".{}_{}",
-                                &self.names[&NameKey::StructMember(ty, index)],
-                                vec_index
-                            )?;
-
-                            if let Some(scalar_index) = scalar {
-                                write!(self.out, "[")?;
-                                match scalar_index {
-                                    Index::Static(index) => {
-                                        write!(self.out, "{index}")?;
-                                    }
-                                    Index::Expression(index) => {
-                                        self.write_expr(module, index, func_ctx)?;
-                                    }
-                                }
-                                write!(self.out, "]")?;
-                            }
-
-                            write!(self.out, " = ")?;
-                            self.write_expr(module, value, func_ctx)?;
-                            writeln!(self.out, ";")?;
-                        } else {
-                            let access = WrappedStructMatrixAccess { ty, index };
-                            match (&vector, &scalar) {
-                                (&Some(_), &Some(_)) => {
-                                    self.write_wrapped_struct_matrix_set_scalar_function_name(
-                                        access,
-                                    )?;
-                                }
-                                (&Some(_), &None) => {
-                                    self.write_wrapped_struct_matrix_set_vec_function_name(access)?;
-                                }
-                                (&None, _) => {
-                                    self.write_wrapped_struct_matrix_set_function_name(access)?;
-                                }
-                            }
-
-                            write!(self.out, "(")?;
-                            self.write_expr(module, base, func_ctx)?;
-                            write!(self.out, ", ")?;
-                            self.write_expr(module, value, func_ctx)?;
-
-                            if let Some(Index::Expression(vec_index)) = vector {
-                                write!(self.out, ", ")?;
-                                self.write_expr(module, vec_index, func_ctx)?;
-
-                                if let Some(scalar_index) = scalar {
-                                    write!(self.out, ", ")?;
-                                    match scalar_index {
-                                        Index::Static(index) => {
-                                            write!(self.out, "{index}")?;
-                                        }
-                                        Index::Expression(index) => {
-                                            self.write_expr(module, index, func_ctx)?;
-                                        }
-                                    }
-                                }
-                            }
-                            writeln!(self.out, ");")?;
-                        }
-                    } else {
-                        // We handle `Store`s to __matCx2 column vectors and scalar elements via
-                        // the previously injected functions __set_col_of_matCx2 / __set_el_of_matCx2.
-                        struct MatrixData {
-                            columns: crate::VectorSize,
-                            base: Handle<crate::Expression>,
-                        }
-
-                        enum Index {
-                            Expression(Handle<crate::Expression>),
-                            Static(u32),
-                        }
-
-                        let mut matrix = None;
-                        let mut vector = None;
-                        let mut scalar = None;
-
-                        let mut current_expr = pointer;
-                        for _ in 0..3 {
-                            let resolved = func_ctx.resolve_type(current_expr, &module.types);
-                            match (resolved, &func_ctx.expressions[current_expr]) {
-                                (
-                                    &TypeInner::ValuePointer {
-                                        size: Some(crate::VectorSize::Bi),
-                                        ..
-                                    },
-                                    &crate::Expression::Access { base, index },
-                                ) => {
-                                    vector = Some(index);
-                                    current_expr = base;
-                                }
-                                (
-                                    &TypeInner::ValuePointer { size: None, .. },
-                                    &crate::Expression::Access { base, index },
-                                ) => {
-                                    scalar = Some(Index::Expression(index));
-                                    current_expr = base;
-                                }
-                                (
-                                    &TypeInner::ValuePointer { size: None, .. },
-                                    &crate::Expression::AccessIndex { base, index },
-                                ) => {
-                                    scalar = Some(Index::Static(index));
-                                    current_expr = base;
-                                }
-                                _ => {
-                                    if let Some(MatrixType {
-                                        columns,
-                                        rows: crate::VectorSize::Bi,
-                                        width: 4,
-                                    }) = get_inner_matrix_of_struct_array_member(
-                                        module,
-                                        current_expr,
-                                        func_ctx,
-                                        true,
-                                    ) {
-                                        matrix = Some(MatrixData {
-                                            columns,
-                                            base: current_expr,
-                                        });
-                                    }
-
-                                    break;
-                                }
-                            }
-                        }
-
-                        if let (Some(MatrixData { columns, base }), Some(vec_index)) =
-                            (matrix, vector)
-                        {
-                            if scalar.is_some() {
-                                write!(self.out, "__set_el_of_mat{}x2", columns as u8)?;
-                            } else {
-                                write!(self.out, "__set_col_of_mat{}x2", columns as u8)?;
-                            }
-                            write!(self.out, "(")?;
-                            self.write_expr(module, base, func_ctx)?;
-                            write!(self.out, ", ")?;
-                            self.write_expr(module, vec_index, func_ctx)?;
-
-                            if let Some(scalar_index) = scalar {
-                                write!(self.out, ", ")?;
-                                match scalar_index {
-                                    Index::Static(index) => {
-                                        write!(self.out, "{index}")?;
-                                    }
-                                    Index::Expression(index) => {
-                                        self.write_expr(module, index, func_ctx)?;
-                                    }
-                                }
-                            }
-
-                            write!(self.out, ", ")?;
-                            self.write_expr(module, value, func_ctx)?;
-
-                            writeln!(self.out, ");")?;
-                        } else {
-                            self.write_expr(module, pointer, func_ctx)?;
-                            write!(self.out, " = ")?;
-
-                            // We cast the RHS of this store in cases where the LHS
-                            // is a struct member with type:
-                            //  - matCx2 or
-                            //  - a (possibly nested) array of matCx2's
-                            if let Some(MatrixType {
-                                columns,
-                                rows: crate::VectorSize::Bi,
-                                width: 4,
-                            }) = get_inner_matrix_of_struct_array_member(
-                                module, pointer, func_ctx, false,
-                            ) {
-                                let mut resolved = func_ctx.resolve_type(pointer, &module.types);
-                                if let TypeInner::Pointer { base, .. } = *resolved {
-                                    resolved = &module.types[base].inner;
-                                }
-
-                                write!(self.out, "(__mat{}x2", columns as u8)?;
-                                if let TypeInner::Array { base, size, .. } = *resolved {
-                                    self.write_array_size(module, base, size)?;
-                                }
-                                write!(self.out, ")")?;
-                            }
-
-                            self.write_expr(module, value, func_ctx)?;
-                            writeln!(self.out, ";")?
-                        }
-                    }
+                    write!(self.out, "{level}if (")?;
+                    self.write_bounds_checks(module, &checks, func_ctx)?;
+                    writeln!(self.out, ") {{")?;
+                    self.write_unchecked_store(module, pointer, value, func_ctx, level.next())?;
+                    writeln!(self.out, "{level}}}")?;
                 }
             }
             Statement::Loop {
@@ -2134,6 +2167,10 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 value,
                 result,
             } => {
+                // Under `ReadZeroSkipWrite`, the whole operation is skipped
+                // when the pointer is out of bounds, leaving a zero result.
+                let checks = self.bounds_checks(module, pointer, func_ctx);
+
                 write!(self.out, "{level}")?;
                 let res_name = match result {
                     None => None,
@@ -2147,10 +2184,34 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                                 self.write_value_type(module, value)?
                             }
                         };
-                        write!(self.out, " {name}; ")?;
+                        write!(self.out, " {name}")?;
+                        if !checks.is_empty() {
+                            write!(self.out, " = ")?;
+                            self.write_default_init_of_resolution(
+                                module,
+                                &func_ctx.info[result].ty,
+                            )?;
+                        }
+                        write!(self.out, ";")?;
                         Some((result, name))
                     }
                 };
+                let call_level = if checks.is_empty() {
+                    if res_name.is_some() {
+                        write!(self.out, " ")?;
+                    }
+                    level
+                } else {
+                    if res_name.is_some() {
+                        writeln!(self.out)?;
+                        write!(self.out, "{level}")?;
+                    }
+                    write!(self.out, "if (")?;
+                    self.write_bounds_checks(module, &checks, func_ctx)?;
+                    writeln!(self.out, ") {{")?;
+                    write!(self.out, "{}", level.next())?;
+                    level.next()
+                };
 
                 // Validation ensures that `pointer` has a `Pointer` type.
                 let pointer_space = func_ctx
@@ -2158,7 +2219,17 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     .pointer_space()
                     .unwrap();
 
-                let fun_str = fun.to_hlsl_suffix();
+                let compare_expr = match *fun {
+                    crate::AtomicFunction::Exchange { compare: Some(cmp) } => Some(cmp),
+                    _ => None,
+                };
+                // `InterlockedCompareExchange` requires the `original_value`
+                // out parameter, so use `InterlockedCompareStore` if nobody
+                // looks at the result.
+                let fun_str = match (compare_expr, res_name.is_some()) {
+                    (Some(_), false) => "CompareStore",
+                    _ => fun.to_hlsl_suffix(),
+                };
                 match pointer_space {
                     crate::AddressSpace::WorkGroup => {
                         write!(self.out, "Interlocked{fun_str}(")?;
@@ -2186,34 +2257,46 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     }
                 }
                 write!(self.out, ", ")?;
+                if let Some(cmp) = compare_expr {
+                    self.write_expr(module, cmp, func_ctx)?;
+                    write!(self.out, ", ")?;
+                }
                 // handle the special cases
-                match *fun {
-                    crate::AtomicFunction::Subtract => {
-                        // we just wrote `InterlockedAdd`, so negate the argument
-                        write!(self.out, "-")?;
-                    }
-                    crate::AtomicFunction::Exchange { compare: Some(_) } => {
-                        return Err(Error::Unimplemented("atomic CompareExchange".to_string()));
-                    }
-                    _ => {}
+                if let crate::AtomicFunction::Subtract = *fun {
+                    // we just wrote `InterlockedAdd`, so negate the argument
+                    write!(self.out, "-")?;
                 }
                 self.write_expr(module, value, func_ctx)?;
 
                 // The `original_value` out parameter is optional for all the
                 // `Interlocked` functions we generate other than
-                // `InterlockedExchange`.
+                // `InterlockedExchange` and `InterlockedCompareExchange`.
                 if let Some((result, name)) = res_name {
-                    write!(self.out, ", {name}")?;
+                    if let Some(cmp) = compare_expr {
+                        // The result is an `__atomic_compare_exchange_result`
+                        // struct, whose `exchanged` member HLSL doesn't
+                        // provide, so compute it from the original value.
+                        writeln!(self.out, ", {name}.old_value);")?;
+                        write!(
+                            self.out,
+                            "{call_level}{name}.exchanged = ({name}.old_value == "
+                        )?;
+                        self.write_expr(module, cmp, func_ctx)?;
+                    } else {
+                        write!(self.out, ", {name}")?;
+                    }
                     self.named_expressions.insert(result, name);
                 }
 
                 writeln!(self.out, ");")?;
+                if !checks.is_empty() {
+                    writeln!(self.out, "{level}}}")?;
+                }
             }
             Statement::WorkGroupUniformLoad { pointer, result } => {
                 self.write_barrier(crate::Barrier::WORK_GROUP, level)?;
-                write!(self.out, "{level}")?;
                 let name = Baked(result).to_string();
-                self.write_named_expr(module, pointer, name, result, func_ctx)?;
+                self.write_named_expr(module, pointer, name, result, func_ctx, level)?;
 
                 self.write_barrier(crate::Barrier::WORK_GROUP, level)?;
             }
@@ -2223,7 +2306,49 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             } => {
                 self.write_switch(module, func_ctx, level, selector, cases)?;
             }
-            Statement::RayQuery { .. } => unreachable!(),
+            Statement::RayQuery { query, ref fun } => match *fun {
+                crate::RayQueryFunction::Initialize {
+                    acceleration_structure,
+                    descriptor,
+                } => {
+                    write!(self.out, "{level}")?;
+                    self.write_expr(module, query, func_ctx)?;
+                    write!(self.out, ".TraceRayInline(")?;
+                    self.write_expr(module, acceleration_structure, func_ctx)?;
+                    write!(self.out, ", ")?;
+                    self.write_expr(module, descriptor, func_ctx)?;
+                    write!(self.out, ".flags, ")?;
+                    self.write_expr(module, descriptor, func_ctx)?;
+                    write!(self.out, ".cull_mask, {RAY_DESC_FUNCTION}(")?;
+                    self.write_expr(module, descriptor, func_ctx)?;
+                    writeln!(self.out, "));")?;
+                }
+                crate::RayQueryFunction::Proceed { result } => {
+                    write!(self.out, "{level}")?;
+                    let name = Baked(result).to_string();
+                    write!(self.out, "const bool {name} = ")?;
+                    self.named_expressions.insert(result, name);
+                    self.write_expr(module, query, func_ctx)?;
+                    writeln!(self.out, ".Proceed();")?;
+                }
+                crate::RayQueryFunction::GenerateIntersection { hit_t } => {
+                    write!(self.out, "{level}")?;
+                    self.write_expr(module, query, func_ctx)?;
+                    write!(self.out, ".CommitProceduralPrimitiveHit(")?;
+                    self.write_expr(module, hit_t, func_ctx)?;
+                    writeln!(self.out, ");")?;
+                }
+                crate::RayQueryFunction::ConfirmIntersection => {
+                    write!(self.out, "{level}")?;
+                    self.write_expr(module, query, func_ctx)?;
+                    writeln!(self.out, ".CommitNonOpaqueTriangleHit();")?;
+                }
+                crate::RayQueryFunction::Terminate => {
+                    write!(self.out, "{level}")?;
+                    self.write_expr(module, query, func_ctx)?;
+                    writeln!(self.out, ".Abort();")?;
+                }
+            },
             Statement::SubgroupBallot { result, predicate } => {
                 write!(self.out, "{level}")?;
                 let name = Baked(result).to_string();
@@ -2243,6 +2368,12 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 argument,
                 result,
             } => {
+                if let crate::CollectiveOperation::ClusteredReduce(size) = collective_op {
+                    return self.write_clustered_reduce(
+                        module, op, size, argument, result, func_ctx, level,
+                    );
+                }
+
                 write!(self.out, "{level}")?;
                 write!(self.out, "const ")?;
                 let name = Baked(result).to_string();
@@ -2297,7 +2428,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                         self.write_expr(module, argument, func_ctx)?;
                         write!(self.out, " * WavePrefixProduct(")?;
                     }
-                    _ => unimplemented!(),
+                    _ => return Err(Error::Unimplemented("subgroup operation".to_string())),
                 }
                 self.write_expr(module, argument, func_ctx)?;
                 writeln!(self.out, ");")?;
@@ -2322,13 +2453,27 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 if matches!(mode, crate::GatherMode::BroadcastFirst) {
                     write!(self.out, "WaveReadLaneFirst(")?;
                     self.write_expr(module, argument, func_ctx)?;
-                } else {
-                    write!(self.out, "WaveReadLaneAt(")?;
+                } else if let crate::GatherMode::QuadBroadcast(index) = mode {
+                    write!(self.out, "QuadReadLaneAt(")?;
                     self.write_expr(module, argument, func_ctx)?;
                     write!(self.out, ", ")?;
-                    match mode {
-                        crate::GatherMode::BroadcastFirst => unreachable!(),
-                        crate::GatherMode::Broadcast(index) | crate::GatherMode::Shuffle(index) => {
+                    self.write_expr(module, index, func_ctx)?;
+                } else if let crate::GatherMode::QuadSwap(direction) = mode {
+                    match direction {
+                        crate::Direction::X => write!(self.out, "QuadReadAcrossX(")?,
+                        crate::Direction::Y => write!(self.out, "QuadReadAcrossY(")?,
+                        crate::Direction::Diagonal => write!(self.out, "QuadReadAcrossDiagonal(")?,
+                    }
+                    self.write_expr(module, argument, func_ctx)?;
+                } else {
+                    write!(self.out, "WaveReadLaneAt(")?;
+                    self.write_expr(module, argument, func_ctx)?;
+                    write!(self.out, ", ")?;
+                    match mode {
+                        crate::GatherMode::BroadcastFirst
+                        | crate::GatherMode::QuadBroadcast(_)
+                        | crate::GatherMode::QuadSwap(_) => unreachable!(),
+                        crate::GatherMode::Broadcast(index) | crate::GatherMode::Shuffle(index) => {
                             self.write_expr(module, index, func_ctx)?;
                         }
                         crate::GatherMode::ShuffleDown(index) => {
@@ -2347,8 +2492,574 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 }
                 writeln!(self.out, ");")?;
             }
+            Statement::MeshFunction(ref fun) => {
+                self.write_mesh_function(module, fun, func_ctx, level)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write a store of `value` through `pointer`, without any
+    /// [`ReadZeroSkipWrite`] bounds checks.
+    ///
+    /// [`ReadZeroSkipWrite`]: index::BoundsCheckPolicy::ReadZeroSkipWrite
+    fn write_unchecked_store(
+        &mut self,
+        module: &Module,
+        pointer: Handle<crate::Expression>,
+        value: Handle<crate::Expression>,
+        func_ctx: &back::FunctionCtx<'_>,
+        level: back::Level,
+    ) -> BackendResult {
+        let ty_inner = func_ctx.resolve_type(pointer, &module.types);
+        if let Some(crate::AddressSpace::Storage { .. }) = ty_inner.pointer_space() {
+            let var_handle = self.fill_access_chain(module, pointer, func_ctx)?;
+            self.write_storage_store(
+                module,
+                var_handle,
+                StoreValue::Expression(value),
+                func_ctx,
+                level,
+            )?;
+        } else {
+            // We treat matrices of the form `matCx2` as a sequence of C `vec2`s.
+            // See the module-level block comment in mod.rs for details.
+            //
+            // We handle matrix Stores here directly (including sub accesses for Vectors and Scalars).
+            // Loads are handled by `Expression::AccessIndex` (since sub accesses work fine for Loads).
+            struct MatrixAccess {
+                base: Handle<crate::Expression>,
+                index: u32,
+            }
+            enum Index {
+                Expression(Handle<crate::Expression>),
+                Static(u32),
+            }
+
+            let get_members = |expr: Handle<crate::Expression>| {
+                let resolved = func_ctx.resolve_type(expr, &module.types);
+                match *resolved {
+                    TypeInner::Pointer { base, .. } => match module.types[base].inner {
+                        TypeInner::Struct { ref members, .. } => Some(members),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            };
+
+            let mut matrix = None;
+            let mut vector = None;
+            let mut scalar = None;
+
+            let mut current_expr = pointer;
+            for _ in 0..3 {
+                let resolved = func_ctx.resolve_type(current_expr, &module.types);
+
+                match (resolved, &func_ctx.expressions[current_expr]) {
+                    (
+                        &TypeInner::Pointer { base: ty, .. },
+                        &crate::Expression::AccessIndex { base, index },
+                    ) if matches!(
+                        module.types[ty].inner,
+                        TypeInner::Matrix {
+                            rows: crate::VectorSize::Bi,
+                            ..
+                        }
+                    ) && get_members(base)
+                        .map(|members| members[index as usize].binding.is_none())
+                        == Some(true) =>
+                    {
+                        matrix = Some(MatrixAccess { base, index });
+                        break;
+                    }
+                    (
+                        &TypeInner::ValuePointer {
+                            size: Some(crate::VectorSize::Bi),
+                            ..
+                        },
+                        &crate::Expression::Access { base, index },
+                    ) => {
+                        vector = Some(Index::Expression(index));
+                        current_expr = base;
+                    }
+                    (
+                        &TypeInner::ValuePointer {
+                            size: Some(crate::VectorSize::Bi),
+                            ..
+                        },
+                        &crate::Expression::AccessIndex { base, index },
+                    ) => {
+                        vector = Some(Index::Static(index));
+                        current_expr = base;
+                    }
+                    (
+                        &TypeInner::ValuePointer { size: None, .. },
+                        &crate::Expression::Access { base, index },
+                    ) => {
+                        scalar = Some(Index::Expression(index));
+                        current_expr = base;
+                    }
+                    (
+                        &TypeInner::ValuePointer { size: None, .. },
+                        &crate::Expression::AccessIndex { base, index },
+                    ) => {
+                        scalar = Some(Index::Static(index));
+                        current_expr = base;
+                    }
+                    _ => break,
+                }
+            }
+
+            write!(self.out, "{level}")?;
+
+            if let Some(MatrixAccess { index, base }) = matrix {
+                let base_ty_res = &func_ctx.info[base].ty;
+                let resolved = base_ty_res.inner_with(&module.types);
+                let ty = match *resolved {
+                    TypeInner::Pointer { base, .. } => base,
+                    _ => base_ty_res.handle().unwrap(),
+                };
+
+                if let Some(Index::Static(vec_index)) = vector {
+                    self.write_expr(module, base, func_ctx)?;
+                    write!(
+                        self.out,
+                        ".{}_{}",
+                        &self.names[&NameKey::StructMember(ty, index)],
+                        vec_index
+                    )?;
+
+                    if let Some(scalar_index) = scalar {
+                        write!(self.out, "[")?;
+                        match scalar_index {
+                            Index::Static(index) => {
+                                write!(self.out, "{index}")?;
+                            }
+                            Index::Expression(index) => {
+                                self.write_expr(module, index, func_ctx)?;
+                            }
+                        }
+                        write!(self.out, "]")?;
+                    }
+
+                    write!(self.out, " = ")?;
+                    self.write_expr(module, value, func_ctx)?;
+                    writeln!(self.out, ";")?;
+                } else {
+                    let access = WrappedStructMatrixAccess { ty, index };
+                    match (&vector, &scalar) {
+                        (&Some(_), &Some(_)) => {
+                            self.write_wrapped_struct_matrix_set_scalar_function_name(access)?;
+                        }
+                        (&Some(_), &None) => {
+                            self.write_wrapped_struct_matrix_set_vec_function_name(access)?;
+                        }
+                        (&None, _) => {
+                            self.write_wrapped_struct_matrix_set_function_name(access)?;
+                        }
+                    }
+
+                    write!(self.out, "(")?;
+                    self.write_expr(module, base, func_ctx)?;
+                    write!(self.out, ", ")?;
+                    self.write_expr(module, value, func_ctx)?;
+
+                    if let Some(Index::Expression(vec_index)) = vector {
+                        write!(self.out, ", ")?;
+                        self.write_expr(module, vec_index, func_ctx)?;
+
+                        if let Some(scalar_index) = scalar {
+                            write!(self.out, ", ")?;
+                            match scalar_index {
+                                Index::Static(index) => {
+                                    write!(self.out, "{index}")?;
+                                }
+                                Index::Expression(index) => {
+                                    self.write_expr(module, index, func_ctx)?;
+                                }
+                            }
+                        }
+                    }
+                    writeln!(self.out, ");")?;
+                }
+            } else {
+                // We handle `Store`s to __matCx2 column vectors and scalar elements via
+                // the previously injected functions __set_col_of_matCx2 / __set_el_of_matCx2.
+                struct MatrixData {
+                    columns: crate::VectorSize,
+                    base: Handle<crate::Expression>,
+                }
+
+                enum Index {
+                    Expression(Handle<crate::Expression>),
+                    Static(u32),
+                }
+
+                let mut matrix = None;
+                let mut vector = None;
+                let mut scalar = None;
+
+                let mut current_expr = pointer;
+                for _ in 0..3 {
+                    let resolved = func_ctx.resolve_type(current_expr, &module.types);
+                    match (resolved, &func_ctx.expressions[current_expr]) {
+                        (
+                            &TypeInner::ValuePointer {
+                                size: Some(crate::VectorSize::Bi),
+                                ..
+                            },
+                            &crate::Expression::Access { base, index },
+                        ) => {
+                            vector = Some(index);
+                            current_expr = base;
+                        }
+                        (
+                            &TypeInner::ValuePointer { size: None, .. },
+                            &crate::Expression::Access { base, index },
+                        ) => {
+                            scalar = Some(Index::Expression(index));
+                            current_expr = base;
+                        }
+                        (
+                            &TypeInner::ValuePointer { size: None, .. },
+                            &crate::Expression::AccessIndex { base, index },
+                        ) => {
+                            scalar = Some(Index::Static(index));
+                            current_expr = base;
+                        }
+                        _ => {
+                            if let Some(MatrixType {
+                                columns,
+                                rows: crate::VectorSize::Bi,
+                                width: 4,
+                            }) = get_inner_matrix_of_struct_array_member(
+                                module,
+                                current_expr,
+                                func_ctx,
+                                true,
+                            ) {
+                                matrix = Some(MatrixData {
+                                    columns,
+                                    base: current_expr,
+                                });
+                            }
+
+                            break;
+                        }
+                    }
+                }
+
+                if let (Some(MatrixData { columns, base }), Some(vec_index)) = (matrix, vector) {
+                    if scalar.is_some() {
+                        write!(self.out, "__set_el_of_mat{}x2", columns as u8)?;
+                    } else {
+                        write!(self.out, "__set_col_of_mat{}x2", columns as u8)?;
+                    }
+                    write!(self.out, "(")?;
+                    self.write_expr(module, base, func_ctx)?;
+                    write!(self.out, ", ")?;
+                    self.write_expr(module, vec_index, func_ctx)?;
+
+                    if let Some(scalar_index) = scalar {
+                        write!(self.out, ", ")?;
+                        match scalar_index {
+                            Index::Static(index) => {
+                                write!(self.out, "{index}")?;
+                            }
+                            Index::Expression(index) => {
+                                self.write_expr(module, index, func_ctx)?;
+                            }
+                        }
+                    }
+
+                    write!(self.out, ", ")?;
+                    self.write_expr(module, value, func_ctx)?;
+
+                    writeln!(self.out, ");")?;
+                } else {
+                    self.write_expr(module, pointer, func_ctx)?;
+                    write!(self.out, " = ")?;
+
+                    // We cast the RHS of this store in cases where the LHS
+                    // is a struct member with type:
+                    //  - matCx2 or
+                    //  - a (possibly nested) array of matCx2's
+                    if let Some(MatrixType {
+                        columns,
+                        rows: crate::VectorSize::Bi,
+                        width: 4,
+                    }) =
+                        get_inner_matrix_of_struct_array_member(module, pointer, func_ctx, false)
+                    {
+                        let mut resolved = func_ctx.resolve_type(pointer, &module.types);
+                        if let TypeInner::Pointer { base, .. } = *resolved {
+                            resolved = &module.types[base].inner;
+                        }
+
+                        write!(self.out, "(__mat{}x2", columns as u8)?;
+                        if let TypeInner::Array { base, size, .. } = *resolved {
+                            self.write_array_size(module, base, size)?;
+                        }
+                        write!(self.out, ")")?;
+                    }
+
+                    self.write_expr(module, value, func_ctx)?;
+                    writeln!(self.out, ";")?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a [`ClusteredReduce`] subgroup operation.
+    ///
+    /// HLSL has no clustered wave intrinsics, so this combines the values of
+    /// each cluster with a butterfly of `WaveReadLaneAt` calls.
+    ///
+    /// [`ClusteredReduce`]: crate::CollectiveOperation::ClusteredReduce
+    #[allow(clippy::too_many_arguments)]
+    fn write_clustered_reduce(
+        &mut self,
+        module: &Module,
+        op: crate::SubgroupOperation,
+        size: u32,
+        argument: Handle<crate::Expression>,
+        result: Handle<crate::Expression>,
+        func_ctx: &back::FunctionCtx<'_>,
+        level: back::Level,
+    ) -> BackendResult {
+        let name = Baked(result).to_string();
+        write!(self.out, "{level}")?;
+        match func_ctx.info[result].ty {
+            proc::TypeResolution::Handle(handle) => self.write_type(module, handle)?,
+            proc::TypeResolution::Value(ref value) => self.write_value_type(module, value)?,
+        };
+        write!(self.out, " {name} = ")?;
+        self.write_expr(module, argument, func_ctx)?;
+        writeln!(self.out, ";")?;
+
+        let mut offset = 1;
+        while offset < size {
+            let other = format!("WaveReadLaneAt({name}, WaveGetLaneIndex() ^ {offset}u)");
+            write!(self.out, "{level}{name} = ")?;
+            match op {
+                crate::SubgroupOperation::Add => write!(self.out, "{name} + {other}")?,
+                crate::SubgroupOperation::Mul => write!(self.out, "{name} * {other}")?,
+                crate::SubgroupOperation::Max => write!(self.out, "max({name}, {other})")?,
+                crate::SubgroupOperation::Min => write!(self.out, "min({name}, {other})")?,
+                crate::SubgroupOperation::And => write!(self.out, "{name} & {other}")?,
+                crate::SubgroupOperation::Or => write!(self.out, "{name} | {other}")?,
+                crate::SubgroupOperation::Xor => write!(self.out, "{name} ^ {other}")?,
+                crate::SubgroupOperation::All | crate::SubgroupOperation::Any => unreachable!(),
+            }
+            writeln!(self.out, ";")?;
+            offset *= 2;
+        }
+
+        self.named_expressions.insert(result, name);
+        Ok(())
+    }
+
+    /// Write a task shader's return as a call to `DispatchMesh`.
+    fn write_task_return(
+        &mut self,
+        module: &Module,
+        ep: &crate::EntryPoint,
+        expr: Handle<crate::Expression>,
+        payload: &str,
+        func_ctx: &back::FunctionCtx<'_>,
+        level: back::Level,
+    ) -> BackendResult {
+        let result_ty = ep.function.result.as_ref().unwrap().ty;
+        let variable_name = self.namer.call("task_output");
+        write!(self.out, "{level}const ")?;
+        self.write_type(module, result_ty)?;
+        write!(self.out, " {variable_name} = ")?;
+        self.write_expr(module, expr, func_ctx)?;
+        writeln!(self.out, ";")?;
+
+        let mut size = variable_name;
+        if let TypeInner::Struct { ref members, .. } = module.types[result_ty].inner {
+            let index = members
+                .iter()
+                .position(|member| {
+                    member.binding == Some(crate::Binding::BuiltIn(crate::BuiltIn::MeshTaskSize))
+                })
+                .unwrap();
+            size = format!(
+                "{size}.{}",
+                self.names[&NameKey::StructMember(result_ty, index as u32)]
+            );
+        }
+        writeln!(
+            self.out,
+            "{level}DispatchMesh({size}.x, {size}.y, {size}.z, {payload});"
+        )?;
+        writeln!(self.out, "{level}return;")?;
+        Ok(())
+    }
+
+    /// Write a mesh shader's [`MeshFunction`] as `SetMeshOutputCounts`, or as
+    /// stores to its output arrays.
+    ///
+    /// [`MeshFunction`]: crate::MeshFunction
+    fn write_mesh_function(
+        &mut self,
+        module: &Module,
+        fun: &crate::MeshFunction,
+        func_ctx: &back::FunctionCtx<'_>,
+        level: back::Level,
+    ) -> BackendResult {
+        let back::FunctionType::EntryPoint(ep_index) = func_ctx.ty else {
+            unreachable!("mesh functions are only valid in entry points")
+        };
+        let mesh_info = module.entry_points[ep_index as usize]
+            .mesh_info
+            .as_ref()
+            .unwrap();
+        match *fun {
+            crate::MeshFunction::SetMeshOutputs {
+                vertex_count,
+                primitive_count,
+            } => {
+                write!(self.out, "{level}SetMeshOutputCounts(")?;
+                self.write_expr(module, vertex_count, func_ctx)?;
+                write!(self.out, ", ")?;
+                self.write_expr(module, primitive_count, func_ctx)?;
+                writeln!(self.out, ");")?;
+            }
+            crate::MeshFunction::SetVertex { index, value } => {
+                let ty = mesh_info.vertex_output_type;
+                let value_name =
+                    self.write_mesh_output_value(module, ty, value, func_ctx, level)?;
+                let index_name = self.write_mesh_output_index(module, index, func_ctx, level)?;
+                let vertices = &self.entry_point_io[ep_index as usize]
+                    .mesh_output
+                    .as_ref()
+                    .unwrap()
+                    .vertices;
+                let (ty_name, arg_name) = (vertices.ty_name.clone(), vertices.arg_name.clone());
+                let members = vertices.members.iter().map(|m| m.index).collect::<Vec<_>>();
+                self.write_mesh_output_store(
+                    ty,
+                    &ty_name,
+                    &arg_name,
+                    &members,
+                    &value_name,
+                    &index_name,
+                    level,
+                )?;
+            }
+            crate::MeshFunction::SetPrimitive { index, value } => {
+                let ty = mesh_info.primitive_output_type;
+                let value_name =
+                    self.write_mesh_output_value(module, ty, value, func_ctx, level)?;
+                let index_name = self.write_mesh_output_index(module, index, func_ctx, level)?;
+                let mesh_output = self.entry_point_io[ep_index as usize]
+                    .mesh_output
+                    .as_ref()
+                    .unwrap();
+                let indices_name = mesh_output.indices_name.clone();
+                let primitives = mesh_output.primitives.as_ref().map(|primitives| {
+                    (
+                        primitives.ty_name.clone(),
+                        primitives.arg_name.clone(),
+                        primitives
+                            .members
+                            .iter()
+                            .map(|m| m.index)
+                            .collect::<Vec<_>>(),
+                    )
+                });
+
+                let TypeInner::Struct { ref members, .. } = module.types[ty].inner else {
+                    unreachable!()
+                };
+                let indices_member = members
+                    .iter()
+                    .position(|member| is_mesh_intrinsic_builtin_binding(&member.binding))
+                    .unwrap();
+                writeln!(
+                    self.out,
+                    "{level}{indices_name}[{index_name}] = {value_name}.{};",
+                    self.names[&NameKey::StructMember(ty, indices_member as u32)]
+                )?;
+                if let Some((ty_name, arg_name, members)) = primitives {
+                    self.write_mesh_output_store(
+                        ty,
+                        &ty_name,
+                        &arg_name,
+                        &members,
+                        &value_name,
+                        &index_name,
+                        level,
+                    )?;
+                }
+            }
         }
+        Ok(())
+    }
 
+    /// Store `value` in a temporary of the mesh output type `ty`, and return
+    /// its name.
+    fn write_mesh_output_value(
+        &mut self,
+        module: &Module,
+        ty: Handle<crate::Type>,
+        value: Handle<crate::Expression>,
+        func_ctx: &back::FunctionCtx<'_>,
+        level: back::Level,
+    ) -> Result<String, Error> {
+        let struct_name = &self.names[&NameKey::Type(ty)];
+        let name = self.namer.call(&struct_name.to_lowercase());
+        write!(self.out, "{level}const {struct_name} {name} = ")?;
+        self.write_expr(module, value, func_ctx)?;
+        writeln!(self.out, ";")?;
+        Ok(name)
+    }
+
+    /// Store the `index` operand of a mesh function in a temporary, and
+    /// return its name.
+    fn write_mesh_output_index(
+        &mut self,
+        module: &Module,
+        index: Handle<crate::Expression>,
+        func_ctx: &back::FunctionCtx<'_>,
+        level: back::Level,
+    ) -> Result<String, Error> {
+        let name = self.namer.call("mesh_index");
+        write!(self.out, "{level}const uint {name} = ")?;
+        self.write_expr(module, index, func_ctx)?;
+        writeln!(self.out, ";")?;
+        Ok(name)
+    }
+
+    /// Rebuild the members of `value_name`, of the mesh output type `ty`, as
+    /// the flattened struct `ty_name`, and store it in the output array
+    /// `arg_name`.
+    #[allow(clippy::too_many_arguments)]
+    fn write_mesh_output_store(
+        &mut self,
+        ty: Handle<crate::Type>,
+        ty_name: &str,
+        arg_name: &str,
+        members: &[u32],
+        value_name: &str,
+        index_name: &str,
+        level: back::Level,
+    ) -> BackendResult {
+        let final_name = self.namer.call(&ty_name.to_lowercase());
+        write!(self.out, "{level}const {ty_name} {final_name} = {{ ")?;
+        for (i, &member_index) in members.iter().enumerate() {
+            if i != 0 {
+                write!(self.out, ", ")?;
+            }
+            let member_name = &self.names[&NameKey::StructMember(ty, member_index)];
+            write!(self.out, "{value_name}.{member_name}")?;
+        }
+        writeln!(self.out, " }};")?;
+        writeln!(self.out, "{level}{arg_name}[{index_name}] = {final_name};")?;
         Ok(())
     }
 
@@ -2383,6 +3094,7 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 // decimal part even it's zero
                 crate::Literal::F64(value) => write!(self.out, "{value:?}L")?,
                 crate::Literal::F32(value) => write!(self.out, "{value:?}")?,
+                crate::Literal::F16(value) => write!(self.out, "{value:?}h")?,
                 crate::Literal::U32(value) => write!(self.out, "{value}u")?,
                 crate::Literal::I32(value) => write!(self.out, "{value}")?,
                 crate::Literal::U64(value) => write!(self.out, "{value}uL")?,
@@ -2587,35 +3299,25 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
 
                     let resolved = func_ctx.resolve_type(base, &module.types);
 
-                    let (indexing_binding_array, non_uniform_qualifier) = match *resolved {
+                    let non_uniform_qualifier = match *resolved {
                         TypeInner::BindingArray { .. } => {
                             let uniformity = &func_ctx.info[index].uniformity;
 
-                            (true, uniformity.non_uniform_result.is_some())
+                            uniformity.non_uniform_result.is_some()
                         }
-                        _ => (false, false),
+                        _ => false,
                     };
 
                     self.write_expr(module, base, func_ctx)?;
                     write!(self.out, "[")?;
 
-                    let needs_bound_check = self.options.restrict_indexing
-                        && !indexing_binding_array
-                        && match resolved.pointer_space() {
-                            Some(
-                                crate::AddressSpace::Function
-                                | crate::AddressSpace::Private
-                                | crate::AddressSpace::WorkGroup
-                                | crate::AddressSpace::PushConstant,
-                            )
-                            | None => true,
-                            Some(crate::AddressSpace::Uniform) => false, // TODO: needs checks for dynamic uniform buffers, see https://github.com/gfx-rs/wgpu/issues/4483
-                            Some(
-                                crate::AddressSpace::Handle | crate::AddressSpace::Storage { .. },
-                            ) => unreachable!(),
-                        };
                     // Decide whether this index needs to be clamped to fall within range.
-                    let restriction_needed = if needs_bound_check {
+                    let restriction_needed = if self.options.bounds_check_policies.choose_policy(
+                        base,
+                        &module.types,
+                        func_ctx.info,
+                    ) == index::BoundsCheckPolicy::Restrict
+                    {
                         index::access_needs_check(
                             base,
                             index::GuardedIndex::Expression(index),
@@ -2626,26 +3328,27 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     } else {
                         None
                     };
-                    if let Some(limit) = restriction_needed {
-                        write!(self.out, "min(uint(")?;
-                        self.write_expr(module, index, func_ctx)?;
-                        write!(self.out, "), ")?;
-                        match limit {
-                            index::IndexableLength::Known(limit) => {
-                                write!(self.out, "{}u", limit - 1)?;
-                            }
-                            index::IndexableLength::Dynamic => unreachable!(),
-                        }
-                        write!(self.out, ")")?;
-                    } else {
-                        if non_uniform_qualifier {
-                            write!(self.out, "NonUniformResourceIndex(")?;
+
+                    if non_uniform_qualifier {
+                        write!(self.out, "NonUniformResourceIndex(")?;
+                    }
+                    match restriction_needed {
+                        Some(index::IndexableLength::Known(limit)) => {
+                            write!(self.out, "min(uint(")?;
+                            self.write_expr(module, index, func_ctx)?;
+                            write!(self.out, "), {}u)", limit - 1)?;
                         }
-                        self.write_expr(module, index, func_ctx)?;
-                        if non_uniform_qualifier {
-                            write!(self.out, ")")?;
+                        // Only storage buffers and binding arrays can be
+                        // runtime-sized. Storage accesses are written by
+                        // `fill_access_chain`, and there is no way to query the
+                        // length of a binding array, so leave these unclamped.
+                        Some(index::IndexableLength::Dynamic) | None => {
+                            self.write_expr(module, index, func_ctx)?;
                         }
                     }
+                    if non_uniform_qualifier {
+                        write!(self.out, ")")?;
+                    }
 
                     write!(self.out, "]")?;
                 }
@@ -2858,30 +3561,23 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 sample,
                 level,
             } => {
-                // https://docs.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-to-load
-                self.write_expr(module, image, func_ctx)?;
-                write!(self.out, ".Load(")?;
-
-                self.write_texture_coordinates(
-                    "int",
+                let address = TexelAddress {
                     coordinate,
                     array_index,
+                    sample,
                     level,
-                    module,
-                    func_ctx,
-                )?;
-
-                if let Some(sample) = sample {
-                    write!(self.out, ", ")?;
-                    self.write_expr(module, sample, func_ctx)?;
-                }
-
-                // close bracket for Load function
-                write!(self.out, ")")?;
-
-                // return x component if return type is scalar
-                if let TypeInner::Scalar(_) = *func_ctx.resolve_type(expr, &module.types) {
-                    write!(self.out, ".x")?;
+                };
+                let policy = self.options.bounds_check_policies.image_load;
+                if policy == index::BoundsCheckPolicy::ReadZeroSkipWrite {
+                    write!(self.out, "(")?;
+                    self.write_image_load_bounds_checks(module, image, address, func_ctx)?;
+                    write!(self.out, " ? ")?;
+                    self.write_image_load(module, expr, image, address, policy, func_ctx)?;
+                    write!(self.out, " : ")?;
+                    self.write_default_init_of_resolution(module, &func_ctx.info[expr].ty)?;
+                    write!(self.out, ")")?;
+                } else {
+                    self.write_image_load(module, expr, image, address, policy, func_ctx)?;
                 }
             }
             Expression::GlobalVariable(handle) => match module.global_variables[handle].space {
@@ -3020,8 +3716,16 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 use crate::MathFunction as Mf;
 
                 enum Function {
-                    Asincosh { is_sin: bool },
+                    Asincosh {
+                        is_sin: bool,
+                    },
                     Atanh,
+                    Dot4AddPacked {
+                        fun_name: &'static str,
+                        zero: &'static str,
+                    },
+                    Dot4I8Packed,
+                    Dot4U8Packed,
                     Pack2x16float,
                     Pack2x16snorm,
                     Pack2x16unorm,
@@ -3084,6 +3788,20 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                     Mf::Pow => Function::Regular("pow"),
                     // geometry
                     Mf::Dot => Function::Regular("dot"),
+                    Mf::Dot4I8Packed if self.options.shader_model >= ShaderModel::V6_4 => {
+                        Function::Dot4AddPacked {
+                            fun_name: "dot4add_i8packed",
+                            zero: "0",
+                        }
+                    }
+                    Mf::Dot4U8Packed if self.options.shader_model >= ShaderModel::V6_4 => {
+                        Function::Dot4AddPacked {
+                            fun_name: "dot4add_u8packed",
+                            zero: "0u",
+                        }
+                    }
+                    Mf::Dot4I8Packed => Function::Dot4I8Packed,
+                    Mf::Dot4U8Packed => Function::Dot4U8Packed,
                     //Mf::Outer => ,
                     Mf::Cross => Function::Regular("cross"),
                     Mf::Distance => Function::Regular("distance"),
@@ -3152,6 +3870,37 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                         self.write_expr(module, arg, func_ctx)?;
                         write!(self.out, "))")?;
                     }
+                    Function::Dot4AddPacked { fun_name, zero } => {
+                        write!(self.out, "{fun_name}(")?;
+                        self.write_expr(module, arg, func_ctx)?;
+                        write!(self.out, ", ")?;
+                        self.write_expr(module, arg1.unwrap(), func_ctx)?;
+                        write!(self.out, ", {zero})")?;
+                    }
+                    fun @ (Function::Dot4I8Packed | Function::Dot4U8Packed) => {
+                        // Unpack both arguments like `Unpack4xI8`/`Unpack4xU8` and take the
+                        // dot product of the resulting vectors.
+                        let vector = match fun {
+                            Function::Dot4I8Packed => "int4",
+                            _ => "uint4",
+                        };
+                        write!(self.out, "dot(")?;
+                        for (i, packed) in [arg, arg1.unwrap()].into_iter().enumerate() {
+                            if i != 0 {
+                                write!(self.out, ", ")?;
+                            }
+                            write!(self.out, "{vector}(")?;
+                            self.write_expr(module, packed, func_ctx)?;
+                            write!(self.out, ", ")?;
+                            self.write_expr(module, packed, func_ctx)?;
+                            write!(self.out, " >> 8, ")?;
+                            self.write_expr(module, packed, func_ctx)?;
+                            write!(self.out, " >> 16, ")?;
+                            self.write_expr(module, packed, func_ctx)?;
+                            write!(self.out, " >> 24) << 24 >> 24")?;
+                        }
+                        write!(self.out, ")")?;
+                    }
                     Function::Pack2x16float => {
                         write!(self.out, "(f32tof16(")?;
                         self.write_expr(module, arg, func_ctx)?;
@@ -3486,43 +4235,8 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 }
             }
             Expression::ArrayLength(expr) => {
-                let var_handle = match func_ctx.expressions[expr] {
-                    Expression::AccessIndex { base, index: _ } => {
-                        match func_ctx.expressions[base] {
-                            Expression::GlobalVariable(handle) => handle,
-                            _ => unreachable!(),
-                        }
-                    }
-                    Expression::GlobalVariable(handle) => handle,
-                    _ => unreachable!(),
-                };
-
-                let var = &module.global_variables[var_handle];
-                let (offset, stride) = match module.types[var.ty].inner {
-                    TypeInner::Array { stride, .. } => (0, stride),
-                    TypeInner::Struct { ref members, .. } => {
-                        let last = members.last().unwrap();
-                        let stride = match module.types[last.ty].inner {
-                            TypeInner::Array { stride, .. } => stride,
-                            _ => unreachable!(),
-                        };
-                        (last.offset, stride)
-                    }
-                    _ => unreachable!(),
-                };
-
-                let storage_access = match var.space {
-                    crate::AddressSpace::Storage { access } => access,
-                    _ => crate::StorageAccess::default(),
-                };
-                let wrapped_array_length = WrappedArrayLength {
-                    writable: storage_access.contains(crate::StorageAccess::STORE),
-                };
-
-                write!(self.out, "((")?;
-                self.write_wrapped_array_length_function_name(wrapped_array_length)?;
-                let var_name = &self.names[&NameKey::GlobalVariable(var_handle)];
-                write!(self.out, "({var_name}) - {offset}) / {stride})")?
+                let var_handle = dynamic_array_global(func_ctx.expressions, expr);
+                self.write_dynamic_array_length(module, var_handle)?;
             }
             Expression::Derivative { axis, ctrl, expr } => {
                 use crate::{DerivativeAxis as Axis, DerivativeControl as Ctrl};
@@ -3580,7 +4294,16 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
                 write!(self.out, ")")?
             }
             // Not supported yet
-            Expression::RayQueryGetIntersection { .. } => unreachable!(),
+            Expression::RayQueryGetIntersection { query, committed } => {
+                let defined_func_name = if committed {
+                    COMMITTED_INTERSECTION_FUNCTION
+                } else {
+                    CANDIDATE_INTERSECTION_FUNCTION
+                };
+                write!(self.out, "{defined_func_name}(")?;
+                self.write_expr(module, query, func_ctx)?;
+                write!(self.out, ")")?;
+            }
             // Nothing to do here, since call expression already cached
             Expression::CallResult(_)
             | Expression::AtomicResult { .. }
@@ -3605,7 +4328,21 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         // Generally, this is the same as handle, except in WorkGroupUniformLoad
         named: Handle<crate::Expression>,
         ctx: &back::FunctionCtx,
+        level: back::Level,
     ) -> BackendResult {
+        // Accesses that need `ReadZeroSkipWrite` checks are written as a
+        // zero-initialized variable, assigned only if the checks pass.
+        // HLSL's `?:` operator doesn't accept structs or arrays, so we
+        // can't use that.
+        let checks = match ctx.expressions[handle] {
+            crate::Expression::Load { pointer } => self.bounds_checks(module, pointer, ctx),
+            crate::Expression::Access { .. } | crate::Expression::AccessIndex { .. } => {
+                self.bounds_checks(module, handle, ctx)
+            }
+            _ => Vec::new(),
+        };
+
+        write!(self.out, "{level}")?;
         match ctx.info[named].ty {
             proc::TypeResolution::Handle(ty_handle) => match module.types[ty_handle].inner {
                 TypeInner::Struct { .. } => {
@@ -3629,8 +4366,20 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
             self.write_array_size(module, base, size)?;
         }
         write!(self.out, " = ")?;
-        self.write_expr(module, handle, ctx)?;
-        writeln!(self.out, ";")?;
+        if checks.is_empty() {
+            self.write_expr(module, handle, ctx)?;
+            writeln!(self.out, ";")?;
+        } else {
+            self.write_default_init_of_resolution(module, &ctx.info[named].ty)?;
+            writeln!(self.out, ";")?;
+            write!(self.out, "{level}if (")?;
+            self.write_bounds_checks(module, &checks, ctx)?;
+            writeln!(self.out, ") {{")?;
+            write!(self.out, "{}{name} = ", level.next())?;
+            self.write_expr(module, handle, ctx)?;
+            writeln!(self.out, ";")?;
+            writeln!(self.out, "{level}}}")?;
+        }
         self.named_expressions.insert(named, name);
 
         Ok(())
@@ -3651,6 +4400,385 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
         Ok(())
     }
 
+    /// Like [`Self::write_default_init`], but for any [`TypeResolution`].
+    ///
+    /// [`TypeResolution`]: proc::TypeResolution
+    fn write_default_init_of_resolution(
+        &mut self,
+        module: &Module,
+        ty: &proc::TypeResolution,
+    ) -> BackendResult {
+        match *ty {
+            proc::TypeResolution::Handle(handle) => self.write_default_init(module, handle),
+            proc::TypeResolution::Value(ref inner) => {
+                write!(self.out, "(")?;
+                self.write_value_type(module, inner)?;
+                write!(self.out, ")0")?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Collect the checks the [`ReadZeroSkipWrite`] policy requires before
+    /// the access chain `chain` may be used.
+    ///
+    /// `chain` is a subtree of [`Access`] and [`AccessIndex`] expressions,
+    /// operating either on a pointer or on a value. The walk stops at any
+    /// named expression, since its own checks were written when it was
+    /// baked. Struct members and binding arrays never get checks here.
+    ///
+    /// Return an empty vector if nothing needs checking.
+    ///
+    /// [`ReadZeroSkipWrite`]: index::BoundsCheckPolicy::ReadZeroSkipWrite
+    /// [`Access`]: crate::Expression::Access
+    /// [`AccessIndex`]: crate::Expression::AccessIndex
+    fn bounds_checks(
+        &self,
+        module: &Module,
+        mut chain: Handle<crate::Expression>,
+        func_ctx: &back::FunctionCtx<'_>,
+    ) -> Vec<BoundsCheck> {
+        let policies = self.options.bounds_check_policies;
+        let mut checks = Vec::new();
+        if !policies.contains(index::BoundsCheckPolicy::ReadZeroSkipWrite) {
+            return checks;
+        }
+
+        loop {
+            let (base, index) = match func_ctx.expressions[chain] {
+                crate::Expression::Access { base, index } => {
+                    (base, index::GuardedIndex::Expression(index))
+                }
+                crate::Expression::AccessIndex { base, index } => {
+                    (base, index::GuardedIndex::Known(index))
+                }
+                _ => break,
+            };
+
+            let mut base_inner = func_ctx.resolve_type(base, &module.types);
+            if let TypeInner::Pointer { base, .. } = *base_inner {
+                base_inner = &module.types[base].inner;
+            }
+            let checkable = !matches!(
+                *base_inner,
+                TypeInner::Struct { .. } | TypeInner::BindingArray { .. }
+            );
+
+            if checkable
+                && policies.choose_policy(base, &module.types, func_ctx.info)
+                    == index::BoundsCheckPolicy::ReadZeroSkipWrite
+            {
+                if let Some(length) = index::access_needs_check(
+                    base,
+                    index,
+                    module,
+                    func_ctx.expressions,
+                    func_ctx.info,
+                ) {
+                    checks.push(BoundsCheck {
+                        base,
+                        index,
+                        length,
+                    });
+                }
+            }
+
+            if self.named_expressions.contains_key(&base) {
+                break;
+            }
+            chain = base;
+        }
+
+        checks
+    }
+
+    /// Write `checks`, collected by [`Self::bounds_checks`], joined by `&&`.
+    fn write_bounds_checks(
+        &mut self,
+        module: &Module,
+        checks: &[BoundsCheck],
+        func_ctx: &back::FunctionCtx<'_>,
+    ) -> BackendResult {
+        for (i, check) in checks.iter().enumerate() {
+            if i != 0 {
+                write!(self.out, " && ")?;
+            }
+
+            // Check that the index falls within bounds. Do this with a single
+            // comparison, by casting the index to `uint` first, so that negative
+            // indices become large positive values.
+            match check.index {
+                index::GuardedIndex::Known(value) => write!(self.out, "{value}u")?,
+                index::GuardedIndex::Expression(value) => {
+                    write!(self.out, "uint(")?;
+                    self.write_expr(module, value, func_ctx)?;
+                    write!(self.out, ")")?;
+                }
+            }
+            write!(self.out, " < ")?;
+            match check.length {
+                index::IndexableLength::Known(length) => write!(self.out, "{length}u")?,
+                index::IndexableLength::Dynamic => {
+                    let var_handle = dynamic_array_global(func_ctx.expressions, check.base);
+                    self.write_dynamic_array_length(module, var_handle)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the length of the runtime-sized array at the end of the
+    /// [`Storage`] global `var_handle`.
+    ///
+    /// [`Storage`]: crate::AddressSpace::Storage
+    pub(super) fn write_dynamic_array_length(
+        &mut self,
+        module: &Module,
+        var_handle: Handle<crate::GlobalVariable>,
+    ) -> BackendResult {
+        let var = &module.global_variables[var_handle];
+        let (offset, stride) = match module.types[var.ty].inner {
+            TypeInner::Array { stride, .. } => (0, stride),
+            TypeInner::Struct { ref members, .. } => {
+                let last = members.last().unwrap();
+                let stride = match module.types[last.ty].inner {
+                    TypeInner::Array { stride, .. } => stride,
+                    _ => unreachable!(),
+                };
+                (last.offset, stride)
+            }
+            _ => unreachable!(),
+        };
+
+        let wrapped_array_length = WrappedArrayLength::for_global(var);
+
+        write!(self.out, "((")?;
+        self.write_wrapped_array_length_function_name(wrapped_array_length)?;
+        let var_name = &self.names[&NameKey::GlobalVariable(var_handle)];
+        write!(self.out, "({var_name}) - {offset}) / {stride})")?;
+        Ok(())
+    }
+
+    /// Write the `Load` call for an [`ImageLoad`] expression.
+    ///
+    /// If `policy` is [`Restrict`], clamp the level of detail, coordinates,
+    /// array index and sample index to the image's bounds. The
+    /// [`ReadZeroSkipWrite`] checks must be written by the caller, with
+    /// [`Self::write_image_load_bounds_checks`].
+    ///
+    /// [`ImageLoad`]: crate::Expression::ImageLoad
+    /// [`Restrict`]: index::BoundsCheckPolicy::Restrict
+    /// [`ReadZeroSkipWrite`]: index::BoundsCheckPolicy::ReadZeroSkipWrite
+    fn write_image_load(
+        &mut self,
+        module: &Module,
+        expr: Handle<crate::Expression>,
+        image: Handle<crate::Expression>,
+        address: TexelAddress,
+        policy: index::BoundsCheckPolicy,
+        func_ctx: &back::FunctionCtx<'_>,
+    ) -> BackendResult {
+        // https://docs.microsoft.com/en-us/windows/win32/direct3dhlsl/dx-graphics-hlsl-to-load
+        self.write_expr(module, image, func_ctx)?;
+        write!(self.out, ".Load(")?;
+
+        if policy == index::BoundsCheckPolicy::Restrict {
+            // HLSL expects the array index and level to be merged with the coordinate
+            let extra = address.array_index.is_some() as usize + address.level.is_some() as usize;
+            if extra != 0 {
+                let num_coords = match *func_ctx.resolve_type(address.coordinate, &module.types) {
+                    TypeInner::Vector { size, .. } => size as usize,
+                    _ => 1,
+                };
+                write!(self.out, "int{}(", num_coords + extra)?;
+            }
+
+            write!(self.out, "min(")?;
+            self.write_cast_to_uint_scalar_or_vector(module, address.coordinate, func_ctx)?;
+            write!(self.out, ", ")?;
+            if let Some(level) = address.level {
+                self.write_image_query_call_start(module, image, ImageQuery::SizeLevel, func_ctx)?;
+                write!(self.out, ", ")?;
+                self.write_restricted_image_level(module, image, level, func_ctx)?;
+                write!(self.out, ")")?;
+            } else {
+                self.write_image_query_call_start(module, image, ImageQuery::Size, func_ctx)?;
+                write!(self.out, ")")?;
+            }
+            write!(self.out, " - 1u)")?;
+
+            if let Some(array_index) = address.array_index {
+                write!(self.out, ", min(uint(")?;
+                self.write_expr(module, array_index, func_ctx)?;
+                write!(self.out, "), ")?;
+                self.write_image_query_call_start(module, image, ImageQuery::NumLayers, func_ctx)?;
+                write!(self.out, ") - 1u)")?;
+            }
+
+            if let Some(level) = address.level {
+                write!(self.out, ", ")?;
+                self.write_restricted_image_level(module, image, level, func_ctx)?;
+            }
+
+            if extra != 0 {
+                write!(self.out, ")")?;
+            }
+
+            if let Some(sample) = address.sample {
+                write!(self.out, ", min(uint(")?;
+                self.write_expr(module, sample, func_ctx)?;
+                write!(self.out, "), ")?;
+                self.write_image_query_call_start(module, image, ImageQuery::NumSamples, func_ctx)?;
+                write!(self.out, ") - 1u)")?;
+            }
+        } else {
+            self.write_texture_coordinates(
+                "int",
+                address.coordinate,
+                address.array_index,
+                address.level,
+                module,
+                func_ctx,
+            )?;
+
+            if let Some(sample) = address.sample {
+                write!(self.out, ", ")?;
+                self.write_expr(module, sample, func_ctx)?;
+            }
+        }
+
+        // close bracket for Load function
+        write!(self.out, ")")?;
+
+        // return x component if return type is scalar
+        if let TypeInner::Scalar(_) = *func_ctx.resolve_type(expr, &module.types) {
+            write!(self.out, ".x")?;
+        }
+
+        Ok(())
+    }
+
+    /// Write an expression that is true if the given image access is in bounds.
+    fn write_image_load_bounds_checks(
+        &mut self,
+        module: &Module,
+        image: Handle<crate::Expression>,
+        address: TexelAddress,
+        func_ctx: &back::FunctionCtx<'_>,
+    ) -> BackendResult {
+        // First, check the level of detail. Only if that is in bounds can we
+        // use it to find the appropriate bounds for the coordinates.
+        if let Some(level) = address.level {
+            write!(self.out, "uint(")?;
+            self.write_expr(module, level, func_ctx)?;
+            write!(self.out, ") < ")?;
+            self.write_image_query_call_start(module, image, ImageQuery::NumLevels, func_ctx)?;
+            write!(self.out, ") && ")?;
+        }
+
+        if let Some(sample) = address.sample {
+            write!(self.out, "uint(")?;
+            self.write_expr(module, sample, func_ctx)?;
+            write!(self.out, ") < ")?;
+            self.write_image_query_call_start(module, image, ImageQuery::NumSamples, func_ctx)?;
+            write!(self.out, ") && ")?;
+        }
+
+        if let Some(array_index) = address.array_index {
+            write!(self.out, "uint(")?;
+            self.write_expr(module, array_index, func_ctx)?;
+            write!(self.out, ") < ")?;
+            self.write_image_query_call_start(module, image, ImageQuery::NumLayers, func_ctx)?;
+            write!(self.out, ") && ")?;
+        }
+
+        // Finally, check if the coordinates are within bounds.
+        let coord_is_vector = matches!(
+            *func_ctx.resolve_type(address.coordinate, &module.types),
+            TypeInner::Vector { .. }
+        );
+        if coord_is_vector {
+            write!(self.out, "all(")?;
+        }
+        self.write_cast_to_uint_scalar_or_vector(module, address.coordinate, func_ctx)?;
+        write!(self.out, " < ")?;
+        if let Some(level) = address.level {
+            self.write_image_query_call_start(module, image, ImageQuery::SizeLevel, func_ctx)?;
+            write!(self.out, ", ")?;
+            self.write_expr(module, level, func_ctx)?;
+        } else {
+            self.write_image_query_call_start(module, image, ImageQuery::Size, func_ctx)?;
+        }
+        write!(self.out, ")")?;
+        if coord_is_vector {
+            write!(self.out, ")")?;
+        }
+
+        Ok(())
+    }
+
+    /// Write `level`, clamped to the number of mip levels of `image`.
+    fn write_restricted_image_level(
+        &mut self,
+        module: &Module,
+        image: Handle<crate::Expression>,
+        level: Handle<crate::Expression>,
+        func_ctx: &back::FunctionCtx<'_>,
+    ) -> BackendResult {
+        write!(self.out, "min(uint(")?;
+        self.write_expr(module, level, func_ctx)?;
+        write!(self.out, "), ")?;
+        self.write_image_query_call_start(module, image, ImageQuery::NumLevels, func_ctx)?;
+        write!(self.out, ") - 1u)")?;
+        Ok(())
+    }
+
+    /// Write a call to the wrapped function for `query` on `image`, up to
+    /// but not including the closing parenthesis, so that the caller can
+    /// add a level of detail argument if needed.
+    fn write_image_query_call_start(
+        &mut self,
+        module: &Module,
+        image: Handle<crate::Expression>,
+        query: ImageQuery,
+        func_ctx: &back::FunctionCtx<'_>,
+    ) -> BackendResult {
+        let TypeInner::Image {
+            dim,
+            arrayed,
+            class,
+        } = *func_ctx.resolve_type(image, &module.types)
+        else {
+            unreachable!("we only query images");
+        };
+        self.write_wrapped_image_query_function_name(WrappedImageQuery {
+            dim,
+            arrayed,
+            class,
+            query,
+        })?;
+        write!(self.out, "(")?;
+        self.write_expr(module, image, func_ctx)?;
+        Ok(())
+    }
+
+    /// Write `expr`, an integer scalar or vector, cast to `uint` or `uintN`.
+    fn write_cast_to_uint_scalar_or_vector(
+        &mut self,
+        module: &Module,
+        expr: Handle<crate::Expression>,
+        func_ctx: &back::FunctionCtx<'_>,
+    ) -> BackendResult {
+        match *func_ctx.resolve_type(expr, &module.types) {
+            TypeInner::Vector { size, .. } => write!(self.out, "uint{}(", size as u8)?,
+            _ => write!(self.out, "uint(")?,
+        }
+        self.write_expr(module, expr, func_ctx)?;
+        write!(self.out, ")")?;
+        Ok(())
+    }
+
     fn write_barrier(&mut self, barrier: crate::Barrier, level: back::Level) -> BackendResult {
         if barrier.contains(crate::Barrier::STORAGE) {
             writeln!(self.out, "{level}DeviceMemoryBarrierWithGroupSync();")?;
@@ -3665,6 +4793,28 @@ impl<'a, W: fmt::Write> super::Writer<'a, W> {
     }
 }
 
+/// Return the [`Storage`] global holding the runtime-sized array that
+/// `expr` refers to.
+///
+/// `expr` must be either the global itself, or an [`AccessIndex`] of its
+/// last member.
+///
+/// [`Storage`]: crate::AddressSpace::Storage
+/// [`AccessIndex`]: crate::Expression::AccessIndex
+pub(super) fn dynamic_array_global(
+    expressions: &crate::Arena<crate::Expression>,
+    expr: Handle<crate::Expression>,
+) -> Handle<crate::GlobalVariable> {
+    match expressions[expr] {
+        crate::Expression::AccessIndex { base, index: _ } => match expressions[base] {
+            crate::Expression::GlobalVariable(handle) => handle,
+            _ => unreachable!(),
+        },
+        crate::Expression::GlobalVariable(handle) => handle,
+        _ => unreachable!(),
+    }
+}
+
 pub(super) struct MatrixType {
     pub(super) columns: crate::VectorSize,
     pub(super) rows: crate::VectorSize,
@@ -3800,3 +4950,54 @@ fn get_inner_matrix_of_global_uniform(
     }
     None
 }
+
+#[test]
+fn test_unimplemented_subgroup_operation() {
+    use crate::valid::{Capabilities, SubgroupOperationSet, ValidationFlags};
+    // create a module with a subgroup reduction the validator accepts
+    let mut module = crate::Module::default();
+    let ty = module.types.insert(
+        crate::Type {
+            name: None,
+            inner: crate::TypeInner::Scalar(crate::Scalar::U32),
+        },
+        Default::default(),
+    );
+    let mut fun = crate::Function::default();
+    let argument = fun.expressions.append(
+        crate::Expression::Literal(crate::Literal::U32(1)),
+        Default::default(),
+    );
+    let result = fun.expressions.append(
+        crate::Expression::SubgroupOperationResult { ty },
+        Default::default(),
+    );
+    fun.body.push(
+        crate::Statement::SubgroupCollectiveOperation {
+            op: crate::SubgroupOperation::Add,
+            collective_op: crate::CollectiveOperation::Reduce,
+            argument,
+            result,
+        },
+        Default::default(),
+    );
+    let fun_handle = module.functions.append(fun, Default::default());
+    // analyse the module
+    let info = valid::Validator::new(ValidationFlags::empty(), Capabilities::SUBGROUP)
+        .subgroup_operations(SubgroupOperationSet::all())
+        .validate(&module)
+        .unwrap();
+    // swap in an exclusive scan computing the minimum, which the validator would reject
+    module.functions[fun_handle].body =
+        crate::Block::from_vec(vec![crate::Statement::SubgroupCollectiveOperation {
+            op: crate::SubgroupOperation::Min,
+            collective_op: crate::CollectiveOperation::ExclusiveScan,
+            argument,
+            result,
+        }]);
+    // process the module
+    let options = Options::default();
+    let mut writer = super::Writer::new(String::new(), &options);
+    let result = writer.write(&module, &info, None);
+    assert!(matches!(result, Err(Error::Unimplemented(_))));
+}
@@ -18,8 +18,10 @@ pub mod spv;
 #[cfg(wgsl_out)]
 pub mod wgsl;
 
-#[cfg(any(hlsl_out, msl_out, spv_out, glsl_out))]
+#[cfg(any(hlsl_out, msl_out, spv_out, glsl_out, wgsl_out))]
 pub mod pipeline_constants;
+#[cfg(any(hlsl_out, msl_out, spv_out, glsl_out))]
+pub mod pointer_arguments;
 
 #[cfg(any(hlsl_out, glsl_out))]
 mod continue_forward;
@@ -99,12 +101,14 @@ pub enum FunctionType {
 }
 
 impl FunctionType {
-    /// Returns true if the function is an entry point for a compute shader.
+    /// Returns true if the function is an entry point for a compute shader,
+    /// or for one of the other stages that run in workgroups: task and mesh.
     pub fn is_compute_entry_point(&self, module: &crate::Module) -> bool {
         match *self {
-            FunctionType::EntryPoint(index) => {
-                module.entry_points[index as usize].stage == crate::ShaderStage::Compute
-            }
+            FunctionType::EntryPoint(index) => matches!(
+                module.entry_points[index as usize].stage,
+                crate::ShaderStage::Compute | crate::ShaderStage::Task | crate::ShaderStage::Mesh
+            ),
             FunctionType::Function(_) => false,
         }
     }
@@ -313,5 +317,7 @@ bitflags::bitflags! {
 #[repr(u32)]
 pub enum RayIntersectionType {
     Triangle = 1,
+    /// A committed intersection that came from a generated AABB hit.
+    Generated = 2,
     BoundingBox = 4,
 }
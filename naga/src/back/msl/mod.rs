@@ -148,6 +148,8 @@ pub enum Error {
     UnsupportedArrayOfType(Handle<crate::Type>),
     #[error("ray tracing is not supported prior to MSL 2.3")]
     UnsupportedRayTracing,
+    #[error("task and mesh shaders are not supported prior to MSL 3.0")]
+    UnsupportedMeshShader,
     #[error("overrides should not be present at this stage")]
     Override,
 }
@@ -405,6 +407,7 @@ impl Options {
                 interpolation,
                 sampling,
                 second_blend_source,
+                per_primitive,
             } => match mode {
                 LocationMode::VertexInput => Ok(ResolvedBinding::Attribute(location)),
                 LocationMode::FragmentOutput => {
@@ -426,7 +429,10 @@ impl Options {
                             "loc"
                         },
                         index: location,
-                        interpolation: {
+                        interpolation: if per_primitive {
+                            // Per-primitive values are never interpolated.
+                            Some(ResolvedInterpolation::Flat)
+                        } else {
                             // unwrap: The verifier ensures that vertex shader outputs and fragment
                             // shader inputs always have fully specified interpolation, and that
                             // sampling is `None` only for Flat interpolation.
@@ -568,9 +574,15 @@ impl ResolvedBinding {
                     Bi::SubgroupId => "simdgroup_index_in_threadgroup",
                     Bi::SubgroupSize => "threads_per_simdgroup",
                     Bi::SubgroupInvocationId => "thread_index_in_simdgroup",
-                    Bi::CullDistance | Bi::ViewIndex | Bi::DrawID => {
-                        return Err(Error::UnsupportedBuiltIn(built_in))
-                    }
+                    // mesh
+                    Bi::CullPrimitive => "primitive_culled",
+                    Bi::CullDistance
+                    | Bi::ViewIndex
+                    | Bi::DrawID
+                    | Bi::MeshTaskSize
+                    | Bi::PointIndex
+                    | Bi::LineIndices
+                    | Bi::TriangleIndices => return Err(Error::UnsupportedBuiltIn(built_in)),
                 };
                 write!(out, "{name}")?;
             }
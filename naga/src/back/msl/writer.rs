@@ -28,10 +28,10 @@ const ATOMIC_REFERENCE: &str = "&";
 
 const RT_NAMESPACE: &str = "metal::raytracing";
 const RAY_QUERY_TYPE: &str = "_RayQuery";
-const RAY_QUERY_FIELD_INTERSECTOR: &str = "intersector";
-const RAY_QUERY_FIELD_INTERSECTION: &str = "intersection";
-const RAY_QUERY_FIELD_READY: &str = "ready";
-const RAY_QUERY_FUN_MAP_INTERSECTION: &str = "_map_intersection_type";
+const RAY_QUERY_FIELD_QUERY: &str = "query";
+const RAY_QUERY_FUN_PARAMS: &str = "_ray_query_params";
+const RAY_QUERY_FUN_MAP_COMMITTED_INTERSECTION: &str = "_map_committed_intersection_type";
+const RAY_QUERY_FUN_MAP_CANDIDATE_INTERSECTION: &str = "_map_candidate_intersection_type";
 
 pub(crate) const ATOMIC_COMP_EXCH_FUNCTION: &str = "naga_atomic_compare_exchange_weak_explicit";
 pub(crate) const MODF_FUNCTION: &str = "naga_modf";
@@ -162,9 +162,11 @@ impl Display for TypeContext<'_> {
                 write!(out, "{}::atomic_{}", NAMESPACE, scalar.to_msl_name())
             }
             crate::TypeInner::Vector { size, scalar } => put_numeric_type(out, scalar, &[size]),
-            crate::TypeInner::Matrix { columns, rows, .. } => {
-                put_numeric_type(out, crate::Scalar::F32, &[rows, columns])
-            }
+            crate::TypeInner::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => put_numeric_type(out, scalar, &[rows, columns]),
             crate::TypeInner::Pointer { base, space } => {
                 let sub = Self {
                     handle: base,
@@ -393,6 +395,10 @@ impl crate::Scalar {
     fn to_msl_name(self) -> &'static str {
         use crate::ScalarKind as Sk;
         match self {
+            Self {
+                kind: Sk::Float,
+                width: 2,
+            } => "half",
             Self {
                 kind: Sk::Float,
                 width: _,
@@ -453,7 +459,7 @@ fn should_pack_struct_member(
     match *ty_inner {
         crate::TypeInner::Vector {
             size: crate::VectorSize::Tri,
-            scalar: scalar @ crate::Scalar { width: 4, .. },
+            scalar: scalar @ crate::Scalar { width: 4 | 2, .. },
         } if is_tight => Some(scalar),
         _ => None,
     }
@@ -481,6 +487,19 @@ fn needs_array_length(ty: Handle<crate::Type>, arena: &crate::UniqueArena<crate:
     }
 }
 
+/// Returns true if `binding` is one of the primitive index built-ins of a
+/// mesh shader, which Metal sets with `metal::mesh::set_index`.
+const fn is_mesh_primitive_indices(binding: Option<&crate::Binding>) -> bool {
+    matches!(
+        binding,
+        Some(&crate::Binding::BuiltIn(
+            crate::BuiltIn::PointIndex
+                | crate::BuiltIn::LineIndices
+                | crate::BuiltIn::TriangleIndices
+        ))
+    )
+}
+
 impl crate::AddressSpace {
     /// Returns true if global variables in this address space are
     /// passed in function arguments. These arguments need to be
@@ -492,7 +511,8 @@ impl crate::AddressSpace {
             | Self::Private
             | Self::WorkGroup
             | Self::PushConstant
-            | Self::Handle => true,
+            | Self::Handle
+            | Self::TaskPayload => true,
             Self::Function => false,
         }
     }
@@ -504,7 +524,7 @@ impl crate::AddressSpace {
             // rely on the actual use of a global by functions. This means we
             // may end up with "const" even if the binding is read-write,
             // and that should be OK.
-            Self::Storage { .. } => true,
+            Self::Storage { .. } | Self::TaskPayload => true,
             // These should always be read-write.
             Self::Private | Self::WorkGroup => false,
             // These translate to `constant` address space, no need for qualifiers.
@@ -521,6 +541,7 @@ impl crate::AddressSpace {
             Self::Storage { .. } => Some("device"),
             Self::Private | Self::Function => Some("thread"),
             Self::WorkGroup => Some("threadgroup"),
+            Self::TaskPayload => Some("object_data"),
         }
     }
 }
@@ -666,6 +687,26 @@ impl<'a> ExpressionContext<'a> {
 struct StatementContext<'a> {
     expression: ExpressionContext<'a>,
     result_struct: Option<&'a str>,
+    mesh_stage: Option<&'a MeshStageOutput<'a>>,
+}
+
+/// The extra entry point argument through which task and mesh shaders write
+/// their outputs.
+enum MeshStageOutput<'a> {
+    /// A task shader's `metal::mesh_grid_properties`, which receives the
+    /// [`MeshTaskSize`] it returns.
+    ///
+    /// [`MeshTaskSize`]: crate::BuiltIn::MeshTaskSize
+    Task { grid_properties: String },
+    /// A mesh shader's `metal::mesh` object.
+    Mesh {
+        output: String,
+        vertex_struct: String,
+        /// The primitive struct, or `None` if the primitives carry no data
+        /// besides their indices.
+        primitive_struct: Option<String>,
+        info: &'a crate::MeshStageInfo,
+    },
 }
 
 impl<W: Write> Writer<W> {
@@ -1328,6 +1369,46 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
+    /// Emit code for a dot product of two packed vectors of four 8-bit integers.
+    ///
+    /// Metal has no native equivalent of [`Dot4I8Packed`] and [`Dot4U8Packed`], so
+    /// both arguments are reinterpreted as `packed_char4` or `packed_uchar4` and
+    /// the products of their components are summed, as in [`put_dot_product`].
+    ///
+    /// [`Dot4I8Packed`]: crate::MathFunction::Dot4I8Packed
+    /// [`Dot4U8Packed`]: crate::MathFunction::Dot4U8Packed
+    /// [`put_dot_product`]: Self::put_dot_product
+    fn put_packed_dot_product(
+        &mut self,
+        arg: Handle<crate::Expression>,
+        arg1: Handle<crate::Expression>,
+        signed: bool,
+        context: &ExpressionContext,
+    ) -> BackendResult {
+        let (packed_type, scalar_type) = if signed {
+            ("packed_char4", "int")
+        } else {
+            ("packed_uchar4", "uint")
+        };
+
+        write!(self.out, "(")?;
+        for index in 0..4 {
+            write!(
+                self.out,
+                " + {scalar_type}(as_type<{NAMESPACE}::{packed_type}>("
+            )?;
+            self.put_expression(arg, context, true)?;
+            write!(
+                self.out,
+                ")[{index}]) * {scalar_type}(as_type<{NAMESPACE}::{packed_type}>("
+            )?;
+            self.put_expression(arg1, context, true)?;
+            write!(self.out, ")[{index}])")?;
+        }
+        write!(self.out, ")")?;
+        Ok(())
+    }
+
     /// Emit code for the sign(i32) expression.
     ///
     fn put_isign(
@@ -1401,6 +1482,21 @@ impl<W: Write> Writer<W> {
                         write!(self.out, "{value}{suffix}")?;
                     }
                 }
+                crate::Literal::F16(value) => {
+                    if value.is_infinite() {
+                        let sign = if value.is_sign_negative() { "-" } else { "" };
+                        write!(self.out, "{sign}INFINITY")?;
+                    } else if value.is_nan() {
+                        write!(self.out, "NAN")?;
+                    } else {
+                        let suffix = if value.to_f32().fract() == 0.0 {
+                            ".0h"
+                        } else {
+                            "h"
+                        };
+                        write!(self.out, "{value}{suffix}")?;
+                    }
+                }
                 crate::Literal::U32(value) => {
                     write!(self.out, "{value}u")?;
                 }
@@ -1911,6 +2007,14 @@ impl<W: Write> Writer<W> {
                             "Correct TypeInner for dot product should be already validated"
                         ),
                     },
+                    fun @ (Mf::Dot4I8Packed | Mf::Dot4U8Packed) => {
+                        return self.put_packed_dot_product(
+                            arg,
+                            arg1.unwrap(),
+                            fun == Mf::Dot4I8Packed,
+                            context,
+                        )
+                    }
                     Mf::Outer => return Err(Error::UnsupportedCall(format!("{fun:?}"))),
                     Mf::Cross => "cross",
                     Mf::Distance => "distance",
@@ -2259,34 +2363,48 @@ impl<W: Write> Writer<W> {
                     return Err(Error::UnsupportedRayTracing);
                 }
 
-                if !committed {
-                    unimplemented!()
-                }
+                let (map_fun, which) = if committed {
+                    (RAY_QUERY_FUN_MAP_COMMITTED_INTERSECTION, "committed")
+                } else {
+                    (RAY_QUERY_FUN_MAP_CANDIDATE_INTERSECTION, "candidate")
+                };
                 let ty = context.module.special_types.ray_intersection.unwrap();
                 let type_name = &self.names[&NameKey::Type(ty)];
-                write!(self.out, "{type_name} {{{RAY_QUERY_FUN_MAP_INTERSECTION}(")?;
+                write!(self.out, "{type_name} {{{map_fun}(")?;
                 self.put_expression(query, context, true)?;
-                write!(self.out, ".{RAY_QUERY_FIELD_INTERSECTION}.type)")?;
+                write!(
+                    self.out,
+                    ".{RAY_QUERY_FIELD_QUERY}.get_{which}_intersection_type())"
+                )?;
+                // Candidate distances are only reported for triangles.
+                let distance = if committed {
+                    "distance"
+                } else {
+                    "triangle_distance"
+                };
                 let fields = [
-                    "distance",
-                    "user_instance_id", // req Metal 2.4
-                    "instance_id",
-                    "", // SBT offset
-                    "geometry_id",
-                    "primitive_id",
-                    "triangle_barycentric_coord",
-                    "triangle_front_facing",
-                    "",                          // padding
-                    "object_to_world_transform", // req Metal 2.4
-                    "world_to_object_transform", // req Metal 2.4
+                    ("get", distance),
+                    ("get", "user_instance_id"), // req Metal 2.4
+                    ("get", "instance_id"),
+                    ("", ""), // SBT offset
+                    ("get", "geometry_id"),
+                    ("get", "primitive_id"),
+                    ("get", "triangle_barycentric_coord"),
+                    ("is", "triangle_front_facing"),
+                    ("", ""),                             // padding
+                    ("get", "object_to_world_transform"), // req Metal 2.4
+                    ("get", "world_to_object_transform"), // req Metal 2.4
                 ];
-                for field in fields {
+                for (prefix, field) in fields {
                     write!(self.out, ", ")?;
                     if field.is_empty() {
                         write!(self.out, "{{}}")?;
                     } else {
                         self.put_expression(query, context, true)?;
-                        write!(self.out, ".{RAY_QUERY_FIELD_INTERSECTION}.{field}")?;
+                        write!(
+                            self.out,
+                            ".{RAY_QUERY_FIELD_QUERY}.{prefix}_{which}_{field}()"
+                        )?;
                     }
                 }
                 write!(self.out, "}}")?;
@@ -2644,6 +2762,258 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
+    /// Pass a task shader's result to its `metal::mesh_grid_properties`.
+    fn put_task_return(
+        &mut self,
+        level: back::Level,
+        expr_handle: Handle<crate::Expression>,
+        context: &StatementContext,
+    ) -> BackendResult {
+        let Some(&MeshStageOutput::Task {
+            ref grid_properties,
+        }) = context.mesh_stage
+        else {
+            unreachable!()
+        };
+        let module = context.expression.module;
+        let result_ty = context.expression.function.result.as_ref().unwrap().ty;
+        write!(
+            self.out,
+            "{level}{grid_properties}.set_threadgroups_per_grid("
+        )?;
+        self.put_expression(expr_handle, &context.expression, true)?;
+        if let crate::TypeInner::Struct { ref members, .. } = module.types[result_ty].inner {
+            let index = members
+                .iter()
+                .position(|member| {
+                    member.binding == Some(crate::Binding::BuiltIn(crate::BuiltIn::MeshTaskSize))
+                })
+                .ok_or_else(|| {
+                    Error::GenericValidation("Expected a mesh task size member".into())
+                })?;
+            let name = &self.names[&NameKey::StructMember(result_ty, index as u32)];
+            write!(self.out, ".{name}")?;
+        }
+        writeln!(self.out, ");")?;
+        writeln!(self.out, "{level}return;")?;
+        Ok(())
+    }
+
+    /// Write a mesh shader's [`MeshFunction`] as calls on its `metal::mesh`
+    /// object.
+    ///
+    /// [`MeshFunction`]: crate::MeshFunction
+    fn put_mesh_function(
+        &mut self,
+        level: back::Level,
+        fun: &crate::MeshFunction,
+        context: &StatementContext,
+    ) -> BackendResult {
+        let Some(&MeshStageOutput::Mesh {
+            ref output,
+            ref vertex_struct,
+            ref primitive_struct,
+            info,
+        }) = context.mesh_stage
+        else {
+            return Err(Error::GenericValidation(
+                "Mesh function outside of a mesh shader entry point".into(),
+            ));
+        };
+        match *fun {
+            crate::MeshFunction::SetMeshOutputs {
+                primitive_count, ..
+            } => {
+                // Metal has no vertex count: only the vertices referenced by
+                // the primitives are used.
+                write!(self.out, "{level}{output}.set_primitive_count(")?;
+                self.put_expression(primitive_count, &context.expression, true)?;
+                writeln!(self.out, ");")?;
+            }
+            crate::MeshFunction::SetVertex { index, value } => {
+                let tmp = self.namer.call("vertex");
+                writeln!(self.out, "{level}{{")?;
+                write!(self.out, "{}const auto {tmp} = ", level.next())?;
+                self.put_expression(value, &context.expression, true)?;
+                writeln!(self.out, ";")?;
+                write!(self.out, "{}{output}.set_vertex(", level.next())?;
+                self.put_expression(index, &context.expression, true)?;
+                write!(self.out, ", ")?;
+                self.put_mesh_output_value(
+                    vertex_struct,
+                    info.vertex_output_type,
+                    &tmp,
+                    context.expression.module,
+                )?;
+                writeln!(self.out, ");")?;
+                writeln!(self.out, "{level}}}")?;
+            }
+            crate::MeshFunction::SetPrimitive { index, value } => {
+                let module = context.expression.module;
+                let ty = info.primitive_output_type;
+                let crate::TypeInner::Struct { ref members, .. } = module.types[ty].inner else {
+                    return Err(Error::GenericValidation(
+                        "Expected a struct for the primitive output type".into(),
+                    ));
+                };
+                let (indices_index, indices_count) = members
+                    .iter()
+                    .enumerate()
+                    .find_map(|(i, member)| match member.binding {
+                        Some(crate::Binding::BuiltIn(crate::BuiltIn::PointIndex)) => Some((i, 1)),
+                        Some(crate::Binding::BuiltIn(crate::BuiltIn::LineIndices)) => Some((i, 2)),
+                        Some(crate::Binding::BuiltIn(crate::BuiltIn::TriangleIndices)) => {
+                            Some((i, 3))
+                        }
+                        _ => None,
+                    })
+                    .ok_or_else(|| {
+                        Error::GenericValidation("Expected a primitive indices member".into())
+                    })?;
+                let indices_name =
+                    self.names[&NameKey::StructMember(ty, indices_index as u32)].clone();
+
+                let tmp = self.namer.call("primitive");
+                let tmp_index = self.namer.call("primitive_index");
+                writeln!(self.out, "{level}{{")?;
+                let inner = level.next();
+                write!(self.out, "{inner}const uint {tmp_index} = ")?;
+                self.put_expression(index, &context.expression, true)?;
+                writeln!(self.out, ";")?;
+                write!(self.out, "{inner}const auto {tmp} = ")?;
+                self.put_expression(value, &context.expression, true)?;
+                writeln!(self.out, ";")?;
+                // Metal takes the vertex indices of all the primitives as a
+                // single flat array.
+                if indices_count == 1 {
+                    writeln!(
+                        self.out,
+                        "{inner}{output}.set_index({tmp_index}, {tmp}.{indices_name});"
+                    )?;
+                } else {
+                    for (i, component) in back::COMPONENTS[..indices_count].iter().enumerate() {
+                        writeln!(
+                            self.out,
+                            "{inner}{output}.set_index({tmp_index} * {indices_count}u + {i}u, {tmp}.{indices_name}.{component});"
+                        )?;
+                    }
+                }
+                if let Some(ref primitive_struct) = *primitive_struct {
+                    write!(self.out, "{inner}{output}.set_primitive({tmp_index}, ")?;
+                    self.put_mesh_output_value(primitive_struct, ty, &tmp, module)?;
+                    writeln!(self.out, ");")?;
+                }
+                writeln!(self.out, "{level}}}")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a `struct_name` initializer holding the members of `tmp`, a
+    /// value of the mesh output type `ty`.
+    ///
+    /// This is the counterpart of [`Writer::write_mesh_output_struct`].
+    fn put_mesh_output_value(
+        &mut self,
+        struct_name: &str,
+        ty: Handle<crate::Type>,
+        tmp: &str,
+        module: &crate::Module,
+    ) -> BackendResult {
+        let crate::TypeInner::Struct { ref members, .. } = module.types[ty].inner else {
+            return Err(Error::GenericValidation(
+                "Expected a struct for the mesh output type".into(),
+            ));
+        };
+        write!(self.out, "{struct_name} {{")?;
+        let mut is_first = true;
+        for (index, member) in members.iter().enumerate() {
+            if is_mesh_primitive_indices(member.binding.as_ref()) {
+                continue;
+            }
+            let comma = if is_first { "" } else { "," };
+            is_first = false;
+            let name = &self.names[&NameKey::StructMember(ty, index as u32)];
+            // Unwrap arrays, as in `put_return_value`.
+            if let crate::TypeInner::Array {
+                size: crate::ArraySize::Constant(size),
+                ..
+            } = module.types[member.ty].inner
+            {
+                write!(self.out, "{comma} {{")?;
+                for j in 0..size.get() {
+                    if j != 0 {
+                        write!(self.out, ",")?;
+                    }
+                    write!(self.out, "{tmp}.{name}.{WRAPPED_ARRAY_FIELD}[{j}]")?;
+                }
+                write!(self.out, "}}")?;
+            } else {
+                write!(self.out, "{comma} {tmp}.{name}")?;
+            }
+        }
+        write!(self.out, " }}")?;
+        Ok(())
+    }
+
+    /// Define a struct named `struct_name` holding the members of the mesh
+    /// output type `ty`, leaving out the primitive indices, which Metal
+    /// takes separately.
+    ///
+    /// Return `false` without writing anything if no members remain.
+    fn write_mesh_output_struct(
+        &mut self,
+        module: &crate::Module,
+        options: &Options,
+        struct_name: &str,
+        ty: Handle<crate::Type>,
+    ) -> Result<bool, Error> {
+        let crate::TypeInner::Struct { ref members, .. } = module.types[ty].inner else {
+            return Err(Error::GenericValidation(
+                "Expected a struct for the mesh output type".into(),
+            ));
+        };
+        if members
+            .iter()
+            .all(|member| is_mesh_primitive_indices(member.binding.as_ref()))
+        {
+            return Ok(false);
+        }
+
+        writeln!(self.out, "struct {struct_name} {{")?;
+        for (index, member) in members.iter().enumerate() {
+            let binding = member
+                .binding
+                .as_ref()
+                .ok_or_else(|| Error::GenericValidation("Expected binding, got None".into()))?;
+            if is_mesh_primitive_indices(Some(binding)) {
+                continue;
+            }
+            let ty_name = TypeContext {
+                handle: member.ty,
+                gctx: module.to_ctx(),
+                names: &self.names,
+                access: crate::StorageAccess::empty(),
+                binding: None,
+                first_time: true,
+            };
+            let name = &self.names[&NameKey::StructMember(ty, index as u32)];
+            let resolved = options.resolve_local_binding(binding, LocationMode::VertexOutput)?;
+            write!(self.out, "{}{} {}", back::INDENT, ty_name, name)?;
+            if let crate::TypeInner::Array {
+                size: crate::ArraySize::Constant(size),
+                ..
+            } = module.types[member.ty].inner
+            {
+                write!(self.out, " [{size}]")?;
+            }
+            resolved.try_fmt(&mut self.out)?;
+            writeln!(self.out, ";")?;
+        }
+        writeln!(self.out, "}};")?;
+        Ok(true)
+    }
+
     fn put_return_value(
         &mut self,
         level: back::Level,
@@ -2787,6 +3157,11 @@ impl<W: Write> Writer<W> {
                             }
                         }
                     }
+                    crate::MathFunction::Dot4I8Packed | crate::MathFunction::Dot4U8Packed => {
+                        // Both arguments are used four times by `put_packed_dot_product`.
+                        self.need_bake_expressions.insert(arg);
+                        self.need_bake_expressions.insert(arg1.unwrap());
+                    }
                     crate::MathFunction::FirstLeadingBit
                     | crate::MathFunction::Pack4xI8
                     | crate::MathFunction::Pack4xU8
@@ -3081,6 +3456,11 @@ impl<W: Write> Writer<W> {
                 crate::Statement::Continue => {
                     writeln!(self.out, "{level}continue;")?;
                 }
+                crate::Statement::Return {
+                    value: Some(expr_handle),
+                } if matches!(context.mesh_stage, Some(&MeshStageOutput::Task { .. })) => {
+                    self.put_task_return(level, expr_handle, context)?;
+                }
                 crate::Statement::Return {
                     value: Some(expr_handle),
                 } => {
@@ -3259,53 +3639,9 @@ impl<W: Write> Writer<W> {
                             //TODO: how to deal with winding?
                             write!(self.out, "{level}")?;
                             self.put_expression(query, &context.expression, true)?;
-                            writeln!(self.out, ".{RAY_QUERY_FIELD_INTERSECTOR}.assume_geometry_type({RT_NAMESPACE}::geometry_type::triangle);")?;
-                            {
-                                let f_opaque = back::RayFlag::CULL_OPAQUE.bits();
-                                let f_no_opaque = back::RayFlag::CULL_NO_OPAQUE.bits();
-                                write!(self.out, "{level}")?;
-                                self.put_expression(query, &context.expression, true)?;
-                                write!(
-                                    self.out,
-                                    ".{RAY_QUERY_FIELD_INTERSECTOR}.set_opacity_cull_mode(("
-                                )?;
-                                self.put_expression(descriptor, &context.expression, true)?;
-                                write!(self.out, ".flags & {f_opaque}) != 0 ? {RT_NAMESPACE}::opacity_cull_mode::opaque : (")?;
-                                self.put_expression(descriptor, &context.expression, true)?;
-                                write!(self.out, ".flags & {f_no_opaque}) != 0 ? {RT_NAMESPACE}::opacity_cull_mode::non_opaque : ")?;
-                                writeln!(self.out, "{RT_NAMESPACE}::opacity_cull_mode::none);")?;
-                            }
-                            {
-                                let f_opaque = back::RayFlag::OPAQUE.bits();
-                                let f_no_opaque = back::RayFlag::NO_OPAQUE.bits();
-                                write!(self.out, "{level}")?;
-                                self.put_expression(query, &context.expression, true)?;
-                                write!(self.out, ".{RAY_QUERY_FIELD_INTERSECTOR}.force_opacity((")?;
-                                self.put_expression(descriptor, &context.expression, true)?;
-                                write!(self.out, ".flags & {f_opaque}) != 0 ? {RT_NAMESPACE}::forced_opacity::opaque : (")?;
-                                self.put_expression(descriptor, &context.expression, true)?;
-                                write!(self.out, ".flags & {f_no_opaque}) != 0 ? {RT_NAMESPACE}::forced_opacity::non_opaque : ")?;
-                                writeln!(self.out, "{RT_NAMESPACE}::forced_opacity::none);")?;
-                            }
-                            {
-                                let flag = back::RayFlag::TERMINATE_ON_FIRST_HIT.bits();
-                                write!(self.out, "{level}")?;
-                                self.put_expression(query, &context.expression, true)?;
-                                write!(
-                                    self.out,
-                                    ".{RAY_QUERY_FIELD_INTERSECTOR}.accept_any_intersection(("
-                                )?;
-                                self.put_expression(descriptor, &context.expression, true)?;
-                                writeln!(self.out, ".flags & {flag}) != 0);")?;
-                            }
-
-                            write!(self.out, "{level}")?;
-                            self.put_expression(query, &context.expression, true)?;
-                            write!(self.out, ".{RAY_QUERY_FIELD_INTERSECTION} = ")?;
-                            self.put_expression(query, &context.expression, true)?;
                             write!(
                                 self.out,
-                                ".{RAY_QUERY_FIELD_INTERSECTOR}.intersect({RT_NAMESPACE}::ray("
+                                ".{RAY_QUERY_FIELD_QUERY}.reset({RT_NAMESPACE}::ray("
                             )?;
                             self.put_expression(descriptor, &context.expression, true)?;
                             write!(self.out, ".origin, ")?;
@@ -3318,11 +3654,9 @@ impl<W: Write> Writer<W> {
                             self.put_expression(acceleration_structure, &context.expression, true)?;
                             write!(self.out, ", ")?;
                             self.put_expression(descriptor, &context.expression, true)?;
-                            write!(self.out, ".cull_mask);")?;
-
-                            write!(self.out, "{level}")?;
-                            self.put_expression(query, &context.expression, true)?;
-                            writeln!(self.out, ".{RAY_QUERY_FIELD_READY} = true;")?;
+                            write!(self.out, ".cull_mask, {RAY_QUERY_FUN_PARAMS}(")?;
+                            self.put_expression(descriptor, &context.expression, true)?;
+                            writeln!(self.out, ".flags));")?;
                         }
                         crate::RayQueryFunction::Proceed { result } => {
                             write!(self.out, "{level}")?;
@@ -3330,17 +3664,30 @@ impl<W: Write> Writer<W> {
                             self.start_baking_expression(result, &context.expression, &name)?;
                             self.named_expressions.insert(result, name);
                             self.put_expression(query, &context.expression, true)?;
-                            writeln!(self.out, ".{RAY_QUERY_FIELD_READY};")?;
-                            //TODO: actually proceed?
-
+                            writeln!(self.out, ".{RAY_QUERY_FIELD_QUERY}.next();")?;
+                        }
+                        crate::RayQueryFunction::GenerateIntersection { hit_t } => {
+                            write!(self.out, "{level}")?;
+                            self.put_expression(query, &context.expression, true)?;
+                            write!(
+                                self.out,
+                                ".{RAY_QUERY_FIELD_QUERY}.commit_bounding_box_intersection("
+                            )?;
+                            self.put_expression(hit_t, &context.expression, true)?;
+                            writeln!(self.out, ");")?;
+                        }
+                        crate::RayQueryFunction::ConfirmIntersection => {
                             write!(self.out, "{level}")?;
                             self.put_expression(query, &context.expression, true)?;
-                            writeln!(self.out, ".{RAY_QUERY_FIELD_READY} = false;")?;
+                            writeln!(
+                                self.out,
+                                ".{RAY_QUERY_FIELD_QUERY}.commit_triangle_intersection();"
+                            )?;
                         }
                         crate::RayQueryFunction::Terminate => {
                             write!(self.out, "{level}")?;
                             self.put_expression(query, &context.expression, true)?;
-                            writeln!(self.out, ".{RAY_QUERY_FIELD_INTERSECTION}.abort();")?;
+                            writeln!(self.out, ".{RAY_QUERY_FIELD_QUERY}.abort();")?;
                         }
                     }
                 }
@@ -3369,6 +3716,48 @@ impl<W: Write> Writer<W> {
                     write!(self.out, "{level}")?;
                     let name = self.namer.call("");
                     self.start_baking_expression(result, &context.expression, &name)?;
+                    if let crate::CollectiveOperation::ClusteredReduce(size) = collective_op {
+                        // There are no clustered SIMD-group functions, so
+                        // combine the values of each cluster with a butterfly
+                        // of shuffles instead.
+                        self.put_expression(argument, &context.expression, true)?;
+                        writeln!(self.out, ";")?;
+                        let mut offset = 1;
+                        while offset < size {
+                            let other = format!("{NAMESPACE}::simd_shuffle_xor({name}, {offset}u)");
+                            write!(self.out, "{level}{name} = ")?;
+                            match op {
+                                crate::SubgroupOperation::Add => {
+                                    write!(self.out, "{name} + {other}")?
+                                }
+                                crate::SubgroupOperation::Mul => {
+                                    write!(self.out, "{name} * {other}")?
+                                }
+                                crate::SubgroupOperation::Max => {
+                                    write!(self.out, "{NAMESPACE}::max({name}, {other})")?
+                                }
+                                crate::SubgroupOperation::Min => {
+                                    write!(self.out, "{NAMESPACE}::min({name}, {other})")?
+                                }
+                                crate::SubgroupOperation::And => {
+                                    write!(self.out, "{name} & {other}")?
+                                }
+                                crate::SubgroupOperation::Or => {
+                                    write!(self.out, "{name} | {other}")?
+                                }
+                                crate::SubgroupOperation::Xor => {
+                                    write!(self.out, "{name} ^ {other}")?
+                                }
+                                crate::SubgroupOperation::All | crate::SubgroupOperation::Any => {
+                                    unreachable!()
+                                }
+                            }
+                            writeln!(self.out, ";")?;
+                            offset *= 2;
+                        }
+                        self.named_expressions.insert(result, name);
+                        continue;
+                    }
                     self.named_expressions.insert(result, name);
                     match (collective_op, op) {
                         (crate::CollectiveOperation::Reduce, crate::SubgroupOperation::All) => {
@@ -3414,7 +3803,11 @@ impl<W: Write> Writer<W> {
                             crate::CollectiveOperation::InclusiveScan,
                             crate::SubgroupOperation::Mul,
                         ) => write!(self.out, "{NAMESPACE}::simd_prefix_inclusive_product(")?,
-                        _ => unimplemented!(),
+                        _ => {
+                            return Err(Error::FeatureNotImplemented(
+                                "subgroup operation".to_string(),
+                            ))
+                        }
                     }
                     self.put_expression(argument, &context.expression, true)?;
                     writeln!(self.out, ");")?;
@@ -3447,6 +3840,12 @@ impl<W: Write> Writer<W> {
                         crate::GatherMode::ShuffleXor(_) => {
                             write!(self.out, "{NAMESPACE}::simd_shuffle_xor(")?;
                         }
+                        crate::GatherMode::QuadBroadcast(_) => {
+                            write!(self.out, "{NAMESPACE}::quad_broadcast(")?;
+                        }
+                        crate::GatherMode::QuadSwap(_) => {
+                            write!(self.out, "{NAMESPACE}::quad_shuffle_xor(")?;
+                        }
                     }
                     self.put_expression(argument, &context.expression, true)?;
                     match mode {
@@ -3455,13 +3854,22 @@ impl<W: Write> Writer<W> {
                         | crate::GatherMode::Shuffle(index)
                         | crate::GatherMode::ShuffleDown(index)
                         | crate::GatherMode::ShuffleUp(index)
-                        | crate::GatherMode::ShuffleXor(index) => {
+                        | crate::GatherMode::ShuffleXor(index)
+                        | crate::GatherMode::QuadBroadcast(index) => {
                             write!(self.out, ", ")?;
                             self.put_expression(index, &context.expression, true)?;
                         }
+                        crate::GatherMode::QuadSwap(direction) => match direction {
+                            crate::Direction::X => write!(self.out, ", 1u")?,
+                            crate::Direction::Y => write!(self.out, ", 2u")?,
+                            crate::Direction::Diagonal => write!(self.out, ", 3u")?,
+                        },
                     }
                     writeln!(self.out, ");")?;
                 }
+                crate::Statement::MeshFunction(ref fun) => {
+                    self.put_mesh_function(level, fun, context)?;
+                }
             }
         }
 
@@ -3670,26 +4078,53 @@ impl<W: Write> Writer<W> {
     fn put_ray_query_type(&mut self) -> BackendResult {
         let tab = back::INDENT;
         writeln!(self.out, "struct {RAY_QUERY_TYPE} {{")?;
-        let full_type = format!("{RT_NAMESPACE}::intersector<{RT_NAMESPACE}::instancing, {RT_NAMESPACE}::triangle_data, {RT_NAMESPACE}::world_space_data>");
-        writeln!(self.out, "{tab}{full_type} {RAY_QUERY_FIELD_INTERSECTOR};")?;
-        writeln!(
-            self.out,
-            "{tab}{full_type}::result_type {RAY_QUERY_FIELD_INTERSECTION};"
-        )?;
-        writeln!(self.out, "{tab}bool {RAY_QUERY_FIELD_READY} = false;")?;
+        let full_type = format!("{RT_NAMESPACE}::intersection_query<{RT_NAMESPACE}::instancing, {RT_NAMESPACE}::triangle_data>");
+        writeln!(self.out, "{tab}{full_type} {RAY_QUERY_FIELD_QUERY};")?;
         writeln!(self.out, "}};")?;
-        writeln!(self.out, "constexpr {NAMESPACE}::uint {RAY_QUERY_FUN_MAP_INTERSECTION}(const {RT_NAMESPACE}::intersection_type ty) {{")?;
-        let v_triangle = back::RayIntersectionType::Triangle as u32;
-        let v_bbox = back::RayIntersectionType::BoundingBox as u32;
-        writeln!(
-            self.out,
-            "{tab}return ty=={RT_NAMESPACE}::intersection_type::triangle ? {v_triangle} : "
-        )?;
+
         writeln!(
             self.out,
-            "{tab}{tab}ty=={RT_NAMESPACE}::intersection_type::bounding_box ? {v_bbox} : 0;"
+            "{RT_NAMESPACE}::intersection_params {RAY_QUERY_FUN_PARAMS}(const {NAMESPACE}::uint flags) {{"
         )?;
+        writeln!(self.out, "{tab}{RT_NAMESPACE}::intersection_params params;")?;
+        {
+            let f_opaque = back::RayFlag::CULL_OPAQUE.bits();
+            let f_no_opaque = back::RayFlag::CULL_NO_OPAQUE.bits();
+            writeln!(self.out, "{tab}params.set_opacity_cull_mode((flags & {f_opaque}) != 0 ? {RT_NAMESPACE}::opacity_cull_mode::opaque : (flags & {f_no_opaque}) != 0 ? {RT_NAMESPACE}::opacity_cull_mode::non_opaque : {RT_NAMESPACE}::opacity_cull_mode::none);")?;
+        }
+        {
+            let f_opaque = back::RayFlag::OPAQUE.bits();
+            let f_no_opaque = back::RayFlag::NO_OPAQUE.bits();
+            writeln!(self.out, "{tab}params.force_opacity((flags & {f_opaque}) != 0 ? {RT_NAMESPACE}::forced_opacity::opaque : (flags & {f_no_opaque}) != 0 ? {RT_NAMESPACE}::forced_opacity::non_opaque : {RT_NAMESPACE}::forced_opacity::none);")?;
+        }
+        {
+            let flag = back::RayFlag::TERMINATE_ON_FIRST_HIT.bits();
+            writeln!(
+                self.out,
+                "{tab}params.accept_any_intersection((flags & {flag}) != 0);"
+            )?;
+        }
+        writeln!(self.out, "{tab}return params;")?;
         writeln!(self.out, "}}")?;
+
+        let v_triangle = back::RayIntersectionType::Triangle as u32;
+        let v_generated = back::RayIntersectionType::Generated as u32;
+        let v_bbox = back::RayIntersectionType::BoundingBox as u32;
+        for (fun_name, v_bbox) in [
+            (RAY_QUERY_FUN_MAP_COMMITTED_INTERSECTION, v_generated),
+            (RAY_QUERY_FUN_MAP_CANDIDATE_INTERSECTION, v_bbox),
+        ] {
+            writeln!(self.out, "constexpr {NAMESPACE}::uint {fun_name}(const {RT_NAMESPACE}::intersection_type ty) {{")?;
+            writeln!(
+                self.out,
+                "{tab}return ty=={RT_NAMESPACE}::intersection_type::triangle ? {v_triangle} : "
+            )?;
+            writeln!(
+                self.out,
+                "{tab}{tab}ty=={RT_NAMESPACE}::intersection_type::bounding_box ? {v_bbox} : 0;"
+            )?;
+            writeln!(self.out, "}}")?;
+        }
         Ok(())
     }
 
@@ -3822,18 +4257,17 @@ impl<W: Write> Writer<W> {
             match type_key {
                 &crate::PredeclaredType::ModfResult { size, width }
                 | &crate::PredeclaredType::FrexpResult { size, width } => {
+                    let scalar_name = match width {
+                        2 => "half",
+                        8 => "double",
+                        _ => "float",
+                    };
                     let arg_type_name_owner;
                     let arg_type_name = if let Some(size) = size {
-                        arg_type_name_owner = format!(
-                            "{NAMESPACE}::{}{}",
-                            if width == 8 { "double" } else { "float" },
-                            size as u8
-                        );
+                        arg_type_name_owner = format!("{NAMESPACE}::{scalar_name}{}", size as u8);
                         &arg_type_name_owner
-                    } else if width == 8 {
-                        "double"
                     } else {
-                        "float"
+                        scalar_name
                     };
 
                     let other_type_name_owner;
@@ -4882,6 +5316,7 @@ template <typename A>
                     pipeline_options,
                 },
                 result_struct: None,
+                mesh_stage: None,
             };
 
             for (local_handle, local) in fun.local_variables.iter() {
@@ -4952,8 +5387,28 @@ template <typename A>
                     LocationMode::Uniform,
                     false,
                 ),
+                crate::ShaderStage::Task => (
+                    "[[object]]",
+                    LocationMode::Uniform,
+                    LocationMode::Uniform,
+                    false,
+                ),
+                crate::ShaderStage::Mesh => (
+                    "[[mesh]]",
+                    LocationMode::Uniform,
+                    LocationMode::VertexOutput,
+                    false,
+                ),
             };
 
+            if matches!(
+                ep.stage,
+                crate::ShaderStage::Task | crate::ShaderStage::Mesh
+            ) && options.lang_version < (3, 0)
+            {
+                return Err(Error::UnsupportedMeshShader);
+            }
+
             // Should this entry point be modified to do vertex pulling?
             let do_vertex_pulling = can_vertex_pull
                 && pipeline_options.vertex_pulling_transform
@@ -5020,7 +5475,8 @@ template <typename A>
                         }
                         crate::AddressSpace::Function
                         | crate::AddressSpace::Private
-                        | crate::AddressSpace::WorkGroup => {}
+                        | crate::AddressSpace::WorkGroup
+                        | crate::AddressSpace::TaskPayload => {}
                     }
                 }
                 if needs_buffer_sizes {
@@ -5034,7 +5490,7 @@ template <typename A>
                 info.entry_point_names.push(Err(err));
                 continue;
             }
-            let fun_name = &self.names[&NameKey::EntryPoint(ep_index as _)];
+            let fun_name = self.names[&NameKey::EntryPoint(ep_index as _)].clone();
             info.entry_point_names.push(Ok(fun_name.clone()));
 
             writeln!(self.out)?;
@@ -5144,7 +5600,8 @@ template <typename A>
             let stage_out_name = self.namer.call(&format!("{fun_name}Output"));
             let result_member_name = self.namer.call("member");
             let result_type_name = match fun.result {
-                Some(ref result) => {
+                // Task shaders pass their result to the mesh grid instead.
+                Some(ref result) if ep.stage != crate::ShaderStage::Task => {
                     let mut result_members = Vec::new();
                     if let crate::TypeInner::Struct { ref members, .. } =
                         module.types[result.ty].inner
@@ -5216,7 +5673,43 @@ template <typename A>
                     writeln!(self.out, "}};")?;
                     &stage_out_name
                 }
-                None => "void",
+                _ => "void",
+            };
+
+            // Task and mesh shaders write their outputs through an extra
+            // argument. For mesh shaders, define the vertex and primitive
+            // types it holds, named `<fun>VertexOutput` and
+            // `<fun>PrimitiveOutput`.
+            let mesh_stage = match ep.stage {
+                crate::ShaderStage::Task => Some(MeshStageOutput::Task {
+                    grid_properties: self.namer.call("grid_properties"),
+                }),
+                crate::ShaderStage::Mesh => {
+                    let mesh_info = ep.mesh_info.as_ref().ok_or_else(|| {
+                        Error::GenericValidation("Expected mesh stage info, got None".into())
+                    })?;
+                    let vertex_struct = self.namer.call(&format!("{fun_name}VertexOutput"));
+                    self.write_mesh_output_struct(
+                        module,
+                        options,
+                        &vertex_struct,
+                        mesh_info.vertex_output_type,
+                    )?;
+                    let primitive_struct = self.namer.call(&format!("{fun_name}PrimitiveOutput"));
+                    let has_primitive_data = self.write_mesh_output_struct(
+                        module,
+                        options,
+                        &primitive_struct,
+                        mesh_info.primitive_output_type,
+                    )?;
+                    Some(MeshStageOutput::Mesh {
+                        output: self.namer.call("mesh_output"),
+                        vertex_struct,
+                        primitive_struct: has_primitive_data.then_some(primitive_struct),
+                        info: mesh_info,
+                    })
+                }
+                _ => None,
             };
 
             // If we're doing a vertex pulling transform, define the buffer
@@ -5299,6 +5792,48 @@ template <typename A>
                 writeln!(self.out)?;
             }
 
+            match mesh_stage {
+                Some(MeshStageOutput::Task {
+                    ref grid_properties,
+                }) => {
+                    let separator = if is_first_argument {
+                        is_first_argument = false;
+                        ' '
+                    } else {
+                        ','
+                    };
+                    writeln!(
+                        self.out,
+                        "{separator} {NAMESPACE}::mesh_grid_properties {grid_properties}"
+                    )?;
+                }
+                Some(MeshStageOutput::Mesh {
+                    ref output,
+                    ref vertex_struct,
+                    ref primitive_struct,
+                    info,
+                }) => {
+                    let separator = if is_first_argument {
+                        is_first_argument = false;
+                        ' '
+                    } else {
+                        ','
+                    };
+                    let primitive_struct = primitive_struct.as_deref().unwrap_or("void");
+                    let topology = match info.topology {
+                        crate::MeshOutputTopology::Points => "point",
+                        crate::MeshOutputTopology::Lines => "line",
+                        crate::MeshOutputTopology::Triangles => "triangle",
+                    };
+                    writeln!(
+                        self.out,
+                        "{separator} {NAMESPACE}::mesh<{vertex_struct}, {primitive_struct}, {}, {}, {NAMESPACE}::topology::{topology}> {output}",
+                        info.max_vertices, info.max_primitives,
+                    )?;
+                }
+                None => {}
+            }
+
             let need_workgroup_variables_initialization =
                 self.need_workgroup_variables_initialization(options, ep, module, fun_info);
 
@@ -5441,7 +5976,7 @@ template <typename A>
                 // the resolves have already been checked for `!fake_missing_bindings` case
                 let resolved = match var.space {
                     crate::AddressSpace::PushConstant => options.resolve_push_constants(ep).ok(),
-                    crate::AddressSpace::WorkGroup => None,
+                    crate::AddressSpace::WorkGroup | crate::AddressSpace::TaskPayload => None,
                     _ => options
                         .resolve_resource_binding(ep, var.binding.as_ref().unwrap())
                         .ok(),
@@ -5472,6 +6007,9 @@ template <typename A>
                 if let Some(resolved) = resolved {
                     resolved.try_fmt(&mut self.out)?;
                 }
+                if var.space == crate::AddressSpace::TaskPayload {
+                    write!(self.out, " [[payload]]")?;
+                }
                 if let Some(value) = var.init {
                     write!(self.out, " = ")?;
                     self.put_const_expression(value, module, mod_info)?;
@@ -5782,6 +6320,7 @@ template <typename A>
                     pipeline_options,
                 },
                 result_struct: Some(&stage_out_name),
+                mesh_stage: mesh_stage.as_ref(),
             };
 
             // Finally, declare all the local variables that we need
@@ -6150,3 +6689,53 @@ impl crate::AtomicFunction {
         })
     }
 }
+
+#[test]
+fn test_unimplemented_subgroup_operation() {
+    use crate::valid::{Capabilities, SubgroupOperationSet, ValidationFlags};
+    // create a module with a subgroup reduction the validator accepts
+    let mut module = crate::Module::default();
+    let ty = module.types.insert(
+        crate::Type {
+            name: None,
+            inner: crate::TypeInner::Scalar(crate::Scalar::U32),
+        },
+        Default::default(),
+    );
+    let mut fun = crate::Function::default();
+    let argument = fun.expressions.append(
+        crate::Expression::Literal(crate::Literal::U32(1)),
+        Default::default(),
+    );
+    let result = fun.expressions.append(
+        crate::Expression::SubgroupOperationResult { ty },
+        Default::default(),
+    );
+    fun.body.push(
+        crate::Statement::SubgroupCollectiveOperation {
+            op: crate::SubgroupOperation::Add,
+            collective_op: crate::CollectiveOperation::Reduce,
+            argument,
+            result,
+        },
+        Default::default(),
+    );
+    let fun_handle = module.functions.append(fun, Default::default());
+    // analyse the module
+    let info = valid::Validator::new(ValidationFlags::empty(), Capabilities::SUBGROUP)
+        .subgroup_operations(SubgroupOperationSet::all())
+        .validate(&module)
+        .unwrap();
+    // swap in an exclusive scan computing the minimum, which the validator would reject
+    module.functions[fun_handle].body =
+        crate::Block::from_vec(vec![crate::Statement::SubgroupCollectiveOperation {
+            op: crate::SubgroupOperation::Min,
+            collective_op: crate::CollectiveOperation::ExclusiveScan,
+            argument,
+            result,
+        }]);
+    // process the module
+    let mut writer = Writer::new(String::new());
+    let result = writer.write(&module, &info, &Default::default(), &Default::default());
+    assert!(matches!(result, Err(Error::FeatureNotImplemented(_))));
+}
@@ -0,0 +1,502 @@
+use crate::{
+    arena::{HandleSet, HandleVec},
+    front::Typifier,
+    proc::{
+        adjust::{adjust_block, adjust_expr},
+        ResolveContext, ResolveError,
+    },
+    valid::{Capabilities, ModuleInfo, ValidationError, ValidationFlags, Validator},
+    AddressSpace, Arena, Block, Expression, FastHashMap, Function, FunctionArgument,
+    GlobalVariable, Handle, Module, Scalar, Span, Statement, Type, TypeInner, WithSpan,
+};
+use std::{borrow::Cow, mem};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone)]
+pub enum PointerArgumentError {
+    #[error(
+        "Argument {index} of a call to function {function:?} is not a pointer into a global variable"
+    )]
+    UnsupportedArgument {
+        function: Option<String>,
+        index: usize,
+    },
+    #[error(transparent)]
+    ResolveError(#[from] ResolveError),
+    #[error(transparent)]
+    ValidationError(#[from] WithSpan<ValidationError>),
+}
+
+/// Replace all functions taking `uniform`, `storage` or `workgroup` pointers
+/// with specialized copies.
+///
+/// WGSL's `unrestricted_pointer_parameters` language extension lets functions
+/// take pointers into these address spaces, but most backends have no way of
+/// expressing such arguments. This pass creates a copy of each such function
+/// for every distinct global variable and access path it is called with,
+/// where the pointer arguments are replaced by the access path itself. Any
+/// dynamic indices along the path are passed as additional arguments.
+///
+/// Validating such a module requires
+/// [`Capabilities::UNRESTRICTED_POINTER_PARAMETERS`], which the result no
+/// longer needs.
+///
+/// If no changes are needed, this just returns `Cow::Borrowed`
+/// references to `module` and `module_info`. Otherwise, it clones
+/// `module`, removes the original functions, and returns `Cow::Owned`
+/// values holding the rewritten module and its validation results.
+pub fn specialize_pointer_arguments<'a>(
+    module: &'a Module,
+    module_info: &'a ModuleInfo,
+) -> Result<(Cow<'a, Module>, Cow<'a, ModuleInfo>), PointerArgumentError> {
+    let mut generic = HandleSet::for_arena(&module.functions);
+    for (handle, function) in module.functions.iter() {
+        if function
+            .arguments
+            .iter()
+            .any(|argument| needs_specialization(&module.types[argument.ty].inner))
+        {
+            generic.insert(handle);
+        }
+    }
+    if generic.iter().next().is_none() {
+        return Ok((Cow::Borrowed(module), Cow::Borrowed(module_info)));
+    }
+
+    let mut module = module.clone();
+    let mut specializer = Specializer {
+        pending: module
+            .functions
+            .iter()
+            .map(|(handle, _)| handle)
+            .filter(|&handle| !generic.contains(handle))
+            .collect(),
+        generic,
+        specializations: FastHashMap::default(),
+    };
+
+    for index in 0..module.entry_points.len() {
+        let mut function = mem::take(&mut module.entry_points[index].function);
+        let result = specializer.process_function(&mut module, &mut function);
+        module.entry_points[index].function = function;
+        result?;
+    }
+
+    // Specializing a function may produce calls to further functions
+    // that need specializing, so keep going until we run out.
+    while let Some(handle) = specializer.pending.pop() {
+        let mut function = mem::take(&mut module.functions[handle]);
+        let result = specializer.process_function(&mut module, &mut function);
+        module.functions[handle] = function;
+        result?;
+    }
+
+    reorder_functions(&mut module, &specializer.generic)?;
+
+    let mut validator = Validator::new(ValidationFlags::all(), Capabilities::all());
+    let module_info = validator.validate(&module)?;
+
+    Ok((Cow::Owned(module), Cow::Owned(module_info)))
+}
+
+/// Return true if a function argument of type `ty` must be specialized away.
+const fn needs_specialization(ty: &TypeInner) -> bool {
+    matches!(
+        ty.pointer_space(),
+        Some(AddressSpace::Uniform | AddressSpace::Storage { .. } | AddressSpace::WorkGroup)
+    )
+}
+
+/// One step of the access path from a global variable to a pointer argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum PathStep {
+    /// An [`AccessIndex`](Expression::AccessIndex) with the given index.
+    Index(u32),
+
+    /// An [`Access`](Expression::Access) whose index is passed to the
+    /// specialized function as an extra argument of the given type.
+    Dynamic(Scalar),
+}
+
+/// The access path a pointer argument takes from its global variable.
+type PointerPath = (Handle<GlobalVariable>, Vec<PathStep>);
+
+struct Specializer {
+    /// Functions taking pointers that need specializing.
+    generic: HandleSet<Function>,
+
+    /// Specialized functions we've already created, keyed by the original
+    /// function and the paths of its pointer arguments.
+    specializations: FastHashMap<(Handle<Function>, Vec<PointerPath>), Handle<Function>>,
+
+    /// Functions whose calls still need to be rewritten.
+    pending: Vec<Handle<Function>>,
+}
+
+impl Specializer {
+    /// Redirect all calls to generic functions in `function` to specialized copies.
+    fn process_function(
+        &mut self,
+        module: &mut Module,
+        function: &mut Function,
+    ) -> Result<(), PointerArgumentError> {
+        let mut typifier = Typifier::new();
+        if let Some((last, _)) = function.expressions.iter().next_back() {
+            let resolve_ctx =
+                ResolveContext::with_locals(module, &function.local_variables, &function.arguments);
+            typifier.grow(last, &function.expressions, &resolve_ctx)?;
+        }
+
+        let expressions = &mut function.expressions;
+        for_each_call(&mut function.body, &mut |callee, arguments, result| {
+            if !self.generic.contains(*callee) {
+                return Ok(());
+            }
+            let specialized =
+                self.rewrite_call(module, expressions, &typifier, *callee, arguments)?;
+            *callee = specialized;
+            if let Some(result) = result {
+                expressions[result] = Expression::CallResult(specialized);
+            }
+            Ok(())
+        })
+    }
+
+    /// Rewrite the `arguments` of a call to `callee` for its specialized copy.
+    ///
+    /// Return the handle of the specialized function, creating it if needed.
+    fn rewrite_call(
+        &mut self,
+        module: &mut Module,
+        expressions: &Arena<Expression>,
+        typifier: &Typifier,
+        callee: Handle<Function>,
+        arguments: &mut Vec<Handle<Expression>>,
+    ) -> Result<Handle<Function>, PointerArgumentError> {
+        let mut paths = Vec::new();
+        let mut new_arguments = Vec::with_capacity(arguments.len());
+        let mut indices = Vec::new();
+        for (index, (&argument, parameter)) in arguments
+            .iter()
+            .zip(module.functions[callee].arguments.iter())
+            .enumerate()
+        {
+            if !needs_specialization(&module.types[parameter.ty].inner) {
+                new_arguments.push(argument);
+                continue;
+            }
+
+            let unsupported = || PointerArgumentError::UnsupportedArgument {
+                function: module.functions[callee].name.clone(),
+                index,
+            };
+
+            // Walk from the pointer back to its global variable.
+            let mut steps = Vec::new();
+            let mut argument_indices = Vec::new();
+            let mut pointer = argument;
+            let global = loop {
+                match expressions[pointer] {
+                    Expression::GlobalVariable(global) => break global,
+                    Expression::AccessIndex { base, index } => {
+                        steps.push(PathStep::Index(index));
+                        pointer = base;
+                    }
+                    Expression::Access { base, index } => {
+                        let TypeInner::Scalar(scalar) = *typifier[index].inner_with(&module.types)
+                        else {
+                            return Err(unsupported());
+                        };
+                        steps.push(PathStep::Dynamic(scalar));
+                        argument_indices.push(index);
+                        pointer = base;
+                    }
+                    _ => return Err(unsupported()),
+                }
+            };
+            steps.reverse();
+            argument_indices.reverse();
+            paths.push((global, steps));
+            indices.extend(argument_indices);
+        }
+        new_arguments.extend(indices);
+        *arguments = new_arguments;
+
+        let key = (callee, paths);
+        if let Some(&specialized) = self.specializations.get(&key) {
+            return Ok(specialized);
+        }
+        let function = specialize(module, callee, &key.1);
+        let span = module.functions.get_span(callee);
+        let specialized = module.functions.append(function, span);
+        self.specializations.insert(key, specialized);
+        self.pending.push(specialized);
+        Ok(specialized)
+    }
+}
+
+/// How an argument of a generic function is represented in its specialization.
+enum ArgumentMapping {
+    /// The argument is kept, at the given index.
+    Kept(u32),
+
+    /// The argument is replaced by the access path with the given index.
+    Path(usize),
+}
+
+/// Build a copy of `callee` whose pointer arguments are replaced by `paths`.
+fn specialize(module: &mut Module, callee: Handle<Function>, paths: &[PointerPath]) -> Function {
+    let index_types: Vec<Handle<Type>> = paths
+        .iter()
+        .flat_map(|&(_, ref steps)| steps.iter())
+        .filter_map(|step| match *step {
+            PathStep::Index(_) => None,
+            PathStep::Dynamic(scalar) => Some(scalar),
+        })
+        .map(|scalar| {
+            module.types.insert(
+                Type {
+                    name: None,
+                    inner: TypeInner::Scalar(scalar),
+                },
+                Span::UNDEFINED,
+            )
+        })
+        .collect();
+
+    let original = &module.functions[callee];
+    let mut function = Function {
+        name: original.name.clone(),
+        result: original.result.clone(),
+        local_variables: original.local_variables.clone(),
+        diagnostic_filter_leaf: original.diagnostic_filter_leaf,
+        ..Function::default()
+    };
+
+    // Keep all the arguments that aren't pointers to be specialized, and
+    // follow them with the dynamic indices of each access path.
+    let mut argument_map = Vec::with_capacity(original.arguments.len());
+    let mut path_names = Vec::with_capacity(paths.len());
+    for argument in original.arguments.iter() {
+        if needs_specialization(&module.types[argument.ty].inner) {
+            argument_map.push(ArgumentMapping::Path(path_names.len()));
+            path_names.push(argument.name.as_ref());
+        } else {
+            argument_map.push(ArgumentMapping::Kept(function.arguments.len() as u32));
+            function.arguments.push(argument.clone());
+        }
+    }
+    let mut index_types = index_types.into_iter();
+    let mut path_roots = Vec::with_capacity(paths.len());
+    for (&(global, ref steps), name) in paths.iter().zip(path_names) {
+        let root = function
+            .expressions
+            .append(Expression::GlobalVariable(global), Span::UNDEFINED);
+        let mut indices = Vec::new();
+        for step in steps.iter() {
+            if let PathStep::Dynamic(_) = *step {
+                let index = function.arguments.len() as u32;
+                function.arguments.push(FunctionArgument {
+                    name: name.map(|name| format!("{name}_index")),
+                    ty: index_types.next().unwrap(),
+                    binding: None,
+                });
+                indices.push(
+                    function
+                        .expressions
+                        .append(Expression::FunctionArgument(index), Span::UNDEFINED),
+                );
+            }
+        }
+        path_roots.push((root, indices));
+    }
+
+    // Build the access paths themselves after all the expressions above,
+    // so that they can be covered by a single `Emit`.
+    let emit_start = function.expressions.len();
+    let mut path_pointers = Vec::with_capacity(paths.len());
+    for (&(_, ref steps), (mut pointer, indices)) in paths.iter().zip(path_roots) {
+        let mut indices = indices.into_iter();
+        for step in steps.iter() {
+            let expression = match *step {
+                PathStep::Index(index) => Expression::AccessIndex {
+                    base: pointer,
+                    index,
+                },
+                PathStep::Dynamic(_) => Expression::Access {
+                    base: pointer,
+                    index: indices.next().unwrap(),
+                },
+            };
+            pointer = function.expressions.append(expression, Span::UNDEFINED);
+        }
+        path_pointers.push(pointer);
+    }
+    let emitted = function.expressions.range_from(emit_start);
+    if emitted.first_and_last().is_some() {
+        function
+            .body
+            .push(Statement::Emit(emitted), Span::UNDEFINED);
+    }
+
+    let mut expression_map = HandleVec::with_capacity(original.expressions.len());
+    for (handle, expression) in original.expressions.iter() {
+        let span = original.expressions.get_span(handle);
+        let new_handle = match *expression {
+            Expression::FunctionArgument(index) => match argument_map[index as usize] {
+                ArgumentMapping::Kept(index) => function
+                    .expressions
+                    .append(Expression::FunctionArgument(index), span),
+                ArgumentMapping::Path(path) => path_pointers[path],
+            },
+            _ => {
+                let mut expression = expression.clone();
+                adjust_expr(&expression_map, &mut expression);
+                function.expressions.append(expression, span)
+            }
+        };
+        expression_map.insert(handle, new_handle);
+    }
+
+    let mut body = original.body.clone();
+    adjust_block(&expression_map, &mut body);
+    function.body.extend_block(body);
+
+    for (_, local) in function.local_variables.iter_mut() {
+        if let Some(ref mut init) = local.init {
+            *init = expression_map[*init];
+        }
+    }
+    for (&handle, name) in original.named_expressions.iter() {
+        function
+            .named_expressions
+            .insert(expression_map[handle], name.clone());
+    }
+
+    function
+}
+
+/// Remove the `generic` functions from `module`, and sort the rest so that
+/// every function comes after the functions it calls.
+fn reorder_functions(
+    module: &mut Module,
+    generic: &HandleSet<Function>,
+) -> Result<(), PointerArgumentError> {
+    let mut order = Vec::with_capacity(module.functions.len());
+    let mut unplaced = FastHashMap::default();
+    for (handle, function, span) in module.functions.drain() {
+        if !generic.contains(handle) {
+            order.push(handle);
+            unplaced.insert(handle, (function, span));
+        }
+    }
+
+    let mut new_handles = FastHashMap::default();
+    for handle in order {
+        place_function(handle, &mut unplaced, module, &mut new_handles)?;
+    }
+
+    let adjust = |function: &mut Function| {
+        for (_, expression) in function.expressions.iter_mut() {
+            if let Expression::CallResult(ref mut callee) = *expression {
+                *callee = new_handles[callee];
+            }
+        }
+        for_each_call(&mut function.body, &mut |callee, _, _| {
+            *callee = new_handles[callee];
+            Ok(())
+        })
+    };
+    for (_, function) in module.functions.iter_mut() {
+        adjust(function)?;
+    }
+    for entry_point in module.entry_points.iter_mut() {
+        adjust(&mut entry_point.function)?;
+    }
+
+    Ok(())
+}
+
+/// Append `handle`'s function to `module`, after all the functions it calls.
+fn place_function(
+    handle: Handle<Function>,
+    unplaced: &mut FastHashMap<Handle<Function>, (Function, Span)>,
+    module: &mut Module,
+    new_handles: &mut FastHashMap<Handle<Function>, Handle<Function>>,
+) -> Result<(), PointerArgumentError> {
+    let Some((mut function, span)) = unplaced.remove(&handle) else {
+        return Ok(());
+    };
+
+    let mut callees = Vec::new();
+    for_each_call(&mut function.body, &mut |callee, _, _| {
+        callees.push(*callee);
+        Ok(())
+    })?;
+    for callee in callees {
+        place_function(callee, unplaced, module, new_handles)?;
+    }
+
+    new_handles.insert(handle, module.functions.append(function, span));
+    Ok(())
+}
+
+/// Call `f` on the callee, arguments and result of every [`Call`] in `block`.
+///
+/// [`Call`]: Statement::Call
+fn for_each_call(
+    block: &mut Block,
+    f: &mut impl FnMut(
+        &mut Handle<Function>,
+        &mut Vec<Handle<Expression>>,
+        Option<Handle<Expression>>,
+    ) -> Result<(), PointerArgumentError>,
+) -> Result<(), PointerArgumentError> {
+    for stmt in block.iter_mut() {
+        match *stmt {
+            Statement::Call {
+                ref mut function,
+                ref mut arguments,
+                result,
+            } => f(function, arguments, result)?,
+            Statement::Block(ref mut block) => for_each_call(block, f)?,
+            Statement::If {
+                ref mut accept,
+                ref mut reject,
+                ..
+            } => {
+                for_each_call(accept, f)?;
+                for_each_call(reject, f)?;
+            }
+            Statement::Switch { ref mut cases, .. } => {
+                for case in cases.iter_mut() {
+                    for_each_call(&mut case.body, f)?;
+                }
+            }
+            Statement::Loop {
+                ref mut body,
+                ref mut continuing,
+                ..
+            } => {
+                for_each_call(body, f)?;
+                for_each_call(continuing, f)?;
+            }
+            Statement::Emit(_)
+            | Statement::Break
+            | Statement::Continue
+            | Statement::Return { .. }
+            | Statement::Kill
+            | Statement::Barrier(_)
+            | Statement::Store { .. }
+            | Statement::ImageStore { .. }
+            | Statement::Atomic { .. }
+            | Statement::WorkGroupUniformLoad { .. }
+            | Statement::RayQuery { .. }
+            | Statement::SubgroupBallot { .. }
+            | Statement::SubgroupGather { .. }
+            | Statement::SubgroupCollectiveOperation { .. }
+            | Statement::MeshFunction(_) => {}
+        }
+    }
+    Ok(())
+}
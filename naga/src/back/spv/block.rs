@@ -3,8 +3,8 @@ Implementations for `BlockContext` methods.
 */
 
 use super::{
-    index::BoundsCheckResult, selection::Selection, Block, BlockContext, Dimension, Error,
-    Instruction, LocalType, LookupType, NumericType, ResultMember, Writer, WriterFlags,
+    index::BoundsCheckResult, selection::Selection, Block, BlockContext, Dimension,
+    EntryPointContext, Error, Instruction, LocalType, LookupType, NumericType, Writer, WriterFlags,
 };
 use crate::{arena::Handle, proc::index::GuardedIndex, Statement};
 use spirv::Word;
@@ -148,7 +148,7 @@ impl Writer {
     // between SPIR-V and our IR.
     // The `position_id` argument is a pointer to a `vecN<f32>`,
     // whose `y` component we will negate.
-    fn write_epilogue_position_y_flip(
+    pub(super) fn write_epilogue_position_y_flip(
         &mut self,
         position_id: Word,
         body: &mut Vec<Instruction>,
@@ -217,14 +217,20 @@ impl Writer {
         Ok(())
     }
 
+    /// Store an entry point's return value to its output variables, and
+    /// return the instruction that should end the function.
+    ///
+    /// This is usually `OpReturn`, but task shaders end with
+    /// `OpEmitMeshTasksEXT`, passing their `mesh_task_size` and payload.
     fn write_entry_point_return(
         &mut self,
         value_id: Word,
         ir_result: &crate::FunctionResult,
-        result_members: &[ResultMember],
+        context: &EntryPointContext,
         body: &mut Vec<Instruction>,
-    ) -> Result<(), Error> {
-        for (index, res_member) in result_members.iter().enumerate() {
+    ) -> Result<Instruction, Error> {
+        let mut termination = Instruction::return_void();
+        for (index, res_member) in context.results.iter().enumerate() {
             let member_value_id = match ir_result.binding {
                 Some(_) => value_id,
                 None => {
@@ -239,6 +245,23 @@ impl Writer {
                 }
             };
 
+            if res_member.built_in == Some(crate::BuiltIn::MeshTaskSize) {
+                let uint_type_id = self.get_uint_type_id();
+                let group_count_ids = [0, 1, 2].map(|component| {
+                    let id = self.id_gen.next();
+                    body.push(Instruction::composite_extract(
+                        uint_type_id,
+                        id,
+                        member_value_id,
+                        &[component],
+                    ));
+                    id
+                });
+                termination =
+                    Instruction::emit_mesh_tasks(group_count_ids, context.task_payload_id);
+                continue;
+            }
+
             body.push(Instruction::store(res_member.id, member_value_id, None));
 
             match res_member.built_in {
@@ -255,7 +278,7 @@ impl Writer {
                 _ => {}
             }
         }
-        Ok(())
+        Ok(termination)
     }
 }
 
@@ -614,7 +637,7 @@ impl BlockContext<'_> {
                             self.cached[expr_handle] = id;
                             return Ok(());
                         }
-                        _ => unimplemented!(),
+                        _ => return Err(Error::FeatureNotImplemented("addition")),
                     },
                     crate::BinaryOperator::Subtract => match *left_ty_inner {
                         crate::TypeInner::Scalar(scalar)
@@ -642,7 +665,7 @@ impl BlockContext<'_> {
                             self.cached[expr_handle] = id;
                             return Ok(());
                         }
-                        _ => unimplemented!(),
+                        _ => return Err(Error::FeatureNotImplemented("subtraction")),
                     },
                     crate::BinaryOperator::Multiply => match (left_dimension, right_dimension) {
                         (Dimension::Scalar, Dimension::Vector) => {
@@ -692,7 +715,7 @@ impl BlockContext<'_> {
                         Some(crate::ScalarKind::Sint) => spirv::Op::SDiv,
                         Some(crate::ScalarKind::Uint) => spirv::Op::UDiv,
                         Some(crate::ScalarKind::Float) => spirv::Op::FDiv,
-                        _ => unimplemented!(),
+                        _ => return Err(Error::FeatureNotImplemented("division")),
                     },
                     crate::BinaryOperator::Modulo => match left_ty_inner.scalar_kind() {
                         // TODO: handle undefined behavior
@@ -705,7 +728,7 @@ impl BlockContext<'_> {
                         // TODO: handle undefined behavior
                         // if right == 0 return ? see https://github.com/gpuweb/gpuweb/issues/2798
                         Some(crate::ScalarKind::Float) => spirv::Op::FRem,
-                        _ => unimplemented!(),
+                        _ => return Err(Error::FeatureNotImplemented("remainder")),
                     },
                     crate::BinaryOperator::Equal => match left_ty_inner.scalar_kind() {
                         Some(crate::ScalarKind::Sint | crate::ScalarKind::Uint) => {
@@ -713,7 +736,7 @@ impl BlockContext<'_> {
                         }
                         Some(crate::ScalarKind::Float) => spirv::Op::FOrdEqual,
                         Some(crate::ScalarKind::Bool) => spirv::Op::LogicalEqual,
-                        _ => unimplemented!(),
+                        _ => return Err(Error::FeatureNotImplemented("equality")),
                     },
                     crate::BinaryOperator::NotEqual => match left_ty_inner.scalar_kind() {
                         Some(crate::ScalarKind::Sint | crate::ScalarKind::Uint) => {
@@ -721,31 +744,39 @@ impl BlockContext<'_> {
                         }
                         Some(crate::ScalarKind::Float) => spirv::Op::FOrdNotEqual,
                         Some(crate::ScalarKind::Bool) => spirv::Op::LogicalNotEqual,
-                        _ => unimplemented!(),
+                        _ => return Err(Error::FeatureNotImplemented("inequality")),
                     },
                     crate::BinaryOperator::Less => match left_ty_inner.scalar_kind() {
                         Some(crate::ScalarKind::Sint) => spirv::Op::SLessThan,
                         Some(crate::ScalarKind::Uint) => spirv::Op::ULessThan,
                         Some(crate::ScalarKind::Float) => spirv::Op::FOrdLessThan,
-                        _ => unimplemented!(),
+                        _ => return Err(Error::FeatureNotImplemented("less-than comparison")),
                     },
                     crate::BinaryOperator::LessEqual => match left_ty_inner.scalar_kind() {
                         Some(crate::ScalarKind::Sint) => spirv::Op::SLessThanEqual,
                         Some(crate::ScalarKind::Uint) => spirv::Op::ULessThanEqual,
                         Some(crate::ScalarKind::Float) => spirv::Op::FOrdLessThanEqual,
-                        _ => unimplemented!(),
+                        _ => {
+                            return Err(Error::FeatureNotImplemented(
+                                "less-than-or-equal comparison",
+                            ))
+                        }
                     },
                     crate::BinaryOperator::Greater => match left_ty_inner.scalar_kind() {
                         Some(crate::ScalarKind::Sint) => spirv::Op::SGreaterThan,
                         Some(crate::ScalarKind::Uint) => spirv::Op::UGreaterThan,
                         Some(crate::ScalarKind::Float) => spirv::Op::FOrdGreaterThan,
-                        _ => unimplemented!(),
+                        _ => return Err(Error::FeatureNotImplemented("greater-than comparison")),
                     },
                     crate::BinaryOperator::GreaterEqual => match left_ty_inner.scalar_kind() {
                         Some(crate::ScalarKind::Sint) => spirv::Op::SGreaterThanEqual,
                         Some(crate::ScalarKind::Uint) => spirv::Op::UGreaterThanEqual,
                         Some(crate::ScalarKind::Float) => spirv::Op::FOrdGreaterThanEqual,
-                        _ => unimplemented!(),
+                        _ => {
+                            return Err(Error::FeatureNotImplemented(
+                                "greater-than-or-equal comparison",
+                            ))
+                        }
                     },
                     crate::BinaryOperator::And => match left_ty_inner.scalar_kind() {
                         Some(crate::ScalarKind::Bool) => spirv::Op::LogicalAnd,
@@ -762,7 +793,7 @@ impl BlockContext<'_> {
                     crate::BinaryOperator::ShiftRight => match left_ty_inner.scalar_kind() {
                         Some(crate::ScalarKind::Sint) => spirv::Op::ShiftRightArithmetic,
                         Some(crate::ScalarKind::Uint) => spirv::Op::ShiftRightLogical,
-                        _ => unimplemented!(),
+                        _ => return Err(Error::FeatureNotImplemented("right shift")),
                     },
                 };
 
@@ -819,20 +850,20 @@ impl BlockContext<'_> {
                                     arg0_id,
                                 ))
                             }
-                            other => unimplemented!("Unexpected abs({:?})", other),
+                            _ => return Err(Error::FeatureNotImplemented("abs")),
                         }
                     }
                     Mf::Min => MathOp::Ext(match arg_scalar_kind {
                         Some(crate::ScalarKind::Float) => spirv::GLOp::FMin,
                         Some(crate::ScalarKind::Sint) => spirv::GLOp::SMin,
                         Some(crate::ScalarKind::Uint) => spirv::GLOp::UMin,
-                        other => unimplemented!("Unexpected min({:?})", other),
+                        _ => return Err(Error::FeatureNotImplemented("min")),
                     }),
                     Mf::Max => MathOp::Ext(match arg_scalar_kind {
                         Some(crate::ScalarKind::Float) => spirv::GLOp::FMax,
                         Some(crate::ScalarKind::Sint) => spirv::GLOp::SMax,
                         Some(crate::ScalarKind::Uint) => spirv::GLOp::UMax,
-                        other => unimplemented!("Unexpected max({:?})", other),
+                        _ => return Err(Error::FeatureNotImplemented("max")),
                     }),
                     Mf::Clamp => match arg_scalar_kind {
                         // Clamp is undefined if min > max. In practice this means it can use a median-of-three
@@ -867,13 +898,13 @@ impl BlockContext<'_> {
                                 &[max_id, arg2_id],
                             ))
                         }
-                        other => unimplemented!("Unexpected max({:?})", other),
+                        _ => return Err(Error::FeatureNotImplemented("clamp")),
                     },
                     Mf::Saturate => {
                         let (maybe_size, scalar) = match *arg_ty {
                             crate::TypeInner::Vector { size, scalar } => (Some(size), scalar),
                             crate::TypeInner::Scalar(scalar) => (None, scalar),
-                            ref other => unimplemented!("Unexpected saturate({:?})", other),
+                            _ => return Err(Error::FeatureNotImplemented("saturate")),
                         };
                         let scalar = crate::Scalar::float(scalar.width);
                         let mut arg1_id = self.writer.get_constant_scalar_with(0, scalar)?;
@@ -951,6 +982,14 @@ impl BlockContext<'_> {
                                 arg1_id,
                                 size as u32,
                                 block,
+                                |result_id, composite_id, index| {
+                                    Instruction::composite_extract(
+                                        result_type_id,
+                                        result_id,
+                                        composite_id,
+                                        &[index],
+                                    )
+                                },
                             );
                             self.cached[expr_handle] = id;
                             return Ok(());
@@ -959,6 +998,92 @@ impl BlockContext<'_> {
                             "Correct TypeInner for dot product should be already validated"
                         ),
                     },
+                    fun @ (Mf::Dot4I8Packed | Mf::Dot4U8Packed) => {
+                        if self
+                            .writer
+                            .require_all(&[
+                                spirv::Capability::DotProduct,
+                                spirv::Capability::DotProductInput4x8BitPacked,
+                            ])
+                            .is_ok()
+                        {
+                            // The integer dot product instructions are core in SPIR-V 1.6,
+                            // and need `SPV_KHR_integer_dot_product` before that.
+                            if self.writer.lang_version() < (1, 6) {
+                                self.writer.use_extension("SPV_KHR_integer_dot_product");
+                            }
+
+                            let op = match fun {
+                                Mf::Dot4I8Packed => spirv::Op::SDot,
+                                Mf::Dot4U8Packed => spirv::Op::UDot,
+                                _ => unreachable!(),
+                            };
+
+                            MathOp::Custom(Instruction::ternary(
+                                op,
+                                result_type_id,
+                                id,
+                                arg0_id,
+                                arg1_id,
+                                spirv::PackedVectorFormat::PackedVectorFormat4x8Bit as Word,
+                            ))
+                        } else {
+                            // Without the packed dot product, extract each 8-bit component
+                            // with `BitField[SU]Extract` and sum their products.
+                            let (extract_op, arg0_id, arg1_id) = match fun {
+                                Mf::Dot4U8Packed => (spirv::Op::BitFieldUExtract, arg0_id, arg1_id),
+                                Mf::Dot4I8Packed => {
+                                    // `BitFieldSExtract` requires its base to have the
+                                    // result type, so reinterpret the arguments as `i32`.
+                                    let new_arg0_id = self.gen_id();
+                                    block.body.push(Instruction::unary(
+                                        spirv::Op::Bitcast,
+                                        result_type_id,
+                                        new_arg0_id,
+                                        arg0_id,
+                                    ));
+                                    let new_arg1_id = self.gen_id();
+                                    block.body.push(Instruction::unary(
+                                        spirv::Op::Bitcast,
+                                        result_type_id,
+                                        new_arg1_id,
+                                        arg1_id,
+                                    ));
+                                    (spirv::Op::BitFieldSExtract, new_arg0_id, new_arg1_id)
+                                }
+                                _ => unreachable!(),
+                            };
+
+                            let eight = self.writer.get_constant_scalar(crate::Literal::U32(8));
+                            const VEC_LENGTH: u8 = 4;
+                            let bit_shifts: [_; VEC_LENGTH as usize] =
+                                std::array::from_fn(|index| {
+                                    self.writer
+                                        .get_constant_scalar(crate::Literal::U32(index as u32 * 8))
+                                });
+
+                            self.write_dot_product(
+                                id,
+                                result_type_id,
+                                arg0_id,
+                                arg1_id,
+                                u32::from(VEC_LENGTH),
+                                block,
+                                |result_id, composite_id, index| {
+                                    Instruction::ternary(
+                                        extract_op,
+                                        result_type_id,
+                                        result_id,
+                                        composite_id,
+                                        bit_shifts[index as usize],
+                                        eight,
+                                    )
+                                },
+                            );
+                            self.cached[expr_handle] = id;
+                            return Ok(());
+                        }
+                    }
                     Mf::Outer => MathOp::Custom(Instruction::binary(
                         spirv::Op::OuterProduct,
                         result_type_id,
@@ -983,7 +1108,7 @@ impl BlockContext<'_> {
                     Mf::Sign => MathOp::Ext(match arg_scalar_kind {
                         Some(crate::ScalarKind::Float) => spirv::GLOp::FSign,
                         Some(crate::ScalarKind::Sint) => spirv::GLOp::SSign,
-                        other => unimplemented!("Unexpected sign({:?})", other),
+                        _ => return Err(Error::FeatureNotImplemented("sign")),
                     }),
                     Mf::Fma => MathOp::Ext(spirv::GLOp::Fma),
                     Mf::Mix => {
@@ -1147,7 +1272,7 @@ impl BlockContext<'_> {
                         let op = match arg_scalar_kind {
                             Some(crate::ScalarKind::Uint) => spirv::Op::BitFieldUExtract,
                             Some(crate::ScalarKind::Sint) => spirv::Op::BitFieldSExtract,
-                            other => unimplemented!("Unexpected sign({:?})", other),
+                            _ => return Err(Error::FeatureNotImplemented("extractBits")),
                         };
 
                         // The behavior of ExtractBits is undefined when offset + count > bit_width. We need
@@ -1270,7 +1395,7 @@ impl BlockContext<'_> {
                             let thing = match arg_scalar_kind {
                                 Some(crate::ScalarKind::Uint) => spirv::GLOp::FindUMsb,
                                 Some(crate::ScalarKind::Sint) => spirv::GLOp::FindSMsb,
-                                other => unimplemented!("Unexpected firstLeadingBit({:?})", other),
+                                _ => return Err(Error::FeatureNotImplemented("firstLeadingBit")),
                             };
                             MathOp::Ext(thing)
                         } else {
@@ -1736,10 +1861,7 @@ impl BlockContext<'_> {
             }
             crate::Expression::ArrayLength(expr) => self.write_runtime_array_length(expr, block)?,
             crate::Expression::RayQueryGetIntersection { query, committed } => {
-                if !committed {
-                    return Err(Error::FeatureNotImplemented("candidate intersection"));
-                }
-                self.write_ray_query_get_intersection(query, block)
+                self.write_ray_query_get_intersection(query, committed, block)
             }
         };
 
@@ -1841,7 +1963,7 @@ impl BlockContext<'_> {
                 crate::Expression::FunctionArgument(index) => {
                     break self.function.parameter_id(index);
                 }
-                ref other => unimplemented!("Unexpected pointer expression {:?}", other),
+                _ => return Err(Error::FeatureNotImplemented("pointer expression")),
             }
         };
 
@@ -2208,6 +2330,10 @@ impl BlockContext<'_> {
     }
 
     /// Build the instructions for the arithmetic expression of a dot product
+    ///
+    /// `extractor` builds the instruction that extracts the component at the
+    /// given index from a vector operand, writing it to the given result id.
+    #[allow(clippy::too_many_arguments)]
     fn write_dot_product(
         &mut self,
         result_id: Word,
@@ -2216,25 +2342,16 @@ impl BlockContext<'_> {
         arg1_id: Word,
         size: u32,
         block: &mut Block,
+        extractor: impl Fn(Word, Word, Word) -> Instruction,
     ) {
         let mut partial_sum = self.writer.get_constant_null(result_type_id);
         let last_component = size - 1;
         for index in 0..=last_component {
             // compute the product of the current components
             let a_id = self.gen_id();
-            block.body.push(Instruction::composite_extract(
-                result_type_id,
-                a_id,
-                arg0_id,
-                &[index],
-            ));
+            block.body.push(extractor(a_id, arg0_id, index));
             let b_id = self.gen_id();
-            block.body.push(Instruction::composite_extract(
-                result_type_id,
-                b_id,
-                arg1_id,
-                &[index],
-            ));
+            block.body.push(extractor(b_id, arg1_id, index));
             let prod_id = self.gen_id();
             block.body.push(Instruction::binary(
                 spirv::Op::IMul,
@@ -2582,15 +2699,12 @@ impl BlockContext<'_> {
                     let instruction = match self.function.entry_point_context {
                         // If this is an entry point, and we need to return anything,
                         // let's instead store the output variables and return `void`.
-                        Some(ref context) => {
-                            self.writer.write_entry_point_return(
-                                value_id,
-                                self.ir_function.result.as_ref().unwrap(),
-                                &context.results,
-                                &mut block.body,
-                            )?;
-                            Instruction::return_void()
-                        }
+                        Some(ref context) => self.writer.write_entry_point_return(
+                            value_id,
+                            self.ir_function.result.as_ref().unwrap(),
+                            context,
+                            &mut block.body,
+                        )?,
                         None => Instruction::return_value(value_id),
                     };
                     self.function.consume(block, instruction);
@@ -2789,7 +2903,7 @@ impl BlockContext<'_> {
                                     kind: crate::ScalarKind::Uint,
                                     width: _,
                                 }) => spirv::Op::AtomicUMin,
-                                _ => unimplemented!(),
+                                _ => return Err(Error::FeatureNotImplemented("atomicMin")),
                             };
                             Instruction::atomic_binary(
                                 spirv_op,
@@ -2811,7 +2925,7 @@ impl BlockContext<'_> {
                                     kind: crate::ScalarKind::Uint,
                                     width: _,
                                 }) => spirv::Op::AtomicUMax,
-                                _ => unimplemented!(),
+                                _ => return Err(Error::FeatureNotImplemented("atomicMax")),
                             };
                             Instruction::atomic_binary(
                                 spirv_op,
@@ -2841,7 +2955,11 @@ impl BlockContext<'_> {
                                         NumericType::Scalar(scalar),
                                     )))
                                 }
-                                _ => unimplemented!(),
+                                _ => {
+                                    return Err(Error::FeatureNotImplemented(
+                                        "atomicCompareExchangeWeak",
+                                    ))
+                                }
                             };
                             let bool_type_id = self.get_type_id(LookupType::Local(
                                 LocalType::Numeric(NumericType::Scalar(crate::Scalar::BOOL)),
@@ -2944,6 +3062,9 @@ impl BlockContext<'_> {
                 } => {
                     self.write_subgroup_gather(mode, argument, result, &mut block)?;
                 }
+                Statement::MeshFunction(ref fun) => {
+                    self.write_mesh_function(fun, &mut block)?;
+                }
             }
         }
 
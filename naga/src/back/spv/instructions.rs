@@ -406,6 +406,10 @@ impl super::Instruction {
         instruction
     }
 
+    pub(super) fn constant_16bit(result_type_id: Word, id: Word, low: Word) -> Self {
+        Self::constant(result_type_id, id, &[low])
+    }
+
     pub(super) fn constant_32bit(result_type_id: Word, id: Word, value: Word) -> Self {
         Self::constant(result_type_id, id, &[value])
     }
@@ -744,6 +748,19 @@ impl super::Instruction {
         instruction
     }
 
+    pub(super) fn ray_query_generate_intersection(query: Word, hit_t: Word) -> Self {
+        let mut instruction = Self::new(Op::RayQueryGenerateIntersectionKHR);
+        instruction.add_operand(query);
+        instruction.add_operand(hit_t);
+        instruction
+    }
+
+    pub(super) fn ray_query_confirm_intersection(query: Word) -> Self {
+        let mut instruction = Self::new(Op::RayQueryConfirmIntersectionKHR);
+        instruction.add_operand(query);
+        instruction
+    }
+
     pub(super) fn ray_query_get_intersection(
         op: Op,
         result_type_id: Word,
@@ -1052,6 +1069,28 @@ impl super::Instruction {
         instruction
     }
 
+    //
+    //  Mesh Shading Instructions
+    //
+
+    pub(super) fn emit_mesh_tasks(group_count_ids: [Word; 3], payload_id: Option<Word>) -> Self {
+        let mut instruction = Self::new(Op::EmitMeshTasksEXT);
+        for group_count_id in group_count_ids {
+            instruction.add_operand(group_count_id);
+        }
+        if let Some(payload_id) = payload_id {
+            instruction.add_operand(payload_id);
+        }
+        instruction
+    }
+
+    pub(super) fn set_mesh_outputs(vertex_count_id: Word, primitive_count_id: Word) -> Self {
+        let mut instruction = Self::new(Op::SetMeshOutputsEXT);
+        instruction.add_operand(vertex_count_id);
+        instruction.add_operand(primitive_count_id);
+        instruction
+    }
+
     //
     //  Atomic Instructions
     //
@@ -1128,6 +1167,7 @@ impl super::Instruction {
         exec_scope_id: Word,
         group_op: Option<spirv::GroupOperation>,
         value: Word,
+        cluster_size: Option<Word>,
     ) -> Self {
         let mut instruction = Self::new(op);
         instruction.set_type(result_type_id);
@@ -1137,6 +1177,9 @@ impl super::Instruction {
             instruction.add_operand(group_op as u32);
         }
         instruction.add_operand(value);
+        if let Some(cluster_size) = cluster_size {
+            instruction.add_operand(cluster_size);
+        }
 
         instruction
     }
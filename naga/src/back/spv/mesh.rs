@@ -0,0 +1,89 @@
+/*!
+Generating SPIR-V for mesh shader output statements.
+*/
+
+use super::{Block, BlockContext, Error, Instruction, MeshOutputMember, WriterFlags};
+use crate::arena::Handle;
+
+impl BlockContext<'_> {
+    pub(super) fn write_mesh_function(
+        &mut self,
+        fun: &crate::MeshFunction,
+        block: &mut Block,
+    ) -> Result<(), Error> {
+        let (index, value, primitive) = match *fun {
+            crate::MeshFunction::SetMeshOutputs {
+                vertex_count,
+                primitive_count,
+            } => {
+                block.body.push(Instruction::set_mesh_outputs(
+                    self.cached[vertex_count],
+                    self.cached[primitive_count],
+                ));
+                return Ok(());
+            }
+            crate::MeshFunction::SetVertex { index, value } => (index, value, false),
+            crate::MeshFunction::SetPrimitive { index, value } => (index, value, true),
+        };
+
+        let context = self
+            .function
+            .entry_point_context
+            .as_ref()
+            .ok_or(Error::Validation(
+                "mesh outputs written outside an entry point",
+            ))?;
+        let outputs = if primitive {
+            &context.mesh_primitive_outputs
+        } else {
+            &context.mesh_vertex_outputs
+        };
+        // Copy the outputs, so we can borrow `self` mutably below.
+        let outputs = outputs.clone();
+
+        self.write_mesh_output(&outputs, index, value, block)
+    }
+
+    /// Store each member of `value` to element `index` of its output array.
+    fn write_mesh_output(
+        &mut self,
+        outputs: &[MeshOutputMember],
+        index: Handle<crate::Expression>,
+        value: Handle<crate::Expression>,
+        block: &mut Block,
+    ) -> Result<(), Error> {
+        let index_id = self.cached[index];
+        let value_id = self.cached[value];
+        for (member_index, output) in outputs.iter().enumerate() {
+            let member_value_id = self.gen_id();
+            block.body.push(Instruction::composite_extract(
+                output.type_id,
+                member_value_id,
+                value_id,
+                &[member_index as u32],
+            ));
+            let pointer_id = self.gen_id();
+            block.body.push(Instruction::access_chain(
+                output.pointer_type_id,
+                pointer_id,
+                output.id,
+                &[index_id],
+            ));
+            block
+                .body
+                .push(Instruction::store(pointer_id, member_value_id, None));
+
+            if let Some(crate::BuiltIn::Position { .. }) = output.built_in {
+                if self
+                    .writer
+                    .flags
+                    .contains(WriterFlags::ADJUST_COORDINATE_SPACE)
+                {
+                    self.writer
+                        .write_epilogue_position_y_flip(pointer_id, &mut block.body)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
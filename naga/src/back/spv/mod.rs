@@ -10,6 +10,7 @@ mod image;
 mod index;
 mod instructions;
 mod layout;
+mod mesh;
 mod ray;
 mod recyclable;
 mod selection;
@@ -134,9 +135,27 @@ struct ResultMember {
     built_in: Option<crate::BuiltIn>,
 }
 
+/// An `Output` variable holding one member of a mesh shader's vertex or
+/// primitive output type, for every vertex or primitive.
+#[derive(Clone, Copy)]
+struct MeshOutputMember {
+    id: Word,
+    /// The type of the member.
+    type_id: Word,
+    /// The type of a pointer to one element of the variable.
+    pointer_type_id: Word,
+    built_in: Option<crate::BuiltIn>,
+}
+
 struct EntryPointContext {
     argument_ids: Vec<Word>,
     results: Vec<ResultMember>,
+    /// The task payload variable, for task and mesh shaders that have one.
+    task_payload_id: Option<Word>,
+    /// The per-vertex output variables of a mesh shader.
+    mesh_vertex_outputs: Vec<MeshOutputMember>,
+    /// The per-primitive output variables of a mesh shader.
+    mesh_primitive_outputs: Vec<MeshOutputMember>,
 }
 
 #[derive(Default)]
@@ -378,6 +397,15 @@ enum LocalType {
         base: Handle<crate::Type>,
         size: u32,
     },
+    /// A pointer to an array of `size` elements of `base`.
+    ///
+    /// Mesh shaders declare one such `Output` variable for each member of
+    /// their vertex and primitive output types.
+    PointerToArray {
+        base: Handle<crate::Type>,
+        size: u32,
+        class: spirv::StorageClass,
+    },
     AccelerationStructure,
     RayQuery,
 }
@@ -2,7 +2,9 @@
 Generating SPIR-V for ray query operations.
 */
 
-use super::{Block, BlockContext, Instruction, LocalType, LookupType, NumericType};
+use super::{
+    selection::Selection, Block, BlockContext, Instruction, LocalType, LookupType, NumericType,
+};
 use crate::arena::Handle;
 
 impl BlockContext<'_> {
@@ -98,31 +100,95 @@ impl BlockContext<'_> {
                     .body
                     .push(Instruction::ray_query_proceed(result_type_id, id, query_id));
             }
+            crate::RayQueryFunction::GenerateIntersection { hit_t } => {
+                let hit_t_id = self.cached[hit_t];
+                block
+                    .body
+                    .push(Instruction::ray_query_generate_intersection(
+                        query_id, hit_t_id,
+                    ));
+            }
+            crate::RayQueryFunction::ConfirmIntersection => {
+                block
+                    .body
+                    .push(Instruction::ray_query_confirm_intersection(query_id));
+            }
             crate::RayQueryFunction::Terminate => {}
         }
     }
 
+    /// Write a [`RayQueryGetIntersection`] expression.
+    ///
+    /// Candidate intersections report their kind as triangle or AABB, rather
+    /// than the none/triangle/generated kinds of committed intersections, so
+    /// we translate the SPIR-V candidate type into Naga's
+    /// [`RayIntersectionType`] values. The distance, barycentrics and facing
+    /// of a candidate are only available for triangles, so for AABB
+    /// candidates we leave them zeroed.
+    ///
+    /// [`RayQueryGetIntersection`]: crate::Expression::RayQueryGetIntersection
+    /// [`RayIntersectionType`]: crate::back::RayIntersectionType
     pub(super) fn write_ray_query_get_intersection(
         &mut self,
         query: Handle<crate::Expression>,
+        committed: bool,
         block: &mut Block,
     ) -> spirv::Word {
         let query_id = self.cached[query];
-        let intersection_id = self.writer.get_constant_scalar(crate::Literal::U32(
-            spirv::RayQueryIntersection::RayQueryCommittedIntersectionKHR as _,
-        ));
+        let intersection = if committed {
+            spirv::RayQueryIntersection::RayQueryCommittedIntersectionKHR
+        } else {
+            spirv::RayQueryIntersection::RayQueryCandidateIntersectionKHR
+        };
+        let intersection_id = self
+            .writer
+            .get_constant_scalar(crate::Literal::U32(intersection as _));
 
         let flag_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
             NumericType::Scalar(crate::Scalar::U32),
         )));
-        let kind_id = self.gen_id();
+        let raw_kind_id = self.gen_id();
         block.body.push(Instruction::ray_query_get_intersection(
             spirv::Op::RayQueryGetIntersectionTypeKHR,
             flag_type_id,
-            kind_id,
+            raw_kind_id,
             query_id,
             intersection_id,
         ));
+        let bool_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(crate::Scalar::BOOL),
+        )));
+        let (kind_id, is_triangle_id) = if committed {
+            (raw_kind_id, None)
+        } else {
+            let candidate_triangle_id = self.writer.get_constant_scalar(crate::Literal::U32(
+                spirv::RayQueryCandidateIntersectionType::RayQueryCandidateIntersectionTriangleKHR
+                    as _,
+            ));
+            let is_triangle_id = self.gen_id();
+            block.body.push(Instruction::binary(
+                spirv::Op::IEqual,
+                bool_type_id,
+                is_triangle_id,
+                raw_kind_id,
+                candidate_triangle_id,
+            ));
+            let triangle_id = self.writer.get_constant_scalar(crate::Literal::U32(
+                crate::back::RayIntersectionType::Triangle as _,
+            ));
+            let bounding_box_id = self.writer.get_constant_scalar(crate::Literal::U32(
+                crate::back::RayIntersectionType::BoundingBox as _,
+            ));
+            let kind_id = self.gen_id();
+            block.body.push(Instruction::select(
+                flag_type_id,
+                kind_id,
+                is_triangle_id,
+                triangle_id,
+                bounding_box_id,
+            ));
+            (kind_id, Some(is_triangle_id))
+        };
         let instance_custom_index_id = self.gen_id();
         block.body.push(Instruction::ray_query_get_intersection(
             spirv::Op::RayQueryGetIntersectionInstanceCustomIndexKHR,
@@ -164,44 +230,6 @@ impl BlockContext<'_> {
             intersection_id,
         ));
 
-        let scalar_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
-            NumericType::Scalar(crate::Scalar::F32),
-        )));
-        let t_id = self.gen_id();
-        block.body.push(Instruction::ray_query_get_intersection(
-            spirv::Op::RayQueryGetIntersectionTKHR,
-            scalar_type_id,
-            t_id,
-            query_id,
-            intersection_id,
-        ));
-
-        let barycentrics_type_id =
-            self.get_type_id(LookupType::Local(LocalType::Numeric(NumericType::Vector {
-                size: crate::VectorSize::Bi,
-                scalar: crate::Scalar::F32,
-            })));
-        let barycentrics_id = self.gen_id();
-        block.body.push(Instruction::ray_query_get_intersection(
-            spirv::Op::RayQueryGetIntersectionBarycentricsKHR,
-            barycentrics_type_id,
-            barycentrics_id,
-            query_id,
-            intersection_id,
-        ));
-
-        let bool_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
-            NumericType::Scalar(crate::Scalar::BOOL),
-        )));
-        let front_face_id = self.gen_id();
-        block.body.push(Instruction::ray_query_get_intersection(
-            spirv::Op::RayQueryGetIntersectionFrontFaceKHR,
-            bool_type_id,
-            front_face_id,
-            query_id,
-            intersection_id,
-        ));
-
         let transform_type_id =
             self.get_type_id(LookupType::Local(LocalType::Numeric(NumericType::Matrix {
                 columns: crate::VectorSize::Quad,
@@ -225,28 +253,104 @@ impl BlockContext<'_> {
             intersection_id,
         ));
 
-        let id = self.gen_id();
+        let scalar_type_id = self.get_type_id(LookupType::Local(LocalType::Numeric(
+            NumericType::Scalar(crate::Scalar::F32),
+        )));
+        let barycentrics_type_id =
+            self.get_type_id(LookupType::Local(LocalType::Numeric(NumericType::Vector {
+                size: crate::VectorSize::Bi,
+                scalar: crate::Scalar::F32,
+            })));
         let intersection_type_id = self.get_type_id(LookupType::Handle(
             self.ir_module.special_types.ray_intersection.unwrap(),
         ));
-        //Note: the arguments must match `generate_ray_intersection_type` layout
-        block.body.push(Instruction::composite_construct(
-            intersection_type_id,
-            id,
-            &[
-                kind_id,
+
+        let mut selection = Selection::start(block, intersection_type_id);
+        if let Some(is_triangle_id) = is_triangle_id {
+            let zero_t_id = self.writer.get_constant_scalar(crate::Literal::F32(0.0));
+            let zero_barycentrics_id = self.writer.get_constant_null(barycentrics_type_id);
+            let false_id = self.writer.get_constant_scalar(crate::Literal::Bool(false));
+            let aabb_id = self.gen_id();
+            //Note: the arguments must match `generate_ray_intersection_type` layout
+            selection
+                .block()
+                .body
+                .push(Instruction::composite_construct(
+                    intersection_type_id,
+                    aabb_id,
+                    &[
+                        kind_id,
+                        zero_t_id,
+                        instance_custom_index_id,
+                        instance_id,
+                        sbt_record_offset_id,
+                        geometry_index_id,
+                        primitive_index_id,
+                        zero_barycentrics_id,
+                        false_id,
+                        object_to_world_id,
+                        world_to_object_id,
+                    ],
+                ));
+            selection.if_true(self, is_triangle_id, aabb_id);
+        }
+
+        let t_id = self.gen_id();
+        selection
+            .block()
+            .body
+            .push(Instruction::ray_query_get_intersection(
+                spirv::Op::RayQueryGetIntersectionTKHR,
+                scalar_type_id,
                 t_id,
-                instance_custom_index_id,
-                instance_id,
-                sbt_record_offset_id,
-                geometry_index_id,
-                primitive_index_id,
+                query_id,
+                intersection_id,
+            ));
+        let barycentrics_id = self.gen_id();
+        selection
+            .block()
+            .body
+            .push(Instruction::ray_query_get_intersection(
+                spirv::Op::RayQueryGetIntersectionBarycentricsKHR,
+                barycentrics_type_id,
                 barycentrics_id,
+                query_id,
+                intersection_id,
+            ));
+        let front_face_id = self.gen_id();
+        selection
+            .block()
+            .body
+            .push(Instruction::ray_query_get_intersection(
+                spirv::Op::RayQueryGetIntersectionFrontFaceKHR,
+                bool_type_id,
                 front_face_id,
-                object_to_world_id,
-                world_to_object_id,
-            ],
-        ));
-        id
+                query_id,
+                intersection_id,
+            ));
+
+        let id = self.gen_id();
+        //Note: the arguments must match `generate_ray_intersection_type` layout
+        selection
+            .block()
+            .body
+            .push(Instruction::composite_construct(
+                intersection_type_id,
+                id,
+                &[
+                    kind_id,
+                    t_id,
+                    instance_custom_index_id,
+                    instance_id,
+                    sbt_record_offset_id,
+                    geometry_index_id,
+                    primitive_index_id,
+                    barycentrics_id,
+                    front_face_id,
+                    object_to_world_id,
+                    world_to_object_id,
+                ],
+            ));
+        selection.finish(self, id)
     }
 }
@@ -69,14 +69,16 @@ impl BlockContext<'_> {
         let (is_scalar, scalar) = match *result_ty_inner {
             TypeInner::Scalar(kind) => (true, kind),
             TypeInner::Vector { scalar: kind, .. } => (false, kind),
-            _ => unimplemented!(),
+            _ => return Err(Error::FeatureNotImplemented("subgroup operation")),
         };
 
         use crate::ScalarKind as sk;
         let spirv_op = match (scalar.kind, *op) {
             (sk::Bool, sg::All) if is_scalar => spirv::Op::GroupNonUniformAll,
             (sk::Bool, sg::Any) if is_scalar => spirv::Op::GroupNonUniformAny,
-            (_, sg::All | sg::Any) => unimplemented!(),
+            (_, sg::All | sg::Any) => {
+                return Err(Error::FeatureNotImplemented("subgroup operation"))
+            }
 
             (sk::Sint | sk::Uint, sg::Add) => spirv::Op::GroupNonUniformIAdd,
             (sk::Float, sg::Add) => spirv::Op::GroupNonUniformFAdd,
@@ -88,7 +90,9 @@ impl BlockContext<'_> {
             (sk::Sint, sg::Min) => spirv::Op::GroupNonUniformSMin,
             (sk::Uint, sg::Min) => spirv::Op::GroupNonUniformUMin,
             (sk::Float, sg::Min) => spirv::Op::GroupNonUniformFMin,
-            (_, sg::Add | sg::Mul | sg::Min | sg::Max) => unimplemented!(),
+            (_, sg::Add | sg::Mul | sg::Min | sg::Max) => {
+                return Err(Error::FeatureNotImplemented("subgroup operation"))
+            }
 
             (sk::Sint | sk::Uint, sg::And) => spirv::Op::GroupNonUniformBitwiseAnd,
             (sk::Sint | sk::Uint, sg::Or) => spirv::Op::GroupNonUniformBitwiseOr,
@@ -96,7 +100,9 @@ impl BlockContext<'_> {
             (sk::Bool, sg::And) => spirv::Op::GroupNonUniformLogicalAnd,
             (sk::Bool, sg::Or) => spirv::Op::GroupNonUniformLogicalOr,
             (sk::Bool, sg::Xor) => spirv::Op::GroupNonUniformLogicalXor,
-            (_, sg::And | sg::Or | sg::Xor) => unimplemented!(),
+            (_, sg::And | sg::Or | sg::Xor) => {
+                return Err(Error::FeatureNotImplemented("subgroup operation"))
+            }
         };
 
         let exec_scope_id = self.get_index_constant(spirv::Scope::Subgroup as u32);
@@ -108,8 +114,19 @@ impl BlockContext<'_> {
                 c::Reduce => spirv::GroupOperation::Reduce,
                 c::InclusiveScan => spirv::GroupOperation::InclusiveScan,
                 c::ExclusiveScan => spirv::GroupOperation::ExclusiveScan,
+                c::ClusteredReduce(_) => {
+                    self.writer.require_any(
+                        "GroupNonUniformClustered",
+                        &[spirv::Capability::GroupNonUniformClustered],
+                    )?;
+                    spirv::GroupOperation::ClusteredReduce
+                }
             }),
         };
+        let cluster_size_id = match *collective_op {
+            c::ClusteredReduce(size) => Some(self.get_index_constant(size)),
+            _ => None,
+        };
 
         let arg_id = self.cached[argument];
         block.body.push(Instruction::group_non_uniform_arithmetic(
@@ -119,6 +136,7 @@ impl BlockContext<'_> {
             exec_scope_id,
             group_op,
             arg_id,
+            cluster_size_id,
         ));
         self.cached[result] = id;
         Ok(())
@@ -153,6 +171,12 @@ impl BlockContext<'_> {
                     &[spirv::Capability::GroupNonUniformShuffleRelative],
                 )?;
             }
+            crate::GatherMode::QuadBroadcast(_) | crate::GatherMode::QuadSwap(_) => {
+                self.writer.require_any(
+                    "GroupNonUniformQuad",
+                    &[spirv::Capability::GroupNonUniformQuad],
+                )?;
+            }
         }
 
         let id = self.gen_id();
@@ -173,14 +197,28 @@ impl BlockContext<'_> {
                         arg_id,
                     ));
             }
+            crate::GatherMode::QuadSwap(direction) => {
+                let direction_id = self.get_index_constant(direction as u32);
+                block.body.push(Instruction::group_non_uniform_gather(
+                    spirv::Op::GroupNonUniformQuadSwap,
+                    result_type_id,
+                    id,
+                    exec_scope_id,
+                    arg_id,
+                    direction_id,
+                ));
+            }
             crate::GatherMode::Broadcast(index)
             | crate::GatherMode::Shuffle(index)
             | crate::GatherMode::ShuffleDown(index)
             | crate::GatherMode::ShuffleUp(index)
-            | crate::GatherMode::ShuffleXor(index) => {
+            | crate::GatherMode::ShuffleXor(index)
+            | crate::GatherMode::QuadBroadcast(index) => {
                 let index_id = self.cached[index];
                 let op = match *mode {
-                    crate::GatherMode::BroadcastFirst => unreachable!(),
+                    crate::GatherMode::BroadcastFirst | crate::GatherMode::QuadSwap(_) => {
+                        unreachable!()
+                    }
                     // Use shuffle to emit broadcast to allow the index to
                     // be dynamically uniform on Vulkan 1.1. The argument to
                     // OpGroupNonUniformBroadcast must be a constant pre SPIR-V
@@ -190,6 +228,7 @@ impl BlockContext<'_> {
                     crate::GatherMode::ShuffleDown(_) => spirv::Op::GroupNonUniformShuffleDown,
                     crate::GatherMode::ShuffleUp(_) => spirv::Op::GroupNonUniformShuffleUp,
                     crate::GatherMode::ShuffleXor(_) => spirv::Op::GroupNonUniformShuffleXor,
+                    crate::GatherMode::QuadBroadcast(_) => spirv::Op::GroupNonUniformQuadBroadcast,
                 };
                 block.body.push(Instruction::group_non_uniform_gather(
                     op,
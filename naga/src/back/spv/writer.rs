@@ -3,8 +3,8 @@ use super::{
     helpers::{contains_builtin, global_needs_wrapper, map_storage_class},
     Block, BlockContext, CachedConstant, CachedExpressions, DebugInfo, EntryPointContext, Error,
     Function, FunctionArgument, GlobalVariable, IdGenerator, Instruction, LocalType, LocalVariable,
-    LogicalLayout, LookupFunctionType, LookupType, NumericType, Options, PhysicalLayout,
-    PipelineOptions, ResultMember, Writer, WriterFlags, BITS_PER_BYTE,
+    LogicalLayout, LookupFunctionType, LookupType, MeshOutputMember, NumericType, Options,
+    PhysicalLayout, PipelineOptions, ResultMember, Writer, WriterFlags, BITS_PER_BYTE,
 };
 use crate::{
     arena::{Handle, HandleVec, UniqueArena},
@@ -15,9 +15,13 @@ use crate::{
 use spirv::Word;
 use std::collections::hash_map::Entry;
 
+const TASK_SIZE_BINDING: crate::Binding = crate::Binding::BuiltIn(crate::BuiltIn::MeshTaskSize);
+
 struct FunctionInterface<'a> {
     varying_ids: &'a mut Vec<Word>,
     stage: crate::ShaderStage,
+    mesh_info: Option<&'a crate::MeshStageInfo>,
+    task_payload: Option<Handle<crate::GlobalVariable>>,
 }
 
 impl Function {
@@ -179,6 +183,41 @@ impl Writer {
         }
     }
 
+    /// Indicate that the code requires all of the listed capabilities.
+    ///
+    /// If all entries of `capabilities` appear in the available capabilities
+    /// specified in the [`Options`] from which this `Writer` was created
+    /// (including the case where no available capabilities were given), add
+    /// them all to this `Writer`'s [`capabilities_used`] table and return
+    /// `Ok(())`. Otherwise, leave the table unchanged and return the first
+    /// unavailable capability, so the caller can fall back to a polyfill.
+    ///
+    /// [`capabilities_used`]: Writer::capabilities_used
+    pub(super) fn require_all(
+        &mut self,
+        capabilities: &[spirv::Capability],
+    ) -> Result<(), spirv::Capability> {
+        if let Some(ref available) = self.capabilities_available {
+            for requested in capabilities {
+                if !available.contains(requested) {
+                    return Err(*requested);
+                }
+            }
+        }
+
+        for requested in capabilities {
+            self.capabilities_used.insert(*requested);
+        }
+
+        Ok(())
+    }
+
+    /// The (major, minor) SPIR-V version this `Writer` targets.
+    pub(super) const fn lang_version(&self) -> (u8, u8) {
+        let version = self.physical_layout.version;
+        ((version >> 16) as u8, (version >> 8) as u8)
+    }
+
     /// Indicate that the code uses the given extension.
     pub(super) fn use_extension(&mut self, extension: &'static str) {
         self.extensions_used.insert(extension);
@@ -315,6 +354,9 @@ impl Writer {
         let mut ep_context = EntryPointContext {
             argument_ids: Vec::new(),
             results: Vec::new(),
+            task_payload_id: None,
+            mesh_vertex_outputs: Vec::new(),
+            mesh_primitive_outputs: Vec::new(),
         };
 
         let mut local_invocation_id = None;
@@ -339,6 +381,7 @@ impl Writer {
                         name,
                         argument.ty,
                         binding,
+                        None,
                     )?;
                     iface.varying_ids.push(varying_id);
                     let id = self.id_gen.next();
@@ -367,6 +410,7 @@ impl Writer {
                             name,
                             member.ty,
                             binding,
+                            None,
                         )?;
                         iface.varying_ids.push(varying_id);
                         let id = self.id_gen.next();
@@ -425,15 +469,23 @@ impl Writer {
                         has_point_size |=
                             *binding == crate::Binding::BuiltIn(crate::BuiltIn::PointSize);
                         let type_id = self.get_type_id(LookupType::Handle(result.ty));
-                        let varying_id = self.write_varying(
-                            ir_module,
-                            iface.stage,
-                            class,
-                            None,
-                            result.ty,
-                            binding,
-                        )?;
-                        iface.varying_ids.push(varying_id);
+                        // A task shader's `mesh_task_size` is passed to
+                        // `OpEmitMeshTasksEXT`, not stored to a variable.
+                        let varying_id = if *binding == TASK_SIZE_BINDING {
+                            0
+                        } else {
+                            let varying_id = self.write_varying(
+                                ir_module,
+                                iface.stage,
+                                class,
+                                None,
+                                result.ty,
+                                binding,
+                                None,
+                            )?;
+                            iface.varying_ids.push(varying_id);
+                            varying_id
+                        };
                         ep_context.results.push(ResultMember {
                             id: varying_id,
                             type_id,
@@ -448,15 +500,21 @@ impl Writer {
                             let binding = member.binding.as_ref().unwrap();
                             has_point_size |=
                                 *binding == crate::Binding::BuiltIn(crate::BuiltIn::PointSize);
-                            let varying_id = self.write_varying(
-                                ir_module,
-                                iface.stage,
-                                class,
-                                name,
-                                member.ty,
-                                binding,
-                            )?;
-                            iface.varying_ids.push(varying_id);
+                            let varying_id = if *binding == TASK_SIZE_BINDING {
+                                0
+                            } else {
+                                let varying_id = self.write_varying(
+                                    ir_module,
+                                    iface.stage,
+                                    class,
+                                    name,
+                                    member.ty,
+                                    binding,
+                                    None,
+                                )?;
+                                iface.varying_ids.push(varying_id);
+                                varying_id
+                            };
                             ep_context.results.push(ResultMember {
                                 id: varying_id,
                                 type_id,
@@ -496,6 +554,26 @@ impl Writer {
             None => self.void_type,
         };
 
+        if let Some(ref mut iface) = interface {
+            if let Some(payload) = iface.task_payload {
+                ep_context.task_payload_id = Some(self.global_variables[payload].var_id);
+            }
+            if let Some(mesh_info) = iface.mesh_info {
+                ep_context.mesh_vertex_outputs = self.write_mesh_outputs(
+                    ir_module,
+                    iface,
+                    mesh_info.vertex_output_type,
+                    mesh_info.max_vertices,
+                )?;
+                ep_context.mesh_primitive_outputs = self.write_mesh_outputs(
+                    ir_module,
+                    iface,
+                    mesh_info.primitive_output_type,
+                    mesh_info.max_primitives,
+                )?;
+            }
+        }
+
         let lookup_function_type = LookupFunctionType {
             parameter_type_ids,
             return_type_id,
@@ -701,6 +779,57 @@ impl Writer {
         Ok(function_id)
     }
 
+    /// Declare an `Output` array variable of `count` elements for each member of
+    /// `ty`, a mesh shader's vertex or primitive output type.
+    fn write_mesh_outputs(
+        &mut self,
+        ir_module: &crate::Module,
+        iface: &mut FunctionInterface,
+        ty: Handle<crate::Type>,
+        count: u32,
+    ) -> Result<Vec<MeshOutputMember>, Error> {
+        let crate::TypeInner::Struct { ref members, .. } = ir_module.types[ty].inner else {
+            return Err(Error::Validation("mesh shader outputs must be structs"));
+        };
+        let class = spirv::StorageClass::Output;
+        let mut outputs = Vec::with_capacity(members.len());
+        for member in members {
+            let binding = member.binding.as_ref().ok_or(Error::Validation(
+                "mesh shader output members must have bindings",
+            ))?;
+            let id = self.write_varying(
+                ir_module,
+                iface.stage,
+                class,
+                member.name.as_deref(),
+                member.ty,
+                binding,
+                Some(count),
+            )?;
+            iface.varying_ids.push(id);
+            outputs.push(MeshOutputMember {
+                id,
+                type_id: self.get_type_id(LookupType::Handle(member.ty)),
+                pointer_type_id: self.get_pointer_id(member.ty, class),
+                built_in: binding.to_built_in(),
+            });
+        }
+        Ok(outputs)
+    }
+
+    /// Require the capability and extension for task and mesh shaders.
+    pub(super) fn require_mesh_shading(&mut self) -> Result<(), Error> {
+        if self.lang_version() < (1, 4) {
+            return Err(Error::UnsupportedVersion(
+                self.lang_version().0,
+                self.lang_version().1,
+            ));
+        }
+        self.require_any("mesh shaders", &[spirv::Capability::MeshShadingEXT])?;
+        self.use_extension("SPV_EXT_mesh_shader");
+        Ok(())
+    }
+
     fn write_execution_mode(
         &mut self,
         function_id: Word,
@@ -728,6 +857,8 @@ impl Writer {
             Some(FunctionInterface {
                 varying_ids: &mut interface_ids,
                 stage: entry_point.stage,
+                mesh_info: entry_point.mesh_info.as_ref(),
+                task_payload: entry_point.task_payload,
             }),
             debug_info,
         )?;
@@ -762,6 +893,50 @@ impl Writer {
                 .to_words(&mut self.logical_layout.execution_modes);
                 spirv::ExecutionModel::GLCompute
             }
+            crate::ShaderStage::Task => {
+                self.require_mesh_shading()?;
+                Instruction::execution_mode(
+                    function_id,
+                    spirv::ExecutionMode::LocalSize,
+                    &entry_point.workgroup_size,
+                )
+                .to_words(&mut self.logical_layout.execution_modes);
+                spirv::ExecutionModel::TaskEXT
+            }
+            crate::ShaderStage::Mesh => {
+                self.require_mesh_shading()?;
+                Instruction::execution_mode(
+                    function_id,
+                    spirv::ExecutionMode::LocalSize,
+                    &entry_point.workgroup_size,
+                )
+                .to_words(&mut self.logical_layout.execution_modes);
+                let mesh_info = entry_point.mesh_info.as_ref().ok_or(Error::Validation(
+                    "mesh shader output information is missing",
+                ))?;
+                let topology_mode = match mesh_info.topology {
+                    crate::MeshOutputTopology::Points => spirv::ExecutionMode::OutputPoints,
+                    crate::MeshOutputTopology::Lines => spirv::ExecutionMode::OutputLinesEXT,
+                    crate::MeshOutputTopology::Triangles => {
+                        spirv::ExecutionMode::OutputTrianglesEXT
+                    }
+                };
+                for (mode, args) in [
+                    (topology_mode, &[][..]),
+                    (
+                        spirv::ExecutionMode::OutputVertices,
+                        &[mesh_info.max_vertices][..],
+                    ),
+                    (
+                        spirv::ExecutionMode::OutputPrimitivesEXT,
+                        &[mesh_info.max_primitives][..],
+                    ),
+                ] {
+                    Instruction::execution_mode(function_id, mode, args)
+                        .to_words(&mut self.logical_layout.execution_modes);
+                }
+                spirv::ExecutionModel::MeshEXT
+            }
         };
         //self.check(exec_model.required_capabilities())?;
 
@@ -796,8 +971,14 @@ impl Writer {
                 Instruction::type_int(id, bits, signedness)
             }
             Sk::Float => {
-                if bits == 64 {
-                    self.capabilities_used.insert(spirv::Capability::Float64);
+                match bits {
+                    16 => {
+                        self.capabilities_used.insert(spirv::Capability::Float16);
+                    }
+                    64 => {
+                        self.capabilities_used.insert(spirv::Capability::Float64);
+                    }
+                    _ => {}
                 }
                 Instruction::type_float(id, bits)
             }
@@ -921,6 +1102,12 @@ impl Writer {
                 let class = map_storage_class(space);
                 Instruction::type_pointer(id, class, inner_ty)
             }
+            LocalType::PointerToArray { base, size, class } => {
+                // `BindingArray` is declared as a plain `OpTypeArray`.
+                let inner_ty =
+                    self.get_type_id(LookupType::Local(LocalType::BindingArray { base, size }));
+                Instruction::type_pointer(id, class, inner_ty)
+            }
             LocalType::AccelerationStructure => Instruction::type_acceleration_structure(id),
             LocalType::RayQuery => Instruction::type_ray_query(id),
         };
@@ -1145,6 +1332,9 @@ impl Writer {
                 Instruction::constant_64bit(type_id, id, bits as u32, (bits >> 32) as u32)
             }
             crate::Literal::F32(value) => Instruction::constant_32bit(type_id, id, value.to_bits()),
+            crate::Literal::F16(value) => {
+                Instruction::constant_16bit(type_id, id, value.to_bits() as u32)
+            }
             crate::Literal::U32(value) => Instruction::constant_32bit(type_id, id, value),
             crate::Literal::I32(value) => Instruction::constant_32bit(type_id, id, value as u32),
             crate::Literal::U64(value) => {
@@ -1394,6 +1584,65 @@ impl Writer {
         Some(next_id)
     }
 
+    /// Request the capabilities needed to hold the 16-bit floats in `ty` in
+    /// a variable of storage class `class`, if it contains any.
+    ///
+    /// Function-local, private and workgroup variables only need `Float16`,
+    /// which [`make_scalar`] takes care of. Interface and buffer variables
+    /// additionally need one of the `SPV_KHR_16bit_storage` capabilities.
+    ///
+    /// [`make_scalar`]: Writer::make_scalar
+    fn request_f16_storage_capabilities(
+        &mut self,
+        ir_module: &crate::Module,
+        ty: Handle<crate::Type>,
+        class: spirv::StorageClass,
+    ) -> Result<(), Error> {
+        fn contains_f16(types: &UniqueArena<crate::Type>, ty: Handle<crate::Type>) -> bool {
+            match types[ty].inner {
+                crate::TypeInner::Scalar(scalar)
+                | crate::TypeInner::Vector { scalar, .. }
+                | crate::TypeInner::Matrix { scalar, .. } => scalar == crate::Scalar::F16,
+                crate::TypeInner::Array { base, .. }
+                | crate::TypeInner::BindingArray { base, .. } => contains_f16(types, base),
+                crate::TypeInner::Struct { ref members, .. } => {
+                    members.iter().any(|member| contains_f16(types, member.ty))
+                }
+                _ => false,
+            }
+        }
+
+        let (what, capability) = match class {
+            spirv::StorageClass::StorageBuffer => (
+                "16-bit floats in storage buffers",
+                spirv::Capability::StorageBuffer16BitAccess,
+            ),
+            spirv::StorageClass::Uniform => (
+                "16-bit floats in uniform buffers",
+                spirv::Capability::UniformAndStorageBuffer16BitAccess,
+            ),
+            spirv::StorageClass::PushConstant => (
+                "16-bit floats in push constants",
+                spirv::Capability::StoragePushConstant16,
+            ),
+            spirv::StorageClass::Input | spirv::StorageClass::Output => (
+                "16-bit floats in shader inputs and outputs",
+                spirv::Capability::StorageInputOutput16,
+            ),
+            _ => return Ok(()),
+        };
+        if !contains_f16(&ir_module.types, ty) {
+            return Ok(());
+        }
+
+        self.require_any(what, &[capability])?;
+        if self.physical_layout.version < 0x10300 {
+            // 16-bit storage is only core since SPIR-V 1.3
+            self.use_extension("SPV_KHR_16bit_storage");
+        }
+        Ok(())
+    }
+
     /// Generate an `OpVariable` for one value in an [`EntryPoint`]'s IO interface.
     ///
     /// The [`Binding`]s of the arguments and result of an [`EntryPoint`]'s
@@ -1413,6 +1662,7 @@ impl Writer {
     /// [`EntryPoint`]: crate::EntryPoint
     /// [`Input`]: spirv::StorageClass::Input
     /// [`Output`]: spirv::StorageClass::Output
+    #[allow(clippy::too_many_arguments)]
     fn write_varying(
         &mut self,
         ir_module: &crate::Module,
@@ -1421,9 +1671,20 @@ impl Writer {
         debug_name: Option<&str>,
         ty: Handle<crate::Type>,
         binding: &crate::Binding,
+        array_size: Option<u32>,
     ) -> Result<Word, Error> {
+        self.request_f16_storage_capabilities(ir_module, ty, class)?;
+
         let id = self.id_gen.next();
-        let pointer_type_id = self.get_pointer_id(ty, class);
+        // Mesh shader outputs hold a value for every vertex or primitive.
+        let pointer_type_id = match array_size {
+            Some(size) => self.get_type_id(LookupType::Local(LocalType::PointerToArray {
+                base: ty,
+                size,
+                class,
+            })),
+            None => self.get_pointer_id(ty, class),
+        };
         Instruction::variable(pointer_type_id, id, class, None)
             .to_words(&mut self.logical_layout.declarations);
 
@@ -1444,9 +1705,15 @@ impl Writer {
                 interpolation,
                 sampling,
                 second_blend_source,
+                per_primitive,
             } => {
                 self.decorate(id, Decoration::Location, &[location]);
 
+                if per_primitive {
+                    self.require_mesh_shading()?;
+                    self.decorate(id, Decoration::PerPrimitiveEXT, &[]);
+                }
+
                 let no_decorations =
                     // VUID-StandaloneSpirv-Flat-06202
                     // > The Flat, NoPerspective, Sample, and Centroid decorations
@@ -1455,7 +1722,9 @@ impl Writer {
                     // VUID-StandaloneSpirv-Flat-06201
                     // > The Flat, NoPerspective, Sample, and Centroid decorations
                     // > must not be used on variables with the Output storage class in a fragment shader
-                    (class == spirv::StorageClass::Output && stage == crate::ShaderStage::Fragment);
+                    (class == spirv::StorageClass::Output && stage == crate::ShaderStage::Fragment) ||
+                    // Per-primitive values are never interpolated.
+                    (class == spirv::StorageClass::Output && per_primitive);
 
                 if !no_decorations {
                     match interpolation {
@@ -1593,6 +1862,20 @@ impl Writer {
                         )?;
                         BuiltIn::SubgroupLocalInvocationId
                     }
+                    // task
+                    Bi::MeshTaskSize => {
+                        return Err(Error::Validation(
+                            "`mesh_task_size` is an argument to `OpEmitMeshTasksEXT`",
+                        ))
+                    }
+                    // mesh
+                    Bi::PointIndex => BuiltIn::PrimitivePointIndicesEXT,
+                    Bi::LineIndices => BuiltIn::PrimitiveLineIndicesEXT,
+                    Bi::TriangleIndices => BuiltIn::PrimitiveTriangleIndicesEXT,
+                    Bi::CullPrimitive => {
+                        self.decorate(id, Decoration::PerPrimitiveEXT, &[]);
+                        BuiltIn::CullPrimitiveEXT
+                    }
                 };
 
                 self.decorate(id, Decoration::BuiltIn, &[built_in as u32]);
@@ -1638,8 +1921,12 @@ impl Writer {
 
         let id = self.id_gen.next();
         let class = map_storage_class(global_variable.space);
+        if global_variable.space == crate::AddressSpace::TaskPayload {
+            self.require_mesh_shading()?;
+        }
 
         //self.check(class.required_capabilities())?;
+        self.request_f16_storage_capabilities(ir_module, global_variable.ty, class)?;
 
         if self.flags.contains(WriterFlags::DEBUG) {
             if let Some(ref name) = global_variable.name {
@@ -2104,3 +2391,52 @@ fn test_write_physical_layout() {
     writer.write_physical_layout();
     assert_eq!(writer.physical_layout.bound, 3);
 }
+
+#[test]
+fn test_unimplemented_subgroup_operation() {
+    use crate::valid::{Capabilities, SubgroupOperationSet, ValidationFlags};
+    // create a module with a subgroup reduction the validator accepts
+    let mut module = crate::Module::default();
+    let ty = module.types.insert(
+        crate::Type {
+            name: None,
+            inner: crate::TypeInner::Scalar(crate::Scalar::U32),
+        },
+        Default::default(),
+    );
+    let mut fun = crate::Function::default();
+    let argument = fun.expressions.append(
+        crate::Expression::Literal(crate::Literal::U32(1)),
+        Default::default(),
+    );
+    let result = fun.expressions.append(
+        crate::Expression::SubgroupOperationResult { ty },
+        Default::default(),
+    );
+    fun.body.push(
+        crate::Statement::SubgroupCollectiveOperation {
+            op: crate::SubgroupOperation::Add,
+            collective_op: crate::CollectiveOperation::Reduce,
+            argument,
+            result,
+        },
+        Default::default(),
+    );
+    let fun_handle = module.functions.append(fun, Default::default());
+    // analyse the module
+    let info = crate::valid::Validator::new(ValidationFlags::empty(), Capabilities::SUBGROUP)
+        .subgroup_operations(SubgroupOperationSet::all())
+        .validate(&module)
+        .unwrap();
+    // swap in `All` on an integer, which the validator would reject
+    module.functions[fun_handle].body =
+        crate::Block::from_vec(vec![crate::Statement::SubgroupCollectiveOperation {
+            op: crate::SubgroupOperation::All,
+            collective_op: crate::CollectiveOperation::Reduce,
+            argument,
+            result,
+        }]);
+    // process the module
+    let result = super::write_vec(&module, &info, &Options::default(), None);
+    assert!(matches!(result, Err(Error::FeatureNotImplemented(_))));
+}
@@ -23,6 +23,8 @@ pub enum Error {
     UnsupportedMathFunction(crate::MathFunction),
     #[error("Unsupported relational function: {0:?}")]
     UnsupportedRelationalFunction(crate::RelationalFunction),
+    #[error(transparent)]
+    PipelineConstant(#[from] Box<super::pipeline_constants::PipelineConstantError>),
 }
 
 pub fn write_string(
@@ -36,6 +38,26 @@ pub fn write_string(
     Ok(output)
 }
 
+/// Write `module` as WGSL, with its overrides replaced by constants.
+///
+/// [`write_string`] declares each of `module`'s overrides with a WGSL
+/// `override` declaration, leaving their values up to the pipeline. This
+/// instead bakes the values in `pipeline_constants` into the output using
+/// [`process_overrides`], so the result declares no overrides at all.
+///
+/// [`process_overrides`]: super::pipeline_constants::process_overrides
+pub fn write_string_with_pipeline_constants(
+    module: &crate::Module,
+    info: &crate::valid::ModuleInfo,
+    flags: WriterFlags,
+    pipeline_constants: &super::PipelineConstants,
+) -> Result<String, Error> {
+    let (module, info) =
+        super::pipeline_constants::process_overrides(module, info, pipeline_constants)
+            .map_err(Box::new)?;
+    write_string(&module, &info, flags)
+}
+
 impl crate::AtomicFunction {
     const fn to_wgsl(self) -> &'static str {
         match self {
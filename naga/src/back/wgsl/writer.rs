@@ -15,10 +15,12 @@ enum Attribute {
     Binding(u32),
     BuiltIn(crate::BuiltIn),
     Group(u32),
+    Id(u16),
     Invariant,
     Interpolate(Option<crate::Interpolation>, Option<crate::Sampling>),
     Location(u32),
-    SecondBlendSource,
+    BlendSrc(u32),
+    PerPrimitive,
     Stage(ShaderStage),
     WorkGroupSize([u32; 3]),
 }
@@ -59,6 +61,11 @@ bitflags::bitflags! {
     pub struct WriterFlags: u32 {
         /// Always annotate the type information instead of inferring.
         const EXPLICIT_TYPES = 0x1;
+        /// Rely on the `pointer_composite_access` language extension, writing
+        /// `p.member` and `p[i]` rather than `(*p).member` and `(*p)[i]`.
+        ///
+        /// This also emits a `requires pointer_composite_access;` directive.
+        const POINTER_COMPOSITE_ACCESS = 0x2;
     }
 }
 
@@ -110,14 +117,11 @@ impl<W: Write> Writer<W> {
     }
 
     pub fn write(&mut self, module: &Module, info: &valid::ModuleInfo) -> BackendResult {
-        if !module.overrides.is_empty() {
-            return Err(Error::Unimplemented(
-                "Pipeline constants are not yet supported for this back-end".to_string(),
-            ));
-        }
-
         self.reset(module);
 
+        // Write all `enable` and `requires` declarations
+        self.write_enable_declarations(module)?;
+
         // Save all ep result types
         for ep in &module.entry_points {
             if let Some(ref result) = ep.function.result {
@@ -151,9 +155,19 @@ impl<W: Write> Writer<W> {
             }
         }
 
+        // Write all overrides
+        let mut overrides = module.overrides.iter().peekable();
+        while let Some((handle, _)) = overrides.next() {
+            self.write_override(module, info, handle)?;
+            // Add extra newline for readability on last iteration
+            if overrides.peek().is_none() {
+                writeln!(self.out)?;
+            }
+        }
+
         // Write all globals
         for (ty, global) in module.global_variables.iter() {
-            self.write_global(module, global, ty)?;
+            self.write_global(module, info, global, ty)?;
         }
 
         if !module.global_variables.is_empty() {
@@ -183,13 +197,14 @@ impl<W: Write> Writer<W> {
         for (index, ep) in module.entry_points.iter().enumerate() {
             let attributes = match ep.stage {
                 ShaderStage::Vertex | ShaderStage::Fragment => vec![Attribute::Stage(ep.stage)],
-                ShaderStage::Compute => vec![
-                    Attribute::Stage(ShaderStage::Compute),
+                ShaderStage::Compute | ShaderStage::Task | ShaderStage::Mesh => vec![
+                    Attribute::Stage(ep.stage),
                     Attribute::WorkGroupSize(ep.workgroup_size),
                 ],
             };
 
             self.write_attributes(&attributes)?;
+            self.write_mesh_shader_attributes(module, ep)?;
             // Add a newline after attribute
             writeln!(self.out)?;
 
@@ -217,6 +232,94 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
+    /// Write the `enable` directives for the extensions that `module` uses.
+    fn write_enable_declarations(&mut self, module: &Module) -> BackendResult {
+        let is_f16_literal = |expr: &crate::Expression| {
+            matches!(*expr, crate::Expression::Literal(crate::Literal::F16(_)))
+        };
+        let uses_f16 = module
+            .types
+            .iter()
+            .any(|(_, ty)| ty.inner.scalar() == Some(crate::Scalar::F16))
+            || module
+                .global_expressions
+                .iter()
+                .any(|(_, expr)| is_f16_literal(expr))
+            || module
+                .functions
+                .iter()
+                .map(|(_, function)| function)
+                .chain(module.entry_points.iter().map(|ep| &ep.function))
+                .any(|function| {
+                    function
+                        .expressions
+                        .iter()
+                        .any(|(_, expr)| is_f16_literal(expr))
+                });
+        let mut uses_clip_distances = false;
+        let mut uses_dual_source_blending = false;
+        let mut uses_mesh_shader = module
+            .global_variables
+            .iter()
+            .any(|(_, var)| var.space == crate::AddressSpace::TaskPayload);
+        for ep in module.entry_points.iter() {
+            if let ShaderStage::Task | ShaderStage::Mesh = ep.stage {
+                uses_mesh_shader = true;
+            }
+            let result = ep.function.result.iter().map(|res| (res.ty, &res.binding));
+            let arguments = ep
+                .function
+                .arguments
+                .iter()
+                .map(|arg| (arg.ty, &arg.binding));
+            for (ty, binding) in result.chain(arguments) {
+                let members = match module.types[ty].inner {
+                    TypeInner::Struct { ref members, .. } => members.as_slice(),
+                    _ => &[],
+                };
+                let member_bindings = members.iter().filter_map(|m| m.binding.as_ref());
+                for binding in binding.iter().chain(member_bindings) {
+                    match *binding {
+                        crate::Binding::BuiltIn(crate::BuiltIn::ClipDistance) => {
+                            uses_clip_distances = true;
+                        }
+                        crate::Binding::Location {
+                            second_blend_source: true,
+                            ..
+                        } => uses_dual_source_blending = true,
+                        crate::Binding::Location {
+                            per_primitive: true,
+                            ..
+                        } => uses_mesh_shader = true,
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        let mut any_written = false;
+        for (used, extension) in [
+            (uses_f16, "f16"),
+            (uses_clip_distances, "clip_distances"),
+            (uses_dual_source_blending, "dual_source_blending"),
+            (uses_mesh_shader, "wgpu_mesh_shader"),
+        ] {
+            if used {
+                writeln!(self.out, "enable {extension};")?;
+                any_written = true;
+            }
+        }
+        if self.flags.contains(WriterFlags::POINTER_COMPOSITE_ACCESS) {
+            writeln!(self.out, "requires pointer_composite_access;")?;
+            any_written = true;
+        }
+        if any_written {
+            writeln!(self.out)?;
+        }
+
+        Ok(())
+    }
+
     /// Helper method used to write struct name
     ///
     /// # Notes
@@ -228,6 +331,8 @@ impl<W: Write> Writer<W> {
                     ShaderStage::Compute => "ComputeOutput",
                     ShaderStage::Fragment => "FragmentOutput",
                     ShaderStage::Vertex => "VertexOutput",
+                    ShaderStage::Task => "TaskOutput",
+                    ShaderStage::Mesh => "MeshOutput",
                 };
 
                 write!(self.out, "{name}")?;
@@ -340,7 +445,8 @@ impl<W: Write> Writer<W> {
         for attribute in attributes {
             match *attribute {
                 Attribute::Location(id) => write!(self.out, "@location({id}) ")?,
-                Attribute::SecondBlendSource => write!(self.out, "@second_blend_source ")?,
+                Attribute::BlendSrc(blend_src) => write!(self.out, "@blend_src({blend_src}) ")?,
+                Attribute::PerPrimitive => write!(self.out, "@per_primitive ")?,
                 Attribute::BuiltIn(builtin_attrib) => {
                     let builtin = builtin_str(builtin_attrib)?;
                     write!(self.out, "@builtin({builtin}) ")?;
@@ -350,6 +456,8 @@ impl<W: Write> Writer<W> {
                         ShaderStage::Vertex => "vertex",
                         ShaderStage::Fragment => "fragment",
                         ShaderStage::Compute => "compute",
+                        ShaderStage::Task => "task",
+                        ShaderStage::Mesh => "mesh",
                     };
                     write!(self.out, "@{stage_str} ")?;
                 }
@@ -362,6 +470,7 @@ impl<W: Write> Writer<W> {
                 }
                 Attribute::Binding(id) => write!(self.out, "@binding({id}) ")?,
                 Attribute::Group(id) => write!(self.out, "@group({id}) ")?,
+                Attribute::Id(id) => write!(self.out, "@id({id}) ")?,
                 Attribute::Invariant => write!(self.out, "@invariant ")?,
                 Attribute::Interpolate(interpolation, sampling) => {
                     if sampling.is_some() && sampling != Some(crate::Sampling::Center) {
@@ -390,6 +499,34 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
+    /// Write the `@payload` and mesh output attributes of a task or mesh
+    /// shader entry point.
+    fn write_mesh_shader_attributes(
+        &mut self,
+        module: &Module,
+        ep: &crate::EntryPoint,
+    ) -> BackendResult {
+        if let Some(payload) = ep.task_payload {
+            let name = &self.names[&NameKey::GlobalVariable(payload)];
+            write!(self.out, "@payload({name}) ")?;
+        }
+        if let Some(ref mesh_info) = ep.mesh_info {
+            let topology = match mesh_info.topology {
+                crate::MeshOutputTopology::Points => "points",
+                crate::MeshOutputTopology::Lines => "lines",
+                crate::MeshOutputTopology::Triangles => "triangles",
+            };
+            write!(self.out, "@output_topology({topology}) ")?;
+            write!(self.out, "@vertex_output(")?;
+            self.write_type(module, mesh_info.vertex_output_type)?;
+            write!(self.out, ", {}) ", mesh_info.max_vertices)?;
+            write!(self.out, "@primitive_output(")?;
+            self.write_type(module, mesh_info.primitive_output_type)?;
+            write!(self.out, ", {}) ", mesh_info.max_primitives)?;
+        }
+        Ok(())
+    }
+
     /// Helper method used to write structs
     ///
     /// # Notes
@@ -404,11 +541,35 @@ impl<W: Write> Writer<W> {
         self.write_struct_name(module, handle)?;
         write!(self.out, " {{")?;
         writeln!(self.out)?;
+        let uses_dual_source_blending = members.iter().any(|member| {
+            matches!(
+                member.binding,
+                Some(crate::Binding::Location {
+                    second_blend_source: true,
+                    ..
+                })
+            )
+        });
         for (index, member) in members.iter().enumerate() {
             // The indentation is only for readability
             write!(self.out, "{}", back::INDENT)?;
             if let Some(ref binding) = member.binding {
-                self.write_attributes(&map_binding_to_attribute(binding))?;
+                let mut attributes = map_binding_to_attribute(binding);
+                // WGSL requires `@blend_src` on both sources, but the IR only
+                // marks the second one.
+                if uses_dual_source_blending
+                    && matches!(
+                        *binding,
+                        crate::Binding::Location {
+                            location: 0,
+                            second_blend_source: false,
+                            ..
+                        }
+                    )
+                {
+                    attributes.insert(1, Attribute::BlendSrc(0));
+                }
+                self.write_attributes(&attributes)?;
             }
             // Write struct member name and type
             let member_name = &self.names[&NameKey::StructMember(handle, index as u32)];
@@ -1008,9 +1169,27 @@ impl<W: Write> Writer<W> {
                     (crate::CollectiveOperation::InclusiveScan, crate::SubgroupOperation::Mul) => {
                         write!(self.out, "subgroupInclusiveMul(")?
                     }
+                    (crate::CollectiveOperation::ClusteredReduce(_), op) => {
+                        let fun = match op {
+                            crate::SubgroupOperation::Add => "subgroupClusteredAdd",
+                            crate::SubgroupOperation::Mul => "subgroupClusteredMul",
+                            crate::SubgroupOperation::Max => "subgroupClusteredMax",
+                            crate::SubgroupOperation::Min => "subgroupClusteredMin",
+                            crate::SubgroupOperation::And => "subgroupClusteredAnd",
+                            crate::SubgroupOperation::Or => "subgroupClusteredOr",
+                            crate::SubgroupOperation::Xor => "subgroupClusteredXor",
+                            crate::SubgroupOperation::All | crate::SubgroupOperation::Any => {
+                                unreachable!()
+                            }
+                        };
+                        write!(self.out, "{fun}(")?
+                    }
                     _ => unimplemented!(),
                 }
                 self.write_expr(module, argument, func_ctx)?;
+                if let crate::CollectiveOperation::ClusteredReduce(size) = collective_op {
+                    write!(self.out, ", {size}u")?;
+                }
                 writeln!(self.out, ");")?;
             }
             Statement::SubgroupGather {
@@ -1042,21 +1221,54 @@ impl<W: Write> Writer<W> {
                     crate::GatherMode::ShuffleXor(_) => {
                         write!(self.out, "subgroupShuffleXor(")?;
                     }
+                    crate::GatherMode::QuadBroadcast(_) => {
+                        write!(self.out, "quadBroadcast(")?;
+                    }
+                    crate::GatherMode::QuadSwap(direction) => match direction {
+                        crate::Direction::X => {
+                            write!(self.out, "quadSwapX(")?;
+                        }
+                        crate::Direction::Y => {
+                            write!(self.out, "quadSwapY(")?;
+                        }
+                        crate::Direction::Diagonal => {
+                            write!(self.out, "quadSwapDiagonal(")?;
+                        }
+                    },
                 }
                 self.write_expr(module, argument, func_ctx)?;
                 match mode {
-                    crate::GatherMode::BroadcastFirst => {}
+                    crate::GatherMode::BroadcastFirst | crate::GatherMode::QuadSwap(_) => {}
                     crate::GatherMode::Broadcast(index)
                     | crate::GatherMode::Shuffle(index)
                     | crate::GatherMode::ShuffleDown(index)
                     | crate::GatherMode::ShuffleUp(index)
-                    | crate::GatherMode::ShuffleXor(index) => {
+                    | crate::GatherMode::ShuffleXor(index)
+                    | crate::GatherMode::QuadBroadcast(index) => {
                         write!(self.out, ", ")?;
                         self.write_expr(module, index, func_ctx)?;
                     }
                 }
                 writeln!(self.out, ");")?;
             }
+            Statement::MeshFunction(ref fun) => {
+                write!(self.out, "{level}")?;
+                let (name, first, second) = match *fun {
+                    crate::MeshFunction::SetMeshOutputs {
+                        vertex_count,
+                        primitive_count,
+                    } => ("setMeshOutputs", vertex_count, primitive_count),
+                    crate::MeshFunction::SetVertex { index, value } => ("setVertex", index, value),
+                    crate::MeshFunction::SetPrimitive { index, value } => {
+                        ("setPrimitive", index, value)
+                    }
+                };
+                write!(self.out, "{name}(")?;
+                self.write_expr(module, first, func_ctx)?;
+                write!(self.out, ", ")?;
+                self.write_expr(module, second, func_ctx)?;
+                writeln!(self.out, ");")?;
+            }
         }
 
         Ok(())
@@ -1205,6 +1417,27 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
+    /// Write the `base` operand of an `Access` or `AccessIndex` expression.
+    ///
+    /// This is `write_expr_with_indirection`, except that when the
+    /// `pointer_composite_access` language extension is available, a pointer
+    /// `p` needn't be written as `(*p)` to be accessed.
+    fn write_access_base(
+        &mut self,
+        module: &Module,
+        base: Handle<crate::Expression>,
+        func_ctx: &back::FunctionCtx<'_>,
+        requested: Indirection,
+    ) -> BackendResult {
+        if self.flags.contains(WriterFlags::POINTER_COMPOSITE_ACCESS) {
+            if let Indirection::Reference = requested {
+                let plain = self.plain_form_indirection(base, module, func_ctx);
+                return self.write_expr_plain_form(module, base, func_ctx, plain);
+            }
+        }
+        self.write_expr_with_indirection(module, base, func_ctx, requested)
+    }
+
     fn write_const_expression(
         &mut self,
         module: &Module,
@@ -1218,6 +1451,127 @@ impl<W: Write> Writer<W> {
         )
     }
 
+    /// Write a global expression that may depend on overrides.
+    ///
+    /// Unlike const-expressions, override-expressions can't always be
+    /// evaluated to a literal or composite before reaching the backend, so
+    /// the arithmetic that [`ConstantEvaluator`] left in
+    /// [`Module::global_expressions`] must be written out as well.
+    ///
+    /// [`ConstantEvaluator`]: proc::ConstantEvaluator
+    fn write_override_expression(
+        &mut self,
+        module: &Module,
+        info: &valid::ModuleInfo,
+        expr: Handle<crate::Expression>,
+    ) -> BackendResult {
+        use crate::Expression;
+
+        match module.global_expressions[expr] {
+            Expression::Override(handle) => {
+                write!(self.out, "{}", self.names[&NameKey::Override(handle)])?;
+            }
+            Expression::Binary { op, left, right } => {
+                write!(self.out, "(")?;
+                self.write_override_expression(module, info, left)?;
+                write!(self.out, " {} ", back::binary_operation_str(op))?;
+                self.write_override_expression(module, info, right)?;
+                write!(self.out, ")")?;
+            }
+            Expression::Unary { op, expr } => {
+                let unary = match op {
+                    crate::UnaryOperator::Negate => "-",
+                    crate::UnaryOperator::LogicalNot => "!",
+                    crate::UnaryOperator::BitwiseNot => "~",
+                };
+                write!(self.out, "{unary}(")?;
+                self.write_override_expression(module, info, expr)?;
+                write!(self.out, ")")?;
+            }
+            Expression::Select {
+                condition,
+                accept,
+                reject,
+            } => {
+                write!(self.out, "select(")?;
+                self.write_override_expression(module, info, reject)?;
+                write!(self.out, ", ")?;
+                self.write_override_expression(module, info, accept)?;
+                write!(self.out, ", ")?;
+                self.write_override_expression(module, info, condition)?;
+                write!(self.out, ")")?;
+            }
+            Expression::Relational { fun, argument } => {
+                let fun_name = match fun {
+                    crate::RelationalFunction::All => "all",
+                    crate::RelationalFunction::Any => "any",
+                    _ => return Err(Error::UnsupportedRelationalFunction(fun)),
+                };
+                write!(self.out, "{fun_name}(")?;
+                self.write_override_expression(module, info, argument)?;
+                write!(self.out, ")")?;
+            }
+            Expression::Math {
+                fun,
+                arg,
+                arg1,
+                arg2,
+                arg3,
+            } => {
+                write!(self.out, "{}(", math_function_str(fun)?)?;
+                self.write_override_expression(module, info, arg)?;
+                for arg in IntoIterator::into_iter([arg1, arg2, arg3]).flatten() {
+                    write!(self.out, ", ")?;
+                    self.write_override_expression(module, info, arg)?;
+                }
+                write!(self.out, ")")?;
+            }
+            Expression::As {
+                expr,
+                kind,
+                convert,
+            } => {
+                let inner = info[expr].inner_with(&module.types);
+                self.write_conversion_type(inner, kind, convert)?;
+                write!(self.out, "(")?;
+                self.write_override_expression(module, info, expr)?;
+                write!(self.out, ")")?;
+            }
+            Expression::Access { base, index } => {
+                self.write_override_expression(module, info, base)?;
+                write!(self.out, "[")?;
+                self.write_override_expression(module, info, index)?;
+                write!(self.out, "]")?;
+            }
+            Expression::AccessIndex { base, index } => {
+                self.write_override_expression(module, info, base)?;
+                match *info[base].inner_with(&module.types) {
+                    TypeInner::Vector { .. } => {
+                        write!(self.out, ".{}", back::COMPONENTS[index as usize])?
+                    }
+                    TypeInner::Struct { .. } => {
+                        // Struct-typed expressions always resolve to a type handle.
+                        let ty = info[base].handle().unwrap();
+                        write!(
+                            self.out,
+                            ".{}",
+                            &self.names[&NameKey::StructMember(ty, index)]
+                        )?
+                    }
+                    _ => write!(self.out, "[{index}]")?,
+                }
+            }
+            _ => self.write_possibly_const_expression(
+                module,
+                expr,
+                &module.global_expressions,
+                |writer, expr| writer.write_override_expression(module, info, expr),
+            )?,
+        }
+
+        Ok(())
+    }
+
     fn write_possibly_const_expression<E>(
         &mut self,
         module: &Module,
@@ -1232,6 +1586,7 @@ impl<W: Write> Writer<W> {
 
         match expressions[expr] {
             Expression::Literal(literal) => match literal {
+                crate::Literal::F16(value) => write!(self.out, "{value}h")?,
                 crate::Literal::F32(value) => write!(self.out, "{value}f")?,
                 crate::Literal::U32(value) => write!(self.out, "{value}u")?,
                 crate::Literal::I32(value) => {
@@ -1298,6 +1653,71 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
+    /// Helper method used to write the type or `bitcast` prefix of an
+    /// [`As`](crate::Expression::As) expression whose operand has type `inner`
+    fn write_conversion_type(
+        &mut self,
+        inner: &TypeInner,
+        kind: crate::ScalarKind,
+        convert: Option<crate::Bytes>,
+    ) -> BackendResult {
+        match *inner {
+            TypeInner::Matrix {
+                columns,
+                rows,
+                scalar,
+            } => {
+                let scalar = crate::Scalar {
+                    kind,
+                    width: convert.unwrap_or(scalar.width),
+                };
+                let scalar_kind_str = scalar_kind_str(scalar);
+                write!(
+                    self.out,
+                    "mat{}x{}<{}>",
+                    back::vector_size_str(columns),
+                    back::vector_size_str(rows),
+                    scalar_kind_str
+                )?;
+            }
+            TypeInner::Vector {
+                size,
+                scalar: crate::Scalar { width, .. },
+            } => {
+                let scalar = crate::Scalar {
+                    kind,
+                    width: convert.unwrap_or(width),
+                };
+                let vector_size_str = back::vector_size_str(size);
+                let scalar_kind_str = scalar_kind_str(scalar);
+                if convert.is_some() {
+                    write!(self.out, "vec{vector_size_str}<{scalar_kind_str}>")?;
+                } else {
+                    write!(self.out, "bitcast<vec{vector_size_str}<{scalar_kind_str}>>")?;
+                }
+            }
+            TypeInner::Scalar(crate::Scalar { width, .. }) => {
+                let scalar = crate::Scalar {
+                    kind,
+                    width: convert.unwrap_or(width),
+                };
+                let scalar_kind_str = scalar_kind_str(scalar);
+                if convert.is_some() {
+                    write!(self.out, "{scalar_kind_str}")?
+                } else {
+                    write!(self.out, "bitcast<{scalar_kind_str}>")?
+                }
+            }
+            _ => {
+                return Err(Error::Unimplemented(format!(
+                    "write_expr expression::as {inner:?}"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Write the 'plain form' of `expr`.
     ///
     /// An expression's 'plain form' is the most general rendition of that
@@ -1342,7 +1762,9 @@ impl<W: Write> Writer<W> {
                     |writer, expr| writer.write_expr(module, expr, func_ctx),
                 )?;
             }
-            Expression::Override(_) => unreachable!(),
+            Expression::Override(handle) => {
+                write!(self.out, "{}", self.names[&NameKey::Override(handle)])?;
+            }
             Expression::FunctionArgument(pos) => {
                 let name_key = func_ctx.argument_key(pos);
                 let name = &self.names[&name_key];
@@ -1356,7 +1778,7 @@ impl<W: Write> Writer<W> {
                 write!(self.out, ")")?;
             }
             Expression::Access { base, index } => {
-                self.write_expr_with_indirection(module, base, func_ctx, indirection)?;
+                self.write_access_base(module, base, func_ctx, indirection)?;
                 write!(self.out, "[")?;
                 self.write_expr(module, index, func_ctx)?;
                 write!(self.out, "]")?
@@ -1365,7 +1787,7 @@ impl<W: Write> Writer<W> {
                 let base_ty_res = &func_ctx.info[base].ty;
                 let mut resolved = base_ty_res.inner_with(&module.types);
 
-                self.write_expr_with_indirection(module, base, func_ctx, indirection)?;
+                self.write_access_base(module, base, func_ctx, indirection)?;
 
                 let base_ty_handle = match *resolved {
                     TypeInner::Pointer { base, space: _ } => {
@@ -1569,59 +1991,7 @@ impl<W: Write> Writer<W> {
                 convert,
             } => {
                 let inner = func_ctx.resolve_type(expr, &module.types);
-                match *inner {
-                    TypeInner::Matrix {
-                        columns,
-                        rows,
-                        scalar,
-                    } => {
-                        let scalar = crate::Scalar {
-                            kind,
-                            width: convert.unwrap_or(scalar.width),
-                        };
-                        let scalar_kind_str = scalar_kind_str(scalar);
-                        write!(
-                            self.out,
-                            "mat{}x{}<{}>",
-                            back::vector_size_str(columns),
-                            back::vector_size_str(rows),
-                            scalar_kind_str
-                        )?;
-                    }
-                    TypeInner::Vector {
-                        size,
-                        scalar: crate::Scalar { width, .. },
-                    } => {
-                        let scalar = crate::Scalar {
-                            kind,
-                            width: convert.unwrap_or(width),
-                        };
-                        let vector_size_str = back::vector_size_str(size);
-                        let scalar_kind_str = scalar_kind_str(scalar);
-                        if convert.is_some() {
-                            write!(self.out, "vec{vector_size_str}<{scalar_kind_str}>")?;
-                        } else {
-                            write!(self.out, "bitcast<vec{vector_size_str}<{scalar_kind_str}>>")?;
-                        }
-                    }
-                    TypeInner::Scalar(crate::Scalar { width, .. }) => {
-                        let scalar = crate::Scalar {
-                            kind,
-                            width: convert.unwrap_or(width),
-                        };
-                        let scalar_kind_str = scalar_kind_str(scalar);
-                        if convert.is_some() {
-                            write!(self.out, "{scalar_kind_str}")?
-                        } else {
-                            write!(self.out, "bitcast<{scalar_kind_str}>")?
-                        }
-                    }
-                    _ => {
-                        return Err(Error::Unimplemented(format!(
-                            "write_expr expression::as {inner:?}"
-                        )));
-                    }
-                };
+                self.write_conversion_type(inner, kind, convert)?;
                 write!(self.out, "(")?;
                 self.write_expr(module, expr, func_ctx)?;
                 write!(self.out, ")")?;
@@ -1668,87 +2038,6 @@ impl<W: Write> Writer<W> {
                 }
 
                 let function = match fun {
-                    Mf::Abs => Function::Regular("abs"),
-                    Mf::Min => Function::Regular("min"),
-                    Mf::Max => Function::Regular("max"),
-                    Mf::Clamp => Function::Regular("clamp"),
-                    Mf::Saturate => Function::Regular("saturate"),
-                    // trigonometry
-                    Mf::Cos => Function::Regular("cos"),
-                    Mf::Cosh => Function::Regular("cosh"),
-                    Mf::Sin => Function::Regular("sin"),
-                    Mf::Sinh => Function::Regular("sinh"),
-                    Mf::Tan => Function::Regular("tan"),
-                    Mf::Tanh => Function::Regular("tanh"),
-                    Mf::Acos => Function::Regular("acos"),
-                    Mf::Asin => Function::Regular("asin"),
-                    Mf::Atan => Function::Regular("atan"),
-                    Mf::Atan2 => Function::Regular("atan2"),
-                    Mf::Asinh => Function::Regular("asinh"),
-                    Mf::Acosh => Function::Regular("acosh"),
-                    Mf::Atanh => Function::Regular("atanh"),
-                    Mf::Radians => Function::Regular("radians"),
-                    Mf::Degrees => Function::Regular("degrees"),
-                    // decomposition
-                    Mf::Ceil => Function::Regular("ceil"),
-                    Mf::Floor => Function::Regular("floor"),
-                    Mf::Round => Function::Regular("round"),
-                    Mf::Fract => Function::Regular("fract"),
-                    Mf::Trunc => Function::Regular("trunc"),
-                    Mf::Modf => Function::Regular("modf"),
-                    Mf::Frexp => Function::Regular("frexp"),
-                    Mf::Ldexp => Function::Regular("ldexp"),
-                    // exponent
-                    Mf::Exp => Function::Regular("exp"),
-                    Mf::Exp2 => Function::Regular("exp2"),
-                    Mf::Log => Function::Regular("log"),
-                    Mf::Log2 => Function::Regular("log2"),
-                    Mf::Pow => Function::Regular("pow"),
-                    // geometry
-                    Mf::Dot => Function::Regular("dot"),
-                    Mf::Cross => Function::Regular("cross"),
-                    Mf::Distance => Function::Regular("distance"),
-                    Mf::Length => Function::Regular("length"),
-                    Mf::Normalize => Function::Regular("normalize"),
-                    Mf::FaceForward => Function::Regular("faceForward"),
-                    Mf::Reflect => Function::Regular("reflect"),
-                    Mf::Refract => Function::Regular("refract"),
-                    // computational
-                    Mf::Sign => Function::Regular("sign"),
-                    Mf::Fma => Function::Regular("fma"),
-                    Mf::Mix => Function::Regular("mix"),
-                    Mf::Step => Function::Regular("step"),
-                    Mf::SmoothStep => Function::Regular("smoothstep"),
-                    Mf::Sqrt => Function::Regular("sqrt"),
-                    Mf::InverseSqrt => Function::Regular("inverseSqrt"),
-                    Mf::Transpose => Function::Regular("transpose"),
-                    Mf::Determinant => Function::Regular("determinant"),
-                    Mf::QuantizeToF16 => Function::Regular("quantizeToF16"),
-                    // bits
-                    Mf::CountTrailingZeros => Function::Regular("countTrailingZeros"),
-                    Mf::CountLeadingZeros => Function::Regular("countLeadingZeros"),
-                    Mf::CountOneBits => Function::Regular("countOneBits"),
-                    Mf::ReverseBits => Function::Regular("reverseBits"),
-                    Mf::ExtractBits => Function::Regular("extractBits"),
-                    Mf::InsertBits => Function::Regular("insertBits"),
-                    Mf::FirstTrailingBit => Function::Regular("firstTrailingBit"),
-                    Mf::FirstLeadingBit => Function::Regular("firstLeadingBit"),
-                    // data packing
-                    Mf::Pack4x8snorm => Function::Regular("pack4x8snorm"),
-                    Mf::Pack4x8unorm => Function::Regular("pack4x8unorm"),
-                    Mf::Pack2x16snorm => Function::Regular("pack2x16snorm"),
-                    Mf::Pack2x16unorm => Function::Regular("pack2x16unorm"),
-                    Mf::Pack2x16float => Function::Regular("pack2x16float"),
-                    Mf::Pack4xI8 => Function::Regular("pack4xI8"),
-                    Mf::Pack4xU8 => Function::Regular("pack4xU8"),
-                    // data unpacking
-                    Mf::Unpack4x8snorm => Function::Regular("unpack4x8snorm"),
-                    Mf::Unpack4x8unorm => Function::Regular("unpack4x8unorm"),
-                    Mf::Unpack2x16snorm => Function::Regular("unpack2x16snorm"),
-                    Mf::Unpack2x16unorm => Function::Regular("unpack2x16unorm"),
-                    Mf::Unpack2x16float => Function::Regular("unpack2x16float"),
-                    Mf::Unpack4xI8 => Function::Regular("unpack4xI8"),
-                    Mf::Unpack4xU8 => Function::Regular("unpack4xU8"),
                     Mf::Inverse => {
                         let typ = func_ctx.resolve_type(arg, &module.types);
 
@@ -1758,7 +2047,7 @@ impl<W: Write> Writer<W> {
 
                         Function::InversePolyfill(overload)
                     }
-                    Mf::Outer => return Err(Error::UnsupportedMathFunction(fun)),
+                    _ => Function::Regular(math_function_str(fun)?),
                 };
 
                 match function {
@@ -1868,6 +2157,7 @@ impl<W: Write> Writer<W> {
     fn write_global(
         &mut self,
         module: &Module,
+        info: &valid::ModuleInfo,
         global: &crate::GlobalVariable,
         handle: Handle<crate::GlobalVariable>,
     ) -> BackendResult {
@@ -1902,7 +2192,7 @@ impl<W: Write> Writer<W> {
         // Write initializer
         if let Some(init) = global.init {
             write!(self.out, " = ")?;
-            self.write_const_expression(module, init)?;
+            self.write_override_expression(module, info, init)?;
         }
 
         // End with semicolon
@@ -1932,6 +2222,33 @@ impl<W: Write> Writer<W> {
         Ok(())
     }
 
+    /// Helper method used to write
+    /// [pipeline-overridable constants](https://gpuweb.github.io/gpuweb/wgsl/#override-decls)
+    ///
+    /// # Notes
+    /// Ends in a newline
+    fn write_override(
+        &mut self,
+        module: &Module,
+        info: &valid::ModuleInfo,
+        handle: Handle<crate::Override>,
+    ) -> BackendResult {
+        let override_ = &module.overrides[handle];
+        if let Some(id) = override_.id {
+            self.write_attributes(&[Attribute::Id(id)])?;
+        }
+        let name = &self.names[&NameKey::Override(handle)];
+        write!(self.out, "override {name}: ")?;
+        self.write_type(module, override_.ty)?;
+        if let Some(init) = override_.init {
+            write!(self.out, " = ")?;
+            self.write_override_expression(module, info, init)?;
+        }
+        writeln!(self.out, ";")?;
+
+        Ok(())
+    }
+
     // See https://github.com/rust-lang/rust-clippy/issues/4979.
     #[allow(clippy::missing_const_for_fn)]
     pub fn finish(self) -> W {
@@ -1939,11 +2256,110 @@ impl<W: Write> Writer<W> {
     }
 }
 
+/// Return the WGSL name of the built-in function implementing `fun`.
+///
+/// Math functions that have no WGSL built-in, such as [`Inverse`] and
+/// [`Outer`], produce [`Error::UnsupportedMathFunction`].
+///
+/// [`Inverse`]: crate::MathFunction::Inverse
+/// [`Outer`]: crate::MathFunction::Outer
+const fn math_function_str(fun: crate::MathFunction) -> Result<&'static str, Error> {
+    use crate::MathFunction as Mf;
+
+    Ok(match fun {
+        Mf::Abs => "abs",
+        Mf::Min => "min",
+        Mf::Max => "max",
+        Mf::Clamp => "clamp",
+        Mf::Saturate => "saturate",
+        // trigonometry
+        Mf::Cos => "cos",
+        Mf::Cosh => "cosh",
+        Mf::Sin => "sin",
+        Mf::Sinh => "sinh",
+        Mf::Tan => "tan",
+        Mf::Tanh => "tanh",
+        Mf::Acos => "acos",
+        Mf::Asin => "asin",
+        Mf::Atan => "atan",
+        Mf::Atan2 => "atan2",
+        Mf::Asinh => "asinh",
+        Mf::Acosh => "acosh",
+        Mf::Atanh => "atanh",
+        Mf::Radians => "radians",
+        Mf::Degrees => "degrees",
+        // decomposition
+        Mf::Ceil => "ceil",
+        Mf::Floor => "floor",
+        Mf::Round => "round",
+        Mf::Fract => "fract",
+        Mf::Trunc => "trunc",
+        Mf::Modf => "modf",
+        Mf::Frexp => "frexp",
+        Mf::Ldexp => "ldexp",
+        // exponent
+        Mf::Exp => "exp",
+        Mf::Exp2 => "exp2",
+        Mf::Log => "log",
+        Mf::Log2 => "log2",
+        Mf::Pow => "pow",
+        // geometry
+        Mf::Dot => "dot",
+        Mf::Dot4I8Packed => "dot4I8Packed",
+        Mf::Dot4U8Packed => "dot4U8Packed",
+        Mf::Cross => "cross",
+        Mf::Distance => "distance",
+        Mf::Length => "length",
+        Mf::Normalize => "normalize",
+        Mf::FaceForward => "faceForward",
+        Mf::Reflect => "reflect",
+        Mf::Refract => "refract",
+        // computational
+        Mf::Sign => "sign",
+        Mf::Fma => "fma",
+        Mf::Mix => "mix",
+        Mf::Step => "step",
+        Mf::SmoothStep => "smoothstep",
+        Mf::Sqrt => "sqrt",
+        Mf::InverseSqrt => "inverseSqrt",
+        Mf::Transpose => "transpose",
+        Mf::Determinant => "determinant",
+        Mf::QuantizeToF16 => "quantizeToF16",
+        // bits
+        Mf::CountTrailingZeros => "countTrailingZeros",
+        Mf::CountLeadingZeros => "countLeadingZeros",
+        Mf::CountOneBits => "countOneBits",
+        Mf::ReverseBits => "reverseBits",
+        Mf::ExtractBits => "extractBits",
+        Mf::InsertBits => "insertBits",
+        Mf::FirstTrailingBit => "firstTrailingBit",
+        Mf::FirstLeadingBit => "firstLeadingBit",
+        // data packing
+        Mf::Pack4x8snorm => "pack4x8snorm",
+        Mf::Pack4x8unorm => "pack4x8unorm",
+        Mf::Pack2x16snorm => "pack2x16snorm",
+        Mf::Pack2x16unorm => "pack2x16unorm",
+        Mf::Pack2x16float => "pack2x16float",
+        Mf::Pack4xI8 => "pack4xI8",
+        Mf::Pack4xU8 => "pack4xU8",
+        // data unpacking
+        Mf::Unpack4x8snorm => "unpack4x8snorm",
+        Mf::Unpack4x8unorm => "unpack4x8unorm",
+        Mf::Unpack2x16snorm => "unpack2x16snorm",
+        Mf::Unpack2x16unorm => "unpack2x16unorm",
+        Mf::Unpack2x16float => "unpack2x16float",
+        Mf::Unpack4xI8 => "unpack4xI8",
+        Mf::Unpack4xU8 => "unpack4xU8",
+        Mf::Inverse | Mf::Outer => return Err(Error::UnsupportedMathFunction(fun)),
+    })
+}
+
 fn builtin_str(built_in: crate::BuiltIn) -> Result<&'static str, Error> {
     use crate::BuiltIn as Bi;
 
     Ok(match built_in {
         Bi::VertexIndex => "vertex_index",
+        Bi::ClipDistance => "clip_distances",
         Bi::InstanceIndex => "instance_index",
         Bi::Position { .. } => "position",
         Bi::FrontFacing => "front_facing",
@@ -1961,9 +2377,13 @@ fn builtin_str(built_in: crate::BuiltIn) -> Result<&'static str, Error> {
         Bi::SubgroupId => "subgroup_id",
         Bi::SubgroupSize => "subgroup_size",
         Bi::SubgroupInvocationId => "subgroup_invocation_id",
+        Bi::MeshTaskSize => "mesh_task_size",
+        Bi::PointIndex => "point_index",
+        Bi::LineIndices => "line_indices",
+        Bi::TriangleIndices => "triangle_indices",
+        Bi::CullPrimitive => "cull_primitive",
         Bi::BaseInstance
         | Bi::BaseVertex
-        | Bi::ClipDistance
         | Bi::CullDistance
         | Bi::PointSize
         | Bi::PointCoord
@@ -1996,6 +2416,10 @@ const fn scalar_kind_str(scalar: crate::Scalar) -> &'static str {
             kind: Sk::Float,
             width: 4,
         } => "f32",
+        Scalar {
+            kind: Sk::Float,
+            width: 2,
+        } => "f16",
         Scalar {
             kind: Sk::Sint,
             width: 4,
@@ -2109,6 +2533,7 @@ const fn address_space_str(
             }
             As::PushConstant => "push_constant",
             As::WorkGroup => "workgroup",
+            As::TaskPayload => "task_payload",
             As::Handle => return (None, None),
             As::Function => "function",
         }),
@@ -2129,20 +2554,18 @@ fn map_binding_to_attribute(binding: &crate::Binding) -> Vec<Attribute> {
             location,
             interpolation,
             sampling,
-            second_blend_source: false,
-        } => vec![
-            Attribute::Location(location),
-            Attribute::Interpolate(interpolation, sampling),
-        ],
-        crate::Binding::Location {
-            location,
-            interpolation,
-            sampling,
-            second_blend_source: true,
-        } => vec![
-            Attribute::Location(location),
-            Attribute::SecondBlendSource,
-            Attribute::Interpolate(interpolation, sampling),
-        ],
+            second_blend_source,
+            per_primitive,
+        } => {
+            let mut attributes = vec![Attribute::Location(location)];
+            if second_blend_source {
+                attributes.push(Attribute::BlendSrc(1));
+            }
+            if per_primitive {
+                attributes.push(Attribute::PerPrimitive);
+            }
+            attributes.push(Attribute::Interpolate(interpolation, sampling));
+            attributes
+        }
     }
 }
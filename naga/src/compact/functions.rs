@@ -5,6 +5,9 @@ pub struct FunctionTracer<'a> {
     pub function: &'a crate::Function,
     pub constants: &'a crate::Arena<crate::Constant>,
 
+    /// Whether named expressions are used by definition.
+    pub keep_named_expressions: bool,
+
     pub types_used: &'a mut HandleSet<crate::Type>,
     pub constants_used: &'a mut HandleSet<crate::Constant>,
     pub global_expressions_used: &'a mut HandleSet<crate::Expression>,
@@ -32,8 +35,10 @@ impl FunctionTracer<'_> {
 
         // Treat named expressions as alive, for the sake of our test suite,
         // which uses `let blah = expr;` to exercise lots of things.
-        for (&value, _name) in &self.function.named_expressions {
-            self.expressions_used.insert(value);
+        if self.keep_named_expressions {
+            for (&value, _name) in &self.function.named_expressions {
+                self.expressions_used.insert(value);
+            }
         }
 
         self.trace_block(&self.function.body);
@@ -93,9 +98,10 @@ impl FunctionMap {
         });
 
         // Adjust named expressions.
-        for (mut handle, name) in function.named_expressions.drain(..) {
-            self.expressions.adjust(&mut handle);
-            reuse.insert(handle, name);
+        for (handle, name) in function.named_expressions.drain(..) {
+            if let Some(handle) = self.expressions.try_adjust(handle) {
+                reuse.insert(handle, name);
+            }
         }
         std::mem::swap(&mut function.named_expressions, reuse);
         assert!(reuse.is_empty());
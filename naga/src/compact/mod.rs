@@ -30,7 +30,21 @@ use handle_set_map::HandleMap;
 ///
 /// If `module` has not passed validation, this may panic.
 pub fn compact(module: &mut crate::Module) {
-    let mut module_tracer = ModuleTracer::new(module);
+    compact_impl(module, true);
+}
+
+/// Like [`compact`], but also remove named expressions that nothing uses.
+///
+/// [`compact`] treats every named expression as used, to keep the `let`
+/// bindings in our test suite alive. Optimization passes have no such
+/// concern.
+#[cfg(feature = "opt")]
+pub(crate) fn compact_unused_named_expressions(module: &mut crate::Module) {
+    compact_impl(module, false);
+}
+
+fn compact_impl(module: &mut crate::Module, keep_named_expressions: bool) {
+    let mut module_tracer = ModuleTracer::new(module, keep_named_expressions);
 
     // We treat all globals as used by definition.
     log::trace!("tracing global variables");
@@ -88,6 +102,14 @@ pub fn compact(module: &mut crate::Module) {
         .iter()
         .map(|e| {
             log::trace!("tracing entry point {:?}", e.function.name);
+            if let Some(ref mesh_info) = e.mesh_info {
+                module_tracer
+                    .types_used
+                    .insert(mesh_info.vertex_output_type);
+                module_tracer
+                    .types_used
+                    .insert(mesh_info.primitive_output_type);
+            }
             let mut used = module_tracer.as_function(&e.function);
             used.trace();
             FunctionMap::from(used)
@@ -199,6 +221,12 @@ pub fn compact(module: &mut crate::Module) {
     // Compact each entry point.
     for (entry, map) in module.entry_points.iter_mut().zip(entry_point_maps.iter()) {
         log::trace!("compacting entry point {:?}", entry.function.name);
+        if let Some(ref mut mesh_info) = entry.mesh_info {
+            module_map.types.adjust(&mut mesh_info.vertex_output_type);
+            module_map
+                .types
+                .adjust(&mut mesh_info.primitive_output_type);
+        }
         map.compact(
             &mut entry.function,
             &module_map,
@@ -209,15 +237,17 @@ pub fn compact(module: &mut crate::Module) {
 
 struct ModuleTracer<'module> {
     module: &'module crate::Module,
+    keep_named_expressions: bool,
     types_used: HandleSet<crate::Type>,
     constants_used: HandleSet<crate::Constant>,
     global_expressions_used: HandleSet<crate::Expression>,
 }
 
 impl<'module> ModuleTracer<'module> {
-    fn new(module: &'module crate::Module) -> Self {
+    fn new(module: &'module crate::Module, keep_named_expressions: bool) -> Self {
         Self {
             module,
+            keep_named_expressions,
             types_used: HandleSet::for_arena(&module.types),
             constants_used: HandleSet::for_arena(&module.constants),
             global_expressions_used: HandleSet::for_arena(&module.global_expressions),
@@ -267,6 +297,7 @@ impl<'module> ModuleTracer<'module> {
         FunctionTracer {
             function,
             constants: &self.module.constants,
+            keep_named_expressions: self.keep_named_expressions,
             types_used: &mut self.types_used,
             constants_used: &mut self.constants_used,
             global_expressions_used: &mut self.global_expressions_used,
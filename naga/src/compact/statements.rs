@@ -114,18 +114,33 @@ impl FunctionTracer<'_> {
                         self.expressions_used.insert(argument);
                         self.expressions_used.insert(result);
                     }
+                    St::MeshFunction(ref fun) => match *fun {
+                        crate::MeshFunction::SetMeshOutputs {
+                            vertex_count,
+                            primitive_count,
+                        } => {
+                            self.expressions_used.insert(vertex_count);
+                            self.expressions_used.insert(primitive_count);
+                        }
+                        crate::MeshFunction::SetVertex { index, value }
+                        | crate::MeshFunction::SetPrimitive { index, value } => {
+                            self.expressions_used.insert(index);
+                            self.expressions_used.insert(value);
+                        }
+                    },
                     St::SubgroupGather {
                         mode,
                         argument,
                         result,
                     } => {
                         match mode {
-                            crate::GatherMode::BroadcastFirst => {}
+                            crate::GatherMode::BroadcastFirst | crate::GatherMode::QuadSwap(_) => {}
                             crate::GatherMode::Broadcast(index)
                             | crate::GatherMode::Shuffle(index)
                             | crate::GatherMode::ShuffleDown(index)
                             | crate::GatherMode::ShuffleUp(index)
-                            | crate::GatherMode::ShuffleXor(index) => {
+                            | crate::GatherMode::ShuffleXor(index)
+                            | crate::GatherMode::QuadBroadcast(index) => {
                                 self.expressions_used.insert(index);
                             }
                         }
@@ -176,6 +191,10 @@ impl FunctionTracer<'_> {
             Qf::Proceed { result } => {
                 self.expressions_used.insert(result);
             }
+            Qf::GenerateIntersection { hit_t } => {
+                self.expressions_used.insert(hit_t);
+            }
+            Qf::ConfirmIntersection => {}
             Qf::Terminate => {}
         }
     }
@@ -305,18 +324,39 @@ impl FunctionMap {
                         adjust(argument);
                         adjust(result);
                     }
+                    St::MeshFunction(ref mut fun) => match *fun {
+                        crate::MeshFunction::SetMeshOutputs {
+                            ref mut vertex_count,
+                            ref mut primitive_count,
+                        } => {
+                            adjust(vertex_count);
+                            adjust(primitive_count);
+                        }
+                        crate::MeshFunction::SetVertex {
+                            ref mut index,
+                            ref mut value,
+                        }
+                        | crate::MeshFunction::SetPrimitive {
+                            ref mut index,
+                            ref mut value,
+                        } => {
+                            adjust(index);
+                            adjust(value);
+                        }
+                    },
                     St::SubgroupGather {
                         ref mut mode,
                         ref mut argument,
                         ref mut result,
                     } => {
                         match *mode {
-                            crate::GatherMode::BroadcastFirst => {}
+                            crate::GatherMode::BroadcastFirst | crate::GatherMode::QuadSwap(_) => {}
                             crate::GatherMode::Broadcast(ref mut index)
                             | crate::GatherMode::Shuffle(ref mut index)
                             | crate::GatherMode::ShuffleDown(ref mut index)
                             | crate::GatherMode::ShuffleUp(ref mut index)
-                            | crate::GatherMode::ShuffleXor(ref mut index) => adjust(index),
+                            | crate::GatherMode::ShuffleXor(ref mut index)
+                            | crate::GatherMode::QuadBroadcast(ref mut index) => adjust(index),
                         }
                         adjust(argument);
                         adjust(result);
@@ -365,6 +405,10 @@ impl FunctionMap {
             Qf::Proceed { ref mut result } => {
                 self.expressions.adjust(result);
             }
+            Qf::GenerateIntersection { ref mut hit_t } => {
+                self.expressions.adjust(hit_t);
+            }
+            Qf::ConfirmIntersection => {}
             Qf::Terminate => {}
         }
     }
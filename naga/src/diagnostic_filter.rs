@@ -27,11 +27,11 @@ pub enum Severity {
 }
 
 impl Severity {
-    /// Checks whether this severity is [`Self::Error`].
+    /// Report `err` at this severity.
     ///
-    /// Naga does not yet support diagnostic items at lesser severities than
-    /// [`Severity::Error`]. When this is implemented, this method should be deleted, and the
-    /// severity should be used directly for reporting diagnostics.
+    /// Returns `err` if this severity is [`Self::Error`]. For [`Self::Info`] and
+    /// [`Self::Warning`], `err` is passed to `log_handler` along with the
+    /// matching log level, and it is up to the handler to record it.
     pub(crate) fn report_diag<E>(
         self,
         err: E,
@@ -39,8 +39,6 @@ impl Severity {
     ) -> Result<(), E> {
         let log_level = match self {
             Severity::Off => return Ok(()),
-
-            // NOTE: These severities are not yet reported.
             Severity::Info => log::Level::Info,
             Severity::Warning => log::Level::Warn,
 
@@ -73,6 +71,26 @@ pub enum FilterableTriggeringRule {
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 pub enum StandardFilterableTriggeringRule {
     DerivativeUniformity,
+    /// A local variable or function parameter that is never read.
+    ///
+    /// Names starting with an underscore are exempt.
+    UnusedVariable,
+    /// A statement following a `return`, `discard`, `break` or `continue` in
+    /// the same block.
+    ///
+    /// Naga IR doesn't allow such statements, so this is checked by the WGSL
+    /// frontend, which leaves them out of the module.
+    UnreachableCode,
+    /// A local variable, `let` binding or function parameter with the same
+    /// name as a module-scope declaration or, for locals, as one of the
+    /// function's parameters.
+    ShadowedDeclaration,
+    /// An abstract numeric value that can't be represented exactly as `f32`
+    /// being implicitly converted to it.
+    ///
+    /// Abstract values only exist in the WGSL frontend, so this is checked
+    /// there.
+    PrecisionLoss,
 }
 
 impl StandardFilterableTriggeringRule {
@@ -83,6 +101,12 @@ impl StandardFilterableTriggeringRule {
     pub(crate) const fn default_severity(self) -> Severity {
         match self {
             Self::DerivativeUniformity => Severity::Error,
+            // These are not part of the WGSL specification, so don't report
+            // anything unless they are explicitly enabled.
+            Self::UnusedVariable
+            | Self::UnreachableCode
+            | Self::ShadowedDeclaration
+            | Self::PrecisionLoss => Severity::Off,
         }
     }
 }
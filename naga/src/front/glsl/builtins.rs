@@ -9,7 +9,8 @@ use super::{
 use crate::{
     BinaryOperator, DerivativeAxis as Axis, DerivativeControl as Ctrl, Expression, Handle,
     ImageClass, ImageDimension as Dim, ImageQuery, MathFunction, Module, RelationalFunction,
-    SampleLevel, Scalar, ScalarKind as Sk, Span, Type, TypeInner, UnaryOperator, VectorSize,
+    SampleLevel, Scalar, ScalarKind as Sk, Span, SubgroupOperation as SgOp, Type, TypeInner,
+    UnaryOperator, VectorSize,
 };
 
 impl crate::ScalarKind {
@@ -1036,6 +1037,66 @@ fn inject_standard_builtins(
         "barrier" => declaration
             .overloads
             .push(module.add_builtin(Vec::new(), MacroCall::Barrier)),
+        "subgroupQuadBroadcast"
+        | "subgroupQuadSwapHorizontal"
+        | "subgroupQuadSwapVertical"
+        | "subgroupQuadSwapDiagonal"
+        | "subgroupClusteredAdd"
+        | "subgroupClusteredMul"
+        | "subgroupClusteredMin"
+        | "subgroupClusteredMax"
+        | "subgroupClusteredAnd"
+        | "subgroupClusteredOr"
+        | "subgroupClusteredXor" => {
+            let mc = match name {
+                "subgroupQuadBroadcast" => MacroCall::SubgroupQuadBroadcast,
+                "subgroupQuadSwapHorizontal" => MacroCall::SubgroupQuadSwap(crate::Direction::X),
+                "subgroupQuadSwapVertical" => MacroCall::SubgroupQuadSwap(crate::Direction::Y),
+                "subgroupQuadSwapDiagonal" => {
+                    MacroCall::SubgroupQuadSwap(crate::Direction::Diagonal)
+                }
+                "subgroupClusteredAdd" => MacroCall::SubgroupClusteredReduce(SgOp::Add),
+                "subgroupClusteredMul" => MacroCall::SubgroupClusteredReduce(SgOp::Mul),
+                "subgroupClusteredMin" => MacroCall::SubgroupClusteredReduce(SgOp::Min),
+                "subgroupClusteredMax" => MacroCall::SubgroupClusteredReduce(SgOp::Max),
+                "subgroupClusteredAnd" => MacroCall::SubgroupClusteredReduce(SgOp::And),
+                "subgroupClusteredOr" => MacroCall::SubgroupClusteredReduce(SgOp::Or),
+                "subgroupClusteredXor" => MacroCall::SubgroupClusteredReduce(SgOp::Xor),
+                _ => unreachable!(),
+            };
+            let bitwise = matches!(
+                mc,
+                MacroCall::SubgroupClusteredReduce(SgOp::And | SgOp::Or | SgOp::Xor)
+            );
+
+            // bits layout
+            // bit 0 through 1 - float/int/uint
+            // bit 2 through 3 - dims
+            for bits in 0..0b10000 {
+                let scalar = match bits & 0b11 {
+                    0b00 if !bitwise => Scalar::F32,
+                    0b01 => Scalar::I32,
+                    0b10 => Scalar::U32,
+                    _ => continue,
+                };
+                let size = match bits >> 2 {
+                    0b00 => None,
+                    0b01 => Some(VectorSize::Bi),
+                    0b10 => Some(VectorSize::Tri),
+                    _ => Some(VectorSize::Quad),
+                };
+
+                let mut args = vec![match size {
+                    Some(size) => TypeInner::Vector { size, scalar },
+                    None => TypeInner::Scalar(scalar),
+                }];
+                if !matches!(mc, MacroCall::SubgroupQuadSwap(_)) {
+                    args.push(TypeInner::Scalar(Scalar::U32));
+                }
+
+                declaration.overloads.push(module.add_builtin(args, mc))
+            }
+        }
         // Add common builtins with floats
         _ => inject_common_builtin(declaration, module, name, 4),
     }
@@ -1572,6 +1633,9 @@ pub enum MacroCall {
     BitCast(Sk),
     Derivate(Axis, Ctrl),
     Barrier,
+    SubgroupQuadBroadcast,
+    SubgroupQuadSwap(crate::Direction),
+    SubgroupClusteredReduce(crate::SubgroupOperation),
     /// SmoothStep needs a separate variant because it might need it's inputs
     /// to be splatted depending on the overload
     SmoothStep {
@@ -2043,6 +2107,55 @@ impl MacroCall {
                     .push(crate::Statement::Barrier(crate::Barrier::all()), meta);
                 return Ok(None);
             }
+            MacroCall::SubgroupQuadBroadcast | MacroCall::SubgroupQuadSwap(_) => {
+                let mode = match *self {
+                    MacroCall::SubgroupQuadSwap(direction) => {
+                        crate::GatherMode::QuadSwap(direction)
+                    }
+                    _ => crate::GatherMode::QuadBroadcast(args[1]),
+                };
+                let ty = ctx.resolve_type_handle(args[0], meta)?;
+                ctx.emit_end();
+                let result =
+                    ctx.add_expression(Expression::SubgroupOperationResult { ty }, meta)?;
+                ctx.body.push(
+                    crate::Statement::SubgroupGather {
+                        mode,
+                        argument: args[0],
+                        result,
+                    },
+                    meta,
+                );
+                ctx.emit_start();
+                result
+            }
+            MacroCall::SubgroupClusteredReduce(op) => {
+                let size = ctx
+                    .module
+                    .to_ctx()
+                    .eval_expr_to_u32_from(args[1], &ctx.expressions)
+                    .map_err(|_| Error {
+                        kind: ErrorKind::SemanticError(
+                            "Cluster size must be a constant integral expression".into(),
+                        ),
+                        meta,
+                    })?;
+                let ty = ctx.resolve_type_handle(args[0], meta)?;
+                ctx.emit_end();
+                let result =
+                    ctx.add_expression(Expression::SubgroupOperationResult { ty }, meta)?;
+                ctx.body.push(
+                    crate::Statement::SubgroupCollectiveOperation {
+                        op,
+                        collective_op: crate::CollectiveOperation::ClusteredReduce(size),
+                        argument: args[0],
+                        result,
+                    },
+                    meta,
+                );
+                ctx.emit_start();
+                result
+            }
             MacroCall::SmoothStep { splatted } => {
                 ctx.implicit_splat(&mut args[0], meta, splatted)?;
                 ctx.implicit_splat(&mut args[1], meta, splatted)?;
@@ -1,11 +1,12 @@
 use super::token::TokenValue;
+use super::SourceMap;
 use crate::SourceLocation;
 use crate::{proc::ConstantEvaluatorError, Span};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
-use codespan_reporting::files::SimpleFile;
+use codespan_reporting::files::{SimpleFile, SimpleFiles};
 use codespan_reporting::term;
 use pp_rs::token::PreprocessorError;
-use std::borrow::Cow;
+use std::{borrow::Cow, sync::Arc};
 use termcolor::{NoColor, WriteColor};
 use thiserror::Error;
 
@@ -114,6 +115,18 @@ pub enum ErrorKind {
     /// An error was returned by the preprocessor.
     #[error("{0:?}")]
     PreprocessorError(PreprocessorError),
+    /// An `#include` directive wasn't followed by a file name in quotes or
+    /// angle brackets.
+    #[error("Expected a file name in quotes or angle brackets after `#include`")]
+    InvalidIncludeDirective,
+    /// The file named by an `#include` directive couldn't be included.
+    #[error("Failed to include {name:?}: {reason}")]
+    IncludeFailed {
+        /// The name given in the `#include` directive.
+        name: String,
+        /// Why the file couldn't be included.
+        reason: String,
+    },
     /// The parser entered an illegal state and exited
     ///
     /// This obviously is a bug and as such should be reported in the github issue tracker
@@ -150,6 +163,9 @@ impl Error {
 #[cfg_attr(test, derive(PartialEq))]
 pub struct ParseErrors {
     pub errors: Vec<Error>,
+    /// Maps the spans of [`Self::errors`] back to the files they came from,
+    /// if the shader has any `#include` directives.
+    pub source_map: Option<Arc<SourceMap>>,
 }
 
 impl ParseErrors {
@@ -159,6 +175,9 @@ impl ParseErrors {
 
     pub fn emit_to_writer_with_path(&self, writer: &mut impl WriteColor, source: &str, path: &str) {
         let path = path.to_string();
+        if let Some(ref source_map) = self.source_map {
+            return self.emit_with_source_map(writer, source, path, source_map);
+        }
         let files = SimpleFile::new(path, source);
         let config = term::Config::default();
 
@@ -173,6 +192,35 @@ impl ParseErrors {
         }
     }
 
+    /// Emit errors with spans in files included by `source`.
+    fn emit_with_source_map(
+        &self,
+        writer: &mut impl WriteColor,
+        source: &str,
+        path: String,
+        source_map: &SourceMap,
+    ) {
+        let mut files = SimpleFiles::new();
+        let root = files.add(path, source);
+        let included: Vec<_> = source_map
+            .files()
+            .iter()
+            .map(|file| files.add(file.name.clone(), file.source.as_str()))
+            .collect();
+        let config = term::Config::default();
+
+        for err in &self.errors {
+            let mut diagnostic = Diagnostic::error().with_message(err.kind.to_string());
+
+            if let Some((file, range)) = source_map.locate(err.meta) {
+                let file_id = file.map_or(root, |index| included[index]);
+                diagnostic = diagnostic.with_labels(vec![Label::primary(file_id, range)]);
+            }
+
+            term::emit(writer, &config, &files, &diagnostic).expect("cannot write error");
+        }
+    }
+
     pub fn emit_to_string(&self, source: &str) -> String {
         let mut writer = NoColor::new(Vec::new());
         self.emit_to_writer(&mut writer, source);
@@ -194,6 +242,9 @@ impl std::error::Error for ParseErrors {
 
 impl From<Vec<Error>> for ParseErrors {
     fn from(errors: Vec<Error>) -> Self {
-        Self { errors }
+        Self {
+            errors,
+            source_map: None,
+        }
     }
 }
@@ -1371,6 +1371,8 @@ impl Frontend {
                 result: ty.map(|ty| FunctionResult { ty, binding: None }),
                 ..Default::default()
             },
+            mesh_info: None,
+            task_payload: None,
         });
 
         Ok(())
@@ -1440,6 +1442,7 @@ impl Context<'_> {
                         interpolation,
                         sampling: None,
                         second_blend_source: false,
+                        per_primitive: false,
                     };
                     location += 1;
 
@@ -1476,6 +1479,7 @@ impl Context<'_> {
                                 interpolation,
                                 sampling: None,
                                 second_blend_source: false,
+                                per_primitive: false,
                             };
                             location += 1;
                             binding
@@ -0,0 +1,322 @@
+/*!
+Expansion of `#include` directives.
+
+The preprocessor we use doesn't know about `#include`, but reports it as an
+unknown directive when, and only when, it appears outside of a skipped
+conditional block. We use that to find the first `#include` that is in effect,
+replace it with the contents of the file it names, and start over, until no
+more `#include` directives are reported. Expanding one directive at a time
+means that macros defined by included files, such as include guards, are taken
+into account when deciding whether later directives are in effect.
+
+The [`SourceMap`] built along the way lets us map spans in the expanded source
+back to the file they came from.
+*/
+
+use super::{Error, ErrorKind, Options};
+use crate::Span;
+use pp_rs::{pp::Preprocessor, token::PreprocessorError};
+use std::{fmt, ops::Range};
+
+/// How deeply `#include` directives may be nested.
+///
+/// This also stops files that include themselves without include guards.
+const MAX_INCLUDE_DEPTH: u32 = 64;
+
+/// The delimiters around the file name in an `#include` directive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IncludeKind {
+    /// `#include "name"`, usually searched for relative to the including file
+    /// first.
+    Relative,
+    /// `#include <name>`, usually only searched for in the include paths.
+    Standard,
+}
+
+/// A file returned by an [`IncludeResolver`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct IncludedFile {
+    /// The name of the file.
+    ///
+    /// This is used in diagnostics, and passed to the resolver as the
+    /// includer of any `#include` directives in this file.
+    pub name: String,
+    /// The contents of the file.
+    pub source: String,
+}
+
+/// Resolves the files named by `#include` directives.
+///
+/// This follows the `GL_GOOGLE_include_directive` extension: the frontend
+/// only passes along the name between the quotes or angle brackets, and it is
+/// up to the resolver to decide where to look for it.
+pub trait IncludeResolver: fmt::Debug + Send + Sync {
+    /// Return the file that `name` refers to.
+    ///
+    /// `includer` is the [`IncludedFile::name`] of the file containing the
+    /// `#include` directive, or `None` if it is the shader source passed to
+    /// [`Frontend::parse`].
+    ///
+    /// The error message is reported as part of the resulting parse error.
+    ///
+    /// [`Frontend::parse`]: super::Frontend::parse
+    fn resolve(
+        &self,
+        name: &str,
+        kind: IncludeKind,
+        includer: Option<&str>,
+    ) -> Result<IncludedFile, String>;
+}
+
+/// A contiguous part of the expanded source that comes from a single file.
+#[derive(Clone, Debug, PartialEq)]
+struct Segment {
+    /// Where this segment starts in the expanded source.
+    ///
+    /// The segment ends where the next one starts.
+    start: usize,
+    /// The index of the file in [`SourceMap::files`], or `None` for the
+    /// shader source itself.
+    file: Option<usize>,
+    /// Where this segment starts in its file.
+    offset: usize,
+    /// How many `#include` directives this segment is nested in.
+    depth: u32,
+}
+
+/// The shader source with its `#include` directives expanded.
+///
+/// When a shader includes other files, spans in the [`Module`] and in
+/// [`ParseErrors`] refer to [`SourceMap::expanded_source`], and
+/// [`SourceMap::locate`] maps them back to the file they came from.
+///
+/// [`Module`]: crate::Module
+/// [`ParseErrors`]: super::ParseErrors
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourceMap {
+    expanded: String,
+    files: Vec<IncludedFile>,
+    segments: Vec<Segment>,
+}
+
+impl SourceMap {
+    fn new(source: &str) -> Self {
+        SourceMap {
+            expanded: source.to_string(),
+            files: Vec::new(),
+            segments: vec![Segment {
+                start: 0,
+                file: None,
+                offset: 0,
+                depth: 0,
+            }],
+        }
+    }
+
+    /// Return the shader source with all `#include` directives expanded.
+    pub fn expanded_source(&self) -> &str {
+        &self.expanded
+    }
+
+    /// Return the included files, in the order they were included.
+    ///
+    /// A file included more than once appears once for each `#include`.
+    pub fn files(&self) -> &[IncludedFile] {
+        &self.files
+    }
+
+    /// Map a span in the expanded source back to the file it came from.
+    ///
+    /// Returns the index of the file in [`SourceMap::files`], or `None` for
+    /// the shader source itself, and the byte range in that file. Spans that
+    /// cross into another file are cut short at the end of the first one.
+    pub fn locate(&self, span: Span) -> Option<(Option<usize>, Range<usize>)> {
+        let range = span.to_range()?;
+        let index = self.segment_index(range.start);
+        let segment = &self.segments[index];
+        let end = self
+            .segments
+            .get(index + 1)
+            .map_or(range.end, |next| range.end.min(next.start));
+        let start = segment.offset + range.start - segment.start;
+        Some((segment.file, start..segment.offset + end - segment.start))
+    }
+
+    fn segment_index(&self, position: usize) -> usize {
+        self.segments
+            .partition_point(|segment| segment.start <= position)
+            .saturating_sub(1)
+    }
+
+    /// Replace the `#include` directive whose name is at `directive` in the
+    /// expanded source with the file it refers to.
+    fn expand(&mut self, directive: Range<usize>, options: &Options) -> Result<(), Error> {
+        let line_start = self.expanded[..directive.start]
+            .rfind('\n')
+            .map_or(0, |newline| newline + 1);
+        let line_end = self.expanded[directive.end..]
+            .find('\n')
+            .map_or(self.expanded.len(), |newline| directive.end + newline);
+        let meta = Span::from(directive.start..line_end);
+
+        let (name, kind) =
+            parse_include_target(&self.expanded[directive.end..line_end]).ok_or(Error {
+                kind: ErrorKind::InvalidIncludeDirective,
+                meta,
+            })?;
+
+        let index = self.segment_index(directive.start);
+        let segment = self.segments[index].clone();
+        let include_error = |reason: &str| Error {
+            kind: ErrorKind::IncludeFailed {
+                name: name.to_string(),
+                reason: reason.to_string(),
+            },
+            meta,
+        };
+        if segment.depth >= MAX_INCLUDE_DEPTH {
+            return Err(include_error("includes are nested too deeply"));
+        }
+        let resolver = options
+            .include_resolver
+            .as_ref()
+            .ok_or_else(|| include_error("no include resolver was provided"))?;
+        let includer = segment.file.map(|file| self.files[file].name.as_str());
+        let file = resolver
+            .resolve(name, kind, includer)
+            .map_err(|reason| include_error(&reason))?;
+
+        // The directive can't span more than one segment, as segments only
+        // end where the line of an earlier `#include` was.
+        let line_start = line_start.max(segment.start);
+        let inserted = file.source.len();
+        let removed = line_end - line_start;
+        self.expanded
+            .replace_range(line_start..line_end, &file.source);
+
+        for later in self.segments[index + 1..].iter_mut() {
+            later.start = later.start - removed + inserted;
+        }
+        let included = Segment {
+            start: line_start,
+            file: Some(self.files.len()),
+            offset: 0,
+            depth: segment.depth + 1,
+        };
+        let rest = Segment {
+            start: line_start + inserted,
+            offset: segment.offset + line_end - segment.start,
+            ..segment
+        };
+        self.files.push(file);
+        self.segments.splice(index + 1..index + 1, [included, rest]);
+        self.segments
+            .retain(|segment| segment.start < self.expanded.len() || segment.start == 0);
+        self.segments.dedup_by(|next, previous| {
+            // Drop segments made empty by an empty file or a directive at the
+            // start of a segment, keeping the last one at each position.
+            if next.start == previous.start {
+                *previous = next.clone();
+                true
+            } else {
+                false
+            }
+        });
+        Ok(())
+    }
+}
+
+/// Parse the rest of an `#include` directive after the `include` itself.
+fn parse_include_target(rest: &str) -> Option<(&str, IncludeKind)> {
+    let rest = rest.trim_start();
+    let (close, kind) = match rest.chars().next()? {
+        '"' => ('"', IncludeKind::Relative),
+        '<' => ('>', IncludeKind::Standard),
+        _ => return None,
+    };
+    let rest = &rest[1..];
+    let end = rest.find(close)?;
+    let trailing = rest[end + 1..].trim();
+    if !(trailing.is_empty() || trailing.starts_with("//") || trailing.starts_with("/*")) {
+        return None;
+    }
+    Some((&rest[..end], kind))
+}
+
+/// Find the first `#include` directive in `source` that is in effect, and
+/// return the range of its `include` keyword.
+///
+/// Fails if one of the [`Options::defines`] can't be preprocessed.
+fn find_include(source: &str, options: &Options) -> Result<Option<Range<usize>>, Error> {
+    let mut pp = Preprocessor::new(source);
+    for (define, value) in &options.defines {
+        // The location refers to the define's value, not to the source.
+        pp.add_define(define, value).map_err(|(error, _)| Error {
+            kind: ErrorKind::PreprocessorError(error),
+            meta: Span::default(),
+        })?;
+    }
+    Ok(pp.find_map(|token| match token {
+        Err((PreprocessorError::UnknownDirective, location)) => {
+            let range = location.start as usize..location.end as usize;
+            (source.get(range.clone()) == Some("include")).then_some(range)
+        }
+        _ => None,
+    }))
+}
+
+/// Return the ranges of the lines in `source` that hold an `#include`
+/// directive, without their line endings.
+fn include_lines(source: &str) -> Vec<Range<usize>> {
+    let mut start = 0;
+    source
+        .split_inclusive('\n')
+        .filter_map(|line| {
+            let range = start..start + line.trim_end().len();
+            start += line.len();
+            let directive = line.trim_start().strip_prefix('#')?;
+            directive
+                .trim_start()
+                .starts_with("include")
+                .then_some(range)
+        })
+        .collect()
+}
+
+/// Expand all of the `#include` directives in `source`.
+///
+/// Returns `None` if there are no `#include` directives. On failure, the error
+/// is returned along with the source map its span refers to.
+pub(super) fn expand_includes(
+    source: &str,
+    options: &Options,
+) -> Result<Option<SourceMap>, (Error, Option<SourceMap>)> {
+    let mut source_map: Option<SourceMap> = None;
+    loop {
+        let expanded = source_map
+            .as_ref()
+            .map_or(source, |map| map.expanded_source());
+        let directive = match find_include(expanded, options) {
+            Ok(directive) => directive,
+            Err(error) => return Err((error, None)),
+        };
+        let Some(directive) = directive else {
+            // The remaining directives are in skipped blocks, but the
+            // preprocessor still lexes those and doesn't accept the quotes
+            // around the file name, so blank them out.
+            let inactive = include_lines(expanded);
+            if !inactive.is_empty() {
+                let map = source_map.get_or_insert_with(|| SourceMap::new(source));
+                for line in inactive {
+                    let blank = " ".repeat(line.len());
+                    map.expanded.replace_range(line, &blank);
+                }
+            }
+            return Ok(source_map);
+        };
+        let map = source_map.get_or_insert_with(|| SourceMap::new(source));
+        if let Err(error) = map.expand(directive, options) {
+            return Err((error, source_map));
+        }
+    }
+}
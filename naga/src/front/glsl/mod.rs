@@ -14,17 +14,20 @@ To begin, take a look at the documentation for the [`Frontend`].
 
 pub use ast::{Precision, Profile};
 pub use error::{Error, ErrorKind, ExpectedToken, ParseErrors};
+pub use include::{IncludeKind, IncludeResolver, IncludedFile, SourceMap};
 pub use token::TokenValue;
 
 use crate::{proc::Layouter, FastHashMap, FastHashSet, Handle, Module, ShaderStage, Span, Type};
 use ast::{EntryArg, FunctionDeclaration, GlobalLookup};
 use parser::ParsingContext;
+use std::sync::Arc;
 
 mod ast;
 mod builtins;
 mod context;
 mod error;
 mod functions;
+mod include;
 mod lex;
 mod offset;
 mod parser;
@@ -56,6 +59,10 @@ pub struct Options {
     /// ```
     /// for each key value pair in the map.
     pub defines: FastHashMap<String, String>,
+    /// Resolves the files named by `#include` directives.
+    ///
+    /// If this is `None`, any `#include` directive in effect is an error.
+    pub include_resolver: Option<Arc<dyn IncludeResolver>>,
 }
 
 impl From<ShaderStage> for Options {
@@ -63,6 +70,7 @@ impl From<ShaderStage> for Options {
         Options {
             stage,
             defines: FastHashMap::default(),
+            include_resolver: None,
         }
     }
 }
@@ -175,6 +183,8 @@ pub struct Frontend {
     layouter: Layouter,
 
     errors: Vec<Error>,
+
+    source_map: Option<Arc<SourceMap>>,
 }
 
 impl Frontend {
@@ -186,6 +196,7 @@ impl Frontend {
         self.global_variables.clear();
         self.entry_args.clear();
         self.layouter.clear();
+        self.source_map = None;
     }
 
     /// Parses a shader either outputting a shader [`Module`] or a list of
@@ -199,22 +210,46 @@ impl Frontend {
     ) -> std::result::Result<Module, ParseErrors> {
         self.reset(options.stage);
 
-        let lexer = lex::Lexer::new(source, &options.defines);
+        let source_map = match include::expand_includes(source, options) {
+            Ok(source_map) => source_map.map(Arc::new),
+            Err((error, source_map)) => {
+                return Err(ParseErrors {
+                    errors: vec![error],
+                    source_map: source_map.map(Arc::new),
+                });
+            }
+        };
+        let expanded = source_map
+            .as_ref()
+            .map_or(source, |source_map| source_map.expanded_source());
+
+        let lexer = lex::Lexer::new(expanded, &options.defines);
         let mut ctx = ParsingContext::new(lexer);
 
-        match ctx.parse(self) {
+        let result = match ctx.parse(self) {
             Ok(module) => {
                 if self.errors.is_empty() {
                     Ok(module)
                 } else {
-                    Err(std::mem::take(&mut self.errors).into())
+                    Err(std::mem::take(&mut self.errors))
                 }
             }
             Err(e) => {
                 self.errors.push(e);
-                Err(std::mem::take(&mut self.errors).into())
+                Err(std::mem::take(&mut self.errors))
             }
-        }
+        };
+        self.source_map.clone_from(&source_map);
+        result.map_err(|errors| ParseErrors { errors, source_map })
+    }
+
+    /// Returns the [`SourceMap`] of the last parsed shader, or `None` if it
+    /// had no `#include` directives.
+    ///
+    /// If there is a source map, the spans of the parsed [`Module`] refer to
+    /// its [`expanded_source`](SourceMap::expanded_source).
+    pub fn source_map(&self) -> Option<&SourceMap> {
+        self.source_map.as_deref()
     }
 
     /// Returns additional information about the parsed shader which might not
@@ -3,7 +3,7 @@ use super::{
     error::ExpectedToken,
     error::{Error, ErrorKind, ParseErrors},
     token::TokenValue,
-    Frontend, Options, Span,
+    Frontend, IncludeKind, IncludeResolver, IncludedFile, Options, Span,
 };
 use crate::ShaderStage;
 use pp_rs::token::PreprocessorError;
@@ -26,6 +26,7 @@ fn version() {
                 kind: ErrorKind::InvalidVersion(99000),
                 meta: Span::new(9, 14)
             }],
+            source_map: None,
         },
     );
 
@@ -41,7 +42,8 @@ fn version() {
             errors: vec![Error {
                 kind: ErrorKind::InvalidVersion(449),
                 meta: Span::new(9, 12)
-            }]
+            }],
+            source_map: None,
         },
     );
 
@@ -57,7 +59,8 @@ fn version() {
             errors: vec![Error {
                 kind: ErrorKind::InvalidProfile("smart".into()),
                 meta: Span::new(13, 18),
-            }]
+            }],
+            source_map: None,
         },
     );
 
@@ -82,7 +85,8 @@ fn version() {
                     ),
                     meta: Span::new(28, 35)
                 }
-            ]
+            ],
+            source_map: None,
         },
     );
 
@@ -459,7 +463,8 @@ fn functions() {
             errors: vec![Error {
                 kind: ErrorKind::SemanticError("Function already defined".into()),
                 meta: Span::new(134, 152),
-            }]
+            }],
+            source_map: None,
         },
     );
 
@@ -638,7 +643,8 @@ fn implicit_conversions() {
             errors: vec![Error {
                 kind: ErrorKind::SemanticError("Unknown function \'test\'".into()),
                 meta: Span::new(156, 165),
-            }]
+            }],
+            source_map: None,
         },
     );
 
@@ -662,7 +668,8 @@ fn implicit_conversions() {
             errors: vec![Error {
                 kind: ErrorKind::SemanticError("Ambiguous best function for \'test\'".into()),
                 meta: Span::new(158, 165),
-            }]
+            }],
+            source_map: None,
         }
     );
 }
@@ -854,3 +861,140 @@ fn expressions() {
         )
         .unwrap();
 }
+
+/// Resolves `#include`s from a fixed set of files.
+#[derive(Debug)]
+struct TestIncludes(&'static [(&'static str, &'static str)]);
+
+impl IncludeResolver for TestIncludes {
+    fn resolve(
+        &self,
+        name: &str,
+        _kind: IncludeKind,
+        _includer: Option<&str>,
+    ) -> Result<IncludedFile, String> {
+        self.0
+            .iter()
+            .find(|&&(file_name, _)| file_name == name)
+            .map(|&(file_name, source)| IncludedFile {
+                name: file_name.to_string(),
+                source: source.to_string(),
+            })
+            .ok_or_else(|| "file not found".to_string())
+    }
+}
+
+fn include_options(files: &'static [(&'static str, &'static str)]) -> Options {
+    Options {
+        include_resolver: Some(std::sync::Arc::new(TestIncludes(files))),
+        ..Options::from(ShaderStage::Vertex)
+    }
+}
+
+#[test]
+fn includes() {
+    let mut frontend = Frontend::default();
+    let options = include_options(&[
+        (
+            "common.glsl",
+            "#ifndef COMMON\n#define COMMON\n#include <math.glsl>\n#endif\n",
+        ),
+        ("math.glsl", "float twice(float x) { return x * 2.0; }"),
+        ("unused.glsl", "this is not glsl"),
+    ]);
+
+    frontend
+        .parse(
+            &options,
+            r#"
+            #version 450
+            #extension GL_GOOGLE_include_directive : require
+            #include "common.glsl"
+            #include "common.glsl" // include guards are respected
+            #ifdef UNDEFINED
+            #include "unused.glsl"
+            #endif
+
+            void main() {
+                gl_Position = vec4(twice(1.0));
+            }
+            "#,
+        )
+        .unwrap();
+    let source_map = frontend.source_map().unwrap();
+    assert_eq!(source_map.files().len(), 3);
+    assert!(!source_map.expanded_source().contains("#include"));
+
+    // Without includes, there is no source map.
+    frontend
+        .parse(&options, "#version 450\nvoid main() {}")
+        .unwrap();
+    assert!(frontend.source_map().is_none());
+}
+
+#[test]
+fn include_errors() {
+    let mut frontend = Frontend::default();
+    let source = "#version 450\n#include \"broken.glsl\"\nvoid main() {}\n";
+    let options = include_options(&[("broken.glsl", "void f() {\n    undefined();\n}\n")]);
+
+    let errors = frontend.parse(&options, source).err().unwrap();
+    assert_eq!(
+        errors.emit_to_string(source),
+        "error: Unknown function 'undefined'
+  ┌─ broken.glsl:2:5
+  │
+2 │     undefined();
+  │     ^^^^^^^^^^^
+
+"
+    );
+
+    let errors = frontend.parse(&include_options(&[]), source).err().unwrap();
+    assert_eq!(
+        errors.errors,
+        vec![Error {
+            kind: ErrorKind::IncludeFailed {
+                name: "broken.glsl".to_string(),
+                reason: "file not found".to_string(),
+            },
+            meta: Span::new(14, 35),
+        }]
+    );
+
+    let errors = frontend
+        .parse(&Options::from(ShaderStage::Vertex), source)
+        .err()
+        .unwrap();
+    assert_eq!(
+        errors.errors[0].kind,
+        ErrorKind::IncludeFailed {
+            name: "broken.glsl".to_string(),
+            reason: "no include resolver was provided".to_string(),
+        }
+    );
+
+    let errors = frontend
+        .parse(&options, "#version 450\n#include broken.glsl\n")
+        .err()
+        .unwrap();
+    assert_eq!(errors.errors[0].kind, ErrorKind::InvalidIncludeDirective);
+
+    let mut options = include_options(&[]);
+    options
+        .defines
+        .insert("BROKEN".to_string(), "#".to_string());
+    let errors = frontend
+        .parse(
+            &options,
+            "#version 450
+void main() {}
+",
+        )
+        .err()
+        .unwrap();
+    assert!(matches!(
+        errors.errors[0].kind,
+        ErrorKind::PreprocessorError(_)
+    ));
+}
@@ -455,6 +455,7 @@ impl Frontend {
                         interpolation,
                         sampling,
                         second_blend_source: false,
+                        per_primitive: false,
                     },
                     handle,
                     storage,
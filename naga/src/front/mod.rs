@@ -50,7 +50,7 @@ use std::ops;
 /// [`register_type`]: Typifier::register_type
 /// [`Compose`]: crate::Expression::Compose
 /// [`LocalVariable`]: crate::LocalVariable
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Typifier {
     resolutions: HandleVec<crate::Expression, TypeResolution>,
 }
@@ -258,6 +258,11 @@ impl<Name, Var> SymbolTable<Name, Var> {
 
         self.cursor -= 1;
     }
+
+    /// Returns the number of active lexical scopes, including the root scope.
+    pub const fn depth(&self) -> usize {
+        self.cursor
+    }
 }
 
 impl<Name, Var> SymbolTable<Name, Var>
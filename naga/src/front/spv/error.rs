@@ -43,6 +43,8 @@ pub enum Error {
     UnsupportedBuiltIn(spirv::Word),
     #[error("unsupported control flow %{0}")]
     UnsupportedControlFlow(spirv::Word),
+    #[error("no ids are left for the blocks needed to structure control flow")]
+    IdBoundOverflow,
     #[error("unsupported binary operator %{0}")]
     UnsupportedBinaryOperator(spirv::Word),
     #[error("Naga supports OpTypeRuntimeArray in the StorageBuffer storage class only")]
@@ -140,6 +142,10 @@ pub enum Error {
     NonBindingArrayOfImageOrSamplers,
     #[error("naga only supports specialization constant IDs up to 65535 but was given {0}")]
     SpecIdTooHigh(u32),
+    #[error("unsupported operation {0:?} in OpSpecConstantOp")]
+    UnsupportedSpecConstantOp(spirv::Op),
+    #[error("failed to evaluate OpSpecConstantOp: {0}")]
+    SpecConstantEvaluation(crate::proc::ConstantEvaluatorError),
 
     #[error("atomic upgrade error: {0}")]
     AtomicUpgradeError(atomic_upgrade::Error),
@@ -171,3 +177,9 @@ impl From<atomic_upgrade::Error> for Error {
         Error::AtomicUpgradeError(source)
     }
 }
+
+impl From<crate::proc::ConstantEvaluatorError> for Error {
+    fn from(source: crate::proc::ConstantEvaluatorError) -> Self {
+        Error::SpecConstantEvaluation(source)
+    }
+}
@@ -35,6 +35,13 @@ impl<I: Iterator<Item = u32>> super::Frontend<I> {
         let _fun_control = self.next()?;
         let fun_type_id = self.next()?;
 
+        let (expressions, spec_range) = self.make_expression_storage(
+            &module.global_variables,
+            &module.constants,
+            &module.overrides,
+            &module.global_expressions,
+        );
+
         let mut fun = {
             let ft = self.lookup_function_type.lookup(fun_type_id)?;
             if ft.return_type_id != result_type_id {
@@ -53,11 +60,7 @@ impl<I: Iterator<Item = u32>> super::Frontend<I> {
                     })
                 },
                 local_variables: Arena::new(),
-                expressions: self.make_expression_storage(
-                    &module.global_variables,
-                    &module.constants,
-                    &module.overrides,
-                ),
+                expressions,
                 named_expressions: crate::NamedExpressions::default(),
                 body: crate::Block::new(),
                 diagnostic_filter_leaf: None,
@@ -136,17 +139,19 @@ impl<I: Iterator<Item = u32>> super::Frontend<I> {
         // Insert the main body whose parent is also himself
         block_ctx.bodies.push(super::Body::with_parent(0));
 
+        // Read the blocks ahead, to add any merge instructions the parser
+        // needs to build structured control flow from them.
+        let mut body = self.read_function_body()?;
+        body.restructure(&mut self.id_bound)?;
+        self.replay = body.into_words();
+
         // Scan the blocks and add them as nodes
         loop {
             let fun_inst = self.next_inst()?;
             log::debug!("{:?}", fun_inst.op);
             match fun_inst.op {
-                spirv::Op::Line => {
-                    fun_inst.expect(4)?;
-                    let _file_id = self.next()?;
-                    let _row_id = self.next()?;
-                    let _col_id = self.next()?;
-                }
+                spirv::Op::Line => self.parse_line(fun_inst)?,
+                spirv::Op::NoLine => self.parse_no_line(fun_inst)?,
                 spirv::Op::Label => {
                     // Read the label ID
                     fun_inst.expect(2)?;
@@ -279,6 +284,12 @@ impl<I: Iterator<Item = u32>> super::Frontend<I> {
         }
 
         fun.body = block_ctx.lower();
+        if let Some(range) = spec_range {
+            // Copies of constants computed from specialization constants can
+            // be used anywhere in the body.
+            let emit = crate::Block::from_vec(vec![crate::Statement::Emit(range)]);
+            fun.body.splice(..0, emit);
+        }
 
         // done
         let fun_handle = module.functions.append(fun, self.span_from_with_op(start));
@@ -570,6 +581,8 @@ impl<I: Iterator<Item = u32>> super::Frontend<I> {
             early_depth_test: ep.early_depth_test,
             workgroup_size: ep.workgroup_size,
             function,
+            mesh_info: None,
+            task_payload: None,
         });
 
         Ok(())
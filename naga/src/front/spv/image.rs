@@ -559,14 +559,11 @@ impl<I: Iterator<Item = u32>> super::Frontend<I> {
                 }
                 spirv::ImageOperands::CONST_OFFSET => {
                     let offset_constant = self.next()?;
-                    let offset_expr = self
+                    let offset_handle = self
                         .lookup_constant
                         .lookup(offset_constant)?
                         .inner
-                        .to_expr();
-                    let offset_handle = ctx
-                        .global_expressions
-                        .append(offset_expr, Default::default());
+                        .to_global_expr(ctx.global_expressions, Default::default());
                     offset = Some(offset_handle);
                     words_left -= 1;
                 }
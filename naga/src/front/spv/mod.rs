@@ -32,6 +32,8 @@ mod error;
 mod function;
 mod image;
 mod null;
+mod restructure;
+mod spec_constant;
 
 use convert::*;
 pub use error::Error;
@@ -45,7 +47,7 @@ use crate::{
 };
 
 use petgraph::graphmap::GraphMap;
-use std::{convert::TryInto, mem, num::NonZeroU32, path::PathBuf};
+use std::{collections::VecDeque, convert::TryInto, mem, num::NonZeroU32, path::PathBuf};
 
 pub const SUPPORTED_CAPABILITIES: &[spirv::Capability] = &[
     spirv::Capability::Shader,
@@ -248,6 +250,7 @@ impl Decoration {
                 interpolation,
                 sampling,
                 second_blend_source: false,
+                per_primitive: false,
             }),
             _ => Err(Error::MissingDecoration(spirv::Decoration::Location)),
         }
@@ -280,17 +283,31 @@ struct LookupType {
     base_id: Option<spirv::Word>,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Copy, Debug)]
 enum Constant {
     Constant(Handle<crate::Constant>),
     Override(Handle<crate::Override>),
+    /// An override-expression in [`Module::global_expressions`], computed from
+    /// specialization constants by `OpSpecConstantOp` or
+    /// `OpSpecConstantComposite`.
+    ///
+    /// See the [`spec_constant`] module for how these are used.
+    ///
+    /// [`Module::global_expressions`]: crate::Module::global_expressions
+    Expression(Handle<crate::Expression>),
 }
 
 impl Constant {
-    const fn to_expr(&self) -> crate::Expression {
-        match *self {
-            Self::Constant(c) => crate::Expression::Constant(c),
-            Self::Override(o) => crate::Expression::Override(o),
+    /// Return an expression in `global_expressions` referring to this constant.
+    fn to_global_expr(
+        self,
+        global_expressions: &mut Arena<crate::Expression>,
+        span: crate::Span,
+    ) -> Handle<crate::Expression> {
+        match self {
+            Self::Constant(c) => global_expressions.append(crate::Expression::Constant(c), span),
+            Self::Override(o) => global_expressions.append(crate::Expression::Override(o), span),
+            Self::Expression(expr) => expr,
         }
     }
 }
@@ -379,6 +396,24 @@ impl Default for Options {
     }
 }
 
+/// Source code embedded in a SPIR-V module by `OpSource`.
+///
+/// When a module carries its source code, the spans in the [`Module`]
+/// produced by [`Frontend::parse_with_debug_source`] are taken from the
+/// module's `OpLine` instructions, and refer to this source code rather than
+/// to byte offsets in the SPIR-V binary.
+///
+/// [`Module`]: crate::Module
+#[derive(Clone, Debug, PartialEq)]
+pub struct DebugSource {
+    /// The name of the source file, if the module gives one.
+    pub file_name: Option<String>,
+    /// The language the source code is written in.
+    pub language: spirv::SourceLanguage,
+    /// The source code, including any `OpSourceContinued` parts.
+    pub source_code: String,
+}
+
 /// An index into the `BlockContext::bodies` table.
 type BodyIndex = usize;
 
@@ -608,6 +643,12 @@ enum SignAnchor {
 pub struct Frontend<I> {
     data: I,
     data_offset: usize,
+    /// Words to read before going on with `data`, when a function's body has
+    /// been read ahead to give it structured control flow.
+    replay: VecDeque<restructure::Word>,
+    /// The bound on ids given in the module header, raised past any blocks
+    /// we add while restructuring functions.
+    id_bound: spirv::Word,
     state: ModuleState,
     layouter: Layouter,
     temp_bytes: Vec<u8>,
@@ -662,6 +703,20 @@ pub struct Frontend<I> {
     /// glslang declares those by default even though they are never written to
     /// (see <https://github.com/KhronosGroup/glslang/issues/1868>)
     gl_per_vertex_builtin_access: FastHashSet<crate::BuiltIn>,
+
+    /// Strings declared by `OpString`, indexed by their id.
+    debug_strings: FastHashMap<spirv::Word, String>,
+    /// The source code embedded by the first `OpSource` that has any.
+    debug_source: Option<DebugSource>,
+    /// The `OpString` id naming the file of [`Self::debug_source`].
+    debug_source_file: Option<spirv::Word>,
+    /// Whether an `OpSourceContinued` extends [`Self::debug_source`].
+    debug_source_continues: bool,
+    /// The byte offset of the start of each line in [`Self::debug_source`].
+    debug_line_starts: Vec<usize>,
+    /// The location given by the `OpLine` instruction currently in effect,
+    /// as a span in [`Self::debug_source`].
+    current_line: Option<crate::Span>,
 }
 
 impl<I: Iterator<Item = u32>> Frontend<I> {
@@ -669,6 +724,8 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
         Frontend {
             data,
             data_offset: 0,
+            replay: VecDeque::new(),
+            id_bound: 0,
             state: ModuleState::Empty,
             layouter: Layouter::default(),
             temp_bytes: Vec::new(),
@@ -696,18 +753,79 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
             options: options.clone(),
             switch_cases: FastIndexMap::default(),
             gl_per_vertex_builtin_access: FastHashSet::default(),
+            debug_strings: FastHashMap::default(),
+            debug_source: None,
+            debug_source_file: None,
+            debug_source_continues: false,
+            debug_line_starts: Vec::new(),
+            current_line: None,
         }
     }
 
     fn span_from(&self, from: usize) -> crate::Span {
-        crate::Span::from(from..self.data_offset)
+        self.instruction_span(from..self.data_offset)
     }
 
     fn span_from_with_op(&self, from: usize) -> crate::Span {
-        crate::Span::from((from - 4)..self.data_offset)
+        self.instruction_span((from - 4)..self.data_offset)
+    }
+
+    /// Return the span of an instruction occupying `range` in the binary.
+    ///
+    /// If the module embeds its source code, this is the location given by
+    /// the `OpLine` in effect, if any. Otherwise, it is the byte range of the
+    /// instruction itself.
+    fn instruction_span(&self, range: std::ops::Range<usize>) -> crate::Span {
+        if self.debug_source.is_some() {
+            self.current_line.unwrap_or_default()
+        } else {
+            crate::Span::from(range)
+        }
+    }
+
+    /// Convert an `OpLine` location to a span in the embedded source code.
+    ///
+    /// SPIR-V only gives the position at which a line starts, so the span
+    /// covers the rest of that source line.
+    fn source_line_span(
+        &mut self,
+        file_id: spirv::Word,
+        line: spirv::Word,
+        column: spirv::Word,
+    ) -> Option<crate::Span> {
+        let source = self.debug_source.as_ref()?;
+        if self.debug_source_file != Some(file_id) {
+            return None;
+        }
+        if self.debug_line_starts.is_empty() {
+            self.debug_line_starts = std::iter::once(0)
+                .chain(source.source_code.match_indices('\n').map(|(i, _)| i + 1))
+                .collect();
+        }
+
+        let code = source.source_code.as_str();
+        let line_start = *self
+            .debug_line_starts
+            .get((line as usize).checked_sub(1)?)?;
+        let line_end = self
+            .debug_line_starts
+            .get(line as usize)
+            .map_or(code.len(), |&next| next - 1);
+        let line_end = line_start + code[line_start..line_end].trim_end().len();
+
+        // Columns are 1-based, with 0 meaning that the column is unknown.
+        let mut start = (line_start + (column as usize).saturating_sub(1)).min(line_end);
+        while !code.is_char_boundary(start) {
+            start += 1;
+        }
+        Some(crate::Span::from(start..line_end))
     }
 
     fn next(&mut self) -> Result<u32, Error> {
+        if let Some((word, offset)) = self.replay.pop_front() {
+            self.data_offset = offset;
+            return Ok(word);
+        }
         if let Some(res) = self.data.next() {
             self.data_offset += 4;
             Ok(res)
@@ -1514,17 +1632,12 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
             use spirv::Op;
             let start = self.data_offset;
             let inst = self.next_inst()?;
-            let span = crate::Span::from(start..(start + 4 * (inst.wc as usize)));
+            let span = self.instruction_span(start..(start + 4 * (inst.wc as usize)));
             log::debug!("\t\t{:?} [{}]", inst.op, inst.wc);
 
             match inst.op {
-                Op::Line => {
-                    inst.expect(4)?;
-                    let _file_id = self.next()?;
-                    let _row_id = self.next()?;
-                    let _col_id = self.next()?;
-                }
-                Op::NoLine => inst.expect(1)?,
+                Op::Line => self.parse_line(inst)?,
+                Op::NoLine => self.parse_no_line(inst)?,
                 Op::Undef => {
                     inst.expect(3)?;
                     let type_id = self.next()?;
@@ -1554,7 +1667,18 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                         inst.expect(5)?;
                         let init_id = self.next()?;
                         let lconst = self.lookup_constant.lookup(init_id)?;
-                        Some(ctx.expressions.append(lconst.inner.to_expr(), span))
+                        Some(match lconst.inner {
+                            Constant::Constant(c) => {
+                                ctx.expressions.append(crate::Expression::Constant(c), span)
+                            }
+                            Constant::Override(o) => {
+                                ctx.expressions.append(crate::Expression::Override(o), span)
+                            }
+                            // Use the copy `make_expression_storage` made.
+                            Constant::Expression(_) => {
+                                self.lookup_expression.lookup(init_id)?.handle
+                            }
+                        })
                     } else {
                         None
                     };
@@ -3872,7 +3996,7 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                                 ctx.gctx().global_expressions[ctx.gctx().constants[constant].init],
                                 crate::Expression::Literal(crate::Literal::Bool(true)),
                             ),
-                            Constant::Override(_) => false,
+                            Constant::Override(_) | Constant::Expression(_) => false,
                         })
                         .is_some()
                     {
@@ -4256,6 +4380,8 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 }
             }
         };
+        // An `OpLine` only applies until the end of its block.
+        self.current_line = None;
 
         block.extend(emitter.finish(ctx.expressions));
         if let Some(stmt) = terminator {
@@ -4270,12 +4396,23 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
         Ok(())
     }
 
+    /// Create a function's expression arena, registering all global variables
+    /// and constants in it.
+    ///
+    /// Constants computed from specialization constants are copied into the
+    /// arena as well. If any of the copies need to be emitted, also return
+    /// their range, which must be covered by an `Emit` at the start of the
+    /// function's body.
     fn make_expression_storage(
         &mut self,
         globals: &Arena<crate::GlobalVariable>,
         constants: &Arena<crate::Constant>,
         overrides: &Arena<crate::Override>,
-    ) -> Arena<crate::Expression> {
+        global_expressions: &Arena<crate::Expression>,
+    ) -> (
+        Arena<crate::Expression>,
+        Option<crate::arena::Range<crate::Expression>>,
+    ) {
         let mut expressions = Arena::new();
         #[allow(clippy::panic)]
         {
@@ -4298,10 +4435,15 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
             );
         }
         // register constants
+        let mut spec_expressions = Vec::new();
         for (&id, con) in self.lookup_constant.iter() {
             let (expr, span) = match con.inner {
                 Constant::Constant(c) => (crate::Expression::Constant(c), constants.get_span(c)),
                 Constant::Override(o) => (crate::Expression::Override(o), overrides.get_span(o)),
+                Constant::Expression(expr) => {
+                    spec_expressions.push((id, con.type_id, expr));
+                    continue;
+                }
             };
             let handle = expressions.append(expr, span);
             self.lookup_expression.insert(
@@ -4316,8 +4458,22 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 },
             );
         }
+        // copy constants computed from specialization constants
+        let roots: Vec<_> = spec_expressions.iter().map(|&(_, _, expr)| expr).collect();
+        let (handles, range) =
+            spec_constant::copy_expressions(&roots, global_expressions, &mut expressions);
+        for (&(id, type_id, _), handle) in spec_expressions.iter().zip(handles) {
+            self.lookup_expression.insert(
+                id,
+                LookupExpression {
+                    type_id,
+                    handle,
+                    block_id: 0,
+                },
+            );
+        }
         // done
-        expressions
+        (expressions, range)
     }
 
     fn switch(&mut self, state: ModuleState, op: spirv::Op) -> Result<(), Error> {
@@ -4380,7 +4536,8 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 | S::RayQuery { .. }
                 | S::SubgroupBallot { .. }
                 | S::SubgroupCollectiveOperation { .. }
-                | S::SubgroupGather { .. } => {}
+                | S::SubgroupGather { .. }
+                | S::MeshFunction(_) => {}
                 S::Call {
                     function: ref mut callee,
                     ref arguments,
@@ -4455,13 +4612,32 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
     }
 
     pub fn parse(mut self) -> Result<crate::Module, Error> {
+        self.parse_module()
+    }
+
+    /// Parse the module, and return it along with the source code it embeds.
+    ///
+    /// If the module has an `OpSource` instruction with source code, the
+    /// spans of the returned module's expressions and statements are taken
+    /// from its `OpLine` instructions and refer to the returned
+    /// [`DebugSource`], so that errors can be reported against the code the
+    /// module was compiled from. Instructions not covered by an `OpLine` for
+    /// that source get undefined spans.
+    pub fn parse_with_debug_source(
+        mut self,
+    ) -> Result<(crate::Module, Option<DebugSource>), Error> {
+        let module = self.parse_module()?;
+        Ok((module, self.debug_source.take()))
+    }
+
+    fn parse_module(&mut self) -> Result<crate::Module, Error> {
         let mut module = {
             if self.next()? != spirv::MAGIC_NUMBER {
                 return Err(Error::InvalidHeader);
             }
             let version_raw = self.next()?;
             let generator = self.next()?;
-            let _bound = self.next()?;
+            self.id_bound = self.next()?;
             let _schema = self.next()?;
             log::info!("Generated by {} version {:x}", generator, version_raw);
             crate::Module::default()
@@ -4491,6 +4667,7 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 Op::ExecutionMode => self.parse_execution_mode(inst),
                 Op::String => self.parse_string(inst),
                 Op::Source => self.parse_source(inst),
+                Op::SourceContinued => self.parse_source_continued(inst),
                 Op::SourceExtension => self.parse_source_extension(inst),
                 Op::Name => self.parse_name(inst),
                 Op::MemberName => self.parse_member_name(inst),
@@ -4522,7 +4699,10 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
                 Op::ConstantFalse | Op::SpecConstantFalse => {
                     self.parse_bool_constant(inst, false, &mut module)
                 }
+                Op::SpecConstantOp => self.parse_spec_constant_op(inst, &mut module),
                 Op::Variable => self.parse_global_variable(inst, &mut module),
+                Op::Line => self.parse_line(inst),
+                Op::NoLine => self.parse_no_line(inst),
                 Op::Function => {
                     self.switch(ModuleState::Function, inst.op)?;
                     inst.expect(5)?;
@@ -4728,16 +4908,75 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
     fn parse_string(&mut self, inst: Instruction) -> Result<(), Error> {
         self.switch(ModuleState::Source, inst.op)?;
         inst.expect_at_least(3)?;
-        let _id = self.next()?;
-        let (_name, _) = self.next_string(inst.wc - 2)?;
+        let id = self.next()?;
+        let (name, _) = self.next_string(inst.wc - 2)?;
+        self.debug_strings.insert(id, name);
         Ok(())
     }
 
     fn parse_source(&mut self, inst: Instruction) -> Result<(), Error> {
         self.switch(ModuleState::Source, inst.op)?;
-        for _ in 1..inst.wc {
-            let _ = self.next()?;
+        inst.expect_at_least(3)?;
+        let language = self.next()?;
+        let _version = self.next()?;
+        let file_id = if inst.wc > 3 {
+            Some(self.next()?)
+        } else {
+            None
+        };
+        let source_code = if inst.wc > 4 {
+            let (source_code, left) = self.next_string(inst.wc - 4)?;
+            if left != 0 {
+                return Err(Error::InvalidOperand);
+            }
+            Some(source_code)
+        } else {
+            None
+        };
+
+        // Spans can only refer to a single source, so we keep the first one
+        // and ignore any others, such as included files.
+        self.debug_source_continues = false;
+        if let Some(source_code) = source_code.filter(|_| self.debug_source.is_none()) {
+            self.debug_source = Some(DebugSource {
+                file_name: file_id.and_then(|id| self.debug_strings.get(&id).cloned()),
+                language: spirv::SourceLanguage::from_u32(language)
+                    .unwrap_or(spirv::SourceLanguage::Unknown),
+                source_code,
+            });
+            self.debug_source_file = file_id;
+            self.debug_source_continues = true;
+        }
+        Ok(())
+    }
+
+    fn parse_source_continued(&mut self, inst: Instruction) -> Result<(), Error> {
+        self.switch(ModuleState::Source, inst.op)?;
+        inst.expect_at_least(2)?;
+        let (source_code, left) = self.next_string(inst.wc - 1)?;
+        if left != 0 {
+            return Err(Error::InvalidOperand);
         }
+        if let Some(ref mut source) = self.debug_source {
+            if self.debug_source_continues {
+                source.source_code.push_str(&source_code);
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_line(&mut self, inst: Instruction) -> Result<(), Error> {
+        inst.expect(4)?;
+        let file_id = self.next()?;
+        let line = self.next()?;
+        let column = self.next()?;
+        self.current_line = self.source_line_span(file_id, line, column);
+        Ok(())
+    }
+
+    fn parse_no_line(&mut self, inst: Instruction) -> Result<(), Error> {
+        inst.expect(1)?;
+        self.current_line = None;
         Ok(())
     }
 
@@ -5484,6 +5723,8 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
             }) => {
                 let low = self.next()?;
                 match width {
+                    // Literals narrower than a word occupy its low-order bits.
+                    2 => crate::Literal::F16(half::f16::from_bits(low as u16)),
                     4 => crate::Literal::F32(f32::from_bits(low)),
                     8 => {
                         inst.expect(5)?;
@@ -5522,14 +5763,14 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
         let ty = type_lookup.handle;
 
         let mut components = Vec::with_capacity(inst.wc as usize - 3);
+        let mut is_const = true;
         for _ in 0..components.capacity() {
             let start = self.data_offset;
             let component_id = self.next()?;
             let span = self.span_from_with_op(start);
-            let constant = self.lookup_constant.lookup(component_id)?;
-            let expr = module
-                .global_expressions
-                .append(constant.inner.to_expr(), span);
+            let constant = self.lookup_constant.lookup(component_id)?.inner;
+            is_const &= matches!(constant, Constant::Constant(_));
+            let expr = constant.to_global_expr(&mut module.global_expressions, span);
             components.push(expr);
         }
 
@@ -5539,7 +5780,12 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
             .global_expressions
             .append(crate::Expression::Compose { ty, components }, span);
 
-        self.insert_parsed_constant(module, id, type_id, ty, init, span)
+        if is_const {
+            self.insert_parsed_constant(module, id, type_id, ty, init, span)
+        } else {
+            self.insert_spec_expression(id, type_id, init);
+            Ok(())
+        }
     }
 
     fn parse_null_constant(
@@ -5621,6 +5867,25 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
         Ok(())
     }
 
+    /// Record that `id` is the override-expression `expr`, computed from
+    /// specialization constants.
+    fn insert_spec_expression(
+        &mut self,
+        id: spirv::Word,
+        type_id: spirv::Word,
+        expr: Handle<crate::Expression>,
+    ) {
+        // Only constants and overrides can carry names.
+        self.future_decor.remove(&id);
+        self.lookup_constant.insert(
+            id,
+            LookupConstant {
+                inner: Constant::Expression(expr),
+                type_id,
+            },
+        );
+    }
+
     fn parse_global_variable(
         &mut self,
         inst: Instruction,
@@ -5638,9 +5903,9 @@ impl<I: Iterator<Item = u32>> Frontend<I> {
             let init_id = self.next()?;
             let span = self.span_from_with_op(start);
             let lconst = self.lookup_constant.lookup(init_id)?;
-            let expr = module
-                .global_expressions
-                .append(lconst.inner.to_expr(), span);
+            let expr = lconst
+                .inner
+                .to_global_expr(&mut module.global_expressions, span);
             Some(expr)
         } else {
             None
@@ -5858,7 +6123,7 @@ fn make_index_literal(
 fn resolve_constant(gctx: crate::proc::GlobalCtx, constant: &Constant) -> Option<u32> {
     let constant = match *constant {
         Constant::Constant(constant) => constant,
-        Constant::Override(_) => return None,
+        Constant::Override(_) | Constant::Expression(_) => return None,
     };
     match gctx.global_expressions[gctx.constants[constant].init] {
         crate::Expression::Literal(crate::Literal::U32(id)) => Some(id),
@@ -5878,6 +6143,23 @@ pub fn parse_u8_slice(data: &[u8], options: &Options) -> Result<crate::Module, E
     Frontend::new(words, options).parse()
 }
 
+/// Parse a SPIR-V module from bytes, along with the source code it embeds.
+///
+/// See [`Frontend::parse_with_debug_source`].
+pub fn parse_u8_slice_with_debug_source(
+    data: &[u8],
+    options: &Options,
+) -> Result<(crate::Module, Option<DebugSource>), Error> {
+    if data.len() % 4 != 0 {
+        return Err(Error::IncompleteData);
+    }
+
+    let words = data
+        .chunks(4)
+        .map(|c| u32::from_le_bytes(c.try_into().unwrap()));
+    Frontend::new(words, options).parse_with_debug_source()
+}
+
 /// Helper function to check if `child` is in the scope of `parent`
 fn is_parent(mut child: usize, parent: usize, block_ctx: &BlockContext) -> bool {
     loop {
@@ -5910,6 +6192,63 @@ mod test {
     }
 }
 
+#[cfg(all(test, feature = "wgsl-in", spv_out))]
+mod test_debug_source {
+    #[test]
+    fn spans_from_op_line() {
+        let source = "\
+@fragment
+fn main(@location(0) x: f32) -> @location(0) vec4<f32> {
+    let y = x * 2.0;
+    return vec4(y);
+}
+";
+        let module = crate::front::wgsl::parse_str(source).unwrap();
+        let info = crate::valid::Validator::new(
+            crate::valid::ValidationFlags::all(),
+            crate::valid::Capabilities::default(),
+        )
+        .validate(&module)
+        .unwrap();
+        let options = crate::back::spv::Options {
+            flags: crate::back::spv::WriterFlags::DEBUG,
+            debug_info: Some(crate::back::spv::DebugInfo {
+                source_code: source,
+                file_name: "main.wgsl".as_ref(),
+                language: spirv::SourceLanguage::WGSL,
+            }),
+            ..Default::default()
+        };
+        let words = crate::back::spv::write_vec(&module, &info, &options, None).unwrap();
+
+        let (module, debug_source) = super::Frontend::new(words.into_iter(), &Default::default())
+            .parse_with_debug_source()
+            .unwrap();
+        let debug_source = debug_source.unwrap();
+        assert_eq!(debug_source.file_name.as_deref(), Some("main.wgsl"));
+        assert_eq!(debug_source.language, spirv::SourceLanguage::WGSL);
+        assert_eq!(debug_source.source_code, source);
+
+        let (_, function) = module.functions.iter().next().unwrap();
+        let (multiply, _) = function
+            .expressions
+            .iter()
+            .find(|&(_, expr)| {
+                matches!(
+                    *expr,
+                    crate::Expression::Binary {
+                        op: crate::BinaryOperator::Multiply,
+                        ..
+                    }
+                )
+            })
+            .unwrap();
+        let span = function.expressions.get_span(multiply);
+        assert_eq!(span.location(source).line_number, 3);
+        assert_eq!(&source[span.to_range().unwrap()], "x * 2.0;");
+    }
+}
+
 #[cfg(all(test, feature = "wgsl-in", wgsl_out))]
 mod test_atomic {
     fn atomic_test(bytes: &[u8]) {
@@ -0,0 +1,692 @@
+/*!
+Recovery of structured control flow for SPIR-V that lacks it.
+
+The rest of the frontend builds Naga [`If`], [`Switch`] and [`Loop`]
+statements from the `OpSelectionMerge` and `OpLoopMerge` instructions that
+structured SPIR-V puts in every construct's header block. Optimizers and some
+compilers leave these out, or produce graphs whose merge structure Naga can't
+follow. So, before a function is parsed, we read all of its blocks, analyze
+its control flow graph, and add merge instructions to the headers that lack
+them:
+
+- A block targeted by a back edge is a loop header. The block that the back
+  edge leaves becomes the loop's continue target, and the single block
+  outside the loop that the loop branches to becomes its merge block.
+
+- A conditional branch or switch is a selection header. Its merge block is
+  the block that immediately post-dominates it, ignoring paths that break out
+  of, or continue, the innermost loop containing it. Conditional branches to
+  a construct's merge or continue target are left alone, since the parser
+  already turns them into `break` and `continue` statements.
+
+When the block we'd pick as a merge block already belongs to another
+construct, or isn't dominated by the header, we add a new block that just
+branches to it, so that every construct gets a merge block of its own.
+Constructs that never reach a merge block get a new block holding
+`OpUnreachable`.
+
+Graphs we can't give a structure this way, such as irreducible loops, loops
+with several back edges or exits, and blocks that several branches join
+without any construct to merge them, are rejected with
+[`Error::UnsupportedControlFlow`].
+
+Functions that are already structured are passed on unchanged.
+
+[`If`]: crate::Statement::If
+[`Switch`]: crate::Statement::Switch
+[`Loop`]: crate::Statement::Loop
+*/
+
+use super::{Error, Frontend};
+use crate::{FastHashMap, FastHashSet};
+
+use petgraph::{
+    algo::dominators::{self, Dominators},
+    graphmap::DiGraphMap,
+    visit::{depth_first_search, DfsEvent},
+    Direction,
+};
+use std::{collections::VecDeque, mem};
+
+/// A word of a function's body, along with the value of
+/// [`Frontend::data_offset`] right after it was read.
+pub(super) type Word = (u32, usize);
+
+/// The virtual node all function exits lead to, for computing
+/// post-dominators. SPIR-V ids are never zero.
+const EXIT: spirv::Word = 0;
+
+#[derive(Clone, Copy, Debug)]
+enum Merge {
+    Selection(spirv::Word),
+    Loop {
+        merge: spirv::Word,
+        continuing: spirv::Word,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TerminatorKind {
+    Branch,
+    Conditional,
+    Switch,
+    /// Returns, kills, unreachable and any block missing a terminator.
+    Exit,
+}
+
+/// A SPIR-V block held back by [`Frontend::read_function_body`].
+#[derive(Debug)]
+struct Block {
+    label: spirv::Word,
+    /// The words of the block, from its `OpLabel` through its terminator,
+    /// preceded by any debug instructions that came before the `OpLabel`.
+    words: Vec<Word>,
+    /// The index in `words` at which the block's terminator starts.
+    terminator_index: usize,
+    merge: Option<Merge>,
+    kind: TerminatorKind,
+    targets: Vec<spirv::Word>,
+}
+
+impl Block {
+    /// Insert a merge instruction right before the block's terminator.
+    fn insert_merge(&mut self, op: spirv::Op, operands: &[spirv::Word]) {
+        let offset = self.words[..self.terminator_index]
+            .last()
+            .map_or(0, |&(_, offset)| offset);
+        let first = ((operands.len() as u32 + 1) << 16) | op as u32;
+        let words = std::iter::once(first)
+            .chain(operands.iter().copied())
+            .map(|word| (word, offset));
+        let count = operands.len() + 1;
+        self.words
+            .splice(self.terminator_index..self.terminator_index, words);
+        self.terminator_index += count;
+    }
+}
+
+/// The natural loop of a loop header.
+struct Loop {
+    header: spirv::Word,
+    /// All the blocks of the loop, including the header.
+    body: FastHashSet<spirv::Word>,
+    continuing: spirv::Word,
+    /// The block control reaches once the loop is done, if there is one.
+    ///
+    /// This is the target of the loop's merge block, if we had to add one
+    /// that just branches to it.
+    exit: Option<spirv::Word>,
+}
+
+/// The body of a function, read ahead of parsing it.
+#[derive(Debug)]
+pub(super) struct FunctionBody {
+    blocks: Vec<Block>,
+    /// The words after the last block, ending with `OpFunctionEnd`.
+    trailer: Vec<Word>,
+}
+
+impl<I: Iterator<Item = u32>> Frontend<I> {
+    /// Read the blocks of the function being parsed, through its
+    /// `OpFunctionEnd`.
+    pub(super) fn read_function_body(&mut self) -> Result<FunctionBody, Error> {
+        use spirv::Op;
+
+        fn finish(blocks: &mut Vec<Block>, block: Option<Block>) {
+            if let Some(mut block) = block {
+                if block.terminator_index == 0 {
+                    block.terminator_index = block.words.len();
+                }
+                blocks.push(block);
+            }
+        }
+
+        let mut blocks = Vec::new();
+        // Words that don't belong to a block yet, like debug instructions
+        // between blocks.
+        let mut pending = Vec::new();
+        let mut current: Option<Block> = None;
+
+        loop {
+            let first = self.next()?;
+            let wc = (first >> 16) as usize;
+            if wc == 0 {
+                return Err(Error::InvalidWordCount);
+            }
+            let mut words = Vec::with_capacity(wc);
+            words.push((first, self.data_offset));
+            for _ in 1..wc {
+                words.push((self.next()?, self.data_offset));
+            }
+            let operand = |index: usize| words.get(index).map_or(0, |&(word, _)| word);
+
+            let op = Op::from_u32(first & 0xffff);
+            match op {
+                Some(Op::Label) => {
+                    finish(&mut blocks, current.take());
+                    let label = operand(1);
+                    let mut block_words = mem::take(&mut pending);
+                    block_words.extend(words);
+                    current = Some(Block {
+                        label,
+                        words: block_words,
+                        terminator_index: 0,
+                        merge: None,
+                        kind: TerminatorKind::Exit,
+                        targets: Vec::new(),
+                    });
+                    continue;
+                }
+                Some(Op::FunctionEnd) => {
+                    finish(&mut blocks, current.take());
+                    pending.extend(words);
+                    return Ok(FunctionBody {
+                        blocks,
+                        trailer: pending,
+                    });
+                }
+                _ => {}
+            }
+
+            let Some(block) = current.as_mut() else {
+                pending.extend(words);
+                continue;
+            };
+            let kind = match op {
+                Some(Op::SelectionMerge) => {
+                    block.merge = Some(Merge::Selection(operand(1)));
+                    None
+                }
+                Some(Op::LoopMerge) => {
+                    block.merge = Some(Merge::Loop {
+                        merge: operand(1),
+                        continuing: operand(2),
+                    });
+                    None
+                }
+                Some(Op::Branch) => {
+                    block.targets.push(operand(1));
+                    Some(TerminatorKind::Branch)
+                }
+                Some(Op::BranchConditional) => {
+                    block.targets.extend([operand(2), operand(3)]);
+                    Some(TerminatorKind::Conditional)
+                }
+                Some(Op::Switch) => {
+                    block.targets.push(operand(2));
+                    block.targets.extend((4..wc).step_by(2).map(operand));
+                    Some(TerminatorKind::Switch)
+                }
+                Some(
+                    Op::Return
+                    | Op::ReturnValue
+                    | Op::Kill
+                    | Op::Unreachable
+                    | Op::TerminateInvocation
+                    | Op::IgnoreIntersectionKHR
+                    | Op::TerminateRayKHR,
+                ) => Some(TerminatorKind::Exit),
+                _ => None,
+            };
+
+            match kind {
+                Some(kind) => {
+                    block.kind = kind;
+                    block.terminator_index = block.words.len();
+                    block.words.extend(words);
+                    finish(&mut blocks, current.take());
+                }
+                None => block.words.extend(words),
+            }
+        }
+    }
+}
+
+impl FunctionBody {
+    /// Give the function structured control flow, by adding merge
+    /// instructions to the construct headers that lack them.
+    ///
+    /// New blocks get ids starting at `id_bound`, which is raised past them.
+    pub(super) fn restructure(&mut self, id_bound: &mut spirv::Word) -> Result<(), Error> {
+        let Some(entry) = self.blocks.first().map(|block| block.label) else {
+            return Ok(());
+        };
+        let block_count = self.blocks.len();
+        let end_offset = self
+            .blocks
+            .last()
+            .and_then(|block| block.words.last())
+            .map_or(0, |&(_, offset)| offset);
+
+        let mut graph = DiGraphMap::<spirv::Word, ()>::new();
+        for block in self.blocks.iter() {
+            graph.add_node(block.label);
+            for &target in block.targets.iter() {
+                graph.add_edge(block.label, target, ());
+            }
+        }
+
+        let doms = dominators::simple_fast(&graph, entry);
+        let reachable = |block| doms.dominators(block).is_some();
+        let dominates = |a, b| {
+            doms.dominators(b)
+                .map_or(false, |mut iter| iter.any(|dominator| dominator == a))
+        };
+
+        // Every edge that closes a cycle must go back to a block dominating
+        // its source, otherwise the graph is irreducible.
+        let mut back_edges = FastHashSet::default();
+        let mut irreducible = None;
+        depth_first_search(&graph, Some(entry), |event| {
+            if let DfsEvent::BackEdge(from, to) = event {
+                if dominates(to, from) {
+                    back_edges.insert((from, to));
+                } else {
+                    irreducible.get_or_insert(to);
+                }
+            }
+        });
+        if let Some(header) = irreducible {
+            return Err(Error::UnsupportedControlFlow(header));
+        }
+
+        // Blocks the parser will treat as the end of a construct, and all the
+        // blocks that already have a place in some construct.
+        let mut mergers = FastHashSet::default();
+        let mut claimed = FastHashSet::default();
+        for block in self.blocks.iter() {
+            match block.merge {
+                Some(Merge::Selection(merge)) => {
+                    mergers.insert(merge);
+                }
+                Some(Merge::Loop { merge, continuing }) => {
+                    mergers.extend([merge, continuing]);
+                }
+                None => {}
+            }
+            if block.kind == TerminatorKind::Switch {
+                claimed.extend(block.targets.iter().copied());
+            }
+        }
+        claimed.extend(mergers.iter().copied());
+
+        let mut loops = Vec::new();
+        for index in 0..block_count {
+            let header = self.blocks[index].label;
+            let latches: Vec<_> = graph
+                .neighbors_directed(header, Direction::Incoming)
+                .filter(|&latch| back_edges.contains(&(latch, header)))
+                .collect();
+            if latches.is_empty() {
+                continue;
+            }
+
+            let mut body = FastHashSet::default();
+            body.insert(header);
+            let mut stack = latches.clone();
+            while let Some(block) = stack.pop() {
+                if body.insert(block) {
+                    stack.extend(
+                        graph
+                            .neighbors_directed(block, Direction::Incoming)
+                            .filter(|&pred| reachable(pred)),
+                    );
+                }
+            }
+
+            match self.blocks[index].merge {
+                Some(Merge::Loop { merge, continuing }) => {
+                    loops.push(Loop {
+                        header,
+                        body,
+                        continuing,
+                        exit: Some(merge),
+                    });
+                    continue;
+                }
+                Some(Merge::Selection(_)) => return Err(Error::UnsupportedControlFlow(header)),
+                None => {}
+            }
+
+            let [latch] = latches[..] else {
+                return Err(Error::UnsupportedControlFlow(header));
+            };
+            let mut exits = Vec::new();
+            for block in self.blocks[..block_count].iter() {
+                if body.contains(&block.label) {
+                    for &target in block.targets.iter() {
+                        if !body.contains(&target) && !exits.contains(&target) {
+                            exits.push(target);
+                        }
+                    }
+                }
+            }
+            let exit = match exits[..] {
+                [] => None,
+                [exit] => Some(exit),
+                _ => return Err(Error::UnsupportedControlFlow(header)),
+            };
+
+            // The back edge must either be unconditional or a `break if`, and
+            // the header can't be a selection header too, unless one of its
+            // targets is the loop's exit.
+            let latch_block = self.block(latch);
+            let header_block = &self.blocks[index];
+            let exits_from =
+                |block: &Block| exit.map_or(false, |exit| block.targets.contains(&exit));
+            let valid = match latch_block.kind {
+                TerminatorKind::Branch => true,
+                TerminatorKind::Conditional => exits_from(latch_block),
+                _ => false,
+            } && (latch == header
+                || match header_block.kind {
+                    TerminatorKind::Branch => true,
+                    TerminatorKind::Conditional => exits_from(header_block),
+                    _ => false,
+                });
+            if !valid {
+                return Err(Error::UnsupportedControlFlow(header));
+            }
+
+            let merge = match exit {
+                Some(exit) if !claimed.contains(&exit) && dominates(header, exit) => exit,
+                _ => self.add_block(id_bound, exit, end_offset)?,
+            };
+            self.blocks[index].insert_merge(
+                spirv::Op::LoopMerge,
+                &[merge, latch, spirv::LoopControl::NONE.bits()],
+            );
+            self.blocks[index].merge = Some(Merge::Loop {
+                merge,
+                continuing: latch,
+            });
+            mergers.extend([merge, latch]);
+            claimed.extend([merge, latch]);
+            loops.push(Loop {
+                header,
+                body,
+                continuing: latch,
+                exit,
+            });
+        }
+
+        // The innermost loop containing each block, as an index in `loops`.
+        let mut innermost = FastHashMap::<spirv::Word, usize>::default();
+        for (index, lp) in loops.iter().enumerate() {
+            for &block in lp.body.iter() {
+                let inner = innermost.entry(block).or_insert(index);
+                if loops[*inner].body.len() > lp.body.len() {
+                    *inner = index;
+                }
+            }
+        }
+
+        let mut post_dominators = FastHashMap::<Option<usize>, Dominators<spirv::Word>>::default();
+        for index in 0..block_count {
+            let block = &self.blocks[index];
+            let label = block.label;
+            if block.merge.is_some() || !reachable(label) {
+                continue;
+            }
+            match block.kind {
+                TerminatorKind::Conditional => {
+                    let is_break_or_continue = |&target: &spirv::Word| {
+                        mergers.contains(&target) || back_edges.contains(&(label, target))
+                    };
+                    if block.targets[0] == block.targets[1]
+                        || block.targets.iter().any(is_break_or_continue)
+                    {
+                        continue;
+                    }
+                }
+                TerminatorKind::Switch => {}
+                TerminatorKind::Branch | TerminatorKind::Exit => continue,
+            }
+
+            let region = innermost.get(&label).copied();
+            let region_post_dominators = post_dominators.entry(region).or_insert_with(|| {
+                post_dominators_in(
+                    &graph,
+                    region.map(|index| &loops[index]),
+                    &reachable,
+                    &back_edges,
+                    &loops,
+                )
+            });
+            let merge = match region_post_dominators.immediate_dominator(label) {
+                Some(merge)
+                    if merge != EXIT && !claimed.contains(&merge) && dominates(label, merge) =>
+                {
+                    merge
+                }
+                Some(merge) if merge != EXIT => {
+                    self.add_block(id_bound, Some(merge), end_offset)?
+                }
+                _ => self.add_block(id_bound, None, end_offset)?,
+            };
+            self.blocks[index].insert_merge(
+                spirv::Op::SelectionMerge,
+                &[merge, spirv::SelectionControl::NONE.bits()],
+            );
+            self.blocks[index].merge = Some(Merge::Selection(merge));
+            mergers.insert(merge);
+            claimed.insert(merge);
+        }
+
+        // Any block that several branches lead to must now be the end of
+        // some construct, otherwise we failed to find a structure for it.
+        for block in self.blocks[..block_count].iter() {
+            let label = block.label;
+            if !reachable(label) || claimed.contains(&label) {
+                continue;
+            }
+            let forward_predecessors = graph
+                .neighbors_directed(label, Direction::Incoming)
+                .filter(|&pred| reachable(pred) && !back_edges.contains(&(pred, label)))
+                .count();
+            if forward_predecessors > 1 {
+                return Err(Error::UnsupportedControlFlow(label));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Flatten the function back into words, for the parser to read.
+    pub(super) fn into_words(self) -> VecDeque<Word> {
+        self.blocks
+            .into_iter()
+            .flat_map(|block| block.words)
+            .chain(self.trailer)
+            .collect()
+    }
+
+    fn block(&self, label: spirv::Word) -> &Block {
+        self.blocks
+            .iter()
+            .find(|block| block.label == label)
+            .unwrap()
+    }
+
+    /// Add a new block at the end of the function that branches to `target`,
+    /// or is unreachable if there is no target, and return its label.
+    fn add_block(
+        &mut self,
+        id_bound: &mut spirv::Word,
+        target: Option<spirv::Word>,
+        offset: usize,
+    ) -> Result<spirv::Word, Error> {
+        let label = *id_bound;
+        *id_bound = label.checked_add(1).ok_or(Error::IdBoundOverflow)?;
+
+        let mut words = vec![(2 << 16) | spirv::Op::Label as u32, label];
+        let terminator_index = words.len();
+        match target {
+            Some(target) => words.extend([(2 << 16) | spirv::Op::Branch as u32, target]),
+            None => words.push((1 << 16) | spirv::Op::Unreachable as u32),
+        }
+        self.blocks.push(Block {
+            label,
+            words: words.into_iter().map(|word| (word, offset)).collect(),
+            terminator_index,
+            merge: None,
+            kind: match target {
+                Some(_) => TerminatorKind::Branch,
+                None => TerminatorKind::Exit,
+            },
+            targets: target.into_iter().collect(),
+        });
+        Ok(label)
+    }
+}
+
+/// Compute post-dominators of the blocks in `region`, or in the whole
+/// function if it is `None`.
+///
+/// Back edges are treated as branches to their loop's exit, so that loops
+/// behave like single blocks. Branches leaving the region, or continuing its
+/// loop, lead to [`EXIT`].
+fn post_dominators_in(
+    graph: &DiGraphMap<spirv::Word, ()>,
+    region: Option<&Loop>,
+    reachable: &impl Fn(spirv::Word) -> bool,
+    back_edges: &FastHashSet<(spirv::Word, spirv::Word)>,
+    loops: &[Loop],
+) -> Dominators<spirv::Word> {
+    let in_region = |block: &spirv::Word| {
+        region.map_or(true, |lp| {
+            lp.body.contains(block) && *block != lp.continuing
+        })
+    };
+
+    let mut reversed = DiGraphMap::new();
+    reversed.add_node(EXIT);
+    for block in graph.nodes() {
+        if !reachable(block) || !region.map_or(true, |lp| lp.body.contains(&block)) {
+            continue;
+        }
+        let mut has_successors = false;
+        for target in graph.neighbors(block) {
+            has_successors = true;
+            let target = if back_edges.contains(&(block, target)) {
+                loops
+                    .iter()
+                    .find(|lp| lp.header == target)
+                    .and_then(|lp| lp.exit)
+            } else {
+                Some(target)
+            };
+            let target = target.filter(in_region).unwrap_or(EXIT);
+            reversed.add_edge(target, block, ());
+        }
+        if !has_successors {
+            reversed.add_edge(EXIT, block, ());
+        }
+    }
+
+    dominators::simple_fast(&reversed, EXIT)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::front::spv::{Error, Frontend};
+
+    const VOID: u32 = 1;
+    const FUNCTION_TYPE: u32 = 2;
+    const BOOL: u32 = 3;
+    const TRUE: u32 = 4;
+    const FUNCTION: u32 = 5;
+
+    fn instruction(op: spirv::Op, operands: &[u32]) -> Vec<u32> {
+        let mut words = vec![((operands.len() as u32 + 1) << 16) | op as u32];
+        words.extend_from_slice(operands);
+        words
+    }
+
+    /// Parse a module with one function, whose body is `blocks`, each given
+    /// as its label and its terminator.
+    fn parse(bound: u32, blocks: &[(u32, spirv::Op, &[u32])]) -> Result<crate::Module, Error> {
+        let mut words = vec![spirv::MAGIC_NUMBER, 0x0001_0000, 0, bound, 0];
+        words.extend(instruction(
+            spirv::Op::Capability,
+            &[spirv::Capability::Shader as u32],
+        ));
+        words.extend(instruction(
+            spirv::Op::MemoryModel,
+            &[
+                spirv::AddressingModel::Logical as u32,
+                spirv::MemoryModel::GLSL450 as u32,
+            ],
+        ));
+        words.extend(instruction(spirv::Op::TypeVoid, &[VOID]));
+        words.extend(instruction(spirv::Op::TypeFunction, &[FUNCTION_TYPE, VOID]));
+        words.extend(instruction(spirv::Op::TypeBool, &[BOOL]));
+        words.extend(instruction(spirv::Op::ConstantTrue, &[BOOL, TRUE]));
+        words.extend(instruction(
+            spirv::Op::Function,
+            &[VOID, FUNCTION, 0, FUNCTION_TYPE],
+        ));
+        for &(label, op, operands) in blocks {
+            words.extend(instruction(spirv::Op::Label, &[label]));
+            words.extend(instruction(op, operands));
+        }
+        words.extend(instruction(spirv::Op::FunctionEnd, &[]));
+
+        Frontend::new(words.into_iter(), &Default::default()).parse()
+    }
+
+    /// An `if` whose branches both return never reaches a merge block, so we
+    /// have to add one.
+    const DIVERGING_IF: &[(u32, spirv::Op, &[u32])] = &[
+        (10, spirv::Op::BranchConditional, &[TRUE, 11, 12]),
+        (11, spirv::Op::Return, &[]),
+        (12, spirv::Op::Return, &[]),
+    ];
+
+    #[test]
+    fn synthesized_merge() {
+        let module = parse(13, DIVERGING_IF).unwrap();
+        let (_, function) = module.functions.iter().next().unwrap();
+        assert!(function
+            .body
+            .iter()
+            .any(|statement| matches!(*statement, crate::Statement::If { .. })));
+    }
+
+    #[test]
+    fn id_bound_overflow() {
+        assert!(matches!(
+            parse(u32::MAX, DIVERGING_IF),
+            Err(Error::IdBoundOverflow)
+        ));
+    }
+
+    #[test]
+    fn irreducible_loop() {
+        // Blocks 11 and 12 form a loop that can be entered at either of them.
+        let result = parse(
+            13,
+            &[
+                (10, spirv::Op::BranchConditional, &[TRUE, 11, 12]),
+                (11, spirv::Op::Branch, &[12]),
+                (12, spirv::Op::Branch, &[11]),
+            ],
+        );
+        assert!(matches!(result, Err(Error::UnsupportedControlFlow(_))));
+    }
+
+    #[test]
+    fn several_back_edges() {
+        // Blocks 11 and 12 both branch back to the loop header 10.
+        let result = parse(
+            14,
+            &[
+                (10, spirv::Op::BranchConditional, &[TRUE, 11, 13]),
+                (11, spirv::Op::BranchConditional, &[TRUE, 10, 12]),
+                (12, spirv::Op::Branch, &[10]),
+                (13, spirv::Op::Return, &[]),
+            ],
+        );
+        assert!(matches!(result, Err(Error::UnsupportedControlFlow(_))));
+    }
+}
@@ -0,0 +1,398 @@
+/*!
+Constants computed from specialization constants.
+
+Specialization constants decorated with `SpecId` become [`Override`]s, so the
+pipeline can set them. Constants computed from them, by `OpSpecConstantOp` or
+by an `OpSpecConstantComposite` with specializable components, can't be
+[`Constant`]s, and they can't be `Override`s either: overrides must be
+scalars, and have a name or id the pipeline can set them by.
+
+Instead, such a constant is an override-expression in
+[`Module::global_expressions`] that no `Constant` or `Override` owns, held by
+[`super::Constant::Expression`]. Each function that might use it gets a copy of
+the expression tree in its own arena, from [`copy_expressions`].
+
+`OpSpecConstantOp`s that only use non-specializable constants are evaluated
+right away, and become ordinary `Constant`s.
+
+[`Override`]: crate::Override
+[`Constant`]: crate::Constant
+[`Module::global_expressions`]: crate::Module::global_expressions
+*/
+
+use super::{convert::map_binary_operator, Error, Instruction, LookupHelper as _, ModuleState};
+use crate::{
+    arena::{Arena, Handle, Range},
+    proc::{ConstantEvaluator, ExpressionKindTracker},
+    BinaryOperator as Bo, Expression, FastHashMap, FastHashSet, ScalarKind as Sk,
+    UnaryOperator as Uo,
+};
+
+impl<I: Iterator<Item = u32>> super::Frontend<I> {
+    pub(super) fn parse_spec_constant_op(
+        &mut self,
+        inst: Instruction,
+        module: &mut crate::Module,
+    ) -> Result<(), Error> {
+        use spirv::Op;
+
+        let start = self.data_offset;
+        self.switch(ModuleState::Type, inst.op)?;
+        inst.expect_at_least(4)?;
+        let type_id = self.next()?;
+        let id = self.next()?;
+        let opcode = self.next()?;
+        let op = Op::from_u32(opcode).ok_or(Error::UnknownInstruction(opcode as u16))?;
+
+        // The number of operands that are ids, rather than literals.
+        let id_count = match op {
+            Op::SConvert | Op::UConvert | Op::FConvert | Op::SNegate | Op::Not | Op::LogicalNot => {
+                inst.expect(5)?;
+                1
+            }
+            Op::IAdd
+            | Op::ISub
+            | Op::IMul
+            | Op::UDiv
+            | Op::SDiv
+            | Op::UMod
+            | Op::SRem
+            | Op::ShiftRightLogical
+            | Op::ShiftRightArithmetic
+            | Op::ShiftLeftLogical
+            | Op::BitwiseOr
+            | Op::BitwiseXor
+            | Op::BitwiseAnd
+            | Op::LogicalOr
+            | Op::LogicalAnd
+            | Op::LogicalEqual
+            | Op::LogicalNotEqual
+            | Op::IEqual
+            | Op::INotEqual
+            | Op::ULessThan
+            | Op::SLessThan
+            | Op::UGreaterThan
+            | Op::SGreaterThan
+            | Op::ULessThanEqual
+            | Op::SLessThanEqual
+            | Op::UGreaterThanEqual
+            | Op::SGreaterThanEqual => {
+                inst.expect(6)?;
+                2
+            }
+            Op::Select => {
+                inst.expect(7)?;
+                3
+            }
+            Op::CompositeExtract => {
+                inst.expect_at_least(6)?;
+                1
+            }
+            Op::VectorShuffle => {
+                inst.expect_at_least(7)?;
+                2
+            }
+            _ => return Err(Error::UnsupportedSpecConstantOp(op)),
+        };
+
+        let mut operands = Vec::with_capacity(id_count);
+        for _ in 0..id_count {
+            let start = self.data_offset;
+            let operand_id = self.next()?;
+            let span = self.span_from_with_op(start);
+            let lconst = self.lookup_constant.lookup(operand_id)?;
+            let ty = self.lookup_type.lookup(lconst.type_id)?.handle;
+            let expr = lconst
+                .inner
+                .to_global_expr(&mut module.global_expressions, span);
+            operands.push((expr, module.types[ty].inner.clone()));
+        }
+        let mut literals = Vec::with_capacity(inst.wc as usize - 4 - id_count);
+        for _ in 0..literals.capacity() {
+            literals.push(self.next()?);
+        }
+        let span = self.span_from_with_op(start);
+
+        let ty = self.lookup_type.lookup(type_id)?.handle;
+        let result_scalar = module.types[ty].inner.scalar();
+        let result_kind = result_scalar.map(|scalar| scalar.kind);
+        let kind_of = |index: usize| operands[index].1.scalar_kind();
+
+        let mut tracker = ExpressionKindTracker::from_arena(&module.global_expressions);
+        let mut eval = ConstantEvaluator::for_wgsl_module(module, &mut tracker, true);
+
+        // Like the function body parser, adjust the signedness of operands to
+        // the signedness the instruction implies.
+        let result = match op {
+            Op::SConvert | Op::UConvert | Op::FConvert => {
+                let scalar = result_scalar.ok_or(Error::InvalidAsType(ty))?;
+                let expr = Expression::As {
+                    expr: operands[0].0,
+                    kind: scalar.kind,
+                    convert: Some(scalar.width),
+                };
+                eval.try_eval_and_append(expr, span)?
+            }
+            Op::SNegate | Op::Not | Op::LogicalNot => {
+                let op = match op {
+                    Op::SNegate => Uo::Negate,
+                    Op::Not => Uo::BitwiseNot,
+                    _ => Uo::LogicalNot,
+                };
+                let expr = bitcast(&mut eval, operands[0].0, kind_of(0), result_kind, span)?;
+                eval.try_eval_and_append(Expression::Unary { op, expr }, span)?
+            }
+            Op::IAdd
+            | Op::ISub
+            | Op::IMul
+            | Op::UDiv
+            | Op::SDiv
+            | Op::UMod
+            | Op::SRem
+            | Op::ShiftRightLogical
+            | Op::ShiftRightArithmetic
+            | Op::ShiftLeftLogical
+            | Op::BitwiseOr
+            | Op::BitwiseXor
+            | Op::BitwiseAnd => {
+                let (op, kind) = match op {
+                    Op::UDiv => (Bo::Divide, Some(Sk::Uint)),
+                    Op::UMod => (Bo::Modulo, Some(Sk::Uint)),
+                    Op::SDiv => (Bo::Divide, Some(Sk::Sint)),
+                    Op::SRem => (Bo::Modulo, Some(Sk::Sint)),
+                    Op::ShiftRightLogical => (Bo::ShiftRight, Some(Sk::Uint)),
+                    Op::ShiftRightArithmetic => (Bo::ShiftRight, Some(Sk::Sint)),
+                    Op::ShiftLeftLogical => (Bo::ShiftLeft, result_kind),
+                    _ => (map_binary_operator(op)?, result_kind),
+                };
+                // Shift amounts are always unsigned in Naga IR.
+                let right_kind = match op {
+                    Bo::ShiftLeft | Bo::ShiftRight => Some(Sk::Uint),
+                    _ => kind,
+                };
+                let left = bitcast(&mut eval, operands[0].0, kind_of(0), kind, span)?;
+                let right = bitcast(&mut eval, operands[1].0, kind_of(1), right_kind, span)?;
+                let value =
+                    eval.try_eval_and_append(Expression::Binary { op, left, right }, span)?;
+                bitcast(&mut eval, value, kind, result_kind, span)?
+            }
+            Op::LogicalOr
+            | Op::LogicalAnd
+            | Op::LogicalEqual
+            | Op::LogicalNotEqual
+            | Op::IEqual
+            | Op::INotEqual
+            | Op::ULessThan
+            | Op::SLessThan
+            | Op::UGreaterThan
+            | Op::SGreaterThan
+            | Op::ULessThanEqual
+            | Op::SLessThanEqual
+            | Op::UGreaterThanEqual
+            | Op::SGreaterThanEqual => {
+                let (op, kind) = match op {
+                    Op::LogicalOr => (Bo::LogicalOr, None),
+                    Op::LogicalAnd => (Bo::LogicalAnd, None),
+                    Op::ULessThan
+                    | Op::UGreaterThan
+                    | Op::ULessThanEqual
+                    | Op::UGreaterThanEqual => (map_binary_operator(op)?, Some(Sk::Uint)),
+                    Op::SLessThan
+                    | Op::SGreaterThan
+                    | Op::SLessThanEqual
+                    | Op::SGreaterThanEqual => (map_binary_operator(op)?, Some(Sk::Sint)),
+                    _ => (map_binary_operator(op)?, kind_of(0)),
+                };
+                let left = bitcast(&mut eval, operands[0].0, kind_of(0), kind, span)?;
+                let right = bitcast(&mut eval, operands[1].0, kind_of(1), kind, span)?;
+                eval.try_eval_and_append(Expression::Binary { op, left, right }, span)?
+            }
+            Op::Select => {
+                let expr = Expression::Select {
+                    condition: operands[0].0,
+                    accept: operands[1].0,
+                    reject: operands[2].0,
+                };
+                eval.try_eval_and_append(expr, span)?
+            }
+            Op::CompositeExtract => {
+                let mut base = operands[0].0;
+                for &index in literals.iter() {
+                    base =
+                        eval.try_eval_and_append(Expression::AccessIndex { base, index }, span)?;
+                }
+                base
+            }
+            Op::VectorShuffle => {
+                let n1 = match operands[0].1 {
+                    crate::TypeInner::Vector { size, .. } => size as u32,
+                    _ => return Err(Error::InvalidOperand),
+                };
+                let mut components = Vec::with_capacity(literals.len());
+                for &index in literals.iter() {
+                    // treat Undefined as X
+                    let index = if index == u32::MAX { 0 } else { index };
+                    let expr = if index < n1 {
+                        Expression::AccessIndex {
+                            base: operands[0].0,
+                            index,
+                        }
+                    } else {
+                        Expression::AccessIndex {
+                            base: operands[1].0,
+                            index: index - n1,
+                        }
+                    };
+                    components.push(eval.try_eval_and_append(expr, span)?);
+                }
+                eval.try_eval_and_append(Expression::Compose { ty, components }, span)?
+            }
+            _ => unreachable!(),
+        };
+
+        if tracker.is_const(result) {
+            self.insert_parsed_constant(module, id, type_id, ty, result, span)
+        } else {
+            self.insert_spec_expression(id, type_id, result);
+            Ok(())
+        }
+    }
+}
+
+/// Reinterpret `expr` as `to`, if that's different from its current kind `from`.
+fn bitcast(
+    eval: &mut ConstantEvaluator,
+    expr: Handle<Expression>,
+    from: Option<Sk>,
+    to: Option<Sk>,
+    span: crate::Span,
+) -> Result<Handle<Expression>, Error> {
+    match to {
+        Some(kind) if from != to => {
+            let expr = Expression::As {
+                expr,
+                kind,
+                convert: None,
+            };
+            Ok(eval.try_eval_and_append(expr, span)?)
+        }
+        _ => Ok(expr),
+    }
+}
+
+/// Copy the expression trees at `roots` from `global_expressions` into a
+/// function's `expressions`.
+///
+/// Return the copies of `roots`, and the range of new expressions that need to
+/// be emitted, if any.
+pub(super) fn copy_expressions(
+    roots: &[Handle<Expression>],
+    global_expressions: &Arena<Expression>,
+    expressions: &mut Arena<Expression>,
+) -> (Vec<Handle<Expression>>, Option<Range<Expression>>) {
+    let mut used = FastHashSet::default();
+    let mut stack = roots.to_vec();
+    while let Some(handle) = stack.pop() {
+        if used.insert(handle) {
+            map_operands(&global_expressions[handle], |operand| {
+                stack.push(operand);
+                operand
+            });
+        }
+    }
+
+    // Operands always come before the expressions using them, so copying in
+    // arena order copies them first. Expressions that don't need emitting go
+    // before the rest, to keep the range to emit contiguous.
+    let mut used: Vec<_> = used.into_iter().collect();
+    used.sort();
+    let (pre_emitted, emitted): (Vec<_>, Vec<_>) = used
+        .into_iter()
+        .partition(|&handle| global_expressions[handle].needs_pre_emit());
+
+    let mut copies = FastHashMap::default();
+    for handle in pre_emitted {
+        let span = global_expressions.get_span(handle);
+        let copy = expressions.append(global_expressions[handle].clone(), span);
+        copies.insert(handle, copy);
+    }
+    let start = expressions.len();
+    for handle in emitted {
+        let span = global_expressions.get_span(handle);
+        let expr = map_operands(&global_expressions[handle], |operand| copies[&operand]);
+        let copy = expressions.append(expr, span);
+        copies.insert(handle, copy);
+    }
+    let range = (expressions.len() > start).then(|| expressions.range_from(start));
+
+    (roots.iter().map(|root| copies[root]).collect(), range)
+}
+
+/// Return a copy of `expr` with its operands replaced by `f(operand)`.
+///
+/// This only handles the expressions `parse_spec_constant_op` and
+/// `parse_composite_constant` can produce.
+fn map_operands(
+    expr: &Expression,
+    mut f: impl FnMut(Handle<Expression>) -> Handle<Expression>,
+) -> Expression {
+    match *expr {
+        Expression::Literal(_)
+        | Expression::Constant(_)
+        | Expression::Override(_)
+        | Expression::ZeroValue(_) => expr.clone(),
+        Expression::Compose { ty, ref components } => Expression::Compose {
+            ty,
+            components: components.iter().map(|&component| f(component)).collect(),
+        },
+        Expression::Splat { size, value } => Expression::Splat {
+            size,
+            value: f(value),
+        },
+        Expression::AccessIndex { base, index } => Expression::AccessIndex {
+            base: f(base),
+            index,
+        },
+        Expression::Swizzle {
+            size,
+            vector,
+            pattern,
+        } => Expression::Swizzle {
+            size,
+            vector: f(vector),
+            pattern,
+        },
+        Expression::Unary { op, expr } => Expression::Unary { op, expr: f(expr) },
+        Expression::Binary { op, left, right } => {
+            let left = f(left);
+            Expression::Binary {
+                op,
+                left,
+                right: f(right),
+            }
+        }
+        Expression::Select {
+            condition,
+            accept,
+            reject,
+        } => {
+            let condition = f(condition);
+            let accept = f(accept);
+            Expression::Select {
+                condition,
+                accept,
+                reject: f(reject),
+            }
+        }
+        Expression::As {
+            expr,
+            kind,
+            convert,
+        } => Expression::As {
+            expr: f(expr),
+            kind,
+            convert,
+        },
+        _ => unreachable!("unexpected specialization constant expression {expr:?}"),
+    }
+}
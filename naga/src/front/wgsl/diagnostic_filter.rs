@@ -47,12 +47,20 @@ impl FilterableTriggeringRule {
 
 impl StandardFilterableTriggeringRule {
     const DERIVATIVE_UNIFORMITY: &'static str = "derivative_uniformity";
+    const UNUSED_VARIABLE: &'static str = "unused_variable";
+    const UNREACHABLE_CODE: &'static str = "unreachable_code";
+    const SHADOWED_DECLARATION: &'static str = "shadowed_declaration";
+    const PRECISION_LOSS: &'static str = "precision_loss";
 
     /// Convert from a sentinel word in WGSL into its associated
     /// [`StandardFilterableTriggeringRule`], if possible.
     pub fn from_wgsl_ident(s: &str) -> Option<Self> {
         Some(match s {
             Self::DERIVATIVE_UNIFORMITY => Self::DerivativeUniformity,
+            Self::UNUSED_VARIABLE => Self::UnusedVariable,
+            Self::UNREACHABLE_CODE => Self::UnreachableCode,
+            Self::SHADOWED_DECLARATION => Self::ShadowedDeclaration,
+            Self::PRECISION_LOSS => Self::PrecisionLoss,
             _ => return None,
         })
     }
@@ -62,6 +70,10 @@ impl StandardFilterableTriggeringRule {
     pub const fn to_wgsl_ident(self) -> &'static str {
         match self {
             Self::DerivativeUniformity => Self::DERIVATIVE_UNIFORMITY,
+            Self::UnusedVariable => Self::UNUSED_VARIABLE,
+            Self::UnreachableCode => Self::UNREACHABLE_CODE,
+            Self::ShadowedDeclaration => Self::SHADOWED_DECLARATION,
+            Self::PrecisionLoss => Self::PRECISION_LOSS,
         }
     }
 }
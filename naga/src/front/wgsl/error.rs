@@ -1,4 +1,6 @@
-use crate::diagnostic_filter::ConflictingDiagnosticRuleError;
+use crate::diagnostic_filter::{
+    ConflictingDiagnosticRuleError, Severity, StandardFilterableTriggeringRule,
+};
 use crate::front::wgsl::parse::directive::enable_extension::{
     EnableExtension, UnimplementedEnableExtension,
 };
@@ -6,11 +8,11 @@ use crate::front::wgsl::parse::directive::language_extension::{
     LanguageExtension, UnimplementedLanguageExtension,
 };
 use crate::front::wgsl::parse::lexer::Token;
-use crate::front::wgsl::Scalar;
+use crate::front::wgsl::{Scalar, SourceFiles};
 use crate::proc::{Alignment, ConstantEvaluatorError, ResolveError};
 use crate::{SourceLocation, Span};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
-use codespan_reporting::files::SimpleFile;
+use codespan_reporting::files::{SimpleFile, SimpleFiles};
 use codespan_reporting::term;
 use std::borrow::Cow;
 use std::ops::Range;
@@ -40,14 +42,23 @@ impl ParseError {
     }
 
     fn diagnostic(&self) -> Diagnostic<()> {
+        self.diagnostic_in(|span| span.to_range().map(|range| ((), range)))
+    }
+
+    /// Build a diagnostic, using `locate` to find the file and range of
+    /// each span.
+    fn diagnostic_in<F>(
+        &self,
+        locate: impl Fn(Span) -> Option<(F, Range<usize>)>,
+    ) -> Diagnostic<F> {
         let diagnostic = Diagnostic::error()
             .with_message(self.message.to_string())
             .with_labels(
                 self.labels
                     .iter()
-                    .filter_map(|label| label.0.to_range().map(|range| (label, range)))
-                    .map(|(label, range)| {
-                        Label::primary((), range).with_message(label.1.to_string())
+                    .filter_map(|label| locate(label.0).map(|location| (label, location)))
+                    .map(|(label, (file, range))| {
+                        Label::primary(file, range).with_message(label.1.to_string())
                     })
                     .collect(),
             )
@@ -96,6 +107,47 @@ impl ParseError {
         String::from_utf8(writer.into_inner()).unwrap()
     }
 
+    /// Emits a summary of the error to standard error stream, for an error
+    /// returned by [`Frontend::parse_files`].
+    ///
+    /// [`Frontend::parse_files`]: super::Frontend::parse_files
+    pub fn emit_to_stderr_with_files(&self, files: &SourceFiles) {
+        let config = term::Config::default();
+        let writer = StandardStream::stderr(ColorChoice::Auto);
+        term::emit(
+            &mut writer.lock(),
+            &config,
+            &Self::simple_files(files),
+            &self.diagnostic_in(|span| files.locate(span)),
+        )
+        .expect("cannot write error");
+    }
+
+    /// Emits a summary of the error to a string, for an error returned by
+    /// [`Frontend::parse_files`].
+    ///
+    /// [`Frontend::parse_files`]: super::Frontend::parse_files
+    pub fn emit_to_string_with_files(&self, files: &SourceFiles) -> String {
+        let config = term::Config::default();
+        let mut writer = NoColor::new(Vec::new());
+        term::emit(
+            &mut writer,
+            &config,
+            &Self::simple_files(files),
+            &self.diagnostic_in(|span| files.locate(span)),
+        )
+        .expect("cannot write error");
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    fn simple_files(files: &SourceFiles) -> SimpleFiles<&str, &str> {
+        let mut simple_files = SimpleFiles::new();
+        for (name, source) in files.iter() {
+            simple_files.add(name, source);
+        }
+        simple_files
+    }
+
     /// Returns a [`SourceLocation`] for the first label in the error message.
     pub fn location(&self, source: &str) -> Option<SourceLocation> {
         self.labels.first().map(|label| label.0.location(source))
@@ -114,6 +166,175 @@ impl std::error::Error for ParseError {
     }
 }
 
+/// The errors returned by [`Frontend::parse_all_errors`], in source order.
+///
+/// [`Frontend::parse_all_errors`]: super::Frontend::parse_all_errors
+#[derive(Clone, Debug)]
+pub struct ParseErrors {
+    pub errors: Vec<ParseError>,
+}
+
+impl ParseErrors {
+    /// Emits a summary of each error to standard error stream.
+    pub fn emit_to_stderr(&self, source: &str) {
+        self.emit_to_stderr_with_path(source, "wgsl")
+    }
+
+    /// Emits a summary of each error to standard error stream.
+    pub fn emit_to_stderr_with_path<P>(&self, source: &str, path: P)
+    where
+        P: AsRef<std::path::Path>,
+    {
+        for error in &self.errors {
+            error.emit_to_stderr_with_path(source, &path);
+        }
+    }
+
+    /// Emits a summary of each error to a string.
+    pub fn emit_to_string(&self, source: &str) -> String {
+        self.emit_to_string_with_path(source, "wgsl")
+    }
+
+    /// Emits a summary of each error to a string.
+    pub fn emit_to_string_with_path<P>(&self, source: &str, path: P) -> String
+    where
+        P: AsRef<std::path::Path>,
+    {
+        self.errors
+            .iter()
+            .map(|error| error.emit_to_string_with_path(source, &path))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for ParseErrors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseErrors {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        None
+    }
+}
+
+impl From<Vec<ParseError>> for ParseErrors {
+    fn from(errors: Vec<ParseError>) -> Self {
+        Self { errors }
+    }
+}
+
+impl From<ParseError> for ParseErrors {
+    fn from(error: ParseError) -> Self {
+        Self {
+            errors: vec![error],
+        }
+    }
+}
+
+/// A diagnostic that was reported while parsing, but did not stop the module
+/// from being built.
+///
+/// Whether a diagnostic is reported, and at which severity, is controlled by
+/// the `diagnostic` directives and attributes in effect where it was triggered.
+/// See [`Frontend::warnings`].
+///
+/// [`Frontend::warnings`]: super::Frontend::warnings
+#[derive(Clone, Debug)]
+pub struct ParseWarning {
+    /// The rule that triggered this diagnostic.
+    pub rule: StandardFilterableTriggeringRule,
+    /// Either [`Severity::Warning`] or [`Severity::Info`].
+    pub severity: Severity,
+    /// What was found, and where.
+    pub error: ParseError,
+}
+
+impl ParseWarning {
+    fn diagnostic(&self) -> Diagnostic<()> {
+        let mut diagnostic = self.error.diagnostic();
+        diagnostic.severity = match self.severity {
+            Severity::Info => codespan_reporting::diagnostic::Severity::Note,
+            // Only `Info` and `Warning` diagnostics become `ParseWarning`s.
+            Severity::Warning | Severity::Error | Severity::Off => {
+                codespan_reporting::diagnostic::Severity::Warning
+            }
+        };
+        diagnostic
+    }
+
+    /// Emits a summary of the warning to standard error stream.
+    pub fn emit_to_stderr(&self, source: &str) {
+        self.emit_to_stderr_with_path(source, "wgsl")
+    }
+
+    /// Emits a summary of the warning to standard error stream.
+    pub fn emit_to_stderr_with_path<P>(&self, source: &str, path: P)
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let path = path.as_ref().display().to_string();
+        let files = SimpleFile::new(path, source);
+        let config = term::Config::default();
+        let writer = StandardStream::stderr(ColorChoice::Auto);
+        term::emit(&mut writer.lock(), &config, &files, &self.diagnostic())
+            .expect("cannot write warning");
+    }
+
+    /// Emits a summary of the warning to a string.
+    pub fn emit_to_string(&self, source: &str) -> String {
+        self.emit_to_string_with_path(source, "wgsl")
+    }
+
+    /// Emits a summary of the warning to a string.
+    pub fn emit_to_string_with_path<P>(&self, source: &str, path: P) -> String
+    where
+        P: AsRef<std::path::Path>,
+    {
+        let path = path.as_ref().display().to_string();
+        let files = SimpleFile::new(path, source);
+        let config = term::Config::default();
+        let mut writer = NoColor::new(Vec::new());
+        term::emit(&mut writer, &config, &files, &self.diagnostic()).expect("cannot write warning");
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    /// Returns a [`SourceLocation`] for the first label in the warning.
+    pub fn location(&self, source: &str) -> Option<SourceLocation> {
+        self.error.location(source)
+    }
+}
+
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+/// A [`ParseWarning`] that has not been rendered against its source yet.
+pub(crate) struct Warning<'a> {
+    pub rule: StandardFilterableTriggeringRule,
+    pub severity: Severity,
+    pub error: Error<'a>,
+}
+
+impl<'a> Warning<'a> {
+    pub(crate) fn as_parse_warning(&self, source: &'a str) -> ParseWarning {
+        ParseWarning {
+            rule: self.rule,
+            severity: self.severity,
+            error: self.error.as_parse_error(source),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum ExpectedToken<'a> {
     Token(Token<'a>),
@@ -146,8 +367,6 @@ pub enum NumberError {
     Invalid,
     #[error("numeric literal not representable by target type")]
     NotRepresentable,
-    #[error("unimplemented f16 type")]
-    UnimplementedF16,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -201,6 +420,20 @@ pub(crate) enum Error<'a> {
     AlignAttributeTooLow(Span, Alignment),
     NonPowerOfTwoAlignAttribute(Span),
     InconsistentBinding(Span),
+    InvalidBlendSrc(Span),
+    BlendSrcOutsideStruct(Span),
+    /// A struct uses `@blend_src`, but has a location output that isn't one
+    /// of the two blend sources.
+    UnpairedBlendSrcMember {
+        r#struct: Span,
+        member: Span,
+    },
+    /// A struct uses `@blend_src`, but doesn't have a member for the source
+    /// with the given index.
+    MissingBlendSrc {
+        r#struct: Span,
+        index: u32,
+    },
     TypeNotConstructible(Span),
     TypeNotInferable(Span),
     InitializationTypeMismatch {
@@ -213,7 +446,6 @@ pub(crate) enum Error<'a> {
     InvalidAtomicPointer(Span),
     InvalidAtomicOperandType(Span),
     InvalidRayQueryPointer(Span),
-    Pointer(&'static str, Span),
     NotPointer(Span),
     NotReference(&'static str, Span),
     InvalidAssignment {
@@ -229,6 +461,15 @@ pub(crate) enum Error<'a> {
         /// Span of the identifier in the new definition.
         current: Span,
     },
+    /// Module-scope names from different files that only differ in characters
+    /// dropped from generated code.
+    NameCollision {
+        /// Span of the identifier in the earlier declaration.
+        previous: Span,
+
+        /// Span of the identifier in the later declaration.
+        current: Span,
+    },
     /// A declaration refers to itself directly.
     RecursiveDeclaration {
         /// The location of the name of the declaration.
@@ -267,7 +508,10 @@ pub(crate) enum Error<'a> {
     ExpectedConstExprConcreteIntegerScalar(Span),
     ExpectedNonNegative(Span),
     ExpectedPositiveArrayLength(Span),
+    InvalidClusterSize(Span),
     MissingWorkgroupSize(Span),
+    MissingMeshShaderAttribute(&'static str, Span),
+    UnexpectedMeshShaderAttribute(Span),
     ConstantEvaluatorError(Box<ConstantEvaluatorError>, Span),
     AutoConversion(Box<AutoConversionError>),
     AutoConversionLeafScalar(Box<AutoConversionLeafScalarError>),
@@ -306,6 +550,17 @@ pub(crate) enum Error<'a> {
         on_what_plural: &'static str,
         spans: Vec<Span>,
     },
+    ImportWithoutResolver(Span),
+    ImportFailed {
+        span: Span,
+        reason: Box<str>,
+    },
+    PrecisionLoss {
+        span: Span,
+        value: Box<str>,
+        rounded: Box<str>,
+    },
+    UnreachableCode(Span),
 }
 
 impl From<ConflictingDiagnosticRuleError> for Error<'_> {
@@ -611,6 +866,35 @@ impl<'a> Error<'a> {
                 labels: vec![(span, "input/output binding is not consistent".into())],
                 notes: vec![],
             },
+            Error::InvalidBlendSrc(span) => ParseError {
+                message: "`@blend_src` index must be 0 or 1".to_string(),
+                labels: vec![(span, "must be 0 or 1".into())],
+                notes: vec![],
+            },
+            Error::BlendSrcOutsideStruct(span) => ParseError {
+                message: "`@blend_src` can only be used on struct members".to_string(),
+                labels: vec![(span, "not a struct member".into())],
+                notes: vec![],
+            },
+            Error::UnpairedBlendSrcMember { r#struct, member } => ParseError {
+                message: format!(
+                    "struct `{}` uses `@blend_src`, so its location outputs must be exactly one `@location(0) @blend_src(0)` and one `@location(0) @blend_src(1)` member",
+                    &source[r#struct]
+                ),
+                labels: vec![
+                    (member, "not one of the two blend sources".into()),
+                    (r#struct, "struct uses `@blend_src`".into()),
+                ],
+                notes: vec![],
+            },
+            Error::MissingBlendSrc { r#struct, index } => ParseError {
+                message: format!(
+                    "struct `{}` uses `@blend_src`, but has no `@location(0) @blend_src({index})` member",
+                    &source[r#struct]
+                ),
+                labels: vec![(r#struct, format!("missing `@blend_src({index})`").into())],
+                notes: vec![],
+            },
             Error::TypeNotConstructible(span) => ParseError {
                 message: format!("type `{}` is not constructible", &source[span]),
                 labels: vec![(span, "type is not constructible".into())],
@@ -704,11 +988,6 @@ impl<'a> Error<'a> {
                     notes,
                 }
             }
-            Error::Pointer(what, span) => ParseError {
-                message: format!("{what} must not be a pointer"),
-                labels: vec![(span, "expression is a pointer".into())],
-                notes: vec![],
-            },
             Error::ReservedKeyword(name_span) => ParseError {
                 message: format!("name `{}` is a reserved keyword", &source[name_span]),
                 labels: vec![(
@@ -731,6 +1010,23 @@ impl<'a> Error<'a> {
                 ],
                 notes: vec![],
             },
+            Error::NameCollision { previous, current } => ParseError {
+                message: format!(
+                    "`{}` collides with `{}` from another file",
+                    &source[current], &source[previous]
+                ),
+                labels: vec![
+                    (current, "".into()),
+                    (
+                        previous,
+                        format!("`{}` is declared here", &source[previous]).into(),
+                    ),
+                ],
+                notes: vec![
+                    "the names only differ in characters that are dropped from generated code"
+                        .into(),
+                ],
+            },
             Error::RecursiveDeclaration { ident, usage } => ParseError {
                 message: format!("declaration of `{}` is recursive", &source[ident]),
                 labels: vec![(ident, "".into()), (usage, "uses itself here".into())],
@@ -836,6 +1132,11 @@ impl<'a> Error<'a> {
                 labels: vec![(span, "must be positive".into())],
                 notes: vec![],
             },
+            Error::InvalidClusterSize(span) => ParseError {
+                message: "cluster size must be a power of two".to_string(),
+                labels: vec![(span, "must be a power of two".into())],
+                notes: vec![],
+            },
             Error::ConstantEvaluatorError(ref e, span) => ParseError {
                 message: e.to_string(),
                 labels: vec![(span, "see msg".into())],
@@ -849,6 +1150,21 @@ impl<'a> Error<'a> {
                 )],
                 notes: vec![],
             },
+            Error::MissingMeshShaderAttribute(name, span) => ParseError {
+                message: format!("mesh shader entry point is missing a `@{name}` attribute"),
+                labels: vec![(span, format!("must be paired with a @{name} attribute").into())],
+                notes: vec![],
+            },
+            Error::UnexpectedMeshShaderAttribute(span) => ParseError {
+                message: "this attribute is only valid on task and mesh shader entry points"
+                    .to_string(),
+                labels: vec![(span, "not a task or mesh shader entry point".into())],
+                notes: vec![
+                    "`@payload` may be applied to `@task` and `@mesh` functions; \
+                    `@output_topology`, `@vertex_output` and `@primitive_output` only to `@mesh` functions"
+                        .into(),
+                ],
+            },
             Error::AutoConversion(ref error) => {
                 // destructuring ensures all fields are handled
                 let AutoConversionError {
@@ -955,6 +1271,42 @@ impl<'a> Error<'a> {
                 )
                 .into()],
             },
+            Error::ImportWithoutResolver(span) => ParseError {
+                message: "`import` directives are not enabled".to_string(),
+                labels: vec![(span, "".into())],
+                notes: vec![concat!(
+                    "imports are only supported when parsing with ",
+                    "`Frontend::parse_files` and an import resolver"
+                )
+                .into()],
+            },
+            Error::ImportFailed { span, ref reason } => ParseError {
+                message: format!("failed to import `{}`: {reason}", &source[span]),
+                labels: vec![(span, "imported here".into())],
+                notes: vec![],
+            },
+            Error::PrecisionLoss {
+                span,
+                ref value,
+                ref rounded,
+            } => ParseError {
+                message: format!("`{value}` cannot be represented exactly as `f32`"),
+                labels: vec![(span, format!("this is rounded to `{rounded}`").into())],
+                notes: vec![concat!(
+                    "This is reported because of the `precision_loss` diagnostic rule. ",
+                    "Use `diagnostic(off, precision_loss)` to allow it."
+                )
+                .into()],
+            },
+            Error::UnreachableCode(span) => ParseError {
+                message: "code is unreachable".into(),
+                labels: vec![(span, "this statement is never executed".into())],
+                notes: vec![concat!(
+                    "This is reported because of the `unreachable_code` diagnostic rule. ",
+                    "Use `diagnostic(off, unreachable_code)` to allow it."
+                )
+                .into()],
+            },
             Error::EnableExtensionNotYetImplemented { kind, span } => ParseError {
                 message: format!(
                     "the `{}` enable-extension is not yet supported",
@@ -990,7 +1342,6 @@ impl<'a> Error<'a> {
                     )
                     .into(),
                 )],
-                #[allow(irrefutable_let_patterns)]
                 notes: if let EnableExtension::Unimplemented(kind) = kind {
                     vec![format!(
                         concat!(
@@ -1002,7 +1353,10 @@ impl<'a> Error<'a> {
                         kind.tracking_issue_num()
                     )]
                 } else {
-                    vec![]
+                    vec![format!(
+                        "You can enable this extension by adding `enable {};` at the top of the shader.",
+                        kind.to_ident()
+                    )]
                 },
             },
             Error::LanguageExtensionNotYetImplemented { kind, span } => ParseError {
@@ -0,0 +1,147 @@
+/*!
+Parsing WGSL modules made up of several files.
+
+The files are stored one after another in a single string, so that spans from
+different files never overlap. [`SourceFiles::locate`] maps a span back to the
+file it came from.
+*/
+
+use crate::Span;
+use std::{fmt, ops::Range};
+
+/// A file returned by an [`ImportResolver`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportedFile {
+    /// The name of the file.
+    ///
+    /// This is used in diagnostics, to tell whether two `import`s refer to the
+    /// same file, and as the importer of any `import` directives in this file.
+    pub name: String,
+    /// The contents of the file.
+    pub source: String,
+}
+
+/// Resolves the files named by `import` directives.
+///
+/// An `import` directive names one or more files, which are parsed along with
+/// the file containing it:
+///
+/// ```wgsl
+/// import lighting, shadows;
+/// ```
+///
+/// Each file is only parsed once, no matter how often it is imported, and the
+/// declarations of all files share a single scope.
+pub trait ImportResolver: fmt::Debug + Send + Sync {
+    /// Return the file that `name` refers to.
+    ///
+    /// `importer` is the name of the file containing the `import` directive.
+    ///
+    /// The error message is reported as part of the resulting parse error.
+    fn resolve(&self, name: &str, importer: &str) -> Result<ImportedFile, String>;
+}
+
+#[derive(Clone, Debug)]
+struct SourceFile {
+    name: String,
+    range: Range<usize>,
+}
+
+/// A set of named WGSL source files to be parsed into a single module.
+///
+/// Spans in the [`Module`] and in [`ParseError`]s refer to
+/// [`SourceFiles::text`], which holds all of the files one after another.
+///
+/// [`Module`]: crate::Module
+/// [`ParseError`]: super::ParseError
+#[derive(Clone, Debug, Default)]
+pub struct SourceFiles {
+    text: String,
+    files: Vec<SourceFile>,
+}
+
+impl SourceFiles {
+    pub const fn new() -> Self {
+        SourceFiles {
+            text: String::new(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Add a file, and return its index.
+    ///
+    /// The directives of the first file apply to the whole module, while
+    /// those of the other files only apply to their own declarations.
+    pub fn add(&mut self, name: impl Into<String>, source: &str) -> usize {
+        let start = self.text.len();
+        self.text.push_str(source);
+        let range = start..self.text.len();
+        // Keep the next file from starting on the last line of this one.
+        if !source.ends_with('\n') {
+            self.text.push('\n');
+        }
+        self.files.push(SourceFile {
+            name: name.into(),
+            range,
+        });
+        self.files.len() - 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Return the index of the file called `name`.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.files.iter().position(|file| file.name == name)
+    }
+
+    pub fn name(&self, index: usize) -> &str {
+        &self.files[index].name
+    }
+
+    pub fn source(&self, index: usize) -> &str {
+        &self.text[self.files[index].range.clone()]
+    }
+
+    /// Return all of the files, one after another.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Return the name and source of each file.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, &str)> + '_ {
+        self.files
+            .iter()
+            .map(|file| (file.name.as_str(), &self.text[file.range.clone()]))
+    }
+
+    /// Map a span in [`SourceFiles::text`] back to the file it came from.
+    ///
+    /// Returns the index of the file and the byte range in that file.
+    pub fn locate(&self, span: Span) -> Option<(usize, Range<usize>)> {
+        let range = span.to_range()?;
+        let index = self
+            .files
+            .partition_point(|file| file.range.start <= range.start)
+            .checked_sub(1)?;
+        let file = &self.files[index];
+        let end = range.end.min(file.range.end).max(range.start);
+        Some((
+            index,
+            range.start - file.range.start..end - file.range.start,
+        ))
+    }
+
+    pub(super) fn ranges(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.files.iter().map(|file| file.range.clone())
+    }
+
+    pub(super) fn range(&self, index: usize) -> Range<usize> {
+        self.files[index].range.clone()
+    }
+}
@@ -1,5 +1,6 @@
-use super::Error;
+use super::{Error, SourceFiles};
 use crate::front::wgsl::parse::ast;
+use crate::proc::Namer;
 use crate::{FastHashMap, Handle, Span};
 
 /// A `GlobalDecl` list in which each definition occurs before all its uses.
@@ -47,6 +48,34 @@ impl<'a> Index<'a> {
         Ok(Self { dependency_order })
     }
 
+    /// Reject module-scope names from different files that would collide in
+    /// generated code.
+    ///
+    /// [`Index::generate`] already rejects identical names. This catches names
+    /// that the [`Namer`] would only tell apart by a numeric suffix, like
+    /// `light` and `light_`.
+    pub fn check_file_collisions(
+        tu: &ast::TranslationUnit<'a>,
+        files: &SourceFiles,
+    ) -> Result<(), Error<'a>> {
+        let idents: Vec<_> = tu
+            .decls
+            .iter()
+            .filter_map(|(_, decl)| decl_ident(decl))
+            .collect();
+        let names = idents.iter().map(|ident| {
+            let file = files.locate(ident.span).map_or(0, |(file, _)| file);
+            (file, ident.name)
+        });
+        match Namer::default().find_collision(names) {
+            Some((previous, current)) => Err(Error::NameCollision {
+                previous: idents[previous].span,
+                current: idents[current].span,
+            }),
+            None => Ok(()),
+        }
+    }
+
     /// Iterate over `GlobalDecl`s, visiting each definition before all its uses.
     ///
     /// Produce handles for all of the `GlobalDecl`s of the `TranslationUnit`
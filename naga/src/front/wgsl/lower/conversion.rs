@@ -1,5 +1,6 @@
 //! WGSL's automatic conversions for abstract types.
 
+use crate::diagnostic_filter::{DiagnosticFilterNode, StandardFilterableTriggeringRule};
 use crate::front::wgsl::error::{
     AutoConversionError, AutoConversionLeafScalarError, ConcretizationFailedError,
 };
@@ -117,6 +118,7 @@ impl<'source> super::ExpressionContext<'source, '_, '_> {
         expr_span: Span,
         goal_scalar: crate::Scalar,
     ) -> Result<Handle<crate::Expression>, super::Error<'source>> {
+        self.check_precision_loss(expr, goal_scalar)?;
         let expr_inner = super::resolve_inner!(self, expr);
         if let crate::TypeInner::Array { .. } = *expr_inner {
             self.as_const_evaluator()
@@ -204,6 +206,7 @@ impl<'source> super::ExpressionContext<'source, '_, '_> {
         // Do nothing if `inner` doesn't even have leaf scalars;
         // it's a type error that validation will catch.
         if inner.scalar() != Some(goal) {
+            self.check_precision_loss(*expr, goal)?;
             let cast = crate::Expression::As {
                 expr: *expr,
                 kind: goal.kind,
@@ -250,6 +253,7 @@ impl<'source> super::ExpressionContext<'source, '_, '_> {
             let concretized = scalar.concretize();
             if concretized != scalar {
                 assert!(scalar.is_abstract());
+                self.check_precision_loss(expr, concretized)?;
                 let expr_span = self.get_expression_span(expr);
                 expr = self
                     .as_const_evaluator()
@@ -272,6 +276,83 @@ impl<'source> super::ExpressionContext<'source, '_, '_> {
         Ok(expr)
     }
 
+    /// Report the `precision_loss` diagnostic if converting `expr` to `goal`
+    /// rounds any of its abstract values.
+    ///
+    /// Only conversions to `f32` are checked. Conversions to other concrete
+    /// types either preserve the value or are errors.
+    fn check_precision_loss(
+        &mut self,
+        expr: Handle<crate::Expression>,
+        goal: crate::Scalar,
+    ) -> Result<(), super::Error<'source>> {
+        if goal != crate::Scalar::F32 {
+            return Ok(());
+        }
+        let severity = DiagnosticFilterNode::search(
+            self.diagnostic_filter_leaf(),
+            &self.module.diagnostic_filters,
+            StandardFilterableTriggeringRule::PrecisionLoss,
+        );
+        if severity == crate::diagnostic_filter::Severity::Off {
+            return Ok(());
+        }
+        let Some((value, rounded)) = self.find_precision_loss(expr) else {
+            return Ok(());
+        };
+        let error = super::Error::PrecisionLoss {
+            span: self.get_expression_span(expr),
+            value: value.into(),
+            rounded: rounded.into(),
+        };
+        severity.report_diag(error, |error, level| {
+            // The source isn't available here, so we can't render the error as
+            // a `ParseError` when logging it.
+            if let super::Error::PrecisionLoss {
+                span,
+                ref value,
+                ref rounded,
+            } = error
+            {
+                log::log!(
+                    level,
+                    "`{value}` at {span:?} cannot be represented exactly as `f32`, and is rounded to `{rounded}`"
+                );
+            }
+            self.warnings.push(super::Warning {
+                rule: StandardFilterableTriggeringRule::PrecisionLoss,
+                severity,
+                error,
+            });
+        })
+    }
+
+    /// Return the first abstract value in `expr` that `f32` can't represent
+    /// exactly, along with the value it is rounded to.
+    fn find_precision_loss(&self, expr: Handle<crate::Expression>) -> Option<(String, String)> {
+        use crate::Expression as E;
+        use crate::Literal as L;
+
+        match *self.get_expression(expr) {
+            E::Literal(L::AbstractFloat(value)) => {
+                let rounded = value as f32;
+                // Rust prints the shortest decimal that rounds to the same
+                // `f32`, so this only fails if some precision is lost.
+                (rounded.to_string().parse::<f64>() != Ok(value))
+                    .then(|| (value.to_string(), rounded.to_string()))
+            }
+            E::Literal(L::AbstractInt(value)) => {
+                let rounded = value as f32;
+                (rounded as f64 != value as f64).then(|| (value.to_string(), rounded.to_string()))
+            }
+            E::Compose { ref components, .. } => components
+                .iter()
+                .find_map(|&component| self.find_precision_loss(component)),
+            E::Splat { value, .. } => self.find_precision_loss(value),
+            _ => None,
+        }
+    }
+
     /// Find the consensus scalar of `components` under WGSL's automatic
     /// conversions.
     ///
@@ -1,6 +1,7 @@
 use std::num::NonZeroU32;
 
-use crate::front::wgsl::error::{Error, ExpectedToken, InvalidAssignmentType};
+use crate::diagnostic_filter::{DiagnosticFilterNode, StandardFilterableTriggeringRule};
+use crate::front::wgsl::error::{Error, ExpectedToken, InvalidAssignmentType, Warning};
 use crate::front::wgsl::index::Index;
 use crate::front::wgsl::parse::number::Number;
 use crate::front::wgsl::parse::{ast, conv};
@@ -88,6 +89,13 @@ pub struct GlobalContext<'source, 'temp, 'out> {
     const_typifier: &'temp mut Typifier,
 
     global_expression_kind_tracker: &'temp mut crate::proc::ExpressionKindTracker,
+
+    /// Diagnostics reported at [`Severity::Warning`] or [`Severity::Info`],
+    /// owned by `Lowerer::lower`.
+    ///
+    /// [`Severity::Warning`]: crate::diagnostic_filter::Severity::Warning
+    /// [`Severity::Info`]: crate::diagnostic_filter::Severity::Info
+    warnings: &'temp mut Vec<Warning<'source>>,
 }
 
 impl<'source> GlobalContext<'source, '_, '_> {
@@ -100,6 +108,7 @@ impl<'source> GlobalContext<'source, '_, '_> {
             const_typifier: self.const_typifier,
             expr_type: ExpressionContextType::Constant(None),
             global_expression_kind_tracker: self.global_expression_kind_tracker,
+            warnings: self.warnings,
         }
     }
 
@@ -112,6 +121,7 @@ impl<'source> GlobalContext<'source, '_, '_> {
             const_typifier: self.const_typifier,
             expr_type: ExpressionContextType::Override,
             global_expression_kind_tracker: self.global_expression_kind_tracker,
+            warnings: self.warnings,
         }
     }
 
@@ -182,6 +192,13 @@ pub struct StatementContext<'source, 'temp, 'out> {
     /// the code for lowering `let` bindings.
     local_expression_kind_tracker: &'temp mut crate::proc::ExpressionKindTracker,
     global_expression_kind_tracker: &'temp mut crate::proc::ExpressionKindTracker,
+
+    /// Diagnostics reported at [`Severity::Warning`] or [`Severity::Info`],
+    /// owned by `Lowerer::lower`.
+    ///
+    /// [`Severity::Warning`]: crate::diagnostic_filter::Severity::Warning
+    /// [`Severity::Info`]: crate::diagnostic_filter::Severity::Info
+    warnings: &'temp mut Vec<Warning<'source>>,
 }
 
 impl<'a, 'temp> StatementContext<'a, 'temp, '_> {
@@ -199,6 +216,7 @@ impl<'a, 'temp> StatementContext<'a, 'temp, '_> {
             ast_expressions: self.ast_expressions,
             const_typifier: self.const_typifier,
             global_expression_kind_tracker: self.global_expression_kind_tracker,
+            warnings: self.warnings,
             module: self.module,
             expr_type: ExpressionContextType::Constant(Some(LocalExpressionContext {
                 local_table: self.local_table,
@@ -225,6 +243,7 @@ impl<'a, 'temp> StatementContext<'a, 'temp, '_> {
             ast_expressions: self.ast_expressions,
             const_typifier: self.const_typifier,
             global_expression_kind_tracker: self.global_expression_kind_tracker,
+            warnings: self.warnings,
             module: self.module,
             expr_type: ExpressionContextType::Runtime(LocalExpressionContext {
                 local_table: self.local_table,
@@ -245,6 +264,7 @@ impl<'a, 'temp> StatementContext<'a, 'temp, '_> {
             module: self.module,
             const_typifier: self.const_typifier,
             global_expression_kind_tracker: self.global_expression_kind_tracker,
+            warnings: self.warnings,
         }
     }
 
@@ -366,6 +386,13 @@ pub struct ExpressionContext<'source, 'temp, 'out> {
     const_typifier: &'temp mut Typifier,
     global_expression_kind_tracker: &'temp mut crate::proc::ExpressionKindTracker,
 
+    /// Diagnostics reported at [`Severity::Warning`] or [`Severity::Info`],
+    /// owned by `Lowerer::lower`.
+    ///
+    /// [`Severity::Warning`]: crate::diagnostic_filter::Severity::Warning
+    /// [`Severity::Info`]: crate::diagnostic_filter::Severity::Info
+    warnings: &'temp mut Vec<Warning<'source>>,
+
     /// Whether we are lowering a constant expression or a general
     /// runtime expression, and the data needed in each case.
     expr_type: ExpressionContextType<'temp, 'out>,
@@ -381,6 +408,7 @@ impl<'source, 'temp, 'out> ExpressionContext<'source, 'temp, 'out> {
             module: self.module,
             expr_type: ExpressionContextType::Constant(None),
             global_expression_kind_tracker: self.global_expression_kind_tracker,
+            warnings: self.warnings,
         }
     }
 
@@ -392,6 +420,7 @@ impl<'source, 'temp, 'out> ExpressionContext<'source, 'temp, 'out> {
             module: self.module,
             const_typifier: self.const_typifier,
             global_expression_kind_tracker: self.global_expression_kind_tracker,
+            warnings: self.warnings,
         }
     }
 
@@ -476,6 +505,28 @@ impl<'source, 'temp, 'out> ExpressionContext<'source, 'temp, 'out> {
         }
     }
 
+    fn get_expression(&self, handle: Handle<crate::Expression>) -> &crate::Expression {
+        match self.expr_type {
+            ExpressionContextType::Runtime(ref ctx)
+            | ExpressionContextType::Constant(Some(ref ctx)) => &ctx.function.expressions[handle],
+            ExpressionContextType::Constant(None) | ExpressionContextType::Override => {
+                &self.module.global_expressions[handle]
+            }
+        }
+    }
+
+    /// Return the innermost diagnostic filter in effect for the expressions
+    /// we are lowering.
+    fn diagnostic_filter_leaf(&self) -> Option<Handle<DiagnosticFilterNode>> {
+        match self.expr_type {
+            ExpressionContextType::Runtime(ref ctx)
+            | ExpressionContextType::Constant(Some(ref ctx)) => ctx.function.diagnostic_filter_leaf,
+            ExpressionContextType::Constant(None) | ExpressionContextType::Override => {
+                self.module.diagnostic_filter_leaf
+            }
+        }
+    }
+
     fn typifier(&self) -> &Typifier {
         match self.expr_type {
             ExpressionContextType::Runtime(ref ctx)
@@ -746,6 +797,24 @@ impl<'source, 'temp, 'out> ExpressionContext<'source, 'temp, 'out> {
         }
     }
 
+    /// Implicitly dereference `expr` if it is a pointer.
+    ///
+    /// The `pointer_composite_access` language extension lets member and
+    /// index accesses apply directly to pointers: `p.field` and `p[i]` mean
+    /// `(*p).field` and `(*p)[i]`. If `expr` is a pointer value, return it as
+    /// a reference. Otherwise, return `expr` unchanged.
+    fn deref_pointer_composite(
+        &mut self,
+        expr: Typed<Handle<crate::Expression>>,
+    ) -> Result<Typed<Handle<crate::Expression>>, Error<'source>> {
+        if let Typed::Plain(handle) = expr {
+            if resolve_inner!(self, handle).pointer_space().is_some() {
+                return Ok(Typed::Reference(handle));
+            }
+        }
+        Ok(expr)
+    }
+
     fn ensure_type_exists(&mut self, inner: crate::TypeInner) -> Handle<crate::Type> {
         self.as_global().ensure_type_exists(None, inner)
     }
@@ -980,6 +1049,8 @@ enum SubgroupGather {
     ShuffleDown,
     ShuffleUp,
     ShuffleXor,
+    QuadBroadcast,
+    QuadSwap(crate::Direction),
 }
 
 impl SubgroupGather {
@@ -991,6 +1062,10 @@ impl SubgroupGather {
             "subgroupShuffleDown" => Self::ShuffleDown,
             "subgroupShuffleUp" => Self::ShuffleUp,
             "subgroupShuffleXor" => Self::ShuffleXor,
+            "quadBroadcast" => Self::QuadBroadcast,
+            "quadSwapX" => Self::QuadSwap(crate::Direction::X),
+            "quadSwapY" => Self::QuadSwap(crate::Direction::Y),
+            "quadSwapDiagonal" => Self::QuadSwap(crate::Direction::Diagonal),
             _ => return None,
         })
     }
@@ -1009,9 +1084,12 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         }
     }
 
+    /// Lower `tu` to a Naga IR module, adding any warnings reported along
+    /// the way to `warnings`.
     pub fn lower(
         &mut self,
         tu: &'temp ast::TranslationUnit<'source>,
+        warnings: &mut Vec<Warning<'source>>,
     ) -> Result<crate::Module, Error<'source>> {
         let mut module = crate::Module {
             diagnostic_filters: tu.diagnostic_filters.clone(),
@@ -1026,6 +1104,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
             module: &mut module,
             const_typifier: &mut Typifier::new(),
             global_expression_kind_tracker: &mut crate::proc::ExpressionKindTracker::new(),
+            warnings,
         };
 
         for decl_handle in self.index.visit_ordered() {
@@ -1259,7 +1338,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 Ok(crate::FunctionArgument {
                     name: Some(arg.name.name.to_string()),
                     ty,
-                    binding: self.binding(&arg.binding, ty, ctx)?,
+                    binding: self.non_member_binding(&arg.binding, ty, ctx)?,
                 })
             })
             .collect::<Result<Vec<_>, _>>()?;
@@ -1271,7 +1350,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 let ty = self.resolve_ast_type(res.ty, ctx)?;
                 Ok(crate::FunctionResult {
                     ty,
-                    binding: self.binding(&res.binding, ty, ctx)?,
+                    binding: self.non_member_binding(&res.binding, ty, ctx)?,
                 })
             })
             .transpose()?;
@@ -1300,6 +1379,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
             module: ctx.module,
             local_expression_kind_tracker: &mut local_expression_kind_tracker,
             global_expression_kind_tracker: ctx.global_expression_kind_tracker,
+            warnings: ctx.warnings,
         };
         let mut body = self.block(&f.body, false, &mut stmt_ctx)?;
         ensure_block_returns(&mut body);
@@ -1324,12 +1404,36 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                 [0; 3]
             };
 
+            let mesh_info = match entry.mesh_output {
+                Some(ref output) => Some(crate::MeshStageInfo {
+                    topology: output.topology,
+                    max_vertices: self.const_u32(output.max_vertices, &mut ctx.as_const())?.0,
+                    max_primitives: self
+                        .const_u32(output.max_primitives, &mut ctx.as_const())?
+                        .0,
+                    vertex_output_type: self.resolve_ast_type(output.vertex_type, ctx)?,
+                    primitive_output_type: self.resolve_ast_type(output.primitive_type, ctx)?,
+                }),
+                None => None,
+            };
+
+            let task_payload = match entry.task_payload {
+                Some(ref ident) => match ctx.globals.get(ident.name) {
+                    Some(&LoweredGlobalDecl::Var(handle)) => Some(handle),
+                    Some(_) => return Err(Error::Unexpected(ident.span, ExpectedToken::Variable)),
+                    None => return Err(Error::UnknownIdent(ident.span, ident.name)),
+                },
+                None => None,
+            };
+
             ctx.module.entry_points.push(crate::EntryPoint {
                 name: f.name.name.to_string(),
                 stage: entry.stage,
                 early_depth_test: entry.early_depth_test,
                 workgroup_size,
                 function,
+                mesh_info,
+                task_payload,
             });
             Ok(LoweredGlobalDecl::EntryPoint)
         } else {
@@ -1346,8 +1450,57 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
     ) -> Result<crate::Block, Error<'source>> {
         let mut block = crate::Block::default();
 
-        for stmt in b.stmts.iter() {
+        let mut stmts = b.stmts.iter().peekable();
+        for stmt in stmts.by_ref() {
             self.statement(stmt, &mut block, is_inside_loop, ctx)?;
+            if block.last().is_some_and(ends_block) {
+                break;
+            }
+        }
+
+        // Naga IR doesn't allow statements after one that ends the block, but
+        // they still need to be checked for errors. Lower them into a copy of
+        // the function, so that they, and the expressions and local variables
+        // they introduce, are dropped afterwards.
+        if let Some(next) = stmts.peek() {
+            let severity = DiagnosticFilterNode::search(
+                ctx.function.diagnostic_filter_leaf,
+                &ctx.module.diagnostic_filters,
+                StandardFilterableTriggeringRule::UnreachableCode,
+            );
+            let span = next.span;
+            severity.report_diag(Error::UnreachableCode(span), |error, level| {
+                log::log!(level, "unreachable statement at {span:?}");
+                ctx.warnings.push(Warning {
+                    rule: StandardFilterableTriggeringRule::UnreachableCode,
+                    severity,
+                    error,
+                });
+            })?;
+
+            let mut local_table = ctx.local_table.clone();
+            let mut typifier = ctx.typifier.clone();
+            let mut function = ctx.function.clone();
+            let mut named_expressions = ctx.named_expressions.clone();
+            let mut local_expression_kind_tracker = ctx.local_expression_kind_tracker.clone();
+            let mut unreachable_ctx = StatementContext {
+                local_table: &mut local_table,
+                globals: ctx.globals,
+                ast_expressions: ctx.ast_expressions,
+                const_typifier: ctx.const_typifier,
+                typifier: &mut typifier,
+                function: &mut function,
+                named_expressions: &mut named_expressions,
+                types: ctx.types,
+                module: ctx.module,
+                local_expression_kind_tracker: &mut local_expression_kind_tracker,
+                global_expression_kind_tracker: ctx.global_expression_kind_tracker,
+                warnings: ctx.warnings,
+            };
+            let mut unreachable = crate::Block::default();
+            for stmt in stmts {
+                self.statement(stmt, &mut unreachable, is_inside_loop, &mut unreachable_ctx)?;
+            }
         }
 
         Ok(block)
@@ -1836,6 +1989,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         let expr: Typed<crate::Expression> = match *expr {
             ast::Expression::Literal(literal) => {
                 let literal = match literal {
+                    ast::Literal::Number(Number::F16(f)) => crate::Literal::F16(f),
                     ast::Literal::Number(Number::F32(f)) => crate::Literal::F32(f),
                     ast::Literal::Number(Number::I32(i)) => crate::Literal::I32(i),
                     ast::Literal::Number(Number::U32(u)) => crate::Literal::U32(u),
@@ -1932,17 +2086,9 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
             }
             ast::Expression::Index { base, index } => {
                 let lowered_base = self.expression_for_reference(base, ctx)?;
+                let lowered_base = ctx.deref_pointer_composite(lowered_base)?;
                 let index = self.expression(index, ctx)?;
 
-                if let Typed::Plain(handle) = lowered_base {
-                    if resolve_inner!(ctx, handle).pointer_space().is_some() {
-                        return Err(Error::Pointer(
-                            "the value indexed by a `[]` subscripting expression",
-                            ctx.ast_expressions.get_span(base),
-                        ));
-                    }
-                }
-
                 lowered_base.map(|base| match ctx.const_access(index) {
                     Some(index) => crate::Expression::AccessIndex { base, index },
                     None => crate::Expression::Access { base, index },
@@ -1950,6 +2096,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
             }
             ast::Expression::Member { base, ref field } => {
                 let lowered_base = self.expression_for_reference(base, ctx)?;
+                let lowered_base = ctx.deref_pointer_composite(lowered_base)?;
 
                 let temp_inner;
                 let composite_type: &crate::TypeInner = match lowered_base {
@@ -1977,18 +2124,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                         }
                     }
 
-                    Typed::Plain(handle) => {
-                        let inner = resolve_inner!(ctx, handle);
-                        if let crate::TypeInner::Pointer { .. }
-                        | crate::TypeInner::ValuePointer { .. } = *inner
-                        {
-                            return Err(Error::Pointer(
-                                "the value accessed by a `.member` expression",
-                                ctx.ast_expressions.get_span(base),
-                            ));
-                        }
-                        inner
-                    }
+                    Typed::Plain(handle) => resolve_inner!(ctx, handle),
                 };
 
                 let access = match *composite_type {
@@ -2203,6 +2339,30 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                     args.finish()?;
 
                     crate::Expression::Derivative { axis, ctrl, expr }
+                } else if let Some(
+                    fun @ (crate::MathFunction::Dot4I8Packed | crate::MathFunction::Dot4U8Packed),
+                ) = conv::map_standard_fun(function.name)
+                {
+                    // Unlike most math functions, the packed dot products take `u32`
+                    // arguments, so abstract integers must be converted to `u32`
+                    // rather than concretized to `i32`.
+                    let goal = crate::proc::TypeResolution::Value(crate::TypeInner::Scalar(
+                        crate::Scalar::U32,
+                    ));
+                    let mut args = ctx.prepare_args(arguments, 2, span);
+                    let arg = self.expression_for_abstract(args.next()?, ctx)?;
+                    let arg = ctx.try_automatic_conversions(arg, &goal, span)?;
+                    let arg1 = self.expression_for_abstract(args.next()?, ctx)?;
+                    let arg1 = ctx.try_automatic_conversions(arg1, &goal, span)?;
+                    args.finish()?;
+
+                    crate::Expression::Math {
+                        fun,
+                        arg,
+                        arg1: Some(arg1),
+                        arg2: None,
+                        arg3: None,
+                    }
                 } else if let Some(fun) = conv::map_standard_fun(function.name) {
                     let expected = fun.argument_count() as _;
                     let mut args = ctx.prepare_args(arguments, expected, span);
@@ -2378,6 +2538,59 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                                 .push(crate::Statement::Barrier(crate::Barrier::SUB_GROUP), span);
                             return Ok(None);
                         }
+                        "setMeshOutputs" => {
+                            // Abstract integer counts are converted to `u32`.
+                            let goal = crate::proc::TypeResolution::Value(
+                                crate::TypeInner::Scalar(crate::Scalar::U32),
+                            );
+                            let mut args = ctx.prepare_args(arguments, 2, span);
+                            let vertex_count = self.expression_for_abstract(args.next()?, ctx)?;
+                            let vertex_count =
+                                ctx.try_automatic_conversions(vertex_count, &goal, span)?;
+                            let primitive_count =
+                                self.expression_for_abstract(args.next()?, ctx)?;
+                            let primitive_count =
+                                ctx.try_automatic_conversions(primitive_count, &goal, span)?;
+                            args.finish()?;
+
+                            let rctx = ctx.runtime_expression_ctx(span)?;
+                            rctx.block
+                                .extend(rctx.emitter.finish(&rctx.function.expressions));
+                            rctx.emitter.start(&rctx.function.expressions);
+                            rctx.block.push(
+                                crate::Statement::MeshFunction(
+                                    crate::MeshFunction::SetMeshOutputs {
+                                        vertex_count,
+                                        primitive_count,
+                                    },
+                                ),
+                                span,
+                            );
+                            return Ok(None);
+                        }
+                        "setVertex" | "setPrimitive" => {
+                            // An abstract integer index is converted to `u32`.
+                            let goal = crate::proc::TypeResolution::Value(
+                                crate::TypeInner::Scalar(crate::Scalar::U32),
+                            );
+                            let mut args = ctx.prepare_args(arguments, 2, span);
+                            let index = self.expression_for_abstract(args.next()?, ctx)?;
+                            let index = ctx.try_automatic_conversions(index, &goal, span)?;
+                            let value = self.expression(args.next()?, ctx)?;
+                            args.finish()?;
+
+                            let fun = if function.name == "setVertex" {
+                                crate::MeshFunction::SetVertex { index, value }
+                            } else {
+                                crate::MeshFunction::SetPrimitive { index, value }
+                            };
+                            let rctx = ctx.runtime_expression_ctx(span)?;
+                            rctx.block
+                                .extend(rctx.emitter.finish(&rctx.function.expressions));
+                            rctx.emitter.start(&rctx.function.expressions);
+                            rctx.block.push(crate::Statement::MeshFunction(fun), span);
+                            return Ok(None);
+                        }
                         "workgroupUniformLoad" => {
                             let mut args = ctx.prepare_args(arguments, 1, span);
                             let expr = args.next()?;
@@ -2574,6 +2787,49 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
                                 committed: true,
                             }
                         }
+                        "rayQueryGetCandidateIntersection" => {
+                            let mut args = ctx.prepare_args(arguments, 1, span);
+                            let query = self.ray_query_pointer(args.next()?, ctx)?;
+                            args.finish()?;
+
+                            let _ = ctx.module.generate_ray_intersection_type();
+
+                            crate::Expression::RayQueryGetIntersection {
+                                query,
+                                committed: false,
+                            }
+                        }
+                        "rayQueryConfirmIntersection" => {
+                            let mut args = ctx.prepare_args(arguments, 1, span);
+                            let query = self.ray_query_pointer(args.next()?, ctx)?;
+                            args.finish()?;
+
+                            let fun = crate::RayQueryFunction::ConfirmIntersection;
+
+                            let rctx = ctx.runtime_expression_ctx(span)?;
+                            rctx.block
+                                .extend(rctx.emitter.finish(&rctx.function.expressions));
+                            rctx.emitter.start(&rctx.function.expressions);
+                            rctx.block
+                                .push(crate::Statement::RayQuery { query, fun }, span);
+                            return Ok(None);
+                        }
+                        "rayQueryGenerateIntersection" => {
+                            let mut args = ctx.prepare_args(arguments, 2, span);
+                            let query = self.ray_query_pointer(args.next()?, ctx)?;
+                            let hit_t = self.expression(args.next()?, ctx)?;
+                            args.finish()?;
+
+                            let fun = crate::RayQueryFunction::GenerateIntersection { hit_t };
+
+                            let rctx = ctx.runtime_expression_ctx(span)?;
+                            rctx.block
+                                .extend(rctx.emitter.finish(&rctx.function.expressions));
+                            rctx.emitter.start(&rctx.function.expressions);
+                            rctx.block
+                                .push(crate::Statement::RayQuery { query, fun }, span);
+                            return Ok(None);
+                        }
                         "RayDesc" => {
                             let ty = ctx.module.generate_ray_desc_type();
                             let handle = self.construct(
@@ -2817,9 +3073,23 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         arguments: &[Handle<ast::Expression<'source>>],
         ctx: &mut ExpressionContext<'source, '_, '_>,
     ) -> Result<Handle<crate::Expression>, Error<'source>> {
-        let mut args = ctx.prepare_args(arguments, 1, span);
+        let arg_count = match collective_op {
+            crate::CollectiveOperation::ClusteredReduce(_) => 2,
+            _ => 1,
+        };
+        let mut args = ctx.prepare_args(arguments, arg_count, span);
 
         let argument = self.expression(args.next()?, ctx)?;
+        let collective_op = match collective_op {
+            crate::CollectiveOperation::ClusteredReduce(_) => {
+                let (size, size_span) = self.const_u32(args.next()?, &mut ctx.as_const())?;
+                if !size.is_power_of_two() {
+                    return Err(Error::InvalidClusterSize(size_span));
+                }
+                crate::CollectiveOperation::ClusteredReduce(size)
+            }
+            other => other,
+        };
         args.finish()?;
 
         let ty = ctx.register_type(argument)?;
@@ -2851,17 +3121,28 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         let argument = self.expression(args.next()?, ctx)?;
 
         use SubgroupGather as Sg;
-        let mode = if let Sg::BroadcastFirst = mode {
-            crate::GatherMode::BroadcastFirst
-        } else {
-            let index = self.expression(args.next()?, ctx)?;
-            match mode {
-                Sg::Broadcast => crate::GatherMode::Broadcast(index),
-                Sg::Shuffle => crate::GatherMode::Shuffle(index),
-                Sg::ShuffleDown => crate::GatherMode::ShuffleDown(index),
-                Sg::ShuffleUp => crate::GatherMode::ShuffleUp(index),
-                Sg::ShuffleXor => crate::GatherMode::ShuffleXor(index),
-                Sg::BroadcastFirst => unreachable!(),
+        let mode = match mode {
+            Sg::BroadcastFirst => crate::GatherMode::BroadcastFirst,
+            Sg::QuadSwap(direction) => crate::GatherMode::QuadSwap(direction),
+            _ => {
+                let index_expr = args.next()?;
+                let index = self.expression(index_expr, ctx)?;
+                match mode {
+                    Sg::Broadcast => crate::GatherMode::Broadcast(index),
+                    Sg::Shuffle => crate::GatherMode::Shuffle(index),
+                    Sg::ShuffleDown => crate::GatherMode::ShuffleDown(index),
+                    Sg::ShuffleUp => crate::GatherMode::ShuffleUp(index),
+                    Sg::ShuffleXor => crate::GatherMode::ShuffleXor(index),
+                    Sg::QuadBroadcast => {
+                        if ctx.const_access(index).is_none() {
+                            return Err(Error::ExpectedConstExprConcreteIntegerScalar(
+                                ctx.ast_expressions.get_span(index_expr),
+                            ));
+                        }
+                        crate::GatherMode::QuadBroadcast(index)
+                    }
+                    Sg::BroadcastFirst | Sg::QuadSwap(_) => unreachable!(),
+                }
             }
         };
 
@@ -2892,6 +3173,7 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         let mut offset = 0;
         let mut struct_alignment = Alignment::ONE;
         let mut members = Vec::with_capacity(s.members.len());
+        let mut uses_blend_src = false;
 
         for member in s.members.iter() {
             let ty = self.resolve_ast_type(member.ty, ctx)?;
@@ -2929,6 +3211,13 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
 
             let binding = self.binding(&member.binding, ty, ctx)?;
 
+            if let Some(ast::Binding::Location {
+                blend_src: Some(_), ..
+            }) = member.binding
+            {
+                uses_blend_src = true;
+            }
+
             offset = member_alignment.round_up(offset);
             struct_alignment = struct_alignment.max(member_alignment);
 
@@ -2942,6 +3231,10 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
             offset += member_size;
         }
 
+        if uses_blend_src {
+            Self::check_blend_src_pair(s, &members)?;
+        }
+
         let size = struct_alignment.round_up(offset);
         let inner = crate::TypeInner::Struct {
             members,
@@ -2958,6 +3251,51 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         Ok(handle)
     }
 
+    /// Check that a struct using `@blend_src` has exactly the two blend
+    /// sources as its location outputs, both at location 0.
+    ///
+    /// The IR only records whether a location is the second blend source, so
+    /// this has to be checked while we can still see the `@blend_src`
+    /// attributes.
+    fn check_blend_src_pair(
+        s: &ast::Struct<'source>,
+        members: &[crate::StructMember],
+    ) -> Result<(), Error<'source>> {
+        let mut sources = [false; 2];
+        for (ast_member, member) in s.members.iter().zip(members) {
+            let Some(crate::Binding::Location {
+                location,
+                second_blend_source,
+                ..
+            }) = member.binding
+            else {
+                continue;
+            };
+            let is_blend_src = matches!(
+                ast_member.binding,
+                Some(ast::Binding::Location {
+                    blend_src: Some(_),
+                    ..
+                })
+            );
+            let index = second_blend_source as usize;
+            if !is_blend_src || location != 0 || sources[index] {
+                return Err(Error::UnpairedBlendSrcMember {
+                    r#struct: s.name.span,
+                    member: ast_member.name.span,
+                });
+            }
+            sources[index] = true;
+        }
+        if let Some(index) = sources.iter().position(|&found| !found) {
+            return Err(Error::MissingBlendSrc {
+                r#struct: s.name.span,
+                index: index as u32,
+            });
+        }
+        Ok(())
+    }
+
     fn const_u32(
         &mut self,
         expr: Handle<ast::Expression<'source>>,
@@ -3109,6 +3447,26 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
         self.resolve_named_ast_type(handle, None, ctx)
     }
 
+    /// Like [`binding`](Self::binding), for bindings outside of structs,
+    /// where `@blend_src` is not allowed.
+    fn non_member_binding(
+        &mut self,
+        binding: &Option<ast::Binding<'source>>,
+        ty: Handle<crate::Type>,
+        ctx: &mut GlobalContext<'source, '_, '_>,
+    ) -> Result<Option<crate::Binding>, Error<'source>> {
+        if let Some(ast::Binding::Location {
+            blend_src: Some(blend_src),
+            ..
+        }) = *binding
+        {
+            return Err(Error::BlendSrcOutsideStruct(
+                ctx.ast_expressions.get_span(blend_src),
+            ));
+        }
+        self.binding(binding, ty, ctx)
+    }
+
     fn binding(
         &mut self,
         binding: &Option<ast::Binding<'source>>,
@@ -3119,15 +3477,33 @@ impl<'source, 'temp> Lowerer<'source, 'temp> {
             Some(ast::Binding::BuiltIn(b)) => Some(crate::Binding::BuiltIn(b)),
             Some(ast::Binding::Location {
                 location,
+                blend_src,
                 second_blend_source,
                 interpolation,
                 sampling,
+                per_primitive,
             }) => {
+                // `@blend_src(1)` marks the second input of the blending
+                // equation, which is what the IR's `second_blend_source` means.
+                let second_blend_source = match blend_src {
+                    Some(blend_src) => match self.const_u32(blend_src, &mut ctx.as_const())? {
+                        (0, _) => false,
+                        (1, _) => true,
+                        (_, span) => return Err(Error::InvalidBlendSrc(span)),
+                    },
+                    None => second_blend_source,
+                };
+                // Per-primitive values are never interpolated.
+                let interpolation = match interpolation {
+                    None if per_primitive => Some(crate::Interpolation::Flat),
+                    _ => interpolation,
+                };
                 let mut binding = crate::Binding::Location {
                     location: self.const_u32(location, &mut ctx.as_const())?.0,
                     second_blend_source,
                     interpolation,
                     sampling,
+                    per_primitive,
                 };
                 binding.apply_default_interpolation(&ctx.module.types[ty].inner);
                 Some(binding)
@@ -3175,3 +3551,14 @@ impl crate::AtomicFunction {
         })
     }
 }
+
+/// Return true if `statement` leaves the block it is in, so that any
+/// statements following it are unreachable.
+fn ends_block(statement: &crate::Statement) -> bool {
+    use crate::Statement as S;
+    match *statement {
+        S::Return { .. } | S::Kill | S::Break | S::Continue => true,
+        S::Block(ref block) => block.last().is_some_and(ends_block),
+        _ => false,
+    }
+}
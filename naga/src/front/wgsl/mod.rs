@@ -6,6 +6,7 @@ Frontend for [WGSL][wgsl] (WebGPU Shading Language).
 
 mod diagnostic_filter;
 mod error;
+mod import;
 mod index;
 mod lower;
 mod parse;
@@ -17,31 +18,157 @@ use crate::front::wgsl::error::Error;
 use crate::front::wgsl::parse::Parser;
 use thiserror::Error;
 
-pub use crate::front::wgsl::error::ParseError;
+pub use crate::front::wgsl::error::{ParseError, ParseErrors, ParseWarning};
+pub use crate::front::wgsl::import::{ImportResolver, ImportedFile, SourceFiles};
 use crate::front::wgsl::lower::Lowerer;
 use crate::Scalar;
 
 pub struct Frontend {
     parser: Parser,
+    warnings: Vec<ParseWarning>,
 }
 
 impl Frontend {
     pub const fn new() -> Self {
         Self {
             parser: Parser::new(),
+            warnings: Vec::new(),
         }
     }
 
+    /// Return the warnings reported by the last call to one of the `parse`
+    /// methods, in the order they were found.
+    ///
+    /// Whether a diagnostic is reported as a warning, at all, or as an error
+    /// that fails parsing, is controlled by the `diagnostic` directives and
+    /// attributes in effect where it was triggered.
+    pub fn warnings(&self) -> &[ParseWarning] {
+        &self.warnings
+    }
+
     pub fn parse(&mut self, source: &str) -> Result<crate::Module, ParseError> {
         self.inner(source).map_err(|x| x.as_parse_error(source))
     }
 
     fn inner<'a>(&mut self, source: &'a str) -> Result<crate::Module, Error<'a>> {
+        self.warnings.clear();
         let tu = self.parser.parse(source)?;
         let index = index::Index::generate(&tu)?;
-        let module = Lowerer::new(&index).lower(&tu)?;
+        self.lower(&tu, &index, source)
+    }
+
+    /// Lower `tu`, recording the warnings reported along the way.
+    fn lower<'a>(
+        &mut self,
+        tu: &parse::ast::TranslationUnit<'a>,
+        index: &index::Index<'a>,
+        source: &'a str,
+    ) -> Result<crate::Module, Error<'a>> {
+        let mut warnings = Vec::new();
+        let module = Lowerer::new(index).lower(tu, &mut warnings);
+        self.warnings = warnings
+            .iter()
+            .map(|warning| warning.as_parse_warning(source))
+            .collect();
+        module
+    }
+
+    /// Parse `source`, reporting as many errors as possible.
+    ///
+    /// Unlike [`Frontend::parse`], which stops at the first error, this
+    /// recovers from syntax errors by skipping to the next statement or
+    /// global declaration, and reports all of them. Errors that can only be
+    /// found once the whole module has been parsed, like unresolved names or
+    /// type mismatches, are only reported if there were no syntax errors,
+    /// and then only the first one.
+    pub fn parse_all_errors(&mut self, source: &str) -> Result<crate::Module, ParseErrors> {
+        self.inner_all_errors(source).map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|x| x.as_parse_error(source))
+                .collect::<Vec<_>>()
+                .into()
+        })
+    }
+
+    fn inner_all_errors<'a>(&mut self, source: &'a str) -> Result<crate::Module, Vec<Error<'a>>> {
+        self.warnings.clear();
+        let tu = self.parser.parse_all_errors(source)?;
+        let index = index::Index::generate(&tu).map_err(|error| vec![error])?;
+        self.lower(&tu, &index, source).map_err(|error| vec![error])
+    }
+
+    /// Parse several files into a single module.
+    ///
+    /// The declarations of all files share a single scope, so they can refer
+    /// to each other. Declaring the same name in two files is an error, as is
+    /// declaring names that only differ in characters dropped from generated
+    /// code, like `light` and `light_`.
+    ///
+    /// If `resolver` is given, `import` directives are allowed, and the files
+    /// they name are added to `files`. Spans in the module and in the error
+    /// refer to [`SourceFiles::text`]; use [`ParseError::emit_to_string_with_files`]
+    /// to report errors against the file they occur in.
+    pub fn parse_files(
+        &mut self,
+        files: &mut SourceFiles,
+        resolver: Option<&dyn ImportResolver>,
+    ) -> Result<crate::Module, ParseError> {
+        self.warnings.clear();
+        if let Some(resolver) = resolver {
+            self.resolve_imports(files, resolver)?;
+        }
+        let source = files.text();
+        self.inner_files(files, resolver.is_some())
+            .map_err(|x| x.as_parse_error(source))
+    }
+
+    fn inner_files<'a>(
+        &mut self,
+        files: &'a SourceFiles,
+        allow_imports: bool,
+    ) -> Result<crate::Module, Error<'a>> {
+        let tu = self
+            .parser
+            .parse_files(files.text(), files.ranges(), allow_imports)?;
+        let index = index::Index::generate(&tu)?;
+        index::Index::check_file_collisions(&tu, files)?;
+        self.lower(&tu, &index, files.text())
+    }
 
-        Ok(module)
+    /// Add the files imported by `files`, directly or indirectly, to `files`.
+    fn resolve_imports(
+        &mut self,
+        files: &mut SourceFiles,
+        resolver: &dyn ImportResolver,
+    ) -> Result<(), ParseError> {
+        let mut next = 0;
+        while next < files.len() {
+            let source = files.text();
+            let imports = self
+                .parser
+                .imports(source, files.range(next))
+                .map_err(|x| x.as_parse_error(source))?
+                .into_iter()
+                .map(|(name, span)| (name.to_string(), span))
+                .collect::<Vec<_>>();
+            for (name, span) in imports {
+                let file = resolver
+                    .resolve(&name, files.name(next))
+                    .map_err(|reason| {
+                        Error::ImportFailed {
+                            span,
+                            reason: reason.into(),
+                        }
+                        .as_parse_error(files.text())
+                    })?;
+                if files.find(&file.name).is_none() {
+                    files.add(file.name, &file.source);
+                }
+            }
+            next += 1;
+        }
+        Ok(())
     }
 }
 
@@ -60,6 +187,14 @@ pub fn parse_str(source: &str) -> Result<crate::Module, ParseError> {
     Frontend::new().parse(source)
 }
 
+/// Like [`parse_str`], but report every syntax error rather than just the
+/// first. See [`Frontend::parse_all_errors`].
+///
+/// The stack space caveats of [`parse_str`] apply here too.
+pub fn parse_all_errors(source: &str) -> Result<crate::Module, ParseErrors> {
+    Frontend::new().parse_all_errors(source)
+}
+
 #[cfg(test)]
 #[track_caller]
 pub fn assert_parse_err(input: &str, snapshot: &str) {
@@ -121,6 +121,19 @@ pub struct EntryPoint<'a> {
     pub stage: crate::ShaderStage,
     pub early_depth_test: Option<crate::EarlyDepthTest>,
     pub workgroup_size: Option<[Option<Handle<Expression<'a>>>; 3]>,
+    pub mesh_output: Option<MeshOutput<'a>>,
+    /// The global named by `@payload`, for task and mesh shaders.
+    pub task_payload: Option<Ident<'a>>,
+}
+
+/// The outputs declared by a `@mesh` entry point's attributes.
+#[derive(Debug)]
+pub struct MeshOutput<'a> {
+    pub topology: crate::MeshOutputTopology,
+    pub vertex_type: Handle<Type<'a>>,
+    pub max_vertices: Handle<Expression<'a>>,
+    pub primitive_type: Handle<Type<'a>>,
+    pub max_primitives: Handle<Expression<'a>>,
 }
 
 #[cfg(doc)]
@@ -141,9 +154,12 @@ pub enum Binding<'a> {
     BuiltIn(crate::BuiltIn),
     Location {
         location: Handle<Expression<'a>>,
+        /// The index given by `@blend_src`, if present.
+        blend_src: Option<Handle<Expression<'a>>>,
         second_blend_source: bool,
         interpolation: Option<crate::Interpolation>,
         sampling: Option<crate::Sampling>,
+        per_primitive: bool,
     },
 }
 
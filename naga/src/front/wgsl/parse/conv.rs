@@ -1,8 +1,14 @@
-use super::Error;
-use crate::front::wgsl::Scalar;
+use crate::front::wgsl::parse::directive::enable_extension::{
+    EnableExtension, EnableExtensions, ImplementedEnableExtension,
+};
+use crate::front::wgsl::{Error, Scalar};
 use crate::Span;
 
-pub fn map_address_space(word: &str, span: Span) -> Result<crate::AddressSpace, Error<'_>> {
+pub fn map_address_space<'a>(
+    enable_extensions: &EnableExtensions,
+    word: &'a str,
+    span: Span,
+) -> Result<crate::AddressSpace, Error<'a>> {
     match word {
         "private" => Ok(crate::AddressSpace::Private),
         "workgroup" => Ok(crate::AddressSpace::WorkGroup),
@@ -12,17 +18,31 @@ pub fn map_address_space(word: &str, span: Span) -> Result<crate::AddressSpace,
         }),
         "push_constant" => Ok(crate::AddressSpace::PushConstant),
         "function" => Ok(crate::AddressSpace::Function),
+        "task_payload" => {
+            if !enable_extensions.contains(ImplementedEnableExtension::WgpuMeshShader) {
+                return Err(Error::EnableExtensionNotEnabled {
+                    kind: EnableExtension::Implemented(ImplementedEnableExtension::WgpuMeshShader),
+                    span,
+                });
+            }
+            Ok(crate::AddressSpace::TaskPayload)
+        }
         _ => Err(Error::UnknownAddressSpace(span)),
     }
 }
 
-pub fn map_built_in(word: &str, span: Span) -> Result<crate::BuiltIn, Error<'_>> {
-    Ok(match word {
+pub fn map_built_in<'a>(
+    enable_extensions: &EnableExtensions,
+    word: &'a str,
+    span: Span,
+) -> Result<crate::BuiltIn, Error<'a>> {
+    let built_in = match word {
         "position" => crate::BuiltIn::Position { invariant: false },
         // vertex
         "vertex_index" => crate::BuiltIn::VertexIndex,
         "instance_index" => crate::BuiltIn::InstanceIndex,
         "view_index" => crate::BuiltIn::ViewIndex,
+        "clip_distances" => crate::BuiltIn::ClipDistance,
         // fragment
         "front_facing" => crate::BuiltIn::FrontFacing,
         "frag_depth" => crate::BuiltIn::FragDepth,
@@ -40,8 +60,35 @@ pub fn map_built_in(word: &str, span: Span) -> Result<crate::BuiltIn, Error<'_>>
         "subgroup_id" => crate::BuiltIn::SubgroupId,
         "subgroup_size" => crate::BuiltIn::SubgroupSize,
         "subgroup_invocation_id" => crate::BuiltIn::SubgroupInvocationId,
+        // task
+        "mesh_task_size" => crate::BuiltIn::MeshTaskSize,
+        // mesh
+        "point_index" => crate::BuiltIn::PointIndex,
+        "line_indices" => crate::BuiltIn::LineIndices,
+        "triangle_indices" => crate::BuiltIn::TriangleIndices,
+        "cull_primitive" => crate::BuiltIn::CullPrimitive,
         _ => return Err(Error::UnknownBuiltin(span)),
-    })
+    };
+
+    let required_extension = match built_in {
+        crate::BuiltIn::ClipDistance => Some(ImplementedEnableExtension::ClipDistances),
+        crate::BuiltIn::MeshTaskSize
+        | crate::BuiltIn::PointIndex
+        | crate::BuiltIn::LineIndices
+        | crate::BuiltIn::TriangleIndices
+        | crate::BuiltIn::CullPrimitive => Some(ImplementedEnableExtension::WgpuMeshShader),
+        _ => None,
+    };
+    if let Some(extension) = required_extension {
+        if !enable_extensions.contains(extension) {
+            return Err(Error::EnableExtensionNotEnabled {
+                kind: EnableExtension::Implemented(extension),
+                span,
+            });
+        }
+    }
+
+    Ok(built_in)
 }
 
 pub fn map_interpolation(word: &str, span: Span) -> Result<crate::Interpolation, Error<'_>> {
@@ -53,6 +100,18 @@ pub fn map_interpolation(word: &str, span: Span) -> Result<crate::Interpolation,
     }
 }
 
+pub fn map_mesh_output_topology(
+    word: &str,
+    span: Span,
+) -> Result<crate::MeshOutputTopology, Error<'_>> {
+    match word {
+        "points" => Ok(crate::MeshOutputTopology::Points),
+        "lines" => Ok(crate::MeshOutputTopology::Lines),
+        "triangles" => Ok(crate::MeshOutputTopology::Triangles),
+        _ => Err(Error::UnknownAttribute(span)),
+    }
+}
+
 pub fn map_sampling(word: &str, span: Span) -> Result<crate::Sampling, Error<'_>> {
     match word {
         "center" => Ok(crate::Sampling::Center),
@@ -111,10 +170,17 @@ pub fn map_storage_format(word: &str, span: Span) -> Result<crate::StorageFormat
     })
 }
 
-pub fn get_scalar_type(word: &str) -> Option<Scalar> {
+pub fn get_scalar_type(
+    enable_extensions: &EnableExtensions,
+    span: Span,
+    word: &str,
+) -> Result<Option<Scalar>, Error<'static>> {
     use crate::ScalarKind as Sk;
-    match word {
-        // "f16" => Some(Scalar { kind: Sk::Float, width: 2 }),
+    let scalar = match word {
+        "f16" => Some(Scalar {
+            kind: Sk::Float,
+            width: 2,
+        }),
         "f32" => Some(Scalar {
             kind: Sk::Float,
             width: 4,
@@ -144,6 +210,36 @@ pub fn get_scalar_type(word: &str) -> Option<Scalar> {
             width: crate::BOOL_WIDTH,
         }),
         _ => None,
+    };
+
+    if matches!(scalar, Some(Scalar::F16))
+        && !enable_extensions.contains(ImplementedEnableExtension::F16)
+    {
+        return Err(Error::EnableExtensionNotEnabled {
+            kind: EnableExtension::Implemented(ImplementedEnableExtension::F16),
+            span,
+        });
+    }
+
+    Ok(scalar)
+}
+
+/// Map the `f16` shorthand type names, `vecNh` and `matCxRh`, to their shape.
+///
+/// Returns `(None, size)` for a vector and `(Some(columns), rows)` for a
+/// matrix.
+pub fn map_f16_shorthand(word: &str) -> Option<(Option<crate::VectorSize>, crate::VectorSize)> {
+    use crate::VectorSize as Vs;
+    let size = |c: u8| match c {
+        b'2' => Some(Vs::Bi),
+        b'3' => Some(Vs::Tri),
+        b'4' => Some(Vs::Quad),
+        _ => None,
+    };
+    match *word.as_bytes() {
+        [b'v', b'e', b'c', n, b'h'] => Some((None, size(n)?)),
+        [b'm', b'a', b't', c, b'x', r, b'h'] => Some((Some(size(c)?), size(r)?)),
+        _ => None,
     }
 }
 
@@ -213,6 +309,8 @@ pub fn map_standard_fun(word: &str) -> Option<crate::MathFunction> {
         "pow" => Mf::Pow,
         // geometry
         "dot" => Mf::Dot,
+        "dot4I8Packed" => Mf::Dot4I8Packed,
+        "dot4U8Packed" => Mf::Dot4U8Packed,
         "cross" => Mf::Cross,
         "distance" => Mf::Distance,
         "length" => Mf::Length,
@@ -292,6 +390,14 @@ pub fn map_subgroup_operation(
         "subgroupExclusiveMul" => (sg::Mul, co::ExclusiveScan),
         "subgroupInclusiveAdd" => (sg::Add, co::InclusiveScan),
         "subgroupInclusiveMul" => (sg::Mul, co::InclusiveScan),
+        // The cluster size is an argument, so it is filled in by the caller.
+        "subgroupClusteredAdd" => (sg::Add, co::ClusteredReduce(0)),
+        "subgroupClusteredMul" => (sg::Mul, co::ClusteredReduce(0)),
+        "subgroupClusteredMin" => (sg::Min, co::ClusteredReduce(0)),
+        "subgroupClusteredMax" => (sg::Max, co::ClusteredReduce(0)),
+        "subgroupClusteredAnd" => (sg::And, co::ClusteredReduce(0)),
+        "subgroupClusteredOr" => (sg::Or, co::ClusteredReduce(0)),
+        "subgroupClusteredXor" => (sg::Xor, co::ClusteredReduce(0)),
         _ => return None,
     })
 }
@@ -13,6 +13,14 @@ pub(crate) enum DirectiveKind {
     Diagnostic,
     /// An [`enable_extension`].
     Enable,
+    /// An import of other WGSL files, resolved by an [`ImportResolver`].
+    ///
+    /// This is not part of the WGSL specification, and is only accepted by
+    /// [`Frontend::parse_files`] when given a resolver.
+    ///
+    /// [`ImportResolver`]: crate::front::wgsl::ImportResolver
+    /// [`Frontend::parse_files`]: crate::front::wgsl::Frontend::parse_files
+    Import,
     /// A [`language_extension`].
     Requires,
 }
@@ -20,6 +28,7 @@ pub(crate) enum DirectiveKind {
 impl DirectiveKind {
     const DIAGNOSTIC: &'static str = "diagnostic";
     const ENABLE: &'static str = "enable";
+    const IMPORT: &'static str = "import";
     const REQUIRES: &'static str = "requires";
 
     /// Convert from a sentinel word in WGSL into its associated [`DirectiveKind`], if possible.
@@ -27,6 +36,7 @@ impl DirectiveKind {
         Some(match s {
             Self::DIAGNOSTIC => Self::Diagnostic,
             Self::ENABLE => Self::Enable,
+            Self::IMPORT => Self::Import,
             Self::REQUIRES => Self::Requires,
             _ => return None,
         })
@@ -85,6 +95,19 @@ error: expected global declaration, but found a global directive
   │
   = note: global directives are only allowed before global declarations; maybe hoist this closer to the top of the shader module?
 
+";
+                }
+                DirectiveKind::Import => {
+                    directive = "import lighting";
+                    expected_msg = "\
+error: expected global declaration, but found a global directive
+  ┌─ wgsl:2:1
+  │
+2 │ import lighting;
+  │ ^^^^^^ written after first global declaration
+  │
+  = note: global directives are only allowed before global declarations; maybe hoist this closer to the top of the shader module?
+
 ";
                 }
                 DirectiveKind::Requires => {
@@ -7,7 +7,6 @@
 /// WGSL spec.: <https://www.w3.org/TR/WGSL/#language-extensions-sec>
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub(crate) enum LanguageExtension {
-    #[allow(unused)]
     Implemented(ImplementedLanguageExtension),
     Unimplemented(UnimplementedLanguageExtension),
 }
@@ -22,17 +21,17 @@ impl LanguageExtension {
     /// Convert from a sentinel word in WGSL into its associated [`LanguageExtension`], if possible.
     pub fn from_ident(s: &str) -> Option<Self> {
         Some(match s {
-            Self::READONLY_AND_READWRITE_STORAGE_TEXTURES => Self::Unimplemented(
-                UnimplementedLanguageExtension::ReadOnlyAndReadWriteStorageTextures,
-            ),
+            Self::READONLY_AND_READWRITE_STORAGE_TEXTURES => {
+                Self::Implemented(ImplementedLanguageExtension::ReadOnlyAndReadWriteStorageTextures)
+            }
             Self::PACKED4X8_INTEGER_DOT_PRODUCT => {
-                Self::Unimplemented(UnimplementedLanguageExtension::Packed4x8IntegerDotProduct)
+                Self::Implemented(ImplementedLanguageExtension::Packed4x8IntegerDotProduct)
             }
             Self::UNRESTRICTED_POINTER_PARAMETERS => {
-                Self::Unimplemented(UnimplementedLanguageExtension::UnrestrictedPointerParameters)
+                Self::Implemented(ImplementedLanguageExtension::UnrestrictedPointerParameters)
             }
             Self::POINTER_COMPOSITE_ACCESS => {
-                Self::Unimplemented(UnimplementedLanguageExtension::PointerCompositeAccess)
+                Self::Implemented(ImplementedLanguageExtension::PointerCompositeAccess)
             }
             _ => return None,
         })
@@ -41,45 +40,54 @@ impl LanguageExtension {
     /// Maps this [`LanguageExtension`] into the sentinel word associated with it in WGSL.
     pub const fn to_ident(self) -> &'static str {
         match self {
-            Self::Implemented(kind) => match kind {},
-            Self::Unimplemented(kind) => match kind {
-                UnimplementedLanguageExtension::ReadOnlyAndReadWriteStorageTextures => {
+            Self::Implemented(kind) => match kind {
+                ImplementedLanguageExtension::ReadOnlyAndReadWriteStorageTextures => {
                     Self::READONLY_AND_READWRITE_STORAGE_TEXTURES
                 }
-                UnimplementedLanguageExtension::Packed4x8IntegerDotProduct => {
+                ImplementedLanguageExtension::Packed4x8IntegerDotProduct => {
                     Self::PACKED4X8_INTEGER_DOT_PRODUCT
                 }
-                UnimplementedLanguageExtension::UnrestrictedPointerParameters => {
+                ImplementedLanguageExtension::UnrestrictedPointerParameters => {
                     Self::UNRESTRICTED_POINTER_PARAMETERS
                 }
-                UnimplementedLanguageExtension::PointerCompositeAccess => {
+                ImplementedLanguageExtension::PointerCompositeAccess => {
                     Self::POINTER_COMPOSITE_ACCESS
                 }
             },
+            Self::Unimplemented(kind) => match kind {},
         }
     }
 }
 
 /// A variant of [`LanguageExtension::Implemented`].
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub(crate) enum ImplementedLanguageExtension {}
-
-/// A variant of [`LanguageExtension::Unimplemented`].
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
-pub(crate) enum UnimplementedLanguageExtension {
+pub(crate) enum ImplementedLanguageExtension {
+    /// Enables `read` and `read_write` access modes for storage textures.
+    ///
+    /// WGSL spec.: <https://www.w3.org/TR/WGSL/#language_extension-readonly_and_readwrite_storage_textures>
     ReadOnlyAndReadWriteStorageTextures,
+    /// Provides the `dot4I8Packed` and `dot4U8Packed` built-in functions.
+    ///
+    /// WGSL spec.: <https://www.w3.org/TR/WGSL/#language_extension-packed_4x8_integer_dot_product>
     Packed4x8IntegerDotProduct,
+    /// Allows pointers in the `storage`, `uniform` and `workgroup` address
+    /// spaces to be passed as function arguments.
+    ///
+    /// WGSL spec.: <https://www.w3.org/TR/WGSL/#language_extension-unrestricted_pointer_parameters>
     UnrestrictedPointerParameters,
+    /// Allows member and index accesses directly on pointers, as in `p.field`
+    /// and `p[i]`.
+    ///
+    /// WGSL spec.: <https://www.w3.org/TR/WGSL/#language_extension-pointer_composite_access>
     PointerCompositeAccess,
 }
 
+/// A variant of [`LanguageExtension::Unimplemented`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum UnimplementedLanguageExtension {}
+
 impl UnimplementedLanguageExtension {
     pub(crate) const fn tracking_issue_num(self) -> u16 {
-        match self {
-            Self::ReadOnlyAndReadWriteStorageTextures => 6204,
-            Self::Packed4x8IntegerDotProduct => 6445,
-            Self::UnrestrictedPointerParameters => 5158,
-            Self::PointerCompositeAccess => 6192,
-        }
+        match self {}
     }
 }
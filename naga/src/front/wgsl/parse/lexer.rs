@@ -1,9 +1,12 @@
 use super::{number::consume_number, Error, ExpectedToken};
 use crate::front::wgsl::error::NumberError;
-use crate::front::wgsl::parse::directive::enable_extension::EnableExtensions;
+use crate::front::wgsl::parse::directive::enable_extension::{
+    EnableExtension, EnableExtensions, ImplementedEnableExtension,
+};
 use crate::front::wgsl::parse::{conv, Number};
 use crate::front::wgsl::Scalar;
 use crate::Span;
+use std::ops::Range;
 
 type TokenSpan<'a> = (Token<'a>, Span);
 
@@ -205,7 +208,6 @@ pub(in crate::front::wgsl) struct Lexer<'a> {
     pub(in crate::front::wgsl) source: &'a str,
     // The byte offset of the end of the last non-trivia token.
     last_end_offset: usize,
-    #[allow(dead_code)]
     pub(in crate::front::wgsl) enable_extensions: EnableExtensions,
 }
 
@@ -219,6 +221,36 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Create a lexer for the part of `source` in `range`.
+    ///
+    /// Spans are still relative to the start of `source`, so that spans from
+    /// files stored one after another in `source` don't overlap.
+    pub(in crate::front::wgsl) fn with_range(source: &'a str, range: Range<usize>) -> Self {
+        Lexer {
+            input: &source[range.clone()],
+            source: &source[..range.end],
+            last_end_offset: range.start,
+            ..Self::new(source)
+        }
+    }
+
+    /// Return an error if `extension` has not been enabled with an `enable`
+    /// directive. `span` is the location of the construct requiring it.
+    pub(in crate::front::wgsl) const fn require_enable_extension(
+        &self,
+        extension: ImplementedEnableExtension,
+        span: Span,
+    ) -> Result<(), Error<'static>> {
+        if self.enable_extensions.contains(extension) {
+            Ok(())
+        } else {
+            Err(Error::EnableExtensionNotEnabled {
+                kind: EnableExtension::Implemented(extension),
+                span,
+            })
+        }
+    }
+
     /// Calls the function with a lexer and returns the result of the function as well as the span for everything the function parsed
     ///
     /// # Examples
@@ -397,7 +429,8 @@ impl<'a> Lexer<'a> {
         self.expect_generic_paren('<')?;
         let pair = match self.next() {
             (Token::Word(word), span) => {
-                conv::get_scalar_type(word).ok_or(Error::UnknownScalarType(span))
+                conv::get_scalar_type(&self.enable_extensions, span, word)?
+                    .ok_or(Error::UnknownScalarType(span))
             }
             (_, span) => Err(Error::UnknownScalarType(span)),
         }?;
@@ -413,9 +446,11 @@ impl<'a> Lexer<'a> {
     ) -> Result<(Scalar, Span), Error<'a>> {
         self.expect_generic_paren('<')?;
         let pair = match self.next() {
-            (Token::Word(word), span) => conv::get_scalar_type(word)
-                .map(|scalar| (scalar, span))
-                .ok_or(Error::UnknownScalarType(span)),
+            (Token::Word(word), span) => {
+                conv::get_scalar_type(&self.enable_extensions, span, word)?
+                    .map(|scalar| (scalar, span))
+                    .ok_or(Error::UnknownScalarType(span))
+            }
             (_, span) => Err(Error::UnknownScalarType(span)),
         }?;
         self.expect_generic_paren('>')?;
@@ -501,14 +536,14 @@ fn test_numbers() {
             Token::Number(Ok(Number::AbstractFloat(0.01))),
             Token::Number(Ok(Number::AbstractFloat(12.34))),
             Token::Number(Ok(Number::F32(0.))),
-            Token::Number(Err(NumberError::UnimplementedF16)),
+            Token::Number(Ok(Number::F16(half::f16::from_f32(0.)))),
             Token::Number(Ok(Number::AbstractFloat(0.001))),
             Token::Number(Ok(Number::AbstractFloat(43.75))),
             Token::Number(Ok(Number::F32(16.))),
             Token::Number(Ok(Number::AbstractFloat(0.1875))),
-            Token::Number(Err(NumberError::UnimplementedF16)),
+            Token::Number(Ok(Number::F16(half::f16::from_f32(12.)))),
             Token::Number(Ok(Number::AbstractFloat(0.12109375))),
-            Token::Number(Err(NumberError::UnimplementedF16)),
+            Token::Number(Ok(Number::F16(half::f16::from_f32(12.5)))),
         ],
     );
 
@@ -641,6 +676,38 @@ fn double_floats() {
     )
 }
 
+#[test]
+fn half_floats() {
+    sub_test(
+        "0x1.2p4h 0x1p-24h 0.5h 65504h 0x1.ffcp15h 10h",
+        &[
+            Token::Number(Ok(Number::F16(half::f16::from_f32(18.0)))),
+            Token::Number(Ok(Number::F16(half::f16::from_bits(1)))),
+            Token::Number(Ok(Number::F16(half::f16::from_f32(0.5)))),
+            Token::Number(Ok(Number::F16(half::f16::MAX))),
+            Token::Number(Ok(Number::F16(half::f16::MAX))),
+            Token::Number(Ok(Number::F16(half::f16::from_f32(10.0)))),
+        ],
+    );
+    sub_test(
+        "65520h 0x1p16h 0x1.001p0h",
+        &[
+            Token::Number(Err(NumberError::NotRepresentable)), // rounds to 2^16
+            Token::Number(Err(NumberError::NotRepresentable)), // = 2^16
+            Token::Number(Err(NumberError::NotRepresentable)),
+        ],
+    );
+    // Just above the midpoint between 1.0 and the next `f16`; rounding
+    // through `f32` first would land on the midpoint and round down to 1.0.
+    sub_test(
+        "1.00048828218132257h 65519.9999999h",
+        &[
+            Token::Number(Ok(Number::F16(half::f16::from_bits(0x3c01)))),
+            Token::Number(Ok(Number::F16(half::f16::MAX))),
+        ],
+    );
+}
+
 #[test]
 fn test_tokens() {
     sub_test("id123_OK", &[Token::Word("id123_OK")]);
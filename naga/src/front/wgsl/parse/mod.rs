@@ -4,7 +4,7 @@ use crate::diagnostic_filter::{
 };
 use crate::front::wgsl::error::{Error, ExpectedToken};
 use crate::front::wgsl::parse::directive::enable_extension::{
-    EnableExtension, EnableExtensions, UnimplementedEnableExtension,
+    EnableExtension, EnableExtensions, ImplementedEnableExtension,
 };
 use crate::front::wgsl::parse::directive::language_extension::LanguageExtension;
 use crate::front::wgsl::parse::directive::DirectiveKind;
@@ -13,6 +13,7 @@ use crate::front::wgsl::parse::number::Number;
 use crate::front::wgsl::Scalar;
 use crate::front::SymbolTable;
 use crate::{Arena, FastIndexSet, Handle, ShaderStage, Span};
+use std::{iter, ops::Range};
 
 pub mod ast;
 pub mod conv;
@@ -85,6 +86,13 @@ struct ExpressionContext<'input, 'temp, 'out> {
     /// [`GlobalDecl`]: ast::GlobalDecl
     /// [`dependencies`]: ast::GlobalDecl::dependencies
     unresolved: &'out mut FastIndexSet<ast::Dependency<'input>>,
+
+    /// Where to record syntax errors in statements, if we are recovering
+    /// from them.
+    ///
+    /// If this is `None`, the first error ends parsing. Otherwise, see
+    /// [`Parser::recovering_statement`].
+    errors: Option<&'temp mut Vec<Error<'input>>>,
 }
 
 impl<'a> ExpressionContext<'a, '_, '_> {
@@ -172,11 +180,13 @@ impl<T> ParsedAttribute<T> {
 #[derive(Default)]
 struct BindingParser<'a> {
     location: ParsedAttribute<Handle<ast::Expression<'a>>>,
+    blend_src: ParsedAttribute<Handle<ast::Expression<'a>>>,
     second_blend_source: ParsedAttribute<bool>,
     built_in: ParsedAttribute<crate::BuiltIn>,
     interpolation: ParsedAttribute<crate::Interpolation>,
     sampling: ParsedAttribute<crate::Sampling>,
     invariant: ParsedAttribute<bool>,
+    per_primitive: ParsedAttribute<bool>,
 }
 
 impl<'a> BindingParser<'a> {
@@ -198,8 +208,10 @@ impl<'a> BindingParser<'a> {
             "builtin" => {
                 lexer.expect(Token::Paren('('))?;
                 let (raw, span) = lexer.next_ident_with_span()?;
-                self.built_in
-                    .set(conv::map_built_in(raw, span)?, name_span)?;
+                self.built_in.set(
+                    conv::map_built_in(&lexer.enable_extensions, raw, span)?,
+                    name_span,
+                )?;
                 lexer.expect(Token::Paren(')'))?;
             }
             "interpolate" => {
@@ -214,18 +226,39 @@ impl<'a> BindingParser<'a> {
                 }
                 lexer.expect(Token::Paren(')'))?;
             }
+            "blend_src" => {
+                lexer.require_enable_extension(
+                    ImplementedEnableExtension::DualSourceBlending,
+                    name_span,
+                )?;
+                lexer.expect(Token::Paren('('))?;
+                self.blend_src
+                    .set(parser.general_expression(lexer, ctx)?, name_span)?;
+                lexer.expect(Token::Paren(')'))?;
+            }
             "second_blend_source" => {
                 self.second_blend_source.set(true, name_span)?;
             }
             "invariant" => {
                 self.invariant.set(true, name_span)?;
             }
+            "per_primitive" => {
+                lexer.require_enable_extension(
+                    ImplementedEnableExtension::WgpuMeshShader,
+                    name_span,
+                )?;
+                self.per_primitive.set(true, name_span)?;
+            }
             _ => return Err(Error::UnknownAttribute(name_span)),
         }
         Ok(())
     }
 
     fn finish(self, span: Span) -> Result<Option<ast::Binding<'a>>, Error<'a>> {
+        let per_primitive = self.per_primitive.value.unwrap_or(false);
+        if per_primitive && self.location.value.is_none() {
+            return Err(Error::InconsistentBinding(span));
+        }
         match (
             self.location.value,
             self.built_in.value,
@@ -233,19 +266,32 @@ impl<'a> BindingParser<'a> {
             self.sampling.value,
             self.invariant.value.unwrap_or_default(),
         ) {
-            (None, None, None, None, false) => Ok(None),
-            (Some(location), None, interpolation, sampling, false) => {
+            (None, None, None, None, false)
+                if self.blend_src.value.is_none() && self.second_blend_source.value.is_none() =>
+            {
+                Ok(None)
+            }
+            (Some(location), None, interpolation, sampling, false)
+                if self.blend_src.value.is_none() || self.second_blend_source.value.is_none() =>
+            {
                 // Before handing over the completed `Module`, we call
                 // `apply_default_interpolation` to ensure that the interpolation and
                 // sampling have been explicitly specified on all vertex shader output and fragment
                 // shader input user bindings, so leaving them potentially `None` here is fine.
                 Ok(Some(ast::Binding::Location {
                     location,
+                    blend_src: self.blend_src.value,
                     interpolation,
                     sampling,
                     second_blend_source: self.second_blend_source.value.unwrap_or(false),
+                    per_primitive,
                 }))
             }
+            (_, _, _, _, _)
+                if self.blend_src.value.is_some() || self.second_blend_source.value.is_some() =>
+            {
+                Err(Error::InconsistentBinding(span))
+            }
             (None, Some(crate::BuiltIn::Position { .. }), None, None, invariant) => {
                 Ok(Some(ast::Binding::BuiltIn(crate::BuiltIn::Position {
                     invariant,
@@ -316,10 +362,25 @@ impl Parser {
         span: Span,
         ctx: &mut ExpressionContext<'a, '_, '_>,
     ) -> Result<Option<ast::ConstructorType<'a>>, Error<'a>> {
-        if let Some(scalar) = conv::get_scalar_type(word) {
+        if let Some(scalar) = conv::get_scalar_type(&lexer.enable_extensions, span, word)? {
             return Ok(Some(ast::ConstructorType::Scalar(scalar)));
         }
 
+        if let Some(shape) = conv::map_f16_shorthand(word) {
+            lexer.require_enable_extension(ImplementedEnableExtension::F16, span)?;
+            let ty = ctx.new_scalar(Scalar::F16);
+            let ty_span = Span::UNDEFINED;
+            return Ok(Some(match shape {
+                (None, size) => ast::ConstructorType::Vector { size, ty, ty_span },
+                (Some(columns), rows) => ast::ConstructorType::Matrix {
+                    columns,
+                    rows,
+                    ty,
+                    ty_span,
+                },
+            }));
+        }
+
         let partial = match word {
             "vec2" => ast::ConstructorType::PartialVector {
                 size: crate::VectorSize::Bi,
@@ -674,15 +735,10 @@ impl Parser {
             }
             (Token::Number(res), span) => {
                 let _ = lexer.next();
-                let num = res.map_err(|err| match err {
-                    super::error::NumberError::UnimplementedF16 => {
-                        Error::EnableExtensionNotEnabled {
-                            kind: EnableExtension::Unimplemented(UnimplementedEnableExtension::F16),
-                            span,
-                        }
-                    }
-                    err => Error::BadNumber(span, err),
-                })?;
+                let num = res.map_err(|err| Error::BadNumber(span, err))?;
+                if let Number::F16(_) = num {
+                    lexer.require_enable_extension(ImplementedEnableExtension::F16, span)?;
+                }
                 ast::Expression::Literal(ast::Literal::Number(num))
             }
             (Token::Word("RAY_FLAG_NONE"), _) => {
@@ -697,6 +753,18 @@ impl Parser {
                 let _ = lexer.next();
                 ast::Expression::Literal(ast::Literal::Number(Number::U32(0)))
             }
+            (Token::Word("RAY_QUERY_INTERSECTION_TRIANGLE"), _) => {
+                let _ = lexer.next();
+                ast::Expression::Literal(ast::Literal::Number(Number::U32(1)))
+            }
+            (Token::Word("RAY_QUERY_INTERSECTION_GENERATED"), _) => {
+                let _ = lexer.next();
+                ast::Expression::Literal(ast::Literal::Number(Number::U32(2)))
+            }
+            (Token::Word("RAY_QUERY_INTERSECTION_AABB"), _) => {
+                let _ = lexer.next();
+                ast::Expression::Literal(ast::Literal::Number(Number::U32(4)))
+            }
             (Token::Word(word), span) => {
                 let start = lexer.start_byte_offset();
                 let _ = lexer.next();
@@ -1006,7 +1074,7 @@ impl Parser {
                     };
                     crate::AddressSpace::Storage { access }
                 }
-                _ => conv::map_address_space(class_str, span)?,
+                _ => conv::map_address_space(&lexer.enable_extensions, class_str, span)?,
             };
             lexer.expect(Token::Paren('>'))?;
         }
@@ -1127,12 +1195,28 @@ impl Parser {
         &mut self,
         lexer: &mut Lexer<'a>,
         word: &'a str,
+        span: Span,
         ctx: &mut ExpressionContext<'a, '_, '_>,
     ) -> Result<Option<ast::Type<'a>>, Error<'a>> {
-        if let Some(scalar) = conv::get_scalar_type(word) {
+        if let Some(scalar) = conv::get_scalar_type(&lexer.enable_extensions, span, word)? {
             return Ok(Some(ast::Type::Scalar(scalar)));
         }
 
+        if let Some(shape) = conv::map_f16_shorthand(word) {
+            lexer.require_enable_extension(ImplementedEnableExtension::F16, span)?;
+            let ty = ctx.new_scalar(Scalar::F16);
+            let ty_span = Span::UNDEFINED;
+            return Ok(Some(match shape {
+                (None, size) => ast::Type::Vector { size, ty, ty_span },
+                (Some(columns), rows) => ast::Type::Matrix {
+                    columns,
+                    rows,
+                    ty,
+                    ty_span,
+                },
+            }));
+        }
+
         Ok(Some(match word {
             "vec2" => {
                 let (ty, ty_span) = self.singular_generic(lexer, ctx)?;
@@ -1291,7 +1375,7 @@ impl Parser {
             "ptr" => {
                 lexer.expect_generic_paren('<')?;
                 let (ident, span) = lexer.next_ident_with_span()?;
-                let mut space = conv::map_address_space(ident, span)?;
+                let mut space = conv::map_address_space(&lexer.enable_extensions, ident, span)?;
                 lexer.expect(Token::Separator(','))?;
                 let base = self.type_decl(lexer, ctx)?;
                 if let crate::AddressSpace::Storage { ref mut access } = space {
@@ -1535,7 +1619,7 @@ impl Parser {
 
         let (name, span) = lexer.next_ident_with_span()?;
 
-        let ty = match self.type_decl_impl(lexer, name, ctx)? {
+        let ty = match self.type_decl_impl(lexer, name, span, ctx)? {
             Some(ty) => ty,
             None => {
                 ctx.unresolved.insert(ast::Dependency {
@@ -2123,7 +2207,12 @@ impl Parser {
                         break;
                     } else {
                         // Otherwise try to parse a statement
-                        self.statement(lexer, ctx, &mut continuing, brace_nesting_level)?;
+                        self.recovering_statement(
+                            lexer,
+                            ctx,
+                            &mut continuing,
+                            brace_nesting_level,
+                        )?;
                     }
                 }
                 // Since the continuing block must be the last part of the loop body,
@@ -2137,7 +2226,7 @@ impl Parser {
                 break;
             }
             // Otherwise try to parse a statement
-            self.statement(lexer, ctx, &mut body, brace_nesting_level)?;
+            self.recovering_statement(lexer, ctx, &mut body, brace_nesting_level)?;
         }
 
         ctx.local_table.pop_scope();
@@ -2189,7 +2278,7 @@ impl Parser {
         let brace_nesting_level = Self::increase_brace_nesting(brace_nesting_level, brace_span)?;
         let mut block = ast::Block::default();
         while !lexer.skip(Token::Paren('}')) {
-            self.statement(lexer, ctx, &mut block, brace_nesting_level)?;
+            self.recovering_statement(lexer, ctx, &mut block, brace_nesting_level)?;
         }
 
         ctx.local_table.pop_scope();
@@ -2198,6 +2287,72 @@ impl Parser {
         Ok((block, span))
     }
 
+    /// Parse a statement, recovering from syntax errors if `ctx` asks us to.
+    ///
+    /// If [`ctx.errors`] is `Some` and the statement has an error, record the
+    /// error there, skip to the end of the statement, and return `Ok`, so the
+    /// caller carries on with the next one. If that leaves nothing more to
+    /// parse, return the error instead: the enclosing declaration is
+    /// incomplete, and [`Parser::parse_all_errors`] will report it there.
+    ///
+    /// [`ctx.errors`]: ExpressionContext::errors
+    fn recovering_statement<'a>(
+        &mut self,
+        lexer: &mut Lexer<'a>,
+        ctx: &mut ExpressionContext<'a, '_, '_>,
+        block: &mut ast::Block<'a>,
+        brace_nesting_level: u8,
+    ) -> Result<(), Error<'a>> {
+        let rule_depth = self.rules.len();
+        let scope_depth = ctx.local_table.depth();
+        let error = match self.statement(lexer, ctx, block, brace_nesting_level) {
+            Ok(()) => return Ok(()),
+            Err(error) => error,
+        };
+        let Some(ref mut errors) = ctx.errors else {
+            return Err(error);
+        };
+
+        // Forget whatever the statement was in the middle of.
+        self.rules.truncate(rule_depth);
+        while ctx.local_table.depth() > scope_depth {
+            ctx.local_table.pop_scope();
+        }
+
+        Self::skip_statement(lexer);
+        if lexer.peek().0 == Token::End {
+            return Err(error);
+        }
+        errors.push(error);
+        Ok(())
+    }
+
+    /// Skip to the end of the statement containing the next token.
+    ///
+    /// Stop after a `;` or a complete `{ ... }` block, or before a `}` that
+    /// closes the enclosing block.
+    fn skip_statement(lexer: &mut Lexer<'_>) {
+        let mut depth = 0usize;
+        loop {
+            match lexer.peek().0 {
+                Token::End => return,
+                Token::Paren('}') if depth == 0 => return,
+                _ => {}
+            }
+            match lexer.next().0 {
+                Token::Separator(';') if depth == 0 => return,
+                Token::Paren('{') => depth += 1,
+                Token::Paren('}') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
     fn varying_binding<'a>(
         &mut self,
         lexer: &mut Lexer<'a>,
@@ -2221,6 +2376,7 @@ impl Parser {
         diagnostic_filter_leaf: Option<Handle<DiagnosticFilterNode>>,
         out: &mut ast::TranslationUnit<'a>,
         dependencies: &mut FastIndexSet<ast::Dependency<'a>>,
+        errors: Option<&mut Vec<Error<'a>>>,
     ) -> Result<ast::Function<'a>, Error<'a>> {
         self.push_rule_span(Rule::FunctionDecl, lexer);
         // read function name
@@ -2234,6 +2390,7 @@ impl Parser {
             locals: &mut locals,
             types: &mut out.types,
             unresolved: dependencies,
+            errors,
         };
 
         // start a scope that contains arguments as well as the function body
@@ -2280,7 +2437,7 @@ impl Parser {
         let brace_nesting_level = 1;
         let mut body = ast::Block::default();
         while !lexer.skip(Token::Paren('}')) {
-            self.statement(lexer, &mut ctx, &mut body, brace_nesting_level)?;
+            self.recovering_statement(lexer, &mut ctx, &mut body, brace_nesting_level)?;
         }
 
         ctx.local_table.pop_scope();
@@ -2333,6 +2490,7 @@ impl Parser {
         &mut self,
         lexer: &mut Lexer<'a>,
         out: &mut ast::TranslationUnit<'a>,
+        errors: Option<&mut Vec<Error<'a>>>,
     ) -> Result<(), Error<'a>> {
         // read attributes
         let mut binding = None;
@@ -2340,6 +2498,13 @@ impl Parser {
         let mut compute_span = Span::new(0, 0);
         let mut workgroup_size = ParsedAttribute::default();
         let mut early_depth_test = ParsedAttribute::default();
+        let mut task_payload = ParsedAttribute::default();
+        let mut output_topology = ParsedAttribute::default();
+        let mut vertex_output = ParsedAttribute::default();
+        let mut primitive_output = ParsedAttribute::default();
+        // Spans of the first task/mesh-only and mesh-only attributes seen.
+        let mut task_attribute_span = None;
+        let mut mesh_attribute_span = None;
         let (mut bind_index, mut bind_group) =
             (ParsedAttribute::default(), ParsedAttribute::default());
         let mut id = ParsedAttribute::default();
@@ -2351,6 +2516,7 @@ impl Parser {
             locals: &mut Arena::new(),
             types: &mut out.types,
             unresolved: &mut dependencies,
+            errors: None,
         };
         let mut diagnostic_filters = DiagnosticFilterMap::new();
         let ensure_no_diag_attrs =
@@ -2400,6 +2566,67 @@ impl Parser {
                     stage.set(ShaderStage::Compute, name_span)?;
                     compute_span = name_span;
                 }
+                "task" => {
+                    lexer.require_enable_extension(
+                        ImplementedEnableExtension::WgpuMeshShader,
+                        name_span,
+                    )?;
+                    stage.set(ShaderStage::Task, name_span)?;
+                    compute_span = name_span;
+                }
+                "mesh" => {
+                    lexer.require_enable_extension(
+                        ImplementedEnableExtension::WgpuMeshShader,
+                        name_span,
+                    )?;
+                    stage.set(ShaderStage::Mesh, name_span)?;
+                    compute_span = name_span;
+                }
+                "payload" => {
+                    lexer.require_enable_extension(
+                        ImplementedEnableExtension::WgpuMeshShader,
+                        name_span,
+                    )?;
+                    lexer.expect(Token::Paren('('))?;
+                    let ident = lexer.next_ident()?;
+                    ctx.unresolved.insert(ast::Dependency {
+                        ident: ident.name,
+                        usage: ident.span,
+                    });
+                    task_payload.set(ident, name_span)?;
+                    lexer.expect(Token::Paren(')'))?;
+                    task_attribute_span.get_or_insert(name_span);
+                }
+                "output_topology" => {
+                    lexer.require_enable_extension(
+                        ImplementedEnableExtension::WgpuMeshShader,
+                        name_span,
+                    )?;
+                    lexer.expect(Token::Paren('('))?;
+                    let (ident, ident_span) = lexer.next_ident_with_span()?;
+                    let topology = conv::map_mesh_output_topology(ident, ident_span)?;
+                    output_topology.set(topology, name_span)?;
+                    lexer.expect(Token::Paren(')'))?;
+                    mesh_attribute_span.get_or_insert(name_span);
+                }
+                "vertex_output" | "primitive_output" => {
+                    lexer.require_enable_extension(
+                        ImplementedEnableExtension::WgpuMeshShader,
+                        name_span,
+                    )?;
+                    lexer.expect(Token::Paren('('))?;
+                    let ty = self.type_decl(lexer, &mut ctx)?;
+                    lexer.expect(Token::Separator(','))?;
+                    let max_count = self.general_expression(lexer, &mut ctx)?;
+                    lexer.expect(Token::Paren(')'))?;
+                    let output = if name == "vertex_output" {
+                        &mut vertex_output
+                    } else {
+                        &mut primitive_output
+                    };
+                    output.set((ty, max_count), name_span)?;
+                    mesh_attribute_span.get_or_insert(name_span);
+                }
                 "workgroup_size" => {
                     lexer.expect(Token::Paren('('))?;
                     let mut new_workgroup_size = [None; 3];
@@ -2531,19 +2758,65 @@ impl Parser {
                     diagnostic_filters,
                     out.diagnostic_filter_leaf,
                 );
-                let function =
-                    self.function_decl(lexer, diagnostic_filter_leaf, out, &mut dependencies)?;
+                let function = self.function_decl(
+                    lexer,
+                    diagnostic_filter_leaf,
+                    out,
+                    &mut dependencies,
+                    errors,
+                )?;
                 Some(ast::GlobalDeclKind::Fn(ast::Function {
                     entry_point: if let Some(stage) = stage.value {
-                        if stage == ShaderStage::Compute && workgroup_size.value.is_none() {
+                        if matches!(
+                            stage,
+                            ShaderStage::Compute | ShaderStage::Task | ShaderStage::Mesh
+                        ) && workgroup_size.value.is_none()
+                        {
                             return Err(Error::MissingWorkgroupSize(compute_span));
                         }
+                        let mesh_output = match stage {
+                            ShaderStage::Mesh => {
+                                let missing =
+                                    |name| Error::MissingMeshShaderAttribute(name, compute_span);
+                                let topology = output_topology
+                                    .value
+                                    .ok_or_else(|| missing("output_topology"))?;
+                                let (vertex_type, max_vertices) = vertex_output
+                                    .value
+                                    .ok_or_else(|| missing("vertex_output"))?;
+                                let (primitive_type, max_primitives) = primitive_output
+                                    .value
+                                    .ok_or_else(|| missing("primitive_output"))?;
+                                Some(ast::MeshOutput {
+                                    topology,
+                                    vertex_type,
+                                    max_vertices,
+                                    primitive_type,
+                                    max_primitives,
+                                })
+                            }
+                            ShaderStage::Task => None,
+                            _ => {
+                                if let Some(span) = task_attribute_span {
+                                    return Err(Error::UnexpectedMeshShaderAttribute(span));
+                                }
+                                None
+                            }
+                        };
+                        if let (None, Some(span)) = (mesh_output.as_ref(), mesh_attribute_span) {
+                            return Err(Error::UnexpectedMeshShaderAttribute(span));
+                        }
                         Some(ast::EntryPoint {
                             stage,
                             early_depth_test: early_depth_test.value,
                             workgroup_size: workgroup_size.value,
+                            mesh_output,
+                            task_payload: task_payload.value,
                         })
                     } else {
+                        if let Some(span) = task_attribute_span.or(mesh_attribute_span) {
+                            return Err(Error::UnexpectedMeshShaderAttribute(span));
+                        }
                         None
                     },
                     ..function
@@ -2587,22 +2860,182 @@ impl Parser {
     }
 
     pub fn parse<'a>(&mut self, source: &'a str) -> Result<ast::TranslationUnit<'a>, Error<'a>> {
+        self.parse_files(source, iter::once(0..source.len()), false)
+    }
+
+    /// Parse `source`, reporting every syntax error rather than just the
+    /// first.
+    ///
+    /// After an error in a statement, skip to the next statement in the same
+    /// block; after an error elsewhere in a global declaration, skip to the
+    /// next global declaration. Errors reported after the first may be
+    /// knock-on effects of an earlier one.
+    pub fn parse_all_errors<'a>(
+        &mut self,
+        source: &'a str,
+    ) -> Result<ast::TranslationUnit<'a>, Vec<Error<'a>>> {
+        let mut errors = Vec::new();
+        let result = self.parse_files_impl(
+            source,
+            iter::once(0..source.len()),
+            false,
+            Some(&mut errors),
+        );
+        match result {
+            Ok(tu) if errors.is_empty() => Ok(tu),
+            Ok(_) => Err(errors),
+            Err(error) => {
+                errors.push(error);
+                Err(errors)
+            }
+        }
+    }
+
+    /// Parse several files, stored one after another in `source`, into a
+    /// single translation unit.
+    ///
+    /// `files` gives the range of each file in `source`. The directives of the
+    /// first file apply to the whole module, while those of the other files
+    /// only apply to their own declarations.
+    ///
+    /// If `allow_imports` is true, `import` directives are skipped, as they
+    /// must have been resolved with [`Parser::imports`] beforehand. Otherwise,
+    /// they are an error.
+    pub fn parse_files<'a>(
+        &mut self,
+        source: &'a str,
+        files: impl IntoIterator<Item = Range<usize>>,
+        allow_imports: bool,
+    ) -> Result<ast::TranslationUnit<'a>, Error<'a>> {
+        self.parse_files_impl(source, files, allow_imports, None)
+    }
+
+    /// Implementation of [`Parser::parse_files`] and
+    /// [`Parser::parse_all_errors`].
+    ///
+    /// If `errors` is `Some`, record syntax errors in declarations there and
+    /// carry on parsing. Errors in directives are still returned directly.
+    fn parse_files_impl<'a>(
+        &mut self,
+        source: &'a str,
+        files: impl IntoIterator<Item = Range<usize>>,
+        allow_imports: bool,
+        mut errors: Option<&mut Vec<Error<'a>>>,
+    ) -> Result<ast::TranslationUnit<'a>, Error<'a>> {
         self.reset();
 
-        let mut lexer = Lexer::new(source);
         let mut tu = ast::TranslationUnit::default();
+        let mut root = None;
+
+        for range in files {
+            let mut lexer = Lexer::with_range(source, range);
+            let (enable_extensions, diagnostic_filters) =
+                self.directives(&mut lexer, allow_imports.then_some(&mut Vec::new()))?;
+
+            lexer.enable_extensions = enable_extensions.clone();
+            tu.diagnostic_filter_leaf = Self::write_diagnostic_filters(
+                &mut tu.diagnostic_filters,
+                diagnostic_filters,
+                None,
+            );
+            if root.is_none() {
+                tu.enable_extensions = enable_extensions;
+                root = Some(tu.diagnostic_filter_leaf);
+            }
+
+            loop {
+                let start = lexer.start_byte_offset();
+                match self.global_decl(&mut lexer, &mut tu, errors.as_deref_mut()) {
+                    Err(error) => {
+                        let Some(ref mut errors) = errors else {
+                            return Err(error);
+                        };
+                        errors.push(error);
+                        self.rules.clear();
+                        Self::skip_global_decl(&mut lexer, start);
+                    }
+                    Ok(()) => {}
+                }
+                if lexer.peek().0 == Token::End {
+                    break;
+                }
+            }
+        }
+
+        tu.diagnostic_filter_leaf = root.flatten();
+        Ok(tu)
+    }
+
+    /// Skip to the start of the next global declaration, after an error in
+    /// the one that began at `start`.
+    ///
+    /// Stop after a `;` or a complete `{ ... }` block, or before a keyword or
+    /// attribute that can begin a declaration, as long as it isn't nested in
+    /// brackets and comes after `start`.
+    fn skip_global_decl(lexer: &mut Lexer<'_>, start: usize) {
+        let mut braces = 0usize;
+        let mut parens = 0usize;
+        loop {
+            let at_top_level = braces == 0 && parens == 0;
+            let offset = lexer.start_byte_offset();
+            match lexer.peek().0 {
+                Token::End => return,
+                Token::Attribute
+                | Token::Word(
+                    "fn" | "var" | "const" | "override" | "struct" | "alias" | "const_assert",
+                ) if at_top_level && offset > start => return,
+                _ => {}
+            }
+            match lexer.next().0 {
+                Token::Separator(';') if braces == 0 => return,
+                Token::Paren('{') => braces += 1,
+                Token::Paren('}') => {
+                    braces = braces.saturating_sub(1);
+                    if braces == 0 {
+                        return;
+                    }
+                }
+                Token::Paren('(') => parens += 1,
+                Token::Paren(')') => parens = parens.saturating_sub(1),
+                _ => {}
+            }
+        }
+    }
+
+    /// Return the names of the files imported by the file at `range` in
+    /// `source`, with their spans.
+    pub fn imports<'a>(
+        &mut self,
+        source: &'a str,
+        range: Range<usize>,
+    ) -> Result<Vec<(&'a str, Span)>, Error<'a>> {
+        self.reset();
+
+        let mut imports = Vec::new();
+        self.directives(&mut Lexer::with_range(source, range), Some(&mut imports))?;
+        Ok(imports)
+    }
+
+    /// Parse the directives at the start of a file.
+    ///
+    /// `import` directives are only accepted if `imports` is `Some`, in which
+    /// case the imported names are added to it.
+    fn directives<'a>(
+        &mut self,
+        lexer: &mut Lexer<'a>,
+        mut imports: Option<&mut Vec<(&'a str, Span)>>,
+    ) -> Result<(EnableExtensions, DiagnosticFilterMap), Error<'a>> {
         let mut enable_extensions = EnableExtensions::empty();
         let mut diagnostic_filters = DiagnosticFilterMap::new();
 
-        // Parse directives.
-        while let Ok((ident, _directive_ident_span)) = lexer.peek_ident_with_span() {
+        while let Ok((ident, directive_ident_span)) = lexer.peek_ident_with_span() {
             if let Some(kind) = DirectiveKind::from_ident(ident) {
-                self.push_rule_span(Rule::Directive, &mut lexer);
+                self.push_rule_span(Rule::Directive, lexer);
                 let _ = lexer.next_ident_with_span().unwrap();
                 match kind {
                     DirectiveKind::Diagnostic => {
-                        let diagnostic_filter = self.diagnostic_filter(&mut lexer)?;
-                        let span = self.peek_rule_span(&lexer);
+                        let diagnostic_filter = self.diagnostic_filter(lexer)?;
+                        let span = self.peek_rule_span(lexer);
                         diagnostic_filters.add(
                             diagnostic_filter,
                             span,
@@ -2611,7 +3044,7 @@ impl Parser {
                         lexer.expect(Token::Separator(';'))?;
                     }
                     DirectiveKind::Enable => {
-                        self.directive_ident_list(&mut lexer, |ident, span| {
+                        self.directive_ident_list(lexer, |ident, span| {
                             let kind = EnableExtension::from_ident(ident, span)?;
                             let extension = match kind {
                                 EnableExtension::Implemented(kind) => kind,
@@ -2626,8 +3059,17 @@ impl Parser {
                             Ok(())
                         })?;
                     }
+                    DirectiveKind::Import => {
+                        let imports = imports
+                            .as_deref_mut()
+                            .ok_or(Error::ImportWithoutResolver(directive_ident_span))?;
+                        self.directive_ident_list(lexer, |ident, span| {
+                            imports.push((ident, span));
+                            Ok(())
+                        })?;
+                    }
                     DirectiveKind::Requires => {
-                        self.directive_ident_list(&mut lexer, |ident, span| {
+                        self.directive_ident_list(lexer, |ident, span| {
                             match LanguageExtension::from_ident(ident) {
                                 Some(LanguageExtension::Implemented(_kind)) => {
                                     // NOTE: No further validation is needed for an extension, so
@@ -2644,29 +3086,13 @@ impl Parser {
                         })?;
                     }
                 }
-                self.pop_rule_span(&lexer);
+                self.pop_rule_span(lexer);
             } else {
                 break;
             }
         }
 
-        lexer.enable_extensions = enable_extensions.clone();
-        tu.enable_extensions = enable_extensions;
-        tu.diagnostic_filter_leaf =
-            Self::write_diagnostic_filters(&mut tu.diagnostic_filters, diagnostic_filters, None);
-
-        loop {
-            match self.global_decl(&mut lexer, &mut tu) {
-                Err(error) => return Err(error),
-                Ok(()) => {
-                    if lexer.peek().0 == Token::End {
-                        break;
-                    }
-                }
-            }
-        }
-
-        Ok(tu)
+        Ok((enable_extensions, diagnostic_filters))
     }
 
     const fn increase_brace_nesting(
@@ -1,3 +1,5 @@
+use half::f16;
+
 use crate::front::wgsl::error::NumberError;
 use crate::front::wgsl::parse::lexer::Token;
 
@@ -16,6 +18,8 @@ pub enum Number {
     I64(i64),
     /// Concrete u64
     U64(u64),
+    /// Concrete f16
+    F16(f16),
     /// Concrete f32
     F32(f32),
     /// Concrete f64
@@ -362,7 +366,17 @@ fn parse_hex_float(input: &str, kind: Option<FloatKind>) -> Result<Number, Numbe
             // can only be ParseHexfErrorKind::Inexact but we can't check since it's private
             _ => Err(NumberError::NotRepresentable),
         },
-        Some(FloatKind::F16) => Err(NumberError::UnimplementedF16),
+        Some(FloatKind::F16) => match hexf_parse::parse_hexf64(input, false) {
+            // `hexf_parse` has no `f16` support, so go through `f64`, which
+            // can hold every `f16` exactly, and check that nothing was lost
+            Ok(num) => {
+                let half = f16::from_f64(num);
+                (half.to_f64() == num)
+                    .then_some(Number::F16(half))
+                    .ok_or(NumberError::NotRepresentable)
+            }
+            _ => Err(NumberError::NotRepresentable),
+        },
         Some(FloatKind::F32) => match hexf_parse::parse_hexf32(input, false) {
             Ok(num) => Ok(Number::F32(num)),
             // can only be ParseHexfErrorKind::Inexact but we can't check since it's private
@@ -398,7 +412,15 @@ fn parse_dec_float(input: &str, kind: Option<FloatKind>) -> Result<Number, Numbe
                 .then_some(Number::F64(num))
                 .ok_or(NumberError::NotRepresentable)
         }
-        Some(FloatKind::F16) => Err(NumberError::UnimplementedF16),
+        Some(FloatKind::F16) => {
+            // `f16`'s own `FromStr` goes through `f32`, rounding twice, so
+            // parse as `f64` and round to the nearest `f16` from there.
+            let num = input.parse::<f64>().unwrap(); // will never fail
+            let half = crate::proc::f16_from_f64(num);
+            (num.is_finite() && half.is_finite())
+                .then_some(Number::F16(half))
+                .ok_or(NumberError::NotRepresentable)
+        }
     }
 }
 
@@ -636,3 +636,213 @@ fn parse_missing_workgroup_size() {
         Error::MissingWorkgroupSize(span) if span == Span::new(1, 8)
     ));
 }
+
+#[test]
+fn parse_multiple_files() {
+    use crate::front::wgsl::{Frontend, SourceFiles};
+
+    let mut files = SourceFiles::new();
+    files.add("main.wgsl", "fn main() -> f32 { return twice(scale); }");
+    files.add(
+        "util.wgsl",
+        "const scale = 2.0;\nfn twice(x: f32) -> f32 { return x * scale; }",
+    );
+    let module = Frontend::new().parse_files(&mut files, None).unwrap();
+
+    let (twice, _) = module
+        .functions
+        .iter()
+        .find(|&(_, function)| function.name.as_deref() == Some("twice"))
+        .unwrap();
+    let (file, range) = files.locate(module.functions.get_span(twice)).unwrap();
+    assert_eq!(files.name(file), "util.wgsl");
+    assert!(files.source(file)[range].starts_with("fn twice"));
+
+    let mut files = SourceFiles::new();
+    files.add("a.wgsl", "const scale = 1.0;");
+    files.add("b.wgsl", "const scale = 2.0;");
+    let error = Frontend::new().parse_files(&mut files, None).unwrap_err();
+    assert_eq!(
+        error.emit_to_string_with_files(&files),
+        "\
+error: redefinition of `scale`
+  ┌─ b.wgsl:1:7
+  │
+1 │ const scale = 2.0;
+  │       ^^^^^ redefinition of `scale`
+  │
+  ┌─ a.wgsl:1:7
+  │
+1 │ const scale = 1.0;
+  │       ^^^^^ previous definition of `scale`
+
+"
+    );
+
+    // Names that only differ in characters dropped from generated code
+    // collide, but only if they come from different files.
+    let mut files = SourceFiles::new();
+    files.add("a.wgsl", "const scale = 1.0;\nconst scale_ = 2.0;");
+    files.add("b.wgsl", "const scaleα = 3.0;");
+    let error = Frontend::new().parse_files(&mut files, None).unwrap_err();
+    assert_eq!(
+        error.emit_to_string_with_files(&files),
+        "\
+error: `scaleα` collides with `scale` from another file
+  ┌─ b.wgsl:1:7
+  │
+1 │ const scaleα = 3.0;
+  │       ^^^^^^
+  │
+  ┌─ a.wgsl:1:7
+  │
+1 │ const scale = 1.0;
+  │       ^^^^^ `scale` is declared here
+  │
+  = note: the names only differ in characters that are dropped from generated code
+
+"
+    );
+}
+
+#[test]
+fn parse_imports() {
+    use crate::front::wgsl::{
+        assert_parse_err, Frontend, ImportResolver, ImportedFile, SourceFiles,
+    };
+
+    #[derive(Debug)]
+    struct Resolver;
+
+    impl ImportResolver for Resolver {
+        fn resolve(&self, name: &str, _importer: &str) -> Result<ImportedFile, String> {
+            let source = match name {
+                "lighting" => "import common;\nfn light() -> f32 { return ambient; }",
+                "common" => "import lighting;\nconst ambient = 0.5;",
+                "broken" => "fn broken() -> f32 { return missing; }",
+                _ => return Err("no such file".to_string()),
+            };
+            Ok(ImportedFile {
+                name: format!("{name}.wgsl"),
+                source: source.to_string(),
+            })
+        }
+    }
+
+    // Files are only parsed once, even if they import each other.
+    let mut files = SourceFiles::new();
+    files.add(
+        "main.wgsl",
+        "import lighting, common;\nfn main() -> f32 { return light(); }",
+    );
+    Frontend::new()
+        .parse_files(&mut files, Some(&Resolver))
+        .unwrap();
+    assert_eq!(
+        files.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        ["main.wgsl", "lighting.wgsl", "common.wgsl"]
+    );
+
+    let mut files = SourceFiles::new();
+    files.add("main.wgsl", "import broken;");
+    let error = Frontend::new()
+        .parse_files(&mut files, Some(&Resolver))
+        .unwrap_err();
+    assert_eq!(
+        error.emit_to_string_with_files(&files),
+        "\
+error: no definition in scope for identifier: 'missing'
+  ┌─ broken.wgsl:1:29
+  │
+1 │ fn broken() -> f32 { return missing; }
+  │                             ^^^^^^^ unknown identifier
+
+"
+    );
+
+    let mut files = SourceFiles::new();
+    files.add("main.wgsl", "import unknown;");
+    let error = Frontend::new()
+        .parse_files(&mut files, Some(&Resolver))
+        .unwrap_err();
+    assert_eq!(
+        error.emit_to_string_with_files(&files),
+        "\
+error: failed to import `unknown`: no such file
+  ┌─ main.wgsl:1:8
+  │
+1 │ import unknown;
+  │        ^^^^^^^ imported here
+
+"
+    );
+
+    assert_parse_err(
+        "import lighting;",
+        "\
+error: `import` directives are not enabled
+  ┌─ wgsl:1:1
+  │
+1 │ import lighting;
+  │ ^^^^^^
+  │
+  = note: imports are only supported when parsing with `Frontend::parse_files` and an import resolver
+
+",
+    );
+}
+
+#[test]
+fn parse_all_errors() {
+    let source = "
+fn first() {
+    let a = ;
+    let b = 1 +;
+    loop {
+        var c = 2 +;
+        continuing {
+            c = ;
+        }
+    }
+    let d = a + b;
+}
+
+struct S {
+    x: f32,,
+}
+
+fn second(x: ) -> f32 {
+    return x;
+}
+
+const k = 3;
+";
+    let errors = super::parse_all_errors(source).unwrap_err().errors;
+    let lines = errors
+        .iter()
+        .map(|error| error.location(source).unwrap().line_number)
+        .collect::<Vec<_>>();
+    assert_eq!(lines, [3, 4, 6, 8, 15, 18]);
+
+    // Only the first error is reported by `parse`.
+    let error = parse_str(source).unwrap_err();
+    assert_eq!(error.message(), errors[0].message());
+
+    // Errors found after parsing are still reported, one at a time.
+    let errors = super::parse_all_errors("fn f() -> f32 { return missing; }")
+        .unwrap_err()
+        .errors;
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].message(),
+        "no definition in scope for identifier: 'missing'"
+    );
+
+    // An unterminated function is reported once.
+    let errors = super::parse_all_errors("fn f() { let a = ;\nlet b = 1")
+        .unwrap_err()
+        .errors;
+    assert_eq!(errors.len(), 2);
+
+    super::parse_all_errors("fn f() -> f32 { return 1.0; }").unwrap();
+}
@@ -260,6 +260,8 @@ pub mod error;
 pub mod front;
 pub mod keywords;
 mod non_max_u32;
+#[cfg(feature = "opt")]
+pub mod opt;
 pub mod proc;
 mod span;
 pub mod valid;
@@ -362,6 +364,12 @@ pub enum ShaderStage {
     Vertex,
     Fragment,
     Compute,
+    /// Task shaders decide how many mesh shader workgroups to launch, and
+    /// pass them a [`TaskPayload`](AddressSpace::TaskPayload).
+    Task,
+    /// Mesh shaders produce vertices and primitives directly, in place of a
+    /// vertex shader. See [`MeshStageInfo`].
+    Mesh,
 }
 
 /// Addressing space of variables.
@@ -384,6 +392,12 @@ pub enum AddressSpace {
     Handle,
     /// Push constants.
     PushConstant,
+    /// Data written by a [`Task`] shader and read by the [`Mesh`] shaders
+    /// it launches. Shared by the task shader's workgroup.
+    ///
+    /// [`Task`]: ShaderStage::Task
+    /// [`Mesh`]: ShaderStage::Mesh
+    TaskPayload,
 }
 
 /// Built-in inputs and outputs.
@@ -422,6 +436,13 @@ pub enum BuiltIn {
     SubgroupId,
     SubgroupSize,
     SubgroupInvocationId,
+    // task
+    MeshTaskSize,
+    // mesh
+    PointIndex,
+    LineIndices,
+    TriangleIndices,
+    CullPrimitive,
 }
 
 /// Number of bytes per scalar.
@@ -873,6 +894,8 @@ pub enum Literal {
     F64(f64),
     /// May not be NaN or infinity.
     F32(f32),
+    /// May not be NaN or infinity.
+    F16(#[cfg_attr(feature = "arbitrary", arbitrary(with = arbitrary_f16))] half::f16),
     U32(u32),
     I32(i32),
     U64(u64),
@@ -882,6 +905,11 @@ pub enum Literal {
     AbstractFloat(f64),
 }
 
+#[cfg(feature = "arbitrary")]
+fn arbitrary_f16(u: &mut arbitrary::Unstructured) -> arbitrary::Result<half::f16> {
+    Ok(half::f16::from_bits(u.arbitrary()?))
+}
+
 /// Pipeline-overridable constant.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(Serialize))]
@@ -947,6 +975,11 @@ pub enum Binding {
         second_blend_source: bool,
         interpolation: Option<Interpolation>,
         sampling: Option<Sampling>,
+        /// Indicates a value produced once per primitive by a [`Mesh`]
+        /// shader, rather than once per vertex.
+        ///
+        /// [`Mesh`]: crate::ShaderStage::Mesh
+        per_primitive: bool,
     },
 }
 
@@ -1181,6 +1214,8 @@ pub enum MathFunction {
     Pow,
     // geometry
     Dot,
+    Dot4I8Packed,
+    Dot4U8Packed,
     Outer,
     Cross,
     Distance,
@@ -1308,6 +1343,32 @@ pub enum GatherMode {
     ShuffleUp(Handle<Expression>),
     /// Each gathers from their lane xored with the given by the expression
     ShuffleXor(Handle<Expression>),
+    /// All gather from the same lane of their quad at the index given by the
+    /// expression, which must be a constant in the range `0..4`
+    QuadBroadcast(Handle<Expression>),
+    /// Each gathers from the lane of their quad across the given direction
+    QuadSwap(Direction),
+}
+
+/// The direction of a [`GatherMode::QuadSwap`].
+///
+/// The lanes of a quad are numbered like this:
+///
+/// ```text
+/// 0 1
+/// 2 3
+/// ```
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum Direction {
+    /// Swap horizontally, with the lane index xored with 1.
+    X = 0,
+    /// Swap vertically, with the lane index xored with 2.
+    Y = 1,
+    /// Swap diagonally, with the lane index xored with 3.
+    Diagonal = 2,
 }
 
 #[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
@@ -1331,9 +1392,12 @@ pub enum SubgroupOperation {
 #[cfg_attr(feature = "deserialize", derive(Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
 pub enum CollectiveOperation {
-    Reduce = 0,
-    InclusiveScan = 1,
-    ExclusiveScan = 2,
+    Reduce,
+    InclusiveScan,
+    ExclusiveScan,
+    /// Reduce over each cluster of consecutive lanes of the given size, which
+    /// must be a power of two.
+    ClusteredReduce(u32),
 }
 
 bitflags::bitflags! {
@@ -1674,6 +1738,14 @@ pub enum Expression {
     /// Return an intersection found by `query`.
     ///
     /// If `committed` is true, return the committed result available when
+    /// the query has finished. Otherwise, return the candidate intersection
+    /// the query is currently stopped at, which is only meaningful while
+    /// [`Proceed`] is still returning `true`.
+    ///
+    /// The expression has the struct type given in
+    /// [`SpecialTypes::ray_intersection`].
+    ///
+    /// [`Proceed`]: RayQueryFunction::Proceed
     RayQueryGetIntersection {
         query: Handle<Expression>,
         committed: bool,
@@ -1757,9 +1829,65 @@ pub enum RayQueryFunction {
         result: Handle<Expression>,
     },
 
+    /// Add a candidate generated intersection to be included
+    /// in the determination of the closest hit for a ray query.
+    ///
+    /// This is meant for the case where the current candidate is an AABB:
+    /// the shader computes the intersection with the procedural geometry
+    /// itself and reports its distance along the ray as `hit_t`, which must
+    /// be an `f32` scalar.
+    GenerateIntersection {
+        hit_t: Handle<Expression>,
+    },
+
+    /// Confirm a triangle intersection to be included in the determination
+    /// of the closest hit for a ray query.
+    ///
+    /// This is meant for the case where the current candidate is a
+    /// non-opaque triangle, for example one that passed an alpha test.
+    ConfirmIntersection,
+
     Terminate,
 }
 
+/// An operation that a [`Mesh`] shader performs on its outputs.
+///
+/// The `value` operands must have the types given by the entry point's
+/// [`MeshStageInfo`].
+///
+/// [`Mesh`]: ShaderStage::Mesh
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum MeshFunction {
+    /// Set the number of vertices and primitives the workgroup produces.
+    ///
+    /// Both counts are `u32` scalars, and must not exceed the entry point's
+    /// [`max_vertices`] and [`max_primitives`].
+    ///
+    /// [`max_vertices`]: MeshStageInfo::max_vertices
+    /// [`max_primitives`]: MeshStageInfo::max_primitives
+    SetMeshOutputs {
+        vertex_count: Handle<Expression>,
+        primitive_count: Handle<Expression>,
+    },
+    /// Write the vertex at `index`, a `u32` scalar.
+    ///
+    /// `value` has the type given by [`MeshStageInfo::vertex_output_type`].
+    SetVertex {
+        index: Handle<Expression>,
+        value: Handle<Expression>,
+    },
+    /// Write the primitive at `index`, a `u32` scalar.
+    ///
+    /// `value` has the type given by [`MeshStageInfo::primitive_output_type`].
+    SetPrimitive {
+        index: Handle<Expression>,
+        value: Handle<Expression>,
+    },
+}
+
 //TODO: consider removing `Clone`. It's not valid to clone `Statement::Emit` anyway.
 /// Instructions which make up an executable block.
 ///
@@ -2058,6 +2186,13 @@ pub enum Statement {
         /// [`SubgroupOperationResult`]: Expression::SubgroupOperationResult
         result: Handle<Expression>,
     },
+    /// Write the outputs of a [`Mesh`] shader.
+    ///
+    /// This may only appear in the body of a mesh shader's entry point
+    /// function, not in functions it calls.
+    ///
+    /// [`Mesh`]: ShaderStage::Mesh
+    MeshFunction(MeshFunction),
 }
 
 /// A function argument.
@@ -2188,6 +2323,67 @@ pub struct EntryPoint {
     pub workgroup_size: [u32; 3],
     /// The entrance function.
     pub function: Function,
+    /// The outputs of a [`Mesh`] stage.
+    ///
+    /// This must be `Some` for mesh shaders, and `None` otherwise.
+    ///
+    /// [`Mesh`]: ShaderStage::Mesh
+    pub mesh_info: Option<MeshStageInfo>,
+    /// The [`TaskPayload`] global shared by a [`Task`] shader and the
+    /// [`Mesh`] shaders it launches, if any.
+    ///
+    /// [`TaskPayload`]: AddressSpace::TaskPayload
+    /// [`Task`]: ShaderStage::Task
+    /// [`Mesh`]: ShaderStage::Mesh
+    pub task_payload: Option<Handle<GlobalVariable>>,
+}
+
+/// The kind of primitive a [`Mesh`] shader produces.
+///
+/// [`Mesh`]: ShaderStage::Mesh
+#[derive(Clone, Copy, Debug, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum MeshOutputTopology {
+    /// Each primitive is a point, indexed by [`BuiltIn::PointIndex`].
+    Points,
+    /// Each primitive is a line, indexed by [`BuiltIn::LineIndices`].
+    Lines,
+    /// Each primitive is a triangle, indexed by [`BuiltIn::TriangleIndices`].
+    Triangles,
+}
+
+/// The outputs of a [`Mesh`] shader entry point.
+///
+/// Rather than returning a value, a mesh shader writes its outputs with
+/// [`MeshFunction`] statements.
+///
+/// [`Mesh`]: ShaderStage::Mesh
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serialize", derive(Serialize))]
+#[cfg_attr(feature = "deserialize", derive(Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub struct MeshStageInfo {
+    /// The kind of primitive the shader produces.
+    pub topology: MeshOutputTopology,
+    /// The largest number of vertices the workgroup may produce.
+    pub max_vertices: u32,
+    /// The largest number of primitives the workgroup may produce.
+    pub max_primitives: u32,
+    /// The type of each vertex, a struct whose members all have bindings.
+    ///
+    /// It must include a [`BuiltIn::Position`] member.
+    pub vertex_output_type: Handle<Type>,
+    /// The type of each primitive, a struct whose members all have bindings.
+    ///
+    /// It must include the index built-in for [`topology`], and its
+    /// [`Location`] members must be [`per_primitive`].
+    ///
+    /// [`topology`]: MeshStageInfo::topology
+    /// [`Location`]: Binding::Location
+    /// [`per_primitive`]: Binding::Location::per_primitive
+    pub primitive_output_type: Handle<Type>,
 }
 
 /// Return types predeclared for the frexp, modf, and atomicCompareExchangeWeak built-in functions.
@@ -0,0 +1,73 @@
+use crate::{
+    arena::HandleVec,
+    proc::{
+        adjust::{adjust_block, adjust_expr, filter_emits_in_block},
+        ConstantEvaluator, Emitter, ExpressionKind, ExpressionKindTracker,
+    },
+    Block, Function, Module,
+};
+use std::mem;
+
+/// Replace the expressions in `function` whose operands are all known at
+/// compile time with their values.
+///
+/// This rebuilds `function`'s expression arena by passing each expression
+/// to [`ConstantEvaluator::try_eval_and_append`], much like
+/// [`process_overrides`] does once overrides have values. Expressions that
+/// fail to evaluate, like an integer division by zero, are kept as they are.
+///
+/// The operands of folded expressions are left in the arena, unused, for
+/// compaction to remove.
+///
+/// [`process_overrides`]: crate::back::pipeline_constants::process_overrides
+pub(super) fn fold_constants(module: &mut Module, function: &mut Function) {
+    // A map from original expression handles to handles in the rebuilt arena.
+    let mut adjusted_expressions = HandleVec::with_capacity(function.expressions.len());
+
+    let mut expression_kind_tracker = ExpressionKindTracker::new();
+
+    // Dummy `emitter` and `block` for the constant evaluator. The `Emit`
+    // statements already in the function are adjusted below instead.
+    let mut emitter = Emitter::default();
+    let mut block = Block::new();
+
+    let mut expressions = mem::take(&mut function.expressions);
+    for (old_h, mut expr, span) in expressions.drain() {
+        adjust_expr(&adjusted_expressions, &mut expr);
+        let mut evaluator = ConstantEvaluator::for_wgsl_function(
+            module,
+            &mut function.expressions,
+            &mut expression_kind_tracker,
+            &mut emitter,
+            &mut block,
+            false,
+        );
+        let h = match evaluator.try_eval_and_append(expr.clone(), span) {
+            Ok(h) => h,
+            Err(error) => {
+                log::debug!("not folding {:?}: {}", expr, error);
+                let h = function.expressions.append(expr, span);
+                expression_kind_tracker.insert(h, ExpressionKind::Runtime);
+                h
+            }
+        };
+        adjusted_expressions.insert(old_h, h);
+    }
+
+    adjust_block(&adjusted_expressions, &mut function.body);
+
+    filter_emits_in_block(&mut function.body, &function.expressions);
+
+    for (_, local) in function.local_variables.iter_mut() {
+        if let Some(ref mut init) = local.init {
+            *init = adjusted_expressions[*init];
+        }
+    }
+
+    let named_expressions = mem::take(&mut function.named_expressions);
+    for (expr_h, name) in named_expressions {
+        function
+            .named_expressions
+            .insert(adjusted_expressions[expr_h], name);
+    }
+}
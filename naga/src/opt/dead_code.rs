@@ -0,0 +1,179 @@
+use crate::{
+    arena::{HandleSet, HandleVec},
+    Arena, Block, Expression, Function, Handle, LocalVariable, Statement,
+};
+use std::mem;
+
+/// Remove [`Store`] statements to local variables that are never read.
+///
+/// A local variable is read if a pointer to it, or to any part of it, is
+/// used in any way other than as the `pointer` of a [`Store`]: loaded from,
+/// passed to a function, and so on.
+///
+/// The stored values and the pointer expressions are left in the arena,
+/// unused, for compaction to remove. The variables themselves are removed by
+/// [`remove_unused_locals`] once that has happened.
+///
+/// [`Store`]: Statement::Store
+pub(super) fn remove_dead_stores(function: &mut Function) {
+    // For each expression that is a pointer into a local variable, that
+    // variable.
+    let mut roots = HandleVec::with_capacity(function.expressions.len());
+    let mut read = HandleSet::for_arena(&function.local_variables);
+    for (handle, expression) in function.expressions.iter() {
+        let root = match *expression {
+            Expression::LocalVariable(local) => Some(local),
+            Expression::Access { base, .. } | Expression::AccessIndex { base, .. } => roots[base],
+            Expression::Load { pointer }
+            | Expression::ArrayLength(pointer)
+            | Expression::RayQueryGetIntersection { query: pointer, .. } => {
+                if let Some(local) = roots[pointer] {
+                    read.insert(local);
+                }
+                None
+            }
+            _ => None,
+        };
+        roots.insert(handle, root);
+    }
+    mark_read_by_statements(&function.body, &roots, &mut read);
+
+    let is_dead = |pointer: Handle<Expression>| match roots[pointer] {
+        Some(local) => !read.contains(local),
+        None => false,
+    };
+    remove_stores(&mut function.body, &is_dead);
+}
+
+/// Add the local variables that statements in `block` read to `read`.
+///
+/// See [`remove_dead_stores`] for what counts as reading a variable.
+fn mark_read_by_statements(
+    block: &Block,
+    roots: &HandleVec<Expression, Option<Handle<LocalVariable>>>,
+    read: &mut HandleSet<LocalVariable>,
+) {
+    for statement in block.iter() {
+        match *statement {
+            Statement::Block(ref block) => mark_read_by_statements(block, roots, read),
+            Statement::If {
+                ref accept,
+                ref reject,
+                ..
+            } => {
+                mark_read_by_statements(accept, roots, read);
+                mark_read_by_statements(reject, roots, read);
+            }
+            Statement::Switch { ref cases, .. } => {
+                for case in cases.iter() {
+                    mark_read_by_statements(&case.body, roots, read);
+                }
+            }
+            Statement::Loop {
+                ref body,
+                ref continuing,
+                ..
+            } => {
+                mark_read_by_statements(body, roots, read);
+                mark_read_by_statements(continuing, roots, read);
+            }
+            Statement::Atomic { pointer, .. }
+            | Statement::WorkGroupUniformLoad { pointer, .. }
+            | Statement::RayQuery { query: pointer, .. } => {
+                if let Some(local) = roots[pointer] {
+                    read.insert(local);
+                }
+            }
+            Statement::Call { ref arguments, .. } => {
+                for &argument in arguments.iter() {
+                    if let Some(local) = roots[argument] {
+                        read.insert(local);
+                    }
+                }
+            }
+            Statement::Emit(_)
+            | Statement::Break
+            | Statement::Continue
+            | Statement::Return { .. }
+            | Statement::Kill
+            | Statement::Barrier(_)
+            | Statement::Store { .. }
+            | Statement::ImageStore { .. }
+            | Statement::SubgroupBallot { .. }
+            | Statement::SubgroupGather { .. }
+            | Statement::SubgroupCollectiveOperation { .. }
+            | Statement::MeshFunction(_) => {}
+        }
+    }
+}
+
+/// Remove the [`Store`] statements in `block` whose pointers are dead.
+///
+/// [`Store`]: Statement::Store
+fn remove_stores(block: &mut Block, is_dead: &impl Fn(Handle<Expression>) -> bool) {
+    let original = mem::replace(block, Block::with_capacity(block.len()));
+    for (mut statement, span) in original.span_into_iter() {
+        match statement {
+            Statement::Store { pointer, .. } if is_dead(pointer) => continue,
+            Statement::Block(ref mut block) => remove_stores(block, is_dead),
+            Statement::If {
+                ref mut accept,
+                ref mut reject,
+                ..
+            } => {
+                remove_stores(accept, is_dead);
+                remove_stores(reject, is_dead);
+            }
+            Statement::Switch { ref mut cases, .. } => {
+                for case in cases.iter_mut() {
+                    remove_stores(&mut case.body, is_dead);
+                }
+            }
+            Statement::Loop {
+                ref mut body,
+                ref mut continuing,
+                ..
+            } => {
+                remove_stores(body, is_dead);
+                remove_stores(continuing, is_dead);
+            }
+            _ => {}
+        }
+        block.push(statement, span);
+    }
+}
+
+/// Remove the local variables of `function` that no expression refers to.
+///
+/// Return true if any were removed.
+pub(super) fn remove_unused_locals(function: &mut Function) -> bool {
+    let mut used = HandleSet::for_arena(&function.local_variables);
+    for (_, expression) in function.expressions.iter() {
+        if let Expression::LocalVariable(local) = *expression {
+            used.insert(local);
+        }
+    }
+    if used.iter().count() == function.local_variables.len() {
+        return false;
+    }
+
+    let mut new_handles = HandleVec::with_capacity(function.local_variables.len());
+    let mut local_variables = Arena::new();
+    for (handle, local, span) in function.local_variables.drain() {
+        let new_handle = if used.contains(handle) {
+            Some(local_variables.append(local, span))
+        } else {
+            None
+        };
+        new_handles.insert(handle, new_handle);
+    }
+    function.local_variables = local_variables;
+
+    for (_, expression) in function.expressions.iter_mut() {
+        if let Expression::LocalVariable(ref mut local) = *expression {
+            *local = new_handles[*local].unwrap();
+        }
+    }
+
+    true
+}
@@ -0,0 +1,483 @@
+use crate::{
+    arena::{HandleSet, HandleVec},
+    proc::adjust::{adjust_block, adjust_expr},
+    Arena, Block, Expression, FastHashMap, Function, Handle, LocalVariable, Module, Range, Span,
+    Statement, TypeInner,
+};
+use std::mem;
+
+/// Inline every function that is called from exactly one place.
+///
+/// Each such call is replaced by a [`Block`] holding a copy of the callee's
+/// body, and the callee's local variables are added to the caller. If the
+/// callee returns a value, it is stored in a new local variable, and the
+/// call's [`CallResult`] expression becomes a [`Load`] from it.
+///
+/// Inlined functions are removed from `module`.
+///
+/// Since a [`Return`] can only be replaced by a store if nothing follows it
+/// in the callee, functions that return early are not inlined. See
+/// [`inline_calls`] for the other cases we leave alone.
+///
+/// [`CallResult`]: Expression::CallResult
+/// [`Load`]: Expression::Load
+/// [`Return`]: Statement::Return
+pub(super) fn inline_single_use_functions(module: &mut Module) {
+    let mut call_counts = FastHashMap::default();
+    let mut count_calls = |body: &mut Block| {
+        for_each_call(body, &mut |callee| {
+            *call_counts.entry(*callee).or_insert(0usize) += 1;
+        });
+    };
+    for (_, function) in module.functions.iter_mut() {
+        count_calls(&mut function.body);
+    }
+    for entry_point in module.entry_points.iter_mut() {
+        count_calls(&mut entry_point.function.body);
+    }
+
+    let mut candidates = HandleSet::for_arena(&module.functions);
+    for (handle, function) in module.functions.iter() {
+        if call_counts.get(&handle) == Some(&1) && returns_only_at_end(&function.body, true) {
+            candidates.insert(handle);
+        }
+    }
+    if candidates.iter().next().is_none() {
+        return;
+    }
+
+    // Callees always come before their callers in the arena, so by the time
+    // we inline a function, any calls it makes have been inlined into it
+    // already.
+    let mut inlined = HandleSet::for_arena(&module.functions);
+    let handles: Vec<_> = module.functions.iter().map(|(handle, _)| handle).collect();
+    for handle in handles {
+        let mut function = mem::take(&mut module.functions[handle]);
+        inline_calls(module, &candidates, &mut inlined, &mut function);
+        module.functions[handle] = function;
+    }
+    for index in 0..module.entry_points.len() {
+        let mut function = mem::take(&mut module.entry_points[index].function);
+        inline_calls(module, &candidates, &mut inlined, &mut function);
+        module.entry_points[index].function = function;
+    }
+
+    remove_functions(module, &inlined);
+}
+
+/// A call in the function being processed that we are going to inline.
+struct CallSite {
+    /// Whether the call is inside a [`Loop`](Statement::Loop).
+    in_loop: bool,
+
+    /// A pointer to the local variable holding the callee's return value,
+    /// if the caller uses it.
+    result_pointer: Option<Handle<Expression>>,
+
+    /// The [`Load`](Expression::Load) from `result_pointer` that replaces the call's
+    /// [`CallResult`](Expression::CallResult) expression.
+    result_load: Option<Handle<Expression>>,
+}
+
+/// Inline the calls in `caller` to functions in `candidates`, and add the
+/// callees to `inlined`.
+///
+/// Calls are not inlined if the callee has different diagnostic filters
+/// from the caller, or if the call is in a loop and the callee's local
+/// variables can't be initialized afresh for each iteration.
+fn inline_calls(
+    module: &Module,
+    candidates: &HandleSet<Function>,
+    inlined: &mut HandleSet<Function>,
+    caller: &mut Function,
+) {
+    let mut sites = FastHashMap::default();
+    find_call_sites(&caller.body, false, &mut |callee, in_loop| {
+        if !candidates.contains(callee) {
+            return;
+        }
+        let function = &module.functions[callee];
+        if function.diagnostic_filter_leaf != caller.diagnostic_filter_leaf
+            || in_loop && !can_reinitialize_locals(module, function)
+        {
+            return;
+        }
+        sites.insert(
+            callee,
+            CallSite {
+                in_loop,
+                result_pointer: None,
+                result_load: None,
+            },
+        );
+    });
+    if sites.is_empty() {
+        return;
+    }
+
+    // Rebuild the caller's expression arena, replacing the results of the
+    // calls we're inlining with loads from their result variables. The
+    // pointers to the variables must come before the loads, so we can't
+    // just replace the `CallResult` expressions in place.
+    let mut expression_map = HandleVec::with_capacity(caller.expressions.len());
+    let mut expressions = mem::take(&mut caller.expressions);
+    for (handle, mut expression, span) in expressions.drain() {
+        if let Expression::CallResult(callee) = expression {
+            if let Some(site) = sites.get_mut(&callee) {
+                let function = &module.functions[callee];
+                let local = LocalVariable {
+                    name: function.name.as_ref().map(|name| format!("{name}_result")),
+                    ty: function.result.as_ref().unwrap().ty,
+                    init: None,
+                };
+                let local = caller.local_variables.append(local, Span::UNDEFINED);
+                let pointer = caller
+                    .expressions
+                    .append(Expression::LocalVariable(local), span);
+                let load = caller
+                    .expressions
+                    .append(Expression::Load { pointer }, span);
+                site.result_pointer = Some(pointer);
+                site.result_load = Some(load);
+                expression_map.insert(handle, load);
+                continue;
+            }
+        }
+        adjust_expr(&expression_map, &mut expression);
+        expression_map.insert(handle, caller.expressions.append(expression, span));
+    }
+
+    adjust_block(&expression_map, &mut caller.body);
+    for (_, local) in caller.local_variables.iter_mut() {
+        if let Some(ref mut init) = local.init {
+            *init = expression_map[*init];
+        }
+    }
+    let named_expressions = mem::take(&mut caller.named_expressions);
+    for (handle, name) in named_expressions {
+        caller
+            .named_expressions
+            .insert(expression_map[handle], name);
+    }
+
+    let mut body = mem::take(&mut caller.body);
+    replace_calls(module, &sites, caller, &mut body);
+    caller.body = body;
+
+    for &callee in sites.keys() {
+        inlined.insert(callee);
+    }
+}
+
+/// Replace the calls in `block` to the functions in `sites` with their bodies.
+fn replace_calls(
+    module: &Module,
+    sites: &FastHashMap<Handle<Function>, CallSite>,
+    caller: &mut Function,
+    block: &mut Block,
+) {
+    let original = mem::replace(block, Block::with_capacity(block.len()));
+    for (mut statement, span) in original.span_into_iter() {
+        match statement {
+            Statement::Call {
+                function,
+                ref arguments,
+                ..
+            } => {
+                if let Some(site) = sites.get(&function) {
+                    let body = inline_call(module, caller, function, arguments, site);
+                    block.push(Statement::Block(body), span);
+                    if let Some(load) = site.result_load {
+                        block.push(Statement::Emit(Range::new_from_bounds(load, load)), span);
+                    }
+                    continue;
+                }
+            }
+            Statement::Block(ref mut inner) => replace_calls(module, sites, caller, inner),
+            Statement::If {
+                ref mut accept,
+                ref mut reject,
+                ..
+            } => {
+                replace_calls(module, sites, caller, accept);
+                replace_calls(module, sites, caller, reject);
+            }
+            Statement::Switch { ref mut cases, .. } => {
+                for case in cases.iter_mut() {
+                    replace_calls(module, sites, caller, &mut case.body);
+                }
+            }
+            Statement::Loop {
+                ref mut body,
+                ref mut continuing,
+                ..
+            } => {
+                replace_calls(module, sites, caller, body);
+                replace_calls(module, sites, caller, continuing);
+            }
+            _ => {}
+        }
+        block.push(statement, span);
+    }
+}
+
+/// Copy `callee` into `caller`, and return the block to replace the call with.
+fn inline_call(
+    module: &Module,
+    caller: &mut Function,
+    callee: Handle<Function>,
+    arguments: &[Handle<Expression>],
+    site: &CallSite,
+) -> Block {
+    let callee = &module.functions[callee];
+
+    let mut local_map = HandleVec::with_capacity(callee.local_variables.len());
+    for (handle, local) in callee.local_variables.iter() {
+        let span = callee.local_variables.get_span(handle);
+        let local = LocalVariable {
+            name: local.name.clone(),
+            ty: local.ty,
+            init: None,
+        };
+        local_map.insert(handle, caller.local_variables.append(local, span));
+    }
+
+    let mut expression_map = HandleVec::with_capacity(callee.expressions.len());
+    for (handle, expression) in callee.expressions.iter() {
+        let span = callee.expressions.get_span(handle);
+        let new_handle = match *expression {
+            Expression::FunctionArgument(index) => arguments[index as usize],
+            Expression::LocalVariable(local) => caller
+                .expressions
+                .append(Expression::LocalVariable(local_map[local]), span),
+            _ => {
+                let mut expression = expression.clone();
+                adjust_expr(&expression_map, &mut expression);
+                caller.expressions.append(expression, span)
+            }
+        };
+        expression_map.insert(handle, new_handle);
+    }
+
+    for (&handle, name) in callee.named_expressions.iter() {
+        if !matches!(callee.expressions[handle], Expression::FunctionArgument(_)) {
+            caller
+                .named_expressions
+                .insert(expression_map[handle], name.clone());
+        }
+    }
+
+    let mut body = Block::new();
+    for (handle, local) in callee.local_variables.iter() {
+        let init = local.init.map(|init| expression_map[init]);
+        caller.local_variables[local_map[handle]].init = init;
+
+        // Local variables are initialized on entry to the function, but we
+        // may run the inlined code several times.
+        if site.in_loop {
+            let pointer = caller.expressions.append(
+                Expression::LocalVariable(local_map[handle]),
+                Span::UNDEFINED,
+            );
+            let value = init.unwrap_or_else(|| {
+                caller
+                    .expressions
+                    .append(Expression::ZeroValue(local.ty), Span::UNDEFINED)
+            });
+            body.push(Statement::Store { pointer, value }, Span::UNDEFINED);
+        }
+    }
+
+    let mut callee_body = callee.body.clone();
+    adjust_block(&expression_map, &mut callee_body);
+    replace_returns(&mut callee_body, site.result_pointer);
+    body.extend_block(callee_body);
+    body
+}
+
+/// Replace the [`Return`] statements in `block` with stores to `result`.
+///
+/// The inlinee must only return at its end, as [`returns_only_at_end`]
+/// checks, so control flows out of the inlined block afterwards anyway.
+///
+/// [`Return`]: Statement::Return
+fn replace_returns(block: &mut Block, result: Option<Handle<Expression>>) {
+    let original = mem::replace(block, Block::with_capacity(block.len()));
+    for (mut statement, span) in original.span_into_iter() {
+        match statement {
+            Statement::Return { value } => {
+                if let (Some(pointer), Some(value)) = (result, value) {
+                    block.push(Statement::Store { pointer, value }, span);
+                }
+                continue;
+            }
+            Statement::Block(ref mut inner) => replace_returns(inner, result),
+            Statement::If {
+                ref mut accept,
+                ref mut reject,
+                ..
+            } => {
+                replace_returns(accept, result);
+                replace_returns(reject, result);
+            }
+            _ => {}
+        }
+        block.push(statement, span);
+    }
+}
+
+/// Return true if every [`Return`] in `block` is its last statement, or the
+/// last statement of an [`If`] or [`Block`] statement that is.
+///
+/// Pass `true` for `at_end` if `block` is a function's body.
+///
+/// [`Return`]: Statement::Return
+/// [`If`]: Statement::If
+/// [`Block`]: Statement::Block
+fn returns_only_at_end(block: &Block, at_end: bool) -> bool {
+    let last = block.len().checked_sub(1);
+    block.iter().enumerate().all(|(index, statement)| {
+        let at_end = at_end && Some(index) == last;
+        match *statement {
+            Statement::Return { .. } => at_end,
+            Statement::Block(ref block) => returns_only_at_end(block, at_end),
+            Statement::If {
+                ref accept,
+                ref reject,
+                ..
+            } => returns_only_at_end(accept, at_end) && returns_only_at_end(reject, at_end),
+            Statement::Switch { ref cases, .. } => cases
+                .iter()
+                .all(|case| returns_only_at_end(&case.body, false)),
+            Statement::Loop {
+                ref body,
+                ref continuing,
+                ..
+            } => returns_only_at_end(body, false) && returns_only_at_end(continuing, false),
+            _ => true,
+        }
+    })
+}
+
+/// Return true if we can store initial values to all of `function`'s
+/// local variables at the start of its inlined body.
+///
+/// That needs an initializer that doesn't have to be emitted first, and
+/// rules out ray queries, which can't be stored to.
+fn can_reinitialize_locals(module: &Module, function: &Function) -> bool {
+    function.local_variables.iter().all(|(_, local)| {
+        let init_ok = match local.init {
+            Some(init) => function.expressions[init].needs_pre_emit(),
+            None => true,
+        };
+        init_ok && !matches!(module.types[local.ty].inner, TypeInner::RayQuery)
+    })
+}
+
+/// Call `f` on the callee of every [`Call`] in `block`, along with whether the
+/// call is inside a loop.
+///
+/// Pass `in_loop` as `true` if `block` itself is part of a loop.
+///
+/// [`Call`]: Statement::Call
+fn find_call_sites(block: &Block, in_loop: bool, f: &mut impl FnMut(Handle<Function>, bool)) {
+    for statement in block.iter() {
+        match *statement {
+            Statement::Call { function, .. } => f(function, in_loop),
+            Statement::Block(ref block) => find_call_sites(block, in_loop, f),
+            Statement::If {
+                ref accept,
+                ref reject,
+                ..
+            } => {
+                find_call_sites(accept, in_loop, f);
+                find_call_sites(reject, in_loop, f);
+            }
+            Statement::Switch { ref cases, .. } => {
+                for case in cases.iter() {
+                    find_call_sites(&case.body, in_loop, f);
+                }
+            }
+            Statement::Loop {
+                ref body,
+                ref continuing,
+                ..
+            } => {
+                find_call_sites(body, true, f);
+                find_call_sites(continuing, true, f);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Call `f` on the callee of every [`Call`] in `block`.
+///
+/// [`Call`]: Statement::Call
+fn for_each_call(block: &mut Block, f: &mut impl FnMut(&mut Handle<Function>)) {
+    for statement in block.iter_mut() {
+        match *statement {
+            Statement::Call {
+                ref mut function, ..
+            } => f(function),
+            Statement::Block(ref mut block) => for_each_call(block, f),
+            Statement::If {
+                ref mut accept,
+                ref mut reject,
+                ..
+            } => {
+                for_each_call(accept, f);
+                for_each_call(reject, f);
+            }
+            Statement::Switch { ref mut cases, .. } => {
+                for case in cases.iter_mut() {
+                    for_each_call(&mut case.body, f);
+                }
+            }
+            Statement::Loop {
+                ref mut body,
+                ref mut continuing,
+                ..
+            } => {
+                for_each_call(body, f);
+                for_each_call(continuing, f);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Remove the `removed` functions from `module`, and adjust the handles of
+/// the rest.
+///
+/// Nothing may call the functions in `removed` any more.
+fn remove_functions(module: &mut Module, removed: &HandleSet<Function>) {
+    let mut new_handles = HandleVec::with_capacity(module.functions.len());
+    let mut functions = Arena::new();
+    for (handle, function, span) in module.functions.drain() {
+        let new_handle = if removed.contains(handle) {
+            None
+        } else {
+            Some(functions.append(function, span))
+        };
+        new_handles.insert(handle, new_handle);
+    }
+    module.functions = functions;
+
+    let adjust = |function: &mut Function| {
+        for (_, expression) in function.expressions.iter_mut() {
+            if let Expression::CallResult(ref mut callee) = *expression {
+                *callee = new_handles[*callee].unwrap();
+            }
+        }
+        for_each_call(&mut function.body, &mut |callee| {
+            *callee = new_handles[*callee].unwrap();
+        });
+    };
+    for (_, function) in module.functions.iter_mut() {
+        adjust(function);
+    }
+    for entry_point in module.entry_points.iter_mut() {
+        adjust(&mut entry_point.function);
+    }
+}
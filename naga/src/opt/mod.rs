@@ -0,0 +1,117 @@
+/*!
+Optimization passes over Naga IR.
+
+Naga's backends translate the IR they are given quite literally, so
+redundant expressions, unused local variables and small helper functions
+all survive into the generated code. Drivers with capable shader compilers
+clean these up themselves, but some GLES and WebGL drivers do not.
+
+The passes here rewrite a validated module into a functionally identical one
+that is simpler to translate. They are all opt-in: see [`Passes`] and
+[`optimize`].
+*/
+
+mod constant_folding;
+mod dead_code;
+mod inline;
+
+use crate::{Function, Module};
+use std::mem;
+
+bitflags::bitflags! {
+    /// The optimization passes [`optimize`] should run.
+    ///
+    /// The default value for `Passes` is `Passes::all()`.
+    #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+    #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub struct Passes: u8 {
+        /// Inline functions that are only called from one place.
+        const INLINE = 0x1;
+        /// Replace expressions whose operands are known at compile time
+        /// with their values, using [`ConstantEvaluator`].
+        ///
+        /// [`ConstantEvaluator`]: crate::proc::ConstantEvaluator
+        const CONSTANT_FOLDING = 0x2;
+        /// Remove stores to local variables that are never read, unused
+        /// local variables, and named expressions that nothing uses.
+        const DEAD_CODE = 0x4;
+    }
+}
+
+impl Default for Passes {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+/// Run the optimization `passes` on `module`.
+///
+/// Inlining runs first, so that constant arguments can be folded into the
+/// inlined code, and dead code elimination runs last, to clean up after
+/// both. Finally, the module is [compacted], which also removes the
+/// expressions left unused by the other passes.
+///
+/// The result is functionally identical to the original, but must be
+/// validated again before being passed to a backend.
+///
+/// # Panics
+///
+/// If `module` has not passed validation, this may panic.
+///
+/// [compacted]: crate::compact::compact
+pub fn optimize(module: &mut Module, passes: Passes) {
+    if passes.is_empty() {
+        return;
+    }
+
+    if passes.contains(Passes::INLINE) {
+        log::trace!("inlining single-use functions");
+        inline::inline_single_use_functions(module);
+    }
+
+    if passes.contains(Passes::CONSTANT_FOLDING) {
+        log::trace!("folding constants");
+        for_each_function(module, constant_folding::fold_constants);
+    }
+
+    if passes.contains(Passes::DEAD_CODE) {
+        log::trace!("removing dead code");
+        for_each_function(module, |_, function| {
+            dead_code::remove_dead_stores(function)
+        });
+        crate::compact::compact_unused_named_expressions(module);
+
+        // Compaction removed the expressions referring to local variables
+        // we no longer store to, so they can go now too. Their initializers
+        // are left behind, so compact once more if we removed any.
+        let mut removed_locals = false;
+        for_each_function(module, |_, function| {
+            removed_locals |= dead_code::remove_unused_locals(function);
+        });
+        if removed_locals {
+            crate::compact::compact_unused_named_expressions(module);
+        }
+    } else {
+        crate::compact::compact(module);
+    }
+}
+
+/// Call `f` on every function and entry point in `module`.
+///
+/// Each function is taken out of `module` while `f` works on it, so `f`
+/// must not look at it through `module`.
+fn for_each_function(module: &mut Module, mut f: impl FnMut(&mut Module, &mut Function)) {
+    let handles: Vec<_> = module.functions.iter().map(|(handle, _)| handle).collect();
+    for handle in handles {
+        let mut function = mem::take(&mut module.functions[handle]);
+        f(module, &mut function);
+        module.functions[handle] = function;
+    }
+
+    for index in 0..module.entry_points.len() {
+        let mut function = mem::take(&mut module.entry_points[index].function);
+        f(module, &mut function);
+        module.entry_points[index].function = function;
+    }
+}
@@ -1,6 +1,7 @@
 use std::iter;
 
 use arrayvec::ArrayVec;
+use half::f16;
 
 use crate::{
     arena::{Arena, Handle, HandleVec, UniqueArena},
@@ -199,6 +200,7 @@ gen_component_wise_extractor! {
     literals: [
         AbstractFloat => AbstractFloat: f64,
         F32 => F32: f32,
+        F16 => F16: f16,
         AbstractInt => AbstractInt: i64,
         U32 => U32: u32,
         I32 => I32: i32,
@@ -218,7 +220,9 @@ gen_component_wise_extractor! {
     component_wise_float -> Float,
     literals: [
         AbstractFloat => Abstract: f64,
+        F64 => F64: f64,
         F32 => F32: f32,
+        F16 => F16: f16,
     ],
     scalar_kinds: [
         Float,
@@ -244,6 +248,7 @@ gen_component_wise_extractor! {
         AbstractFloat => AbstractFloat: f64,
         AbstractInt => AbstractInt: i64,
         F32 => F32: f32,
+        F16 => F16: f16,
         I32 => I32: i32,
     ],
     scalar_kinds: [
@@ -307,6 +312,15 @@ pub struct ConstantEvaluator<'a> {
     /// The module's override arena.
     overrides: &'a Arena<Override>,
 
+    /// The module's special types.
+    ///
+    /// Built-ins like [`Modf`] and [`Frexp`] return predeclared struct types,
+    /// which the front end must generate before asking us to evaluate them.
+    ///
+    /// [`Modf`]: crate::MathFunction::Modf
+    /// [`Frexp`]: crate::MathFunction::Frexp
+    special_types: &'a crate::SpecialTypes,
+
     /// The arena to which we are contributing expressions.
     expressions: &'a mut Arena<Expression>,
 
@@ -354,7 +368,7 @@ pub enum ExpressionKind {
     Runtime,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct ExpressionKindTracker {
     inner: HandleVec<Expression, ExpressionKind>,
 }
@@ -411,7 +425,6 @@ impl ExpressionKindTracker {
     }
 
     fn type_of_with_expr(&self, expr: &Expression) -> ExpressionKind {
-        use crate::MathFunction as Mf;
         match *expr {
             Expression::Literal(_) | Expression::ZeroValue(_) | Expression::Constant(_) => {
                 ExpressionKind::ImplConst
@@ -434,11 +447,11 @@ impl ExpressionKindTracker {
                 .max(self.type_of(right))
                 .max(ExpressionKind::Const),
             Expression::Math {
-                fun,
                 arg,
                 arg1,
                 arg2,
                 arg3,
+                ..
             } => self
                 .type_of(arg)
                 .max(
@@ -452,34 +465,8 @@ impl ExpressionKindTracker {
                 .max(
                     arg3.map(|arg| self.type_of(arg))
                         .unwrap_or(ExpressionKind::Const),
-                )
-                .max(
-                    if matches!(
-                        fun,
-                        Mf::Dot
-                            | Mf::Outer
-                            | Mf::Cross
-                            | Mf::Distance
-                            | Mf::Length
-                            | Mf::Normalize
-                            | Mf::FaceForward
-                            | Mf::Reflect
-                            | Mf::Refract
-                            | Mf::Ldexp
-                            | Mf::Modf
-                            | Mf::Mix
-                            | Mf::Frexp
-                    ) {
-                        ExpressionKind::Const
-                    } else {
-                        ExpressionKind::ImplConst
-                    },
                 ),
-            Expression::As { convert, expr, .. } => self.type_of(expr).max(if convert.is_some() {
-                ExpressionKind::ImplConst
-            } else {
-                ExpressionKind::Const
-            }),
+            Expression::As { expr, .. } => self.type_of(expr),
             Expression::Select {
                 condition,
                 accept,
@@ -487,8 +474,7 @@ impl ExpressionKindTracker {
             } => self
                 .type_of(condition)
                 .max(self.type_of(accept))
-                .max(self.type_of(reject))
-                .max(ExpressionKind::Const),
+                .max(self.type_of(reject)),
             Expression::Relational { argument, .. } => self.type_of(argument),
             Expression::ArrayLength(expr) => self.type_of(expr),
             _ => ExpressionKind::Runtime,
@@ -545,6 +531,14 @@ pub enum ConstantEvaluatorError {
     InvalidMathArgCount(crate::MathFunction, usize, usize),
     #[error("value of `low` is greater than `high` for clamp built-in function")]
     InvalidClamp,
+    #[error("`offset` plus `count` exceeds the bit width for the {0:?} built-in function")]
+    InvalidBitRange(crate::MathFunction),
+    #[error("Cannot apply the select built-in function to the arguments")]
+    InvalidSelectArgs,
+    #[error("Cannot apply the {0:?} built-in function to the argument")]
+    InvalidRelationalArg(crate::RelationalFunction),
+    #[error("Cannot bitcast the argument to a scalar of kind {0:?}")]
+    InvalidBitcastArg(ScalarKind),
     #[error("Splat is defined only on scalar values")]
     SplatScalarOnly,
     #[error("Can only swizzle vector constants")]
@@ -566,6 +560,11 @@ pub enum ConstantEvaluatorError {
         value: String,
         to_type: &'static str,
     },
+    #[error("value `{value}` is outside the range of `{to_type}`")]
+    CastOutOfRange {
+        value: String,
+        to_type: &'static str,
+    },
     #[error("abstract floating-point values cannot be automatically converted to integers")]
     AutomaticConversionFloatToInt { to_type: &'static str },
     #[error("Division by zero")]
@@ -630,6 +629,7 @@ impl<'a> ConstantEvaluator<'a> {
             types: &mut module.types,
             constants: &module.constants,
             overrides: &module.overrides,
+            special_types: &module.special_types,
             expressions: &mut module.global_expressions,
             expression_kind_tracker: global_expression_kind_tracker,
         }
@@ -661,6 +661,7 @@ impl<'a> ConstantEvaluator<'a> {
             types: &mut module.types,
             constants: &module.constants,
             overrides: &module.overrides,
+            special_types: &module.special_types,
             expressions,
             expression_kind_tracker: local_expression_kind_tracker,
         }
@@ -686,6 +687,7 @@ impl<'a> ConstantEvaluator<'a> {
             types: &mut module.types,
             constants: &module.constants,
             overrides: &module.overrides,
+            special_types: &module.special_types,
             expressions,
             expression_kind_tracker: local_expression_kind_tracker,
         }
@@ -909,17 +911,25 @@ impl<'a> ConstantEvaluator<'a> {
 
                 match convert {
                     Some(width) => self.cast(expr, crate::Scalar { kind, width }, span),
-                    None => Err(ConstantEvaluatorError::NotImplemented(
-                        "bitcast built-in function".into(),
-                    )),
+                    None => self.bitcast(expr, kind, span),
                 }
             }
-            Expression::Select { .. } => Err(ConstantEvaluatorError::NotImplemented(
-                "select built-in function".into(),
-            )),
-            Expression::Relational { fun, .. } => Err(ConstantEvaluatorError::NotImplemented(
-                format!("{fun:?} built-in function"),
-            )),
+            Expression::Select {
+                condition,
+                accept,
+                reject,
+            } => {
+                let condition = self.check_and_get(condition)?;
+                let accept = self.check_and_get(accept)?;
+                let reject = self.check_and_get(reject)?;
+
+                self.select(condition, accept, reject, span)
+            }
+            Expression::Relational { fun, argument } => {
+                let argument = self.check_and_get(argument)?;
+
+                self.relational(fun, argument, span)
+            }
             Expression::ArrayLength(expr) => match self.behavior {
                 Behavior::Wgsl(_) => Err(ConstantEvaluatorError::ArrayLength),
                 Behavior::Glsl(_) => {
@@ -1088,6 +1098,7 @@ impl<'a> ConstantEvaluator<'a> {
                 component_wise_scalar(self, span, [arg], |args| match args {
                     Scalar::AbstractFloat([e]) => Ok(Scalar::AbstractFloat([e.abs()])),
                     Scalar::F32([e]) => Ok(Scalar::F32([e.abs()])),
+                    Scalar::F16([e]) => Ok(Scalar::F16([e.abs()])),
                     Scalar::AbstractInt([e]) => Ok(Scalar::AbstractInt([e.abs()])),
                     Scalar::I32([e]) => Ok(Scalar::I32([e.wrapping_abs()])),
                     Scalar::U32([e]) => Ok(Scalar::U32([e])), // TODO: just re-use the expression, ezpz
@@ -1119,9 +1130,12 @@ impl<'a> ConstantEvaluator<'a> {
                     }
                 )
             }
-            crate::MathFunction::Saturate => {
-                component_wise_float!(self, span, [arg], |e| { Ok([e.clamp(0., 1.)]) })
-            }
+            crate::MathFunction::Saturate => component_wise_float(self, span, [arg], |e| match e {
+                Float::Abstract([e]) => Ok(Float::Abstract([e.clamp(0., 1.)])),
+                Float::F64([e]) => Ok(Float::F64([e.clamp(0., 1.)])),
+                Float::F32([e]) => Ok(Float::F32([e.clamp(0., 1.)])),
+                Float::F16([e]) => Ok(Float::F16([e.clamp(f16::ZERO, f16::ONE)])),
+            }),
 
             // trigonometry
             crate::MathFunction::Cos => {
@@ -1151,6 +1165,11 @@ impl<'a> ConstantEvaluator<'a> {
             crate::MathFunction::Atan => {
                 component_wise_float!(self, span, [arg], |e| { Ok([e.atan()]) })
             }
+            crate::MathFunction::Atan2 => {
+                component_wise_float!(self, span, [arg, arg1.unwrap()], |y, x| {
+                    Ok([y.atan2(x)])
+                })
+            }
             crate::MathFunction::Asinh => {
                 component_wise_float!(self, span, [arg], |e| { Ok([e.asinh()]) })
             }
@@ -1197,7 +1216,9 @@ impl<'a> ConstantEvaluator<'a> {
                 }
                 component_wise_float(self, span, [arg], |e| match e {
                     Float::Abstract([e]) => Ok(Float::Abstract([round_ties_even(e)])),
+                    Float::F64([e]) => Ok(Float::F64([round_ties_even(e)])),
                     Float::F32([e]) => Ok(Float::F32([(round_ties_even(e as f64) as f32)])),
+                    Float::F16([e]) => Ok(Float::F16([f16::from_f64(round_ties_even(e.to_f64()))])),
                 })
             }
             crate::MathFunction::Fract => {
@@ -1210,6 +1231,9 @@ impl<'a> ConstantEvaluator<'a> {
             crate::MathFunction::Trunc => {
                 component_wise_float!(self, span, [arg], |e| { Ok([e.trunc()]) })
             }
+            crate::MathFunction::Modf => self.modf(arg, span),
+            crate::MathFunction::Frexp => self.frexp(arg, span),
+            crate::MathFunction::Ldexp => self.ldexp(arg, arg1.unwrap(), span),
 
             // exponent
             crate::MathFunction::Exp => {
@@ -1230,6 +1254,67 @@ impl<'a> ConstantEvaluator<'a> {
                 })
             }
 
+            // geometry
+            crate::MathFunction::Dot => self.dot(arg, arg1.unwrap(), span),
+            crate::MathFunction::Dot4I8Packed => {
+                self.packed_dot_product(arg, arg1.unwrap(), span, true)
+            }
+            crate::MathFunction::Dot4U8Packed => {
+                self.packed_dot_product(arg, arg1.unwrap(), span, false)
+            }
+            crate::MathFunction::Outer => self.outer(arg, arg1.unwrap(), span),
+            crate::MathFunction::Cross => self.geometric([arg, arg1.unwrap()], span, |[a, b]| {
+                let (&[a0, a1, a2], &[b0, b1, b2]) = (a.as_slice(), b.as_slice()) else {
+                    return Err(ConstantEvaluatorError::InvalidMathArg);
+                };
+                Ok(Geometric::Vector(
+                    [a1 * b2 - a2 * b1, a2 * b0 - a0 * b2, a0 * b1 - a1 * b0]
+                        .into_iter()
+                        .collect(),
+                ))
+            }),
+            crate::MathFunction::Distance => self.geometric([arg, arg1.unwrap()], span, |[a, b]| {
+                let difference = a.iter().zip(&b).map(|(a, b)| a - b);
+                Ok(Geometric::Scalar(length(difference)))
+            }),
+            crate::MathFunction::Length => self.geometric([arg], span, |[e]| {
+                Ok(Geometric::Scalar(length(e.iter().copied())))
+            }),
+            crate::MathFunction::Normalize => self.geometric([arg], span, |[e]| {
+                let length = length(e.iter().copied());
+                Ok(Geometric::Vector(e.iter().map(|e| e / length).collect()))
+            }),
+            crate::MathFunction::FaceForward => {
+                self.geometric([arg, arg1.unwrap(), arg2.unwrap()], span, |[n, i, n_ref]| {
+                    let sign = if dot(&n_ref, &i) < 0.0 { 1.0 } else { -1.0 };
+                    Ok(Geometric::Vector(n.iter().map(|n| sign * n).collect()))
+                })
+            }
+            crate::MathFunction::Reflect => self.geometric([arg, arg1.unwrap()], span, |[i, n]| {
+                let dot = dot(&n, &i);
+                Ok(Geometric::Vector(
+                    i.iter().zip(&n).map(|(i, n)| i - 2.0 * dot * n).collect(),
+                ))
+            }),
+            crate::MathFunction::Refract => {
+                self.geometric([arg, arg1.unwrap(), arg2.unwrap()], span, |[i, n, eta]| {
+                    let &[eta] = eta.as_slice() else {
+                        return Err(ConstantEvaluatorError::InvalidMathArg);
+                    };
+                    let dot = dot(&n, &i);
+                    let k = 1.0 - eta * eta * (1.0 - dot * dot);
+                    if k < 0.0 {
+                        return Ok(Geometric::Vector(i.iter().map(|_| 0.0).collect()));
+                    }
+                    Ok(Geometric::Vector(
+                        i.iter()
+                            .zip(&n)
+                            .map(|(i, n)| eta * i - (eta * dot + k.sqrt()) * n)
+                            .collect(),
+                    ))
+                })
+            }
+
             // computational
             crate::MathFunction::Sign => {
                 component_wise_signed!(self, span, [arg], |e| { Ok([e.signum()]) })
@@ -1242,16 +1327,72 @@ impl<'a> ConstantEvaluator<'a> {
                     |e1, e2, e3| { Ok([e1.mul_add(e2, e3)]) }
                 )
             }
+            crate::MathFunction::Mix => {
+                let (x, y, a) = (arg, arg1.unwrap(), arg2.unwrap());
+                let a = self.splat_to_match(a, x, span)?;
+                component_wise_float(self, span, [x, y, a], |args| match args {
+                    Float::Abstract([x, y, a]) => Ok(Float::Abstract([x * (1.0 - a) + y * a])),
+                    Float::F64([x, y, a]) => Ok(Float::F64([x * (1.0 - a) + y * a])),
+                    Float::F32([x, y, a]) => Ok(Float::F32([x * (1.0 - a) + y * a])),
+                    Float::F16([x, y, a]) => Ok(Float::F16([x * (f16::ONE - a) + y * a])),
+                })
+            }
             crate::MathFunction::Step => {
-                component_wise_float!(self, span, [arg, arg1.unwrap()], |edge, x| {
-                    Ok([if edge <= x { 1.0 } else { 0.0 }])
+                component_wise_float(self, span, [arg, arg1.unwrap()], |args| match args {
+                    Float::Abstract([edge, x]) => {
+                        Ok(Float::Abstract([if edge <= x { 1.0 } else { 0.0 }]))
+                    }
+                    Float::F64([edge, x]) => Ok(Float::F64([if edge <= x { 1.0 } else { 0.0 }])),
+                    Float::F32([edge, x]) => Ok(Float::F32([if edge <= x { 1.0 } else { 0.0 }])),
+                    Float::F16([edge, x]) => {
+                        Ok(Float::F16([if edge <= x { f16::ONE } else { f16::ZERO }]))
+                    }
+                })
+            }
+            crate::MathFunction::SmoothStep => {
+                fn smooth_step(low: f64, high: f64, x: f64) -> f64 {
+                    let t = ((x - low) / (high - low)).clamp(0.0, 1.0);
+                    t * t * (3.0 - 2.0 * t)
+                }
+                let (low, high, x) = (arg, arg1.unwrap(), arg2.unwrap());
+                let low = self.splat_to_match(low, x, span)?;
+                let high = self.splat_to_match(high, x, span)?;
+                component_wise_float(self, span, [low, high, x], |args| match args {
+                    Float::Abstract([low, high, x]) => {
+                        Ok(Float::Abstract([smooth_step(low, high, x)]))
+                    }
+                    Float::F64([low, high, x]) => Ok(Float::F64([smooth_step(low, high, x)])),
+                    Float::F32([low, high, x]) => {
+                        Ok(Float::F32([
+                            smooth_step(low.into(), high.into(), x.into()) as f32
+                        ]))
+                    }
+                    Float::F16([low, high, x]) => Ok(Float::F16([f16::from_f64(smooth_step(
+                        low.to_f64(),
+                        high.to_f64(),
+                        x.to_f64(),
+                    ))])),
                 })
             }
             crate::MathFunction::Sqrt => {
                 component_wise_float!(self, span, [arg], |e| { Ok([e.sqrt()]) })
             }
             crate::MathFunction::InverseSqrt => {
-                component_wise_float!(self, span, [arg], |e| { Ok([1. / e.sqrt()]) })
+                component_wise_float(self, span, [arg], |e| match e {
+                    Float::Abstract([e]) => Ok(Float::Abstract([1. / e.sqrt()])),
+                    Float::F64([e]) => Ok(Float::F64([1. / e.sqrt()])),
+                    Float::F32([e]) => Ok(Float::F32([1. / e.sqrt()])),
+                    Float::F16([e]) => Ok(Float::F16([f16::ONE / e.sqrt()])),
+                })
+            }
+            crate::MathFunction::Inverse => self.inverse(arg, span),
+            crate::MathFunction::Transpose => self.transpose(arg, span),
+            crate::MathFunction::Determinant => self.determinant(arg, span),
+            crate::MathFunction::QuantizeToF16 => {
+                component_wise_float(self, span, [arg], |e| match e {
+                    Float::F32([e]) => Ok(Float::F32([f16::from_f32(e).to_f32()])),
+                    _ => Err(ConstantEvaluatorError::InvalidMathArg),
+                })
             }
 
             // bits
@@ -1285,6 +1426,28 @@ impl<'a> ConstantEvaluator<'a> {
             crate::MathFunction::ReverseBits => {
                 component_wise_concrete_int!(self, span, [arg], |e| { Ok([e.reverse_bits()]) })
             }
+            crate::MathFunction::ExtractBits => {
+                let (offset, count) = self.bit_range(fun, arg1.unwrap(), arg2.unwrap(), span)?;
+                component_wise_concrete_int(self, span, [arg], |e| match e {
+                    ConcreteInt::I32([e]) => {
+                        Ok(ConcreteInt::I32([extract_signed_bits(e, offset, count)]))
+                    }
+                    ConcreteInt::U32([e]) => Ok(ConcreteInt::U32([extract_bits(e, offset, count)])),
+                })
+            }
+            crate::MathFunction::InsertBits => {
+                let (offset, count) = self.bit_range(fun, arg2.unwrap(), arg3.unwrap(), span)?;
+                component_wise_concrete_int(self, span, [arg, arg1.unwrap()], |e| match e {
+                    ConcreteInt::I32([e, newbits]) => {
+                        Ok(ConcreteInt::I32([
+                            insert_bits(e as u32, newbits as u32, offset, count) as i32,
+                        ]))
+                    }
+                    ConcreteInt::U32([e, newbits]) => {
+                        Ok(ConcreteInt::U32([insert_bits(e, newbits, offset, count)]))
+                    }
+                })
+            }
             crate::MathFunction::FirstTrailingBit => {
                 component_wise_concrete_int(self, span, [arg], |ci| Ok(first_trailing_bit(ci)))
             }
@@ -1292,12 +1455,749 @@ impl<'a> ConstantEvaluator<'a> {
                 component_wise_concrete_int(self, span, [arg], |ci| Ok(first_leading_bit(ci)))
             }
 
-            fun => Err(ConstantEvaluatorError::NotImplemented(format!(
-                "{fun:?} built-in function"
-            ))),
+            // data packing
+            crate::MathFunction::Pack4x8snorm
+            | crate::MathFunction::Pack4x8unorm
+            | crate::MathFunction::Pack2x16snorm
+            | crate::MathFunction::Pack2x16unorm
+            | crate::MathFunction::Pack2x16float
+            | crate::MathFunction::Pack4xI8
+            | crate::MathFunction::Pack4xU8 => self.pack(fun, arg, span),
+
+            // data unpacking
+            crate::MathFunction::Unpack4x8snorm
+            | crate::MathFunction::Unpack4x8unorm
+            | crate::MathFunction::Unpack2x16snorm
+            | crate::MathFunction::Unpack2x16unorm
+            | crate::MathFunction::Unpack2x16float
+            | crate::MathFunction::Unpack4xI8
+            | crate::MathFunction::Unpack4xU8 => self.unpack(fun, arg, span),
+        }
+    }
+
+    /// Dot product of two packed vectors of four 8-bit integers.
+    ///
+    /// Implements [`Dot4I8Packed`] if `signed` is true, and [`Dot4U8Packed`] otherwise.
+    ///
+    /// [`Dot4I8Packed`]: crate::MathFunction::Dot4I8Packed
+    /// [`Dot4U8Packed`]: crate::MathFunction::Dot4U8Packed
+    fn packed_dot_product(
+        &mut self,
+        a: Handle<Expression>,
+        b: Handle<Expression>,
+        span: Span,
+        signed: bool,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let a = self.eval_zero_value_and_splat(a, span)?;
+        let b = self.eval_zero_value_and_splat(b, span)?;
+
+        let (&Expression::Literal(Literal::U32(a)), &Expression::Literal(Literal::U32(b))) =
+            (&self.expressions[a], &self.expressions[b])
+        else {
+            return Err(ConstantEvaluatorError::InvalidMathArg);
+        };
+
+        let products = a.to_le_bytes().into_iter().zip(b.to_le_bytes());
+        let result = if signed {
+            Literal::I32(
+                products
+                    .map(|(a, b)| i32::from(a as i8) * i32::from(b as i8))
+                    .sum(),
+            )
+        } else {
+            Literal::U32(products.map(|(a, b)| u32::from(a) * u32::from(b)).sum())
+        };
+
+        self.register_evaluated_expr(Expression::Literal(result), span)
+    }
+
+    /// Dot product of two scalar or vector arguments.
+    ///
+    /// Floating-point products are accumulated in `f64`. Integer products must
+    /// not overflow.
+    fn dot(
+        &mut self,
+        a: Handle<Expression>,
+        b: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let err = ConstantEvaluatorError::InvalidMathArg;
+        let (_, a) = self.literal_components(a, err.clone(), span)?;
+        let (_, b) = self.literal_components(b, err.clone(), span)?;
+        if a.len() != b.len() {
+            return Err(err);
+        }
+        let scalar = a[0].scalar();
+
+        if let (Some(a), Some(b)) = (float_values(&a), float_values(&b)) {
+            let result = float_literal(dot(&a, &b), scalar)?;
+            return self.register_evaluated_expr(Expression::Literal(result), span);
+        }
+
+        let overflow = || ConstantEvaluatorError::Overflow("dot product".into());
+        let mut sum = Literal::zero(scalar).ok_or_else(|| err.clone())?;
+        for (&a, &b) in a.iter().zip(&b) {
+            sum = match (sum, a, b) {
+                (Literal::I32(sum), Literal::I32(a), Literal::I32(b)) => Literal::I32(
+                    a.checked_mul(b)
+                        .and_then(|product| sum.checked_add(product))
+                        .ok_or_else(overflow)?,
+                ),
+                (Literal::U32(sum), Literal::U32(a), Literal::U32(b)) => Literal::U32(
+                    a.checked_mul(b)
+                        .and_then(|product| sum.checked_add(product))
+                        .ok_or_else(overflow)?,
+                ),
+                (Literal::I64(sum), Literal::I64(a), Literal::I64(b)) => Literal::I64(
+                    a.checked_mul(b)
+                        .and_then(|product| sum.checked_add(product))
+                        .ok_or_else(overflow)?,
+                ),
+                (Literal::U64(sum), Literal::U64(a), Literal::U64(b)) => Literal::U64(
+                    a.checked_mul(b)
+                        .and_then(|product| sum.checked_add(product))
+                        .ok_or_else(overflow)?,
+                ),
+                (Literal::AbstractInt(sum), Literal::AbstractInt(a), Literal::AbstractInt(b)) => {
+                    Literal::AbstractInt(
+                        a.checked_mul(b)
+                            .and_then(|product| sum.checked_add(product))
+                            .ok_or_else(overflow)?,
+                    )
+                }
+                _ => return Err(err),
+            };
+        }
+
+        self.register_evaluated_expr(Expression::Literal(sum), span)
+    }
+
+    /// Evaluate a floating-point geometric built-in function.
+    ///
+    /// Each of `args` is evaluated to a list of its components, which must all
+    /// have the same floating-point type. `handler` computes the result in
+    /// `f64`, which is then converted back to that type.
+    fn geometric<const N: usize>(
+        &mut self,
+        args: [Handle<Expression>; N],
+        span: Span,
+        handler: impl FnOnce(
+            [ArrayVec<f64, { crate::VectorSize::MAX }>; N],
+        ) -> Result<Geometric, ConstantEvaluatorError>,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let err = ConstantEvaluatorError::InvalidMathArg;
+        let mut scalar = None;
+        let mut values = ArrayVec::<_, N>::new();
+        for arg in args {
+            let (_, components) = self.literal_components(arg, err.clone(), span)?;
+            if components
+                .iter()
+                .any(|component| *scalar.get_or_insert(component.scalar()) != component.scalar())
+            {
+                return Err(err);
+            }
+            values.push(float_values(&components).ok_or_else(|| err.clone())?);
+        }
+        let scalar = scalar.ok_or_else(|| err.clone())?;
+        let values = values.into_inner().map_err(|_| err.clone())?;
+
+        match handler(values)? {
+            Geometric::Scalar(value) => {
+                let literal = float_literal(value, scalar)?;
+                self.register_evaluated_expr(Expression::Literal(literal), span)
+            }
+            Geometric::Vector(values) => {
+                let size = vector_size(values.len()).ok_or(err)?;
+                let components = values
+                    .into_iter()
+                    .map(|value| float_literal(value, scalar))
+                    .collect::<Result<_, _>>()?;
+                self.register_literal_components(Some(size), components, span)
+            }
         }
     }
 
+    /// Outer product of two floating-point vectors.
+    fn outer(
+        &mut self,
+        a: Handle<Expression>,
+        b: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let err = ConstantEvaluatorError::InvalidMathArg;
+        let (_, a) = self.literal_components(a, err.clone(), span)?;
+        let (_, b) = self.literal_components(b, err.clone(), span)?;
+        let scalar = a[0].scalar();
+        let (Some(a), Some(b)) = (float_values(&a), float_values(&b)) else {
+            return Err(err);
+        };
+
+        let columns = b
+            .iter()
+            .map(|b| a.iter().map(|a| a * b).collect())
+            .collect();
+        self.register_matrix(scalar, columns, span)
+    }
+
+    fn inverse(
+        &mut self,
+        matrix: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let (scalar, columns) = self.matrix_columns(matrix, span)?;
+        if columns.len() != columns[0].len() {
+            return Err(ConstantEvaluatorError::InvalidMathArg);
+        }
+
+        // The inverse is the transposed matrix of cofactors, divided by the
+        // determinant.
+        let det = determinant(&columns);
+        let inverse = (0..columns.len())
+            .map(|column| {
+                (0..columns.len())
+                    .map(|row| {
+                        let sign = if (row + column) % 2 == 0 { 1.0 } else { -1.0 };
+                        sign * determinant(&minor(&columns, row, column)) / det
+                    })
+                    .collect()
+            })
+            .collect();
+        self.register_matrix(scalar, inverse, span)
+    }
+
+    fn transpose(
+        &mut self,
+        matrix: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let (scalar, columns) = self.matrix_columns(matrix, span)?;
+        let transposed = (0..columns[0].len())
+            .map(|row| columns.iter().map(|column| column[row]).collect())
+            .collect();
+        self.register_matrix(scalar, transposed, span)
+    }
+
+    fn determinant(
+        &mut self,
+        matrix: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let (scalar, columns) = self.matrix_columns(matrix, span)?;
+        if columns.len() != columns[0].len() {
+            return Err(ConstantEvaluatorError::InvalidMathArg);
+        }
+        let literal = float_literal(determinant(&columns), scalar)?;
+        self.register_evaluated_expr(Expression::Literal(literal), span)
+    }
+
+    /// Split a floating-point value into its fractional and whole parts.
+    fn modf(
+        &mut self,
+        arg: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let err = ConstantEvaluatorError::InvalidMathArg;
+        let (size, components) = self.literal_components(arg, err.clone(), span)?;
+        let width = components[0].scalar().width;
+        let values = float_values(&components).ok_or(err)?;
+        let ty = self.predeclared_type(
+            crate::MathFunction::Modf,
+            crate::PredeclaredType::ModfResult { size, width },
+        )?;
+
+        let scalar = crate::Scalar::float(width);
+        let mut fract = ArrayVec::new();
+        let mut whole = ArrayVec::new();
+        for value in values {
+            fract.push(float_literal(value - value.trunc(), scalar)?);
+            whole.push(float_literal(value.trunc(), scalar)?);
+        }
+
+        let components = vec![
+            self.register_literal_components(size, fract, span)?,
+            self.register_literal_components(size, whole, span)?,
+        ];
+        self.register_evaluated_expr(Expression::Compose { ty, components }, span)
+    }
+
+    /// Split a floating-point value into a significand in `[0.5, 1.0)` and an
+    /// exponent of two.
+    fn frexp(
+        &mut self,
+        arg: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let err = ConstantEvaluatorError::InvalidMathArg;
+        let (size, components) = self.literal_components(arg, err.clone(), span)?;
+        let width = components[0].scalar().width;
+        let values = float_values(&components).ok_or(err)?;
+        let ty = self.predeclared_type(
+            crate::MathFunction::Frexp,
+            crate::PredeclaredType::FrexpResult { size, width },
+        )?;
+
+        let scalar = crate::Scalar::float(width);
+        let mut fract = ArrayVec::new();
+        let mut exp = ArrayVec::new();
+        for value in values {
+            let (significand, exponent) = frexp(value);
+            fract.push(float_literal(significand, scalar)?);
+            exp.push(match width {
+                4 => Literal::I32(exponent),
+                8 => Literal::I64(exponent.into()),
+                _ => return Err(ConstantEvaluatorError::TypeNotConstructible),
+            });
+        }
+
+        let components = vec![
+            self.register_literal_components(size, fract, span)?,
+            self.register_literal_components(size, exp, span)?,
+        ];
+        self.register_evaluated_expr(Expression::Compose { ty, components }, span)
+    }
+
+    /// Multiply a floating-point value by two raised to an integer power.
+    fn ldexp(
+        &mut self,
+        arg: Handle<Expression>,
+        exponent: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let err = ConstantEvaluatorError::InvalidMathArg;
+        let (size, components) = self.literal_components(arg, err.clone(), span)?;
+        let (_, exponents) = self.literal_components(exponent, err.clone(), span)?;
+        let scalar = components[0].scalar();
+        let values = float_values(&components).ok_or_else(|| err.clone())?;
+        if values.len() != exponents.len() {
+            return Err(err);
+        }
+
+        let components = values
+            .into_iter()
+            .zip(exponents)
+            .map(|(value, exponent)| {
+                let exponent = match exponent {
+                    Literal::I32(exponent) => exponent,
+                    Literal::AbstractInt(exponent) => exponent
+                        .clamp(i32::MIN.into(), i32::MAX.into())
+                        .try_into()
+                        .unwrap(),
+                    _ => return Err(err.clone()),
+                };
+                float_literal(ldexp(value, exponent), scalar)
+            })
+            .collect::<Result<_, _>>()?;
+        self.register_literal_components(size, components, span)
+    }
+
+    /// Check the `offset` and `count` arguments of [`ExtractBits`] and
+    /// [`InsertBits`], returning their values.
+    ///
+    /// In constant expressions, it is an error for `offset + count` to exceed
+    /// the bit width of the value operated on.
+    ///
+    /// [`ExtractBits`]: crate::MathFunction::ExtractBits
+    /// [`InsertBits`]: crate::MathFunction::InsertBits
+    fn bit_range(
+        &mut self,
+        fun: crate::MathFunction,
+        offset: Handle<Expression>,
+        count: Handle<Expression>,
+        span: Span,
+    ) -> Result<(u32, u32), ConstantEvaluatorError> {
+        let offset = self.eval_zero_value(offset, span)?;
+        let count = self.eval_zero_value(count, span)?;
+
+        let (&Expression::Literal(Literal::U32(offset)), &Expression::Literal(Literal::U32(count))) =
+            (&self.expressions[offset], &self.expressions[count])
+        else {
+            return Err(ConstantEvaluatorError::InvalidMathArg);
+        };
+
+        match offset.checked_add(count) {
+            Some(end) if end <= u32::BITS => Ok((offset, count)),
+            _ => Err(ConstantEvaluatorError::InvalidBitRange(fun)),
+        }
+    }
+
+    /// Evaluate one of the data packing built-in functions.
+    fn pack(
+        &mut self,
+        fun: crate::MathFunction,
+        arg: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        use crate::MathFunction as Mf;
+
+        let err = ConstantEvaluatorError::InvalidMathArg;
+        let (_, components) = self.literal_components(arg, err.clone(), span)?;
+        let floats = || {
+            components
+                .iter()
+                .map(|component| match *component {
+                    Literal::F32(value) => Ok(value),
+                    _ => Err(err.clone()),
+                })
+                .collect::<Result<ArrayVec<f32, { crate::VectorSize::MAX }>, _>>()
+        };
+
+        let (bits, packed): (u32, ArrayVec<u32, { crate::VectorSize::MAX }>) = match fun {
+            Mf::Pack4x8snorm => (8, floats()?.iter().map(|&e| snorm(e, 127.0)).collect()),
+            Mf::Pack4x8unorm => (8, floats()?.iter().map(|&e| unorm(e, 255.0)).collect()),
+            Mf::Pack2x16snorm => (16, floats()?.iter().map(|&e| snorm(e, 32767.0)).collect()),
+            Mf::Pack2x16unorm => (16, floats()?.iter().map(|&e| unorm(e, 65535.0)).collect()),
+            Mf::Pack2x16float => (
+                16,
+                floats()?
+                    .iter()
+                    .map(|&e| f16::from_f32(e).to_bits().into())
+                    .collect(),
+            ),
+            Mf::Pack4xI8 | Mf::Pack4xU8 => (
+                8,
+                components
+                    .iter()
+                    .map(|component| match *component {
+                        Literal::I32(value) => Ok(value as u32),
+                        Literal::U32(value) => Ok(value),
+                        _ => Err(err.clone()),
+                    })
+                    .collect::<Result<_, _>>()?,
+            ),
+            _ => unreachable!(),
+        };
+        if packed.len() as u32 * bits != u32::BITS {
+            return Err(err);
+        }
+
+        let mask = u32::MAX >> (u32::BITS - bits);
+        let result = packed
+            .iter()
+            .zip((0..).step_by(bits as usize))
+            .fold(0, |result, (&e, shift)| result | (e & mask) << shift);
+        self.register_evaluated_expr(Expression::Literal(Literal::U32(result)), span)
+    }
+
+    /// Evaluate one of the data unpacking built-in functions.
+    fn unpack(
+        &mut self,
+        fun: crate::MathFunction,
+        arg: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        use crate::MathFunction as Mf;
+
+        let arg = self.eval_zero_value(arg, span)?;
+        let Expression::Literal(Literal::U32(e)) = self.expressions[arg] else {
+            return Err(ConstantEvaluatorError::InvalidMathArg);
+        };
+        let [b0, b1, b2, b3] = e.to_le_bytes();
+        let halves = [e as u16, (e >> 16) as u16];
+
+        let components: ArrayVec<Literal, { crate::VectorSize::MAX }> = match fun {
+            Mf::Unpack4x8snorm => [b0, b1, b2, b3]
+                .map(|b| Literal::F32((f32::from(b as i8) / 127.0).max(-1.0)))
+                .into(),
+            Mf::Unpack4x8unorm => [b0, b1, b2, b3]
+                .map(|b| Literal::F32(f32::from(b) / 255.0))
+                .into(),
+            Mf::Unpack2x16snorm => halves
+                .map(|h| Literal::F32((f32::from(h as i16) / 32767.0).max(-1.0)))
+                .into_iter()
+                .collect(),
+            Mf::Unpack2x16unorm => halves
+                .map(|h| Literal::F32(f32::from(h) / 65535.0))
+                .into_iter()
+                .collect(),
+            Mf::Unpack2x16float => halves
+                .map(|h| Literal::F32(f16::from_bits(h).to_f32()))
+                .into_iter()
+                .collect(),
+            Mf::Unpack4xI8 => [b0, b1, b2, b3]
+                .map(|b| Literal::I32((b as i8).into()))
+                .into(),
+            Mf::Unpack4xU8 => [b0, b1, b2, b3].map(|b| Literal::U32(b.into())).into(),
+            _ => unreachable!(),
+        };
+
+        let size = vector_size(components.len()).ok_or(ConstantEvaluatorError::InvalidMathArg)?;
+        self.register_literal_components(Some(size), components, span)
+    }
+
+    /// Return the handle of the predeclared type `ty`, which the result of
+    /// `fun` must have.
+    fn predeclared_type(
+        &self,
+        fun: crate::MathFunction,
+        ty: crate::PredeclaredType,
+    ) -> Result<Handle<Type>, ConstantEvaluatorError> {
+        self.special_types
+            .predeclared_types
+            .get(&ty)
+            .copied()
+            .ok_or_else(|| {
+                ConstantEvaluatorError::NotImplemented(format!(
+                    "{fun:?} built-in function without a predeclared result type"
+                ))
+            })
+    }
+
+    /// Evaluate the [`Select`] expression with the given operands.
+    ///
+    /// [`Select`]: Expression::Select
+    fn select(
+        &mut self,
+        condition: Handle<Expression>,
+        accept: Handle<Expression>,
+        reject: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let err = ConstantEvaluatorError::InvalidSelectArgs;
+        let (size, conditions) = self.literal_components(condition, err.clone(), span)?;
+
+        // A scalar condition selects between whole values of any type.
+        if size.is_none() {
+            return match conditions[0] {
+                Literal::Bool(true) => Ok(accept),
+                Literal::Bool(false) => Ok(reject),
+                _ => Err(err),
+            };
+        }
+
+        let (_, accept) = self.literal_components(accept, err.clone(), span)?;
+        let (_, reject) = self.literal_components(reject, err.clone(), span)?;
+        if accept.len() != conditions.len() || reject.len() != conditions.len() {
+            return Err(err);
+        }
+
+        let components = conditions
+            .iter()
+            .zip(accept.into_iter().zip(reject))
+            .map(|(condition, (accept, reject))| match *condition {
+                Literal::Bool(condition) => Ok(if condition { accept } else { reject }),
+                _ => Err(err.clone()),
+            })
+            .collect::<Result<_, _>>()?;
+        self.register_literal_components(size, components, span)
+    }
+
+    /// Evaluate the [`Relational`] expression `fun(argument)`.
+    ///
+    /// [`Relational`]: Expression::Relational
+    fn relational(
+        &mut self,
+        fun: crate::RelationalFunction,
+        argument: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        use crate::RelationalFunction as Rf;
+
+        let err = ConstantEvaluatorError::InvalidRelationalArg(fun);
+        let (size, components) = self.literal_components(argument, err.clone(), span)?;
+
+        match fun {
+            Rf::All | Rf::Any => {
+                let values = components
+                    .iter()
+                    .map(|component| match *component {
+                        Literal::Bool(value) => Ok(value),
+                        _ => Err(err.clone()),
+                    })
+                    .collect::<Result<ArrayVec<bool, { crate::VectorSize::MAX }>, _>>()?;
+                let result = if fun == Rf::All {
+                    values.iter().all(|&value| value)
+                } else {
+                    values.iter().any(|&value| value)
+                };
+                self.register_evaluated_expr(Expression::Literal(Literal::Bool(result)), span)
+            }
+            Rf::IsNan | Rf::IsInf => {
+                let values = float_values(&components).ok_or(err)?;
+                let components = values
+                    .iter()
+                    .map(|value| {
+                        Literal::Bool(if fun == Rf::IsNan {
+                            value.is_nan()
+                        } else {
+                            value.is_infinite()
+                        })
+                    })
+                    .collect();
+                self.register_literal_components(size, components, span)
+            }
+        }
+    }
+
+    /// Reinterpret the bits of the scalar components of `expr` as scalars of
+    /// `kind` with the same width.
+    fn bitcast(
+        &mut self,
+        expr: Handle<Expression>,
+        kind: ScalarKind,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let err = ConstantEvaluatorError::InvalidBitcastArg(kind);
+        let (size, components) = self.literal_components(expr, err.clone(), span)?;
+        let components = components
+            .into_iter()
+            .map(|component| bitcast_literal(component, kind).ok_or_else(|| err.clone()))
+            .collect::<Result<_, _>>()?;
+        self.register_literal_components(size, components, span)
+    }
+
+    /// If `like` is a vector and `value` is a scalar, splat `value` to the
+    /// size of `like`. Otherwise, return `value` unchanged.
+    fn splat_to_match(
+        &mut self,
+        value: Handle<Expression>,
+        like: Handle<Expression>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let like = self.eval_zero_value_and_splat(like, span)?;
+        let value = self.eval_zero_value(value, span)?;
+        match (&self.expressions[like], &self.expressions[value]) {
+            (&Expression::Compose { ty, .. }, &Expression::Literal(_)) => {
+                match self.types[ty].inner {
+                    TypeInner::Vector { size, .. } => self.splat(value, size, span),
+                    _ => Ok(value),
+                }
+            }
+            _ => Ok(value),
+        }
+    }
+
+    /// Evaluate the scalar or vector `expr` to a list of its [`Literal`]
+    /// components.
+    ///
+    /// Return the size of the vector, or `None` if `expr` is a scalar, along
+    /// with the components. If `expr` is neither, return `err`.
+    fn literal_components(
+        &mut self,
+        expr: Handle<Expression>,
+        err: ConstantEvaluatorError,
+        span: Span,
+    ) -> Result<
+        (
+            Option<crate::VectorSize>,
+            ArrayVec<Literal, { crate::VectorSize::MAX }>,
+        ),
+        ConstantEvaluatorError,
+    > {
+        let expr = self.eval_zero_value_and_splat(expr, span)?;
+        let (size, components) = match self.expressions[expr] {
+            Expression::Literal(literal) => return Ok((None, [literal].into_iter().collect())),
+            Expression::Compose { ty, ref components } => match self.types[ty].inner {
+                TypeInner::Vector { size, .. } => (
+                    size,
+                    crate::proc::flatten_compose(ty, components, self.expressions, self.types)
+                        .collect::<ArrayVec<_, { crate::VectorSize::MAX }>>(),
+                ),
+                _ => return Err(err),
+            },
+            _ => return Err(err),
+        };
+
+        let mut literals = ArrayVec::new();
+        for component in components {
+            let component = self.eval_zero_value_and_splat(component, span)?;
+            match self.expressions[component] {
+                Expression::Literal(literal) => literals.push(literal),
+                _ => return Err(err),
+            }
+        }
+        Ok((Some(size), literals))
+    }
+
+    /// Register `components` as a scalar, or as a vector of the given `size`.
+    ///
+    /// This is the inverse of [`Self::literal_components`].
+    fn register_literal_components(
+        &mut self,
+        size: Option<crate::VectorSize>,
+        components: ArrayVec<Literal, { crate::VectorSize::MAX }>,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let Some(size) = size else {
+            let &[literal] = components.as_slice() else {
+                return Err(ConstantEvaluatorError::InvalidMathArg);
+            };
+            return self.register_evaluated_expr(Expression::Literal(literal), span);
+        };
+
+        let scalar = components[0].scalar();
+        let ty = self.types.insert(
+            Type {
+                name: None,
+                inner: TypeInner::Vector { size, scalar },
+            },
+            span,
+        );
+        let components = components
+            .into_iter()
+            .map(|literal| self.register_evaluated_expr(Expression::Literal(literal), span))
+            .collect::<Result<_, _>>()?;
+        self.register_evaluated_expr(Expression::Compose { ty, components }, span)
+    }
+
+    /// Evaluate the matrix `expr` to the floating-point values of its columns.
+    fn matrix_columns(
+        &mut self,
+        expr: Handle<Expression>,
+        span: Span,
+    ) -> Result<(crate::Scalar, Columns), ConstantEvaluatorError> {
+        let err = ConstantEvaluatorError::InvalidMathArg;
+        let expr = self.eval_zero_value(expr, span)?;
+        let Expression::Compose { ty, ref components } = self.expressions[expr] else {
+            return Err(err);
+        };
+        let TypeInner::Matrix { scalar, .. } = self.types[ty].inner else {
+            return Err(err);
+        };
+
+        let mut columns = Columns::new();
+        for column in components.clone() {
+            let (_, components) = self.literal_components(column, err.clone(), span)?;
+            columns.push(float_values(&components).ok_or_else(|| err.clone())?);
+        }
+        Ok((scalar, columns))
+    }
+
+    /// Register a matrix of `scalar` values with the given `columns`.
+    ///
+    /// This is the inverse of [`Self::matrix_columns`].
+    fn register_matrix(
+        &mut self,
+        scalar: crate::Scalar,
+        columns: Columns,
+        span: Span,
+    ) -> Result<Handle<Expression>, ConstantEvaluatorError> {
+        let err = ConstantEvaluatorError::InvalidMathArg;
+        let rows = vector_size(columns[0].len()).ok_or_else(|| err.clone())?;
+        let column_count = vector_size(columns.len()).ok_or(err)?;
+
+        let mut components = Vec::with_capacity(columns.len());
+        for column in columns {
+            let literals = column
+                .into_iter()
+                .map(|value| float_literal(value, scalar))
+                .collect::<Result<_, _>>()?;
+            components.push(self.register_literal_components(Some(rows), literals, span)?);
+        }
+
+        let ty = self.types.insert(
+            Type {
+                name: None,
+                inner: TypeInner::Matrix {
+                    columns: column_count,
+                    rows,
+                    scalar,
+                },
+            },
+            span,
+        );
+        self.register_evaluated_expr(Expression::Compose { ty, components }, span)
+    }
+
     fn array_length(
         &mut self,
         array: Handle<Expression>,
@@ -1529,6 +2429,7 @@ impl<'a> ConstantEvaluator<'a> {
                         Literal::I32(v) => v,
                         Literal::U32(v) => v as i32,
                         Literal::F32(v) => v as i32,
+                        Literal::F16(v) => v.to_f32() as i32,
                         Literal::Bool(v) => v as i32,
                         Literal::F64(_) | Literal::I64(_) | Literal::U64(_) => {
                             return make_error();
@@ -1540,6 +2441,7 @@ impl<'a> ConstantEvaluator<'a> {
                         Literal::I32(v) => v as u32,
                         Literal::U32(v) => v,
                         Literal::F32(v) => v as u32,
+                        Literal::F16(v) => v.to_f32() as u32,
                         Literal::Bool(v) => v as u32,
                         Literal::F64(_) | Literal::I64(_) | Literal::U64(_) => {
                             return make_error();
@@ -1551,6 +2453,7 @@ impl<'a> ConstantEvaluator<'a> {
                         Literal::I32(v) => v as i64,
                         Literal::U32(v) => v as i64,
                         Literal::F32(v) => v as i64,
+                        Literal::F16(v) => v.to_f32() as i64,
                         Literal::Bool(v) => v as i64,
                         Literal::F64(v) => v as i64,
                         Literal::I64(v) => v,
@@ -1562,6 +2465,7 @@ impl<'a> ConstantEvaluator<'a> {
                         Literal::I32(v) => v as u64,
                         Literal::U32(v) => v as u64,
                         Literal::F32(v) => v as u64,
+                        Literal::F16(v) => v.to_f32() as u64,
                         Literal::Bool(v) => v as u64,
                         Literal::F64(v) => v as u64,
                         Literal::I64(v) => v as u64,
@@ -1573,6 +2477,7 @@ impl<'a> ConstantEvaluator<'a> {
                         Literal::I32(v) => v as f32,
                         Literal::U32(v) => v as f32,
                         Literal::F32(v) => v,
+                        Literal::F16(v) => v.to_f32(),
                         Literal::Bool(v) => v as u32 as f32,
                         Literal::F64(_) | Literal::I64(_) | Literal::U64(_) => {
                             return make_error();
@@ -1580,10 +2485,23 @@ impl<'a> ConstantEvaluator<'a> {
                         Literal::AbstractInt(v) => f32::try_from_abstract(v)?,
                         Literal::AbstractFloat(v) => f32::try_from_abstract(v)?,
                     }),
+                    Sc::F16 => Literal::F16(match literal {
+                        Literal::I32(v) => f16_from_f32_in_range(v as f32)?,
+                        Literal::U32(v) => f16_from_f32_in_range(v as f32)?,
+                        Literal::F32(v) => f16_from_f32_in_range(v)?,
+                        Literal::F16(v) => v,
+                        Literal::Bool(v) => f16::from_f32(v as u32 as f32),
+                        Literal::F64(_) | Literal::I64(_) | Literal::U64(_) => {
+                            return make_error();
+                        }
+                        Literal::AbstractInt(v) => f16::try_from_abstract(v)?,
+                        Literal::AbstractFloat(v) => f16::try_from_abstract(v)?,
+                    }),
                     Sc::F64 => Literal::F64(match literal {
                         Literal::I32(v) => v as f64,
                         Literal::U32(v) => v as f64,
                         Literal::F32(v) => v as f64,
+                        Literal::F16(v) => v.to_f64(),
                         Literal::F64(v) => v,
                         Literal::Bool(v) => v as u32 as f64,
                         Literal::I64(_) | Literal::U64(_) => return make_error(),
@@ -1594,6 +2512,7 @@ impl<'a> ConstantEvaluator<'a> {
                         Literal::I32(v) => v != 0,
                         Literal::U32(v) => v != 0,
                         Literal::F32(v) => v != 0.0,
+                        Literal::F16(v) => v != f16::ZERO,
                         Literal::Bool(v) => v,
                         Literal::F64(_)
                         | Literal::I64(_)
@@ -1743,6 +2662,7 @@ impl<'a> ConstantEvaluator<'a> {
                 UnaryOperator::Negate => match value {
                     Literal::I32(v) => Literal::I32(v.wrapping_neg()),
                     Literal::F32(v) => Literal::F32(-v),
+                    Literal::F16(v) => Literal::F16(-v),
                     Literal::AbstractInt(v) => Literal::AbstractInt(v.wrapping_neg()),
                     Literal::AbstractFloat(v) => Literal::AbstractFloat(-v),
                     _ => return Err(ConstantEvaluatorError::InvalidUnaryOpArg),
@@ -1881,6 +2801,14 @@ impl<'a> ConstantEvaluator<'a> {
                             BinaryOperator::Modulo => a % b,
                             _ => return Err(ConstantEvaluatorError::InvalidBinaryOpArgs),
                         }),
+                        (Literal::F16(a), Literal::F16(b)) => Literal::F16(match op {
+                            BinaryOperator::Add => a + b,
+                            BinaryOperator::Subtract => a - b,
+                            BinaryOperator::Multiply => a * b,
+                            BinaryOperator::Divide => a / b,
+                            BinaryOperator::Modulo => a % b,
+                            _ => return Err(ConstantEvaluatorError::InvalidBinaryOpArgs),
+                        }),
                         (Literal::AbstractInt(a), Literal::AbstractInt(b)) => {
                             Literal::AbstractInt(match op {
                                 BinaryOperator::Add => a.checked_add(b).ok_or_else(|| {
@@ -2334,6 +3262,209 @@ fn first_leading_bit_smoke() {
     }
 }
 
+/// The result of a [geometric] built-in function, computed in `f64`.
+///
+/// [geometric]: ConstantEvaluator::geometric
+enum Geometric {
+    Scalar(f64),
+    Vector(ArrayVec<f64, { crate::VectorSize::MAX }>),
+}
+
+/// The floating-point values of a matrix's columns.
+type Columns = ArrayVec<ArrayVec<f64, { crate::VectorSize::MAX }>, { crate::VectorSize::MAX }>;
+
+const fn vector_size(len: usize) -> Option<crate::VectorSize> {
+    match len {
+        2 => Some(crate::VectorSize::Bi),
+        3 => Some(crate::VectorSize::Tri),
+        4 => Some(crate::VectorSize::Quad),
+        _ => None,
+    }
+}
+
+/// Return the values of `literals` as `f64`, if they are all floating-point.
+fn float_values(literals: &[Literal]) -> Option<ArrayVec<f64, { crate::VectorSize::MAX }>> {
+    literals
+        .iter()
+        .map(|literal| match *literal {
+            Literal::F16(value) => Some(value.to_f64()),
+            Literal::F32(value) => Some(value.into()),
+            Literal::F64(value) | Literal::AbstractFloat(value) => Some(value),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Return a floating-point literal of type `scalar` holding `value`.
+fn float_literal(value: f64, scalar: crate::Scalar) -> Result<Literal, ConstantEvaluatorError> {
+    Ok(match scalar {
+        crate::Scalar::F16 => Literal::F16(f16_from_f64(value)),
+        crate::Scalar::F32 => Literal::F32(value as f32),
+        crate::Scalar::F64 => Literal::F64(value),
+        crate::Scalar::ABSTRACT_FLOAT => Literal::AbstractFloat(value),
+        _ => return Err(ConstantEvaluatorError::InvalidMathArg),
+    })
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+fn length(components: impl Iterator<Item = f64>) -> f64 {
+    components.map(|e| e * e).sum::<f64>().sqrt()
+}
+
+/// Return `columns` with the given column and row removed.
+fn minor(
+    columns: &[ArrayVec<f64, { crate::VectorSize::MAX }>],
+    column: usize,
+    row: usize,
+) -> Columns {
+    columns
+        .iter()
+        .enumerate()
+        .filter(|&(index, _)| index != column)
+        .map(|(_, values)| {
+            values
+                .iter()
+                .enumerate()
+                .filter(|&(index, _)| index != row)
+                .map(|(_, &value)| value)
+                .collect()
+        })
+        .collect()
+}
+
+/// Determinant of a square matrix, by cofactor expansion along the first column.
+fn determinant(columns: &[ArrayVec<f64, { crate::VectorSize::MAX }>]) -> f64 {
+    if let [ref column] = *columns {
+        return column[0];
+    }
+    columns[0]
+        .iter()
+        .enumerate()
+        .map(|(row, &value)| {
+            let sign = if row % 2 == 0 { 1.0 } else { -1.0 };
+            sign * value * determinant(&minor(columns, 0, row))
+        })
+        .sum()
+}
+
+/// Multiply `value` by two raised to the power `exponent`.
+fn ldexp(value: f64, exponent: i32) -> f64 {
+    // Scale in two steps, so that subnormal values can reach the top of the
+    // range, and vice versa, without the factor itself overflowing.
+    let exponent = exponent.clamp(-2 * f64::MAX_EXP, 2 * f64::MAX_EXP);
+    let half = exponent / 2;
+    value * 2f64.powi(half) * 2f64.powi(exponent - half)
+}
+
+/// Split `value` into a significand whose magnitude is in `[0.5, 1.0)` and an
+/// exponent of two.
+fn frexp(value: f64) -> (f64, i32) {
+    if value == 0.0 || !value.is_finite() {
+        return (value, 0);
+    }
+    let exponent = value.abs().log2().floor() as i32 + 1;
+    let significand = ldexp(value, -exponent);
+    // `log2` may have rounded across a power of two.
+    if significand.abs() >= 1.0 {
+        (significand / 2.0, exponent + 1)
+    } else if significand.abs() < 0.5 {
+        (significand * 2.0, exponent - 1)
+    } else {
+        (significand, exponent)
+    }
+}
+
+#[test]
+fn frexp_smoke() {
+    assert_eq!(frexp(0.0), (0.0, 0));
+    assert_eq!(frexp(1.0), (0.5, 1));
+    assert_eq!(frexp(-3.0), (-0.75, 2));
+    assert_eq!(frexp(0.125), (0.5, -2));
+    assert_eq!(frexp(f64::MAX), (1.0 - f64::EPSILON / 2.0, 1024));
+    let (significand, exponent) = frexp(f64::MIN_POSITIVE / 4.0);
+    assert_eq!((significand, exponent), (0.5, -1023));
+    assert_eq!(ldexp(significand, exponent), f64::MIN_POSITIVE / 4.0);
+}
+
+/// Convert `e` to a signed normalized integer in the range `-scale..=scale`,
+/// as the `pack*snorm` built-in functions do.
+fn snorm(e: f32, scale: f32) -> u32 {
+    (0.5 + scale * e.clamp(-1.0, 1.0)).floor() as i32 as u32
+}
+
+/// Convert `e` to an unsigned normalized integer in the range `0..=scale`, as
+/// the `pack*unorm` built-in functions do.
+fn unorm(e: f32, scale: f32) -> u32 {
+    (0.5 + scale * e.clamp(0.0, 1.0)).floor() as u32
+}
+
+/// Return `count` bits of `e` starting at `offset`, in the low bits of the result.
+const fn extract_bits(e: u32, offset: u32, count: u32) -> u32 {
+    if count == 0 {
+        return 0;
+    }
+    (e >> offset) & (u32::MAX >> (u32::BITS - count))
+}
+
+/// Like [`extract_bits`], but sign-extend the result from its highest bit.
+const fn extract_signed_bits(e: i32, offset: u32, count: u32) -> i32 {
+    if count == 0 {
+        return 0;
+    }
+    let unused = u32::BITS - count;
+    ((extract_bits(e as u32, offset, count) << unused) as i32) >> unused
+}
+
+/// Replace `count` bits of `e` starting at `offset` with the low bits of `newbits`.
+const fn insert_bits(e: u32, newbits: u32, offset: u32, count: u32) -> u32 {
+    if count == 0 {
+        return e;
+    }
+    let mask = (u32::MAX >> (u32::BITS - count)) << offset;
+    (e & !mask) | ((newbits << offset) & mask)
+}
+
+#[test]
+fn extract_insert_bits_smoke() {
+    assert_eq!(extract_bits(0xabcd_1234, 8, 8), 0x12);
+    assert_eq!(extract_bits(u32::MAX, 0, 32), u32::MAX);
+    assert_eq!(extract_bits(u32::MAX, 32, 0), 0);
+    assert_eq!(extract_signed_bits(0x0000_0f00, 8, 4), -1);
+    assert_eq!(extract_signed_bits(0x0000_0700, 8, 4), 7);
+    assert_eq!(extract_signed_bits(i32::MIN, 0, 32), i32::MIN);
+    assert_eq!(insert_bits(0xffff_ffff, 0, 4, 8), 0xffff_f00f);
+    assert_eq!(insert_bits(0, 0xff, 28, 4), 0xf000_0000);
+    assert_eq!(insert_bits(0x1234, 0xffff, 0, 0), 0x1234);
+    assert_eq!(insert_bits(0x1234, 0xabcd_ef01, 0, 32), 0xabcd_ef01);
+}
+
+/// Reinterpret the bits of `literal` as a scalar of `kind` and the same width.
+fn bitcast_literal(literal: Literal, kind: ScalarKind) -> Option<Literal> {
+    let (bits, width) = match literal {
+        Literal::F16(value) => (value.to_bits().into(), 2),
+        Literal::I32(value) => ((value as u32).into(), 4),
+        Literal::U32(value) => (value.into(), 4),
+        Literal::F32(value) => (value.to_bits().into(), 4),
+        Literal::I64(value) => (value as u64, 8),
+        Literal::U64(value) => (value, 8),
+        Literal::F64(value) => (value.to_bits(), 8),
+        Literal::Bool(_) | Literal::AbstractInt(_) | Literal::AbstractFloat(_) => return None,
+    };
+    Some(match (kind, width) {
+        (ScalarKind::Float, 2) => Literal::F16(f16::from_bits(bits as u16)),
+        (ScalarKind::Sint, 4) => Literal::I32(bits as u32 as i32),
+        (ScalarKind::Uint, 4) => Literal::U32(bits as u32),
+        (ScalarKind::Float, 4) => Literal::F32(f32::from_bits(bits as u32)),
+        (ScalarKind::Sint, 8) => Literal::I64(bits as i64),
+        (ScalarKind::Uint, 8) => Literal::U64(bits),
+        (ScalarKind::Float, 8) => Literal::F64(f64::from_bits(bits)),
+        _ => return None,
+    })
+}
+
 /// Trait for conversions of abstract values to concrete types.
 trait TryFromAbstract<T>: Sized {
     /// Convert an abstract literal `value` to `Self`.
@@ -2410,6 +3541,77 @@ impl TryFromAbstract<f64> for f32 {
     }
 }
 
+impl TryFromAbstract<i64> for f16 {
+    fn try_from_abstract(value: i64) -> Result<Self, ConstantEvaluatorError> {
+        let f = f16_from_f64(value as f64);
+        // The range of `f16` is only ±65504, so unlike the wider float
+        // types, this conversion can overflow.
+        if f.is_infinite() {
+            return Err(ConstantEvaluatorError::AutomaticConversionLossy {
+                value: format!("{value:?}"),
+                to_type: "f16",
+            });
+        }
+        Ok(f)
+    }
+}
+
+impl TryFromAbstract<f64> for f16 {
+    fn try_from_abstract(value: f64) -> Result<f16, ConstantEvaluatorError> {
+        let f = f16_from_f64(value);
+        if f.is_infinite() {
+            return Err(ConstantEvaluatorError::AutomaticConversionLossy {
+                value: format!("{value:?}"),
+                to_type: "f16",
+            });
+        }
+        Ok(f)
+    }
+}
+
+/// Round an `f64` value to the nearest `f16`, with ties going to even.
+///
+/// [`f16::from_f64`] may convert through `f32` when the target supports it,
+/// rounding twice, so check the result's neighbours and pick the closest.
+pub(crate) fn f16_from_f64(value: f64) -> f16 {
+    let candidate = f16::from_f64(value);
+    // Values below 65520 round down to `f16::MAX`, even if they round up to
+    // 65520 in `f32`.
+    if candidate.is_infinite() && value.abs() < 65520.0 {
+        return f16::MAX.copysign(candidate);
+    }
+    if !candidate.is_finite() || candidate.to_f64() == value {
+        return candidate;
+    }
+    let bits = candidate.to_bits();
+    [bits.wrapping_sub(1), bits.wrapping_add(1)]
+        .into_iter()
+        .map(f16::from_bits)
+        // Stay on the same side of zero, and never round to infinity or NaN.
+        .filter(|n| n.is_finite() && n.is_sign_negative() == candidate.is_sign_negative())
+        .fold(candidate, |best, n| {
+            let best_distance = (best.to_f64() - value).abs();
+            let distance = (n.to_f64() - value).abs();
+            if distance < best_distance || (distance == best_distance && n.to_bits() & 1 == 0) {
+                n
+            } else {
+                best
+            }
+        })
+}
+
+/// Convert a concrete `f32` value to `f16`, rejecting values that `f16`
+/// cannot represent instead of letting them become infinities.
+fn f16_from_f32_in_range(value: f32) -> Result<f16, ConstantEvaluatorError> {
+    if value.abs() > f16::MAX.to_f32() {
+        return Err(ConstantEvaluatorError::CastOutOfRange {
+            value: format!("{value:?}"),
+            to_type: "f16",
+        });
+    }
+    Ok(f16::from_f32(value))
+}
+
 impl TryFromAbstract<i64> for f64 {
     fn try_from_abstract(value: i64) -> Result<Self, ConstantEvaluatorError> {
         let f = value as f64;
@@ -2450,6 +3652,50 @@ impl TryFromAbstract<f64> for u64 {
     }
 }
 
+macro_rules! f16_math {
+    ($($name:ident),+ $(,)?) => {
+        /// The floating-point methods of `f32` that the `half` crate doesn't
+        /// provide for [`f16`].
+        ///
+        /// These let `f16` share the handlers of [`component_wise_float`] and
+        /// friends with the other float types. Each operation is carried out in
+        /// `f32`, which represents every `f16` value exactly, and the result is
+        /// rounded back.
+        trait F16Math {
+            $(fn $name(self) -> Self;)+
+            fn abs(self) -> Self;
+            fn powf(self, n: Self) -> Self;
+            fn atan2(self, other: Self) -> Self;
+            fn mul_add(self, a: Self, b: Self) -> Self;
+        }
+
+        impl F16Math for f16 {
+            $(
+                fn $name(self) -> Self {
+                    f16::from_f32(self.to_f32().$name())
+                }
+            )+
+            fn abs(self) -> Self {
+                f16::from_bits(self.to_bits() & 0x7fff)
+            }
+            fn powf(self, n: Self) -> Self {
+                f16::from_f32(self.to_f32().powf(n.to_f32()))
+            }
+            fn atan2(self, other: Self) -> Self {
+                f16::from_f32(self.to_f32().atan2(other.to_f32()))
+            }
+            fn mul_add(self, a: Self, b: Self) -> Self {
+                f16::from_f32(self.to_f32().mul_add(a.to_f32(), b.to_f32()))
+            }
+        }
+    };
+}
+
+f16_math!(
+    cos, cosh, sin, sinh, tan, tanh, acos, asin, atan, asinh, acosh, atanh, to_radians, to_degrees,
+    ceil, floor, trunc, exp, exp2, ln, log2, sqrt,
+);
+
 #[cfg(test)]
 mod tests {
     use std::vec;
@@ -2459,7 +3705,9 @@ mod tests {
         UniqueArena, VectorSize,
     };
 
-    use super::{Behavior, ConstantEvaluator, ExpressionKindTracker, WgslRestrictions};
+    use super::{
+        Behavior, ConstantEvaluator, ExpressionKindTracker, GlslRestrictions, WgslRestrictions,
+    };
 
     #[test]
     fn unary_op() {
@@ -2546,6 +3794,7 @@ mod tests {
             types: &mut types,
             constants: &constants,
             overrides: &overrides,
+            special_types: &crate::SpecialTypes::default(),
             expressions: &mut global_expressions,
             expression_kind_tracker,
         };
@@ -2632,6 +3881,7 @@ mod tests {
             types: &mut types,
             constants: &constants,
             overrides: &overrides,
+            special_types: &crate::SpecialTypes::default(),
             expressions: &mut global_expressions,
             expression_kind_tracker,
         };
@@ -2646,6 +3896,96 @@ mod tests {
         );
     }
 
+    #[test]
+    fn relational() {
+        let mut types = UniqueArena::new();
+        let constants = Arena::new();
+        let overrides = Arena::new();
+        let mut global_expressions = Arena::new();
+
+        let vec_ty = types.insert(
+            Type {
+                name: None,
+                inner: TypeInner::Vector {
+                    size: VectorSize::Tri,
+                    scalar: crate::Scalar::F32,
+                },
+            },
+            Default::default(),
+        );
+
+        // Literals can't be infinite or NaN, so only finite values can reach
+        // `isinf` and `isnan` during constant evaluation.
+        let components = [1.0, -0.0, f32::MAX]
+            .map(|value| {
+                global_expressions
+                    .append(Expression::Literal(Literal::F32(value)), Default::default())
+            })
+            .to_vec();
+        let argument = global_expressions.append(
+            Expression::Compose {
+                ty: vec_ty,
+                components,
+            },
+            Default::default(),
+        );
+
+        let expression_kind_tracker = &mut ExpressionKindTracker::from_arena(&global_expressions);
+        let mut solver = ConstantEvaluator {
+            behavior: Behavior::Glsl(GlslRestrictions::Const),
+            types: &mut types,
+            constants: &constants,
+            overrides: &overrides,
+            special_types: &crate::SpecialTypes::default(),
+            expressions: &mut global_expressions,
+            expression_kind_tracker,
+        };
+
+        let is_nan = solver
+            .try_eval_and_append(
+                Expression::Relational {
+                    fun: crate::RelationalFunction::IsNan,
+                    argument,
+                },
+                Default::default(),
+            )
+            .unwrap();
+        let is_inf = solver
+            .try_eval_and_append(
+                Expression::Relational {
+                    fun: crate::RelationalFunction::IsInf,
+                    argument,
+                },
+                Default::default(),
+            )
+            .unwrap();
+        let any = solver
+            .try_eval_and_append(
+                Expression::Relational {
+                    fun: crate::RelationalFunction::Any,
+                    argument: is_nan,
+                },
+                Default::default(),
+            )
+            .unwrap();
+
+        for result in [is_nan, is_inf] {
+            let Expression::Compose { ref components, .. } = global_expressions[result] else {
+                panic!("expected a vector, got {:?}", global_expressions[result]);
+            };
+            for &component in components {
+                assert_eq!(
+                    global_expressions[component],
+                    Expression::Literal(Literal::Bool(false))
+                );
+            }
+        }
+        assert_eq!(
+            global_expressions[any],
+            Expression::Literal(Literal::Bool(false))
+        );
+    }
+
     #[test]
     fn access() {
         let mut types = UniqueArena::new();
@@ -2750,6 +4090,7 @@ mod tests {
             types: &mut types,
             constants: &constants,
             overrides: &overrides,
+            special_types: &crate::SpecialTypes::default(),
             expressions: &mut global_expressions,
             expression_kind_tracker,
         };
@@ -2843,6 +4184,7 @@ mod tests {
             types: &mut types,
             constants: &constants,
             overrides: &overrides,
+            special_types: &crate::SpecialTypes::default(),
             expressions: &mut global_expressions,
             expression_kind_tracker,
         };
@@ -2925,6 +4267,7 @@ mod tests {
             types: &mut types,
             constants: &constants,
             overrides: &overrides,
+            special_types: &crate::SpecialTypes::default(),
             expressions: &mut global_expressions,
             expression_kind_tracker,
         };
@@ -8,6 +8,7 @@ const SEPARATOR: char = '_';
 #[derive(Debug, Eq, Hash, PartialEq)]
 pub enum NameKey {
     Constant(Handle<crate::Constant>),
+    Override(Handle<crate::Override>),
     GlobalVariable(Handle<crate::GlobalVariable>),
     Type(Handle<crate::Type>),
     StructMember(Handle<crate::Type>, u32),
@@ -129,6 +130,31 @@ impl Namer {
         }
     }
 
+    /// Find two names that [`Namer::call`] would build from the same base.
+    ///
+    /// Distinct names, such as `light` and `light_`, can differ only in
+    /// characters that `call` drops when it sanitizes them, and are then only
+    /// told apart by a numeric suffix. Each name comes with a group, such as
+    /// the file it was declared in, and only names from different groups are
+    /// compared.
+    ///
+    /// Returns the positions in `names` of the earlier and later name of the
+    /// first such pair.
+    pub fn find_collision<'a>(
+        &self,
+        names: impl IntoIterator<Item = (usize, &'a str)>,
+    ) -> Option<(usize, usize)> {
+        let mut bases: FastHashMap<Cow<'a, str>, Vec<(usize, usize)>> = FastHashMap::default();
+        for (index, (group, name)) in names.into_iter().enumerate() {
+            let earlier = bases.entry(self.sanitize(name)).or_default();
+            if let Some(&(_, previous)) = earlier.iter().find(|&&(other, _)| other != group) {
+                return Some((previous, index));
+            }
+            earlier.push((group, index));
+        }
+        None
+    }
+
     pub fn call_or(&mut self, label: &Option<String>, fallback: &str) -> String {
         self.call(match *label {
             Some(ref name) => name,
@@ -226,6 +252,11 @@ impl Namer {
             output.insert(NameKey::GlobalVariable(handle), name);
         }
 
+        for (handle, override_) in module.overrides.iter() {
+            let name = self.call_or(&override_.name, "override");
+            output.insert(NameKey::Override(handle), name);
+        }
+
         for (handle, constant) in module.constants.iter() {
             let label = match constant.name {
                 Some(ref name) => name,
@@ -279,3 +310,17 @@ fn test() {
     assert_eq!(namer.call("__x"), "_x");
     assert_eq!(namer.call("1___x"), "_x_1");
 }
+
+#[test]
+fn find_collision() {
+    let namer = Namer::default();
+    assert_eq!(namer.find_collision([(0, "light"), (0, "light_")]), None);
+    assert_eq!(
+        namer.find_collision([(0, "light"), (0, "shadow"), (1, "light_")]),
+        Some((0, 2))
+    );
+    assert_eq!(
+        namer.find_collision([(0, "a__b"), (1, "a_b")]),
+        Some((0, 1))
+    );
+}
@@ -706,6 +706,8 @@ impl<'a> ResolveContext<'a> {
                             )))
                         }
                     },
+                    Mf::Dot4I8Packed => TypeResolution::Value(Ti::Scalar(crate::Scalar::I32)),
+                    Mf::Dot4U8Packed => TypeResolution::Value(Ti::Scalar(crate::Scalar::U32)),
                     Mf::Outer => {
                         let arg1 = arg1.ok_or_else(|| {
                             ResolveError::IncompatibleOperands(format!("{fun:?}(_, None)"))
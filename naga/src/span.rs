@@ -240,11 +240,21 @@ impl<E> WithSpan<E> {
     }
 
     pub(crate) fn diagnostic(&self) -> codespan_reporting::diagnostic::Diagnostic<()>
+    where
+        E: Error,
+    {
+        self.diagnostic_with_severity(codespan_reporting::diagnostic::Severity::Error)
+    }
+
+    pub(crate) fn diagnostic_with_severity(
+        &self,
+        severity: codespan_reporting::diagnostic::Severity,
+    ) -> codespan_reporting::diagnostic::Diagnostic<()>
     where
         E: Error,
     {
         use codespan_reporting::diagnostic::{Diagnostic, Label};
-        let diagnostic = Diagnostic::error()
+        let diagnostic = Diagnostic::new(severity)
             .with_message(self.inner.to_string())
             .with_labels(
                 self.spans()
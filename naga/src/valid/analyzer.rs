@@ -6,7 +6,7 @@
 //! - expression reference counts
 
 use super::{ExpressionError, FunctionError, ModuleInfo, ShaderStages, ValidationFlags};
-use crate::diagnostic_filter::{DiagnosticFilterNode, StandardFilterableTriggeringRule};
+use crate::diagnostic_filter::{DiagnosticFilterNode, Severity, StandardFilterableTriggeringRule};
 use crate::span::{AddSpan as _, WithSpan};
 use crate::{
     arena::{Arena, Handle},
@@ -226,6 +226,36 @@ struct Sampling {
     sampler: GlobalOrArgument,
 }
 
+/// Types of the mesh shader outputs written by a function.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
+pub struct FunctionMeshShaderInfo {
+    /// Type of the values passed to [`MeshFunction::SetVertex`], if any.
+    ///
+    /// [`MeshFunction::SetVertex`]: crate::MeshFunction::SetVertex
+    pub vertex_type: Option<Handle<crate::Type>>,
+    /// Type of the values passed to [`MeshFunction::SetPrimitive`], if any.
+    ///
+    /// [`MeshFunction::SetPrimitive`]: crate::MeshFunction::SetPrimitive
+    pub primitive_type: Option<Handle<crate::Type>>,
+}
+
+impl FunctionMeshShaderInfo {
+    /// Record that an output of type `ty` is written to `slot`.
+    ///
+    /// Returns `false` if `slot` already recorded a different type.
+    fn merge_type(slot: &mut Option<Handle<crate::Type>>, ty: Handle<crate::Type>) -> bool {
+        match *slot {
+            Some(existing) => existing == ty,
+            None => {
+                *slot = Some(ty);
+                true
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
 #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
@@ -289,15 +319,69 @@ pub struct FunctionInfo {
     /// Indicates that the function is using dual source blending.
     pub dual_source_blending: bool,
 
+    /// Types of the vertices and primitives this function writes with
+    /// [`MeshFunction`] statements.
+    ///
+    /// [`MeshFunction`]: crate::Statement::MeshFunction
+    pub mesh_shader_info: FunctionMeshShaderInfo,
+
     /// The leaf of all module-wide diagnostic filter rules tree parsed from directives in this
     /// module.
     ///
     /// See [`DiagnosticFilterNode`] for details on how the tree is represented and used in
     /// validation.
     diagnostic_filter_leaf: Option<Handle<DiagnosticFilterNode>>,
+
+    /// Findings reported at [`Severity::Info`] or [`Severity::Warning`] while
+    /// validating this function.
+    ///
+    /// These are moved to [`ModuleInfo::warnings`] once the whole function has
+    /// been validated.
+    #[cfg_attr(any(feature = "serialize", feature = "deserialize"), serde(skip))]
+    pub(super) warnings: Vec<FunctionWarning>,
+}
+
+/// A [`ValidationWarning`] that has not been attributed to a function yet.
+///
+/// [`ValidationWarning`]: super::ValidationWarning
+#[derive(Clone, Debug)]
+pub(super) struct FunctionWarning {
+    pub rule: StandardFilterableTriggeringRule,
+    pub severity: Severity,
+    pub error: WithSpan<FunctionError>,
 }
 
 impl FunctionInfo {
+    /// Return the severity of `rule` in this function.
+    pub(super) fn severity(
+        &self,
+        rule: StandardFilterableTriggeringRule,
+        diagnostic_filter_arena: &Arena<DiagnosticFilterNode>,
+    ) -> Severity {
+        DiagnosticFilterNode::search(self.diagnostic_filter_leaf, diagnostic_filter_arena, rule)
+    }
+
+    /// Report `error`, which was triggered by `rule`, at the severity the
+    /// diagnostic filters in effect in this function give it.
+    ///
+    /// Returns `error` if it should fail validation.
+    pub(super) fn report(
+        &mut self,
+        rule: StandardFilterableTriggeringRule,
+        error: WithSpan<FunctionError>,
+        diagnostic_filter_arena: &Arena<DiagnosticFilterNode>,
+    ) -> Result<(), WithSpan<FunctionError>> {
+        let severity = self.severity(rule, diagnostic_filter_arena);
+        severity.report_diag(error, |error, level| {
+            log::log!(level, "{error}");
+            self.warnings.push(FunctionWarning {
+                rule,
+                severity,
+                error,
+            });
+        })
+    }
+
     pub const fn global_variable_count(&self) -> usize {
         self.global_uses.len()
     }
@@ -628,6 +712,9 @@ impl FunctionInfo {
                     As::Function | As::Private => false,
                     // workgroup memory is exclusively accessed by the group
                     As::WorkGroup => true,
+                    // the task payload is shared by the task shader's group,
+                    // and by all of the mesh shader groups it launches
+                    As::TaskPayload => true,
                     // uniform data
                     As::Uniform | As::PushConstant => true,
                     // storage data is only uniform when read-only
@@ -849,20 +936,11 @@ impl FunctionInfo {
                             && !req.is_empty()
                         {
                             if let Some(cause) = disruptor {
-                                let severity = DiagnosticFilterNode::search(
-                                    self.diagnostic_filter_leaf,
-                                    diagnostic_filter_arena,
+                                self.report(
                                     StandardFilterableTriggeringRule::DerivativeUniformity,
-                                );
-                                severity.report_diag(
                                     FunctionError::NonUniformControlFlow(req, expr, cause)
                                         .with_span_handle(expr, expression_arena),
-                                    // TODO: Yes, this isn't contextualized with source, because
-                                    // the user is supposed to render what would normally be an
-                                    // error here. Once we actually support warning-level
-                                    // diagnostic items, then we won't need this non-compliant hack:
-                                    // <https://github.com/gfx-rs/wgpu/issues/6458>
-                                    |e, level| log::log!(level, "{e}"),
+                                    diagnostic_filter_arena,
                                 )?;
                             }
                         }
@@ -1063,13 +1141,20 @@ impl FunctionInfo {
                 }
                 S::RayQuery { query, ref fun } => {
                     let _ = self.add_ref(query);
-                    if let crate::RayQueryFunction::Initialize {
-                        acceleration_structure,
-                        descriptor,
-                    } = *fun
-                    {
-                        let _ = self.add_ref(acceleration_structure);
-                        let _ = self.add_ref(descriptor);
+                    match *fun {
+                        crate::RayQueryFunction::Initialize {
+                            acceleration_structure,
+                            descriptor,
+                        } => {
+                            let _ = self.add_ref(acceleration_structure);
+                            let _ = self.add_ref(descriptor);
+                        }
+                        crate::RayQueryFunction::GenerateIntersection { hit_t } => {
+                            let _ = self.add_ref(hit_t);
+                        }
+                        crate::RayQueryFunction::Proceed { result: _ }
+                        | crate::RayQueryFunction::ConfirmIntersection
+                        | crate::RayQueryFunction::Terminate => {}
                     }
                     FunctionUniformity::new()
                 }
@@ -1098,13 +1183,48 @@ impl FunctionInfo {
                 } => {
                     let _ = self.add_ref(argument);
                     match mode {
-                        crate::GatherMode::BroadcastFirst => {}
+                        crate::GatherMode::BroadcastFirst | crate::GatherMode::QuadSwap(_) => {}
                         crate::GatherMode::Broadcast(index)
                         | crate::GatherMode::Shuffle(index)
                         | crate::GatherMode::ShuffleDown(index)
                         | crate::GatherMode::ShuffleUp(index)
-                        | crate::GatherMode::ShuffleXor(index) => {
+                        | crate::GatherMode::ShuffleXor(index)
+                        | crate::GatherMode::QuadBroadcast(index) => {
+                            let _ = self.add_ref(index);
+                        }
+                    }
+                    FunctionUniformity::new()
+                }
+                S::MeshFunction(ref fun) => {
+                    match *fun {
+                        crate::MeshFunction::SetMeshOutputs {
+                            vertex_count,
+                            primitive_count,
+                        } => {
+                            let _ = self.add_ref(vertex_count);
+                            let _ = self.add_ref(primitive_count);
+                        }
+                        crate::MeshFunction::SetVertex { index, value }
+                        | crate::MeshFunction::SetPrimitive { index, value } => {
                             let _ = self.add_ref(index);
+                            let _ = self.add_ref(value);
+                            let ty = match self.expressions[value.index()].ty {
+                                TypeResolution::Handle(ty) => ty,
+                                TypeResolution::Value(_) => {
+                                    return Err(FunctionError::InvalidMeshOutputValue(value)
+                                        .with_span_handle(value, expression_arena))
+                                }
+                            };
+                            let slot = match *fun {
+                                crate::MeshFunction::SetVertex { .. } => {
+                                    &mut self.mesh_shader_info.vertex_type
+                                }
+                                _ => &mut self.mesh_shader_info.primitive_type,
+                            };
+                            if !FunctionMeshShaderInfo::merge_type(slot, ty) {
+                                return Err(FunctionError::ConflictingMeshOutputType(ty)
+                                    .with_span_handle(value, expression_arena));
+                            }
                         }
                     }
                     FunctionUniformity::new()
@@ -1150,7 +1270,9 @@ impl ModuleInfo {
             expressions: vec![ExpressionInfo::new(); fun.expressions.len()].into_boxed_slice(),
             sampling: crate::FastHashSet::default(),
             dual_source_blending: false,
+            mesh_shader_info: FunctionMeshShaderInfo::default(),
             diagnostic_filter_leaf: fun.diagnostic_filter_leaf,
+            warnings: Vec::new(),
         };
         let resolve_context =
             ResolveContext::with_locals(module, &fun.local_variables, &fun.arguments);
@@ -1270,7 +1392,9 @@ fn uniform_control_flow() {
         expressions: vec![ExpressionInfo::new(); expressions.len()].into_boxed_slice(),
         sampling: crate::FastHashSet::default(),
         dual_source_blending: false,
+        mesh_shader_info: FunctionMeshShaderInfo::default(),
         diagnostic_filter_leaf: None,
+        warnings: Vec::new(),
     };
     let resolve_context = ResolveContext {
         constants: &Arena::new(),
@@ -1199,6 +1199,22 @@ impl super::Validator {
                             ));
                         }
                     }
+                    Mf::Dot4I8Packed | Mf::Dot4U8Packed => {
+                        let arg1_ty = match (arg1_ty, arg2_ty, arg3_ty) {
+                            (Some(ty1), None, None) => ty1,
+                            _ => return Err(ExpressionError::WrongArgumentCount(fun)),
+                        };
+                        if !matches!(*arg_ty, Ti::Scalar(Sc::U32)) {
+                            return Err(ExpressionError::InvalidArgumentType(fun, 0, arg));
+                        }
+                        if !matches!(*arg1_ty, Ti::Scalar(Sc::U32)) {
+                            return Err(ExpressionError::InvalidArgumentType(
+                                fun,
+                                1,
+                                arg1.unwrap(),
+                            ));
+                        }
+                    }
                     Mf::Outer | Mf::Reflect => {
                         let arg1_ty = match (arg1_ty, arg2_ty, arg3_ty) {
                             (Some(ty1), None, None) => ty1,
@@ -1672,7 +1688,7 @@ impl super::Validator {
                     // WorkGroupUniformLoad
                     .contains(TypeFlags::SIZED | TypeFlags::CONSTRUCTIBLE)
                 {
-                    ShaderStages::COMPUTE
+                    ShaderStages::COMPUTE_LIKE
                 } else {
                     return Err(ExpressionError::InvalidWorkGroupUniformLoadResultType(ty));
                 }
@@ -1759,6 +1775,7 @@ pub fn check_literal_value(literal: crate::Literal) -> Result<(), LiteralError>
     let is_nan = match literal {
         crate::Literal::F64(v) => v.is_nan(),
         crate::Literal::F32(v) => v.is_nan(),
+        crate::Literal::F16(v) => v.is_nan(),
         _ => false,
     };
     if is_nan {
@@ -1768,6 +1785,7 @@ pub fn check_literal_value(literal: crate::Literal) -> Result<(), LiteralError>
     let is_infinite = match literal {
         crate::Literal::F64(v) => v.is_infinite(),
         crate::Literal::F32(v) => v.is_infinite(),
+        crate::Literal::F16(v) => v.is_infinite(),
         _ => false,
     };
     if is_infinite {
@@ -1874,3 +1892,34 @@ fn f64_const_literals() {
     );
     assert!(result.is_ok());
 }
+
+/// Using F16 requires the `SHADER_FLOAT16` capability.
+#[test]
+fn f16_literals() {
+    let result = validate_with_expression(
+        crate::Expression::Literal(crate::Literal::F16(half::f16::from_f32(1.5))),
+        super::Capabilities::default(),
+    );
+    let error = result.unwrap_err().into_inner();
+    assert!(matches!(
+        error,
+        crate::valid::ValidationError::Function {
+            source: super::FunctionError::Expression {
+                source: ExpressionError::Literal(LiteralError::Width(
+                    super::r#type::WidthError::MissingCapability {
+                        name: "f16",
+                        flag: "SHADER_FLOAT16",
+                    }
+                ),),
+                ..
+            },
+            ..
+        }
+    ));
+
+    let result = validate_with_expression(
+        crate::Expression::Literal(crate::Literal::F16(half::f16::from_f32(1.5))),
+        super::Capabilities::default() | super::Capabilities::SHADER_FLOAT16,
+    );
+    assert!(result.is_ok());
+}
@@ -72,6 +72,10 @@ pub enum SubgroupError {
     UnsupportedOperation(super::SubgroupOperationSet),
     #[error("Unknown operation")]
     UnknownOperation,
+    #[error("Quad lane index {0:?} must be a const-expression less than 4")]
+    InvalidQuadIndex(Handle<crate::Expression>),
+    #[error("Cluster size {0} is not a power of two")]
+    InvalidClusterSize(u32),
 }
 
 #[derive(Clone, Debug, thiserror::Error)]
@@ -162,6 +166,8 @@ pub enum FunctionError {
     InvalidRayDescriptor(Handle<crate::Expression>),
     #[error("Ray Query {0:?} does not have a matching type")]
     InvalidRayQueryType(Handle<crate::Type>),
+    #[error("Hit distance {0:?} of a generated ray query intersection must be an `f32` scalar")]
+    InvalidHitDistanceType(Handle<crate::Expression>),
     #[error("Shader requires capability {0:?}")]
     MissingCapability(super::Capabilities),
     #[error(
@@ -190,6 +196,23 @@ pub enum FunctionError {
     EmitResult(Handle<crate::Expression>),
     #[error("Expression not visited by the appropriate statement")]
     UnvisitedExpression(Handle<crate::Expression>),
+    #[error("Mesh shader output {0:?} is not a value of a named struct type")]
+    InvalidMeshOutputValue(Handle<crate::Expression>),
+    #[error("Mesh shader outputs of type {0:?} conflict with outputs of another type")]
+    ConflictingMeshOutputType(Handle<crate::Type>),
+    #[error("Mesh shader output count or index {0:?} is not a `u32` scalar")]
+    InvalidMeshOutputIndex(Handle<crate::Expression>),
+    #[error("Mesh shader outputs can only be written by the entry point function itself")]
+    MeshFunctionOutsideEntryPoint,
+    #[error("Local variable {handle:?} '{name}' is never read")]
+    UnusedLocalVariable {
+        handle: Handle<crate::LocalVariable>,
+        name: String,
+    },
+    #[error("Argument '{name}' at index {index} is never used")]
+    UnusedArgument { index: usize, name: String },
+    #[error("Declaration of '{name}' shadows another declaration")]
+    ShadowedDeclaration { name: String },
 }
 
 bitflags::bitflags! {
@@ -221,6 +244,9 @@ struct BlockContext<'a> {
     special_types: &'a crate::SpecialTypes,
     prev_infos: &'a [FunctionInfo],
     return_type: Option<Handle<crate::Type>>,
+    entry_point: bool,
+    local_expr_kind: &'a crate::proc::ExpressionKindTracker,
+    global_ctx: crate::proc::GlobalCtx<'a>,
 }
 
 impl<'a> BlockContext<'a> {
@@ -229,6 +255,8 @@ impl<'a> BlockContext<'a> {
         module: &'a crate::Module,
         info: &'a FunctionInfo,
         prev_infos: &'a [FunctionInfo],
+        entry_point: bool,
+        local_expr_kind: &'a crate::proc::ExpressionKindTracker,
     ) -> Self {
         Self {
             abilities: ControlFlowAbility::RETURN,
@@ -241,6 +269,9 @@ impl<'a> BlockContext<'a> {
             special_types: &module.special_types,
             prev_infos,
             return_type: fun.result.as_ref().map(|fr| fr.ty),
+            entry_point,
+            local_expr_kind,
+            global_ctx: module.to_ctx(),
         }
     }
 
@@ -596,6 +627,16 @@ impl super::Validator {
                 | sg::Xor,
             ) => {}
             (co::InclusiveScan | co::ExclusiveScan, sg::Add | sg::Mul) => {}
+            (
+                co::ClusteredReduce(size),
+                sg::Add | sg::Mul | sg::Min | sg::Max | sg::And | sg::Or | sg::Xor,
+            ) => {
+                if !size.is_power_of_two() {
+                    return Err(SubgroupError::InvalidClusterSize(size)
+                        .with_span()
+                        .into_other());
+                }
+            }
 
             (_, _) => {
                 return Err(SubgroupError::UnknownOperation.with_span().into_other());
@@ -642,6 +683,22 @@ impl super::Validator {
                     }
                 }
             }
+            crate::GatherMode::QuadBroadcast(index) => {
+                let index_ty = context.resolve_type(index, &self.valid_expression_set)?;
+                let in_range = context
+                    .global_ctx
+                    .eval_expr_to_u32_from(index, context.expressions)
+                    .map_or(true, |value| value < 4);
+                if *index_ty != crate::TypeInner::Scalar(crate::Scalar::U32)
+                    || !context.local_expr_kind.is_const(index)
+                    || !in_range
+                {
+                    return Err(SubgroupError::InvalidQuadIndex(index)
+                        .with_span_handle(index, context.expressions)
+                        .into_other());
+                }
+            }
+            crate::GatherMode::QuadSwap(_) => {}
         }
         let argument_inner = context.resolve_type(argument, &self.valid_expression_set)?;
         if !matches!(*argument_inner,
@@ -919,7 +976,7 @@ impl super::Validator {
                     finished = true;
                 }
                 S::Barrier(barrier) => {
-                    stages &= super::ShaderStages::COMPUTE;
+                    stages &= super::ShaderStages::COMPUTE_LIKE;
                     if barrier.contains(crate::Barrier::SUB_GROUP) {
                         if !self.capabilities.contains(
                             super::Capabilities::SUBGROUP | super::Capabilities::SUBGROUP_BARRIER,
@@ -1137,7 +1194,7 @@ impl super::Validator {
                     self.validate_atomic(pointer, fun, value, result, span, context)?;
                 }
                 S::WorkGroupUniformLoad { pointer, result } => {
-                    stages &= super::ShaderStages::COMPUTE;
+                    stages &= super::ShaderStages::COMPUTE_LIKE;
                     let pointer_inner =
                         context.resolve_type(pointer, &self.valid_expression_set)?;
                     match *pointer_inner {
@@ -1220,6 +1277,19 @@ impl super::Validator {
                         crate::RayQueryFunction::Proceed { result } => {
                             self.emit_expression(result, context)?;
                         }
+                        crate::RayQueryFunction::GenerateIntersection { hit_t } => {
+                            match *context.resolve_type(hit_t, &self.valid_expression_set)? {
+                                Ti::Scalar(crate::Scalar {
+                                    kind: crate::ScalarKind::Float,
+                                    width: 4,
+                                }) => {}
+                                _ => {
+                                    return Err(FunctionError::InvalidHitDistanceType(hit_t)
+                                        .with_span_static(span, "invalid hit_t"))
+                                }
+                            }
+                        }
+                        crate::RayQueryFunction::ConfirmIntersection => {}
                         crate::RayQueryFunction::Terminate => {}
                     }
                 }
@@ -1273,10 +1343,12 @@ impl super::Validator {
                         )
                         .with_span_static(span, "missing capability for this operation"));
                     }
-                    let operation = op.required_operations();
+                    let operation = op.required_operations() | collective_op.required_operations();
                     if !self.subgroup_operations.contains(operation) {
                         return Err(FunctionError::InvalidSubgroup(
-                            SubgroupError::UnsupportedOperation(operation),
+                            SubgroupError::UnsupportedOperation(
+                                operation - self.subgroup_operations,
+                            ),
                         )
                         .with_span_static(span, "support for this operation is not present"));
                     }
@@ -1301,8 +1373,52 @@ impl super::Validator {
                         )
                         .with_span_static(span, "support for this operation is not present"));
                     }
+                    if operation.contains(super::SubgroupOperationSet::QUAD_FRAGMENT_COMPUTE)
+                        && !self
+                            .subgroup_operations
+                            .contains(super::SubgroupOperationSet::QUAD_ALL_STAGES)
+                    {
+                        stages &= super::ShaderStages::FRAGMENT | super::ShaderStages::COMPUTE;
+                    }
                     self.validate_subgroup_gather(mode, argument, result, context)?;
                 }
+                S::MeshFunction(ref fun) => {
+                    stages &= super::ShaderStages::MESH;
+                    if !self.capabilities.contains(super::Capabilities::MESH_SHADER) {
+                        return Err(FunctionError::MissingCapability(
+                            super::Capabilities::MESH_SHADER,
+                        )
+                        .with_span_static(span, "missing capability for this operation"));
+                    }
+                    // Backends write mesh outputs through entry point
+                    // variables, so other functions can't reach them.
+                    if !context.entry_point {
+                        return Err(FunctionError::MeshFunctionOutsideEntryPoint
+                            .with_span_static(span, "mesh function used here"));
+                    }
+                    let check_u32 =
+                        |index| match *context.resolve_type(index, &self.valid_expression_set)? {
+                            Ti::Scalar(crate::Scalar::U32) => Ok(()),
+                            _ => Err(FunctionError::InvalidMeshOutputIndex(index)
+                                .with_span_handle(index, context.expressions)),
+                        };
+                    match *fun {
+                        crate::MeshFunction::SetMeshOutputs {
+                            vertex_count,
+                            primitive_count,
+                        } => {
+                            check_u32(vertex_count)?;
+                            check_u32(primitive_count)?;
+                        }
+                        crate::MeshFunction::SetVertex { index, value }
+                        | crate::MeshFunction::SetPrimitive { index, value } => {
+                            check_u32(index)?;
+                            // The value's type is checked against the entry
+                            // point's `MeshStageInfo` by the interface validator.
+                            context.resolve_type(value, &self.valid_expression_set)?;
+                        }
+                    }
+                }
             }
         }
         Ok(BlockInfo { stages, finished })
@@ -1380,6 +1496,13 @@ impl super::Validator {
         for (index, argument) in fun.arguments.iter().enumerate() {
             match module.types[argument.ty].inner.pointer_space() {
                 Some(crate::AddressSpace::Private | crate::AddressSpace::Function) | None => {}
+                Some(
+                    crate::AddressSpace::Uniform
+                    | crate::AddressSpace::Storage { .. }
+                    | crate::AddressSpace::WorkGroup,
+                ) if self
+                    .capabilities
+                    .contains(super::Capabilities::UNRESTRICTED_POINTER_PARAMETERS) => {}
                 Some(other) => {
                     return Err(FunctionError::InvalidArgumentPointerSpace {
                         index,
@@ -1462,7 +1585,14 @@ impl super::Validator {
             let stages = self
                 .validate_block(
                     &fun.body,
-                    &BlockContext::new(fun, module, &info, &mod_info.functions),
+                    &BlockContext::new(
+                        fun,
+                        module,
+                        &info,
+                        &mod_info.functions,
+                        entry_point,
+                        &local_expr_kind,
+                    ),
                 )?
                 .stages;
             info.available_stages &= stages;
@@ -1474,6 +1604,8 @@ impl super::Validator {
                 }
             }
         }
+
+        info.lint(fun, module, entry_point)?;
         Ok(info)
     }
 }
@@ -175,6 +175,13 @@ impl super::Validator {
 
         for entry_point in entry_points.iter() {
             validate_function(None, &entry_point.function)?;
+            if let Some(ref mesh_info) = entry_point.mesh_info {
+                validate_type(mesh_info.vertex_output_type)?;
+                validate_type(mesh_info.primitive_output_type)?;
+            }
+            if let Some(task_payload) = entry_point.task_payload {
+                task_payload.check_valid_for(global_variables)?;
+            }
         }
 
         for (function_handle, function) in functions.iter() {
@@ -582,6 +589,10 @@ impl super::Validator {
                     crate::RayQueryFunction::Proceed { result } => {
                         validate_expr(result)?;
                     }
+                    crate::RayQueryFunction::GenerateIntersection { hit_t } => {
+                        validate_expr(hit_t)?;
+                    }
+                    crate::RayQueryFunction::ConfirmIntersection => {}
                     crate::RayQueryFunction::Terminate => {}
                 }
                 Ok(())
@@ -608,16 +619,33 @@ impl super::Validator {
             } => {
                 validate_expr(argument)?;
                 match mode {
-                    crate::GatherMode::BroadcastFirst => {}
+                    crate::GatherMode::BroadcastFirst | crate::GatherMode::QuadSwap(_) => {}
                     crate::GatherMode::Broadcast(index)
                     | crate::GatherMode::Shuffle(index)
                     | crate::GatherMode::ShuffleDown(index)
                     | crate::GatherMode::ShuffleUp(index)
-                    | crate::GatherMode::ShuffleXor(index) => validate_expr(index)?,
+                    | crate::GatherMode::ShuffleXor(index)
+                    | crate::GatherMode::QuadBroadcast(index) => validate_expr(index)?,
                 }
                 validate_expr(result)?;
                 Ok(())
             }
+            crate::Statement::MeshFunction(ref fun) => match *fun {
+                crate::MeshFunction::SetMeshOutputs {
+                    vertex_count,
+                    primitive_count,
+                } => {
+                    validate_expr(vertex_count)?;
+                    validate_expr(primitive_count)?;
+                    Ok(())
+                }
+                crate::MeshFunction::SetVertex { index, value }
+                | crate::MeshFunction::SetPrimitive { index, value } => {
+                    validate_expr(index)?;
+                    validate_expr(value)?;
+                    Ok(())
+                }
+            },
             crate::Statement::Break
             | crate::Statement::Continue
             | crate::Statement::Kill
@@ -84,6 +84,16 @@ pub enum VaryingError {
     },
     #[error("Workgroup size is multi dimensional, @builtin(subgroup_id) and @builtin(subgroup_invocation_id) are not supported.")]
     InvalidMultiDimensionalSubgroupBuiltIn,
+    #[error("The clip distances array has {count} elements, but at most {limit} are allowed")]
+    TooManyClipDistances { count: u32, limit: u32 },
+    #[error("Built-in {0:?} is not valid in mesh shader {1} outputs")]
+    InvalidMeshOutputBuiltIn(crate::BuiltIn, &'static str),
+    #[error("Location {location} must {not}be `per_primitive` in mesh shader {output} outputs", not = if *.per_primitive { "" } else { "not " })]
+    InvalidMeshOutputLocation {
+        location: u32,
+        output: &'static str,
+        per_primitive: bool,
+    },
 }
 
 #[derive(Clone, Debug, thiserror::Error)]
@@ -119,6 +129,41 @@ pub enum EntryPointError {
         "Invalid locations {location_mask:?} are set while dual source blending. Only location 0 may be set."
     )]
     InvalidLocationsWhileDualSourceBlending { location_mask: BitSet },
+    #[error("The two sources of dual source blending have different types {0:?} and {1:?}")]
+    DualSourceBlendingTypeMismatch(Handle<crate::Type>, Handle<crate::Type>),
+    #[error(
+        "The second source of dual source blending is used without a first source at location 0"
+    )]
+    MissingFirstBlendSource,
+    #[error("Capability {0:?} is not supported")]
+    UnsupportedCapability(Capabilities),
+    #[error("Task shaders must return a `@builtin(mesh_task_size)` output value")]
+    MissingTaskOutputMeshTaskSize,
+    #[error("Mesh shaders must not return a value")]
+    UnexpectedMeshShaderResult,
+    #[error("Mesh shader output information is missing")]
+    MissingMeshStageInfo,
+    #[error("Mesh shader output information is only applicable to mesh shaders")]
+    UnexpectedMeshStageInfo,
+    #[error("Mesh shaders must produce at least one vertex and one primitive")]
+    InvalidMeshOutputCount,
+    #[error("Mesh shader {output} output type {ty:?} is not a struct")]
+    InvalidMeshOutputType {
+        output: &'static str,
+        ty: Handle<crate::Type>,
+    },
+    #[error("Mesh shader primitive outputs must include {0:?} for their topology")]
+    MissingMeshPrimitiveIndices(crate::BuiltIn),
+    #[error("Mesh shader outputs of type {0:?} do not match the type declared by the entry point")]
+    MeshOutputTypeMismatch(Handle<crate::Type>),
+    #[error("A task payload is only applicable to task and mesh shaders")]
+    UnexpectedTaskPayload,
+    #[error("Task payload {0:?} is not in the `task_payload` address space")]
+    InvalidTaskPayload(Handle<crate::GlobalVariable>),
+    #[error(
+        "Global variable {0:?} is used as a task payload, but is not the entry point's payload"
+    )]
+    UndeclaredTaskPayload(Handle<crate::GlobalVariable>),
 }
 
 fn storage_usage(access: crate::StorageAccess) -> GlobalUse {
@@ -141,6 +186,7 @@ struct VaryingContext<'a> {
     location_mask: &'a mut BitSet,
     built_ins: &'a mut crate::FastHashSet<crate::BuiltIn>,
     capabilities: Capabilities,
+    max_clip_distances: u32,
     flags: super::ValidationFlags,
 }
 
@@ -179,6 +225,11 @@ impl VaryingContext<'_> {
                     | Bi::SubgroupId
                     | Bi::SubgroupSize
                     | Bi::SubgroupInvocationId => Capabilities::SUBGROUP,
+                    Bi::MeshTaskSize
+                    | Bi::PointIndex
+                    | Bi::LineIndices
+                    | Bi::TriangleIndices
+                    | Bi::CullPrimitive => Capabilities::MESH_SHADER,
                     _ => Capabilities::empty(),
                 };
                 if !self.capabilities.contains(required) {
@@ -203,7 +254,7 @@ impl VaryingContext<'_> {
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
                     Bi::ClipDistance | Bi::CullDistance => (
-                        self.stage == St::Vertex && self.output,
+                        matches!(self.stage, St::Vertex | St::Mesh) && self.output,
                         match *ty_inner {
                             Ti::Array { base, .. } => {
                                 self.types[base].inner == Ti::Scalar(crate::Scalar::F32)
@@ -212,7 +263,7 @@ impl VaryingContext<'_> {
                         },
                     ),
                     Bi::PointSize => (
-                        self.stage == St::Vertex && self.output,
+                        matches!(self.stage, St::Vertex | St::Mesh) && self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::F32),
                     ),
                     Bi::PointCoord => (
@@ -225,9 +276,9 @@ impl VaryingContext<'_> {
                     ),
                     Bi::Position { .. } => (
                         match self.stage {
-                            St::Vertex => self.output,
+                            St::Vertex | St::Mesh => self.output,
                             St::Fragment => !self.output,
-                            St::Compute => false,
+                            St::Compute | St::Task => false,
                         },
                         *ty_inner
                             == Ti::Vector {
@@ -238,7 +289,7 @@ impl VaryingContext<'_> {
                     Bi::ViewIndex => (
                         match self.stage {
                             St::Vertex | St::Fragment => !self.output,
-                            St::Compute => false,
+                            St::Compute | St::Task | St::Mesh => false,
                         },
                         *ty_inner == Ti::Scalar(crate::Scalar::I32),
                     ),
@@ -263,7 +314,7 @@ impl VaryingContext<'_> {
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
                     Bi::LocalInvocationIndex => (
-                        self.stage == St::Compute && !self.output,
+                        matches!(self.stage, St::Compute | St::Task | St::Mesh) && !self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
                     Bi::GlobalInvocationId
@@ -271,7 +322,7 @@ impl VaryingContext<'_> {
                     | Bi::WorkGroupId
                     | Bi::WorkGroupSize
                     | Bi::NumWorkGroups => (
-                        self.stage == St::Compute && !self.output,
+                        matches!(self.stage, St::Compute | St::Task | St::Mesh) && !self.output,
                         *ty_inner
                             == Ti::Vector {
                                 size: Vs::Tri,
@@ -279,16 +330,48 @@ impl VaryingContext<'_> {
                             },
                     ),
                     Bi::NumSubgroups | Bi::SubgroupId => (
-                        self.stage == St::Compute && !self.output,
+                        matches!(self.stage, St::Compute | St::Task | St::Mesh) && !self.output,
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
                     Bi::SubgroupSize | Bi::SubgroupInvocationId => (
                         match self.stage {
-                            St::Compute | St::Fragment => !self.output,
+                            St::Compute | St::Fragment | St::Task | St::Mesh => !self.output,
                             St::Vertex => false,
                         },
                         *ty_inner == Ti::Scalar(crate::Scalar::U32),
                     ),
+                    Bi::MeshTaskSize => (
+                        self.stage == St::Task && self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Tri,
+                                scalar: crate::Scalar::U32,
+                            },
+                    ),
+                    Bi::PointIndex => (
+                        self.stage == St::Mesh && self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::U32),
+                    ),
+                    Bi::LineIndices => (
+                        self.stage == St::Mesh && self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Bi,
+                                scalar: crate::Scalar::U32,
+                            },
+                    ),
+                    Bi::TriangleIndices => (
+                        self.stage == St::Mesh && self.output,
+                        *ty_inner
+                            == Ti::Vector {
+                                size: Vs::Tri,
+                                scalar: crate::Scalar::U32,
+                            },
+                    ),
+                    Bi::CullPrimitive => (
+                        self.stage == St::Mesh && self.output,
+                        *ty_inner == Ti::Scalar(crate::Scalar::BOOL),
+                    ),
                 };
 
                 if !visible {
@@ -298,12 +381,28 @@ impl VaryingContext<'_> {
                     log::warn!("Wrong builtin type: {:?}", ty_inner);
                     return Err(VaryingError::InvalidBuiltInType(built_in));
                 }
+                if let (
+                    Bi::ClipDistance,
+                    &Ti::Array {
+                        size: crate::ArraySize::Constant(size),
+                        ..
+                    },
+                ) = (built_in, ty_inner)
+                {
+                    if size.get() > self.max_clip_distances {
+                        return Err(VaryingError::TooManyClipDistances {
+                            count: size.get(),
+                            limit: self.max_clip_distances,
+                        });
+                    }
+                }
             }
             crate::Binding::Location {
                 location,
                 interpolation,
                 sampling,
                 second_blend_source,
+                per_primitive,
             } => {
                 // Only IO-shareable types may be stored in locations.
                 if !self.type_info[ty.index()]
@@ -348,6 +447,27 @@ impl VaryingContext<'_> {
                     }
                 }
 
+                if per_primitive {
+                    if !self.capabilities.contains(Capabilities::MESH_SHADER) {
+                        return Err(VaryingError::UnsupportedCapability(
+                            Capabilities::MESH_SHADER,
+                        ));
+                    }
+                    // Per-primitive values flow from mesh shader outputs to
+                    // fragment shader inputs.
+                    let valid = match self.stage {
+                        crate::ShaderStage::Mesh => self.output,
+                        crate::ShaderStage::Fragment => !self.output,
+                        _ => false,
+                    };
+                    if !valid {
+                        return Err(VaryingError::InvalidAttributeInStage(
+                            "per_primitive",
+                            self.stage,
+                        ));
+                    }
+                }
+
                 if let Some(interpolation) = interpolation {
                     let invalid_sampling = match (interpolation, sampling) {
                         (_, None)
@@ -374,9 +494,9 @@ impl VaryingContext<'_> {
                 }
 
                 let needs_interpolation = match self.stage {
-                    crate::ShaderStage::Vertex => self.output,
+                    crate::ShaderStage::Vertex | crate::ShaderStage::Mesh => self.output,
                     crate::ShaderStage::Fragment => !self.output,
-                    crate::ShaderStage::Compute => false,
+                    crate::ShaderStage::Compute | crate::ShaderStage::Task => false,
                 };
 
                 // It doesn't make sense to specify a sampling when `interpolation` is `Flat`, but
@@ -553,6 +673,14 @@ impl super::Validator {
             }
             crate::AddressSpace::Private => (TypeFlags::CONSTRUCTIBLE, false),
             crate::AddressSpace::WorkGroup => (TypeFlags::DATA | TypeFlags::SIZED, false),
+            crate::AddressSpace::TaskPayload => {
+                if !self.capabilities.contains(Capabilities::MESH_SHADER) {
+                    return Err(GlobalVariableError::UnsupportedCapability(
+                        Capabilities::MESH_SHADER,
+                    ));
+                }
+                (TypeFlags::DATA | TypeFlags::SIZED, false)
+            }
             crate::AddressSpace::PushConstant => {
                 if !self.capabilities.contains(Capabilities::PUSH_CONSTANT) {
                     return Err(GlobalVariableError::UnsupportedCapability(
@@ -614,10 +742,7 @@ impl super::Validator {
         if ep.early_depth_test.is_some() {
             let required = Capabilities::EARLY_DEPTH_TEST;
             if !self.capabilities.contains(required) {
-                return Err(
-                    EntryPointError::Result(VaryingError::UnsupportedCapability(required))
-                        .with_span(),
-                );
+                return Err(EntryPointError::UnsupportedCapability(required).with_span());
             }
 
             if ep.stage != crate::ShaderStage::Fragment {
@@ -625,7 +750,20 @@ impl super::Validator {
             }
         }
 
-        if ep.stage == crate::ShaderStage::Compute {
+        if matches!(
+            ep.stage,
+            crate::ShaderStage::Task | crate::ShaderStage::Mesh
+        ) {
+            let required = Capabilities::MESH_SHADER;
+            if !self.capabilities.contains(required) {
+                return Err(EntryPointError::UnsupportedCapability(required).with_span());
+            }
+        }
+
+        if matches!(
+            ep.stage,
+            crate::ShaderStage::Compute | crate::ShaderStage::Task | crate::ShaderStage::Mesh
+        ) {
             if ep
                 .workgroup_size
                 .iter()
@@ -648,6 +786,8 @@ impl super::Validator {
                 crate::ShaderStage::Vertex => ShaderStages::VERTEX,
                 crate::ShaderStage::Fragment => ShaderStages::FRAGMENT,
                 crate::ShaderStage::Compute => ShaderStages::COMPUTE,
+                crate::ShaderStage::Task => ShaderStages::TASK,
+                crate::ShaderStage::Mesh => ShaderStages::MESH,
             };
 
             if !info.available_stages.contains(stage_bit) {
@@ -668,6 +808,7 @@ impl super::Validator {
                 location_mask: &mut self.location_mask,
                 built_ins: &mut argument_built_ins,
                 capabilities: self.capabilities,
+                max_clip_distances: self.max_clip_distances,
                 flags: self.flags,
             };
             ctx.validate(ep, fa.ty, fa.binding.as_ref())
@@ -686,11 +827,41 @@ impl super::Validator {
                 location_mask: &mut self.location_mask,
                 built_ins: &mut result_built_ins,
                 capabilities: self.capabilities,
+                max_clip_distances: self.max_clip_distances,
                 flags: self.flags,
             };
             ctx.validate(ep, fr.ty, fr.binding.as_ref())
                 .map_err_inner(|e| EntryPointError::Result(e).with_span())?;
             if ctx.second_blend_source {
+                // Both sources are at location 0, so they must be members of
+                // the result struct. Blending combines them component-wise,
+                // which requires them to have the same type.
+                let crate::TypeInner::Struct { ref members, .. } = module.types[fr.ty].inner else {
+                    return Err(EntryPointError::MissingFirstBlendSource.with_span());
+                };
+                let source_ty = |second: bool| {
+                    members.iter().find_map(|member| match member.binding {
+                        Some(crate::Binding::Location {
+                            location: 0,
+                            second_blend_source,
+                            ..
+                        }) if second_blend_source == second => Some(member.ty),
+                        _ => None,
+                    })
+                };
+                match (source_ty(false), source_ty(true)) {
+                    (Some(first), Some(second)) if first != second => {
+                        return Err(
+                            EntryPointError::DualSourceBlendingTypeMismatch(first, second)
+                                .with_span_handle(second, &module.types),
+                        );
+                    }
+                    (None, _) => {
+                        return Err(EntryPointError::MissingFirstBlendSource.with_span());
+                    }
+                    _ => {}
+                }
+
                 // Only the first location may be used when dual source blending
                 if ctx.location_mask.len() == 1 && ctx.location_mask.contains(0) {
                     info.dual_source_blending = true;
@@ -707,8 +878,42 @@ impl super::Validator {
             {
                 return Err(EntryPointError::MissingVertexOutputPosition.with_span());
             }
+            if ep.stage == crate::ShaderStage::Task
+                && !result_built_ins.contains(&crate::BuiltIn::MeshTaskSize)
+            {
+                return Err(EntryPointError::MissingTaskOutputMeshTaskSize.with_span());
+            }
+            if ep.stage == crate::ShaderStage::Mesh {
+                return Err(EntryPointError::UnexpectedMeshShaderResult.with_span());
+            }
         } else if ep.stage == crate::ShaderStage::Vertex {
             return Err(EntryPointError::MissingVertexOutputPosition.with_span());
+        } else if ep.stage == crate::ShaderStage::Task {
+            return Err(EntryPointError::MissingTaskOutputMeshTaskSize.with_span());
+        }
+
+        match (ep.stage, ep.mesh_info.as_ref()) {
+            (crate::ShaderStage::Mesh, Some(mesh_info)) => {
+                self.validate_mesh_outputs(ep, mesh_info, &info, module)?;
+            }
+            (crate::ShaderStage::Mesh, None) => {
+                return Err(EntryPointError::MissingMeshStageInfo.with_span());
+            }
+            (_, Some(_)) => return Err(EntryPointError::UnexpectedMeshStageInfo.with_span()),
+            (_, None) => {}
+        }
+
+        if let Some(payload) = ep.task_payload {
+            if !matches!(
+                ep.stage,
+                crate::ShaderStage::Task | crate::ShaderStage::Mesh
+            ) {
+                return Err(EntryPointError::UnexpectedTaskPayload.with_span());
+            }
+            if module.global_variables[payload].space != crate::AddressSpace::TaskPayload {
+                return Err(EntryPointError::InvalidTaskPayload(payload)
+                    .with_span_handle(payload, &module.global_variables));
+            }
         }
 
         {
@@ -753,6 +958,17 @@ impl super::Validator {
                 },
                 crate::AddressSpace::Private | crate::AddressSpace::WorkGroup => GlobalUse::all(),
                 crate::AddressSpace::PushConstant => GlobalUse::READ,
+                crate::AddressSpace::TaskPayload => {
+                    if ep.task_payload != Some(var_handle) {
+                        return Err(EntryPointError::UndeclaredTaskPayload(var_handle)
+                            .with_span_handle(var_handle, &module.global_variables));
+                    }
+                    // Task shaders produce the payload; mesh shaders only read it.
+                    match ep.stage {
+                        crate::ShaderStage::Task => GlobalUse::all(),
+                        _ => GlobalUse::READ | GlobalUse::QUERY,
+                    }
+                }
             };
             if !allowed_usage.contains(usage) {
                 log::warn!("\tUsage error for: {:?}", var);
@@ -777,4 +993,123 @@ impl super::Validator {
 
         Ok(info)
     }
+
+    /// Validate the vertex and primitive outputs of a mesh shader entry point.
+    fn validate_mesh_outputs(
+        &mut self,
+        ep: &crate::EntryPoint,
+        mesh_info: &crate::MeshStageInfo,
+        info: &FunctionInfo,
+        module: &crate::Module,
+    ) -> Result<(), WithSpan<EntryPointError>> {
+        use crate::BuiltIn as Bi;
+
+        if mesh_info.max_vertices == 0 || mesh_info.max_primitives == 0 {
+            return Err(EntryPointError::InvalidMeshOutputCount.with_span());
+        }
+
+        let index_built_in = match mesh_info.topology {
+            crate::MeshOutputTopology::Points => Bi::PointIndex,
+            crate::MeshOutputTopology::Lines => Bi::LineIndices,
+            crate::MeshOutputTopology::Triangles => Bi::TriangleIndices,
+        };
+
+        // Vertex and primitive outputs share a single location space.
+        self.location_mask.clear();
+        for (output, ty, per_primitive) in [
+            ("vertex", mesh_info.vertex_output_type, false),
+            ("primitive", mesh_info.primitive_output_type, true),
+        ] {
+            let crate::TypeInner::Struct { ref members, .. } = module.types[ty].inner else {
+                return Err(EntryPointError::InvalidMeshOutputType { output, ty }
+                    .with_span_handle(ty, &module.types));
+            };
+
+            let mut built_ins = crate::FastHashSet::default();
+            let mut ctx = VaryingContext {
+                stage: ep.stage,
+                output: true,
+                second_blend_source: false,
+                types: &module.types,
+                type_info: &self.types,
+                location_mask: &mut self.location_mask,
+                built_ins: &mut built_ins,
+                capabilities: self.capabilities,
+                max_clip_distances: self.max_clip_distances,
+                flags: self.flags,
+            };
+            ctx.validate(ep, ty, None)
+                .map_err_inner(|e| EntryPointError::Result(e).with_span())?;
+
+            for member in members {
+                let error = match member.binding {
+                    Some(crate::Binding::BuiltIn(built_in)) => {
+                        let is_primitive_built_in = matches!(
+                            built_in,
+                            Bi::PointIndex
+                                | Bi::LineIndices
+                                | Bi::TriangleIndices
+                                | Bi::CullPrimitive
+                        );
+                        let valid = if per_primitive {
+                            built_in == index_built_in || built_in == Bi::CullPrimitive
+                        } else {
+                            !is_primitive_built_in
+                        };
+                        (!valid).then_some(VaryingError::InvalidMeshOutputBuiltIn(built_in, output))
+                    }
+                    Some(crate::Binding::Location {
+                        location,
+                        per_primitive: member_per_primitive,
+                        ..
+                    }) => (member_per_primitive != per_primitive).then_some(
+                        VaryingError::InvalidMeshOutputLocation {
+                            location,
+                            output,
+                            per_primitive,
+                        },
+                    ),
+                    None => None,
+                };
+                if let Some(error) = error {
+                    return Err(EntryPointError::Result(error).with_span_handle(ty, &module.types));
+                }
+            }
+
+            let required = if per_primitive {
+                index_built_in
+            } else {
+                Bi::Position { invariant: false }
+            };
+            if !built_ins.contains(&required) {
+                let error = if per_primitive {
+                    EntryPointError::MissingMeshPrimitiveIndices(index_built_in)
+                } else {
+                    EntryPointError::MissingVertexOutputPosition
+                };
+                return Err(error.with_span_handle(ty, &module.types));
+            }
+        }
+
+        let used_types = [
+            (
+                info.mesh_shader_info.vertex_type,
+                mesh_info.vertex_output_type,
+            ),
+            (
+                info.mesh_shader_info.primitive_type,
+                mesh_info.primitive_output_type,
+            ),
+        ];
+        for (used, declared) in used_types {
+            if let Some(used) = used {
+                if used != declared {
+                    return Err(EntryPointError::MeshOutputTypeMismatch(used)
+                        .with_span_handle(used, &module.types));
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
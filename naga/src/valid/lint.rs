@@ -0,0 +1,263 @@
+//! Lints controlled by [`DiagnosticFilter`]s.
+//!
+//! These check for code that is valid, but probably not what the author
+//! intended. They are only run for the rules that aren't [`Severity::Off`] in a
+//! function, which is the default.
+//!
+//! [`DiagnosticFilter`]: crate::diagnostic_filter::DiagnosticFilter
+
+use super::{analyzer::FunctionInfo, FunctionError};
+use crate::diagnostic_filter::{Severity, StandardFilterableTriggeringRule as Rule};
+use crate::span::{AddSpan as _, WithSpan};
+use crate::{FastHashMap, Handle, Span};
+
+impl FunctionInfo {
+    /// Check `fun` for the lints enabled in it.
+    ///
+    /// This must be called after `fun` has been validated.
+    pub(super) fn lint(
+        &mut self,
+        fun: &crate::Function,
+        module: &crate::Module,
+        entry_point: bool,
+    ) -> Result<(), WithSpan<FunctionError>> {
+        let filters = &module.diagnostic_filters;
+        let mut findings = Vec::new();
+
+        if self.severity(Rule::UnusedVariable, filters) != Severity::Off {
+            self.unused_variables(fun, entry_point, &mut findings);
+        }
+        if self.severity(Rule::ShadowedDeclaration, filters) != Severity::Off {
+            shadowed_declarations(fun, module, &mut findings);
+        }
+
+        for (rule, error) in findings {
+            self.report(rule, error, filters)?;
+        }
+        Ok(())
+    }
+
+    /// Find local variables that are only ever written to, and arguments that
+    /// are never used at all.
+    ///
+    /// Arguments of entry points are part of the pipeline interface, so they
+    /// are not reported.
+    fn unused_variables(
+        &self,
+        fun: &crate::Function,
+        entry_point: bool,
+        findings: &mut Vec<(Rule, WithSpan<FunctionError>)>,
+    ) {
+        use crate::Expression as E;
+
+        // For each expression that is a pointer into a local variable, the
+        // variable it points into.
+        let mut pointers = vec![None; fun.expressions.len()];
+        // For each of those expressions, how many of its uses only write
+        // through it, or only produce another pointer into the same variable.
+        let mut writes = vec![0; fun.expressions.len()];
+        let mut arguments_used = vec![false; fun.arguments.len()];
+
+        for (handle, expr) in fun.expressions.iter() {
+            match *expr {
+                E::LocalVariable(var) => pointers[handle.index()] = Some(var),
+                E::Access { base, .. } | E::AccessIndex { base, .. } => {
+                    if let Some(var) = pointers[base.index()] {
+                        pointers[handle.index()] = Some(var);
+                        writes[base.index()] += 1;
+                    }
+                }
+                E::FunctionArgument(index) => {
+                    if self[handle].ref_count > 0 {
+                        arguments_used[index as usize] = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+        count_stores(&fun.body, &pointers, &mut writes);
+
+        let mut variables_used = vec![false; fun.local_variables.len()];
+        for (handle, _) in fun.expressions.iter() {
+            if let Some(var) = pointers[handle.index()] {
+                if self[handle].ref_count > writes[handle.index()] {
+                    variables_used[var.index()] = true;
+                }
+            }
+        }
+
+        for (handle, var) in fun.local_variables.iter() {
+            let Some(ref name) = var.name else {
+                continue;
+            };
+            if !variables_used[handle.index()] && !name.starts_with('_') {
+                findings.push((
+                    Rule::UnusedVariable,
+                    FunctionError::UnusedLocalVariable {
+                        handle,
+                        name: name.clone(),
+                    }
+                    .with_span_handle(handle, &fun.local_variables),
+                ));
+            }
+        }
+
+        if entry_point {
+            return;
+        }
+        for (index, argument) in fun.arguments.iter().enumerate() {
+            let Some(ref name) = argument.name else {
+                continue;
+            };
+            if !arguments_used[index] && !name.starts_with('_') {
+                let error = FunctionError::UnusedArgument {
+                    index,
+                    name: name.clone(),
+                };
+                findings.push((
+                    Rule::UnusedVariable,
+                    match argument_expression(fun, index) {
+                        Some(expr) => error.with_span_handle(expr, &fun.expressions),
+                        None => error.with_span(),
+                    },
+                ));
+            }
+        }
+    }
+}
+
+/// Count the [`Store`] statements in `block` that write through each of the
+/// expressions in `pointers`.
+///
+/// [`Store`]: crate::Statement::Store
+fn count_stores(
+    block: &crate::Block,
+    pointers: &[Option<Handle<crate::LocalVariable>>],
+    writes: &mut [usize],
+) {
+    use crate::Statement as S;
+
+    for statement in block {
+        match *statement {
+            S::Store { pointer, .. } => {
+                if pointers[pointer.index()].is_some() {
+                    writes[pointer.index()] += 1;
+                }
+            }
+            S::Block(ref block) => count_stores(block, pointers, writes),
+            S::If {
+                ref accept,
+                ref reject,
+                ..
+            } => {
+                count_stores(accept, pointers, writes);
+                count_stores(reject, pointers, writes);
+            }
+            S::Switch { ref cases, .. } => {
+                for case in cases {
+                    count_stores(&case.body, pointers, writes);
+                }
+            }
+            S::Loop {
+                ref body,
+                ref continuing,
+                ..
+            } => {
+                count_stores(body, pointers, writes);
+                count_stores(continuing, pointers, writes);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Find local declarations that have the same name as a module-scope
+/// declaration or, for locals, as one of the function's arguments.
+fn shadowed_declarations(
+    fun: &crate::Function,
+    module: &crate::Module,
+    findings: &mut Vec<(Rule, WithSpan<FunctionError>)>,
+) {
+    let module_scope = module
+        .types
+        .iter()
+        .filter_map(|(handle, ty)| Some((ty.name.as_deref()?, module.types.get_span(handle))))
+        .chain(module.constants.iter().filter_map(|(handle, constant)| {
+            Some((constant.name.as_deref()?, module.constants.get_span(handle)))
+        }))
+        .chain(module.overrides.iter().filter_map(|(handle, override_)| {
+            Some((
+                override_.name.as_deref()?,
+                module.overrides.get_span(handle),
+            ))
+        }))
+        .chain(module.global_variables.iter().filter_map(|(handle, var)| {
+            Some((
+                var.name.as_deref()?,
+                module.global_variables.get_span(handle),
+            ))
+        }))
+        .chain(module.functions.iter().filter_map(|(handle, function)| {
+            Some((function.name.as_deref()?, module.functions.get_span(handle)))
+        }))
+        .chain(
+            module
+                .entry_points
+                .iter()
+                .map(|ep| (ep.name.as_str(), Span::UNDEFINED)),
+        );
+    let mut outer = FastHashMap::default();
+    for (name, span) in module_scope {
+        outer.entry(name).or_insert(span);
+    }
+
+    let mut shadowed = |name: &str, error_span: Span, outer: &FastHashMap<&str, Span>| {
+        if let Some(&span) = outer.get(name) {
+            findings.push((
+                Rule::ShadowedDeclaration,
+                FunctionError::ShadowedDeclaration {
+                    name: name.to_string(),
+                }
+                .with_span_static(error_span, "declared here")
+                .with_span(span, "shadowed declaration"),
+            ));
+        }
+    };
+
+    let mut arguments = FastHashMap::default();
+    for (index, argument) in fun.arguments.iter().enumerate() {
+        let Some(ref name) = argument.name else {
+            continue;
+        };
+        let span = argument_expression(fun, index)
+            .map_or(Span::UNDEFINED, |expr| fun.expressions.get_span(expr));
+        shadowed(name, span, &outer);
+        arguments.insert(name.as_str(), span);
+    }
+    outer.extend(arguments);
+
+    for (handle, var) in fun.local_variables.iter() {
+        if let Some(ref name) = var.name {
+            shadowed(name, fun.local_variables.get_span(handle), &outer);
+        }
+    }
+    for (&handle, name) in fun.named_expressions.iter() {
+        // Arguments have been checked above.
+        if let crate::Expression::FunctionArgument(_) = fun.expressions[handle] {
+            continue;
+        }
+        shadowed(name, fun.expressions.get_span(handle), &outer);
+    }
+}
+
+/// Return the [`FunctionArgument`] expression for the argument at `index`.
+///
+/// [`FunctionArgument`]: crate::Expression::FunctionArgument
+fn argument_expression(fun: &crate::Function, index: usize) -> Option<Handle<crate::Expression>> {
+    fun.expressions
+        .iter()
+        .find_map(|(handle, expr)| match *expr {
+            crate::Expression::FunctionArgument(i) if i as usize == index => Some(handle),
+            _ => None,
+        })
+}
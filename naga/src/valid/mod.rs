@@ -8,10 +8,12 @@ mod expression;
 mod function;
 mod handles;
 mod interface;
+mod lint;
 mod r#type;
 
 use crate::{
     arena::{Handle, HandleSet},
+    diagnostic_filter::{Severity, StandardFilterableTriggeringRule},
     proc::{ExpressionKindTracker, LayoutError, Layouter, TypeResolution},
     FastHashSet,
 };
@@ -22,11 +24,14 @@ use std::ops;
 // merge the corresponding matches over expressions and statements.
 
 use crate::span::{AddSpan as _, WithSpan};
-pub use analyzer::{ExpressionInfo, FunctionInfo, GlobalUse, Uniformity, UniformityRequirements};
+pub use analyzer::{
+    ExpressionInfo, FunctionInfo, FunctionMeshShaderInfo, GlobalUse, Uniformity,
+    UniformityRequirements,
+};
 pub use compose::ComposeError;
 pub use expression::{check_literal_value, LiteralError};
 pub use expression::{ConstExpressionError, ExpressionError};
-pub use function::{CallError, FunctionError, LocalVariableError};
+pub use function::{CallError, FunctionError, LocalVariableError, SubgroupError};
 pub use interface::{EntryPointError, GlobalVariableError, VaryingError};
 pub use r#type::{Disalignment, TypeError, TypeFlags, WidthError};
 
@@ -143,6 +148,30 @@ bitflags::bitflags! {
         const SHADER_INT64_ATOMIC_MIN_MAX = 0x80000;
         /// Support for all atomic operations on 64-bit integers.
         const SHADER_INT64_ATOMIC_ALL_OPS = 0x100000;
+        /// Float values with width = 2.
+        const SHADER_FLOAT16 = 0x200000;
+        /// Support for task and mesh shader stages.
+        const MESH_SHADER = 0x400000;
+        /// Support for [`GatherMode::QuadBroadcast`] and [`GatherMode::QuadSwap`]
+        /// in fragment and compute stages.
+        ///
+        /// [`GatherMode::QuadBroadcast`]: crate::GatherMode::QuadBroadcast
+        /// [`GatherMode::QuadSwap`]: crate::GatherMode::QuadSwap
+        const SUBGROUP_QUAD = 0x800000;
+        /// Support for [`CollectiveOperation::ClusteredReduce`].
+        ///
+        /// [`CollectiveOperation::ClusteredReduce`]: crate::CollectiveOperation::ClusteredReduce
+        const SUBGROUP_CLUSTERED = 0x1000000;
+        /// Support for function arguments that are pointers into the
+        /// [`Uniform`], [`Storage`] or [`WorkGroup`] address spaces.
+        ///
+        /// Backends can't write such modules directly. Pass them through
+        /// `back::pointer_arguments::specialize_pointer_arguments` first.
+        ///
+        /// [`Uniform`]: crate::AddressSpace::Uniform
+        /// [`Storage`]: crate::AddressSpace::Storage
+        /// [`WorkGroup`]: crate::AddressSpace::WorkGroup
+        const UNRESTRICTED_POINTER_PARAMETERS = 0x2000000;
     }
 }
 
@@ -157,7 +186,7 @@ bitflags::bitflags! {
     #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
     #[cfg_attr(feature = "deserialize", derive(serde::Deserialize))]
     #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
-    pub struct SubgroupOperationSet: u8 {
+    pub struct SubgroupOperationSet: u16 {
         /// Elect, Barrier
         const BASIC = 1 << 0;
         /// Any, All
@@ -170,13 +199,12 @@ bitflags::bitflags! {
         const SHUFFLE = 1 << 4;
         /// shuffle up, down
         const SHUFFLE_RELATIVE = 1 << 5;
-        // We don't support these operations yet
-        // /// Clustered
-        // const CLUSTERED = 1 << 6;
-        // /// Quad supported
-        // const QUAD_FRAGMENT_COMPUTE = 1 << 7;
-        // /// Quad supported in all stages
-        // const QUAD_ALL_STAGES = 1 << 8;
+        /// clustered reductions
+        const CLUSTERED = 1 << 6;
+        /// quad broadcast, quad swap in fragment and compute stages
+        const QUAD_FRAGMENT_COMPUTE = 1 << 7;
+        /// quad broadcast, quad swap in all stages
+        const QUAD_ALL_STAGES = 1 << 8;
     }
 }
 
@@ -199,6 +227,17 @@ impl super::GatherMode {
             Self::BroadcastFirst | Self::Broadcast(_) => S::BALLOT,
             Self::Shuffle(_) | Self::ShuffleXor(_) => S::SHUFFLE,
             Self::ShuffleUp(_) | Self::ShuffleDown(_) => S::SHUFFLE_RELATIVE,
+            Self::QuadBroadcast(_) | Self::QuadSwap(_) => S::QUAD_FRAGMENT_COMPUTE,
+        }
+    }
+}
+
+impl super::CollectiveOperation {
+    const fn required_operations(&self) -> SubgroupOperationSet {
+        use SubgroupOperationSet as S;
+        match *self {
+            Self::Reduce | Self::InclusiveScan | Self::ExclusiveScan => S::empty(),
+            Self::ClusteredReduce(_) => S::CLUSTERED,
         }
     }
 }
@@ -212,6 +251,10 @@ bitflags::bitflags! {
         const VERTEX = 0x1;
         const FRAGMENT = 0x2;
         const COMPUTE = 0x4;
+        const TASK = 0x8;
+        const MESH = 0x10;
+        /// Stages that run in workgroups: compute, task and mesh.
+        const COMPUTE_LIKE = Self::COMPUTE.bits() | Self::TASK.bits() | Self::MESH.bits();
     }
 }
 
@@ -223,6 +266,93 @@ pub struct ModuleInfo {
     functions: Vec<FunctionInfo>,
     entry_points: Vec<FunctionInfo>,
     const_expression_types: Box<[TypeResolution]>,
+    /// Diagnostics reported at [`Severity::Warning`] or [`Severity::Info`].
+    #[cfg_attr(any(feature = "serialize", feature = "deserialize"), serde(skip))]
+    warnings: Vec<ValidationWarning>,
+}
+
+/// A diagnostic that was reported by the validator, but did not make the
+/// module invalid.
+///
+/// Whether a diagnostic is reported, and at which severity, is controlled by
+/// the [`DiagnosticFilter`]s in effect where it was triggered.
+///
+/// [`DiagnosticFilter`]: crate::diagnostic_filter::DiagnosticFilter
+#[derive(Clone, Debug)]
+pub struct ValidationWarning {
+    /// The rule that triggered this diagnostic.
+    pub rule: StandardFilterableTriggeringRule,
+    /// Either [`Severity::Warning`] or [`Severity::Info`].
+    pub severity: Severity,
+    /// What was found, and where.
+    pub error: WithSpan<ValidationError>,
+}
+
+impl ModuleInfo {
+    /// Return the warnings reported while validating the module, in the order
+    /// they were found.
+    pub fn warnings(&self) -> &[ValidationWarning] {
+        &self.warnings
+    }
+}
+
+impl ValidationWarning {
+    /// Returns a [`SourceLocation`] for the first span of the warning.
+    ///
+    /// [`SourceLocation`]: crate::SourceLocation
+    pub fn location(&self, source: &str) -> Option<crate::SourceLocation> {
+        self.error.location(source)
+    }
+
+    pub(crate) fn diagnostic(&self) -> codespan_reporting::diagnostic::Diagnostic<()> {
+        use codespan_reporting::diagnostic::Severity as Cs;
+        let severity = match self.severity {
+            Severity::Info => Cs::Note,
+            // Only `Info` and `Warning` diagnostics become `ValidationWarning`s.
+            Severity::Warning | Severity::Error | Severity::Off => Cs::Warning,
+        };
+        let mut diagnostic = self.error.diagnostic_with_severity(severity);
+        // The outer errors only say that the function is invalid, which isn't
+        // true of a warning, so lead with the innermost one instead.
+        if let Some(message) = diagnostic.notes.pop() {
+            diagnostic.message = message;
+            diagnostic.notes.clear();
+            match *self.error.as_inner() {
+                ValidationError::Function { ref name, .. } => {
+                    diagnostic.notes.push(format!("in function '{name}'"));
+                }
+                ValidationError::EntryPoint { ref name, .. } => {
+                    diagnostic.notes.push(format!("in entry point '{name}'"));
+                }
+                _ => {}
+            }
+        }
+        diagnostic
+    }
+
+    /// Emits a summary of the warning to standard error stream.
+    pub fn emit_to_stderr_with_path(&self, source: &str, path: &str) {
+        use codespan_reporting::{files, term};
+        use term::termcolor::{ColorChoice, StandardStream};
+
+        let files = files::SimpleFile::new(path, source);
+        let config = term::Config::default();
+        let writer = StandardStream::stderr(ColorChoice::Auto);
+        term::emit(&mut writer.lock(), &config, &files, &self.diagnostic())
+            .expect("cannot write warning");
+    }
+
+    /// Emits a summary of the warning to a string.
+    pub fn emit_to_string_with_path(&self, source: &str, path: &str) -> String {
+        use codespan_reporting::{files, term};
+        use term::termcolor::NoColor;
+
+        let files = files::SimpleFile::new(path, source);
+        let config = term::Config::default();
+        let mut writer = NoColor::new(Vec::new());
+        term::emit(&mut writer, &config, &files, &self.diagnostic()).expect("cannot write warning");
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
 }
 
 impl ops::Index<Handle<crate::Type>> for ModuleInfo {
@@ -252,6 +382,7 @@ pub struct Validator {
     capabilities: Capabilities,
     subgroup_stages: ShaderStages,
     subgroup_operations: SubgroupOperationSet,
+    max_clip_distances: u32,
     types: Vec<r#type::TypeInfo>,
     layouter: Layouter,
     location_mask: BitSet,
@@ -422,7 +553,15 @@ impl Validator {
     pub fn new(flags: ValidationFlags, capabilities: Capabilities) -> Self {
         let subgroup_operations = if capabilities.contains(Capabilities::SUBGROUP) {
             use SubgroupOperationSet as S;
-            S::BASIC | S::VOTE | S::ARITHMETIC | S::BALLOT | S::SHUFFLE | S::SHUFFLE_RELATIVE
+            let mut operations =
+                S::BASIC | S::VOTE | S::ARITHMETIC | S::BALLOT | S::SHUFFLE | S::SHUFFLE_RELATIVE;
+            if capabilities.contains(Capabilities::SUBGROUP_QUAD) {
+                operations |= S::QUAD_FRAGMENT_COMPUTE;
+            }
+            if capabilities.contains(Capabilities::SUBGROUP_CLUSTERED) {
+                operations |= S::CLUSTERED;
+            }
+            operations
         } else {
             SubgroupOperationSet::empty()
         };
@@ -442,6 +581,7 @@ impl Validator {
             capabilities,
             subgroup_stages,
             subgroup_operations,
+            max_clip_distances: 8,
             types: Vec::new(),
             layouter: Layouter::default(),
             location_mask: BitSet::new(),
@@ -465,6 +605,15 @@ impl Validator {
         self
     }
 
+    /// Set the maximum number of elements in a [`BuiltIn::ClipDistance`]
+    /// array, which depends on the device. Defaults to 8.
+    ///
+    /// [`BuiltIn::ClipDistance`]: crate::BuiltIn::ClipDistance
+    pub fn max_clip_distances(&mut self, max: u32) -> &mut Self {
+        self.max_clip_distances = max;
+        self
+    }
+
     /// Reset the validator internals
     pub fn reset(&mut self) {
         self.types.clear();
@@ -537,6 +686,7 @@ impl Validator {
                 crate::Scalar::BOOL
                 | crate::Scalar::I32
                 | crate::Scalar::U32
+                | crate::Scalar::F16
                 | crate::Scalar::F32
                 | crate::Scalar::F64,
             ) => {}
@@ -599,6 +749,7 @@ impl Validator {
             entry_points: Vec::with_capacity(module.entry_points.len()),
             const_expression_types: vec![placeholder; module.global_expressions.len()]
                 .into_boxed_slice(),
+            warnings: Vec::new(),
         };
 
         for (handle, ty) in module.types.iter() {
@@ -684,7 +835,25 @@ impl Validator {
 
         for (handle, fun) in module.functions.iter() {
             match self.validate_function(fun, module, &mod_info, false, &global_expr_kind) {
-                Ok(info) => mod_info.functions.push(info),
+                Ok(mut info) => {
+                    for warning in info.warnings.drain(..) {
+                        mod_info.warnings.push(ValidationWarning {
+                            rule: warning.rule,
+                            severity: warning.severity,
+                            // Unlike errors, leave out the function's span, so
+                            // that the warning's location is what triggered it.
+                            error: warning.error.and_then(|source| {
+                                ValidationError::Function {
+                                    handle,
+                                    name: fun.name.clone().unwrap_or_default(),
+                                    source,
+                                }
+                                .with_span()
+                            }),
+                        });
+                    }
+                    mod_info.functions.push(info)
+                }
                 Err(error) => {
                     return Err(error.and_then(|source| {
                         ValidationError::Function {
@@ -710,7 +879,23 @@ impl Validator {
             }
 
             match self.validate_entry_point(ep, module, &mod_info, &global_expr_kind) {
-                Ok(info) => mod_info.entry_points.push(info),
+                Ok(mut info) => {
+                    for warning in info.warnings.drain(..) {
+                        mod_info.warnings.push(ValidationWarning {
+                            rule: warning.rule,
+                            severity: warning.severity,
+                            error: warning.error.and_then(|source| {
+                                ValidationError::EntryPoint {
+                                    stage: ep.stage,
+                                    name: ep.name.clone(),
+                                    source: EntryPointError::Function(source),
+                                }
+                                .with_span()
+                            }),
+                        });
+                    }
+                    mod_info.entry_points.push(info)
+                }
                 Err(error) => {
                     return Err(error.and_then(|source| {
                         ValidationError::EntryPoint {
@@ -195,14 +195,19 @@ fn check_member_layout(
 /// `TypeFlags::empty()`.
 ///
 /// Pointers passed as arguments to user-defined functions must be in the
-/// `Function` or `Private` address space.
+/// `Function`, `Private`, `Uniform`, `Storage` or `WorkGroup` address space.
+/// The latter three are permitted by WGSL's `unrestricted_pointer_parameters`
+/// language extension, and the validator only accepts them as arguments with
+/// [`Capabilities::UNRESTRICTED_POINTER_PARAMETERS`].
+///
+/// [`Capabilities::UNRESTRICTED_POINTER_PARAMETERS`]: super::Capabilities::UNRESTRICTED_POINTER_PARAMETERS
 const fn ptr_space_argument_flag(space: crate::AddressSpace) -> TypeFlags {
     use crate::AddressSpace as As;
     match space {
-        As::Function | As::Private => TypeFlags::ARGUMENT,
-        As::Uniform | As::Storage { .. } | As::Handle | As::PushConstant | As::WorkGroup => {
-            TypeFlags::empty()
+        As::Function | As::Private | As::Uniform | As::Storage { .. } | As::WorkGroup => {
+            TypeFlags::ARGUMENT
         }
+        As::Handle | As::PushConstant | As::TaskPayload => TypeFlags::empty(),
     }
 }
 
@@ -252,6 +257,14 @@ impl super::Validator {
                         });
                     }
                     true
+                } else if scalar.width == 2 {
+                    if !self.capabilities.contains(Capabilities::SHADER_FLOAT16) {
+                        return Err(WidthError::MissingCapability {
+                            name: "f16",
+                            flag: "SHADER_FLOAT16",
+                        });
+                    }
+                    true
                 } else {
                     scalar.width == 4
                 }
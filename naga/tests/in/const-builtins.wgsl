@@ -0,0 +1,101 @@
+// Constant evaluation of built-in functions.
+//
+// Each `const_assert` fails to compile if the evaluator gets the wrong answer.
+
+// select
+const_assert select(1, 2, true) == 2;
+const_assert select(1, 2, false) == 1;
+const_assert all(select(vec2(1, 2), vec2(3, 4), vec2(true, false)) == vec2(3, 2));
+
+// relational
+const_assert all(vec3(true, true, true));
+const_assert !all(vec2(true, false));
+const_assert any(vec2(false, true));
+const_assert !any(vec4<bool>());
+
+// bitcast
+const_assert bitcast<u32>(1.0f) == 0x3f800000u;
+const_assert bitcast<f32>(0x40000000u) == 2.0;
+const_assert bitcast<i32>(0xffffffffu) == -1;
+const_assert all(bitcast<vec2<u32>>(vec2(-1i, 1i)) == vec2(0xffffffffu, 1u));
+
+// trigonometry
+const_assert atan2(0.0, 1.0) == 0.0;
+const_assert all(atan2(vec2(1.0, 0.0), vec2(1.0, -1.0)) == vec2(0.7853981633974483, 3.141592653589793));
+
+// decomposition
+const_assert modf(1.5).fract == 0.5;
+const_assert modf(-2.25).whole == -2.0;
+const_assert all(modf(vec2(1.25, -3.5)).fract == vec2(0.25, -0.5));
+const_assert frexp(1.5).fract == 0.75;
+const_assert frexp(1.5).exp == 1;
+const_assert all(frexp(vec2(8.0, -0.125)).exp == vec2(4, -2));
+const_assert ldexp(0.75, 3) == 6.0;
+const_assert all(ldexp(vec2(1.0, 2.0), vec2(-1, 4)) == vec2(0.5, 32.0));
+
+const_assert dot(vec3(1, 2, 3), vec3(4, 5, 6)) == 32;
+const_assert dot(vec2(0.5, 2.0), vec2(4.0, 0.25)) == 2.5;
+const_assert all(cross(vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0)) == vec3(0.0, 0.0, 1.0));
+const_assert distance(vec2(1.0, 1.0), vec2(4.0, 5.0)) == 5.0;
+const_assert length(vec3(2.0, 3.0, 6.0)) == 7.0;
+const_assert length(-2.0) == 2.0;
+const_assert all(normalize(vec2(3.0, 4.0)) == vec2(0.6, 0.8));
+const_assert all(faceForward(vec2(1.0, 2.0), vec2(1.0, 0.0), vec2(1.0, 0.0)) == vec2(-1.0, -2.0));
+const_assert all(reflect(vec2(1.0, -1.0), vec2(0.0, 1.0)) == vec2(1.0, 1.0));
+const_assert all(refract(vec2(0.0, -1.0), vec2(0.0, 1.0), 1.0) == vec2(0.0, -1.0));
+const_assert all(refract(vec2(0.8, -0.6), vec2(0.0, 1.0), 2.0) == vec2(0.0));
+
+// computational
+const_assert mix(2.0, 4.0, 0.25) == 2.5;
+const_assert all(mix(vec2(0.0, 10.0), vec2(1.0, 20.0), 0.5) == vec2(0.5, 15.0));
+const_assert smoothstep(0.0, 2.0, 1.0) == 0.5;
+const_assert all(smoothstep(vec2(0.0), vec2(1.0), vec2(-1.0, 2.0)) == vec2(0.0, 1.0));
+const m = mat2x3(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+const_assert all(transpose(m)[0] == vec2(1.0, 4.0));
+const_assert all(transpose(m)[2] == vec2(3.0, 6.0));
+const_assert determinant(mat2x2(1.0, 2.0, 3.0, 4.0)) == -2.0;
+const_assert determinant(mat3x3(2.0, 0.0, 0.0, 0.0, 3.0, 0.0, 0.0, 0.0, 4.0)) == 24.0;
+const_assert determinant(mat4x4(1.0, 0.0, 2.0, -1.0, 3.0, 0.0, 0.0, 5.0, 2.0, 1.0, 4.0, -3.0, 1.0, 0.0, 5.0, 0.0)) == 30.0;
+const_assert quantizeToF16(1.0) == 1.0;
+const_assert quantizeToF16(0.1) == 0.0999755859375;
+
+// bits
+const_assert extractBits(0x12345678u, 8u, 8u) == 0x56u;
+const_assert extractBits(0x0f00i, 8u, 4u) == -1;
+const_assert all(extractBits(vec2(0xf0u, 0x0fu), 4u, 4u) == vec2(0xfu, 0u));
+const_assert insertBits(0xffffffffu, 0u, 4u, 8u) == 0xfffff00fu;
+const_assert insertBits(0, 5, 0u, 3u) == 5;
+
+// data packing
+const_assert pack4x8snorm(vec4(1.0, -1.0, 0.0, 0.5)) == 0x4000817fu;
+const_assert pack4x8unorm(vec4(1.0, 0.0, 0.5, 0.25)) == 0x408000ffu;
+const_assert pack2x16snorm(vec2(1.0, -1.0)) == 0x80017fffu;
+const_assert pack2x16unorm(vec2(1.0, 0.5)) == 0x8000ffffu;
+const_assert pack2x16float(vec2(1.0, -2.0)) == 0xc0003c00u;
+const_assert pack4xI8(vec4(1, -1, 127, -128)) == 0x807fff01u;
+const_assert pack4xU8(vec4(1u, 2u, 3u, 255u)) == 0xff030201u;
+
+// data unpacking
+const_assert all(unpack4x8snorm(0x00817f00u) == vec4(0.0, 1.0, -1.0, 0.0));
+const_assert all(unpack4x8unorm(0x00ff00ffu) == vec4(1.0, 0.0, 1.0, 0.0));
+const_assert all(unpack2x16snorm(0x80017fffu) == vec2(1.0, -1.0));
+const_assert all(unpack2x16unorm(0x0000ffffu) == vec2(1.0, 0.0));
+const_assert all(unpack2x16float(0xc0003c00u) == vec2(1.0, -2.0));
+const_assert all(unpack4xI8(0x807fff01u) == vec4(1, -1, 127, -128));
+const_assert all(unpack4xU8(0xff030201u) == vec4(1u, 2u, 3u, 255u));
+
+// Results of built-in functions can initialize constants and overrides.
+const modf_result = modf(vec2(1.5, -0.25));
+const frexp_result = frexp(2.5);
+const packed = pack2x16float(vec2(0.5, 1.0));
+const unpacked = unpack4xU8(packed);
+const transposed = transpose(m);
+override selected: f32 = select(modf_result.fract.x, frexp_result.fract, true);
+
+@compute @workgroup_size(1)
+fn main() {
+    const local_select = select(vec3(1i), vec3(2i), vec3(true, false, true));
+    const local_bitcast = bitcast<vec2<f32>>(vec2(0x3f800000u, 0x40000000u));
+    const local_determinant = determinant(mat2x2(2.0, 1.0, 1.0, 2.0));
+    let x = vec4<f32>(local_bitcast, vec2(local_determinant)) * f32(local_select.x) + selected;
+}
@@ -0,0 +1,13 @@
+/* Dual source blending with the `@blend_src` attribute */
+enable dual_source_blending;
+
+struct FragmentOutput{
+    @location(0) @blend_src(0) color: vec4<f32>,
+    @location(0) @blend_src(1) mask: vec4<f32>,
+}
+@fragment
+fn main(@builtin(position) position: vec4<f32>) -> FragmentOutput {
+    var color = vec4(0.4,0.3,0.2,0.1);
+    var mask = vec4(0.9,0.8,0.7,0.6);
+    return FragmentOutput(color, mask);
+}
@@ -0,0 +1,64 @@
+enable wgpu_mesh_shader;
+
+const positions = array(
+    vec4(0.0, 1.0, 0.0, 1.0),
+    vec4(-1.0, -1.0, 0.0, 1.0),
+    vec4(1.0, -1.0, 0.0, 1.0),
+);
+
+struct TaskPayload {
+    color_mask: vec4<f32>,
+    visible: bool,
+}
+
+struct TaskOutput {
+    @builtin(mesh_task_size) size: vec3<u32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+}
+
+struct PrimitiveOutput {
+    @builtin(triangle_indices) indices: vec3<u32>,
+    @builtin(cull_primitive) cull: bool,
+    @per_primitive @location(1) color_mask: vec4<f32>,
+}
+
+struct PrimitiveInput {
+    @per_primitive @location(1) color_mask: vec4<f32>,
+}
+
+var<task_payload> task_payload: TaskPayload;
+var<workgroup> workgroup_data: f32;
+
+@task
+@payload(task_payload)
+@workgroup_size(1)
+fn ts_main() -> TaskOutput {
+    workgroup_data = 1.0;
+    task_payload.color_mask = vec4(1.0, 1.0, 0.0, 1.0);
+    task_payload.visible = true;
+    return TaskOutput(vec3(3u, 1u, 1u));
+}
+
+@mesh
+@output_topology(triangles)
+@vertex_output(VertexOutput, 3)
+@primitive_output(PrimitiveOutput, 1)
+@payload(task_payload)
+@workgroup_size(1)
+fn ms_main(@builtin(local_invocation_index) index: u32, @builtin(global_invocation_id) id: vec3<u32>) {
+    setMeshOutputs(3u, 1u);
+    workgroup_data = 2.0;
+    for (var i = 0u; i < 3u; i++) {
+        setVertex(i, VertexOutput(positions[i], vec4(0.0, 1.0, 0.0, 1.0)));
+    }
+    setPrimitive(0u, PrimitiveOutput(vec3(0u, 1u, 2u), !task_payload.visible, task_payload.color_mask));
+}
+
+@fragment
+fn fs_main(vertex: VertexOutput, primitive: PrimitiveInput) -> @location(0) vec4<f32> {
+    return vertex.color * primitive.color_mask;
+}
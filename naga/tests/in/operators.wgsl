@@ -9,12 +9,20 @@ fn builtins() -> vec4<f32> {
     let s1 = select(0, 1, condition);
     let s2 = select(v_f32_zero, v_f32_one, condition);
     let s3 = select(v_f32_one, v_f32_zero, vec4<bool>(false, false, false, false));
+    let s4 = select(v_f32_one, v_f32_zero, vec4<bool>(!condition));
     // mix()
     let m1 = mix(v_f32_zero, v_f32_one, v_f32_half);
     let m2 = mix(v_f32_zero, v_f32_one, 0.1);
+    let half = v_f32_half;
+    let tenth = 0.1;
+    let m3 = mix(v_f32_zero, v_f32_one, half);
+    let m4 = mix(v_f32_zero, v_f32_one, tenth);
     // bitcast()
     let b1 = bitcast<f32>(v_i32_one.x);
     let b2 = bitcast<vec4<f32>>(v_i32_one);
+    let one = v_i32_one;
+    let b3 = bitcast<f32>(one.x);
+    let b4 = bitcast<vec4<f32>>(one);
     // convert
     let v_i32_zero = vec4<i32>(v_f32_zero);
     // done
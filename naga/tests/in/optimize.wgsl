@@ -0,0 +1,54 @@
+// Exercise the optimization passes in `naga::opt`.
+
+@group(0) @binding(0)
+var<storage, read_write> output: array<f32>;
+
+// Called once, with a constant argument that folds into its body.
+fn scale(v: f32, k: f32) -> f32 {
+    let factor = k * 4.0;
+    return v * factor;
+}
+
+// Called once, from inside a loop, so its local is reinitialized.
+fn accumulate(v: f32) -> f32 {
+    var sum = 1.0;
+    sum += v;
+    return sum;
+}
+
+// Called once, but returns early, so it isn't inlined.
+fn clamp_positive(v: f32) -> f32 {
+    if v < 0.0 {
+        return 0.0;
+    }
+    return v;
+}
+
+// Called twice, so it isn't inlined.
+fn square(v: f32) -> f32 {
+    return v * v;
+}
+
+// Called once, with no return value.
+fn store(index: u32, value: f32) {
+    output[index] = value;
+}
+
+@compute @workgroup_size(1)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let x = output[id.x];
+
+    // Never read, so both it and the store to it go away.
+    var scratch: f32;
+    scratch = square(x);
+
+    // Unused, so it goes away.
+    let unused = x + 1.0;
+
+    var total = scale(x, 2.0);
+    for (var i = 0u; i < 4u; i++) {
+        total += accumulate(f32(i));
+    }
+    total = clamp_positive(total) + square(total);
+    store(id.x, total);
+}
@@ -0,0 +1,12 @@
+fn test_packed_integer_dot_product() -> u32 {
+    let a = 1u;
+    let b = 2u;
+    let c: i32 = dot4I8Packed(a, b);
+    let d: u32 = dot4U8Packed(a, b);
+    return d + u32(c);
+}
+
+@compute @workgroup_size(1)
+fn main() {
+    let c = test_packed_integer_dot_product();
+}
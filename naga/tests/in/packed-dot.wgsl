@@ -0,0 +1,26 @@
+requires packed_4x8_integer_dot_product;
+
+// Evaluated at compile time.
+const signed_const = dot4I8Packed(0xFF02FE7Fu, 0x7F0301FFu);
+const unsigned_const = dot4U8Packed(0xFF02FE7Fu, 0x7F0301FFu);
+
+fn test_packed_integer_dot_product() -> u32 {
+    let a_5 = 1u;
+    let b_5 = 2u;
+    let c_5: i32 = dot4I8Packed(a_5, b_5);
+
+    let a_6 = 3u;
+    let b_6 = 4u;
+    let c_6: u32 = dot4U8Packed(a_6, b_6);
+
+    // Abstract integer arguments are converted to `u32`, and non-trivial
+    // arguments are only evaluated once.
+    let c_7: i32 = dot4I8Packed(5 + c_6, 6 + c_6);
+    let c_8: u32 = dot4U8Packed(7, 8);
+    return c_8 + u32(c_5 + c_7) + u32(signed_const) + unsigned_const;
+}
+
+@compute @workgroup_size(1)
+fn main() {
+    let c = test_packed_integer_dot_product();
+}
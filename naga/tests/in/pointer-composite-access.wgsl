@@ -0,0 +1,33 @@
+requires pointer_composite_access;
+
+struct Light {
+    position: vec3<f32>,
+    intensity: f32,
+}
+
+struct Scene {
+    lights: array<Light, 4>,
+    ambient: vec4<f32>,
+}
+
+fn brightness(scene: ptr<function, Scene>, index: i32) -> f32 {
+    let light = &scene.lights[index];
+    return light.intensity * scene.ambient.w + light.position.x;
+}
+
+fn dim(scene: ptr<function, Scene>) {
+    scene.lights[0].intensity *= 0.5;
+    scene.ambient.w = 0.0;
+    let lights = &scene.lights;
+    lights[1].position = vec3(1.0, 2.0, 3.0);
+}
+
+@compute @workgroup_size(1)
+fn main() {
+    var scene: Scene;
+    dim(&scene);
+    let total = brightness(&scene, 0) + brightness(&scene, 1);
+    var result = array<f32, 2>(total, 0.0);
+    let p = &result;
+    p[1] = p[0] * 2.0;
+}
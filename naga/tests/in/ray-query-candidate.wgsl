@@ -0,0 +1,63 @@
+@group(0) @binding(0)
+var acc_struct: acceleration_structure;
+
+struct Sphere {
+    center: vec3<f32>,
+    radius: f32,
+}
+
+@group(0) @binding(1)
+var<storage, read> spheres: array<Sphere>;
+
+@group(0) @binding(2)
+var alpha_mask: texture_2d<f32>;
+
+@group(0) @binding(3)
+var alpha_sampler: sampler;
+
+struct Output {
+    hit: u32,
+    t: f32,
+}
+
+@group(0) @binding(4)
+var<storage, read_write> output: Output;
+
+fn intersect_sphere(sphere: Sphere, origin: vec3<f32>, dir: vec3<f32>) -> f32 {
+    let oc = origin - sphere.center;
+    let b = dot(oc, dir);
+    let c = dot(oc, oc) - sphere.radius * sphere.radius;
+    let discriminant = b * b - c;
+    if (discriminant < 0.0) {
+        return -1.0;
+    }
+    return -b - sqrt(discriminant);
+}
+
+@compute @workgroup_size(1)
+fn main() {
+    let origin = vec3<f32>(0.0);
+    let dir = vec3<f32>(0.0, 1.0, 0.0);
+
+    var rq: ray_query;
+    rayQueryInitialize(&rq, acc_struct, RayDesc(0u, 0xFFu, 0.1, 100.0, origin, dir));
+
+    while (rayQueryProceed(&rq)) {
+        let candidate = rayQueryGetCandidateIntersection(&rq);
+        if (candidate.kind == RAY_QUERY_INTERSECTION_AABB) {
+            let t = intersect_sphere(spheres[candidate.primitive_index], origin, dir);
+            if (t >= 0.0) {
+                rayQueryGenerateIntersection(&rq, t);
+            }
+        } else {
+            let alpha = textureSampleLevel(alpha_mask, alpha_sampler, candidate.barycentrics, 0.0).a;
+            if (alpha > 0.5) {
+                rayQueryConfirmIntersection(&rq);
+            }
+        }
+    }
+
+    let intersection = rayQueryGetCommittedIntersection(&rq);
+    output.hit = intersection.kind;
+    output.t = intersection.t;
+}
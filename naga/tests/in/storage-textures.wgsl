@@ -0,0 +1,24 @@
+requires readonly_and_readwrite_storage_textures;
+
+@group(0) @binding(0) var s_r_r: texture_storage_2d<r32float, read>;
+@group(0) @binding(1) var s_rgba8_r: texture_storage_2d<rgba8unorm, read>;
+@group(0) @binding(2) var s_rgba32_r: texture_storage_2d<rgba32float, read>;
+
+@compute @workgroup_size(1) fn csLoad() {
+    _ = textureLoad(s_r_r, vec2u(0));
+    _ = textureLoad(s_rgba8_r, vec2u(0));
+    _ = textureLoad(s_rgba32_r, vec2u(0));
+}
+
+@group(1) @binding(0) var s_r_rw: texture_storage_2d<r32float, read_write>;
+@group(1) @binding(1) var s_rgba8_rw: texture_storage_2d<rgba8unorm, read_write>;
+@group(1) @binding(2) var s_rgba32_rw: texture_storage_2d<rgba32float, read_write>;
+
+@compute @workgroup_size(1) fn csStore() {
+    let r = textureLoad(s_r_rw, vec2u(0));
+    textureStore(s_r_rw, vec2u(0), r);
+    let rgba8 = textureLoad(s_rgba8_rw, vec2u(0));
+    textureStore(s_rgba8_rw, vec2u(0), rgba8);
+    let rgba32 = textureLoad(s_rgba32_rw, vec2u(0));
+    textureStore(s_rgba32_rw, vec2u(0), rgba32);
+}
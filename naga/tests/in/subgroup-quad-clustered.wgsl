@@ -0,0 +1,17 @@
+@compute @workgroup_size(1)
+fn main(
+    @builtin(subgroup_invocation_id) subgroup_invocation_id: u32,
+) {
+    quadBroadcast(subgroup_invocation_id, 2u);
+    quadSwapX(subgroup_invocation_id);
+    quadSwapY(subgroup_invocation_id);
+    quadSwapDiagonal(subgroup_invocation_id);
+
+    subgroupClusteredAdd(subgroup_invocation_id, 4u);
+    subgroupClusteredMul(subgroup_invocation_id, 2u);
+    subgroupClusteredMin(f32(subgroup_invocation_id), 8u);
+    subgroupClusteredMax(vec2(i32(subgroup_invocation_id)), 4u);
+    subgroupClusteredAnd(subgroup_invocation_id, 1u);
+    subgroupClusteredOr(subgroup_invocation_id, 4u);
+    subgroupClusteredXor(subgroup_invocation_id, 4u);
+}
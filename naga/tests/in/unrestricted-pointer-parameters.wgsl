@@ -0,0 +1,51 @@
+requires unrestricted_pointer_parameters;
+
+struct Particle {
+    position: vec4<f32>,
+    velocity: vec4<f32>,
+}
+
+struct Particles {
+    count: u32,
+    items: array<Particle>,
+}
+
+@group(0) @binding(0)
+var<storage, read_write> particles: Particles;
+
+@group(0) @binding(1)
+var<uniform> params: vec4<f32>;
+
+var<workgroup> sums: array<f32, 64>;
+
+fn advance(particle: ptr<storage, Particle, read_write>, delta: f32) {
+    (*particle).position += (*particle).velocity * delta;
+}
+
+fn read_delta(p: ptr<uniform, vec4<f32>>) -> f32 {
+    return (*p).x;
+}
+
+fn accumulate(slot: ptr<workgroup, f32>, value: f32) {
+    *slot += value;
+}
+
+fn advance_and_accumulate(
+    particle: ptr<storage, Particle, read_write>,
+    slot: ptr<workgroup, f32>,
+    delta: f32,
+) {
+    advance(particle, delta);
+    accumulate(slot, (*particle).position.x);
+}
+
+@compute @workgroup_size(64)
+fn main(
+    @builtin(local_invocation_index) index: u32,
+    @builtin(global_invocation_id) id: vec3<u32>,
+) {
+    let delta = read_delta(&params);
+    advance_and_accumulate(&particles.items[id.x], &sums[index], delta);
+    advance(&particles.items[0], delta);
+    accumulate(&sums[0], 1.0);
+}
@@ -0,0 +1,62 @@
+#version 310 es
+
+precision highp float;
+precision highp int;
+
+layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+struct _modf_result_f32_ {
+    float fract_;
+    float whole;
+};
+struct _modf_result_vec2_f32_ {
+    vec2 fract_;
+    vec2 whole;
+};
+struct _frexp_result_f32_ {
+    float fract_;
+    int exp_;
+};
+struct _frexp_result_vec2_f32_ {
+    vec2 fract_;
+    ivec2 exp_;
+};
+
+_modf_result_f32_ naga_modf(float arg) {
+    float other;
+    float fract = modf(arg, other);
+    return _modf_result_f32_(fract, other);
+}
+
+_modf_result_vec2_f32_ naga_modf(vec2 arg) {
+    vec2 other;
+    vec2 fract = modf(arg, other);
+    return _modf_result_vec2_f32_(fract, other);
+}
+
+_frexp_result_f32_ naga_frexp(float arg) {
+    int other;
+    float fract = frexp(arg, other);
+    return _frexp_result_f32_(fract, other);
+}
+
+_frexp_result_vec2_f32_ naga_frexp(vec2 arg) {
+    ivec2 other;
+    vec2 fract = frexp(arg, other);
+    return _frexp_result_vec2_f32_(fract, other);
+}
+const mat2x3 m = mat2x3(vec3(1.0, 2.0, 3.0), vec3(4.0, 5.0, 6.0));
+const _modf_result_vec2_f32_ modf_result = _modf_result_vec2_f32_(vec2(0.5, -0.25), vec2(1.0, -0.0));
+const _frexp_result_f32_ frexp_result = _frexp_result_f32_(0.625, 2);
+const uint packed_ = 1006647296u;
+const uvec4 unpacked = uvec4(0u, 56u, 0u, 60u);
+const mat3x2 transposed = mat3x2(vec2(1.0, 4.0), vec2(2.0, 5.0), vec2(3.0, 6.0));
+const float selected = 0.625;
+
+
+void main() {
+    ivec3 local_select = ivec3(2, 1, 2);
+    vec2 local_bitcast = vec2(1.0, 2.0);
+    vec4 x = vec4(2.625, 4.625, 6.625, 6.625);
+}
+
@@ -0,0 +1,137 @@
+#version 450 core
+#extension GL_ARB_compute_shader : require
+#extension GL_ARB_shader_storage_buffer_object : require
+#extension GL_EXT_shader_explicit_arithmetic_types_float16 : require
+#extension GL_EXT_shader_16bit_storage : require
+layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+struct UniformCompatible {
+    uint val_u32_;
+    int val_i32_;
+    float val_f32_;
+    float16_t val_f16_;
+    f16vec2 val_f16_2_;
+    f16vec3 val_f16_3_;
+    f16vec4 val_f16_4_;
+    float16_t final_value;
+    f16mat2x2 val_mat2x2_f16_;
+    f16mat2x4 val_mat2x4_f16_;
+    f16mat3x2 val_mat3x2_f16_;
+    f16mat4x4 val_mat4x4_f16_;
+};
+struct StorageCompatible {
+    float16_t val_f16_array_2_[2];
+};
+struct LayoutTest {
+    float16_t scalar1_;
+    float16_t scalar2_;
+    f16vec3 v3_;
+    float16_t tuck_in;
+    float16_t scalar4_;
+    uint larger;
+};
+const float16_t constant_variable = 15.203125hf;
+
+uniform UniformCompatible_block_0Compute { UniformCompatible _group_0_binding_0_cs; };
+
+layout(std430) readonly buffer UniformCompatible_block_1Compute { UniformCompatible _group_0_binding_1_cs; };
+
+layout(std430) readonly buffer StorageCompatible_block_2Compute { StorageCompatible _group_0_binding_2_cs; };
+
+layout(std430) buffer UniformCompatible_block_3Compute { UniformCompatible _group_0_binding_3_cs; };
+
+layout(std430) buffer StorageCompatible_block_4Compute { StorageCompatible _group_0_binding_4_cs; };
+
+
+float16_t f16_function(float16_t x) {
+    float16_t val = 15.203125hf;
+    float16_t _e4 = val;
+    val = (_e4 + -33344.0hf);
+    float16_t _e6 = val;
+    float16_t _e9 = val;
+    val = (_e9 + (_e6 + 5.0hf));
+    float _e13 = _group_0_binding_0_cs.val_f32_;
+    float16_t _e14 = val;
+    float16_t _e18 = val;
+    val = (_e18 + float16_t((_e13 + float(_e14))));
+    float16_t _e22 = _group_0_binding_0_cs.val_f16_;
+    float16_t _e25 = val;
+    val = (_e25 + f16vec3(_e22).z);
+    float16_t _e31 = _group_0_binding_0_cs.val_f16_;
+    float16_t _e34 = _group_0_binding_1_cs.val_f16_;
+    _group_0_binding_3_cs.val_f16_ = (_e31 + _e34);
+    f16vec2 _e40 = _group_0_binding_0_cs.val_f16_2_;
+    f16vec2 _e43 = _group_0_binding_1_cs.val_f16_2_;
+    _group_0_binding_3_cs.val_f16_2_ = (_e40 + _e43);
+    f16vec3 _e49 = _group_0_binding_0_cs.val_f16_3_;
+    f16vec3 _e52 = _group_0_binding_1_cs.val_f16_3_;
+    _group_0_binding_3_cs.val_f16_3_ = (_e49 + _e52);
+    f16vec4 _e58 = _group_0_binding_0_cs.val_f16_4_;
+    f16vec4 _e61 = _group_0_binding_1_cs.val_f16_4_;
+    _group_0_binding_3_cs.val_f16_4_ = (_e58 + _e61);
+    f16mat2x2 _e67 = _group_0_binding_0_cs.val_mat2x2_f16_;
+    f16mat2x2 _e70 = _group_0_binding_1_cs.val_mat2x2_f16_;
+    _group_0_binding_3_cs.val_mat2x2_f16_ = (_e67 + _e70);
+    f16mat2x4 _e76 = _group_0_binding_0_cs.val_mat2x4_f16_;
+    f16mat2x4 _e79 = _group_0_binding_1_cs.val_mat2x4_f16_;
+    _group_0_binding_3_cs.val_mat2x4_f16_ = (_e76 + _e79);
+    f16mat3x2 _e85 = _group_0_binding_0_cs.val_mat3x2_f16_;
+    f16mat3x2 _e88 = _group_0_binding_1_cs.val_mat3x2_f16_;
+    _group_0_binding_3_cs.val_mat3x2_f16_ = (_e85 + _e88);
+    f16mat4x4 _e94 = _group_0_binding_0_cs.val_mat4x4_f16_;
+    f16mat4x4 _e97 = _group_0_binding_1_cs.val_mat4x4_f16_;
+    _group_0_binding_3_cs.val_mat4x4_f16_ = (_e94 + _e97);
+    float16_t _e103[2] = _group_0_binding_2_cs.val_f16_array_2_;
+    _group_0_binding_4_cs.val_f16_array_2_ = _e103;
+    float16_t _e104 = val;
+    float16_t _e106 = val;
+    val = (_e106 + abs(_e104));
+    float16_t _e108 = val;
+    float16_t _e109 = val;
+    float16_t _e110 = val;
+    float16_t _e112 = val;
+    val = (_e112 + clamp(_e108, _e109, _e110));
+    float16_t _e114 = val;
+    float16_t _e116 = val;
+    float16_t _e119 = val;
+    val = (_e119 + dot(f16vec2(_e114), f16vec2(_e116)));
+    float16_t _e121 = val;
+    float16_t _e122 = val;
+    float16_t _e124 = val;
+    val = (_e124 + max(_e121, _e122));
+    float16_t _e126 = val;
+    float16_t _e127 = val;
+    float16_t _e129 = val;
+    val = (_e129 + min(_e126, _e127));
+    float16_t _e131 = val;
+    float16_t _e133 = val;
+    val = (_e133 + sign(_e131));
+    float16_t _e136 = val;
+    val = (_e136 + 1.0hf);
+    f16vec2 _e140 = _group_0_binding_0_cs.val_f16_2_;
+    vec2 float_vec2_ = vec2(_e140);
+    _group_0_binding_3_cs.val_f16_2_ = f16vec2(float_vec2_);
+    f16vec3 _e147 = _group_0_binding_0_cs.val_f16_3_;
+    vec3 float_vec3_ = vec3(_e147);
+    _group_0_binding_3_cs.val_f16_3_ = f16vec3(float_vec3_);
+    f16vec4 _e154 = _group_0_binding_0_cs.val_f16_4_;
+    vec4 float_vec4_ = vec4(_e154);
+    _group_0_binding_3_cs.val_f16_4_ = f16vec4(float_vec4_);
+    f16mat2x2 _e163 = _group_0_binding_0_cs.val_mat2x2_f16_;
+    _group_0_binding_3_cs.val_mat2x2_f16_ = f16mat2x2(mat2x2(_e163));
+    f16mat2x4 _e170 = _group_0_binding_0_cs.val_mat2x4_f16_;
+    _group_0_binding_3_cs.val_mat2x4_f16_ = f16mat2x4(mat2x4(_e170));
+    f16mat3x2 _e177 = _group_0_binding_0_cs.val_mat3x2_f16_;
+    _group_0_binding_3_cs.val_mat3x2_f16_ = f16mat3x2(mat3x2(_e177));
+    f16mat4x4 _e184 = _group_0_binding_0_cs.val_mat4x4_f16_;
+    _group_0_binding_3_cs.val_mat4x4_f16_ = f16mat4x4(mat4x4(_e184));
+    float16_t _e187 = val;
+    return _e187;
+}
+
+void main() {
+    float16_t _e3 = f16_function(2.0hf);
+    _group_0_binding_3_cs.final_value = _e3;
+    return;
+}
+
@@ -0,0 +1,73 @@
+#version 310 es
+
+precision highp float;
+precision highp int;
+
+layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+layout(std430) buffer type_1_block_0Compute { float _group_0_binding_0_cs[]; };
+
+
+float clamp_positive(float v) {
+    if ((v < 0.0)) {
+        return 0.0;
+    }
+    return v;
+}
+
+float square(float v_1) {
+    return (v_1 * v_1);
+}
+
+void main() {
+    uvec3 id = gl_GlobalInvocationID;
+    float total = 0.0;
+    uint i = 0u;
+    float scale_result = 0.0;
+    float accumulate_result = 0.0;
+    float sum = 1.0;
+    float x = _group_0_binding_0_cs[id.x];
+    float _e5 = square(x);
+    {
+        scale_result = (x * 8.0);
+    }
+    float _e7 = scale_result;
+    total = _e7;
+    bool loop_init = true;
+    while(true) {
+        if (!loop_init) {
+            uint _e21 = i;
+            i = (_e21 + 1u);
+        }
+        loop_init = false;
+        uint _e11 = i;
+        if ((_e11 < 4u)) {
+        } else {
+            break;
+        }
+        {
+            uint _e14 = i;
+            {
+                sum = 1.0;
+                float _e34 = sum;
+                sum = (_e34 + float(_e14));
+                float _e36 = sum;
+                accumulate_result = _e36;
+            }
+            float _e17 = accumulate_result;
+            float _e18 = total;
+            total = (_e18 + _e17);
+        }
+    }
+    float _e23 = total;
+    float _e24 = clamp_positive(_e23);
+    float _e25 = total;
+    float _e26 = square(_e25);
+    total = (_e24 + _e26);
+    float _e29 = total;
+    {
+        _group_0_binding_0_cs[id.x] = _e29;
+    }
+    return;
+}
+
@@ -0,0 +1,25 @@
+#version 310 es
+
+precision highp float;
+precision highp int;
+
+layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+const int signed_const = -250;
+const uint unsigned_const = 65030u;
+
+
+uint test_packed_integer_dot_product() {
+    int c_5_ = ( + bitfieldExtract(int(1u), 0, 8) * bitfieldExtract(int(2u), 0, 8) + bitfieldExtract(int(1u), 8, 8) * bitfieldExtract(int(2u), 8, 8) + bitfieldExtract(int(1u), 16, 8) * bitfieldExtract(int(2u), 16, 8) + bitfieldExtract(int(1u), 24, 8) * bitfieldExtract(int(2u), 24, 8));
+    uint c_6_ = ( + bitfieldExtract(3u, 0, 8) * bitfieldExtract(4u, 0, 8) + bitfieldExtract(3u, 8, 8) * bitfieldExtract(4u, 8, 8) + bitfieldExtract(3u, 16, 8) * bitfieldExtract(4u, 16, 8) + bitfieldExtract(3u, 24, 8) * bitfieldExtract(4u, 24, 8));
+    uint _e7 = (5u + c_6_);
+    uint _e9 = (6u + c_6_);
+    int c_7_ = ( + bitfieldExtract(int(_e7), 0, 8) * bitfieldExtract(int(_e9), 0, 8) + bitfieldExtract(int(_e7), 8, 8) * bitfieldExtract(int(_e9), 8, 8) + bitfieldExtract(int(_e7), 16, 8) * bitfieldExtract(int(_e9), 16, 8) + bitfieldExtract(int(_e7), 24, 8) * bitfieldExtract(int(_e9), 24, 8));
+    return (((56u + uint((c_5_ + c_7_))) + 4294967046u) + unsigned_const);
+}
+
+void main() {
+    uint _e0 = test_packed_integer_dot_product();
+    return;
+}
+
@@ -0,0 +1,44 @@
+#version 310 es
+
+precision highp float;
+precision highp int;
+
+layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+struct Light {
+    vec3 position;
+    float intensity;
+};
+struct Scene {
+    Light lights[4];
+    vec4 ambient;
+};
+
+float brightness(inout Scene scene_1, int index) {
+    float _e5 = scene_1.lights[index].intensity;
+    float _e8 = scene_1.ambient.w;
+    float _e12 = scene_1.lights[index].position.x;
+    return ((_e5 * _e8) + _e12);
+}
+
+void dim(inout Scene scene_2) {
+    float _e5 = scene_2.lights[0].intensity;
+    scene_2.lights[0].intensity = (_e5 * 0.5);
+    scene_2.ambient.w = 0.0;
+    scene_2.lights[1].position = vec3(1.0, 2.0, 3.0);
+    return;
+}
+
+void main() {
+    Scene scene = Scene(Light[4](Light(vec3(0.0), 0.0), Light(vec3(0.0), 0.0), Light(vec3(0.0), 0.0), Light(vec3(0.0), 0.0)), vec4(0.0));
+    float result[2] = float[2](0.0, 0.0);
+    dim(scene);
+    float _e2 = brightness(scene, 0);
+    float _e4 = brightness(scene, 1);
+    float total = (_e2 + _e4);
+    result = float[2](total, 0.0);
+    float _e11 = result[0];
+    result[1] = (_e11 * 2.0);
+    return;
+}
+
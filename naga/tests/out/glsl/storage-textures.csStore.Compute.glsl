@@ -0,0 +1,21 @@
+#version 430 core
+#extension GL_ARB_compute_shader : require
+layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+layout(r32f) uniform image2D _group_1_binding_0_cs;
+
+layout(rgba8) uniform image2D _group_1_binding_1_cs;
+
+layout(rgba32f) uniform image2D _group_1_binding_2_cs;
+
+
+void main() {
+    vec4 r = imageLoad(_group_1_binding_0_cs, ivec2(uvec2(0u)));
+    imageStore(_group_1_binding_0_cs, ivec2(uvec2(0u)), r);
+    vec4 rgba8_ = imageLoad(_group_1_binding_1_cs, ivec2(uvec2(0u)));
+    imageStore(_group_1_binding_1_cs, ivec2(uvec2(0u)), rgba8_);
+    vec4 rgba32_ = imageLoad(_group_1_binding_2_cs, ivec2(uvec2(0u)));
+    imageStore(_group_1_binding_2_cs, ivec2(uvec2(0u)), rgba32_);
+    return;
+}
+
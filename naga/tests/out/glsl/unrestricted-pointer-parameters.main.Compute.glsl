@@ -0,0 +1,74 @@
+#version 310 es
+
+precision highp float;
+precision highp int;
+
+layout(local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+struct Particle {
+    vec4 position;
+    vec4 velocity;
+};
+layout(std430) buffer Particles_block_0Compute {
+    uint count;
+    Particle items[];
+} _group_0_binding_0_cs;
+
+uniform type_1_block_1Compute { vec4 _group_0_binding_1_cs; };
+
+shared float sums[64];
+
+
+float read_delta() {
+    float _e2 = _group_0_binding_1_cs.x;
+    return _e2;
+}
+
+void advance(float delta, uint particle_index) {
+    vec4 _e7 = _group_0_binding_0_cs.items[particle_index].velocity;
+    vec4 _e9 = _group_0_binding_0_cs.items[particle_index].position;
+    _group_0_binding_0_cs.items[particle_index].position = (_e9 + (_e7 * delta));
+    return;
+}
+
+void accumulate(float value, uint slot_index) {
+    float _e4 = sums[slot_index];
+    sums[slot_index] = (_e4 + value);
+    return;
+}
+
+void advance_and_accumulate(float delta_1, uint particle_index_1, uint slot_index_1) {
+    advance(delta_1, particle_index_1);
+    float _e10 = _group_0_binding_0_cs.items[particle_index_1].position.x;
+    accumulate(_e10, slot_index_1);
+    return;
+}
+
+void advance_1(float delta_2) {
+    vec4 _e6 = _group_0_binding_0_cs.items[0].velocity;
+    vec4 _e8 = _group_0_binding_0_cs.items[0].position;
+    _group_0_binding_0_cs.items[0].position = (_e8 + (_e6 * delta_2));
+    return;
+}
+
+void accumulate_1(float value_1) {
+    float _e3 = sums[0];
+    sums[0] = (_e3 + value_1);
+    return;
+}
+
+void main() {
+    if (gl_LocalInvocationID == uvec3(0u)) {
+        sums = float[64](0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+    }
+    memoryBarrierShared();
+    barrier();
+    uint index = gl_LocalInvocationIndex;
+    uvec3 id = gl_GlobalInvocationID;
+    float _e3 = read_delta();
+    advance_and_accumulate(_e3, id.x, index);
+    advance_1(_e3);
+    accumulate_1(1.0);
+    return;
+}
+
@@ -130,15 +130,15 @@ void test_matrix_within_struct_accesses()
     float3x2 l0_ = GetMatmOnBaz(baz);
     float2 l1_ = GetMatmOnBaz(baz)[0];
     int _e14 = idx;
-    float2 l2_ = GetMatmOnBaz(baz)[_e14];
+    float2 l2_ = GetMatmOnBaz(baz)[min(uint(_e14), 2u)];
     float l3_ = GetMatmOnBaz(baz)[0].y;
     int _e25 = idx;
-    float l4_ = GetMatmOnBaz(baz)[0][_e25];
+    float l4_ = GetMatmOnBaz(baz)[0][min(uint(_e25), 1u)];
     int _e30 = idx;
-    float l5_ = GetMatmOnBaz(baz)[_e30].y;
+    float l5_ = GetMatmOnBaz(baz)[min(uint(_e30), 2u)].y;
     int _e36 = idx;
     int _e38 = idx;
-    float l6_ = GetMatmOnBaz(baz)[_e36][_e38];
+    float l6_ = GetMatmOnBaz(baz)[min(uint(_e36), 2u)][min(uint(_e38), 1u)];
     int _e51 = idx;
     idx = (_e51 + 1);
     SetMatmOnBaz(t, float3x2((6.0).xx, (5.0).xx, (4.0).xx));
@@ -181,12 +181,12 @@ void test_matrix_within_array_within_struct_accesses()
     float2 l3_1 = __get_col_of_mat4x2(nested_mat_cx2_.am[0], _e20);
     float l4_1 = nested_mat_cx2_.am[0]._0.y;
     int _e33 = idx_1;
-    float l5_1 = nested_mat_cx2_.am[0]._0[_e33];
+    float l5_1 = nested_mat_cx2_.am[0]._0[min(uint(_e33), 1u)];
     int _e39 = idx_1;
     float l6_1 = __get_col_of_mat4x2(nested_mat_cx2_.am[0], _e39).y;
     int _e46 = idx_1;
     int _e48 = idx_1;
-    float l7_ = __get_col_of_mat4x2(nested_mat_cx2_.am[0], _e46)[_e48];
+    float l7_ = __get_col_of_mat4x2(nested_mat_cx2_.am[0], _e46)[min(uint(_e48), 1u)];
     int _e55 = idx_1;
     idx_1 = (_e55 + 1);
     t_1.am = (__mat4x2[2])ZeroValuearray2_float4x2_();
@@ -294,7 +294,7 @@ float4 foo_vert(uint vi : SV_VertexID) : SV_Position
     float4x3 _matrix = float4x3(asfloat(bar.Load3(0+0)), asfloat(bar.Load3(0+16)), asfloat(bar.Load3(0+32)), asfloat(bar.Load3(0+48)));
     uint2 arr_1[2] = Constructarray2_uint2_(asuint(bar.Load2(144+0)), asuint(bar.Load2(144+8)));
     float b = asfloat(bar.Load(0+3u*16+0));
-    int a_1 = asint(bar.Load(0+(((NagaBufferLengthRW(bar) - 160) / 8) - 2u)*8+160));
+    int a_1 = asint(bar.Load(0+min(uint((((NagaBufferLengthRW(bar) - 160) / 8) - 2u)), ((NagaBufferLengthRW(bar) - 160) / 8) - 1u)*8+160));
     int2 c = asint(qux.Load2(0));
     const float _e33 = read_from_private(foo);
     c2_ = Constructarray5_int_(a_1, int(b), 3, 4, 5);
@@ -323,7 +323,7 @@ float4 foo_frag() : SV_Target0
         bar.Store2(144+0, asuint(_value2[0]));
         bar.Store2(144+8, asuint(_value2[1]));
     }
-    bar.Store(0+8+160, asuint(1));
+    bar.Store(0+min(1u, ((NagaBufferLengthRW(bar) - 160) / 8) - 1u)*8+160, asuint(1));
     qux.Store2(0, asuint(ZeroValueint2()));
     return (0.0).xxxx;
 }
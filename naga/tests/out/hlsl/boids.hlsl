@@ -19,6 +19,20 @@ cbuffer params : register(b0) { SimParams params; }
 ByteAddressBuffer particlesSrc : register(t1);
 RWByteAddressBuffer particlesDst : register(u2);
 
+uint NagaBufferLength(ByteAddressBuffer buffer)
+{
+    uint ret;
+    buffer.GetDimensions(ret);
+    return ret;
+}
+
+uint NagaBufferLengthRW(RWByteAddressBuffer buffer)
+{
+    uint ret;
+    buffer.GetDimensions(ret);
+    return ret;
+}
+
 [numthreads(64, 1, 1)]
 void main(uint3 global_invocation_id : SV_DispatchThreadID)
 {
@@ -37,9 +51,9 @@ void main(uint3 global_invocation_id : SV_DispatchThreadID)
     if ((index >= NUM_PARTICLES)) {
         return;
     }
-    float2 _e8 = asfloat(particlesSrc.Load2(0+index*16+0));
+    float2 _e8 = asfloat(particlesSrc.Load2(0+min(uint(index), ((NagaBufferLength(particlesSrc) - 0) / 16) - 1u)*16+0));
     vPos = _e8;
-    float2 _e14 = asfloat(particlesSrc.Load2(8+index*16+0));
+    float2 _e14 = asfloat(particlesSrc.Load2(8+min(uint(index), ((NagaBufferLength(particlesSrc) - 0) / 16) - 1u)*16+0));
     vVel = _e14;
     bool loop_init = true;
     while(true) {
@@ -57,10 +71,10 @@ void main(uint3 global_invocation_id : SV_DispatchThreadID)
             continue;
         }
         uint _e43 = i;
-        float2 _e46 = asfloat(particlesSrc.Load2(0+_e43*16+0));
+        float2 _e46 = asfloat(particlesSrc.Load2(0+min(uint(_e43), ((NagaBufferLength(particlesSrc) - 0) / 16) - 1u)*16+0));
         pos = _e46;
         uint _e49 = i;
-        float2 _e52 = asfloat(particlesSrc.Load2(8+_e49*16+0));
+        float2 _e52 = asfloat(particlesSrc.Load2(8+min(uint(_e49), ((NagaBufferLength(particlesSrc) - 0) / 16) - 1u)*16+0));
         vel = _e52;
         float2 _e53 = pos;
         float2 _e54 = vPos;
@@ -137,8 +151,8 @@ void main(uint3 global_invocation_id : SV_DispatchThreadID)
         vPos.y = -1.0;
     }
     float2 _e174 = vPos;
-    particlesDst.Store2(0+index*16+0, asuint(_e174));
+    particlesDst.Store2(0+min(uint(index), ((NagaBufferLengthRW(particlesDst) - 0) / 16) - 1u)*16+0, asuint(_e174));
     float2 _e179 = vVel;
-    particlesDst.Store2(8+index*16+0, asuint(_e179));
+    particlesDst.Store2(8+min(uint(index), ((NagaBufferLengthRW(particlesDst) - 0) / 16) - 1u)*16+0, asuint(_e179));
     return;
 }
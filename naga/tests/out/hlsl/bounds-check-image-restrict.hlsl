@@ -0,0 +1,256 @@
+Texture1D<float4> image_1d : register(t0);
+Texture2D<float4> image_2d : register(t1);
+Texture2DArray<float4> image_2d_array : register(t2);
+Texture3D<float4> image_3d : register(t3);
+Texture2DMS<float4> image_multisampled_2d : register(t4);
+Texture2D<float> image_depth_2d : register(t5);
+Texture2DArray<float> image_depth_2d_array : register(t6);
+Texture2DMS<float> image_depth_multisampled_2d : register(t7);
+RWTexture1D<unorm float4> image_storage_1d : register(u8);
+RWTexture2D<unorm float4> image_storage_2d : register(u9);
+RWTexture2DArray<unorm float4> image_storage_2d_array : register(u10);
+RWTexture3D<unorm float4> image_storage_3d : register(u11);
+
+uint NagaMipDimensions1D(Texture1D<float4> tex, uint mip_level)
+{
+    uint4 ret;
+    tex.GetDimensions(mip_level, ret.x, ret.y);
+    return ret.x;
+}
+
+uint NagaNumLevels1D(Texture1D<float4> tex)
+{
+    uint4 ret;
+    tex.GetDimensions(0, ret.x, ret.y, ret.z);
+    return ret.z;
+}
+
+float4 test_textureLoad_1d(int coords, int level)
+{
+    float4 _e3 = image_1d.Load(int2(min(uint(coords), NagaMipDimensions1D(image_1d, min(uint(level), NagaNumLevels1D(image_1d) - 1u)) - 1u), min(uint(level), NagaNumLevels1D(image_1d) - 1u)));
+    return _e3;
+}
+
+uint2 NagaMipDimensions2D(Texture2D<float4> tex, uint mip_level)
+{
+    uint4 ret;
+    tex.GetDimensions(mip_level, ret.x, ret.y, ret.z);
+    return ret.xy;
+}
+
+uint NagaNumLevels2D(Texture2D<float4> tex)
+{
+    uint4 ret;
+    tex.GetDimensions(0, ret.x, ret.y, ret.z);
+    return ret.z;
+}
+
+float4 test_textureLoad_2d(int2 coords_1, int level_1)
+{
+    float4 _e3 = image_2d.Load(int3(min(uint2(coords_1), NagaMipDimensions2D(image_2d, min(uint(level_1), NagaNumLevels2D(image_2d) - 1u)) - 1u), min(uint(level_1), NagaNumLevels2D(image_2d) - 1u)));
+    return _e3;
+}
+
+uint2 NagaMipDimensions2DArray(Texture2DArray<float4> tex, uint mip_level)
+{
+    uint4 ret;
+    tex.GetDimensions(mip_level, ret.x, ret.y, ret.z, ret.w);
+    return ret.xy;
+}
+
+uint NagaNumLevels2DArray(Texture2DArray<float4> tex)
+{
+    uint4 ret;
+    tex.GetDimensions(0, ret.x, ret.y, ret.z, ret.w);
+    return ret.w;
+}
+
+uint NagaNumLayers2DArray(Texture2DArray<float4> tex)
+{
+    uint4 ret;
+    tex.GetDimensions(0, ret.x, ret.y, ret.z, ret.w);
+    return ret.z;
+}
+
+float4 test_textureLoad_2d_array_u(int2 coords_2, uint index, int level_2)
+{
+    float4 _e4 = image_2d_array.Load(int4(min(uint2(coords_2), NagaMipDimensions2DArray(image_2d_array, min(uint(level_2), NagaNumLevels2DArray(image_2d_array) - 1u)) - 1u), min(uint(index), NagaNumLayers2DArray(image_2d_array) - 1u), min(uint(level_2), NagaNumLevels2DArray(image_2d_array) - 1u)));
+    return _e4;
+}
+
+float4 test_textureLoad_2d_array_s(int2 coords_3, int index_1, int level_3)
+{
+    float4 _e4 = image_2d_array.Load(int4(min(uint2(coords_3), NagaMipDimensions2DArray(image_2d_array, min(uint(level_3), NagaNumLevels2DArray(image_2d_array) - 1u)) - 1u), min(uint(index_1), NagaNumLayers2DArray(image_2d_array) - 1u), min(uint(level_3), NagaNumLevels2DArray(image_2d_array) - 1u)));
+    return _e4;
+}
+
+uint3 NagaMipDimensions3D(Texture3D<float4> tex, uint mip_level)
+{
+    uint4 ret;
+    tex.GetDimensions(mip_level, ret.x, ret.y, ret.z, ret.w);
+    return ret.xyz;
+}
+
+uint NagaNumLevels3D(Texture3D<float4> tex)
+{
+    uint4 ret;
+    tex.GetDimensions(0, ret.x, ret.y, ret.z, ret.w);
+    return ret.w;
+}
+
+float4 test_textureLoad_3d(int3 coords_4, int level_4)
+{
+    float4 _e3 = image_3d.Load(int4(min(uint3(coords_4), NagaMipDimensions3D(image_3d, min(uint(level_4), NagaNumLevels3D(image_3d) - 1u)) - 1u), min(uint(level_4), NagaNumLevels3D(image_3d) - 1u)));
+    return _e3;
+}
+
+uint2 NagaMSDimensions2D(Texture2DMS<float4> tex)
+{
+    uint4 ret;
+    tex.GetDimensions(ret.x, ret.y, ret.z);
+    return ret.xy;
+}
+
+uint NagaMSNumSamples2D(Texture2DMS<float4> tex)
+{
+    uint4 ret;
+    tex.GetDimensions(ret.x, ret.y, ret.z);
+    return ret.z;
+}
+
+float4 test_textureLoad_multisampled_2d(int2 coords_5, int _sample)
+{
+    float4 _e3 = image_multisampled_2d.Load(min(uint2(coords_5), NagaMSDimensions2D(image_multisampled_2d) - 1u), min(uint(_sample), NagaMSNumSamples2D(image_multisampled_2d) - 1u));
+    return _e3;
+}
+
+uint2 NagaDepthMipDimensions2D(Texture2D<float> tex, uint mip_level)
+{
+    uint4 ret;
+    tex.GetDimensions(mip_level, ret.x, ret.y, ret.z);
+    return ret.xy;
+}
+
+uint NagaDepthNumLevels2D(Texture2D<float> tex)
+{
+    uint4 ret;
+    tex.GetDimensions(0, ret.x, ret.y, ret.z);
+    return ret.z;
+}
+
+float test_textureLoad_depth_2d(int2 coords_6, int level_5)
+{
+    float _e3 = image_depth_2d.Load(int3(min(uint2(coords_6), NagaDepthMipDimensions2D(image_depth_2d, min(uint(level_5), NagaDepthNumLevels2D(image_depth_2d) - 1u)) - 1u), min(uint(level_5), NagaDepthNumLevels2D(image_depth_2d) - 1u))).x;
+    return _e3;
+}
+
+uint2 NagaDepthMipDimensions2DArray(Texture2DArray<float> tex, uint mip_level)
+{
+    uint4 ret;
+    tex.GetDimensions(mip_level, ret.x, ret.y, ret.z, ret.w);
+    return ret.xy;
+}
+
+uint NagaDepthNumLevels2DArray(Texture2DArray<float> tex)
+{
+    uint4 ret;
+    tex.GetDimensions(0, ret.x, ret.y, ret.z, ret.w);
+    return ret.w;
+}
+
+uint NagaDepthNumLayers2DArray(Texture2DArray<float> tex)
+{
+    uint4 ret;
+    tex.GetDimensions(0, ret.x, ret.y, ret.z, ret.w);
+    return ret.z;
+}
+
+float test_textureLoad_depth_2d_array_u(int2 coords_7, uint index_2, int level_6)
+{
+    float _e4 = image_depth_2d_array.Load(int4(min(uint2(coords_7), NagaDepthMipDimensions2DArray(image_depth_2d_array, min(uint(level_6), NagaDepthNumLevels2DArray(image_depth_2d_array) - 1u)) - 1u), min(uint(index_2), NagaDepthNumLayers2DArray(image_depth_2d_array) - 1u), min(uint(level_6), NagaDepthNumLevels2DArray(image_depth_2d_array) - 1u))).x;
+    return _e4;
+}
+
+float test_textureLoad_depth_2d_array_s(int2 coords_8, int index_3, int level_7)
+{
+    float _e4 = image_depth_2d_array.Load(int4(min(uint2(coords_8), NagaDepthMipDimensions2DArray(image_depth_2d_array, min(uint(level_7), NagaDepthNumLevels2DArray(image_depth_2d_array) - 1u)) - 1u), min(uint(index_3), NagaDepthNumLayers2DArray(image_depth_2d_array) - 1u), min(uint(level_7), NagaDepthNumLevels2DArray(image_depth_2d_array) - 1u))).x;
+    return _e4;
+}
+
+uint2 NagaDepthMSDimensions2D(Texture2DMS<float> tex)
+{
+    uint4 ret;
+    tex.GetDimensions(ret.x, ret.y, ret.z);
+    return ret.xy;
+}
+
+uint NagaDepthMSNumSamples2D(Texture2DMS<float> tex)
+{
+    uint4 ret;
+    tex.GetDimensions(ret.x, ret.y, ret.z);
+    return ret.z;
+}
+
+float test_textureLoad_depth_multisampled_2d(int2 coords_9, int _sample_1)
+{
+    float _e3 = image_depth_multisampled_2d.Load(min(uint2(coords_9), NagaDepthMSDimensions2D(image_depth_multisampled_2d) - 1u), min(uint(_sample_1), NagaDepthMSNumSamples2D(image_depth_multisampled_2d) - 1u)).x;
+    return _e3;
+}
+
+void test_textureStore_1d(int coords_10, float4 value)
+{
+    image_storage_1d[coords_10] = value;
+    return;
+}
+
+void test_textureStore_2d(int2 coords_11, float4 value_1)
+{
+    image_storage_2d[coords_11] = value_1;
+    return;
+}
+
+void test_textureStore_2d_array_u(int2 coords_12, uint array_index, float4 value_2)
+{
+    image_storage_2d_array[int3(coords_12, array_index)] = value_2;
+    return;
+}
+
+void test_textureStore_2d_array_s(int2 coords_13, int array_index_1, float4 value_3)
+{
+    image_storage_2d_array[int3(coords_13, array_index_1)] = value_3;
+    return;
+}
+
+void test_textureStore_3d(int3 coords_14, float4 value_4)
+{
+    image_storage_3d[coords_14] = value_4;
+    return;
+}
+
+int2 ZeroValueint2() {
+    return (int2)0;
+}
+
+int3 ZeroValueint3() {
+    return (int3)0;
+}
+
+float4 ZeroValuefloat4() {
+    return (float4)0;
+}
+
+float4 fragment_shader() : SV_Target0
+{
+    const float4 _e2 = test_textureLoad_1d(0, 0);
+    const float4 _e5 = test_textureLoad_2d(ZeroValueint2(), 0);
+    const float4 _e9 = test_textureLoad_2d_array_u(ZeroValueint2(), 0u, 0);
+    const float4 _e13 = test_textureLoad_2d_array_s(ZeroValueint2(), 0, 0);
+    const float4 _e16 = test_textureLoad_3d(ZeroValueint3(), 0);
+    const float4 _e19 = test_textureLoad_multisampled_2d(ZeroValueint2(), 0);
+    test_textureStore_1d(0, ZeroValuefloat4());
+    test_textureStore_2d(ZeroValueint2(), ZeroValuefloat4());
+    test_textureStore_2d_array_u(ZeroValueint2(), 0u, ZeroValuefloat4());
+    test_textureStore_2d_array_s(ZeroValueint2(), 0, ZeroValuefloat4());
+    test_textureStore_3d(ZeroValueint3(), ZeroValuefloat4());
+    return float4(0.0, 0.0, 0.0, 0.0);
+}
@@ -0,0 +1,114 @@
+RWByteAddressBuffer globals : register(u0);
+
+float index_array(int i)
+{
+    float _e4 = asfloat(globals.Load(min(uint(i), 9u)*4+0));
+    return _e4;
+}
+
+uint NagaBufferLengthRW(RWByteAddressBuffer buffer)
+{
+    uint ret;
+    buffer.GetDimensions(ret);
+    return ret;
+}
+
+float index_dynamic_array(int i_1)
+{
+    float _e4 = asfloat(globals.Load(min(uint(i_1), ((NagaBufferLengthRW(globals) - 112) / 4) - 1u)*4+112));
+    return _e4;
+}
+
+float index_vector(int i_2)
+{
+    float _e4 = asfloat(globals.Load(min(uint(i_2), 3u)*4+48));
+    return _e4;
+}
+
+float index_vector_by_value(float4 v, int i_3)
+{
+    return v[min(uint(i_3), 3u)];
+}
+
+float4 index_matrix(int i_4)
+{
+    float4 _e4 = asfloat(globals.Load4(min(uint(i_4), 2u)*16+64));
+    return _e4;
+}
+
+float index_twice(int i_5, int j)
+{
+    float _e6 = asfloat(globals.Load(min(uint(j), 3u)*4+min(uint(i_5), 2u)*16+64));
+    return _e6;
+}
+
+float index_expensive(int i_6)
+{
+    float _e11 = asfloat(globals.Load(min(uint(int((sin((float(i_6) / 100.0)) * 100.0))), 9u)*4+0));
+    return _e11;
+}
+
+float index_in_bounds()
+{
+    float _e3 = asfloat(globals.Load(36+0));
+    float _e7 = asfloat(globals.Load(12+48));
+    float _e13 = asfloat(globals.Load(12+32+64));
+    return ((_e3 + _e7) + _e13);
+}
+
+void set_array(int i_7, float v_1)
+{
+    globals.Store(min(uint(i_7), 9u)*4+0, asuint(v_1));
+    return;
+}
+
+void set_dynamic_array(int i_8, float v_2)
+{
+    globals.Store(min(uint(i_8), ((NagaBufferLengthRW(globals) - 112) / 4) - 1u)*4+112, asuint(v_2));
+    return;
+}
+
+void set_vector(int i_9, float v_3)
+{
+    globals.Store(min(uint(i_9), 3u)*4+48, asuint(v_3));
+    return;
+}
+
+void set_matrix(int i_10, float4 v_4)
+{
+    globals.Store4(min(uint(i_10), 2u)*16+64, asuint(v_4));
+    return;
+}
+
+void set_index_twice(int i_11, int j_1, float v_5)
+{
+    globals.Store(min(uint(j_1), 3u)*4+min(uint(i_11), 2u)*16+64, asuint(v_5));
+    return;
+}
+
+void set_expensive(int i_12, float v_6)
+{
+    globals.Store(min(uint(int((sin((float(i_12) / 100.0)) * 100.0))), 9u)*4+0, asuint(v_6));
+    return;
+}
+
+void set_in_bounds(float v_7)
+{
+    globals.Store(36+0, asuint(v_7));
+    globals.Store(12+48, asuint(v_7));
+    globals.Store(12+32+64, asuint(v_7));
+    return;
+}
+
+float index_dynamic_array_constant_index()
+{
+    float _e3 = asfloat(globals.Load(min(1000u, ((NagaBufferLengthRW(globals) - 112) / 4) - 1u)*4+112));
+    return _e3;
+}
+
+void set_dynamic_array_constant_index(float v_8)
+{
+    globals.Store(min(1000u, ((NagaBufferLengthRW(globals) - 112) / 4) - 1u)*4+112, asuint(v_8));
+    return;
+}
+
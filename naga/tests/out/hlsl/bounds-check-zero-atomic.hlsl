@@ -0,0 +1,75 @@
+RWByteAddressBuffer globals : register(u0);
+
+uint fetch_add_atomic()
+{
+    uint _e3; globals.InterlockedAdd(0, 1u, _e3);
+    return _e3;
+}
+
+uint fetch_add_atomic_static_sized_array(int i)
+{
+    uint _e5 = (uint)0;
+    if (uint(i) < 10u) {
+        globals.InterlockedAdd(i*4+4, 1u, _e5);
+    }
+    return _e5;
+}
+
+uint NagaBufferLengthRW(RWByteAddressBuffer buffer)
+{
+    uint ret;
+    buffer.GetDimensions(ret);
+    return ret;
+}
+
+uint fetch_add_atomic_dynamic_sized_array(int i_1)
+{
+    uint _e5 = (uint)0;
+    if (uint(i_1) < ((NagaBufferLengthRW(globals) - 44) / 4)) {
+        globals.InterlockedAdd(i_1*4+44, 1u, _e5);
+    }
+    return _e5;
+}
+
+uint exchange_atomic()
+{
+    uint _e3; globals.InterlockedExchange(0, 1u, _e3);
+    return _e3;
+}
+
+uint exchange_atomic_static_sized_array(int i_2)
+{
+    uint _e5 = (uint)0;
+    if (uint(i_2) < 10u) {
+        globals.InterlockedExchange(i_2*4+4, 1u, _e5);
+    }
+    return _e5;
+}
+
+uint exchange_atomic_dynamic_sized_array(int i_3)
+{
+    uint _e5 = (uint)0;
+    if (uint(i_3) < ((NagaBufferLengthRW(globals) - 44) / 4)) {
+        globals.InterlockedExchange(i_3*4+44, 1u, _e5);
+    }
+    return _e5;
+}
+
+uint fetch_add_atomic_dynamic_sized_array_static_index()
+{
+    uint _e4 = (uint)0;
+    if (1000u < ((NagaBufferLengthRW(globals) - 44) / 4)) {
+        globals.InterlockedAdd(4000+44, 1u, _e4);
+    }
+    return _e4;
+}
+
+uint exchange_atomic_dynamic_sized_array_static_index()
+{
+    uint _e4 = (uint)0;
+    if (1000u < ((NagaBufferLengthRW(globals) - 44) / 4)) {
+        globals.InterlockedExchange(4000+44, 1u, _e4);
+    }
+    return _e4;
+}
+
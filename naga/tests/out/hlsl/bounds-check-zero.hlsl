@@ -0,0 +1,155 @@
+RWByteAddressBuffer globals : register(u0);
+
+float index_array(int i)
+{
+    float _e4 = (float)0;
+    if (uint(i) < 10u) {
+        _e4 = asfloat(globals.Load(i*4+0));
+    }
+    return _e4;
+}
+
+uint NagaBufferLengthRW(RWByteAddressBuffer buffer)
+{
+    uint ret;
+    buffer.GetDimensions(ret);
+    return ret;
+}
+
+float index_dynamic_array(int i_1)
+{
+    float _e4 = (float)0;
+    if (uint(i_1) < ((NagaBufferLengthRW(globals) - 112) / 4)) {
+        _e4 = asfloat(globals.Load(i_1*4+112));
+    }
+    return _e4;
+}
+
+float index_vector(int i_2)
+{
+    float _e4 = (float)0;
+    if (uint(i_2) < 4u) {
+        _e4 = asfloat(globals.Load(i_2*4+48));
+    }
+    return _e4;
+}
+
+float index_vector_by_value(float4 v, int i_3)
+{
+    float _e2 = (float)0;
+    if (uint(i_3) < 4u) {
+        _e2 = v[i_3];
+    }
+    return _e2;
+}
+
+float4 index_matrix(int i_4)
+{
+    float4 _e4 = (float4)0;
+    if (uint(i_4) < 3u) {
+        _e4 = asfloat(globals.Load4(i_4*16+64));
+    }
+    return _e4;
+}
+
+float index_twice(int i_5, int j)
+{
+    float _e6 = (float)0;
+    if (uint(j) < 4u && uint(i_5) < 3u) {
+        _e6 = asfloat(globals.Load(j*4+i_5*16+64));
+    }
+    return _e6;
+}
+
+float index_expensive(int i_6)
+{
+    int _e9 = int((sin((float(i_6) / 100.0)) * 100.0));
+    float _e11 = (float)0;
+    if (uint(_e9) < 10u) {
+        _e11 = asfloat(globals.Load(_e9*4+0));
+    }
+    return _e11;
+}
+
+float index_in_bounds()
+{
+    float _e3 = asfloat(globals.Load(36+0));
+    float _e7 = asfloat(globals.Load(12+48));
+    float _e13 = asfloat(globals.Load(12+32+64));
+    return ((_e3 + _e7) + _e13);
+}
+
+void set_array(int i_7, float v_1)
+{
+    if (uint(i_7) < 10u) {
+        globals.Store(i_7*4+0, asuint(v_1));
+    }
+    return;
+}
+
+void set_dynamic_array(int i_8, float v_2)
+{
+    if (uint(i_8) < ((NagaBufferLengthRW(globals) - 112) / 4)) {
+        globals.Store(i_8*4+112, asuint(v_2));
+    }
+    return;
+}
+
+void set_vector(int i_9, float v_3)
+{
+    if (uint(i_9) < 4u) {
+        globals.Store(i_9*4+48, asuint(v_3));
+    }
+    return;
+}
+
+void set_matrix(int i_10, float4 v_4)
+{
+    if (uint(i_10) < 3u) {
+        globals.Store4(i_10*16+64, asuint(v_4));
+    }
+    return;
+}
+
+void set_index_twice(int i_11, int j_1, float v_5)
+{
+    if (uint(j_1) < 4u && uint(i_11) < 3u) {
+        globals.Store(j_1*4+i_11*16+64, asuint(v_5));
+    }
+    return;
+}
+
+void set_expensive(int i_12, float v_6)
+{
+    int _e10 = int((sin((float(i_12) / 100.0)) * 100.0));
+    if (uint(_e10) < 10u) {
+        globals.Store(_e10*4+0, asuint(v_6));
+    }
+    return;
+}
+
+void set_in_bounds(float v_7)
+{
+    globals.Store(36+0, asuint(v_7));
+    globals.Store(12+48, asuint(v_7));
+    globals.Store(12+32+64, asuint(v_7));
+    return;
+}
+
+float index_dynamic_array_constant_index()
+{
+    float _e3 = (float)0;
+    if (1000u < ((NagaBufferLengthRW(globals) - 112) / 4)) {
+        _e3 = asfloat(globals.Load(4000+112));
+    }
+    return _e3;
+}
+
+void set_dynamic_array_constant_index(float v_8)
+{
+    if (1000u < ((NagaBufferLengthRW(globals) - 112) / 4)) {
+        globals.Store(4000+112, asuint(v_8));
+    }
+    return;
+}
+
@@ -29,11 +29,18 @@ uint collatz_iterations(uint n_base)
     return _e23;
 }
 
+uint NagaBufferLengthRW(RWByteAddressBuffer buffer)
+{
+    uint ret;
+    buffer.GetDimensions(ret);
+    return ret;
+}
+
 [numthreads(1, 1, 1)]
 void main(uint3 global_id : SV_DispatchThreadID)
 {
-    uint _e9 = asuint(v_indices.Load(global_id.x*4+0));
+    uint _e9 = asuint(v_indices.Load(min(uint(global_id.x), ((NagaBufferLengthRW(v_indices) - 0) / 4) - 1u)*4+0));
     const uint _e10 = collatz_iterations(_e9);
-    v_indices.Store(global_id.x*4+0, asuint(_e10));
+    v_indices.Store(min(uint(global_id.x), ((NagaBufferLengthRW(v_indices) - 0) / 4) - 1u)*4+0, asuint(_e10));
     return;
 }
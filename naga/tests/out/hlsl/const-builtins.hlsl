@@ -0,0 +1,81 @@
+struct _modf_result_f32_ {
+    float fract;
+    float whole;
+};
+
+struct _modf_result_vec2_f32_ {
+    float2 fract;
+    float2 whole;
+};
+
+struct _frexp_result_f32_ {
+    float fract;
+    int exp_;
+};
+
+struct _frexp_result_vec2_f32_ {
+    float2 fract;
+    int2 exp_;
+};
+
+_modf_result_f32_ naga_modf(float arg) {
+    float other;
+    _modf_result_f32_ result;
+    result.fract = modf(arg, other);
+    result.whole = other;
+    return result;
+}
+
+_modf_result_vec2_f32_ naga_modf(float2 arg) {
+    float2 other;
+    _modf_result_vec2_f32_ result;
+    result.fract = modf(arg, other);
+    result.whole = other;
+    return result;
+}
+
+_frexp_result_f32_ naga_frexp(float arg) {
+    float other;
+    _frexp_result_f32_ result;
+    result.fract = sign(arg) * frexp(arg, other);
+    result.exp_ = other;
+    return result;
+}
+
+_frexp_result_vec2_f32_ naga_frexp(float2 arg) {
+    float2 other;
+    _frexp_result_vec2_f32_ result;
+    result.fract = sign(arg) * frexp(arg, other);
+    result.exp_ = other;
+    return result;
+}
+
+_modf_result_vec2_f32_ Construct_modf_result_vec2_f32_(float2 arg0, float2 arg1) {
+    _modf_result_vec2_f32_ ret = (_modf_result_vec2_f32_)0;
+    ret.fract = arg0;
+    ret.whole = arg1;
+    return ret;
+}
+
+_frexp_result_f32_ Construct_frexp_result_f32_(float arg0, int arg1) {
+    _frexp_result_f32_ ret = (_frexp_result_f32_)0;
+    ret.fract = arg0;
+    ret.exp_ = arg1;
+    return ret;
+}
+
+static const float2x3 m = float2x3(float3(1.0, 2.0, 3.0), float3(4.0, 5.0, 6.0));
+static const _modf_result_vec2_f32_ modf_result = Construct_modf_result_vec2_f32_(float2(0.5, -0.25), float2(1.0, -0.0));
+static const _frexp_result_f32_ frexp_result = Construct_frexp_result_f32_(0.625, 2);
+static const uint packed = 1006647296u;
+static const uint4 unpacked = uint4(0u, 56u, 0u, 60u);
+static const float3x2 transposed = float3x2(float2(1.0, 4.0), float2(2.0, 5.0), float2(3.0, 6.0));
+static const float selected = 0.625;
+
+[numthreads(1, 1, 1)]
+void main()
+{
+    int3 local_select = int3(2, 1, 2);
+    float2 local_bitcast = float2(1.0, 2.0);
+    float4 x = float4(2.625, 4.625, 6.625, 6.625);
+}
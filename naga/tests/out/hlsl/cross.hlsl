@@ -1,5 +1,7 @@
 [numthreads(1, 1, 1)]
 void main()
 {
-    float3 a = cross(float3(0.0, 1.0, 2.0), float3(0.0, 1.0, 2.0));
+    float3 a = float3(0.0, 0.0, 0.0);
+    float3 v = float3(0.0, 1.0, 2.0);
+    float3 b = cross(v, v);
 }
@@ -0,0 +1,244 @@
+struct NagaConstants {
+    int first_vertex;
+    int first_instance;
+    uint other;
+};
+ConstantBuffer<NagaConstants> _NagaConstants: register(b0, space1);
+
+struct UniformCompatible {
+    uint val_u32_;
+    int val_i32_;
+    float val_f32_;
+    half val_f16_;
+    half _pad4_0;
+    half2 val_f16_2_;
+    int _pad5_0;
+    half3 val_f16_3_;
+    half _pad6_0;
+    half4 val_f16_4_;
+    half final_value;
+    half _pad8_0;
+    half2 val_mat2x2_f16__0; half2 val_mat2x2_f16__1;
+    int _pad9_0;
+    row_major half2x4 val_mat2x4_f16_;
+    half2 val_mat3x2_f16__0; half2 val_mat3x2_f16__1; half2 val_mat3x2_f16__2;
+    int _pad11_0;
+    row_major half4x4 val_mat4x4_f16_;
+};
+
+struct StorageCompatible {
+    half val_f16_array_2_[2];
+};
+
+struct LayoutTest {
+    half scalar1_;
+    half scalar2_;
+    int _pad2_0;
+    half3 v3_;
+    half tuck_in;
+    half scalar4_;
+    half _pad5_0;
+    uint larger;
+};
+
+static const half constant_variable = 15.203125h;
+
+static half private_variable = 1.0h;
+cbuffer input_uniform : register(b0) { UniformCompatible input_uniform; }
+ByteAddressBuffer input_storage : register(t1);
+ByteAddressBuffer input_arrays : register(t2);
+RWByteAddressBuffer output : register(u3);
+RWByteAddressBuffer output_arrays : register(u4);
+
+half2x2 GetMatval_mat2x2_f16_OnUniformCompatible(UniformCompatible obj) {
+    return half2x2(obj.val_mat2x2_f16__0, obj.val_mat2x2_f16__1);
+}
+
+void SetMatval_mat2x2_f16_OnUniformCompatible(UniformCompatible obj, half2x2 mat) {
+    obj.val_mat2x2_f16__0 = mat[0];
+    obj.val_mat2x2_f16__1 = mat[1];
+}
+
+void SetMatVecval_mat2x2_f16_OnUniformCompatible(UniformCompatible obj, half2 vec, uint mat_idx) {
+    switch(mat_idx) {
+    case 0: { obj.val_mat2x2_f16__0 = vec; break; }
+    case 1: { obj.val_mat2x2_f16__1 = vec; break; }
+    }
+}
+
+void SetMatScalarval_mat2x2_f16_OnUniformCompatible(UniformCompatible obj, half scalar, uint mat_idx, uint vec_idx) {
+    switch(mat_idx) {
+    case 0: { obj.val_mat2x2_f16__0[vec_idx] = scalar; break; }
+    case 1: { obj.val_mat2x2_f16__1[vec_idx] = scalar; break; }
+    }
+}
+
+half3x2 GetMatval_mat3x2_f16_OnUniformCompatible(UniformCompatible obj) {
+    return half3x2(obj.val_mat3x2_f16__0, obj.val_mat3x2_f16__1, obj.val_mat3x2_f16__2);
+}
+
+void SetMatval_mat3x2_f16_OnUniformCompatible(UniformCompatible obj, half3x2 mat) {
+    obj.val_mat3x2_f16__0 = mat[0];
+    obj.val_mat3x2_f16__1 = mat[1];
+    obj.val_mat3x2_f16__2 = mat[2];
+}
+
+void SetMatVecval_mat3x2_f16_OnUniformCompatible(UniformCompatible obj, half2 vec, uint mat_idx) {
+    switch(mat_idx) {
+    case 0: { obj.val_mat3x2_f16__0 = vec; break; }
+    case 1: { obj.val_mat3x2_f16__1 = vec; break; }
+    case 2: { obj.val_mat3x2_f16__2 = vec; break; }
+    }
+}
+
+void SetMatScalarval_mat3x2_f16_OnUniformCompatible(UniformCompatible obj, half scalar, uint mat_idx, uint vec_idx) {
+    switch(mat_idx) {
+    case 0: { obj.val_mat3x2_f16__0[vec_idx] = scalar; break; }
+    case 1: { obj.val_mat3x2_f16__1[vec_idx] = scalar; break; }
+    case 2: { obj.val_mat3x2_f16__2[vec_idx] = scalar; break; }
+    }
+}
+
+typedef half ret_Constructarray2_half_[2];
+ret_Constructarray2_half_ Constructarray2_half_(half arg0, half arg1) {
+    half ret[2] = { arg0, arg1 };
+    return ret;
+}
+
+half f16_function(half x)
+{
+    half val = 15.203125h;
+
+    half _e4 = val;
+    val = (_e4 + -33344.0h);
+    half _e6 = val;
+    half _e9 = val;
+    val = (_e9 + (_e6 + 5.0h));
+    float _e13 = input_uniform.val_f32_;
+    half _e14 = val;
+    half _e18 = val;
+    val = (_e18 + half((_e13 + float(_e14))));
+    half _e22 = input_uniform.val_f16_;
+    half _e25 = val;
+    val = (_e25 + (_e22).xxx.z);
+    half _e31 = input_uniform.val_f16_;
+    half _e34 = input_storage.Load<half>(12);
+    output.Store(12, (_e31 + _e34));
+    half2 _e40 = input_uniform.val_f16_2_;
+    half2 _e43 = input_storage.Load<half2>(16);
+    output.Store(16, (_e40 + _e43));
+    half3 _e49 = input_uniform.val_f16_3_;
+    half3 _e52 = input_storage.Load<half3>(24);
+    output.Store(24, (_e49 + _e52));
+    half4 _e58 = input_uniform.val_f16_4_;
+    half4 _e61 = input_storage.Load<half4>(32);
+    output.Store(32, (_e58 + _e61));
+    half2x2 _e67 = GetMatval_mat2x2_f16_OnUniformCompatible(input_uniform);
+    half2x2 _e70 = half2x2(input_storage.Load<half2>(44+0), input_storage.Load<half2>(44+4));
+    {
+        half2x2 _value2 = (_e67 + _e70);
+        output.Store(44+0, _value2[0]);
+        output.Store(44+4, _value2[1]);
+    }
+    half2x4 _e76 = input_uniform.val_mat2x4_f16_;
+    half2x4 _e79 = half2x4(input_storage.Load<half4>(56+0), input_storage.Load<half4>(56+8));
+    {
+        half2x4 _value2 = (_e76 + _e79);
+        output.Store(56+0, _value2[0]);
+        output.Store(56+8, _value2[1]);
+    }
+    half3x2 _e85 = GetMatval_mat3x2_f16_OnUniformCompatible(input_uniform);
+    half3x2 _e88 = half3x2(input_storage.Load<half2>(72+0), input_storage.Load<half2>(72+4), input_storage.Load<half2>(72+8));
+    {
+        half3x2 _value2 = (_e85 + _e88);
+        output.Store(72+0, _value2[0]);
+        output.Store(72+4, _value2[1]);
+        output.Store(72+8, _value2[2]);
+    }
+    half4x4 _e94 = input_uniform.val_mat4x4_f16_;
+    half4x4 _e97 = half4x4(input_storage.Load<half4>(88+0), input_storage.Load<half4>(88+8), input_storage.Load<half4>(88+16), input_storage.Load<half4>(88+24));
+    {
+        half4x4 _value2 = (_e94 + _e97);
+        output.Store(88+0, _value2[0]);
+        output.Store(88+8, _value2[1]);
+        output.Store(88+16, _value2[2]);
+        output.Store(88+24, _value2[3]);
+    }
+    half _e103[2] = Constructarray2_half_(input_arrays.Load<half>(0+0), input_arrays.Load<half>(0+2));
+    {
+        half _value2[2] = _e103;
+        output_arrays.Store(0+0, _value2[0]);
+        output_arrays.Store(0+2, _value2[1]);
+    }
+    half _e104 = val;
+    half _e106 = val;
+    val = (_e106 + abs(_e104));
+    half _e108 = val;
+    half _e109 = val;
+    half _e110 = val;
+    half _e112 = val;
+    val = (_e112 + clamp(_e108, _e109, _e110));
+    half _e114 = val;
+    half _e116 = val;
+    half _e119 = val;
+    val = (_e119 + dot((_e114).xx, (_e116).xx));
+    half _e121 = val;
+    half _e122 = val;
+    half _e124 = val;
+    val = (_e124 + max(_e121, _e122));
+    half _e126 = val;
+    half _e127 = val;
+    half _e129 = val;
+    val = (_e129 + min(_e126, _e127));
+    half _e131 = val;
+    half _e133 = val;
+    val = (_e133 + sign(_e131));
+    half _e136 = val;
+    val = (_e136 + 1.0h);
+    half2 _e140 = input_uniform.val_f16_2_;
+    float2 float_vec2_ = float2(_e140);
+    output.Store(16, half2(float_vec2_));
+    half3 _e147 = input_uniform.val_f16_3_;
+    float3 float_vec3_ = float3(_e147);
+    output.Store(24, half3(float_vec3_));
+    half4 _e154 = input_uniform.val_f16_4_;
+    float4 float_vec4_ = float4(_e154);
+    output.Store(32, half4(float_vec4_));
+    half2x2 _e163 = GetMatval_mat2x2_f16_OnUniformCompatible(input_uniform);
+    {
+        half2x2 _value2 = half2x2(float2x2(_e163));
+        output.Store(44+0, _value2[0]);
+        output.Store(44+4, _value2[1]);
+    }
+    half2x4 _e170 = input_uniform.val_mat2x4_f16_;
+    {
+        half2x4 _value2 = half2x4(float2x4(_e170));
+        output.Store(56+0, _value2[0]);
+        output.Store(56+8, _value2[1]);
+    }
+    half3x2 _e177 = GetMatval_mat3x2_f16_OnUniformCompatible(input_uniform);
+    {
+        half3x2 _value2 = half3x2(float3x2(_e177));
+        output.Store(72+0, _value2[0]);
+        output.Store(72+4, _value2[1]);
+        output.Store(72+8, _value2[2]);
+    }
+    half4x4 _e184 = input_uniform.val_mat4x4_f16_;
+    {
+        half4x4 _value2 = half4x4(float4x4(_e184));
+        output.Store(88+0, _value2[0]);
+        output.Store(88+8, _value2[1]);
+        output.Store(88+16, _value2[2]);
+        output.Store(88+24, _value2[3]);
+    }
+    half _e187 = val;
+    return _e187;
+}
+
+[numthreads(1, 1, 1)]
+void main()
+{
+    const half _e3 = f16_function(2.0h);
+    output.Store(40, _e3);
+    return;
+}
@@ -14,8 +14,7 @@ int test_integer_dot_product()
     uint3 a_3_ = (1u).xxx;
     uint3 b_3_ = (1u).xxx;
     uint c_3_ = dot(a_3_, b_3_);
-    int c_4_ = dot((4).xxxx, (2).xxxx);
-    return c_4_;
+    return 32;
 }
 
 [numthreads(1, 1, 1)]
@@ -90,7 +90,7 @@ void test_msl_packed_vec3_()
     alignment.Store(0+0, asuint(1.0));
     alignment.Store(0+0, asuint(2.0));
     int _e16 = idx;
-    alignment.Store(_e16*4+0, asuint(3.0));
+    alignment.Store(min(uint(_e16), 2u)*4+0, asuint(3.0));
     FooStruct data = ConstructFooStruct(asfloat(alignment.Load3(0)), asfloat(alignment.Load(12)));
     float3 l0_ = data.v3_;
     float2 l1_ = data.v3_.zx;
@@ -126,7 +126,7 @@ void main(uint3 __local_invocation_id : SV_GroupThreadID)
     float3x2 _e16 = ((float3x2)global_mat);
     float3 _e18 = global_vec;
     wg[6] = mul(_e18, _e16).x;
-    float _e26 = asfloat(dummy.Load(4+8));
+    float _e26 = asfloat(dummy.Load(4+min(1u, ((NagaBufferLength(dummy) - 0) / 8) - 1u)*8));
     wg[5] = _e26;
     float _e32 = float_vecs[0].w;
     wg[4] = _e32;
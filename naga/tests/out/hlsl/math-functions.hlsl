@@ -63,8 +63,25 @@ _frexp_result_vec4_f32_ naga_frexp(float4 arg) {
     return result;
 }
 
-int2 ZeroValueint2() {
-    return (int2)0;
+_modf_result_f32_ Construct_modf_result_f32_(float arg0, float arg1) {
+    _modf_result_f32_ ret = (_modf_result_f32_)0;
+    ret.fract = arg0;
+    ret.whole = arg1;
+    return ret;
+}
+
+_modf_result_vec2_f32_ Construct_modf_result_vec2_f32_(float2 arg0, float2 arg1) {
+    _modf_result_vec2_f32_ ret = (_modf_result_vec2_f32_)0;
+    ret.fract = arg0;
+    ret.whole = arg1;
+    return ret;
+}
+
+_frexp_result_f32_ Construct_frexp_result_f32_(float arg0, int arg1) {
+    _frexp_result_f32_ ret = (_frexp_result_f32_)0;
+    ret.fract = arg0;
+    ret.exp_ = arg1;
+    return ret;
 }
 
 void main()
@@ -78,7 +95,6 @@ void main()
     float4 g = refract(v, v, 1.0);
     int4 sign_b = int4(-1, -1, -1, -1);
     float4 sign_d = float4(-1.0, -1.0, -1.0, -1.0);
-    int const_dot = dot(ZeroValueint2(), ZeroValueint2());
     int2 flb_b = int2(-1, -1);
     uint2 flb_c = uint2(0u, 0u);
     int2 ftb_c = int2(0, 0);
@@ -89,20 +105,11 @@ void main()
     int2 ctz_h = int2(0, 0);
     int2 clz_c = int2(0, 0);
     uint2 clz_d = uint2(31u, 31u);
-    float lde_a = ldexp(1.0, 2);
-    float2 lde_b = ldexp(float2(1.0, 2.0), int2(3, 4));
-    _modf_result_f32_ modf_a = naga_modf(1.5);
-    float modf_b = naga_modf(1.5).fract;
-    float modf_c = naga_modf(1.5).whole;
-    _modf_result_vec2_f32_ modf_d = naga_modf(float2(1.5, 1.5));
-    float modf_e = naga_modf(float4(1.5, 1.5, 1.5, 1.5)).whole.x;
-    float modf_f = naga_modf(float2(1.5, 1.5)).fract.y;
-    _frexp_result_f32_ frexp_a = naga_frexp(1.5);
-    float frexp_b = naga_frexp(1.5).fract;
-    int frexp_c = naga_frexp(1.5).exp_;
-    int frexp_d = naga_frexp(float4(1.5, 1.5, 1.5, 1.5)).exp_.x;
-    float quantizeToF16_a = f16tof32(f32tof16(1.0));
-    float2 quantizeToF16_b = f16tof32(f32tof16(float2(1.0, 1.0)));
-    float3 quantizeToF16_c = f16tof32(f32tof16(float3(1.0, 1.0, 1.0)));
-    float4 quantizeToF16_d = f16tof32(f32tof16(float4(1.0, 1.0, 1.0, 1.0)));
+    float2 lde_b = float2(8.0, 32.0);
+    _modf_result_f32_ modf_a = Construct_modf_result_f32_(0.5, 1.0);
+    _modf_result_vec2_f32_ modf_d = Construct_modf_result_vec2_f32_(float2(0.5, 0.5), float2(1.0, 1.0));
+    _frexp_result_f32_ frexp_a = Construct_frexp_result_f32_(0.75, 1);
+    float2 quantizeToF16_b = float2(1.0, 1.0);
+    float3 quantizeToF16_c = float3(1.0, 1.0, 1.0);
+    float4 quantizeToF16_d = float4(1.0, 1.0, 1.0, 1.0);
 }
@@ -0,0 +1,139 @@
+struct TaskPayload {
+    float4 color_mask;
+    bool visible;
+    int _end_pad_0;
+    int _end_pad_1;
+    int _end_pad_2;
+};
+
+struct TaskOutput {
+    uint3 size;
+};
+
+struct VertexOutput {
+    float4 position : SV_Position;
+    float4 color : LOC0;
+};
+
+struct PrimitiveOutput {
+    uint3 indices_;
+    bool cull : SV_CullPrimitive;
+    nointerpolation float4 color_mask : LOC1;
+};
+
+struct PrimitiveInput {
+    nointerpolation float4 color_mask : LOC1;
+};
+
+typedef float4 ret_Constructarray3_float4_[3];
+ret_Constructarray3_float4_ Constructarray3_float4_(float4 arg0, float4 arg1, float4 arg2) {
+    float4 ret[3] = { arg0, arg1, arg2 };
+    return ret;
+}
+
+static const float4 positions[3] = Constructarray3_float4_(float4(0.0, 1.0, 0.0, 1.0), float4(-1.0, -1.0, 0.0, 1.0), float4(1.0, -1.0, 0.0, 1.0));
+
+groupshared TaskPayload task_payload;
+groupshared float workgroup_data;
+
+struct MeshVertexOutput_ms_main {
+    float4 color : LOC0;
+    float4 position : SV_Position;
+};
+
+struct MeshPrimitiveOutput_ms_main {
+    nointerpolation float4 color_mask : LOC1;
+    bool cull : SV_CullPrimitive;
+};
+
+struct FragmentInput_fs_main {
+    float4 color_1 : LOC0;
+    nointerpolation float4 color_mask_1 : LOC1;
+    float4 position_1 : SV_Position;
+};
+
+TaskOutput ConstructTaskOutput(uint3 arg0) {
+    TaskOutput ret = (TaskOutput)0;
+    ret.size = arg0;
+    return ret;
+}
+
+[numthreads(1, 1, 1)]
+void ts_main(uint3 __local_invocation_id : SV_GroupThreadID)
+{
+    if (all(__local_invocation_id == uint3(0u, 0u, 0u))) {
+        workgroup_data = (float)0;
+    }
+    GroupMemoryBarrierWithGroupSync();
+    workgroup_data = 1.0;
+    task_payload.color_mask = float4(1.0, 1.0, 0.0, 1.0);
+    task_payload.visible = true;
+    const TaskOutput task_output = ConstructTaskOutput(uint3(3u, 1u, 1u));
+    DispatchMesh(task_output.size.x, task_output.size.y, task_output.size.z, task_payload);
+    return;
+}
+
+VertexOutput ConstructVertexOutput(float4 arg0, float4 arg1) {
+    VertexOutput ret = (VertexOutput)0;
+    ret.position = arg0;
+    ret.color = arg1;
+    return ret;
+}
+
+PrimitiveOutput ConstructPrimitiveOutput(uint3 arg0, bool arg1, float4 arg2) {
+    PrimitiveOutput ret = (PrimitiveOutput)0;
+    ret.indices_ = arg0;
+    ret.cull = arg1;
+    ret.color_mask = arg2;
+    return ret;
+}
+
+[outputtopology("triangle")]
+[numthreads(1, 1, 1)]
+void ms_main(uint index : SV_GroupIndex, uint3 id : SV_DispatchThreadID, uint3 __local_invocation_id : SV_GroupThreadID, in payload TaskPayload task_payload, out vertices MeshVertexOutput_ms_main meshvertexoutput_ms_main[3], out indices uint3 indices_[1], out primitives MeshPrimitiveOutput_ms_main meshprimitiveoutput_ms_main[1])
+{
+    if (all(__local_invocation_id == uint3(0u, 0u, 0u))) {
+        workgroup_data = (float)0;
+    }
+    GroupMemoryBarrierWithGroupSync();
+    uint i = 0u;
+
+    SetMeshOutputCounts(3u, 1u);
+    workgroup_data = 2.0;
+    bool loop_init = true;
+    while(true) {
+        if (!loop_init) {
+            uint _e22 = i;
+            i = (_e22 + 1u);
+        }
+        loop_init = false;
+        uint _e8 = i;
+        if ((_e8 < 3u)) {
+        } else {
+            break;
+        }
+        {
+            uint _e11 = i;
+            uint _e13 = i;
+            const VertexOutput vertexoutput = ConstructVertexOutput(positions[min(uint(_e13), 2u)], float4(0.0, 1.0, 0.0, 1.0));
+            const uint mesh_index = _e11;
+            const MeshVertexOutput_ms_main meshvertexoutput_ms_main_1 = { vertexoutput.color, vertexoutput.position };
+            meshvertexoutput_ms_main[mesh_index] = meshvertexoutput_ms_main_1;
+        }
+    }
+    bool _e31 = task_payload.visible;
+    float4 _e35 = task_payload.color_mask;
+    const PrimitiveOutput primitiveoutput = ConstructPrimitiveOutput(uint3(0u, 1u, 2u), !(_e31), _e35);
+    const uint mesh_index_1 = 0u;
+    indices_[mesh_index_1] = primitiveoutput.indices_;
+    const MeshPrimitiveOutput_ms_main meshprimitiveoutput_ms_main_1 = { primitiveoutput.color_mask, primitiveoutput.cull };
+    meshprimitiveoutput_ms_main[mesh_index_1] = meshprimitiveoutput_ms_main_1;
+    return;
+}
+
+float4 fs_main(FragmentInput_fs_main fragmentinput_fs_main) : SV_Target0
+{
+    VertexOutput vertex = { fragmentinput_fs_main.position_1, fragmentinput_fs_main.color_1 };
+    PrimitiveInput primitive = { fragmentinput_fs_main.color_mask_1 };
+    return (vertex.color * primitive.color_mask);
+}
@@ -7,13 +7,17 @@ float4 builtins()
 {
     int s1_ = (true ? 1 : 0);
     float4 s2_ = (true ? v_f32_one : v_f32_zero);
-    float4 s3_ = (bool4(false, false, false, false) ? v_f32_zero : v_f32_one);
-    float4 m1_ = lerp(v_f32_zero, v_f32_one, v_f32_half);
-    float4 m2_ = lerp(v_f32_zero, v_f32_one, 0.1);
-    float b1_ = asfloat(1);
-    float4 b2_ = asfloat(v_i32_one);
+    float4 s3_ = float4(1.0, 1.0, 1.0, 1.0);
+    float4 s4_ = ((!(true)).xxxx ? v_f32_zero : v_f32_one);
+    float4 m1_ = float4(0.5, 0.5, 0.5, 0.5);
+    float4 m2_ = float4(0.1, 0.1, 0.1, 0.1);
+    float4 m3_ = lerp(v_f32_zero, v_f32_one, v_f32_half);
+    float4 m4_ = lerp(v_f32_zero, v_f32_one, 0.1);
+    float4 b2_ = float4(1e-45, 1e-45, 1e-45, 1e-45);
+    float b3_ = asfloat(v_i32_one.x);
+    float4 b4_ = asfloat(v_i32_one);
     int4 v_i32_zero = int4(0, 0, 0, 0);
-    return (((((float4(((s1_).xxxx + v_i32_zero)) + s2_) + m1_) + m2_) + (b1_).xxxx) + b2_);
+    return (((((float4(((s1_).xxxx + v_i32_zero)) + s2_) + m1_) + m2_) + (1e-45).xxxx) + b2_);
 }
 
 float4 splat(float m, int n)
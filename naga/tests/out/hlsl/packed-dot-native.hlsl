@@ -0,0 +1,13 @@
+uint test_packed_integer_dot_product()
+{
+    int c = dot4add_i8packed(1u, 2u, 0);
+    uint d = dot4add_u8packed(1u, 2u, 0u);
+    return (d + uint(c));
+}
+
+[numthreads(1, 1, 1)]
+void main()
+{
+    const uint _e0 = test_packed_integer_dot_product();
+    return;
+}
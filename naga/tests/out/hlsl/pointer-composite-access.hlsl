@@ -0,0 +1,48 @@
+struct Light {
+    float3 position;
+    float intensity;
+};
+
+struct Scene {
+    Light lights[4];
+    float4 ambient;
+};
+
+float brightness(inout Scene scene_1, int index)
+{
+    float _e5 = scene_1.lights[min(uint(index), 3u)].intensity;
+    float _e8 = scene_1.ambient.w;
+    float _e12 = scene_1.lights[min(uint(index), 3u)].position.x;
+    return ((_e5 * _e8) + _e12);
+}
+
+void dim(inout Scene scene_2)
+{
+    float _e5 = scene_2.lights[0].intensity;
+    scene_2.lights[0].intensity = (_e5 * 0.5);
+    scene_2.ambient.w = 0.0;
+    scene_2.lights[1].position = float3(1.0, 2.0, 3.0);
+    return;
+}
+
+typedef float ret_Constructarray2_float_[2];
+ret_Constructarray2_float_ Constructarray2_float_(float arg0, float arg1) {
+    float ret[2] = { arg0, arg1 };
+    return ret;
+}
+
+[numthreads(1, 1, 1)]
+void main()
+{
+    Scene scene = (Scene)0;
+    float result[2] = (float[2])0;
+
+    dim(scene);
+    const float _e2 = brightness(scene, 0);
+    const float _e4 = brightness(scene, 1);
+    float total = (_e2 + _e4);
+    result = Constructarray2_float_(total, 0.0);
+    float _e11 = result[0];
+    result[1] = (_e11 * 2.0);
+    return;
+}
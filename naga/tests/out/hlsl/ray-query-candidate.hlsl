@@ -0,0 +1,165 @@
+struct Sphere {
+    float3 center_;
+    float radius;
+};
+
+struct Output {
+    uint hit;
+    float t;
+};
+
+struct RayDesc_ {
+    uint flags;
+    uint cull_mask;
+    float tmin;
+    float tmax;
+    float3 origin;
+    int _pad5_0;
+    float3 dir;
+    int _end_pad_0;
+};
+
+struct RayIntersection {
+    uint kind;
+    float t;
+    uint instance_custom_index;
+    uint instance_id;
+    uint sbt_record_offset;
+    uint geometry_index;
+    uint primitive_index;
+    float2 barycentrics;
+    bool front_face;
+    int _pad9_0;
+    int _pad9_1;
+    row_major float4x3 object_to_world;
+    int _pad10_0;
+    row_major float4x3 world_to_object;
+    int _end_pad_0;
+};
+
+RayDesc naga_ray_desc_from(RayDesc_ arg) {
+    RayDesc ret = (RayDesc)0;
+    ret.Origin = arg.origin;
+    ret.TMin = arg.tmin;
+    ret.Direction = arg.dir;
+    ret.TMax = arg.tmax;
+    return ret;
+}
+
+RayIntersection naga_committed_intersection(RayQuery<RAY_FLAG_NONE> rq) {
+    RayIntersection ret = (RayIntersection)0;
+    ret.kind = (uint)rq.CommittedStatus();
+    if (rq.CommittedStatus() != COMMITTED_NOTHING) {
+        ret.t = rq.CommittedRayT();
+        ret.instance_custom_index = rq.CommittedInstanceID();
+        ret.instance_id = rq.CommittedInstanceIndex();
+        ret.sbt_record_offset = rq.CommittedInstanceContributionToHitGroupIndex();
+        ret.geometry_index = rq.CommittedGeometryIndex();
+        ret.primitive_index = rq.CommittedPrimitiveIndex();
+        ret.object_to_world = rq.CommittedObjectToWorld4x3();
+        ret.world_to_object = rq.CommittedWorldToObject4x3();
+        if (rq.CommittedStatus() == COMMITTED_TRIANGLE_HIT) {
+            ret.barycentrics = rq.CommittedTriangleBarycentrics();
+            ret.front_face = rq.CommittedTriangleFrontFace();
+        }
+    }
+    return ret;
+}
+
+RayIntersection naga_candidate_intersection(RayQuery<RAY_FLAG_NONE> rq) {
+    RayIntersection ret = (RayIntersection)0;
+    ret.kind = rq.CandidateType() == CANDIDATE_NON_OPAQUE_TRIANGLE ? 1u : 4u;
+    ret.instance_custom_index = rq.CandidateInstanceID();
+    ret.instance_id = rq.CandidateInstanceIndex();
+    ret.sbt_record_offset = rq.CandidateInstanceContributionToHitGroupIndex();
+    ret.geometry_index = rq.CandidateGeometryIndex();
+    ret.primitive_index = rq.CandidatePrimitiveIndex();
+    ret.object_to_world = rq.CandidateObjectToWorld4x3();
+    ret.world_to_object = rq.CandidateWorldToObject4x3();
+    if (rq.CandidateType() == CANDIDATE_NON_OPAQUE_TRIANGLE) {
+        ret.t = rq.CandidateTriangleRayT();
+        ret.barycentrics = rq.CandidateTriangleBarycentrics();
+        ret.front_face = rq.CandidateTriangleFrontFace();
+    }
+    return ret;
+}
+
+RaytracingAccelerationStructure acc_struct : register(t0);
+ByteAddressBuffer spheres : register(t1);
+Texture2D<float4> alpha_mask : register(t2);
+SamplerState alpha_sampler : register(s3);
+RWByteAddressBuffer output : register(u4);
+
+float intersect_sphere(Sphere sphere, float3 origin, float3 dir)
+{
+    float3 oc = (origin - sphere.center_);
+    float b = dot(oc, dir);
+    float c = (dot(oc, oc) - (sphere.radius * sphere.radius));
+    float discriminant = ((b * b) - c);
+    if ((discriminant < 0.0)) {
+        return -1.0;
+    }
+    return (-(b) - sqrt(discriminant));
+}
+
+RayDesc_ ConstructRayDesc_(uint arg0, uint arg1, float arg2, float arg3, float3 arg4, float3 arg5) {
+    RayDesc_ ret = (RayDesc_)0;
+    ret.flags = arg0;
+    ret.cull_mask = arg1;
+    ret.tmin = arg2;
+    ret.tmax = arg3;
+    ret.origin = arg4;
+    ret.dir = arg5;
+    return ret;
+}
+
+uint NagaBufferLength(ByteAddressBuffer buffer)
+{
+    uint ret;
+    buffer.GetDimensions(ret);
+    return ret;
+}
+
+Sphere ConstructSphere(float3 arg0, float arg1) {
+    Sphere ret = (Sphere)0;
+    ret.center_ = arg0;
+    ret.radius = arg1;
+    return ret;
+}
+
+[numthreads(1, 1, 1)]
+void main()
+{
+    RayQuery<RAY_FLAG_NONE> rq;
+
+    float3 origin_1 = (0.0).xxx;
+    float3 dir_1 = float3(0.0, 1.0, 0.0);
+    rq.TraceRayInline(acc_struct, ConstructRayDesc_(0u, 255u, 0.1, 100.0, origin_1, dir_1).flags, ConstructRayDesc_(0u, 255u, 0.1, 100.0, origin_1, dir_1).cull_mask, naga_ray_desc_from(ConstructRayDesc_(0u, 255u, 0.1, 100.0, origin_1, dir_1)));
+    while(true) {
+        const bool _e13 = rq.Proceed();
+        if (_e13) {
+        } else {
+            break;
+        }
+        {
+            RayIntersection candidate = naga_candidate_intersection(rq);
+            if ((candidate.kind == 4u)) {
+                Sphere _e21 = ConstructSphere(asfloat(spheres.Load3(min(uint(candidate.primitive_index), ((NagaBufferLength(spheres) - 0) / 16) - 1u)*16+0)), asfloat(spheres.Load(min(uint(candidate.primitive_index), ((NagaBufferLength(spheres) - 0) / 16) - 1u)*16+12)));
+                const float _e22 = intersect_sphere(_e21, origin_1, dir_1);
+                if ((_e22 >= 0.0)) {
+                    rq.CommitProceduralPrimitiveHit(_e22);
+                }
+            } else {
+                float4 _e29 = alpha_mask.SampleLevel(alpha_sampler, candidate.barycentrics, 0.0);
+                float alpha = _e29.w;
+                if ((alpha > 0.5)) {
+                    rq.CommitNonOpaqueTriangleHit();
+                }
+            }
+        }
+    }
+    RayIntersection intersection = naga_committed_intersection(rq);
+    output.Store(0, asuint(intersection.kind));
+    output.Store(4, asuint(intersection.t));
+    return;
+}
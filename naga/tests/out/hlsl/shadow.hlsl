@@ -77,6 +77,13 @@ VertexOutput_vs_main vs_main(int4 position : LOC0, int4 normal : LOC1)
     return vertexoutput_1;
 }
 
+uint NagaBufferLength(ByteAddressBuffer buffer)
+{
+    uint ret;
+    buffer.GetDimensions(ret);
+    return ret;
+}
+
 Light ConstructLight(float4x4 arg0, float4 arg1, float4 arg2) {
     Light ret = (Light)0;
     ret.proj = arg0;
@@ -107,7 +114,7 @@ float4 fs_main(FragmentInput_fs_main fragmentinput_fs_main) : SV_Target0
         }
         {
             uint _e16 = i;
-            Light light = ConstructLight(float4x4(asfloat(s_lights.Load4(_e16*96+0+0)), asfloat(s_lights.Load4(_e16*96+0+16)), asfloat(s_lights.Load4(_e16*96+0+32)), asfloat(s_lights.Load4(_e16*96+0+48))), asfloat(s_lights.Load4(_e16*96+64)), asfloat(s_lights.Load4(_e16*96+80)));
+            Light light = ConstructLight(float4x4(asfloat(s_lights.Load4(min(uint(_e16), ((NagaBufferLength(s_lights) - 0) / 96) - 1u)*96+0+0)), asfloat(s_lights.Load4(min(uint(_e16), ((NagaBufferLength(s_lights) - 0) / 96) - 1u)*96+0+16)), asfloat(s_lights.Load4(min(uint(_e16), ((NagaBufferLength(s_lights) - 0) / 96) - 1u)*96+0+32)), asfloat(s_lights.Load4(min(uint(_e16), ((NagaBufferLength(s_lights) - 0) / 96) - 1u)*96+0+48))), asfloat(s_lights.Load4(min(uint(_e16), ((NagaBufferLength(s_lights) - 0) / 96) - 1u)*96+64)), asfloat(s_lights.Load4(min(uint(_e16), ((NagaBufferLength(s_lights) - 0) / 96) - 1u)*96+80)));
             uint _e19 = i;
             const float _e23 = fetch_shadow(_e19, mul(in_.world_position, light.proj));
             float3 light_dir = normalize((light.pos.xyz - in_.world_position.xyz));
@@ -143,7 +150,7 @@ float4 fs_main_without_storage(FragmentInput_fs_main_without_storage fragmentinp
         }
         {
             uint _e16 = i_1;
-            Light light_1 = u_lights[_e16];
+            Light light_1 = u_lights[min(uint(_e16), 9u)];
             uint _e19 = i_1;
             const float _e23 = fetch_shadow(_e19, mul(in_1.world_position, light_1.proj));
             float3 light_dir_1 = normalize((light_1.pos.xyz - in_1.world_position.xyz));
@@ -0,0 +1,26 @@
+RWTexture2D<float> s_r_r : register(u0);
+RWTexture2D<unorm float4> s_rgba8_r : register(u1);
+RWTexture2D<float4> s_rgba32_r : register(u2);
+RWTexture2D<float> s_r_rw : register(u0, space1);
+RWTexture2D<unorm float4> s_rgba8_rw : register(u1, space1);
+RWTexture2D<float4> s_rgba32_rw : register(u2, space1);
+
+[numthreads(1, 1, 1)]
+void csLoad()
+{
+    float4 phony = s_r_r.Load((0u).xx);
+    float4 phony_1 = s_rgba8_r.Load((0u).xx);
+    float4 phony_2 = s_rgba32_r.Load((0u).xx);
+}
+
+[numthreads(1, 1, 1)]
+void csStore()
+{
+    float4 r = s_r_rw.Load((0u).xx);
+    s_r_rw[(0u).xx] = r;
+    float4 rgba8_ = s_rgba8_rw.Load((0u).xx);
+    s_rgba8_rw[(0u).xx] = rgba8_;
+    float4 rgba32_ = s_rgba32_rw.Load((0u).xx);
+    s_rgba32_rw[(0u).xx] = rgba32_;
+    return;
+}
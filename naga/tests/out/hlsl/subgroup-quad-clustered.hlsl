@@ -0,0 +1,32 @@
+struct ComputeInput_main {
+};
+
+[numthreads(1, 1, 1)]
+void main(ComputeInput_main computeinput_main)
+{
+    uint subgroup_invocation_id = WaveGetLaneIndex();
+    const uint _e2 = QuadReadLaneAt(subgroup_invocation_id, 2u);
+    const uint _e3 = QuadReadAcrossX(subgroup_invocation_id);
+    const uint _e4 = QuadReadAcrossY(subgroup_invocation_id);
+    const uint _e5 = QuadReadAcrossDiagonal(subgroup_invocation_id);
+    uint _e6 = subgroup_invocation_id;
+    _e6 = _e6 + WaveReadLaneAt(_e6, WaveGetLaneIndex() ^ 1u);
+    _e6 = _e6 + WaveReadLaneAt(_e6, WaveGetLaneIndex() ^ 2u);
+    uint _e7 = subgroup_invocation_id;
+    _e7 = _e7 * WaveReadLaneAt(_e7, WaveGetLaneIndex() ^ 1u);
+    float _e9 = float(subgroup_invocation_id);
+    _e9 = min(_e9, WaveReadLaneAt(_e9, WaveGetLaneIndex() ^ 1u));
+    _e9 = min(_e9, WaveReadLaneAt(_e9, WaveGetLaneIndex() ^ 2u));
+    _e9 = min(_e9, WaveReadLaneAt(_e9, WaveGetLaneIndex() ^ 4u));
+    int2 _e12 = (int(subgroup_invocation_id)).xx;
+    _e12 = max(_e12, WaveReadLaneAt(_e12, WaveGetLaneIndex() ^ 1u));
+    _e12 = max(_e12, WaveReadLaneAt(_e12, WaveGetLaneIndex() ^ 2u));
+    uint _e13 = subgroup_invocation_id;
+    uint _e14 = subgroup_invocation_id;
+    _e14 = _e14 | WaveReadLaneAt(_e14, WaveGetLaneIndex() ^ 1u);
+    _e14 = _e14 | WaveReadLaneAt(_e14, WaveGetLaneIndex() ^ 2u);
+    uint _e15 = subgroup_invocation_id;
+    _e15 = _e15 ^ WaveReadLaneAt(_e15, WaveGetLaneIndex() ^ 1u);
+    _e15 = _e15 ^ WaveReadLaneAt(_e15, WaveGetLaneIndex() ^ 2u);
+    return;
+}
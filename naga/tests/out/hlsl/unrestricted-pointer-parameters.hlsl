@@ -0,0 +1,73 @@
+struct Particle {
+    float4 position;
+    float4 velocity;
+};
+
+RWByteAddressBuffer particles : register(u0);
+cbuffer params : register(b1) { float4 params; }
+groupshared float sums[64];
+
+float read_delta()
+{
+    float _e2 = params.x;
+    return _e2;
+}
+
+uint NagaBufferLengthRW(RWByteAddressBuffer buffer)
+{
+    uint ret;
+    buffer.GetDimensions(ret);
+    return ret;
+}
+
+void advance(float delta, uint particle_index)
+{
+    float4 _e7 = asfloat(particles.Load4(16+min(uint(particle_index), ((NagaBufferLengthRW(particles) - 16) / 32) - 1u)*32+16));
+    float4 _e9 = asfloat(particles.Load4(0+min(uint(particle_index), ((NagaBufferLengthRW(particles) - 16) / 32) - 1u)*32+16));
+    particles.Store4(0+min(uint(particle_index), ((NagaBufferLengthRW(particles) - 16) / 32) - 1u)*32+16, asuint((_e9 + (_e7 * delta))));
+    return;
+}
+
+void accumulate(float value, uint slot_index)
+{
+    float _e4 = sums[min(uint(slot_index), 63u)];
+    sums[min(uint(slot_index), 63u)] = (_e4 + value);
+    return;
+}
+
+void advance_and_accumulate(float delta_1, uint particle_index_1, uint slot_index_1)
+{
+    advance(delta_1, particle_index_1);
+    float _e10 = asfloat(particles.Load(0+0+min(uint(particle_index_1), ((NagaBufferLengthRW(particles) - 16) / 32) - 1u)*32+16));
+    accumulate(_e10, slot_index_1);
+    return;
+}
+
+void advance_1(float delta_2)
+{
+    float4 _e6 = asfloat(particles.Load4(16+min(0u, ((NagaBufferLengthRW(particles) - 16) / 32) - 1u)*32+16));
+    float4 _e8 = asfloat(particles.Load4(0+min(0u, ((NagaBufferLengthRW(particles) - 16) / 32) - 1u)*32+16));
+    particles.Store4(0+min(0u, ((NagaBufferLengthRW(particles) - 16) / 32) - 1u)*32+16, asuint((_e8 + (_e6 * delta_2))));
+    return;
+}
+
+void accumulate_1(float value_1)
+{
+    float _e3 = sums[0];
+    sums[0] = (_e3 + value_1);
+    return;
+}
+
+[numthreads(64, 1, 1)]
+void main(uint index : SV_GroupIndex, uint3 id : SV_DispatchThreadID, uint3 __local_invocation_id : SV_GroupThreadID)
+{
+    if (all(__local_invocation_id == uint3(0u, 0u, 0u))) {
+        sums = (float[64])0;
+    }
+    GroupMemoryBarrierWithGroupSync();
+    const float _e3 = read_delta();
+    advance_and_accumulate(_e3, id.x, index);
+    advance_1(_e3);
+    accumulate_1(1.0);
+    return;
+}
@@ -0,0 +1,15 @@
+const m: mat2x3<f32> = mat2x3<f32>(vec3<f32>(1f, 2f, 3f), vec3<f32>(4f, 5f, 6f));
+const modf_result: _modf_result_vec2_f32_ = _modf_result_vec2_f32_(vec2<f32>(0.5f, -0.25f), vec2<f32>(1f, -0f));
+const frexp_result: _frexp_result_f32_ = _frexp_result_f32_(0.625f, 2i);
+const packed: u32 = 1006647296u;
+const unpacked: vec4<u32> = vec4<u32>(0u, 56u, 0u, 60u);
+const transposed: mat3x2<f32> = mat3x2<f32>(vec2<f32>(1f, 4f), vec2<f32>(2f, 5f), vec2<f32>(3f, 6f));
+
+override selected: f32 = 0.625f;
+
+@compute @workgroup_size(1, 1, 1) 
+fn main() {
+    const local_select = vec3<i32>(2i, 1i, 2i);
+    const local_bitcast = vec2<f32>(1f, 2f);
+    let x = (vec4<f32>(vec2<f32>(2f, 4f), vec2<f32>(6f, 6f)) + vec4(selected));
+}
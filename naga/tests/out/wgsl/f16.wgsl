@@ -0,0 +1,137 @@
+enable f16;
+
+struct UniformCompatible {
+    val_u32_: u32,
+    val_i32_: i32,
+    val_f32_: f32,
+    val_f16_: f16,
+    val_f16_2_: vec2<f16>,
+    val_f16_3_: vec3<f16>,
+    val_f16_4_: vec4<f16>,
+    final_value: f16,
+    val_mat2x2_f16_: mat2x2<f16>,
+    val_mat2x4_f16_: mat2x4<f16>,
+    val_mat3x2_f16_: mat3x2<f16>,
+    val_mat4x4_f16_: mat4x4<f16>,
+}
+
+struct StorageCompatible {
+    val_f16_array_2_: array<f16, 2>,
+}
+
+struct LayoutTest {
+    scalar1_: f16,
+    scalar2_: f16,
+    v3_: vec3<f16>,
+    tuck_in: f16,
+    scalar4_: f16,
+    larger: u32,
+}
+
+const constant_variable: f16 = 15.203125h;
+
+var<private> private_variable: f16 = 1h;
+@group(0) @binding(0) 
+var<uniform> input_uniform: UniformCompatible;
+@group(0) @binding(1) 
+var<storage> input_storage: UniformCompatible;
+@group(0) @binding(2) 
+var<storage> input_arrays: StorageCompatible;
+@group(0) @binding(3) 
+var<storage, read_write> output: UniformCompatible;
+@group(0) @binding(4) 
+var<storage, read_write> output_arrays: StorageCompatible;
+
+fn f16_function(x: f16) -> f16 {
+    var val: f16 = 15.203125h;
+
+    let _e4 = val;
+    val = (_e4 + -33344h);
+    let _e6 = val;
+    let _e9 = val;
+    val = (_e9 + (_e6 + 5h));
+    let _e13 = input_uniform.val_f32_;
+    let _e14 = val;
+    let _e18 = val;
+    val = (_e18 + f16((_e13 + f32(_e14))));
+    let _e22 = input_uniform.val_f16_;
+    let _e25 = val;
+    val = (_e25 + vec3(_e22).z);
+    let _e31 = input_uniform.val_f16_;
+    let _e34 = input_storage.val_f16_;
+    output.val_f16_ = (_e31 + _e34);
+    let _e40 = input_uniform.val_f16_2_;
+    let _e43 = input_storage.val_f16_2_;
+    output.val_f16_2_ = (_e40 + _e43);
+    let _e49 = input_uniform.val_f16_3_;
+    let _e52 = input_storage.val_f16_3_;
+    output.val_f16_3_ = (_e49 + _e52);
+    let _e58 = input_uniform.val_f16_4_;
+    let _e61 = input_storage.val_f16_4_;
+    output.val_f16_4_ = (_e58 + _e61);
+    let _e67 = input_uniform.val_mat2x2_f16_;
+    let _e70 = input_storage.val_mat2x2_f16_;
+    output.val_mat2x2_f16_ = (_e67 + _e70);
+    let _e76 = input_uniform.val_mat2x4_f16_;
+    let _e79 = input_storage.val_mat2x4_f16_;
+    output.val_mat2x4_f16_ = (_e76 + _e79);
+    let _e85 = input_uniform.val_mat3x2_f16_;
+    let _e88 = input_storage.val_mat3x2_f16_;
+    output.val_mat3x2_f16_ = (_e85 + _e88);
+    let _e94 = input_uniform.val_mat4x4_f16_;
+    let _e97 = input_storage.val_mat4x4_f16_;
+    output.val_mat4x4_f16_ = (_e94 + _e97);
+    let _e103 = input_arrays.val_f16_array_2_;
+    output_arrays.val_f16_array_2_ = _e103;
+    let _e104 = val;
+    let _e106 = val;
+    val = (_e106 + abs(_e104));
+    let _e108 = val;
+    let _e109 = val;
+    let _e110 = val;
+    let _e112 = val;
+    val = (_e112 + clamp(_e108, _e109, _e110));
+    let _e114 = val;
+    let _e116 = val;
+    let _e119 = val;
+    val = (_e119 + dot(vec2(_e114), vec2(_e116)));
+    let _e121 = val;
+    let _e122 = val;
+    let _e124 = val;
+    val = (_e124 + max(_e121, _e122));
+    let _e126 = val;
+    let _e127 = val;
+    let _e129 = val;
+    val = (_e129 + min(_e126, _e127));
+    let _e131 = val;
+    let _e133 = val;
+    val = (_e133 + sign(_e131));
+    let _e136 = val;
+    val = (_e136 + 1h);
+    let _e140 = input_uniform.val_f16_2_;
+    let float_vec2_ = vec2<f32>(_e140);
+    output.val_f16_2_ = vec2<f16>(float_vec2_);
+    let _e147 = input_uniform.val_f16_3_;
+    let float_vec3_ = vec3<f32>(_e147);
+    output.val_f16_3_ = vec3<f16>(float_vec3_);
+    let _e154 = input_uniform.val_f16_4_;
+    let float_vec4_ = vec4<f32>(_e154);
+    output.val_f16_4_ = vec4<f16>(float_vec4_);
+    let _e163 = input_uniform.val_mat2x2_f16_;
+    output.val_mat2x2_f16_ = mat2x2<f16>(mat2x2<f32>(_e163));
+    let _e170 = input_uniform.val_mat2x4_f16_;
+    output.val_mat2x4_f16_ = mat2x4<f16>(mat2x4<f32>(_e170));
+    let _e177 = input_uniform.val_mat3x2_f16_;
+    output.val_mat3x2_f16_ = mat3x2<f16>(mat3x2<f32>(_e177));
+    let _e184 = input_uniform.val_mat4x4_f16_;
+    output.val_mat4x4_f16_ = mat4x4<f16>(mat4x4<f32>(_e184));
+    let _e187 = val;
+    return _e187;
+}
+
+@compute @workgroup_size(1, 1, 1) 
+fn main() {
+    let _e3 = f16_function(2h);
+    output.final_value = _e3;
+    return;
+}
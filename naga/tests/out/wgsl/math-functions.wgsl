@@ -9,7 +9,6 @@ fn main() {
     let g = refract(v, v, 1f);
     const sign_b = vec4<i32>(-1i, -1i, -1i, -1i);
     const sign_d = vec4<f32>(-1f, -1f, -1f, -1f);
-    let const_dot = dot(vec2<i32>(), vec2<i32>());
     const flb_b = vec2<i32>(-1i, -1i);
     const flb_c = vec2<u32>(0u, 0u);
     const ftb_c = vec2<i32>(0i, 0i);
@@ -20,20 +19,11 @@ fn main() {
     const ctz_h = vec2<i32>(0i, 0i);
     const clz_c = vec2<i32>(0i, 0i);
     const clz_d = vec2<u32>(31u, 31u);
-    let lde_a = ldexp(1f, 2i);
-    let lde_b = ldexp(vec2<f32>(1f, 2f), vec2<i32>(3i, 4i));
-    let modf_a = modf(1.5f);
-    let modf_b = modf(1.5f).fract;
-    let modf_c = modf(1.5f).whole;
-    let modf_d = modf(vec2<f32>(1.5f, 1.5f));
-    let modf_e = modf(vec4<f32>(1.5f, 1.5f, 1.5f, 1.5f)).whole.x;
-    let modf_f = modf(vec2<f32>(1.5f, 1.5f)).fract.y;
-    let frexp_a = frexp(1.5f);
-    let frexp_b = frexp(1.5f).fract;
-    let frexp_c = frexp(1.5f).exp;
-    let frexp_d = frexp(vec4<f32>(1.5f, 1.5f, 1.5f, 1.5f)).exp.x;
-    let quantizeToF16_a = quantizeToF16(1f);
-    let quantizeToF16_b = quantizeToF16(vec2<f32>(1f, 1f));
-    let quantizeToF16_c = quantizeToF16(vec3<f32>(1f, 1f, 1f));
-    let quantizeToF16_d = quantizeToF16(vec4<f32>(1f, 1f, 1f, 1f));
+    const lde_b = vec2<f32>(8f, 32f);
+    const modf_a = _modf_result_f32_(0.5f, 1f);
+    const modf_d = _modf_result_vec2_f32_(vec2<f32>(0.5f, 0.5f), vec2<f32>(1f, 1f));
+    const frexp_a = _frexp_result_f32_(0.75f, 1i);
+    const quantizeToF16_b = vec2<f32>(1f, 1f);
+    const quantizeToF16_c = vec3<f32>(1f, 1f, 1f);
+    const quantizeToF16_d = vec4<f32>(1f, 1f, 1f, 1f);
 }
@@ -0,0 +1,71 @@
+enable wgpu_mesh_shader;
+
+struct TaskPayload {
+    color_mask: vec4<f32>,
+    visible: bool,
+}
+
+struct TaskOutput {
+    @builtin(mesh_task_size) size: vec3<u32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+}
+
+struct PrimitiveOutput {
+    @builtin(triangle_indices) indices: vec3<u32>,
+    @builtin(cull_primitive) cull: bool,
+    @location(1) @per_primitive @interpolate(flat) color_mask: vec4<f32>,
+}
+
+struct PrimitiveInput {
+    @location(1) @per_primitive @interpolate(flat) color_mask: vec4<f32>,
+}
+
+const positions: array<vec4<f32>, 3> = array<vec4<f32>, 3>(vec4<f32>(0f, 1f, 0f, 1f), vec4<f32>(-1f, -1f, 0f, 1f), vec4<f32>(1f, -1f, 0f, 1f));
+
+var<task_payload> task_payload: TaskPayload;
+var<workgroup> workgroup_data: f32;
+
+@task @workgroup_size(1, 1, 1) @payload(task_payload) 
+fn ts_main() -> TaskOutput {
+    workgroup_data = 1f;
+    task_payload.color_mask = vec4<f32>(1f, 1f, 0f, 1f);
+    task_payload.visible = true;
+    return TaskOutput(vec3<u32>(3u, 1u, 1u));
+}
+
+@mesh @workgroup_size(1, 1, 1) @payload(task_payload) @output_topology(triangles) @vertex_output(VertexOutput, 3) @primitive_output(PrimitiveOutput, 1) 
+fn ms_main(@builtin(local_invocation_index) index: u32, @builtin(global_invocation_id) id: vec3<u32>) {
+    var i: u32 = 0u;
+
+    setMeshOutputs(3u, 1u);
+    workgroup_data = 2f;
+    loop {
+        let _e8 = i;
+        if (_e8 < 3u) {
+        } else {
+            break;
+        }
+        {
+            let _e11 = i;
+            let _e13 = i;
+            setVertex(_e11, VertexOutput(positions[_e13], vec4<f32>(0f, 1f, 0f, 1f)));
+        }
+        continuing {
+            let _e22 = i;
+            i = (_e22 + 1u);
+        }
+    }
+    let _e31 = task_payload.visible;
+    let _e35 = task_payload.color_mask;
+    setPrimitive(0u, PrimitiveOutput(vec3<u32>(0u, 1u, 2u), !(_e31), _e35));
+    return;
+}
+
+@fragment 
+fn fs_main(vertex: VertexOutput, primitive: PrimitiveInput) -> @location(0) vec4<f32> {
+    return (vertex.color * primitive.color_mask);
+}
@@ -0,0 +1,64 @@
+@group(0) @binding(0) 
+var<storage, read_write> output: array<f32>;
+
+fn clamp_positive(v: f32) -> f32 {
+    if (v < 0f) {
+        return 0f;
+    }
+    return v;
+}
+
+fn square(v_1: f32) -> f32 {
+    return (v_1 * v_1);
+}
+
+@compute @workgroup_size(1, 1, 1) 
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    var total: f32;
+    var i: u32 = 0u;
+    var scale_result: f32;
+    var accumulate_result: f32;
+    var sum: f32 = 1f;
+
+    let x = output[id.x];
+    let _e5 = square(x);
+    {
+        scale_result = (x * 8f);
+    }
+    let _e7 = scale_result;
+    total = _e7;
+    loop {
+        let _e11 = i;
+        if (_e11 < 4u) {
+        } else {
+            break;
+        }
+        {
+            let _e14 = i;
+            {
+                sum = 1f;
+                let _e34 = sum;
+                sum = (_e34 + f32(_e14));
+                let _e36 = sum;
+                accumulate_result = _e36;
+            }
+            let _e17 = accumulate_result;
+            let _e18 = total;
+            total = (_e18 + _e17);
+        }
+        continuing {
+            let _e21 = i;
+            i = (_e21 + 1u);
+        }
+    }
+    let _e23 = total;
+    let _e24 = clamp_positive(_e23);
+    let _e25 = total;
+    let _e26 = square(_e25);
+    total = (_e24 + _e26);
+    let _e29 = total;
+    {
+        output[id.x] = _e29;
+    }
+    return;
+}
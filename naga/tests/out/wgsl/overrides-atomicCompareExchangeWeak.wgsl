@@ -0,0 +1,9 @@
+const o: i32 = 2i;
+
+var<workgroup> a: atomic<u32>;
+
+@compute @workgroup_size(1, 1, 1) 
+fn f() {
+    let _e5 = atomicCompareExchangeWeak((&a), 2u, 1u);
+    return;
+}
@@ -0,0 +1,15 @@
+const signed_const: i32 = -250i;
+const unsigned_const: u32 = 65030u;
+
+fn test_packed_integer_dot_product() -> u32 {
+    let c_5_ = dot4I8Packed(1u, 2u);
+    let c_6_ = dot4U8Packed(3u, 4u);
+    let c_7_ = dot4I8Packed((5u + c_6_), (6u + c_6_));
+    return (((56u + u32((c_5_ + c_7_))) + 4294967046u) + unsigned_const);
+}
+
+@compute @workgroup_size(1, 1, 1) 
+fn main() {
+    let _e0 = test_packed_integer_dot_product();
+    return;
+}
@@ -0,0 +1,43 @@
+requires pointer_composite_access;
+
+struct Light {
+    position: vec3<f32>,
+    intensity: f32,
+}
+
+struct Scene {
+    lights: array<Light, 4>,
+    ambient: vec4<f32>,
+}
+
+fn brightness(scene_1: ptr<function, Scene>, index: i32) -> f32 {
+    let light = (&scene_1.lights[index]);
+    let _e5 = light.intensity;
+    let _e8 = scene_1.ambient.w;
+    let _e12 = light.position.x;
+    return ((_e5 * _e8) + _e12);
+}
+
+fn dim(scene_2: ptr<function, Scene>) {
+    let _e5 = scene_2.lights[0].intensity;
+    scene_2.lights[0].intensity = (_e5 * 0.5f);
+    scene_2.ambient.w = 0f;
+    let lights = (&scene_2.lights);
+    lights[1].position = vec3<f32>(1f, 2f, 3f);
+    return;
+}
+
+@compute @workgroup_size(1, 1, 1) 
+fn main() {
+    var scene: Scene;
+    var result: array<f32, 2>;
+
+    dim((&scene));
+    let _e2 = brightness((&scene), 0i);
+    let _e4 = brightness((&scene), 1i);
+    let total = (_e2 + _e4);
+    result = array<f32, 2>(total, 0f);
+    let _e11 = result[0];
+    result[1] = (_e11 * 2f);
+    return;
+}
@@ -0,0 +1,20 @@
+const TEN: i32 = 10i;
+
+@id(0) override SIZE: i32 = 4i;
+@id(1) override SCALE: f32 = 1.5f;
+@id(2) override FLIP: bool = true;
+
+var<private> color: vec4<f32>;
+
+fn main_1() {
+    let _e17 = vec2<f32>(SCALE, 2f);
+    color = vec4<f32>(f32(select(8i, (SIZE * 2i), (SIZE < 8i))), f32((bitcast<u32>(SIZE) >> 1u)), _e17.x, select(_e17.y, f32(TEN), !(FLIP)));
+    return;
+}
+
+@fragment 
+fn main() -> @location(0) vec4<f32> {
+    main_1();
+    let _e1 = color;
+    return _e1;
+}
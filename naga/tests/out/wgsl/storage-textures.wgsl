@@ -0,0 +1,30 @@
+@group(0) @binding(0) 
+var s_r_r: texture_storage_2d<r32float,read>;
+@group(0) @binding(1) 
+var s_rgba8_r: texture_storage_2d<rgba8unorm,read>;
+@group(0) @binding(2) 
+var s_rgba32_r: texture_storage_2d<rgba32float,read>;
+@group(1) @binding(0) 
+var s_r_rw: texture_storage_2d<r32float,read_write>;
+@group(1) @binding(1) 
+var s_rgba8_rw: texture_storage_2d<rgba8unorm,read_write>;
+@group(1) @binding(2) 
+var s_rgba32_rw: texture_storage_2d<rgba32float,read_write>;
+
+@compute @workgroup_size(1, 1, 1) 
+fn csLoad() {
+    let phony = textureLoad(s_r_r, vec2(0u));
+    let phony_1 = textureLoad(s_rgba8_r, vec2(0u));
+    let phony_2 = textureLoad(s_rgba32_r, vec2(0u));
+}
+
+@compute @workgroup_size(1, 1, 1) 
+fn csStore() {
+    let r = textureLoad(s_r_rw, vec2(0u));
+    textureStore(s_r_rw, vec2(0u), r);
+    let rgba8_ = textureLoad(s_rgba8_rw, vec2(0u));
+    textureStore(s_rgba8_rw, vec2(0u), rgba8_);
+    let rgba32_ = textureLoad(s_rgba32_rw, vec2(0u));
+    textureStore(s_rgba32_rw, vec2(0u), rgba32_);
+    return;
+}
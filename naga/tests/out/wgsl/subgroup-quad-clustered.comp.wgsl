@@ -0,0 +1,58 @@
+struct Values {
+    values: array<u32>,
+}
+
+@group(0) @binding(0) 
+var<storage, read_write> global: Values;
+var<private> gl_GlobalInvocationID_1: vec3<u32>;
+
+fn main_1() {
+    var index: u32;
+    var value: u32;
+    var quad: u32;
+    var clustered: u32;
+
+    let _e3 = gl_GlobalInvocationID_1;
+    index = _e3.x;
+    let _e6 = index;
+    let _e8 = global.values[_e6];
+    value = _e8;
+    let _e12 = value;
+    let _e14 = quadBroadcast(_e12, 3u);
+    quad = _e14;
+    let _e16 = quad;
+    let _e18 = value;
+    let _e19 = quadSwapX(_e18);
+    quad = (_e16 + _e19);
+    let _e21 = quad;
+    let _e23 = value;
+    let _e24 = quadSwapY(_e23);
+    quad = (_e21 + _e24);
+    let _e26 = quad;
+    let _e28 = value;
+    let _e29 = quadSwapDiagonal(_e28);
+    quad = (_e26 + _e29);
+    let _e33 = value;
+    let _e35 = subgroupClusteredAdd(_e33, 4u);
+    clustered = _e35;
+    let _e37 = clustered;
+    let _e40 = value;
+    let _e42 = subgroupClusteredMax(_e40, 2u);
+    clustered = (_e37 + _e42);
+    let _e44 = clustered;
+    let _e47 = value;
+    let _e49 = subgroupClusteredXor(_e47, 4u);
+    clustered = (_e44 + _e49);
+    let _e51 = index;
+    let _e53 = quad;
+    let _e54 = clustered;
+    global.values[_e51] = (_e53 + _e54);
+    return;
+}
+
+@compute @workgroup_size(4, 1, 1) 
+fn main(@builtin(global_invocation_id) gl_GlobalInvocationID: vec3<u32>) {
+    gl_GlobalInvocationID_1 = gl_GlobalInvocationID;
+    main_1();
+    return;
+}
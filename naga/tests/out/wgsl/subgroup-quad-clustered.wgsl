@@ -0,0 +1,15 @@
+@compute @workgroup_size(1, 1, 1) 
+fn main(@builtin(subgroup_invocation_id) subgroup_invocation_id: u32) {
+    let _e2 = quadBroadcast(subgroup_invocation_id, 2u);
+    let _e3 = quadSwapX(subgroup_invocation_id);
+    let _e4 = quadSwapY(subgroup_invocation_id);
+    let _e5 = quadSwapDiagonal(subgroup_invocation_id);
+    let _e6 = subgroupClusteredAdd(subgroup_invocation_id, 4u);
+    let _e7 = subgroupClusteredMul(subgroup_invocation_id, 2u);
+    let _e9 = subgroupClusteredMin(f32(subgroup_invocation_id), 8u);
+    let _e12 = subgroupClusteredMax(vec2(i32(subgroup_invocation_id)), 4u);
+    let _e13 = subgroupClusteredAnd(subgroup_invocation_id, 1u);
+    let _e14 = subgroupClusteredOr(subgroup_invocation_id, 4u);
+    let _e15 = subgroupClusteredXor(subgroup_invocation_id, 4u);
+    return;
+}
@@ -0,0 +1,49 @@
+struct Particle {
+    position: vec4<f32>,
+    velocity: vec4<f32>,
+}
+
+struct Particles {
+    count: u32,
+    items: array<Particle>,
+}
+
+@group(0) @binding(0) 
+var<storage, read_write> particles: Particles;
+@group(0) @binding(1) 
+var<uniform> params: vec4<f32>;
+var<workgroup> sums: array<f32, 64>;
+
+fn advance(particle: ptr<storage, Particle, read_write>, delta: f32) {
+    let _e4 = (*particle).velocity;
+    let _e6 = (*particle).position;
+    (*particle).position = (_e6 + (_e4 * delta));
+    return;
+}
+
+fn read_delta(p: ptr<uniform, vec4<f32>>) -> f32 {
+    let _e2 = (*p).x;
+    return _e2;
+}
+
+fn accumulate(slot: ptr<workgroup, f32>, value: f32) {
+    let _e2 = (*slot);
+    (*slot) = (_e2 + value);
+    return;
+}
+
+fn advance_and_accumulate(particle_1: ptr<storage, Particle, read_write>, slot_1: ptr<workgroup, f32>, delta_1: f32) {
+    advance(particle_1, delta_1);
+    let _e5 = (*particle_1).position.x;
+    accumulate(slot_1, _e5);
+    return;
+}
+
+@compute @workgroup_size(64, 1, 1) 
+fn main(@builtin(local_invocation_index) index: u32, @builtin(global_invocation_id) id: vec3<u32>) {
+    let _e3 = read_delta((&params));
+    advance_and_accumulate((&particles.items[id.x]), (&sums[index]), _e3);
+    advance((&particles.items[0]), _e3);
+    accumulate((&sums[0]), 1f);
+    return;
+}
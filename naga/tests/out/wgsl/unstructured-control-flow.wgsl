@@ -0,0 +1,71 @@
+fn nested(y: i32) -> i32 {
+    var phi_53_: i32;
+
+    phi_53_ = y;
+    if (y > 0i) {
+        if (y > 10i) {
+            phi_53_ = (y * 2i);
+        } else {
+            phi_53_ = (y + 5i);
+        }
+    }
+    let _e10 = phi_53_;
+    return _e10;
+}
+
+fn loop_sum(x: i32) -> i32 {
+    var phi_28_: i32;
+    var phi_32_: i32;
+    var phi_34_: i32;
+    var phi_33_: i32;
+    var phi_43_: i32;
+
+    if (x > 0i) {
+        phi_28_ = (x + 1i);
+    } else {
+        phi_28_ = (0i - x);
+    }
+    let _e9 = phi_28_;
+    phi_32_ = 0i;
+    phi_34_ = _e9;
+    loop {
+        let _e11 = phi_32_;
+        let _e13 = phi_34_;
+        phi_43_ = _e13;
+        if (_e11 < 10i) {
+            phi_33_ = _e13;
+            if ((_e11 & 1i) == 1i) {
+                let _e17 = (_e13 + _e11);
+                phi_33_ = _e17;
+                phi_43_ = _e17;
+                if (_e17 > 100i) {
+                    break;
+                } else {
+                    continue;
+                }
+            } else {
+                continue;
+            }
+        } else {
+            break;
+        }
+        continuing {
+            let _e20 = phi_33_;
+            phi_32_ = (_e11 + 1i);
+            phi_34_ = _e20;
+        }
+    }
+    let _e23 = phi_43_;
+    return _e23;
+}
+
+fn main_1() {
+    let _e1 = loop_sum(3i);
+    let _e2 = nested(3i);
+    return;
+}
+
+@compute @workgroup_size(1, 1, 1) 
+fn main() {
+    main_1();
+}
@@ -62,6 +62,12 @@ struct SpirvOutParameters {
 struct WgslOutParameters {
     #[serde(default)]
     explicit_types: bool,
+    #[serde(default)]
+    pointer_composite_access: bool,
+    /// Replace overrides with the values in `pipeline_constants`, rather
+    /// than writing `override` declarations.
+    #[serde(default)]
+    bake_overrides: bool,
 }
 
 #[derive(Default, serde::Deserialize)]
@@ -92,9 +98,15 @@ struct Parameters {
     #[cfg(all(feature = "deserialize", glsl_out))]
     #[serde(default)]
     glsl_multiview: Option<std::num::NonZeroU32>,
-    #[cfg(all(feature = "deserialize", any(hlsl_out, msl_out, spv_out, glsl_out)))]
+    #[cfg(all(
+        feature = "deserialize",
+        any(hlsl_out, msl_out, spv_out, glsl_out, wgsl_out)
+    ))]
     #[serde(default)]
     pipeline_constants: naga::back::PipelineConstants,
+    #[cfg(all(feature = "deserialize", feature = "opt"))]
+    #[serde(default)]
+    optimize: Option<naga::opt::Passes>,
 }
 
 /// Information about a shader input file.
@@ -344,6 +356,26 @@ fn check_targets(
             })
     };
 
+    #[cfg(all(feature = "deserialize", feature = "opt"))]
+    let info = match params.optimize {
+        Some(passes) => {
+            naga::opt::optimize(module, passes);
+
+            naga::valid::Validator::new(validation_flags, capabilities)
+                .subgroup_stages(subgroup_stages)
+                .subgroup_operations(subgroup_operations)
+                .validate(module)
+                .unwrap_or_else(|err| {
+                    panic!(
+                        "Post-optimization module validation failed on test '{}':\n<{:?}",
+                        name.display(),
+                        err,
+                    )
+                })
+        }
+        None => info,
+    };
+
     #[cfg(feature = "serialize")]
     {
         if targets.contains(Targets::ANALYSIS) {
@@ -434,7 +466,13 @@ fn check_targets(
     #[cfg(all(feature = "deserialize", wgsl_out))]
     {
         if targets.contains(Targets::WGSL) {
-            write_output_wgsl(input, module, &info, &params.wgsl);
+            write_output_wgsl(
+                input,
+                module,
+                &info,
+                &params.wgsl,
+                &params.pipeline_constants,
+            );
         }
     }
 }
@@ -478,6 +516,9 @@ fn write_output_spv(
     let (module, info) =
         naga::back::pipeline_constants::process_overrides(module, info, pipeline_constants)
             .expect("override evaluation failed");
+    let (module, info) =
+        naga::back::pointer_arguments::specialize_pointer_arguments(&module, &info)
+            .expect("pointer argument specialization failed");
 
     if params.separate_entry_points {
         for ep in module.entry_points.iter() {
@@ -542,6 +583,9 @@ fn write_output_msl(
     let (module, info) =
         naga::back::pipeline_constants::process_overrides(module, info, pipeline_constants)
             .expect("override evaluation failed");
+    let (module, info) =
+        naga::back::pointer_arguments::specialize_pointer_arguments(&module, &info)
+            .expect("pointer argument specialization failed");
 
     let mut options = options.clone();
     options.bounds_check_policies = bounds_check_policies;
@@ -584,6 +628,9 @@ fn write_output_glsl(
     let (module, info) =
         naga::back::pipeline_constants::process_overrides(module, info, pipeline_constants)
             .expect("override evaluation failed");
+    let (module, info) =
+        naga::back::pointer_arguments::specialize_pointer_arguments(&module, &info)
+            .expect("pointer argument specialization failed");
     let mut writer = glsl::Writer::new(
         &mut buffer,
         &module,
@@ -616,6 +663,9 @@ fn write_output_hlsl(
     let (module, info) =
         naga::back::pipeline_constants::process_overrides(module, info, pipeline_constants)
             .expect("override evaluation failed");
+    let (module, info) =
+        naga::back::pointer_arguments::specialize_pointer_arguments(&module, &info)
+            .expect("pointer argument specialization failed");
 
     let mut buffer = String::new();
     let mut writer = hlsl::Writer::new(&mut buffer, options);
@@ -638,6 +688,8 @@ fn write_output_hlsl(
             naga::ShaderStage::Vertex => &mut config.vertex,
             naga::ShaderStage::Fragment => &mut config.fragment,
             naga::ShaderStage::Compute => &mut config.compute,
+            naga::ShaderStage::Task => &mut config.task,
+            naga::ShaderStage::Mesh => &mut config.mesh,
         }
         .push(hlsl_snapshots::ConfigItem {
             entry_point: name.clone(),
@@ -658,6 +710,7 @@ fn write_output_wgsl(
     module: &naga::Module,
     info: &naga::valid::ModuleInfo,
     params: &WgslOutParameters,
+    pipeline_constants: &naga::back::PipelineConstants,
 ) {
     use naga::back::wgsl;
 
@@ -665,8 +718,17 @@ fn write_output_wgsl(
 
     let mut flags = wgsl::WriterFlags::empty();
     flags.set(wgsl::WriterFlags::EXPLICIT_TYPES, params.explicit_types);
+    flags.set(
+        wgsl::WriterFlags::POINTER_COMPOSITE_ACCESS,
+        params.pointer_composite_access,
+    );
 
-    let string = wgsl::write_string(module, info, flags).expect("WGSL write failed");
+    let string = if params.bake_overrides {
+        wgsl::write_string_with_pipeline_constants(module, info, flags, pipeline_constants)
+    } else {
+        wgsl::write_string(module, info, flags)
+    }
+    .expect("WGSL write failed");
 
     input.write_output_file("wgsl", "wgsl", string);
 }
@@ -732,6 +794,23 @@ fn convert_wgsl() {
             "image",
             Targets::SPIRV | Targets::METAL | Targets::HLSL | Targets::WGSL | Targets::GLSL,
         ),
+        (
+            "storage-textures",
+            Targets::SPIRV | Targets::METAL | Targets::HLSL | Targets::WGSL | Targets::GLSL,
+        ),
+        (
+            "packed-dot",
+            Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
+        ),
+        ("packed-dot-native", Targets::HLSL),
+        (
+            "unrestricted-pointer-parameters",
+            Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
+        ),
+        (
+            "pointer-composite-access",
+            Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
+        ),
         ("extra", Targets::SPIRV | Targets::METAL | Targets::WGSL),
         ("push-constants", Targets::GLSL | Targets::HLSL),
         (
@@ -750,6 +829,10 @@ fn convert_wgsl() {
             "dualsource",
             Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
         ),
+        (
+            "dual-source-blend-src",
+            Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
+        ),
         ("functions-webgl", Targets::GLSL),
         (
             "interpolate",
@@ -775,7 +858,7 @@ fn convert_wgsl() {
         ),
         (
             "atomicCompareExchange",
-            Targets::SPIRV | Targets::METAL | Targets::WGSL,
+            Targets::SPIRV | Targets::METAL | Targets::HLSL | Targets::WGSL,
         ),
         (
             "padding",
@@ -791,7 +874,7 @@ fn convert_wgsl() {
         ),
         (
             "atomicCompareExchange-int64",
-            Targets::SPIRV | Targets::WGSL,
+            Targets::SPIRV | Targets::HLSL | Targets::WGSL,
         ),
         ("pointers", Targets::SPIRV | Targets::WGSL),
         (
@@ -811,16 +894,23 @@ fn convert_wgsl() {
             "globals",
             Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
         ),
-        ("bounds-check-zero", Targets::SPIRV | Targets::METAL),
-        ("bounds-check-zero-atomic", Targets::METAL),
-        ("bounds-check-restrict", Targets::SPIRV | Targets::METAL),
+        (
+            "bounds-check-zero",
+            Targets::SPIRV | Targets::METAL | Targets::HLSL,
+        ),
+        ("bounds-check-zero-atomic", Targets::METAL | Targets::HLSL),
+        ("optimize", Targets::WGSL | Targets::GLSL),
+        (
+            "bounds-check-restrict",
+            Targets::SPIRV | Targets::METAL | Targets::HLSL,
+        ),
         (
             "bounds-check-image-restrict",
-            Targets::SPIRV | Targets::METAL | Targets::GLSL,
+            Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL,
         ),
         (
             "bounds-check-image-rzsw",
-            Targets::SPIRV | Targets::METAL | Targets::GLSL,
+            Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL,
         ),
         ("policy-mix", Targets::SPIRV | Targets::METAL),
         (
@@ -868,7 +958,11 @@ fn convert_wgsl() {
         ("sprite", Targets::SPIRV),
         ("force_point_size_vertex_shader_webgl", Targets::GLSL),
         ("invariant", Targets::GLSL),
-        ("ray-query", Targets::SPIRV | Targets::METAL),
+        ("ray-query", Targets::SPIRV | Targets::METAL | Targets::HLSL),
+        (
+            "ray-query-candidate",
+            Targets::SPIRV | Targets::METAL | Targets::HLSL,
+        ),
         ("hlsl-keyword", Targets::HLSL),
         (
             "constructors",
@@ -880,6 +974,15 @@ fn convert_wgsl() {
             Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
         ),
         ("const_assert", Targets::WGSL | Targets::IR),
+        (
+            "const-builtins",
+            Targets::IR
+                | Targets::SPIRV
+                | Targets::METAL
+                | Targets::GLSL
+                | Targets::HLSL
+                | Targets::WGSL,
+        ),
         ("separate-entry-points", Targets::SPIRV | Targets::GLSL),
         (
             "struct-layout",
@@ -889,6 +992,14 @@ fn convert_wgsl() {
             "f64",
             Targets::SPIRV | Targets::GLSL | Targets::HLSL | Targets::WGSL,
         ),
+        (
+            "f16",
+            Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
+        ),
+        (
+            "clip-distances",
+            Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
+        ),
         (
             "abstract-types-const",
             Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::WGSL,
@@ -909,6 +1020,14 @@ fn convert_wgsl() {
             "subgroup-operations",
             Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
         ),
+        (
+            "subgroup-quad-clustered",
+            Targets::SPIRV | Targets::METAL | Targets::GLSL | Targets::HLSL | Targets::WGSL,
+        ),
+        (
+            "mesh-shader",
+            Targets::SPIRV | Targets::METAL | Targets::HLSL | Targets::WGSL,
+        ),
         (
             "overrides",
             Targets::IR
@@ -916,11 +1035,12 @@ fn convert_wgsl() {
                 | Targets::SPIRV
                 | Targets::METAL
                 | Targets::HLSL
-                | Targets::GLSL,
+                | Targets::GLSL
+                | Targets::WGSL,
         ),
         (
             "overrides-atomicCompareExchangeWeak",
-            Targets::IR | Targets::SPIRV | Targets::METAL,
+            Targets::IR | Targets::SPIRV | Targets::METAL | Targets::HLSL | Targets::WGSL,
         ),
         (
             "overrides-ray-query",
@@ -1063,6 +1183,7 @@ fn convert_spv_all() {
     convert_spv("builtin-accessed-outside-entrypoint", true, Targets::WGSL);
     convert_spv("spec-constants", true, Targets::IR);
     convert_spv("spec-constants-issue-5598", true, Targets::GLSL);
+    convert_spv("spec-constant-op", false, Targets::WGSL);
     convert_spv(
         "subgroup-operations-s",
         false,
@@ -1074,6 +1195,7 @@ fn convert_spv_all() {
         false,
         Targets::IR | Targets::SPIRV | Targets::METAL | Targets::HLSL | Targets::WGSL,
     );
+    convert_spv("unstructured-control-flow", false, Targets::WGSL);
 }
 
 #[cfg(feature = "glsl-in")]
@@ -1087,6 +1209,7 @@ fn convert_glsl_variations_check() {
             &naga::front::glsl::Options {
                 stage: naga::ShaderStage::Fragment,
                 defines: Default::default(),
+                include_resolver: None,
             },
             &source,
         )
@@ -1122,6 +1245,7 @@ fn convert_glsl_folder() {
                         ext => panic!("Unknown extension for glsl file {ext}"),
                     },
                     defines: Default::default(),
+                    include_resolver: None,
                 },
                 &input.read_source(),
             )
@@ -1136,7 +1260,13 @@ fn convert_glsl_folder() {
 
         #[cfg(wgsl_out)]
         {
-            write_output_wgsl(&input, &module, &info, &WgslOutParameters::default());
+            write_output_wgsl(
+                &input,
+                &module,
+                &info,
+                &WgslOutParameters::default(),
+                &naga::back::PipelineConstants::default(),
+            );
         }
     }
 }
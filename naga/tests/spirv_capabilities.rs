@@ -208,3 +208,23 @@ fn int64() {
         "#,
     );
 }
+
+#[test]
+fn packed_integer_dot_product() {
+    require(
+        &[Ca::DotProduct, Ca::DotProductInput4x8BitPacked],
+        r#"
+            fn f(a: u32, b: u32) -> i32 {
+                return dot4I8Packed(a, b);
+            }
+        "#,
+    );
+    require(
+        &[Ca::DotProduct, Ca::DotProductInput4x8BitPacked],
+        r#"
+            fn f(a: u32, b: u32) -> u32 {
+                return dot4U8Packed(a, b);
+            }
+        "#,
+    );
+}
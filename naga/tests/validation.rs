@@ -269,47 +269,77 @@ fn bad_cross_builtin_args() {
         (
             "vec2(0., 1.)",
             "\
-error: Entry point main at Compute is invalid
+error: Cannot apply math function to type
   ┌─ wgsl:3:13
   │
 3 │     let a = cross(vec2(0., 1.), vec2(0., 1.));
+  │             ^^^^^ see msg
+
+",
+            "\
+error: Entry point main at Compute is invalid
+  ┌─ wgsl:4:13
+  │
+4 │     let a = cross(x, x);
   │             ^^^^^ naga::Expression [6]
   │
   = Expression [6] is invalid
-  = Argument [0] to Cross as expression [2] has an invalid type.
+  = Argument [0] to Cross as expression [4] has an invalid type.
 
 ",
         ),
         (
             "vec4(0., 1., 2., 3.)",
             "\
-error: Entry point main at Compute is invalid
+error: Cannot apply math function to type
   ┌─ wgsl:3:13
   │
 3 │     let a = cross(vec4(0., 1., 2., 3.), vec4(0., 1., 2., 3.));
-  │             ^^^^^ naga::Expression [10]
+  │             ^^^^^ see msg
+
+",
+            "\
+error: Entry point main at Compute is invalid
+  ┌─ wgsl:4:13
   │
-  = Expression [10] is invalid
-  = Argument [0] to Cross as expression [4] has an invalid type.
+4 │     let a = cross(x, x);
+  │             ^^^^^ naga::Expression [8]
+  │
+  = Expression [8] is invalid
+  = Argument [0] to Cross as expression [6] has an invalid type.
 
 ",
         ),
     ];
 
-    for (invalid_arg, expected_err) in cases {
+    for (invalid_arg, expected_const_err, expected_runtime_err) in cases {
+        // Constant arguments are rejected by constant evaluation.
         let source = format!(
             "\
 @compute @workgroup_size(1)
 fn main() {{
     let a = cross({invalid_arg}, {invalid_arg});
 }}
+"
+        );
+        let err = naga::front::wgsl::parse_str(&source).expect_err("module should be invalid");
+        assert_eq!(err.emit_to_string(&source), expected_const_err);
+
+        // Runtime arguments are rejected by the validator.
+        let source = format!(
+            "\
+@compute @workgroup_size(1)
+fn main() {{
+    var x = {invalid_arg};
+    let a = cross(x, x);
+}}
 "
         );
         let module = naga::front::wgsl::parse_str(&source).unwrap();
         let err = valid::Validator::new(Default::default(), valid::Capabilities::all())
             .validate_no_overrides(&module)
             .expect_err("module should be invalid");
-        assert_eq!(err.emit_to_string(&source), expected_err);
+        assert_eq!(err.emit_to_string(&source), expected_runtime_err);
     }
 }
 
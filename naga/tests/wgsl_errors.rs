@@ -582,6 +582,8 @@ fn local_var_missing_type() {
 
 #[test]
 fn postfix_pointers() {
+    // With `pointer_composite_access`, postfix accesses bind tighter than `*`,
+    // so these dereference the accessed component, which isn't a pointer.
     check(
         r#"
             fn main() {
@@ -590,11 +592,11 @@ fn postfix_pointers() {
                 let a = *pv[3]; // Problematic line
             }
         "#,
-        r#"error: the value indexed by a `[]` subscripting expression must not be a pointer
-  ┌─ wgsl:5:26
+        r#"error: the operand of the `*` operator must be a pointer
+  ┌─ wgsl:5:25
   │
 5 │                 let a = *pv[3]; // Problematic line
-  │                          ^^ expression is a pointer
+  │                         ^^^^^^ expression is not a pointer
 
 "#,
     );
@@ -608,11 +610,11 @@ fn postfix_pointers() {
                 let a = *ps.m; // Problematic line
             }
         "#,
-        r#"error: the value accessed by a `.member` expression must not be a pointer
-  ┌─ wgsl:6:26
+        r#"error: the operand of the `*` operator must be a pointer
+  ┌─ wgsl:6:25
   │
 6 │                 let a = *ps.m; // Problematic line
-  │                          ^^ expression is a pointer
+  │                         ^^^^^ expression is not a pointer
 
 "#,
     );
@@ -1151,7 +1153,8 @@ fn invalid_functions() {
         })
     }
 
-    // Pointers of these address spaces cannot be passed as arguments.
+    // Pointers of these address spaces cannot be passed as arguments, unless
+    // the validator allows unrestricted pointer parameters.
     check_validation! {
         "fn unacceptable_ptr_space(arg: ptr<storage, array<f32>>) { }":
         Err(naga::valid::ValidationError::Function {
@@ -1191,6 +1194,29 @@ fn invalid_functions() {
         })
         if function_name == "unacceptable_ptr_space" && argument_name == "arg"
     }
+    check_validation! {
+        "fn acceptable_ptr_space(arg: ptr<storage, array<f32>>) { }",
+        "fn acceptable_ptr_space(arg: ptr<uniform, f32>) { }",
+        "fn acceptable_ptr_space(arg: ptr<workgroup, f32>) { }":
+        Ok(_),
+        naga::valid::Capabilities::UNRESTRICTED_POINTER_PARAMETERS
+    }
+
+    // Pointers of this address space cannot be passed as arguments.
+    check_validation! {
+        "fn unacceptable_ptr_space(arg: ptr<push_constant, f32>) { }":
+        Err(naga::valid::ValidationError::Function {
+            name: function_name,
+            source: naga::valid::FunctionError::InvalidArgumentPointerSpace {
+                index: 0,
+                name: argument_name,
+                space: naga::AddressSpace::PushConstant,
+            },
+            ..
+        })
+        if function_name == "unacceptable_ptr_space" && argument_name == "arg",
+        naga::valid::Capabilities::PUSH_CONSTANT
+    }
 
     check_validation! {
         "
@@ -1473,10 +1499,7 @@ fn dead_code() {
             return 2;
         }
         ":
-        Err(naga::valid::ValidationError::Function {
-            source: naga::valid::FunctionError::InstructionsAfterReturn,
-            ..
-        })
+        Ok(_)
     }
 }
 
@@ -2429,3 +2452,965 @@ fn const_assert_failed() {
 "###,
     );
 }
+
+#[test]
+fn f16_requires_enable() {
+    check(
+        "
+            var<private> x: f16;
+        ",
+        r###"error: `f16` enable-extension is not enabled
+  ┌─ wgsl:2:29
+  │
+2 │             var<private> x: f16;
+  │                             ^^^ the `f16` enable-extension is needed for this functionality, but it is not currently enabled
+  │
+  = note: You can enable this extension by adding `enable f16;` at the top of the shader.
+
+"###,
+    );
+    check(
+        "
+            const x = 1.5h;
+        ",
+        r###"error: `f16` enable-extension is not enabled
+  ┌─ wgsl:2:23
+  │
+2 │             const x = 1.5h;
+  │                       ^^^^ the `f16` enable-extension is needed for this functionality, but it is not currently enabled
+  │
+  = note: You can enable this extension by adding `enable f16;` at the top of the shader.
+
+"###,
+    );
+    check(
+        "
+            const x = vec2h();
+        ",
+        r###"error: `f16` enable-extension is not enabled
+  ┌─ wgsl:2:23
+  │
+2 │             const x = vec2h();
+  │                       ^^^^^ the `f16` enable-extension is needed for this functionality, but it is not currently enabled
+  │
+  = note: You can enable this extension by adding `enable f16;` at the top of the shader.
+
+"###,
+    );
+}
+
+#[test]
+fn f16_literal_out_of_range() {
+    check(
+        "
+            enable f16;
+            const x = 65520h;
+        ",
+        r###"error: numeric literal not representable by target type: `65520h`
+  ┌─ wgsl:3:23
+  │
+3 │             const x = 65520h;
+  │                       ^^^^^^ numeric literal not representable by target type
+
+"###,
+    );
+}
+
+#[test]
+fn f16_cast_out_of_range() {
+    check(
+        "
+            enable f16;
+            const x = f16(65536u);
+        ",
+        r###"error: value `65536.0` is outside the range of `f16`
+  ┌─ wgsl:3:23
+  │
+3 │             const x = f16(65536u);
+  │                       ^^^^^^^^^^^ see msg
+
+"###,
+    );
+}
+
+#[test]
+fn clip_distances_requires_enable() {
+    check(
+        "
+            struct VertexOutput {
+                @builtin(position) position: vec4<f32>,
+                @builtin(clip_distances) clip_distances: array<f32, 1>,
+            }
+        ",
+        r###"error: `clip_distances` enable-extension is not enabled
+  ┌─ wgsl:4:26
+  │
+4 │                 @builtin(clip_distances) clip_distances: array<f32, 1>,
+  │                          ^^^^^^^^^^^^^^ the `clip_distances` enable-extension is needed for this functionality, but it is not currently enabled
+  │
+  = note: You can enable this extension by adding `enable clip_distances;` at the top of the shader.
+
+"###,
+    );
+}
+
+#[test]
+fn too_many_clip_distances() {
+    check_validation! {
+        "
+        enable clip_distances;
+
+        struct VertexOutput {
+            @builtin(position) position: vec4<f32>,
+            @builtin(clip_distances) clip_distances: array<f32, 9>,
+        }
+
+        @vertex
+        fn main() -> VertexOutput {
+            var out: VertexOutput;
+            return out;
+        }
+        ":
+        Err(naga::valid::ValidationError::EntryPoint {
+            stage: naga::ShaderStage::Vertex,
+            source: naga::valid::EntryPointError::Result(
+                naga::valid::VaryingError::TooManyClipDistances {
+                    count: 9,
+                    limit: 8,
+                },
+            ),
+            ..
+        }),
+        naga::valid::Capabilities::CLIP_DISTANCE
+    }
+
+    // The limit comes from the caller.
+    let source = "
+        enable clip_distances;
+
+        struct VertexOutput {
+            @builtin(position) position: vec4<f32>,
+            @builtin(clip_distances) clip_distances: array<f32, 6>,
+        }
+
+        @vertex
+        fn main() -> VertexOutput {
+            var out: VertexOutput;
+            return out;
+        }
+    ";
+    let module = naga::front::wgsl::parse_str(source).unwrap();
+    let mut validator = naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::CLIP_DISTANCE,
+    );
+    validator.validate(&module).unwrap();
+    let error = validator
+        .max_clip_distances(4)
+        .validate(&module)
+        .unwrap_err()
+        .into_inner();
+    assert!(matches!(
+        error,
+        naga::valid::ValidationError::EntryPoint {
+            source: naga::valid::EntryPointError::Result(
+                naga::valid::VaryingError::TooManyClipDistances { count: 6, limit: 4 },
+            ),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn blend_src_requires_enable() {
+    check(
+        "
+            struct FragmentOutput {
+                @location(0) @blend_src(0) color: vec4<f32>,
+            }
+        ",
+        r###"error: `dual_source_blending` enable-extension is not enabled
+  ┌─ wgsl:3:31
+  │
+3 │                 @location(0) @blend_src(0) color: vec4<f32>,
+  │                               ^^^^^^^^^ the `dual_source_blending` enable-extension is needed for this functionality, but it is not currently enabled
+  │
+  = note: You can enable this extension by adding `enable dual_source_blending;` at the top of the shader.
+
+"###,
+    );
+}
+
+#[test]
+fn invalid_blend_src() {
+    check(
+        "
+            enable dual_source_blending;
+            struct FragmentOutput {
+                @location(0) @blend_src(2) color: vec4<f32>,
+            }
+            @fragment
+            fn main() -> FragmentOutput {
+                return FragmentOutput(vec4(0.0));
+            }
+        ",
+        r###"error: `@blend_src` index must be 0 or 1
+  ┌─ wgsl:4:41
+  │
+4 │                 @location(0) @blend_src(2) color: vec4<f32>,
+  │                                         ^ must be 0 or 1
+
+"###,
+    );
+}
+
+#[test]
+fn dual_source_blending_type_mismatch() {
+    check_validation! {
+        "
+        enable dual_source_blending;
+        struct FragmentOutput {
+            @location(0) @blend_src(0) color: vec4<f32>,
+            @location(0) @blend_src(1) mask: vec3<f32>,
+        }
+        @fragment
+        fn main() -> FragmentOutput {
+            return FragmentOutput(vec4(0.0), vec3(0.0));
+        }
+        ":
+        Err(naga::valid::ValidationError::EntryPoint {
+            stage: naga::ShaderStage::Fragment,
+            source: naga::valid::EntryPointError::DualSourceBlendingTypeMismatch(_, _),
+            ..
+        }),
+        naga::valid::Capabilities::DUAL_SOURCE_BLENDING
+    }
+}
+
+#[test]
+fn blend_src_outside_struct() {
+    check(
+        "
+        enable dual_source_blending;
+        @fragment
+        fn main() -> @location(0) @blend_src(0) vec4<f32> {
+            return vec4(0.0);
+        }
+    ",
+        r###"error: `@blend_src` can only be used on struct members
+  ┌─ wgsl:4:46
+  │
+4 │         fn main() -> @location(0) @blend_src(0) vec4<f32> {
+  │                                              ^ not a struct member
+
+"###,
+    );
+}
+
+#[test]
+fn blend_src_with_other_location() {
+    check(
+        "
+        enable dual_source_blending;
+        struct FragmentOutput {
+            @location(0) @blend_src(0) a: vec4<f32>,
+            @location(1) b: vec4<f32>,
+        }
+    ",
+        r###"error: struct `FragmentOutput` uses `@blend_src`, so its location outputs must be exactly one `@location(0) @blend_src(0)` and one `@location(0) @blend_src(1)` member
+  ┌─ wgsl:3:16
+  │
+3 │         struct FragmentOutput {
+  │                ^^^^^^^^^^^^^^ struct uses `@blend_src`
+4 │             @location(0) @blend_src(0) a: vec4<f32>,
+5 │             @location(1) b: vec4<f32>,
+  │                          ^ not one of the two blend sources
+
+"###,
+    );
+}
+
+#[test]
+fn blend_src_unpaired() {
+    check(
+        "
+        enable dual_source_blending;
+        struct FragmentOutput {
+            @location(0) @blend_src(1) b: vec4<f32>,
+        }
+    ",
+        r###"error: struct `FragmentOutput` uses `@blend_src`, but has no `@location(0) @blend_src(0)` member
+  ┌─ wgsl:3:16
+  │
+3 │         struct FragmentOutput {
+  │                ^^^^^^^^^^^^^^ missing `@blend_src(0)`
+
+"###,
+    );
+}
+
+#[test]
+fn blend_src_duplicated() {
+    check(
+        "
+        enable dual_source_blending;
+        struct FragmentOutput {
+            @location(0) @blend_src(0) a: vec4<f32>,
+            @location(0) @blend_src(0) b: vec4<f32>,
+        }
+    ",
+        r###"error: struct `FragmentOutput` uses `@blend_src`, so its location outputs must be exactly one `@location(0) @blend_src(0)` and one `@location(0) @blend_src(1)` member
+  ┌─ wgsl:3:16
+  │
+3 │         struct FragmentOutput {
+  │                ^^^^^^^^^^^^^^ struct uses `@blend_src`
+4 │             @location(0) @blend_src(0) a: vec4<f32>,
+5 │             @location(0) @blend_src(0) b: vec4<f32>,
+  │                                        ^ not one of the two blend sources
+
+"###,
+    );
+}
+
+#[test]
+fn blend_src_not_at_location_zero() {
+    check(
+        "
+        enable dual_source_blending;
+        struct FragmentOutput {
+            @location(0) @blend_src(0) a: vec4<f32>,
+            @location(1) @blend_src(1) b: vec4<f32>,
+        }
+    ",
+        r###"error: struct `FragmentOutput` uses `@blend_src`, so its location outputs must be exactly one `@location(0) @blend_src(0)` and one `@location(0) @blend_src(1)` member
+  ┌─ wgsl:3:16
+  │
+3 │         struct FragmentOutput {
+  │                ^^^^^^^^^^^^^^ struct uses `@blend_src`
+4 │             @location(0) @blend_src(0) a: vec4<f32>,
+5 │             @location(1) @blend_src(1) b: vec4<f32>,
+  │                                        ^ not one of the two blend sources
+
+"###,
+    );
+}
+
+#[test]
+fn dual_source_blending_missing_first_source() {
+    check_validation! {
+        "
+        struct FragmentOutput {
+            @location(0) @second_blend_source mask: vec4<f32>,
+        }
+        @fragment
+        fn main() -> FragmentOutput {
+            return FragmentOutput(vec4(0.0));
+        }
+        ":
+        Err(naga::valid::ValidationError::EntryPoint {
+            stage: naga::ShaderStage::Fragment,
+            source: naga::valid::EntryPointError::MissingFirstBlendSource,
+            ..
+        }),
+        naga::valid::Capabilities::DUAL_SOURCE_BLENDING
+    }
+}
+
+#[test]
+fn packed_dot_product_argument_type() {
+    check(
+        "
+        fn f(a: i32, b: u32) -> i32 {
+            return dot4I8Packed(a, b);
+        }
+    ",
+        r###"error: automatic conversions cannot convert `i32` to `u32`
+  ┌─ wgsl:2:14
+  │
+2 │         fn f(a: i32, b: u32) -> i32 {
+  │              ^ this expression has type i32
+3 │             return dot4I8Packed(a, b);
+  │                    ^^^^^^^^^^^^ a value of type u32 is required here
+
+"###,
+    );
+}
+
+#[test]
+fn const_extract_bits_out_of_range() {
+    check(
+        "
+            const a = extractBits(1u, 30u, 4u);
+        ",
+        r###"error: `offset` plus `count` exceeds the bit width for the ExtractBits built-in function
+  ┌─ wgsl:2:23
+  │
+2 │             const a = extractBits(1u, 30u, 4u);
+  │                       ^^^^^^^^^^^ see msg
+
+"###,
+    );
+}
+
+#[test]
+fn const_bitcast_to_nan() {
+    check(
+        "
+            const a = bitcast<f32>(0x7fc00000u);
+        ",
+        r###"error: Float literal is NaN
+  ┌─ wgsl:2:23
+  │
+2 │             const a = bitcast<f32>(0x7fc00000u);
+  │                       ^^^^^^^^^^^^^^^^^^^^^^^^^ see msg
+
+"###,
+    );
+}
+
+#[test]
+fn mesh_shader_requires_enable() {
+    check(
+        "
+            @task @workgroup_size(1)
+            fn main() -> @builtin(mesh_task_size) vec3<u32> {
+                return vec3(1u);
+            }
+        ",
+        r###"error: `wgpu_mesh_shader` enable-extension is not enabled
+  ┌─ wgsl:2:14
+  │
+2 │             @task @workgroup_size(1)
+  │              ^^^^ the `wgpu_mesh_shader` enable-extension is needed for this functionality, but it is not currently enabled
+  │
+  = note: You can enable this extension by adding `enable wgpu_mesh_shader;` at the top of the shader.
+
+"###,
+    );
+}
+
+#[test]
+fn mesh_shader_missing_output_attribute() {
+    check(
+        "
+            enable wgpu_mesh_shader;
+            struct PrimitiveOutput {
+                @builtin(triangle_indices) indices: vec3<u32>,
+            }
+            @mesh @workgroup_size(1)
+            @output_topology(triangles)
+            @primitive_output(PrimitiveOutput, 1)
+            fn main() {}
+        ",
+        r###"error: mesh shader entry point is missing a `@vertex_output` attribute
+  ┌─ wgsl:6:14
+  │
+6 │             @mesh @workgroup_size(1)
+  │              ^^^^ must be paired with a @vertex_output attribute
+
+"###,
+    );
+}
+
+#[test]
+fn task_shader_missing_mesh_task_size() {
+    check_validation! {
+        "
+        enable wgpu_mesh_shader;
+        @task @workgroup_size(1)
+        fn main() {}
+        ":
+        Err(naga::valid::ValidationError::EntryPoint {
+            stage: naga::ShaderStage::Task,
+            source: naga::valid::EntryPointError::MissingTaskOutputMeshTaskSize,
+            ..
+        }),
+        naga::valid::Capabilities::MESH_SHADER
+    }
+}
+
+#[test]
+fn mesh_shader_missing_primitive_indices() {
+    check_validation! {
+        "
+        enable wgpu_mesh_shader;
+        struct VertexOutput {
+            @builtin(position) position: vec4<f32>,
+        }
+        struct PrimitiveOutput {
+            @builtin(cull_primitive) cull: bool,
+        }
+        @mesh @workgroup_size(1)
+        @output_topology(triangles)
+        @vertex_output(VertexOutput, 3)
+        @primitive_output(PrimitiveOutput, 1)
+        fn main() {}
+        ":
+        Err(naga::valid::ValidationError::EntryPoint {
+            stage: naga::ShaderStage::Mesh,
+            source: naga::valid::EntryPointError::MissingMeshPrimitiveIndices(
+                naga::BuiltIn::TriangleIndices
+            ),
+            ..
+        }),
+        naga::valid::Capabilities::MESH_SHADER
+    }
+}
+
+#[test]
+fn mesh_shader_capability() {
+    check_validation! {
+        "
+        enable wgpu_mesh_shader;
+        @task @workgroup_size(1)
+        fn main() -> @builtin(mesh_task_size) vec3<u32> {
+            return vec3(1u);
+        }
+        ":
+        Err(naga::valid::ValidationError::EntryPoint {
+            stage: naga::ShaderStage::Task,
+            source: naga::valid::EntryPointError::UnsupportedCapability(
+                naga::valid::Capabilities::MESH_SHADER
+            ),
+            ..
+        })
+    }
+}
+
+#[test]
+fn subgroup_quad_and_clustered() {
+    check_validation! {
+        "
+        @compute @workgroup_size(1)
+        fn main(@builtin(subgroup_invocation_id) id: u32) {
+            quadSwapX(id);
+        }
+        ":
+        Err(naga::valid::ValidationError::EntryPoint {
+            source: naga::valid::EntryPointError::Function(
+                naga::valid::FunctionError::InvalidSubgroup(
+                    naga::valid::SubgroupError::UnsupportedOperation(
+                        naga::valid::SubgroupOperationSet::QUAD_FRAGMENT_COMPUTE
+                    )
+                )
+            ),
+            ..
+        }),
+        naga::valid::Capabilities::SUBGROUP
+    }
+
+    check_validation! {
+        "
+        @compute @workgroup_size(1)
+        fn main(@builtin(subgroup_invocation_id) id: u32) {
+            subgroupClusteredAdd(id, 4u);
+        }
+        ":
+        Err(naga::valid::ValidationError::EntryPoint {
+            source: naga::valid::EntryPointError::Function(
+                naga::valid::FunctionError::InvalidSubgroup(
+                    naga::valid::SubgroupError::UnsupportedOperation(
+                        naga::valid::SubgroupOperationSet::CLUSTERED
+                    )
+                )
+            ),
+            ..
+        }),
+        naga::valid::Capabilities::SUBGROUP
+    }
+
+    check_validation! {
+        "
+        @compute @workgroup_size(1)
+        fn main(@builtin(subgroup_invocation_id) id: u32) {
+            quadBroadcast(id, 4u);
+        }
+        ":
+        Err(naga::valid::ValidationError::EntryPoint {
+            source: naga::valid::EntryPointError::Function(
+                naga::valid::FunctionError::InvalidSubgroup(
+                    naga::valid::SubgroupError::InvalidQuadIndex(_)
+                )
+            ),
+            ..
+        }),
+        naga::valid::Capabilities::SUBGROUP | naga::valid::Capabilities::SUBGROUP_QUAD
+    }
+
+    check_validation! {
+        "
+        const lane = 7u;
+
+        @compute @workgroup_size(1)
+        fn main(@builtin(subgroup_invocation_id) id: u32) {
+            quadBroadcast(id, lane);
+        }
+        ":
+        Err(naga::valid::ValidationError::EntryPoint {
+            source: naga::valid::EntryPointError::Function(
+                naga::valid::FunctionError::InvalidSubgroup(
+                    naga::valid::SubgroupError::InvalidQuadIndex(_)
+                )
+            ),
+            ..
+        }),
+        naga::valid::Capabilities::SUBGROUP | naga::valid::Capabilities::SUBGROUP_QUAD
+    }
+
+    check(
+        "
+        @compute @workgroup_size(1)
+        fn main(@builtin(subgroup_invocation_id) id: u32) {
+            quadBroadcast(id, id);
+        }
+        ",
+        r###"error: must be a const-expression that resolves to a concrete integer scalar (u32 or i32)
+  ┌─ wgsl:4:31
+  │
+4 │             quadBroadcast(id, id);
+  │                               ^^ must resolve to u32 or i32
+
+"###,
+    );
+
+    check(
+        "
+        @compute @workgroup_size(1)
+        fn main(@builtin(subgroup_invocation_id) id: u32) {
+            subgroupClusteredAdd(id, 3u);
+        }
+        ",
+        r###"error: cluster size must be a power of two
+  ┌─ wgsl:4:38
+  │
+4 │             subgroupClusteredAdd(id, 3u);
+  │                                      ^^ must be a power of two
+
+"###,
+    );
+}
+
+#[test]
+fn ray_query_generate_intersection() {
+    check_validation! {
+        "
+        @group(0) @binding(0)
+        var acc_struct: acceleration_structure;
+
+        fn trace(t: f32) {
+            var rq: ray_query;
+            rayQueryInitialize(&rq, acc_struct, RayDesc(0u, 0xFFu, 0.1, 100.0, vec3f(), vec3f(0.0, 1.0, 0.0)));
+            while (rayQueryProceed(&rq)) {
+                rayQueryGenerateIntersection(&rq, t);
+            }
+        }
+        ":
+        Ok(_),
+        naga::valid::Capabilities::RAY_QUERY
+    }
+
+    check_validation! {
+        "
+        @group(0) @binding(0)
+        var acc_struct: acceleration_structure;
+
+        fn trace(t: u32) {
+            var rq: ray_query;
+            rayQueryInitialize(&rq, acc_struct, RayDesc(0u, 0xFFu, 0.1, 100.0, vec3f(), vec3f(0.0, 1.0, 0.0)));
+            while (rayQueryProceed(&rq)) {
+                rayQueryGenerateIntersection(&rq, t);
+            }
+        }
+        ":
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::InvalidHitDistanceType(_),
+            ..
+        }),
+        naga::valid::Capabilities::RAY_QUERY
+    }
+}
+
+#[test]
+fn lint_rules() {
+    check_validation! {
+        "
+        diagnostic(error, unused_variable);
+        fn f() {
+            var x = 1;
+            x = 2;
+        }
+        ":
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::UnusedLocalVariable { name, .. },
+            ..
+        })
+        if name == "x"
+    }
+
+    check_validation! {
+        "
+        diagnostic(error, unused_variable);
+        fn f(a: i32, _b: i32) -> i32 {
+            var _c = 1;
+            var d = array(1, 2);
+            d[1] = a;
+            return d[0];
+        }
+        ":
+        Ok(_)
+    }
+
+    check_validation! {
+        "
+        diagnostic(error, unused_variable);
+        fn f(a: i32, b: i32) -> i32 {
+            return a;
+        }
+        ":
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::UnusedArgument { index: 1, name },
+            ..
+        })
+        if name == "b"
+    }
+
+    check_validation! {
+        "
+        diagnostic(error, shadowed_declaration);
+        const x = 1;
+        fn f() -> i32 {
+            let x = 2;
+            return x;
+        }
+        ",
+        "
+        diagnostic(error, shadowed_declaration);
+        fn f(x: i32) -> i32 {
+            {
+                var x = 1;
+                return x;
+            }
+        }
+        ":
+        Err(naga::valid::ValidationError::Function {
+            source: naga::valid::FunctionError::ShadowedDeclaration { name },
+            ..
+        })
+        if name == "x"
+    }
+
+    // These rules are off by default.
+    check_validation! {
+        "
+        const x = 1;
+        fn f(a: i32) {
+            var x = 2;
+            return;
+            x = 3;
+        }
+        ":
+        Ok(_)
+    }
+}
+
+#[test]
+fn lint_warnings() {
+    let source = "
+        diagnostic(warning, unused_variable);
+        const x = 1;
+        @diagnostic(info, shadowed_declaration)
+        fn f() {
+            var x = 2;
+            x = 3;
+        }
+    ";
+    let info = validation_error(source, naga::valid::Capabilities::default()).unwrap();
+    let warnings: Vec<_> = info
+        .warnings()
+        .iter()
+        .map(|warning| {
+            let naga::valid::ValidationError::Function { ref source, .. } =
+                *warning.error.as_inner()
+            else {
+                panic!("unexpected warning: {:?}", warning.error);
+            };
+            (warning.rule, warning.severity, source.to_string())
+        })
+        .collect();
+    assert_eq!(
+        warnings,
+        [
+            (
+                naga::diagnostic_filter::StandardFilterableTriggeringRule::UnusedVariable,
+                naga::diagnostic_filter::Severity::Warning,
+                "Local variable [0] 'x' is never read".to_string(),
+            ),
+            (
+                naga::diagnostic_filter::StandardFilterableTriggeringRule::ShadowedDeclaration,
+                naga::diagnostic_filter::Severity::Info,
+                "Declaration of 'x' shadows another declaration".to_string(),
+            ),
+        ]
+    );
+    assert_eq!(
+        info.warnings()[0].emit_to_string_with_path(source, "wgsl"),
+        r###"warning: Local variable [0] 'x' is never read
+  ┌─ wgsl:6:13
+  │
+6 │             var x = 2;
+  │             ^^^^^^^^^^ naga::LocalVariable [0]
+  │
+  = in function 'f'
+
+"###,
+    );
+}
+
+#[test]
+fn precision_loss() {
+    check(
+        "
+        diagnostic(error, precision_loss);
+        const a: f32 = 0.5;
+        const b: f32 = 16777217;
+        ",
+        r###"error: `16777217` cannot be represented exactly as `f32`
+  ┌─ wgsl:4:24
+  │
+4 │         const b: f32 = 16777217;
+  │                        ^^^^^^^^ this is rounded to `16777216`
+  │
+  = note: This is reported because of the `precision_loss` diagnostic rule. Use `diagnostic(off, precision_loss)` to allow it.
+
+"###,
+    );
+
+    check(
+        "
+        diagnostic(error, precision_loss);
+        fn f() -> vec2f {
+            return vec2(0.1, 3.14159265358979);
+        }
+        ",
+        r###"error: `3.14159265358979` cannot be represented exactly as `f32`
+  ┌─ wgsl:4:20
+  │
+4 │             return vec2(0.1, 3.14159265358979);
+  │                    ^^^^^^^^^^^^^^^^^^^^^^^^^^^ this is rounded to `3.1415927`
+  │
+  = note: This is reported because of the `precision_loss` diagnostic rule. Use `diagnostic(off, precision_loss)` to allow it.
+
+"###,
+    );
+
+    // Off by default.
+    naga::front::wgsl::parse_str("const b: f32 = 16777217;").unwrap();
+}
+
+#[test]
+fn unreachable_code() {
+    check(
+        "
+        diagnostic(error, unreachable_code);
+        fn f() -> i32 {
+            {
+                return 1;
+            }
+            let x = 2;
+            return x;
+        }
+        ",
+        r###"error: code is unreachable
+  ┌─ wgsl:7:13
+  │
+7 │             let x = 2;
+  │             ^^^^^^^^^^ this statement is never executed
+  │
+  = note: This is reported because of the `unreachable_code` diagnostic rule. Use `diagnostic(off, unreachable_code)` to allow it.
+
+"###,
+    );
+
+    // Unreachable statements are still checked for errors.
+    check(
+        "
+        fn f() -> i32 {
+            return 1;
+            let x: f32 = undefined_name + true;
+        }
+        ",
+        r###"error: no definition in scope for identifier: 'undefined_name'
+  ┌─ wgsl:4:26
+  │
+4 │             let x: f32 = undefined_name + true;
+  │                          ^^^^^^^^^^^^^^ unknown identifier
+
+"###,
+    );
+
+    // The unreachable statements are left out of the module, so a call after
+    // `break` doesn't leave a dangling `CallResult`.
+    check_validation! {
+        "
+        fn g() -> i32 { return 1; }
+        fn f() {
+            loop {
+                break;
+                let x = g();
+            }
+        }
+        ":
+        Ok(_)
+    }
+}
+
+#[test]
+fn frontend_warnings() {
+    let source = "
+        diagnostic(warning, precision_loss);
+        const b: f32 = 16777217;
+        @diagnostic(info, unreachable_code)
+        fn f() -> i32 {
+            return 1;
+            let x = 2;
+        }
+    ";
+    let mut frontend = naga::front::wgsl::Frontend::new();
+    frontend.parse(source).unwrap();
+    let warnings: Vec<_> = frontend
+        .warnings()
+        .iter()
+        .map(|warning| (warning.rule, warning.severity, warning.to_string()))
+        .collect();
+    assert_eq!(
+        warnings,
+        [
+            (
+                naga::diagnostic_filter::StandardFilterableTriggeringRule::PrecisionLoss,
+                naga::diagnostic_filter::Severity::Warning,
+                "`16777217` cannot be represented exactly as `f32`".to_string(),
+            ),
+            (
+                naga::diagnostic_filter::StandardFilterableTriggeringRule::UnreachableCode,
+                naga::diagnostic_filter::Severity::Info,
+                "code is unreachable".to_string(),
+            ),
+        ]
+    );
+    assert_eq!(
+        frontend.warnings()[0].emit_to_string(source),
+        r###"warning: `16777217` cannot be represented exactly as `f32`
+  ┌─ wgsl:3:24
+  │
+3 │         const b: f32 = 16777217;
+  │                        ^^^^^^^^ this is rounded to `16777216`
+  │
+  = note: This is reported because of the `precision_loss` diagnostic rule. Use `diagnostic(off, precision_loss)` to allow it.
+
+"###,
+    );
+
+    // Each parse only reports its own warnings.
+    frontend.parse("fn f() {}").unwrap();
+    assert!(frontend.warnings().is_empty());
+}
@@ -114,3 +114,42 @@ static BIND_GROUP_NONFILTERING_LAYOUT_MIPMAP_SAMPLER: GpuTestConfiguration =
                 false,
             );
         });
+
+/// Create a bind group layout with a single read-write storage texture of `format`.
+fn create_read_write_storage_texture_layout(
+    ctx: &TestingContext,
+    format: wgpu::TextureFormat,
+) -> wgpu::BindGroupLayout {
+    ctx.device
+        .create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("read_write_storage_texture_layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::StorageTexture {
+                    access: wgpu::StorageTextureAccess::ReadWrite,
+                    format,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                },
+                count: None,
+            }],
+        })
+}
+
+#[gpu_test]
+static BIND_GROUP_LAYOUT_READ_WRITE_STORAGE_TEXTURE_FORMAT: GpuTestConfiguration =
+    GpuTestConfiguration::new()
+        .parameters(TestParameters::default())
+        .run_sync(|ctx| {
+            // WebGPU guarantees read-write access for `r32float`.
+            wgpu_test::valid(&ctx.device, || {
+                create_read_write_storage_texture_layout(&ctx, wgpu::TextureFormat::R32Float)
+            });
+            // Other formats need `TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`, which
+            // isn't enabled here.
+            wgpu_test::fail(
+                &ctx.device,
+                || create_read_write_storage_texture_layout(&ctx, wgpu::TextureFormat::Rgba8Unorm),
+                Some("read-write storage textures of format Rgba8Unorm"),
+            );
+        });
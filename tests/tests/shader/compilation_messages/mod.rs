@@ -47,3 +47,61 @@ static SHADER_COMPILE_ERROR: GpuTestConfiguration = GpuTestConfiguration::new()
             "Expected the column number to be 33, because we're counting lines from 1"
         );
     });
+
+#[gpu_test]
+static SHADER_COMPILE_WARNING: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_async(|ctx| async move {
+        let sm = ctx
+            .device
+            .create_shader_module(include_wgsl!("warning_shader.wgsl"));
+
+        let compilation_info = sm.get_compilation_info().await;
+        let warning = |needle: &str| {
+            let message = compilation_info
+                .messages
+                .iter()
+                .find(|message| message.message.contains(needle))
+                .expect("Expected warning message not found");
+            assert_eq!(message.message_type, wgpu::CompilationMessageType::Warning);
+            message.location.expect("Expected span not found")
+        };
+
+        // Reported by the WGSL frontend.
+        let span = warning("cannot be represented exactly as `f32`");
+        assert_eq!(span.line_number, 4);
+
+        // Reported by the validator.
+        let span = warning("'unused' is never read");
+        assert_eq!(span.line_number, 8);
+    });
+
+#[gpu_test]
+static SHADER_COMPILE_MULTIPLE_ERRORS: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(TestParameters::default())
+    .run_async(|ctx| async move {
+        ctx.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let sm = ctx
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(
+                    "fn f() {\n    let a = ;\n    let b = 1 +;\n}\n".into(),
+                ),
+            });
+        assert!(pollster::block_on(ctx.device.pop_error_scope()).is_some());
+
+        let compilation_info = sm.get_compilation_info().await;
+        let lines = compilation_info
+            .messages
+            .iter()
+            .filter(|message| message.message_type == wgpu::CompilationMessageType::Error)
+            .map(|message| {
+                message
+                    .location
+                    .expect("Expected span not found")
+                    .line_number
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [2, 3]);
+    });
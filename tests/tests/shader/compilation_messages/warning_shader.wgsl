@@ -0,0 +1,9 @@
+diagnostic(warning, unused_variable);
+diagnostic(warning, precision_loss);
+
+const rounded: f32 = 16777217;
+
+@compute @workgroup_size(1)
+fn main() {
+    var unused = 1u;
+}
@@ -259,3 +259,33 @@ static INT64_POLYFILL: GpuTestConfiguration = GpuTestConfiguration::new()
         shader_input_output_test(ctx, InputStorageType::Storage, create_int64_polyfill_test())
     });
 */
+
+fn create_f16_storage_test() -> Vec<ShaderTest> {
+    let mut tests = Vec::new();
+
+    // The input is `vec2<f16>(1.5, -0.25)`, and the output `vec2<f16>(3.0, -0.5)`.
+    let test = ShaderTest::new(
+        "f16 in storage buffers".into(),
+        "value: vec2<f16>".into(),
+        "output[0] = pack2x16float(vec2<f32>(input.value * 2.0h));".into(),
+        &[0xB400_3E00_u32],
+        &[0xB800_4200_u32],
+    )
+    .header("enable f16;".into());
+
+    tests.push(test);
+
+    tests
+}
+
+#[gpu_test]
+static F16_STORAGE: GpuTestConfiguration = GpuTestConfiguration::new()
+    .parameters(
+        TestParameters::default()
+            .features(wgt::Features::SHADER_F16)
+            .downlevel_flags(DownlevelFlags::COMPUTE_SHADERS)
+            .limits(Limits::downlevel_defaults()),
+    )
+    .run_async(|ctx| {
+        shader_input_output_test(ctx, InputStorageType::Storage, create_f16_storage_test())
+    });
@@ -20,7 +20,7 @@ use crate::{
     present,
     resource::{
         self, BufferAccessError, BufferAccessResult, BufferMapOperation, CreateBufferError,
-        Fallible,
+        Fallible, InvalidResourceError,
     },
     storage::Storage,
     Label, LabelHelpers,
@@ -999,6 +999,16 @@ impl Global {
         (id, Some(error))
     }
 
+    /// Return the non-fatal diagnostics reported while parsing and validating
+    /// the shader module.
+    pub fn shader_module_get_warnings(
+        &self,
+        shader_module_id: id::ShaderModuleId,
+    ) -> Result<Vec<pipeline::ShaderModuleWarning>, InvalidResourceError> {
+        let shader_module = self.hub.shader_modules.get(shader_module_id).get()?;
+        Ok(shader_module.warnings.clone())
+    }
+
     pub fn shader_module_drop(&self, shader_module_id: id::ShaderModuleId) {
         profiling::scope!("ShaderModule::drop");
         api_log!("ShaderModule::drop {shader_module_id:?}");
@@ -474,6 +474,7 @@ impl ImplicitPipelineIds<'_> {
 pub fn create_validator(
     features: wgt::Features,
     downlevel: wgt::DownlevelFlags,
+    limits: &wgt::Limits,
     flags: naga::valid::ValidationFlags,
 ) -> naga::valid::Validator {
     use naga::valid::Capabilities as Caps;
@@ -483,6 +484,14 @@ pub fn create_validator(
         features.contains(wgt::Features::PUSH_CONSTANTS),
     );
     caps.set(Caps::FLOAT64, features.contains(wgt::Features::SHADER_F64));
+    caps.set(
+        Caps::CLIP_DISTANCE,
+        features.contains(wgt::Features::CLIP_DISTANCES),
+    );
+    caps.set(
+        Caps::SHADER_FLOAT16,
+        features.contains(wgt::Features::SHADER_F16),
+    );
     caps.set(
         Caps::PRIMITIVE_INDEX,
         features.contains(wgt::Features::SHADER_PRIMITIVE_INDEX),
@@ -546,6 +555,14 @@ pub fn create_validator(
         Caps::SUBGROUP_BARRIER,
         features.intersects(wgt::Features::SUBGROUP_BARRIER),
     );
+    caps.set(
+        Caps::SUBGROUP_QUAD,
+        features.contains(wgt::Features::SUBGROUP_QUAD),
+    );
+    caps.set(
+        Caps::SUBGROUP_CLUSTERED,
+        features.contains(wgt::Features::SUBGROUP_CLUSTERED),
+    );
     caps.set(
         Caps::RAY_QUERY,
         features.intersects(wgt::Features::EXPERIMENTAL_RAY_QUERY),
@@ -554,6 +571,11 @@ pub fn create_validator(
         Caps::SUBGROUP_VERTEX_STAGE,
         features.contains(wgt::Features::SUBGROUP_VERTEX),
     );
+    // Every backend runs `specialize_pointer_arguments` before writing the
+    // shader, so pointers to any address space may be passed to functions.
+    caps.insert(Caps::UNRESTRICTED_POINTER_PARAMETERS);
 
-    naga::valid::Validator::new(flags, caps)
+    let mut validator = naga::valid::Validator::new(flags, caps);
+    validator.max_clip_distances(limits.max_clip_distances);
+    validator
 }
@@ -1448,31 +1448,42 @@ impl Device {
     ) -> Result<Arc<pipeline::ShaderModule>, pipeline::CreateShaderModuleError> {
         self.check_is_valid()?;
 
+        let mut warnings = Vec::new();
         let (module, source) = match source {
             #[cfg(feature = "wgsl")]
             pipeline::ShaderModuleSource::Wgsl(code) => {
-                profiling::scope!("naga::front::wgsl::parse_str");
-                let module = naga::front::wgsl::parse_str(&code).map_err(|inner| {
+                profiling::scope!("naga::front::wgsl::Frontend::parse_all_errors");
+                let mut frontend = naga::front::wgsl::Frontend::new();
+                let module = frontend.parse_all_errors(&code).map_err(|inner| {
                     pipeline::CreateShaderModuleError::Parsing(naga::error::ShaderError {
                         source: code.to_string(),
                         label: desc.label.as_ref().map(|l| l.to_string()),
                         inner: Box::new(inner),
                     })
                 })?;
+                warnings.extend(frontend.warnings().iter().map(|warning| {
+                    pipeline::ShaderModuleWarning::Parsing(naga::error::ShaderError {
+                        source: code.to_string(),
+                        label: desc.label.as_ref().map(|l| l.to_string()),
+                        inner: Box::new(warning.clone()),
+                    })
+                }));
                 (Cow::Owned(module), code.into_owned())
             }
             #[cfg(feature = "spirv")]
             pipeline::ShaderModuleSource::SpirV(spv, options) => {
                 let parser = naga::front::spv::Frontend::new(spv.iter().cloned(), &options);
                 profiling::scope!("naga::front::spv::Frontend");
-                let module = parser.parse().map_err(|inner| {
+                let (module, debug_source) = parser.parse_with_debug_source().map_err(|inner| {
                     pipeline::CreateShaderModuleError::ParsingSpirV(naga::error::ShaderError {
                         source: String::new(),
                         label: desc.label.as_ref().map(|l| l.to_string()),
                         inner: Box::new(inner),
                     })
                 })?;
-                (Cow::Owned(module), String::new())
+                // If the module embeds its source code, spans refer to it.
+                let source = debug_source.map_or_else(String::new, |debug| debug.source_code);
+                (Cow::Owned(module), source)
             }
             #[cfg(feature = "glsl")]
             pipeline::ShaderModuleSource::Glsl(code, options) => {
@@ -1485,7 +1496,13 @@ impl Device {
                         inner: Box::new(inner),
                     })
                 })?;
-                (Cow::Owned(module), code.into_owned())
+                // If the shader has `#include` directives, spans refer to the
+                // expanded source.
+                let source = parser.source_map().map_or_else(
+                    || code.into_owned(),
+                    |source_map| source_map.expanded_source().to_string(),
+                );
+                (Cow::Owned(module), source)
             }
             pipeline::ShaderModuleSource::Naga(module) => (module, String::new()),
             pipeline::ShaderModuleSource::Dummy(_) => panic!("found `ShaderModuleSource::Dummy`"),
@@ -1518,19 +1535,33 @@ impl Device {
                 None
             };
 
-        let info = create_validator(
+        let info = match create_validator(
             self.features,
             self.downlevel.flags,
+            &self.limits,
             naga::valid::ValidationFlags::all(),
         )
         .validate(&module)
-        .map_err(|inner| {
-            pipeline::CreateShaderModuleError::Validation(naga::error::ShaderError {
-                source,
+        {
+            Ok(info) => info,
+            Err(inner) => {
+                return Err(pipeline::CreateShaderModuleError::Validation(
+                    naga::error::ShaderError {
+                        source,
+                        label: desc.label.as_ref().map(|l| l.to_string()),
+                        inner: Box::new(inner),
+                    },
+                ))
+            }
+        };
+
+        warnings.extend(info.warnings().iter().map(|warning| {
+            pipeline::ShaderModuleWarning::Validation(naga::error::ShaderError {
+                source: source.clone(),
                 label: desc.label.as_ref().map(|l| l.to_string()),
-                inner: Box::new(inner),
+                inner: Box::new(warning.clone()),
             })
-        })?;
+        }));
 
         let interface = validation::Interface::new(&module, &info, self.limits.clone());
         let hal_shader = hal::ShaderInput::Naga(hal::NagaShader {
@@ -1562,6 +1593,7 @@ impl Device {
             device: self.clone(),
             interface: Some(interface),
             label: desc.label.to_string(),
+            warnings,
         };
 
         let module = Arc::new(module);
@@ -1603,6 +1635,7 @@ impl Device {
             device: self.clone(),
             interface: None,
             label: desc.label.to_string(),
+            warnings: Vec::new(),
         };
 
         let module = Arc::new(module);
@@ -1749,7 +1782,7 @@ impl Device {
                 Bt::StorageTexture {
                     access,
                     view_dimension,
-                    format: _,
+                    format,
                 } => {
                     match view_dimension {
                         TextureViewDimension::Cube | TextureViewDimension::CubeArray => {
@@ -1760,19 +1793,23 @@ impl Device {
                         }
                         _ => (),
                     }
-                    match access {
-                        wgt::StorageTextureAccess::ReadOnly
-                        | wgt::StorageTextureAccess::ReadWrite
-                            if !self.features.contains(
-                                wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
-                            ) =>
-                        {
-                            return Err(binding_model::CreateBindGroupLayoutError::Entry {
-                                binding: entry.binding,
-                                error: BindGroupLayoutEntryError::StorageTextureReadWrite,
-                            });
-                        }
-                        _ => (),
+                    // WebGPU only guarantees read-write access for these
+                    // formats; others depend on the adapter.
+                    if access == wgt::StorageTextureAccess::ReadWrite
+                        && !matches!(
+                            format,
+                            wgt::TextureFormat::R32Float
+                                | wgt::TextureFormat::R32Uint
+                                | wgt::TextureFormat::R32Sint
+                        )
+                        && !self
+                            .features
+                            .contains(wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+                    {
+                        return Err(binding_model::CreateBindGroupLayoutError::Entry {
+                            binding: entry.binding,
+                            error: BindGroupLayoutEntryError::StorageTextureReadWriteFormat(format),
+                        });
                     }
                     (
                         Some(
@@ -1780,17 +1817,9 @@ impl Device {
                                 | wgt::Features::STORAGE_RESOURCE_BINDING_ARRAY,
                         ),
                         match access {
-                            wgt::StorageTextureAccess::WriteOnly => WritableStorage::Yes,
-                            wgt::StorageTextureAccess::ReadOnly => {
-                                required_features |=
-                                    wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
-                                WritableStorage::No
-                            }
-                            wgt::StorageTextureAccess::ReadWrite => {
-                                required_features |=
-                                    wgt::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES;
-                                WritableStorage::Yes
-                            }
+                            wgt::StorageTextureAccess::WriteOnly
+                            | wgt::StorageTextureAccess::ReadWrite => WritableStorage::Yes,
+                            wgt::StorageTextureAccess::ReadOnly => WritableStorage::No,
                         },
                     )
                 }
@@ -2494,23 +2523,16 @@ impl Device {
 
                 let internal_use = match access {
                     wgt::StorageTextureAccess::WriteOnly => hal::TextureUses::STORAGE_READ_WRITE,
-                    wgt::StorageTextureAccess::ReadOnly => {
-                        if !view
-                            .format_features
-                            .flags
-                            .contains(wgt::TextureFormatFeatureFlags::STORAGE_READ_WRITE)
-                        {
-                            return Err(Error::StorageReadNotSupported(view.desc.format));
-                        }
-                        hal::TextureUses::STORAGE_READ
-                    }
+                    wgt::StorageTextureAccess::ReadOnly => hal::TextureUses::STORAGE_READ,
                     wgt::StorageTextureAccess::ReadWrite => {
+                        // The layout already allowed the formats WebGPU
+                        // guarantees; whether others work depends on the adapter.
                         if !view
                             .format_features
                             .flags
                             .contains(wgt::TextureFormatFeatureFlags::STORAGE_READ_WRITE)
                         {
-                            return Err(Error::StorageReadNotSupported(view.desc.format));
+                            return Err(Error::StorageReadWriteNotSupported(view.desc.format));
                         }
 
                         hal::TextureUses::STORAGE_READ_WRITE
@@ -3354,7 +3376,8 @@ impl Device {
                     }
                     hal::PipelineError::EntryPoint(stage) => {
                         pipeline::CreateRenderPipelineError::Internal {
-                            stage: hal::auxil::map_naga_stage(stage),
+                            stage: hal::auxil::map_naga_stage(stage)
+                                .unwrap_or(wgt::ShaderStages::NONE),
                             error: ENTRYPOINT_FAILURE_ERROR.to_string(),
                         }
                     }
@@ -100,12 +100,13 @@ impl IndirectValidation {
             CreateShaderModuleError::Parsing(naga::error::ShaderError {
                 source: src.clone(),
                 label: None,
-                inner: Box::new(inner),
+                inner: Box::new(inner.into()),
             })
         })?;
         let info = crate::device::create_validator(
             wgt::Features::PUSH_CONSTANTS,
             wgt::DownlevelFlags::empty(),
+            &wgt::Limits::default(),
             naga::valid::ValidationFlags::all(),
         )
         .validate(&module)
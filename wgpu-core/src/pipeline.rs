@@ -9,7 +9,7 @@ use crate::{
 };
 use arrayvec::ArrayVec;
 use naga::error::ShaderError;
-use std::{borrow::Cow, marker::PhantomData, mem::ManuallyDrop, num::NonZeroU32, sync::Arc};
+use std::{borrow::Cow, fmt, marker::PhantomData, mem::ManuallyDrop, num::NonZeroU32, sync::Arc};
 use thiserror::Error;
 
 /// Information about buffer bindings, which
@@ -51,6 +51,8 @@ pub struct ShaderModule {
     pub(crate) interface: Option<validation::Interface>,
     /// The `label` from the descriptor used to create the resource.
     pub(crate) label: String,
+    /// Non-fatal diagnostics reported while parsing and validating the shader.
+    pub(crate) warnings: Vec<ShaderModuleWarning>,
 }
 
 impl Drop for ShaderModule {
@@ -88,13 +90,58 @@ impl ShaderModule {
     }
 }
 
+/// A warning or informational message from parsing or validating a shader
+/// module.
+///
+/// Which of these are reported is controlled by the shader's `diagnostic`
+/// filters.
+#[derive(Clone, Debug)]
+pub enum ShaderModuleWarning {
+    #[cfg(feature = "wgsl")]
+    Parsing(ShaderError<naga::front::wgsl::ParseWarning>),
+    Validation(ShaderError<naga::valid::ValidationWarning>),
+}
+
+impl ShaderModuleWarning {
+    /// Either [`Severity::Warning`] or [`Severity::Info`].
+    ///
+    /// [`Severity::Warning`]: naga::diagnostic_filter::Severity::Warning
+    /// [`Severity::Info`]: naga::diagnostic_filter::Severity::Info
+    pub fn severity(&self) -> naga::diagnostic_filter::Severity {
+        match *self {
+            #[cfg(feature = "wgsl")]
+            Self::Parsing(ref warning) => warning.inner.severity,
+            Self::Validation(ref warning) => warning.inner.severity,
+        }
+    }
+
+    /// Where in the shader's source the warning was reported, if known.
+    pub fn location(&self) -> Option<naga::SourceLocation> {
+        match *self {
+            #[cfg(feature = "wgsl")]
+            Self::Parsing(ref warning) => warning.inner.location(&warning.source),
+            Self::Validation(ref warning) => warning.inner.location(&warning.source),
+        }
+    }
+}
+
+impl fmt::Display for ShaderModuleWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            #[cfg(feature = "wgsl")]
+            Self::Parsing(ref warning) => warning.fmt(f),
+            Self::Validation(ref warning) => warning.fmt(f),
+        }
+    }
+}
+
 //Note: `Clone` would require `WithSpan: Clone`.
 #[derive(Clone, Debug, Error)]
 #[non_exhaustive]
 pub enum CreateShaderModuleError {
     #[cfg(any(feature = "wgsl", feature = "indirect-validation"))]
     #[error(transparent)]
-    Parsing(#[from] ShaderError<naga::front::wgsl::ParseError>),
+    Parsing(#[from] ShaderError<naga::front::wgsl::ParseErrors>),
     #[cfg(feature = "glsl")]
     #[error(transparent)]
     ParsingGlsl(#[from] ShaderError<naga::front::glsl::ParseErrors>),
@@ -1207,7 +1207,9 @@ impl Interface {
                                             iv.ty.dim.num_components(),
                                         )
                                     }
-                                    naga::ShaderStage::Compute => (false, 0),
+                                    naga::ShaderStage::Compute
+                                    | naga::ShaderStage::Task
+                                    | naga::ShaderStage::Mesh => (false, 0),
                                 };
                                 if compatible {
                                     Ok(num_components)
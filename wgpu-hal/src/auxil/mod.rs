@@ -46,11 +46,18 @@ pub mod db {
 /// offset at some intermediate point, internally, as i32.
 pub const MAX_I32_BINDING_SIZE: u32 = 1 << 31;
 
-pub fn map_naga_stage(stage: naga::ShaderStage) -> wgt::ShaderStages {
+/// Map a naga shader stage to its [`wgt::ShaderStages`] bit.
+///
+/// There are no task or mesh pipelines yet, so those stages are rejected with
+/// [`crate::PipelineError::EntryPoint`].
+pub fn map_naga_stage(stage: naga::ShaderStage) -> Result<wgt::ShaderStages, crate::PipelineError> {
     match stage {
-        naga::ShaderStage::Vertex => wgt::ShaderStages::VERTEX,
-        naga::ShaderStage::Fragment => wgt::ShaderStages::FRAGMENT,
-        naga::ShaderStage::Compute => wgt::ShaderStages::COMPUTE,
+        naga::ShaderStage::Vertex => Ok(wgt::ShaderStages::VERTEX),
+        naga::ShaderStage::Fragment => Ok(wgt::ShaderStages::FRAGMENT),
+        naga::ShaderStage::Compute => Ok(wgt::ShaderStages::COMPUTE),
+        naga::ShaderStage::Task | naga::ShaderStage::Mesh => {
+            Err(crate::PipelineError::EntryPoint(stage))
+        }
     }
 }
 
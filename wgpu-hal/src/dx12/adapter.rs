@@ -317,7 +317,8 @@ impl super::Adapter {
             | wgt::Features::RG11B10UFLOAT_RENDERABLE
             | wgt::Features::DUAL_SOURCE_BLENDING
             | wgt::Features::TEXTURE_FORMAT_NV12
-            | wgt::Features::FLOAT32_FILTERABLE;
+            | wgt::Features::FLOAT32_FILTERABLE
+            | wgt::Features::CLIP_DISTANCES;
 
         //TODO: in order to expose this, we need to run a compute shader
         // that extract the necessary statistics out of the D3D12 result.
@@ -386,6 +387,17 @@ impl super::Adapter {
                 && hr.is_ok()
                 && features1.WaveOps.as_bool(),
         );
+        // Clustered reductions are emulated with `WaveReadLaneAt`.
+        features.set(
+            wgt::Features::SUBGROUP_CLUSTERED,
+            features.contains(wgt::Features::SUBGROUP),
+        );
+        // Quad operations are only available in compute shaders from SM 6.6.
+        features.set(
+            wgt::Features::SUBGROUP_QUAD,
+            features.contains(wgt::Features::SUBGROUP)
+                && shader_model >= naga::back::hlsl::ShaderModel::V6_6,
+        );
 
         let atomic_int64_on_typed_resource_supported = {
             let mut features9 = Direct3D12::D3D12_FEATURE_DATA_D3D12_OPTIONS9::default();
@@ -489,6 +501,7 @@ impl super::Adapter {
                     //
                     // Source: https://learn.microsoft.com/en-us/windows/win32/direct3d12/root-signature-limits#memory-limits-and-costs
                     max_push_constant_size: 128,
+                    max_clip_distances: Direct3D12::D3D12_CLIP_OR_CULL_DISTANCE_COUNT,
                     min_uniform_buffer_offset_alignment:
                         Direct3D12::D3D12_CONSTANT_BUFFER_DATA_PLACEMENT_ALIGNMENT,
                     min_storage_buffer_offset_alignment: 4,
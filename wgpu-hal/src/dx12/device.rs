@@ -262,7 +262,7 @@ impl super::Device {
             })
             .transpose()?;
 
-        let stage_bit = auxil::map_naga_stage(naga_stage);
+        let stage_bit = auxil::map_naga_stage(naga_stage)?;
 
         let (module, info) = naga::back::pipeline_constants::process_overrides(
             &stage.module.naga.module,
@@ -270,15 +270,25 @@ impl super::Device {
             stage.constants,
         )
         .map_err(|e| crate::PipelineError::PipelineConstants(stage_bit, format!("HLSL: {e:?}")))?;
+        let (module, info) =
+            naga::back::pointer_arguments::specialize_pointer_arguments(&module, &info)
+                .map_err(|e| crate::PipelineError::Linkage(stage_bit, format!("{e}")))?;
 
         let needs_temp_options = stage.zero_initialize_workgroup_memory
             != layout.naga_options.zero_initialize_workgroup_memory
-            || stage.module.runtime_checks != layout.naga_options.restrict_indexing;
+            || !stage.module.runtime_checks;
         let mut temp_options;
         let naga_options = if needs_temp_options {
             temp_options = layout.naga_options.clone();
             temp_options.zero_initialize_workgroup_memory = stage.zero_initialize_workgroup_memory;
-            temp_options.restrict_indexing = stage.module.runtime_checks;
+            if !stage.module.runtime_checks {
+                temp_options.bounds_check_policies = naga::proc::BoundsCheckPolicies {
+                    index: naga::proc::BoundsCheckPolicy::Unchecked,
+                    buffer: naga::proc::BoundsCheckPolicy::Unchecked,
+                    image_load: naga::proc::BoundsCheckPolicy::Unchecked,
+                    binding_array: naga::proc::BoundsCheckPolicy::Unchecked,
+                };
+            }
             &temp_options
         } else {
             &layout.naga_options
@@ -1222,7 +1232,15 @@ impl crate::Device for super::Device {
                 special_constants_binding,
                 push_constants_target,
                 zero_initialize_workgroup_memory: true,
-                restrict_indexing: true,
+                bounds_check_policies: naga::proc::BoundsCheckPolicies {
+                    index: naga::proc::BoundsCheckPolicy::Restrict,
+                    // D3D12 already keeps buffer accesses and texture loads
+                    // within bounds, so there's no need for naga to check them.
+                    buffer: naga::proc::BoundsCheckPolicy::Unchecked,
+                    image_load: naga::proc::BoundsCheckPolicy::Unchecked,
+                    // TODO: support bounds checks on binding arrays
+                    binding_array: naga::proc::BoundsCheckPolicy::Unchecked,
+                },
             },
         })
     }
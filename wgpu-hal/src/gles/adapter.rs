@@ -192,6 +192,7 @@ impl super::Adapter {
 
     pub(super) unsafe fn expose(
         context: super::AdapterContext,
+        flags: wgt::InstanceFlags,
     ) -> Option<crate::ExposedAdapter<super::Api>> {
         let gl = context.lock();
         let extensions = gl.supported_extensions();
@@ -556,6 +557,10 @@ impl super::Adapter {
                 || extensions.contains("GL_EXT_color_buffer_float")
                 || extensions.contains("OES_texture_float_linear"),
         );
+        features.set(
+            wgt::Features::CLIP_DISTANCES,
+            es_ver.is_none() || extensions.contains("GL_EXT_clip_cull_distance"),
+        );
 
         if es_ver.is_none() {
             features |= wgt::Features::POLYGON_MODE_LINE | wgt::Features::POLYGON_MODE_POINT;
@@ -733,6 +738,11 @@ impl super::Adapter {
             min_subgroup_size: 0,
             max_subgroup_size: 0,
             max_push_constant_size: super::MAX_PUSH_CONSTANTS as u32 * 4,
+            max_clip_distances: if features.contains(wgt::Features::CLIP_DISTANCES) {
+                (unsafe { gl.get_parameter_i32(glow::MAX_CLIP_DISTANCES) } as u32)
+            } else {
+                0
+            },
             min_uniform_buffer_offset_alignment,
             min_storage_buffer_offset_alignment,
             max_inter_stage_shader_components: {
@@ -826,6 +836,7 @@ impl super::Adapter {
                     next_shader_id: Default::default(),
                     program_cache: Default::default(),
                     es: es_ver.is_some(),
+                    optimize_shaders: flags.contains(wgt::InstanceFlags::OPTIMIZE_SHADERS),
                     max_msaa_samples: max_samples,
                 }),
             },
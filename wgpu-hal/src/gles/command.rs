@@ -23,6 +23,7 @@ pub(super) struct State {
     stencil: super::StencilState,
     depth_bias: wgt::DepthBiasState,
     alpha_to_coverage_enabled: bool,
+    clip_distance_count: u32,
     samplers: [Option<glow::Sampler>; super::MAX_SAMPLERS],
     texture_slots: [TextureSlotDesc; super::MAX_TEXTURE_SLOTS],
     render_size: wgt::Extent3d,
@@ -52,6 +53,7 @@ impl Default for State {
             stencil: Default::default(),
             depth_bias: Default::default(),
             alpha_to_coverage_enabled: Default::default(),
+            clip_distance_count: Default::default(),
             samplers: Default::default(),
             texture_slots: Default::default(),
             render_size: Default::default(),
@@ -699,6 +701,13 @@ impl crate::CommandEncoder for super::CommandEncoder {
         }
         self.state.vertex_attributes.clear();
         self.state.primitive = super::PrimitiveState::default();
+        if self.state.clip_distance_count != 0 {
+            self.cmd_buffer.commands.push(C::SetClipDistances {
+                old_count: self.state.clip_distance_count,
+                new_count: 0,
+            });
+            self.state.clip_distance_count = 0;
+        }
 
         if let Some(query) = self.state.end_of_pass_timestamp.take() {
             self.cmd_buffer.commands.push(C::TimestampQuery(query));
@@ -955,6 +964,15 @@ impl crate::CommandEncoder for super::CommandEncoder {
                 .push(C::SetAlphaToCoverage(pipeline.alpha_to_coverage_enabled));
         }
 
+        // set clip plane count
+        if pipeline.inner.clip_distance_count != self.state.clip_distance_count {
+            self.cmd_buffer.commands.push(C::SetClipDistances {
+                old_count: self.state.clip_distance_count,
+                new_count: pipeline.inner.clip_distance_count,
+            });
+            self.state.clip_distance_count = pipeline.inner.clip_distance_count;
+        }
+
         // set blend states
         if self.state.color_targets[..] != pipeline.color_targets[..] {
             if pipeline
@@ -2,6 +2,7 @@ use super::{conv, PrivateCapabilities};
 use crate::auxil::map_naga_stage;
 use glow::HasContext;
 use std::{
+    borrow::Cow,
     cmp::max,
     convert::TryInto,
     ptr,
@@ -24,6 +25,8 @@ struct CompilationContext<'a> {
     name_binding_map: &'a mut NameBindingMap,
     push_constant_items: &'a mut Vec<naga::back::glsl::PushConstantItem>,
     multiview: Option<std::num::NonZeroU32>,
+    clip_distance_count: &'a mut u32,
+    optimize_shaders: bool,
 }
 
 impl CompilationContext<'_> {
@@ -98,11 +101,15 @@ impl CompilationContext<'_> {
                     assert_eq!(location.index, 0);
                     unsafe { gl.bind_frag_data_location(program, location.location, &name) }
                 }
-                naga::ShaderStage::Compute => {}
+                naga::ShaderStage::Compute | naga::ShaderStage::Task | naga::ShaderStage::Mesh => {}
             }
         }
 
         *self.push_constant_items = reflection_info.push_constant_items;
+
+        if naga_stage == naga::ShaderStage::Vertex {
+            *self.clip_distance_count = reflection_info.clip_distance_count;
+        }
     }
 }
 
@@ -170,6 +177,9 @@ impl super::Device {
             naga::ShaderStage::Vertex => glow::VERTEX_SHADER,
             naga::ShaderStage::Fragment => glow::FRAGMENT_SHADER,
             naga::ShaderStage::Compute => glow::COMPUTE_SHADER,
+            naga::ShaderStage::Task | naga::ShaderStage::Mesh => {
+                return Err(crate::PipelineError::EntryPoint(naga_stage));
+            }
         };
 
         let raw = unsafe { gl.create_shader(target) }.unwrap();
@@ -195,7 +205,7 @@ impl super::Device {
             log::error!("\tShader compilation failed: {}", msg);
             unsafe { gl.delete_shader(raw) };
             Err(crate::PipelineError::Linkage(
-                map_naga_stage(naga_stage),
+                map_naga_stage(naga_stage)?,
                 msg,
             ))
         }
@@ -209,6 +219,7 @@ impl super::Device {
         program: glow::Program,
     ) -> Result<glow::Shader, crate::PipelineError> {
         use naga::back::glsl;
+        let stage_bit = map_naga_stage(naga_stage)?;
         let pipeline_options = glsl::PipelineOptions {
             shader_stage: naga_stage,
             entry_point: stage.entry_point.to_string(),
@@ -222,8 +233,34 @@ impl super::Device {
         )
         .map_err(|e| {
             let msg = format!("{e}");
-            crate::PipelineError::PipelineConstants(map_naga_stage(naga_stage), msg)
+            crate::PipelineError::PipelineConstants(stage_bit, msg)
         })?;
+        let (module, info) = naga::back::pointer_arguments::specialize_pointer_arguments(
+            &module, &info,
+        )
+        .map_err(|e| {
+            let msg = format!("{e}");
+            crate::PipelineError::Linkage(stage_bit, msg)
+        })?;
+
+        // Some drivers' GLSL compilers do very little optimization of their
+        // own, so do some of it for them if we've been asked to.
+        let (module, info) = if context.optimize_shaders {
+            let mut module = module.into_owned();
+            naga::opt::optimize(&mut module, naga::opt::Passes::all());
+            let info = naga::valid::Validator::new(
+                naga::valid::ValidationFlags::all(),
+                naga::valid::Capabilities::all(),
+            )
+            .validate(&module)
+            .map_err(|e| {
+                let msg = format!("{e}");
+                crate::PipelineError::Linkage(stage_bit, msg)
+            })?;
+            (Cow::Owned(module), Cow::Owned(info))
+        } else {
+            (module, info)
+        };
 
         let entry_point_index = module
             .entry_points
@@ -271,12 +308,12 @@ impl super::Device {
         )
         .map_err(|e| {
             let msg = format!("{e}");
-            crate::PipelineError::Linkage(map_naga_stage(naga_stage), msg)
+            crate::PipelineError::Linkage(stage_bit, msg)
         })?;
 
         let reflection_info = writer.write().map_err(|e| {
             let msg = format!("{e}");
-            crate::PipelineError::Linkage(map_naga_stage(naga_stage), msg)
+            crate::PipelineError::Linkage(stage_bit, msg)
         })?;
 
         log::debug!("Naga generated shader:\n{}", output);
@@ -335,6 +372,7 @@ impl super::Device {
                     multiview,
                     self.shared.shading_language_version,
                     self.shared.private_caps,
+                    self.shared.optimize_shaders,
                 )
             })
             .to_owned()?;
@@ -343,6 +381,7 @@ impl super::Device {
         Ok(program)
     }
 
+    #[allow(clippy::too_many_arguments)]
     unsafe fn create_program<'a>(
         gl: &glow::Context,
         shaders: ArrayVec<ShaderStage<'a>, { crate::MAX_CONCURRENT_SHADER_STAGES }>,
@@ -351,6 +390,7 @@ impl super::Device {
         multiview: Option<std::num::NonZeroU32>,
         glsl_version: naga::back::glsl::Version,
         private_caps: PrivateCapabilities,
+        optimize_shaders: bool,
     ) -> Result<Arc<super::PipelineInner>, crate::PipelineError> {
         let glsl_version = match glsl_version {
             naga::back::glsl::Version::Embedded { version, .. } => format!("{version} es"),
@@ -369,10 +409,11 @@ impl super::Device {
         let mut push_constant_items = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
         let mut sampler_map = [None; super::MAX_TEXTURE_SLOTS];
         let mut has_stages = wgt::ShaderStages::empty();
+        let mut clip_distance_count = 0;
         let mut shaders_to_delete = ArrayVec::<_, { crate::MAX_CONCURRENT_SHADER_STAGES }>::new();
 
         for &(naga_stage, stage) in &shaders {
-            has_stages |= map_naga_stage(naga_stage);
+            has_stages |= map_naga_stage(naga_stage)?;
             let pc_item = {
                 push_constant_items.push(Vec::new());
                 push_constant_items.last_mut().unwrap()
@@ -383,6 +424,8 @@ impl super::Device {
                 name_binding_map: &mut name_binding_map,
                 push_constant_items: pc_item,
                 multiview,
+                clip_distance_count: &mut clip_distance_count,
+                optimize_shaders,
             };
 
             let shader = Self::create_shader(gl, naga_stage, stage, context, program)?;
@@ -494,6 +537,7 @@ impl super::Device {
             sampler_map,
             first_instance_location,
             push_constant_descs: uniforms,
+            clip_distance_count,
         }))
     }
 }
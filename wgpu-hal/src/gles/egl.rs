@@ -1089,10 +1089,13 @@ impl crate::Instance for Instance {
         inner.egl.unmake_current();
 
         unsafe {
-            super::Adapter::expose(AdapterContext {
-                glow: Mutex::new(gl),
-                egl: Some(inner.egl.clone()),
-            })
+            super::Adapter::expose(
+                AdapterContext {
+                    glow: Mutex::new(gl),
+                    egl: Some(inner.egl.clone()),
+                },
+                self.flags,
+            )
         }
         .into_iter()
         .collect()
@@ -1114,10 +1117,13 @@ impl super::Adapter {
     ) -> Option<crate::ExposedAdapter<super::Api>> {
         let context = unsafe { glow::Context::from_loader_function(fun) };
         unsafe {
-            Self::expose(AdapterContext {
-                glow: Mutex::new(ManuallyDrop::new(context)),
-                egl: None,
-            })
+            Self::expose(
+                AdapterContext {
+                    glow: Mutex::new(ManuallyDrop::new(context)),
+                    egl: None,
+                },
+                wgt::InstanceFlags::empty(),
+            )
         }
     }
 
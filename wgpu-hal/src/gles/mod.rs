@@ -277,6 +277,10 @@ struct AdapterShared {
     program_cache: Mutex<ProgramCache>,
     es: bool,
 
+    /// Whether to run Naga's optimization passes on shaders before
+    /// translating them to GLSL, per [`wgt::InstanceFlags::OPTIMIZE_SHADERS`].
+    optimize_shaders: bool,
+
     /// Result of `gl.get_parameter_i32(glow::MAX_SAMPLES)`.
     /// Cached here so it doesn't need to be queried every time texture format capabilities are requested.
     /// (this has been shown to be a significant enough overhead)
@@ -649,6 +653,7 @@ struct PipelineInner {
     sampler_map: SamplerBindMap,
     first_instance_location: Option<glow::UniformLocation>,
     push_constant_descs: ArrayVec<PushConstantDesc, MAX_PUSH_CONSTANT_COMMANDS>,
+    clip_distance_count: u32,
 }
 
 #[derive(Clone, Debug)]
@@ -1001,6 +1006,10 @@ enum Command {
     SetDepthBias(wgt::DepthBiasState),
     ConfigureDepthStencil(crate::FormatAspects),
     SetAlphaToCoverage(bool),
+    SetClipDistances {
+        old_count: u32,
+        new_count: u32,
+    },
     SetVertexAttribute {
         buffer: Option<glow::Buffer>,
         buffer_desc: VertexBufferDesc,
@@ -177,6 +177,7 @@ unsafe impl Sync for Inner {}
 
 pub struct Instance {
     srgb_capable: bool,
+    flags: InstanceFlags,
     inner: Arc<Mutex<Inner>>,
 }
 
@@ -543,6 +544,7 @@ impl crate::Instance for Instance {
                 context: Some(context),
             })),
             srgb_capable,
+            flags: desc.flags,
         })
     }
 
@@ -573,9 +575,12 @@ impl crate::Instance for Instance {
         _surface_hint: Option<&Surface>,
     ) -> Vec<crate::ExposedAdapter<super::Api>> {
         unsafe {
-            super::Adapter::expose(AdapterContext {
-                inner: self.inner.clone(),
-            })
+            super::Adapter::expose(
+                AdapterContext {
+                    inner: self.inner.clone(),
+                },
+                self.flags,
+            )
         }
         .into_iter()
         .collect()
@@ -597,13 +602,16 @@ impl super::Adapter {
     ) -> Option<crate::ExposedAdapter<super::Api>> {
         let context = unsafe { glow::Context::from_loader_function(fun) };
         unsafe {
-            Self::expose(AdapterContext {
-                inner: Arc::new(Mutex::new(Inner {
-                    gl: ManuallyDrop::new(context),
-                    device: create_instance_device().ok()?,
-                    context: None,
-                })),
-            })
+            Self::expose(
+                AdapterContext {
+                    inner: Arc::new(Mutex::new(Inner {
+                        gl: ManuallyDrop::new(context),
+                        device: create_instance_device().ok()?,
+                        context: None,
+                    })),
+                },
+                InstanceFlags::empty(),
+            )
         }
     }
 
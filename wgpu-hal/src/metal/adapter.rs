@@ -852,7 +852,8 @@ impl super::PrivateCapabilities {
             | F::TEXTURE_FORMAT_16BIT_NORM
             | F::SHADER_F16
             | F::DEPTH32FLOAT_STENCIL8
-            | F::BGRA8UNORM_STORAGE;
+            | F::BGRA8UNORM_STORAGE
+            | F::CLIP_DISTANCES;
 
         features.set(F::FLOAT32_FILTERABLE, self.supports_float_filtering);
         features.set(
@@ -918,7 +919,9 @@ impl super::PrivateCapabilities {
         features.set(F::RG11B10UFLOAT_RENDERABLE, self.format_rg11b10_all);
 
         if self.supports_simd_scoped_operations {
-            features.insert(F::SUBGROUP | F::SUBGROUP_BARRIER);
+            features.insert(
+                F::SUBGROUP | F::SUBGROUP_BARRIER | F::SUBGROUP_QUAD | F::SUBGROUP_CLUSTERED,
+            );
         }
 
         features
@@ -978,6 +981,7 @@ impl super::PrivateCapabilities {
                 min_subgroup_size: 4,
                 max_subgroup_size: 64,
                 max_push_constant_size: 0x1000,
+                max_clip_distances: 8,
                 min_uniform_buffer_offset_alignment: self.buffer_alignment as u32,
                 min_storage_buffer_offset_alignment: self.buffer_alignment as u32,
                 max_inter_stage_shader_components: self.max_varying_components,
@@ -106,7 +106,7 @@ impl super::Device {
         primitive_class: metal::MTLPrimitiveTopologyClass,
         naga_stage: naga::ShaderStage,
     ) -> Result<CompiledShader, crate::PipelineError> {
-        let stage_bit = map_naga_stage(naga_stage);
+        let stage_bit = map_naga_stage(naga_stage)?;
 
         let (module, module_info) = naga::back::pipeline_constants::process_overrides(
             &stage.module.naga.module,
@@ -114,8 +114,14 @@ impl super::Device {
             stage.constants,
         )
         .map_err(|e| crate::PipelineError::PipelineConstants(stage_bit, format!("MSL: {:?}", e)))?;
+        let (module, module_info) =
+            naga::back::pointer_arguments::specialize_pointer_arguments(&module, &module_info)
+                .map_err(|e| crate::PipelineError::Linkage(stage_bit, format!("{e}")))?;
 
-        let ep_resources = &layout.per_stage_map[naga_stage];
+        let ep_resources = layout
+            .per_stage_map
+            .get(naga_stage)
+            .ok_or(crate::PipelineError::EntryPoint(naga_stage))?;
 
         let bounds_check_policy = if stage.module.runtime_checks {
             naga::proc::BoundsCheckPolicy::Restrict
@@ -614,6 +620,7 @@ impl crate::Device for super::Device {
         #[derive(Debug)]
         struct StageInfo {
             stage: naga::ShaderStage,
+            stage_bit: wgt::ShaderStages,
             counters: super::ResourceData<super::ResourceIndex>,
             pc_buffer: Option<super::ResourceIndex>,
             pc_limit: u32,
@@ -622,8 +629,9 @@ impl crate::Device for super::Device {
             resources: naga::back::msl::BindingMap,
         }
 
-        let mut stage_data = super::NAGA_STAGES.map(|stage| StageInfo {
+        let mut stage_data = super::STAGES.map(|(stage, stage_bit)| StageInfo {
             stage,
+            stage_bit,
             counters: super::ResourceData::default(),
             pc_buffer: None,
             pc_limit: 0,
@@ -637,7 +645,7 @@ impl crate::Device for super::Device {
         let mut total_push_constants = 0;
         for info in stage_data.iter_mut() {
             for pcr in desc.push_constant_ranges {
-                if pcr.stages.contains(map_naga_stage(info.stage)) {
+                if pcr.stages.contains(info.stage_bit) {
                     debug_assert_eq!(pcr.range.end % 4, 0);
                     info.pc_limit = (pcr.range.end / 4).max(info.pc_limit);
                 }
@@ -672,14 +680,14 @@ impl crate::Device for super::Device {
                 } = entry.ty
                 {
                     for info in stage_data.iter_mut() {
-                        if entry.visibility.contains(map_naga_stage(info.stage)) {
+                        if entry.visibility.contains(info.stage_bit) {
                             info.need_sizes_buffer = true;
                         }
                     }
                 }
 
                 for info in stage_data.iter_mut() {
-                    if !entry.visibility.contains(map_naga_stage(info.stage)) {
+                    if !entry.visibility.contains(info.stage_bit) {
                         continue;
                     }
 
@@ -792,8 +800,7 @@ impl crate::Device for super::Device {
         >,
     ) -> DeviceResult<super::BindGroup> {
         let mut bg = super::BindGroup::default();
-        for (&stage, counter) in super::NAGA_STAGES.iter().zip(bg.counters.iter_mut()) {
-            let stage_bit = map_naga_stage(stage);
+        for (&(_, stage_bit), counter) in super::STAGES.iter().zip(bg.counters.iter_mut()) {
             let mut dynamic_offsets_count = 0u32;
             let layout_and_entry_iter = desc.entries.iter().map(|entry| {
                 let layout = desc
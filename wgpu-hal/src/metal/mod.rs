@@ -26,7 +26,7 @@ mod surface;
 mod time;
 
 use std::{
-    fmt, iter, ops,
+    fmt, iter,
     ptr::NonNull,
     sync::{atomic, Arc},
     thread,
@@ -571,24 +571,23 @@ struct MultiStageData<T> {
     cs: T,
 }
 
-const NAGA_STAGES: MultiStageData<naga::ShaderStage> = MultiStageData {
-    vs: naga::ShaderStage::Vertex,
-    fs: naga::ShaderStage::Fragment,
-    cs: naga::ShaderStage::Compute,
+/// Each pipeline stage, along with its bit in [`wgt::ShaderStages`].
+const STAGES: MultiStageData<(naga::ShaderStage, wgt::ShaderStages)> = MultiStageData {
+    vs: (naga::ShaderStage::Vertex, wgt::ShaderStages::VERTEX),
+    fs: (naga::ShaderStage::Fragment, wgt::ShaderStages::FRAGMENT),
+    cs: (naga::ShaderStage::Compute, wgt::ShaderStages::COMPUTE),
 };
 
-impl<T> ops::Index<naga::ShaderStage> for MultiStageData<T> {
-    type Output = T;
-    fn index(&self, stage: naga::ShaderStage) -> &T {
+impl<T> MultiStageData<T> {
+    /// Returns `None` for the task and mesh stages, which have no pipelines yet.
+    fn get(&self, stage: naga::ShaderStage) -> Option<&T> {
         match stage {
-            naga::ShaderStage::Vertex => &self.vs,
-            naga::ShaderStage::Fragment => &self.fs,
-            naga::ShaderStage::Compute => &self.cs,
+            naga::ShaderStage::Vertex => Some(&self.vs),
+            naga::ShaderStage::Fragment => Some(&self.fs),
+            naga::ShaderStage::Compute => Some(&self.cs),
+            naga::ShaderStage::Task | naga::ShaderStage::Mesh => None,
         }
     }
-}
-
-impl<T> MultiStageData<T> {
     fn map_ref<Y>(&self, fun: impl Fn(&T) -> Y) -> MultiStageData<Y> {
         MultiStageData {
             vs: fun(&self.vs),
@@ -282,7 +282,7 @@ impl PhysicalDeviceFeatures {
                         | wgt::Features::STORAGE_RESOURCE_BINDING_ARRAY,
                 ))
                 //.shader_storage_image_array_dynamic_indexing(
-                //.shader_clip_distance(requested_features.contains(wgt::Features::SHADER_CLIP_DISTANCE))
+                .shader_clip_distance(requested_features.contains(wgt::Features::CLIP_DISTANCES))
                 //.shader_cull_distance(requested_features.contains(wgt::Features::SHADER_CULL_DISTANCE))
                 .shader_float64(requested_features.contains(wgt::Features::SHADER_F64))
                 .shader_int64(requested_features.contains(wgt::Features::SHADER_INT64))
@@ -382,7 +382,9 @@ impl PhysicalDeviceFeatures {
                     vk::PhysicalDeviceShaderFloat16Int8Features::default().shader_float16(true),
                     vk::PhysicalDevice16BitStorageFeatures::default()
                         .storage_buffer16_bit_access(true)
-                        .uniform_and_storage_buffer16_bit_access(true),
+                        .uniform_and_storage_buffer16_bit_access(true)
+                        .storage_push_constant16(private_caps.storage_push_constant16)
+                        .storage_input_output16(private_caps.storage_input_output16),
                 ))
             } else {
                 None
@@ -576,7 +578,7 @@ impl PhysicalDeviceFeatures {
             features.insert(F::STORAGE_RESOURCE_BINDING_ARRAY);
         }
         //if self.core.shader_storage_image_array_dynamic_indexing != 0 {
-        //if self.core.shader_clip_distance != 0 {
+        features.set(F::CLIP_DISTANCES, self.core.shader_clip_distance != 0);
         //if self.core.shader_cull_distance != 0 {
         features.set(F::SHADER_F64, self.core.shader_float64 != 0);
         features.set(F::SHADER_INT64, self.core.shader_int64 != 0);
@@ -693,6 +695,18 @@ impl PhysicalDeviceFeatures {
                         .contains(vk::ShaderStageFlags::VERTEX),
                 );
                 features.insert(F::SUBGROUP_BARRIER);
+                features.set(
+                    F::SUBGROUP_QUAD,
+                    subgroup
+                        .supported_operations
+                        .contains(vk::SubgroupFeatureFlags::QUAD),
+                );
+                features.set(
+                    F::SUBGROUP_CLUSTERED,
+                    subgroup
+                        .supported_operations
+                        .contains(vk::SubgroupFeatureFlags::CLUSTERED),
+                );
             }
         }
 
@@ -1088,6 +1102,7 @@ impl PhysicalDeviceProperties {
                 .map(|subgroup_size| subgroup_size.max_subgroup_size)
                 .unwrap_or(0),
             max_push_constant_size: limits.max_push_constants_size,
+            max_clip_distances: limits.max_clip_distances,
             min_uniform_buffer_offset_alignment: limits.min_uniform_buffer_offset_alignment as u32,
             min_storage_buffer_offset_alignment: limits.min_storage_buffer_offset_alignment as u32,
             max_inter_stage_shader_components: limits
@@ -1556,6 +1571,14 @@ impl super::Instance {
                 }),
             image_format_list: phd_capabilities.device_api_version >= vk::API_VERSION_1_2
                 || phd_capabilities.supports_extension(khr::image_format_list::NAME),
+            storage_push_constant16: phd_features
+                .shader_float16
+                .as_ref()
+                .map_or(false, |(_, bit16)| bit16.storage_push_constant16 != 0),
+            storage_input_output16: phd_features
+                .shader_float16
+                .as_ref()
+                .map_or(false, |(_, bit16)| bit16.storage_input_output16 != 0),
             #[cfg(windows)]
             external_memory_win32: phd_capabilities
                 .supports_extension(khr::external_memory_win32::NAME),
@@ -1780,6 +1803,12 @@ impl super::Adapter {
                 capabilities.push(spv::Capability::GroupNonUniformShuffle);
                 capabilities.push(spv::Capability::GroupNonUniformShuffleRelative);
             }
+            if features.contains(wgt::Features::SUBGROUP_QUAD) {
+                capabilities.push(spv::Capability::GroupNonUniformQuad);
+            }
+            if features.contains(wgt::Features::SUBGROUP_CLUSTERED) {
+                capabilities.push(spv::Capability::GroupNonUniformClustered);
+            }
 
             if features.intersects(
                 wgt::Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING
@@ -1806,6 +1835,20 @@ impl super::Adapter {
                 capabilities.push(spv::Capability::Int64Atomics);
             }
 
+            if features.contains(wgt::Features::SHADER_F16) {
+                // `SHADER_F16` requires `storageBuffer16BitAccess` and
+                // `uniformAndStorageBuffer16BitAccess`; the others are optional.
+                capabilities.push(spv::Capability::Float16);
+                capabilities.push(spv::Capability::StorageBuffer16BitAccess);
+                capabilities.push(spv::Capability::UniformAndStorageBuffer16BitAccess);
+                if self.private_caps.storage_push_constant16 {
+                    capabilities.push(spv::Capability::StoragePushConstant16);
+                }
+                if self.private_caps.storage_input_output16 {
+                    capabilities.push(spv::Capability::StorageInputOutput16);
+                }
+            }
+
             let mut flags = spv::WriterFlags::empty();
             flags.set(
                 spv::WriterFlags::DEBUG,
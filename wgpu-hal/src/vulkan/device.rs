@@ -886,7 +886,7 @@ impl super::Device {
         naga_stage: naga::ShaderStage,
         binding_map: &naga::back::spv::BindingMap,
     ) -> Result<CompiledStage, crate::PipelineError> {
-        let stage_flags = crate::auxil::map_naga_stage(naga_stage);
+        let stage_flags = crate::auxil::map_naga_stage(naga_stage)?;
         let vk_module = match *stage.module {
             super::ShaderModule::Raw(raw) => raw,
             super::ShaderModule::Intermediate {
@@ -941,6 +941,9 @@ impl super::Device {
                 .map_err(|e| {
                     crate::PipelineError::PipelineConstants(stage_flags, format!("{e}"))
                 })?;
+                let (module, info) =
+                    naga::back::pointer_arguments::specialize_pointer_arguments(&module, &info)
+                        .map_err(|e| crate::PipelineError::Linkage(stage_flags, format!("{e}")))?;
 
                 let spv = {
                     profiling::scope!("naga::spv::write_vec");
@@ -1831,14 +1834,14 @@ impl crate::Device for super::Device {
                         binding_array: naga::proc::BoundsCheckPolicy::Unchecked,
                     };
                 }
+                let (module, info) = naga::back::pointer_arguments::specialize_pointer_arguments(
+                    &naga_shader.module,
+                    &naga_shader.info,
+                )
+                .map_err(|e| crate::ShaderError::Compilation(format!("{e}")))?;
                 Cow::Owned(
-                    naga::back::spv::write_vec(
-                        &naga_shader.module,
-                        &naga_shader.info,
-                        &naga_options,
-                        None,
-                    )
-                    .map_err(|e| crate::ShaderError::Compilation(format!("{e}")))?,
+                    naga::back::spv::write_vec(&module, &info, &naga_options, None)
+                        .map_err(|e| crate::ShaderError::Compilation(format!("{e}")))?,
                 )
             }
             crate::ShaderInput::SpirV(spv) => Cow::Borrowed(spv),
@@ -532,6 +532,14 @@ struct PrivateCapabilities {
     robust_image_access2: bool,
     zero_initialize_workgroup_memory: bool,
     image_format_list: bool,
+    /// True if this adapter supports the optional `storagePushConstant16`
+    /// feature of `VK_KHR_16bit_storage`, which lets shaders keep 16-bit
+    /// floats in push constants.
+    storage_push_constant16: bool,
+    /// True if this adapter supports the optional `storageInputOutput16`
+    /// feature of `VK_KHR_16bit_storage`, which lets shaders pass 16-bit
+    /// floats between stages.
+    storage_input_output16: bool,
     #[cfg(windows)]
     external_memory_win32: bool,
 }
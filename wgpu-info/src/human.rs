@@ -146,6 +146,7 @@ fn print_adapter(output: &mut impl io::Write, report: &AdapterReport, idx: usize
         min_subgroup_size,
         max_subgroup_size,
         max_push_constant_size,
+        max_clip_distances,
         min_uniform_buffer_offset_alignment,
         min_storage_buffer_offset_alignment,
         max_inter_stage_shader_components,
@@ -181,6 +182,7 @@ fn print_adapter(output: &mut impl io::Write, report: &AdapterReport, idx: usize
     writeln!(output, "\t\t                               Min Subgroup Size: {min_subgroup_size}")?;
     writeln!(output, "\t\t                               Max Subgroup Size: {max_subgroup_size}")?;
     writeln!(output, "\t\t                          Max Push Constant Size: {max_push_constant_size}")?;
+    writeln!(output, "\t\t                              Max Clip Distances: {max_clip_distances}")?;
     writeln!(output, "\t\t             Min Uniform Buffer Offset Alignment: {min_uniform_buffer_offset_alignment}")?;
     writeln!(output, "\t\t             Min Storage Buffer Offset Alignment: {min_storage_buffer_offset_alignment}")?;
     writeln!(output, "\t\t                Max Inter-Stage Shader Component: {max_inter_stage_shader_components}")?;
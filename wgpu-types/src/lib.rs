@@ -392,7 +392,7 @@ bitflags::bitflags! {
 
         /// Allows shaders to acquire the FP16 ability
         ///
-        /// Note: this is not supported in `naga` yet, only through `spirv-passthrough` right now.
+        /// This allows the use of the `f16` type in WGSL shaders, after enabling it with `enable f16;`.
         ///
         /// Supported Platforms:
         /// - Vulkan
@@ -434,7 +434,20 @@ bitflags::bitflags! {
         /// This is a web and native feature.
         const FLOAT32_FILTERABLE = 1 << 11;
 
-        // Bits 12-19 available for webgpu features. Should you chose to use some of them for
+        /// Allows the use of the `clip_distances` built-in in WGSL shaders, after enabling it
+        /// with `enable clip_distances;`. Provide a non-zero limit to
+        /// [`Limits::max_clip_distances`] to choose how many elements the array may have.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan
+        /// - DX12
+        /// - Metal
+        /// - OpenGL (desktop, or ES with `GL_EXT_clip_cull_distance`)
+        ///
+        /// This is a web and native feature.
+        const CLIP_DISTANCES = 1 << 12;
+
+        // Bits 13-19 available for webgpu features. Should you chose to use some of them for
         // for native features, don't forget to update `all_webgpu_mask` and `all_native_mask`
         // accordingly.
 
@@ -491,7 +504,8 @@ bitflags::bitflags! {
         ///
         /// By default only texture format properties as defined by the WebGPU specification are allowed.
         /// Enabling this feature flag extends the features of each format to the ones supported by the current device.
-        /// Note that without this flag, read/write storage access is not allowed at all.
+        /// Note that without this flag, read-write storage access is only allowed for the `r32float`,
+        /// `r32uint` and `r32sint` formats.
         ///
         /// This extension does not enable additional formats.
         ///
@@ -820,6 +834,16 @@ bitflags::bitflags! {
         ///
         /// This is a native only feature.
         const VERTEX_ATTRIBUTE_64BIT = 1 << 45;
+        /// Allows compute and fragment shaders to use the clustered subgroup
+        /// reduction built-ins, like `subgroupClusteredAdd`.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan
+        /// - DX12
+        /// - Metal
+        ///
+        /// This is a native only feature.
+        const SUBGROUP_CLUSTERED = 1 << 46;
         /// Allows for creation of textures of format [`TextureFormat::NV12`]
         ///
         /// Supported platforms:
@@ -895,6 +919,9 @@ bitflags::bitflags! {
         /// Allows two outputs from a shader to be used for blending.
         /// Note that dual-source blending doesn't support multiple render targets.
         ///
+        /// In WGSL, this is used by enabling it with `enable dual_source_blending;` and marking
+        /// the two outputs at `@location(0)` with `@blend_src(0)` and `@blend_src(1)`.
+        ///
         /// For more info see the OpenGL ES extension GL_EXT_blend_func_extended.
         ///
         /// Supported platforms:
@@ -978,6 +1005,17 @@ bitflags::bitflags! {
         /// [VK_GOOGLE_display_timing]: https://registry.khronos.org/vulkan/specs/1.3-extensions/man/html/VK_GOOGLE_display_timing.html
         /// [`Surface::as_hal()`]: https://docs.rs/wgpu/latest/wgpu/struct.Surface.html#method.as_hal
         const VULKAN_GOOGLE_DISPLAY_TIMING = 1 << 62;
+        /// Allows compute and fragment shaders to use the quad subgroup
+        /// built-ins, `quadBroadcast`, `quadSwapX`, `quadSwapY` and
+        /// `quadSwapDiagonal`.
+        ///
+        /// Supported Platforms:
+        /// - Vulkan
+        /// - DX12 (with SM 6.6+)
+        /// - Metal
+        ///
+        /// This is a native only feature.
+        const SUBGROUP_QUAD = 1 << 63;
     }
 }
 
@@ -1042,6 +1080,13 @@ bitflags::bitflags! {
         /// - Vulkan, via the `VK_LAYER_KHRONOS_validation` layer; called ["GPU-Assisted
         ///   Validation"](https://github.com/KhronosGroup/Vulkan-ValidationLayers/blob/e45aeb85079e0835694cb8f03e6681fd18ae72c9/docs/gpu_validation.md#gpu-assisted-validation)
         const GPU_BASED_VALIDATION = 1 << 4;
+        /// Run Naga's optimization passes on shaders before translating them for the backend.
+        ///
+        /// This inlines single-use functions, folds constant expressions and removes dead code,
+        /// which helps drivers whose shader compilers do little optimization of their own. It
+        /// makes creating pipelines slower. Currently, this only changes behavior on the OpenGL
+        /// backend.
+        const OPTIMIZE_SHADERS = 1 << 5;
     }
 }
 
@@ -1088,6 +1133,7 @@ impl InstanceFlags {
     /// The environment variables are named after the flags prefixed with "WGPU_". For example:
     /// - WGPU_DEBUG
     /// - WGPU_VALIDATION
+    /// - WGPU_OPTIMIZE_SHADERS
     #[must_use]
     pub fn with_env(mut self) -> Self {
         fn env(key: &str) -> Option<bool> {
@@ -1109,6 +1155,9 @@ impl InstanceFlags {
         if let Some(bit) = env("WGPU_GPU_BASED_VALIDATION") {
             self.set(Self::GPU_BASED_VALIDATION, bit);
         }
+        if let Some(bit) = env("WGPU_OPTIMIZE_SHADERS") {
+            self.set(Self::OPTIMIZE_SHADERS, bit);
+        }
 
         self
     }
@@ -1255,6 +1304,12 @@ pub struct Limits {
     /// - OpenGL doesn't natively support push constants, and are emulated with uniforms,
     ///   so this number is less useful but likely 256.
     pub max_push_constant_size: u32,
+    /// Maximum number of elements in a vertex shader's `clip_distances` output. Defaults to 0.
+    /// Higher is "better".
+    /// Requesting more than 0 during device creation requires [`Features::CLIP_DISTANCES`] to be enabled.
+    ///
+    /// Expect 8 on every backend that supports [`Features::CLIP_DISTANCES`].
+    pub max_clip_distances: u32,
     /// Maximum number of live non-sampler bindings.
     ///
     /// This limit only affects the d3d12 backend. Using a large number will allow the device
@@ -1306,6 +1361,7 @@ impl Limits {
             min_subgroup_size: 0,
             max_subgroup_size: 0,
             max_push_constant_size: 0,
+            max_clip_distances: 0,
             max_non_sampler_bindings: 1_000_000,
         }
     }
@@ -1337,6 +1393,7 @@ impl Limits {
     ///     min_subgroup_size: 0,
     ///     max_subgroup_size: 0,
     ///     max_push_constant_size: 0,
+    ///     max_clip_distances: 0,
     ///     min_uniform_buffer_offset_alignment: 256,
     ///     min_storage_buffer_offset_alignment: 256,
     ///     max_inter_stage_shader_components: 60,
@@ -1394,6 +1451,7 @@ impl Limits {
     ///     min_subgroup_size: 0,
     ///     max_subgroup_size: 0,
     ///     max_push_constant_size: 0,
+    ///     max_clip_distances: 0,
     ///     min_uniform_buffer_offset_alignment: 256,
     ///     min_storage_buffer_offset_alignment: 256,
     ///     max_inter_stage_shader_components: 31,
@@ -1539,6 +1597,7 @@ impl Limits {
             compare!(max_subgroup_size, Less);
         }
         compare!(max_push_constant_size, Less);
+        compare!(max_clip_distances, Less);
         compare!(max_non_sampler_bindings, Less);
     }
 }
@@ -2381,7 +2440,7 @@ bitflags::bitflags! {
         /// to a render pass for an automatic driver-implemented resolve.
         const MULTISAMPLE_RESOLVE = 1 << 5;
         /// When used as a STORAGE texture, then a texture with this format can be bound with
-        /// [`StorageTextureAccess::ReadOnly`] or [`StorageTextureAccess::ReadWrite`].
+        /// [`StorageTextureAccess::ReadWrite`].
         const STORAGE_READ_WRITE = 1 << 6;
         /// If not present, the texture can't be blended into the render target.
         const BLENDABLE = 1 << 7;
@@ -3517,6 +3576,11 @@ impl TextureFormat {
             Self::Astc { .. } =>          (        noaa,      basic),
         };
 
+        // WebGPU guarantees read-write storage access for these formats.
+        if matches!(*self, Self::R32Uint | Self::R32Sint | Self::R32Float) {
+            flags |= TextureFormatFeatureFlags::STORAGE_READ_WRITE;
+        }
+
         // Get whether the format is filterable, taking features into account
         let sample_type1 = self.sample_type(None, Some(device_features));
         let is_filterable = sample_type1 == Some(TextureSampleType::Float { filterable: true });
@@ -6577,8 +6641,9 @@ pub enum StorageTextureAccess {
     /// The texture can only be read in the shader and it must be annotated with `read` (WGSL) or
     /// `readonly` (GLSL).
     ///
-    /// [`Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`] must be enabled to use this access
-    /// mode. This is a native-only extension.
+    /// Any format that supports [`TextureUsages::STORAGE_BINDING`] can be bound with this access
+    /// mode. This corresponds to the `readonly_and_readwrite_storage_textures` WGSL language
+    /// extension.
     ///
     /// Example WGSL syntax:
     /// ```rust,ignore
@@ -6594,8 +6659,9 @@ pub enum StorageTextureAccess {
     /// The texture can be both read and written in the shader and must be annotated with
     /// `read_write` in WGSL.
     ///
-    /// [`Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`] must be enabled to use this access
-    /// mode.  This is a nonstandard, native-only extension.
+    /// The texture format must have [`TextureFormatFeatureFlags::STORAGE_READ_WRITE`]. WebGPU
+    /// guarantees this for `r32float`, `r32uint` and `r32sint`; other formats require
+    /// [`Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES`].
     ///
     /// Example WGSL syntax:
     /// ```rust,ignore
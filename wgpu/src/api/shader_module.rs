@@ -92,17 +92,25 @@ pub struct SourceLocation {
 }
 
 #[cfg(all(feature = "wgsl", wgpu_core))]
-impl From<crate::naga::error::ShaderError<crate::naga::front::wgsl::ParseError>>
+impl From<crate::naga::error::ShaderError<crate::naga::front::wgsl::ParseErrors>>
     for CompilationInfo
 {
-    fn from(value: crate::naga::error::ShaderError<crate::naga::front::wgsl::ParseError>) -> Self {
-        CompilationInfo {
-            messages: vec![CompilationMessage {
-                message: value.to_string(),
+    fn from(value: crate::naga::error::ShaderError<crate::naga::front::wgsl::ParseErrors>) -> Self {
+        let label = value.label.as_deref().unwrap_or_default();
+        let messages = value
+            .inner
+            .errors
+            .iter()
+            .map(|err| CompilationMessage {
+                message: format!(
+                    "\nShader '{label}' parsing {}",
+                    err.emit_to_string(&value.source)
+                ),
                 message_type: CompilationMessageType::Error,
-                location: value.inner.location(&value.source).map(Into::into),
-            }],
-        }
+                location: err.location(&value.source).map(Into::into),
+            })
+            .collect();
+        CompilationInfo { messages }
     }
 }
 #[cfg(feature = "glsl")]
@@ -156,6 +164,21 @@ impl
     }
 }
 
+#[cfg(wgpu_core)]
+impl From<wgc::pipeline::ShaderModuleWarning> for CompilationMessage {
+    fn from(value: wgc::pipeline::ShaderModuleWarning) -> Self {
+        let message_type = match value.severity() {
+            crate::naga::diagnostic_filter::Severity::Info => CompilationMessageType::Info,
+            _ => CompilationMessageType::Warning,
+        };
+        CompilationMessage {
+            message: value.to_string(),
+            message_type,
+            location: value.location().map(Into::into),
+        }
+    }
+}
+
 #[cfg(any(wgpu_core, naga))]
 impl From<crate::naga::SourceLocation> for SourceLocation {
     fn from(value: crate::naga::SourceLocation) -> Self {
@@ -817,7 +817,11 @@ impl crate::Context for ContextWgpuCore {
                 stage,
                 defines,
             } => {
-                let options = naga::front::glsl::Options { stage, defines };
+                let options = naga::front::glsl::Options {
+                    stage,
+                    defines,
+                    include_resolver: None,
+                };
                 wgc::pipeline::ShaderModuleSource::Glsl(Borrowed(shader), options)
             }
             #[cfg(feature = "wgsl")]
@@ -839,7 +843,15 @@ impl crate::Context for ContextWgpuCore {
                 );
                 CompilationInfo::from(cause)
             }
-            None => CompilationInfo { messages: vec![] },
+            None => CompilationInfo {
+                messages: self
+                    .0
+                    .shader_module_get_warnings(id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(CompilationMessage::from)
+                    .collect(),
+            },
         };
 
         ShaderModule {